@@ -28,6 +28,21 @@ pub mod x11 {
 
     /// WM_CHANGE_STATE iconic value (requests the WM to minimize)
     pub const ICONIC_STATE: u32 = 3;
+
+    /// _NET_WM_DESKTOP value of windows shown on all desktops
+    pub const ALL_DESKTOPS: u32 = 0xFFFF_FFFF;
+
+    /// WM_CLASS class of thumbnail windows
+    pub const THUMBNAIL_CLASS: &str = "eve-preview-thumbnail";
+
+    /// _MOTIF_WM_HINTS flag: the decorations field is set
+    pub const MOTIF_HINTS_DECORATIONS: u32 = 1 << 1;
+
+    /// Seconds between reconnect attempts after the X server connection is lost
+    pub const RECONNECT_DELAY_SECS: u64 = 2;
+
+    /// Reconnect attempts before the daemon gives up and exits (about a minute)
+    pub const RECONNECT_ATTEMPTS: u32 = 30;
 }
 
 /// Input event constants (from evdev)
@@ -51,12 +66,29 @@ pub mod input {
     pub const BTN_LEFT: u16 = 272;
     /// Button code for right mouse button (BTN_RIGHT = 0x111)
     pub const BTN_RIGHT: u16 = 273;
+
+    /// Number of recent hotkey switches the latency statistics cover
+    pub const LATENCY_SAMPLES: usize = 100;
+
+    /// 95th percentile hotkey latency (ms) above which Diagnostics warns
+    pub const LATENCY_WARN_MS: u64 = 50;
+
+    /// Default longest gap (ms) between the release of a key and its second press for a double tap
+    pub const DOUBLE_TAP_MS: u64 = 250;
+
+    /// Default time (ms) a key has to be held for a long press
+    pub const LONG_PRESS_MS: u64 = 500;
+
+    /// How often (ms) the evdev backend checks for keyboard mapping changes
+    pub const KEYMAP_POLL_MS: u64 = 250;
 }
 
 /// Mouse button constants
 pub mod mouse {
     /// Left mouse button number
     pub const BUTTON_LEFT: u8 = 1;
+    /// Middle mouse button number
+    pub const BUTTON_MIDDLE: u8 = 2;
     /// Right mouse button number
     pub const BUTTON_RIGHT: u8 = 3;
 }
@@ -73,10 +105,173 @@ pub mod eve {
     pub const LOGGED_OUT_DISPLAY_NAME: &str = "login_screen";
 }
 
+/// Window scanning constants
+pub mod scan {
+    /// Seconds between full rescans catching clients whose events were missed
+    pub const RESCAN_INTERVAL_SECS: u64 = 30;
+
+    /// Levels below the root walked by a full scan (frame windows of reparenting
+    /// window managers put clients one level down)
+    pub const TREE_DEPTH: usize = 2;
+}
+
+/// Touchscreen gesture constants
+pub mod touch {
+    /// Movement in pixels a touch may make and still count as a tap or long press
+    pub const TAP_SLOP: f64 = 12.0;
+
+    /// Milliseconds a finger must rest before moving it drags the thumbnail
+    pub const LONG_PRESS_MS: u64 = 500;
+}
+
 /// Default window positioning constants
 pub mod positioning {
     /// Padding offset from source window when spawning thumbnails
     pub const DEFAULT_SPAWN_OFFSET: i16 = 20;
+
+    /// Number of thumbnail moves kept for undo (per daemon session)
+    pub const UNDO_HISTORY_LEN: usize = 20;
+}
+
+/// Skipped indicator drawing constants
+pub mod skipped {
+    /// Distance between stripes of the striped indicator in pixels
+    pub const STRIPE_SPACING: i16 = 16;
+
+    /// Alpha of the black layer of the dimmed indicator (60%)
+    pub const DIM_ALPHA: u16 = 0x9999;
+
+    /// Label prefix of the label prefix indicator
+    pub const PREFIX: &str = "[SKIP]";
+}
+
+/// Thumbnail badge layout constants
+pub mod badge {
+    /// Space between a badge's glyph and the edge of its tile in pixels
+    pub const PADDING: u16 = 3;
+
+    /// Gap between neighbouring badges in pixels
+    pub const SPACING: u16 = 4;
+
+    /// Distance of the badge row from the thumbnail edge in pixels
+    pub const MARGIN: i16 = 6;
+
+    /// Background of account number badges (ARGB)
+    pub const ACCOUNT_COLOR: u32 = 0xFF34495E;
+
+    /// Background of cycle order badges (ARGB)
+    pub const CYCLE_ORDER_COLOR: u32 = 0xFF7F8C8D;
+
+    /// Color of badge letters and numbers (ARGB)
+    pub const TEXT_COLOR: u32 = 0xFFFFFFFF;
+}
+
+/// Cycle group accent strip constants
+pub mod group_accent {
+    /// Height of the strip along the bottom of member thumbnails in pixels
+    pub const STRIP_SIZE: u16 = 4;
+}
+
+/// Active cycle group indicator constants
+pub mod group_indicator {
+    /// How long the indicator stays on screen after a group switch
+    pub const DURATION_MS: u64 = 1500;
+
+    /// Space between the label and the edge of the indicator in pixels
+    pub const PADDING: u16 = 12;
+
+    /// Distance of the indicator from the top of the screen in pixels
+    pub const TOP_MARGIN: i16 = 48;
+
+    /// Label size relative to the thumbnail font size
+    pub const TEXT_SCALE: f32 = 1.5;
+
+    /// Indicator background (RGB)
+    pub const BACKGROUND_COLOR: u32 = 0x00202020;
+
+    /// Indicator label color (ARGB)
+    pub const TEXT_COLOR: u32 = 0xFFFFFFFF;
+}
+
+/// On-screen display of hotkey switches
+pub mod switch_osd {
+    /// How long the display stays on screen after a switch
+    pub const DURATION_MS: u64 = 600;
+
+    /// Space between the label and the edge of the display in pixels
+    pub const PADDING: u16 = 12;
+
+    /// Label size relative to the thumbnail font size
+    pub const TEXT_SCALE: f32 = 2.0;
+
+    /// Display background (RGB)
+    pub const BACKGROUND_COLOR: u32 = 0x00202020;
+
+    /// Display label color (ARGB)
+    pub const TEXT_COLOR: u32 = 0xFFFFFFFF;
+}
+
+/// Per-character activation actions
+pub mod activation {
+    /// Environment variable holding the activated character's name
+    pub const CHARACTER_ENV: &str = "EVE_PREVIEW_MANAGER_CHARACTER";
+
+    /// Environment variable holding the activated client's X11 window ID
+    pub const WINDOW_ENV: &str = "EVE_PREVIEW_MANAGER_WINDOW";
+
+    /// How long the switch waits for a "before" command to finish (milliseconds)
+    pub const BEFORE_TIMEOUT_MS: u64 = 500;
+}
+
+/// Snap guide lines shown while dragging a thumbnail
+pub mod snap_guides {
+    /// Guide line color (RGB)
+    pub const COLOR: u32 = 0x0000BFFF;
+
+    /// Width of a guide line in pixels
+    pub const THICKNESS: u16 = 1;
+}
+
+/// Hotkey overlay constants
+pub mod hotkey_overlay {
+    /// Text size relative to the thumbnail font size
+    pub const TEXT_SCALE: f32 = 2.5;
+
+    /// Hotkey text color (ARGB)
+    pub const TEXT_COLOR: u32 = 0xFFFFD700;
+}
+
+/// Thumbnail tooltip constants
+pub mod tooltip {
+    /// Space between the text and the edge of the tooltip in pixels
+    pub const PADDING: u16 = 6;
+
+    /// Distance between the thumbnail and its tooltip in pixels
+    pub const GAP: i16 = 4;
+
+    /// Side of the character portrait in pixels
+    pub const PORTRAIT_SIZE: u16 = 64;
+
+    /// Text size relative to the thumbnail font size
+    pub const TEXT_SCALE: f32 = 1.0;
+
+    /// Tooltip background (RGB)
+    pub const BACKGROUND_COLOR: u32 = 0x00202020;
+
+    /// Tooltip text color (ARGB)
+    pub const TEXT_COLOR: u32 = 0xFFFFFFFF;
+}
+
+/// Thumbnail selection constants
+pub mod selection {
+    /// Border of selected thumbnails (ARGB)
+    pub const COLOR: u32 = 0xFF00BFFF;
+
+    /// Rubber band outline color (RGB)
+    pub const BAND_COLOR: u32 = 0x0000BFFF;
+
+    /// Width of the rubber band outline in pixels
+    pub const BAND_THICKNESS: u16 = 1;
 }
 
 /// Fixed-point arithmetic constants (X11 render transforms)
@@ -99,6 +294,17 @@ pub mod permissions {
 
     /// Command to add user to input group
     pub const ADD_TO_INPUT_GROUP: &str = "sudo usermod -a -G input $USER";
+
+    /// udev rule giving the input group read access to event devices
+    pub const UDEV_RULE: &str =
+        r#"KERNEL=="event*", SUBSYSTEM=="input", GROUP="input", MODE="0660""#;
+
+    /// Where to put `UDEV_RULE`
+    pub const UDEV_RULE_PATH: &str = "/etc/udev/rules.d/99-eve-preview-manager-input.rules";
+
+    /// Command to apply new udev rules to existing devices
+    pub const RELOAD_UDEV_RULES: &str =
+        "sudo udevadm control --reload-rules && sudo udevadm trigger --subsystem-match=input";
 }
 
 /// Configuration paths and filenames
@@ -123,6 +329,137 @@ pub mod config {
         /// Default retention count
         pub const RETENTION_COUNT: u32 = 30;
     }
+
+    /// Log file constants
+    pub mod logs {
+        /// Directory name for log files (relative to app config dir)
+        pub const SUBDIR: &str = "logs";
+
+        /// Log file extension
+        pub const EXTENSION: &str = "log";
+
+        /// Number of daily log files kept per process
+        pub const RETENTION_COUNT: usize = 7;
+
+        /// File prefix for the Manager's log
+        pub const MANAGER_PREFIX: &str = "manager";
+
+        /// File prefix for the daemon's log
+        pub const DAEMON_PREFIX: &str = "daemon";
+
+        /// Default log verbosity
+        pub const LEVEL: &str = "info";
+    }
+
+    /// Crash report constants
+    pub mod crash_reports {
+        /// Directory name for crash reports (relative to app config dir)
+        pub const SUBDIR: &str = "crash-reports";
+
+        /// Filename prefix of crash reports (followed by a sortable timestamp)
+        pub const PREFIX: &str = "crash-";
+
+        /// Crash report file extension
+        pub const EXTENSION: &str = "txt";
+
+        /// File recording the newest report already shown by the Manager
+        pub const SEEN_MARKER: &str = ".last-seen";
+
+        /// Number of crash reports kept
+        pub const RETENTION_COUNT: usize = 10;
+    }
+
+    /// Focus statistics constants
+    pub mod focus_stats {
+        /// Directory name for per-session CSV files (relative to app config dir)
+        pub const SUBDIR: &str = "stats";
+
+        /// Filename prefix of session files (followed by a sortable timestamp)
+        pub const PREFIX: &str = "session-";
+
+        /// Session file extension
+        pub const EXTENSION: &str = "csv";
+
+        /// Seconds between statistics updates sent to the Manager (and CSV rewrites)
+        pub const REPORT_INTERVAL_SECS: u64 = 5;
+    }
+
+    /// Hotkey cheat sheet constants
+    pub mod cheat_sheet {
+        /// Directory name for saved cheat sheets (relative to app config dir)
+        pub const SUBDIR: &str = "cheat-sheets";
+
+        /// Cheat sheet file extension
+        pub const EXTENSION: &str = "html";
+    }
+
+    /// AFK snapshot constants
+    pub mod snapshots {
+        /// Directory for snapshots, one subdirectory per character (relative to app config dir)
+        pub const SUBDIR: &str = "snapshots";
+
+        /// Snapshot file extension
+        pub const EXTENSION: &str = "png";
+
+        /// Seconds between checks whether snapshots are due
+        pub const CHECK_INTERVAL_SECS: u64 = 15;
+    }
+
+    /// Source window screenshot constants
+    pub mod screenshot {
+        /// Default directory name under the XDG pictures directory (or the app config dir
+        /// when there is none)
+        pub const SUBDIR: &str = "eve-screenshots";
+
+        /// Screenshot file extension
+        pub const EXTENSION: &str = "png";
+    }
+}
+
+/// XDG autostart integration
+pub mod autostart {
+    /// Directory under XDG config holding autostart entries
+    pub const DIR: &str = "autostart";
+
+    /// Filename of our autostart entry
+    pub const FILENAME: &str = "com.evepreview.manager.desktop";
+}
+
+/// Runtime state shared between processes (under XDG_RUNTIME_DIR)
+pub mod runtime {
+    /// File advertising the IPC endpoint of a standalone (`--daemon-only`) daemon
+    pub const ATTACH_ENDPOINT_FILENAME: &str = "daemon.ipc";
+}
+
+/// systemd service integration
+pub mod systemd {
+    /// Environment variable holding the sd_notify socket path
+    pub const NOTIFY_SOCKET_ENV: &str = "NOTIFY_SOCKET";
+
+    /// Environment variable holding the watchdog timeout in microseconds
+    pub const WATCHDOG_USEC_ENV: &str = "WATCHDOG_USEC";
+
+    /// Environment variable holding the PID the watchdog applies to
+    pub const WATCHDOG_PID_ENV: &str = "WATCHDOG_PID";
+}
+
+/// Sources read without XRender (`daemon::software`)
+pub mod software_scaling {
+    /// Shortest time between two reads of a source; each is a `GetImage` of the whole window
+    pub const FRAME_INTERVAL_MS: u64 = 100;
+}
+
+/// Rendering benchmark (`bench` subcommand)
+pub mod bench {
+    /// Size of the synthetic source windows (a typical windowed EVE client)
+    pub const SOURCE_WIDTH: u16 = 1280;
+    pub const SOURCE_HEIGHT: u16 = 720;
+
+    /// Frames rendered before measuring, to settle caches and the compositor
+    pub const WARMUP_FRAMES: u32 = 5;
+
+    /// Maximum time to wait for a frame's damage events
+    pub const EVENT_TIMEOUT_MS: u64 = 1000;
 }
 
 /// Manager-specific constants (egui manager window)
@@ -148,6 +485,30 @@ pub mod manager_ui {
 
     /// Debounce delay for config auto-saving, ie preview window position updates
     pub const AUTO_SAVE_DELAY_MS: u64 = 1000;
+
+    /// Time a daemon gets to exit after SIGTERM before it is killed
+    pub const DAEMON_SHUTDOWN_TIMEOUT_MS: u64 = 2000;
+
+    /// Delay before the first automatic daemon restart after a crash (doubles per consecutive crash)
+    pub const DAEMON_RESTART_BASE_DELAY_MS: u64 = 1000;
+
+    /// Upper bound for the automatic daemon restart delay
+    pub const DAEMON_RESTART_MAX_DELAY_MS: u64 = 60_000;
+
+    /// Uptime after which a daemon is considered stable and the restart delay resets
+    pub const DAEMON_STABLE_RUN_SECS: u64 = 60;
+
+    /// Number of crashes in a session before the crash dialog is shown
+    pub const DAEMON_CRASH_DIALOG_THRESHOLD: u32 = 3;
+
+    /// Number of daemon log lines kept in memory
+    pub const DAEMON_LOG_CAPACITY: usize = 1000;
+
+    /// Number of login history entries kept in memory
+    pub const CHARACTER_HISTORY_CAPACITY: usize = 500;
+
+    /// Width of a daemon error toast in pixels
+    pub const TOAST_WIDTH: f32 = 320.0;
 }
 
 /// Default configuration values
@@ -197,6 +558,9 @@ pub mod defaults {
 
         /// Default inactive border color
         pub const INACTIVE_COLOR: &str = "#707070";
+
+        /// Default border color of the next cycle target
+        pub const NEXT_TARGET_COLOR: &str = "#FFA500";
     }
 
     /// Text overlay settings
@@ -219,6 +583,12 @@ pub mod defaults {
             &["DejaVu Sans Mono Book", "Liberation Mono", "Noto Sans Mono"];
     }
 
+    /// Skipped indicator settings
+    pub mod skipped {
+        /// Default skipped indicator color
+        pub const COLOR: &str = "#FFFF0000";
+    }
+
     /// Daemon behavior settings
     pub mod behavior {
         /// Default profile name
@@ -241,5 +611,11 @@ pub mod defaults {
 
         /// Hide thumbnails when EVE window loses focus
         pub const HIDE_WHEN_NO_FOCUS: bool = false;
+
+        /// Periodic position save interval in seconds (0 = disabled)
+        pub const AUTOSAVE_INTERVAL_SECS: u32 = 0;
+
+        /// Maximum periodic position save interval selectable in the Manager
+        pub const MAX_AUTOSAVE_INTERVAL_SECS: u32 = 600;
     }
 }
@@ -0,0 +1,265 @@
+//! Crash reports written by the panic hook
+//!
+//! Both processes install a hook that saves the panic message, backtrace, version and a
+//! redacted config summary under the config directory. The Manager offers to open
+//! reports it has not shown yet on its next start.
+
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use anyhow::{Context, Result};
+use tracing::error;
+
+use crate::common::constants::config::crash_reports;
+use crate::config::profile::Config;
+
+/// Summary of the last config loaded or saved by this process, so the panic hook only has to
+/// copy it instead of touching the config file
+static CONFIG_SUMMARY: RwLock<Option<String>> = RwLock::new(None);
+
+/// Remember `config` for the summary in crash reports
+pub fn record_config(config: &Config) {
+    let summary = config_summary(config);
+    if let Ok(mut current) = CONFIG_SUMMARY.write() {
+        *current = Some(summary);
+    }
+}
+
+/// Directory holding crash reports (next to the config file)
+pub fn report_dir() -> PathBuf {
+    let mut path = Config::path();
+    path.pop(); // Remove filename
+    path.push(crash_reports::SUBDIR);
+    path
+}
+
+/// Install a panic hook that writes a crash report before running the default hook.
+///
+/// `process` identifies the crashing process in the report and its filename.
+pub fn install_panic_hook(process: &'static str) {
+    // Loading records the summary; a managed daemon never loads the config itself
+    if Config::path().exists() {
+        let _ = Config::load();
+    }
+
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let message = info
+            .payload()
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| info.payload().downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "<non-string panic payload>".to_string());
+        let location = info
+            .location()
+            .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()))
+            .unwrap_or_else(|| "<unknown>".to_string());
+        let backtrace = std::backtrace::Backtrace::force_capture();
+
+        // try_read: never block inside the hook (e.g. a panic while the summary is replaced)
+        let config = CONFIG_SUMMARY
+            .try_read()
+            .ok()
+            .and_then(|summary| summary.clone());
+        let report = format_report(
+            process,
+            &message,
+            &location,
+            &backtrace.to_string(),
+            config.as_deref(),
+        );
+
+        match write_report(&report_dir(), process, &report) {
+            Ok(path) => error!(path = %path.display(), "Panic, crash report written"),
+            Err(e) => error!(error = ?e, "Panic, failed to write crash report"),
+        }
+
+        default_hook(info);
+    }));
+}
+
+/// Reports not yet shown by the Manager, oldest first
+pub fn unseen_reports() -> Vec<PathBuf> {
+    let dir = report_dir();
+    let last_seen = std::fs::read_to_string(dir.join(crash_reports::SEEN_MARKER)).ok();
+    list_reports(&dir)
+        .into_iter()
+        .filter(|path| {
+            // Filenames start with a timestamp, so they order chronologically
+            let name = path.file_name().map(|n| n.to_string_lossy().into_owned());
+            last_seen
+                .as_deref()
+                .is_none_or(|seen| name.as_deref() > Some(seen.trim()))
+        })
+        .collect()
+}
+
+/// Remember `report` (and everything older) as shown
+pub fn mark_seen(report: &Path) -> Result<()> {
+    let name = report
+        .file_name()
+        .context("Crash report path has no filename")?
+        .to_string_lossy()
+        .into_owned();
+    let marker = report_dir().join(crash_reports::SEEN_MARKER);
+    std::fs::write(&marker, name)
+        .with_context(|| format!("Failed to write crash report marker {:?}", marker))
+}
+
+fn list_reports(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut reports: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.file_name().is_some_and(|name| {
+                let name = name.to_string_lossy();
+                name.starts_with(crash_reports::PREFIX) && name.ends_with(crash_reports::EXTENSION)
+            })
+        })
+        .collect();
+    reports.sort();
+    reports
+}
+
+fn write_report(dir: &Path, process: &str, report: &str) -> Result<PathBuf> {
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create crash report directory {:?}", dir))?;
+
+    let filename = format!(
+        "{}{}-{}.{}",
+        crash_reports::PREFIX,
+        chrono::Local::now().format("%Y%m%d-%H%M%S"),
+        process,
+        crash_reports::EXTENSION
+    );
+    let path = dir.join(filename);
+    std::fs::write(&path, report)
+        .with_context(|| format!("Failed to write crash report {:?}", path))?;
+
+    // Keep only the newest reports
+    let reports = list_reports(dir);
+    let excess = reports.len().saturating_sub(crash_reports::RETENTION_COUNT);
+    for old in &reports[..excess] {
+        let _ = std::fs::remove_file(old);
+    }
+
+    Ok(path)
+}
+
+fn format_report(
+    process: &str,
+    message: &str,
+    location: &str,
+    backtrace: &str,
+    config_summary: Option<&str>,
+) -> String {
+    let mut report = String::new();
+    let _ = writeln!(report, "EVE Preview Manager crash report");
+    let _ = writeln!(report, "Version: {}", env!("CARGO_PKG_VERSION"));
+    let _ = writeln!(report, "Process: {}", process);
+    let _ = writeln!(report, "Time: {}", chrono::Local::now().to_rfc3339());
+    let _ = writeln!(
+        report,
+        "Thread: {}",
+        std::thread::current().name().unwrap_or("<unnamed>")
+    );
+    let _ = writeln!(report, "Panic: {}", message);
+    let _ = writeln!(report, "Location: {}", location);
+    let _ = writeln!(report);
+    let _ = writeln!(report, "Configuration (names redacted):");
+    match config_summary {
+        Some(summary) => report.push_str(summary),
+        None => report.push_str("  <unavailable>\n"),
+    }
+    let _ = writeln!(report);
+    let _ = writeln!(report, "Backtrace:");
+    report.push_str(backtrace);
+    report
+}
+
+/// Settings relevant for debugging, without character, source or profile names
+fn config_summary(config: &Config) -> String {
+    let mut summary = String::new();
+    let _ = writeln!(
+        summary,
+        "  profiles: {}, log level: {}, log files: {}",
+        config.profiles.len(),
+        config.global.log_level,
+        config.global.log_to_file
+    );
+    for (idx, profile) in config.profiles.iter().enumerate() {
+        let active = if profile.profile_name == config.global.selected_profile {
+            " (active)"
+        } else {
+            ""
+        };
+        let _ = writeln!(
+            summary,
+            "  profile {}{}: thumbnails {}, {}x{} default size, opacity {}%, hotkeys {:?}, \
+             {} characters, {} custom sources, {} cycle groups, auto-save {}, save on exit {}",
+            idx + 1,
+            active,
+            if profile.thumbnail_enabled {
+                "on"
+            } else {
+                "off"
+            },
+            profile.thumbnail_default_width,
+            profile.thumbnail_default_height,
+            profile.thumbnail_opacity,
+            profile.hotkey_backend,
+            profile.character_thumbnails.len(),
+            profile.custom_windows.len(),
+            profile.cycle_groups.len(),
+            profile.thumbnail_auto_save_position,
+            profile.thumbnail_save_on_exit,
+        );
+    }
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::types::CharacterSettings;
+
+    #[test]
+    fn test_config_summary_redacts_names() {
+        let mut config = Config::default();
+        let profile = &mut config.profiles[0];
+        profile.profile_name = "Mining Fleet".to_string();
+        profile.character_thumbnails.insert(
+            "Secret Pilot".to_string(),
+            CharacterSettings::new(0, 0, 100, 100),
+        );
+        config.global.selected_profile = "Mining Fleet".to_string();
+
+        let summary = config_summary(&config);
+        assert!(summary.contains("profile 1 (active)"));
+        assert!(summary.contains("1 characters"));
+        assert!(!summary.contains("Secret Pilot"));
+        assert!(!summary.contains("Mining Fleet"));
+    }
+
+    #[test]
+    fn test_write_report_prunes_old_reports() {
+        let dir = tempfile::tempdir().unwrap();
+        for i in 0..crash_reports::RETENTION_COUNT + 2 {
+            let name = format!(
+                "{}20000101-0000{:02}-daemon.{}",
+                crash_reports::PREFIX,
+                i,
+                crash_reports::EXTENSION
+            );
+            std::fs::write(dir.path().join(name), "old").unwrap();
+        }
+
+        let path = write_report(dir.path(), "manager", "report").unwrap();
+        let reports = list_reports(dir.path());
+        assert_eq!(reports.len(), crash_reports::RETENTION_COUNT);
+        assert_eq!(reports.last(), Some(&path));
+    }
+}
@@ -0,0 +1,225 @@
+//! Translations for GUI and overlay text
+//!
+//! Messages live in Fluent files under `i18n/`, embedded at build time, with English as the
+//! fallback for anything a translation is missing. To add a language: add its `.ftl` file, a
+//! `Language` variant, and its entries in `Language::ALL`, `label`, `source` and `langid`.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use fluent_bundle::{FluentArgs, FluentBundle, FluentResource};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+/// GUI and overlay language
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Language {
+    /// Follow the system locale (`LC_ALL`, `LC_MESSAGES`, `LANG`)
+    #[default]
+    Auto,
+    English,
+    German,
+}
+
+impl Language {
+    pub const ALL: [Language; 3] = [Language::Auto, Language::English, Language::German];
+
+    /// Name shown in the language picker, in the language itself
+    pub fn label(&self) -> &'static str {
+        match self {
+            Language::Auto => "Auto (system)",
+            Language::English => "English",
+            Language::German => "Deutsch",
+        }
+    }
+
+    /// The concrete language, resolving `Auto` from the environment
+    pub fn resolve(self) -> Language {
+        match self {
+            Language::Auto => ["LC_ALL", "LC_MESSAGES", "LANG"]
+                .into_iter()
+                .filter_map(|var| std::env::var(var).ok())
+                .find(|value| !value.is_empty())
+                .map(|locale| Self::from_locale(&locale))
+                .unwrap_or(Language::English),
+            other => other,
+        }
+    }
+
+    /// Language for a POSIX locale such as `de_DE.UTF-8`
+    fn from_locale(locale: &str) -> Language {
+        match locale
+            .split(['_', '.', '@', '-'])
+            .next()
+            .unwrap_or_default()
+        {
+            "de" => Language::German,
+            _ => Language::English,
+        }
+    }
+
+    fn source(self) -> &'static str {
+        match self {
+            Language::German => include_str!("i18n/de.ftl"),
+            Language::English | Language::Auto => include_str!("i18n/en.ftl"),
+        }
+    }
+
+    fn langid(self) -> &'static str {
+        match self {
+            Language::German => "de-DE",
+            Language::English | Language::Auto => "en-US",
+        }
+    }
+}
+
+/// Language used by `tr`, stored as an index into `Language::ALL`
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+
+/// Set the language for `tr` and `tr_with`
+pub fn set_language(language: Language) {
+    let language = language.resolve();
+    let idx = Language::ALL
+        .iter()
+        .position(|l| *l == language)
+        .unwrap_or_default();
+    CURRENT.store(idx as u8, Ordering::Relaxed);
+}
+
+fn current() -> Language {
+    Language::ALL
+        .get(CURRENT.load(Ordering::Relaxed) as usize)
+        .copied()
+        .unwrap_or_default()
+}
+
+/// Translate message `id` in the current language
+pub fn tr(id: &str) -> String {
+    tr_in(current(), id, None)
+}
+
+/// Translate message `id` in the current language, filling in `{ $name }` placeables
+pub fn tr_with(id: &str, args: &[(&'static str, String)]) -> String {
+    tr_in_with(current(), id, args)
+}
+
+/// Translate message `id` in `language`, filling in `{ $name }` placeables
+pub fn tr_in_with(language: Language, id: &str, args: &[(&'static str, String)]) -> String {
+    let mut fluent_args = FluentArgs::new();
+    for (name, value) in args {
+        fluent_args.set(*name, value.clone());
+    }
+    tr_in(language, id, Some(&fluent_args))
+}
+
+/// Translate message `id` in `language`, falling back to English and then to the id itself
+pub fn tr_in(language: Language, id: &str, args: Option<&FluentArgs>) -> String {
+    [language.resolve(), Language::English]
+        .into_iter()
+        .find_map(|language| format_in(language, id, args))
+        .unwrap_or_else(|| {
+            warn!(id, "Missing translation");
+            id.to_string()
+        })
+}
+
+fn format_in(language: Language, id: &str, args: Option<&FluentArgs>) -> Option<String> {
+    thread_local! {
+        static BUNDLES: RefCell<HashMap<Language, FluentBundle<FluentResource>>> =
+            RefCell::new(HashMap::new());
+    }
+
+    BUNDLES.with(|bundles| {
+        let mut bundles = bundles.borrow_mut();
+        let bundle = bundles
+            .entry(language)
+            .or_insert_with(|| build_bundle(language));
+        let pattern = bundle.get_message(id)?.value()?;
+        let mut errors = Vec::new();
+        let text = bundle.format_pattern(pattern, args, &mut errors);
+        if !errors.is_empty() {
+            warn!(id, ?errors, "Errors formatting translation");
+        }
+        Some(text.into_owned())
+    })
+}
+
+fn build_bundle(language: Language) -> FluentBundle<FluentResource> {
+    let langid = language
+        .langid()
+        .parse()
+        .expect("built-in language identifiers are valid");
+    let mut bundle = FluentBundle::new(vec![langid]);
+    // Unicode isolation marks around placeables render as boxes in some fonts
+    bundle.set_use_isolating(false);
+    let resource = FluentResource::try_new(language.source().to_string()).unwrap_or_else(
+        |(resource, errors)| {
+            warn!(?language, ?errors, "Errors parsing translations");
+            resource
+        },
+    );
+    if let Err(errors) = bundle.add_resource(resource) {
+        warn!(?language, ?errors, "Duplicate translation messages");
+    }
+    bundle
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Message ids defined in the English resource
+    fn english_ids() -> Vec<&'static str> {
+        Language::English
+            .source()
+            .lines()
+            .filter_map(|line| line.split_once(" ="))
+            .map(|(id, _)| id.trim())
+            .filter(|id| !id.is_empty() && !id.starts_with('#') && !id.starts_with('.'))
+            .collect()
+    }
+
+    #[test]
+    fn test_translations_cover_english_messages() {
+        let ids = english_ids();
+        assert!(ids.contains(&"overlay-minimized"));
+        for language in [Language::English, Language::German] {
+            for id in &ids {
+                assert!(
+                    format_in(language, id, None).is_some(),
+                    "{:?} is missing '{}'",
+                    language,
+                    id
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_tr_in_fallbacks_and_args() {
+        assert_eq!(
+            tr_in(Language::German, "overlay-minimized", None),
+            "MINIMIERT"
+        );
+        assert_eq!(
+            tr_in(Language::German, "no-such-message", None),
+            "no-such-message"
+        );
+
+        let mut args = FluentArgs::new();
+        args.set("pid", 42);
+        assert_eq!(
+            tr_in(Language::English, "header-daemon-pid", Some(&args)),
+            "(PID: 42)"
+        );
+    }
+
+    #[test]
+    fn test_language_from_locale() {
+        assert_eq!(Language::from_locale("de_DE.UTF-8"), Language::German);
+        assert_eq!(Language::from_locale("en_GB.UTF-8"), Language::English);
+        assert_eq!(Language::from_locale("C"), Language::English);
+    }
+}
@@ -0,0 +1,53 @@
+# Deutsch
+
+## Thumbnail overlay
+overlay-minimized = MINIMIERT
+overlay-last-character = (zuletzt: { $name })
+overlay-client-slot = Client { $slot }
+
+## Daemon status
+daemon-running = Daemon läuft
+daemon-starting = Daemon startet...
+daemon-stopped = Daemon gestoppt
+daemon-crashed = Daemon abgestürzt
+daemon-crashed-exit = Daemon abgestürzt (Exit { $code })
+
+## Header
+header-daemon-pid = (PID: { $pid })
+header-restarting-in = (Neustart in { $seconds } s)
+tab-behavior = Verhalten
+tab-appearance = Darstellung
+tab-hotkeys = Tastenkürzel
+tab-characters = Charaktere
+tab-sources = Quellen
+tab-layout = Layout
+tab-diagnostics = Diagnose
+tab-stats = Statistik
+tab-logs = Protokolle
+header-discard = ✖ Änderungen verwerfen
+header-save = 💾 Speichern & Anwenden
+header-save-hint = Speichern (Strg+S)
+header-undo = ⟲ Rückgängig
+header-undo-hint = Rückgängig (Strg+Z)
+header-redo = ⟳ Wiederholen
+header-redo-hint = Wiederholen (Strg+Umschalt+Z)
+header-unsaved-changes = Ungespeicherte Änderungen
+header-unsaved-positions = Ungespeicherte Vorschaupositionen
+
+## Tray menu
+tray-title = EVE Preview Manager
+tray-title-unsaved = EVE Preview Manager (ungespeicherte Positionen)
+tray-show-manager = Manager anzeigen
+tray-refresh = Aktualisieren
+tray-profiles = Profile ({ $profile })
+tray-thumbnails = Vorschaubilder
+tray-no-characters = Keine Charaktere erkannt
+tray-save-positions = Vorschaupositionen speichern
+tray-save-positions-unsaved = Vorschaupositionen speichern (ungespeichert)
+tray-undo-move = Letzte Verschiebung rückgängig
+tray-rescan = Fenster neu suchen
+tray-quit = Beenden
+
+## Behavior tab
+settings-language = Sprache
+settings-language-hint = Gilt sofort für den Manager und nach „Speichern & Anwenden“ für die Vorschauen. Noch sind nicht alle Texte übersetzt.
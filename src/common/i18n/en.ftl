@@ -0,0 +1,53 @@
+# English (fallback for every other language)
+
+## Thumbnail overlay
+overlay-minimized = MINIMIZED
+overlay-last-character = (last: { $name })
+overlay-client-slot = Client { $slot }
+
+## Daemon status
+daemon-running = Daemon running
+daemon-starting = Daemon starting...
+daemon-stopped = Daemon stopped
+daemon-crashed = Daemon crashed
+daemon-crashed-exit = Daemon crashed (exit { $code })
+
+## Header
+header-daemon-pid = (PID: { $pid })
+header-restarting-in = (restarting in { $seconds }s)
+tab-behavior = Behavior
+tab-appearance = Appearance
+tab-hotkeys = Hotkeys
+tab-characters = Characters
+tab-sources = Sources
+tab-layout = Layout
+tab-diagnostics = Diagnostics
+tab-stats = Stats
+tab-logs = Logs
+header-discard = ✖ Discard Changes
+header-save = 💾 Save & Apply
+header-save-hint = Save (Ctrl+S)
+header-undo = ⟲ Undo
+header-undo-hint = Undo (Ctrl+Z)
+header-redo = ⟳ Redo
+header-redo-hint = Redo (Ctrl+Shift+Z)
+header-unsaved-changes = Unsaved changes
+header-unsaved-positions = Unsaved thumbnail positions
+
+## Tray menu
+tray-title = EVE Preview Manager
+tray-title-unsaved = EVE Preview Manager (unsaved positions)
+tray-show-manager = Show Manager
+tray-refresh = Refresh
+tray-profiles = Profiles ({ $profile })
+tray-thumbnails = Thumbnails
+tray-no-characters = No characters detected
+tray-save-positions = Save Thumbnail Positions
+tray-save-positions-unsaved = Save Thumbnail Positions (unsaved)
+tray-undo-move = Undo Last Thumbnail Move
+tray-rescan = Rescan Windows
+tray-quit = Quit
+
+## Behavior tab
+settings-language = Language
+settings-language-hint = Applies to the manager right away and to thumbnails after Save & Apply. Not every text is translated yet.
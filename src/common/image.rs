@@ -0,0 +1,54 @@
+//! Image files shared by the daemon overlays and the Manager previews
+
+use anyhow::{Context, Result, anyhow};
+use std::path::PathBuf;
+
+/// `path` with a leading `~/` expanded to the home directory
+pub fn expand_path(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// The PNG at `path` as straight RGBA pixels, with its width and height
+pub fn decode_png(path: &str) -> Result<(Vec<u8>, u32, u32)> {
+    let file = std::fs::File::open(expand_path(path))
+        .context(format!("Failed to open image '{}'", path))?;
+    let mut decoder = png::Decoder::new(std::io::BufReader::new(file));
+    decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+    let mut reader = decoder
+        .read_info()
+        .context(format!("Failed to read image '{}'", path))?;
+    let mut buf = vec![
+        0;
+        reader
+            .output_buffer_size()
+            .context("PNG has no output buffer size")?
+    ];
+    let info = reader
+        .next_frame(&mut buf)
+        .context(format!("Failed to decode image '{}'", path))?;
+    let data = &buf[..info.buffer_size()];
+
+    let rgba: Vec<u8> = match info.color_type {
+        png::ColorType::Rgba => data.to_vec(),
+        png::ColorType::Rgb => data
+            .chunks_exact(3)
+            .flat_map(|c| [c[0], c[1], c[2], 0xFF])
+            .collect(),
+        png::ColorType::GrayscaleAlpha => data
+            .chunks_exact(2)
+            .flat_map(|c| [c[0], c[0], c[0], c[1]])
+            .collect(),
+        png::ColorType::Grayscale => data.iter().flat_map(|&g| [g, g, g, 0xFF]).collect(),
+        other => {
+            return Err(anyhow!(
+                "Unsupported image color type {:?} in '{}'",
+                other,
+                path
+            ));
+        }
+    };
+    Ok((rgba, info.width, info.height))
+}
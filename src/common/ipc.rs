@@ -1,7 +1,11 @@
+use std::collections::HashMap;
+
 use ipc_channel::ipc::{IpcReceiver, IpcSender};
 use serde::{Deserialize, Serialize};
 
+use crate::common::types::{CharacterSettings, MonitorScale, Position};
 use crate::config::DaemonConfig;
+use crate::config::profile::CycleSlot;
 
 /// Messages sent from Manager to Daemon
 #[derive(Debug, Serialize, Deserialize)]
@@ -26,6 +30,24 @@ pub enum ConfigMessage {
         width: u16,
         height: u16,
     },
+
+    /// Revert the most recent thumbnail drag (e.g. from the tray menu).
+    UndoMove,
+
+    /// Rescan all windows for EVE clients and custom sources the Daemon missed.
+    Rescan,
+
+    /// Temporarily hide or show one character's thumbnail (e.g. from the tray menu).
+    /// Session-only: the Daemon forgets it on restart.
+    SetCharacterHidden { name: String, hidden: bool },
+
+    /// Skip a character while cycling, or include it again (like the toggle-skip hotkey)
+    SetCharacterSkipped { name: String, skipped: bool },
+
+    /// Members of each cycle group in their current order, with nested groups expanded.
+    /// Sent whenever the user edits a cycle group so the new order cycles right away, before
+    /// the change is saved.
+    CycleOrder(Vec<(String, Vec<CycleSlot>)>),
 }
 
 /// Messages sent from Daemon to Manager
@@ -41,6 +63,10 @@ pub enum DaemonMessage {
         name: String,
         is_custom: bool,
     },
+    /// A character logged out or its client closed
+    CharacterLost {
+        name: String,
+    },
     /// Notification that a thumbnail's spatial state was detected or changed by the Daemon.
     ///
     /// Upon receipt, the Manager updates its local state, saves to disk, and acknowledges
@@ -53,16 +79,191 @@ pub enum DaemonMessage {
         width: u16,
         height: u16,
         is_custom: bool,
+        /// Top-left corner of the client window when the position was taken
+        client_origin: Option<Position>,
     },
-    /// Daemon encountered an error
-    Error(String),
+    /// Daemon hit a non-fatal failure the user should see
+    Error(DaemonError),
     /// Generic status update for the Manager UI
     Status(String),
     RequestProfileSwitch(String),
+    /// Whether in-memory thumbnail positions have unsaved changes (auto-save disabled)
+    PositionsDirty(bool),
+    /// Merge these thumbnail positions into `profile` on disk (save on exit, periodic or
+    /// hotkey saves). Sent instead of writing config.json while a Manager is attached, so only
+    /// one process writes the file.
+    SavePositions {
+        profile: String,
+        character_thumbnails: HashMap<String, CharacterSettings>,
+        custom_source_thumbnails: HashMap<String, CharacterSettings>,
+    },
     /// Periodic heartbeat (optional)
     Heartbeat,
+    /// Focus time per character since the daemon started, most focused first
+    FocusStats(Vec<FocusTime>),
+    /// Hotkey switch latency, sent after every hotkey-triggered window switch
+    HotkeyLatency(HotkeyLatency),
+    /// An EVE client logged in, logged out, swapped characters or closed
+    CharacterEvent(CharacterEvent),
+    /// Characters currently skipped from cycling, sent whenever that changes
+    SkippedCharacters(Vec<String>),
+    /// Monitors of the display the daemon previews, ordered by position; sent on every
+    /// (re)connection so both sides resolve monitor percentages against the same monitors
+    Monitors(Vec<MonitorScale>),
+}
+
+/// A non-fatal daemon failure, shown in the Manager as a toast
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DaemonError {
+    pub kind: DaemonErrorKind,
+    pub message: String,
+}
+
+impl DaemonError {
+    pub fn new(kind: DaemonErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+}
+
+/// What failed, used as the toast title
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DaemonErrorKind {
+    /// Creating or drawing a thumbnail of a source window
+    Composite,
+    /// Writing thumbnail positions to the config file
+    Save,
+    /// Starting the hotkey listener or grabbing its keys
+    HotkeyGrab,
+    Clipboard,
+    Screenshot,
+    /// No compositing manager, so thumbnail opacity was turned off
+    Compositor,
+}
+
+impl DaemonErrorKind {
+    pub fn title(self) -> &'static str {
+        match self {
+            DaemonErrorKind::Composite => "Thumbnail failed",
+            DaemonErrorKind::Save => "Saving positions failed",
+            DaemonErrorKind::HotkeyGrab => "Hotkeys unavailable",
+            DaemonErrorKind::Clipboard => "Clipboard copy failed",
+            DaemonErrorKind::Screenshot => "Screenshot failed",
+            DaemonErrorKind::Compositor => "No compositor",
+        }
+    }
+}
+
+/// Login state change of one EVE client
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CharacterEvent {
+    /// Unix time in seconds
+    pub timestamp: i64,
+    pub kind: CharacterEventKind,
+}
+
+impl CharacterEvent {
+    pub fn now(kind: CharacterEventKind) -> Self {
+        Self {
+            timestamp: chrono::Utc::now().timestamp(),
+            kind,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CharacterEventKind {
+    Login(String),
+    Logout(String),
+    /// The client switched characters without passing the character selection screen
+    Swap {
+        from: String,
+        to: String,
+    },
+    /// The client closed (or crashed) while a character was logged in
+    Closed(String),
+}
+
+impl CharacterEventKind {
+    /// Event for a client whose character changed from `old` to `new` (empty when logged out)
+    pub fn from_change(old: &str, new: &str) -> Option<Self> {
+        match (old.is_empty(), new.is_empty()) {
+            (true, true) => None,
+            (true, false) => Some(Self::Login(new.to_string())),
+            (false, true) => Some(Self::Logout(old.to_string())),
+            (false, false) if old == new => None,
+            (false, false) => Some(Self::Swap {
+                from: old.to_string(),
+                to: new.to_string(),
+            }),
+        }
+    }
+}
+
+/// Time from reading a hotkey press to completing the window switch, over recent switches
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HotkeyLatency {
+    /// Backend the presses were read by
+    pub backend: crate::config::profile::HotkeyBackendType,
+    /// Switches measured this session
+    pub samples: u32,
+    pub last: std::time::Duration,
+    pub median: std::time::Duration,
+    pub p95: std::time::Duration,
+    pub max: std::time::Duration,
+}
+
+/// How long one character's client was focused during the session
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FocusTime {
+    pub name: String,
+    pub focused_secs: u64,
+    /// Times the client gained focus
+    pub switches: u32,
 }
 
 /// The bootstrap payload sent over the initial server channel.
 /// Contains the channel for receiving config updates and the channel for sending status updates.
 pub type BootstrapMessage = (IpcSender<ConfigMessage>, IpcReceiver<DaemonMessage>);
+
+/// The payload sent to a standalone daemon's attach endpoint.
+///
+/// Contains the name of the Manager's `IpcOneShotServer<BootstrapMessage>`; the daemon
+/// connects back to it and performs the regular bootstrap handshake.
+pub type AttachMessage = String;
+
+/// Location of the file advertising a standalone daemon's attach endpoint.
+pub fn attach_endpoint_path() -> std::path::PathBuf {
+    dirs::runtime_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join(crate::common::constants::config::APP_DIR)
+        .join(crate::common::constants::runtime::ATTACH_ENDPOINT_FILENAME)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_character_event_from_change() {
+        assert_eq!(
+            CharacterEventKind::from_change("", "Alice"),
+            Some(CharacterEventKind::Login("Alice".to_string()))
+        );
+        assert_eq!(
+            CharacterEventKind::from_change("Alice", ""),
+            Some(CharacterEventKind::Logout("Alice".to_string()))
+        );
+        assert_eq!(
+            CharacterEventKind::from_change("Alice", "Bob"),
+            Some(CharacterEventKind::Swap {
+                from: "Alice".to_string(),
+                to: "Bob".to_string()
+            })
+        );
+        assert_eq!(CharacterEventKind::from_change("", ""), None);
+        assert_eq!(CharacterEventKind::from_change("Alice", "Alice"), None);
+    }
+}
@@ -0,0 +1,132 @@
+//! Tracing subscriber setup
+//!
+//! Logs always go to stdout (captured by the Manager for its log viewer). Optionally a
+//! copy is written to daily-rotated files under the config directory so a backgrounded
+//! daemon still leaves a trail.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, fmt};
+
+use crate::common::constants::config::logs;
+use crate::config::profile::Config;
+
+/// Levels accepted by `--log-level` and the Manager's verbosity setting
+pub const LEVELS: [&str; 5] = ["error", "warn", "info", "debug", "trace"];
+
+/// Default directory for rotated log files (next to the config file)
+pub fn default_log_dir() -> PathBuf {
+    let mut path = Config::path();
+    path.pop(); // Remove filename
+    path.push(logs::SUBDIR);
+    path
+}
+
+/// Install the global tracing subscriber.
+///
+/// `log_dir` enables file logging; files are named `<file_prefix>.<date>.log`.
+pub fn init(directives: &str, log_dir: Option<&Path>, file_prefix: &str) -> Result<()> {
+    let filter = EnvFilter::new(directives);
+
+    // A broken log directory should not prevent startup; report it once logging works
+    let (file_layer, file_error) = match log_dir.map(|dir| file_appender(dir, file_prefix)) {
+        Some(Ok(appender)) => (
+            Some(fmt::layer().with_ansi(false).with_writer(appender)),
+            None,
+        ),
+        Some(Err(e)) => (None, Some(e)),
+        None => (None, None),
+    };
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(fmt::layer())
+        .with(file_layer)
+        .try_init()
+        .context("Failed to set tracing subscriber")?;
+
+    if let Some(e) = file_error {
+        tracing::warn!(error = ?e, "File logging disabled");
+    } else if let Some(dir) = log_dir {
+        tracing::debug!(dir = ?dir, "Writing log files");
+    }
+    Ok(())
+}
+
+/// Resolve the filter directives.
+///
+/// Precedence: explicit `--log-level`, then `RUST_LOG`, then `--debug`, then the
+/// configured verbosity.
+pub fn filter_directives(
+    cli_level: Option<&str>,
+    env_filter: Option<&str>,
+    debug: bool,
+    configured_level: &str,
+) -> String {
+    if let Some(level) = cli_level {
+        return level_directives(level);
+    }
+    if let Some(env) = env_filter.filter(|env| !env.trim().is_empty()) {
+        return env.to_string();
+    }
+    if debug {
+        // Debug mode: detailed logs for our app, but keep noisy libraries (x11rb) at info
+        return level_directives("debug");
+    }
+    level_directives(configured_level)
+}
+
+fn level_directives(level: &str) -> String {
+    match level {
+        // Verbose levels only apply to our crate; x11rb and winit are extremely chatty
+        "debug" | "trace" => format!("info,eve_preview_manager={}", level),
+        "error" | "warn" => level.to_string(),
+        _ => "info,winit=warn".to_string(),
+    }
+}
+
+fn file_appender(dir: &Path, file_prefix: &str) -> Result<RollingFileAppender> {
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create log directory {:?}", dir))?;
+
+    RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(file_prefix)
+        .filename_suffix(logs::EXTENSION)
+        .max_log_files(logs::RETENTION_COUNT)
+        .build(dir)
+        .with_context(|| format!("Failed to open log file in {:?}", dir))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_directives_precedence() {
+        assert_eq!(
+            filter_directives(Some("warn"), Some("trace"), true, "error"),
+            "warn"
+        );
+        assert_eq!(
+            filter_directives(None, Some("x11rb=trace"), true, "error"),
+            "x11rb=trace"
+        );
+        assert_eq!(
+            filter_directives(None, None, true, "error"),
+            "info,eve_preview_manager=debug"
+        );
+        assert_eq!(filter_directives(None, Some(""), false, "error"), "error");
+    }
+
+    #[test]
+    fn test_level_directives_default_to_info() {
+        assert_eq!(level_directives("info"), "info,winit=warn");
+        assert_eq!(level_directives("bogus"), "info,winit=warn");
+        assert_eq!(level_directives("trace"), "info,eve_preview_manager=trace");
+    }
+}
@@ -4,6 +4,12 @@
 
 pub mod color;
 pub mod constants;
+pub mod crash_report;
 pub mod debug;
+pub mod i18n;
+pub mod image;
 pub mod ipc;
+pub mod logging;
+pub mod pattern;
+pub mod process;
 pub mod types;
@@ -0,0 +1,79 @@
+//! Case-insensitive patterns for custom window rules
+//!
+//! A rule's patterns are plain text, or regular expressions when the rule says so. Rules are
+//! matched on every window event, so each rule keeps its compiled regexes.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
+
+use regex::{Regex, RegexBuilder};
+
+/// Compiled patterns per rule before its cache is reset (live editing compiles every
+/// keystroke)
+const CACHE_LIMIT: usize = 32;
+
+/// Compile `pattern` as a case-insensitive regex
+pub fn compile(pattern: &str) -> Result<Regex, regex::Error> {
+    RegexBuilder::new(pattern).case_insensitive(true).build()
+}
+
+/// Regexes compiled from one rule's patterns. Not saved; a clone starts empty.
+#[derive(Debug, Default)]
+pub struct PatternCache(Mutex<HashMap<String, Option<Regex>>>);
+
+impl Clone for PatternCache {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl PatternCache {
+    /// Whether `pattern` matches anywhere in `text`, ignoring case.
+    ///
+    /// Without `regex` the pattern is literal text. Regex patterns that don't compile
+    /// (e.g. a half-typed `(beta`) match as literal text too.
+    pub fn is_match(&self, pattern: &str, regex: bool, text: &str) -> bool {
+        if !regex {
+            return text.to_lowercase().contains(&pattern.to_lowercase());
+        }
+
+        let mut cache = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        if !cache.contains_key(pattern) {
+            if cache.len() >= CACHE_LIMIT {
+                cache.clear();
+            }
+            let regex = compile(pattern)
+                .or_else(|_| compile(&regex::escape(pattern)))
+                .ok();
+            cache.insert(pattern.to_string(), regex);
+        }
+        cache[pattern]
+            .as_ref()
+            .is_some_and(|regex| regex.is_match(text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_match() {
+        let cache = PatternCache::default();
+        assert!(cache.is_match("firefox", true, "Mozilla Firefox"));
+        assert!(cache.is_match("^discord$", true, "discord"));
+        assert!(!cache.is_match("^discord$", true, "discord-canary"));
+        assert!(cache.is_match("picture.in.picture", true, "Picture-in-Picture"));
+        // Invalid regex falls back to literal text
+        assert!(cache.is_match("(beta", true, "Some App (Beta)"));
+        assert!(!cache.is_match("(beta", true, "beta"));
+    }
+
+    #[test]
+    fn test_plain_text_is_not_regex() {
+        let cache = PatternCache::default();
+        assert!(cache.is_match("Firefox", false, "mozilla firefox"));
+        assert!(!cache.is_match("picture.in.picture", false, "Picture-in-Picture"));
+        assert!(cache.is_match("[EVE]", false, "Pyfa [EVE] fits"));
+    }
+}
@@ -0,0 +1,61 @@
+//! Process name lookups under /proc
+//!
+//! Custom window rules can match the process owning a window (via `_NET_WM_PID`). Wine
+//! apps often have useless WM_CLASS values, but their executable name is stable; Wine
+//! exposes it through `comm` and `argv[0]`, while `exe` points at the Wine loader.
+
+use std::path::Path;
+
+/// Names a process is known by: `comm`, the `exe` file name and the `argv[0]` file name.
+///
+/// Empty if the process does not exist (or belongs to another machine's X client).
+pub fn process_names(pid: u32) -> Vec<String> {
+    let proc_dir = Path::new("/proc").join(pid.to_string());
+    let comm = std::fs::read_to_string(proc_dir.join("comm")).unwrap_or_default();
+    let exe = std::fs::read_link(proc_dir.join("exe"))
+        .map(|path| path.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let cmdline = std::fs::read(proc_dir.join("cmdline")).unwrap_or_default();
+    names_from(&comm, &exe, &cmdline)
+}
+
+fn names_from(comm: &str, exe: &str, cmdline: &[u8]) -> Vec<String> {
+    let argv0 = cmdline.split(|&b| b == 0).next().unwrap_or_default();
+    let argv0 = String::from_utf8_lossy(argv0);
+
+    let mut names: Vec<String> = Vec::new();
+    for name in [comm.trim(), file_name(exe), file_name(&argv0)] {
+        if !name.is_empty() && !names.iter().any(|n| n == name) {
+            names.push(name.to_string());
+        }
+    }
+    names
+}
+
+/// Last component of a Unix or Windows path (Wine's `argv[0]` is `C:\...\app.exe`)
+fn file_name(path: &str) -> &str {
+    path.rsplit(['/', '\\']).next().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_names_from_wine_process() {
+        let names = names_from(
+            "pyfa.exe\n",
+            "/usr/bin/wine64-preloader",
+            b"C:\\Program Files\\pyfa\\pyfa.exe\0--debug\0",
+        );
+        assert_eq!(names, ["pyfa.exe", "wine64-preloader"]);
+    }
+
+    #[test]
+    fn test_process_names_of_self() {
+        let exe = std::env::current_exe().unwrap();
+        let exe_name = exe.file_name().unwrap().to_string_lossy().into_owned();
+        assert!(process_names(std::process::id()).contains(&exe_name));
+        assert!(process_names(u32::MAX).is_empty());
+    }
+}
@@ -1,8 +1,10 @@
 //! Character-specific types and settings for EVE Online windows
 
-use super::geometry::{Dimensions, Position};
+use super::geometry::{Dimensions, MonitorPercent, MonitorScale, Position};
 use serde::{Deserialize, Serialize};
 
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
 /// EVE Online window type classification
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum EveWindowType {
@@ -63,6 +65,168 @@ pub enum PreviewMode {
     Static { color: String },
 }
 
+/// Fleet role shown by a role badge
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum BadgeRole {
+    #[default]
+    Dps,
+    Logistics,
+    Tackle,
+    Ewar,
+    Command,
+    Scout,
+    Miner,
+    Hauler,
+}
+
+impl BadgeRole {
+    pub const ALL: [BadgeRole; 8] = [
+        BadgeRole::Dps,
+        BadgeRole::Logistics,
+        BadgeRole::Tackle,
+        BadgeRole::Ewar,
+        BadgeRole::Command,
+        BadgeRole::Scout,
+        BadgeRole::Miner,
+        BadgeRole::Hauler,
+    ];
+
+    /// Name shown in the Manager
+    pub fn label(self) -> &'static str {
+        match self {
+            BadgeRole::Dps => "DPS",
+            BadgeRole::Logistics => "Logistics",
+            BadgeRole::Tackle => "Tackle",
+            BadgeRole::Ewar => "EWAR",
+            BadgeRole::Command => "Command",
+            BadgeRole::Scout => "Scout",
+            BadgeRole::Miner => "Miner",
+            BadgeRole::Hauler => "Hauler",
+        }
+    }
+
+    /// Letter drawn on the badge
+    pub fn letter(self) -> &'static str {
+        match self {
+            BadgeRole::Dps => "D",
+            BadgeRole::Logistics => "L",
+            BadgeRole::Tackle => "T",
+            BadgeRole::Ewar => "E",
+            BadgeRole::Command => "C",
+            BadgeRole::Scout => "S",
+            BadgeRole::Miner => "M",
+            BadgeRole::Hauler => "H",
+        }
+    }
+
+    /// Badge background (ARGB)
+    pub fn color(self) -> u32 {
+        match self {
+            BadgeRole::Dps => 0xFFC0392B,
+            BadgeRole::Logistics => 0xFF27AE60,
+            BadgeRole::Tackle => 0xFFD35400,
+            BadgeRole::Ewar => 0xFF8E44AD,
+            BadgeRole::Command => 0xFF2980B9,
+            BadgeRole::Scout => 0xFF16A085,
+            BadgeRole::Miner => 0xFFB7950B,
+            BadgeRole::Hauler => 0xFF7F8C8D,
+        }
+    }
+}
+
+/// Small status icon drawn on a thumbnail
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Badge {
+    /// Fleet role, drawn as a colored tile with the role's letter
+    Role(BadgeRole),
+    /// Account number, drawn as a numbered tile
+    Account(u8),
+    /// Cross glyph, shown only while the character is skipped in the cycle
+    Skipped,
+    /// PNG image, scaled to the badge size
+    Image(String),
+}
+
+impl Badge {
+    /// Name shown in the Manager
+    pub fn label(&self) -> &'static str {
+        match self {
+            Badge::Role(_) => "Role",
+            Badge::Account(_) => "Account",
+            Badge::Skipped => "Skipped",
+            Badge::Image(_) => "Image",
+        }
+    }
+}
+
+/// How a thumbnail shows that its character is skipped in the cycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SkippedStyle {
+    /// Two diagonal lines across the whole thumbnail
+    #[default]
+    Cross,
+    /// Diagonal hatching across the whole thumbnail
+    Stripes,
+    /// A skipped badge in the badge corner
+    Badge,
+    /// The thumbnail darkened
+    Dim,
+    /// The label prefixed with `[SKIP]`
+    Prefix,
+}
+
+impl SkippedStyle {
+    pub const ALL: [SkippedStyle; 5] = [
+        SkippedStyle::Cross,
+        SkippedStyle::Stripes,
+        SkippedStyle::Badge,
+        SkippedStyle::Dim,
+        SkippedStyle::Prefix,
+    ];
+
+    /// Name shown in the Manager
+    pub fn label(self) -> &'static str {
+        match self {
+            SkippedStyle::Cross => "Cross",
+            SkippedStyle::Stripes => "Stripes",
+            SkippedStyle::Badge => "Corner Badge",
+            SkippedStyle::Dim => "Dim",
+            SkippedStyle::Prefix => "Label Prefix",
+        }
+    }
+
+    /// Whether the indicator is drawn in the configurable skipped color
+    pub fn uses_color(self) -> bool {
+        self != SkippedStyle::Dim
+    }
+}
+
+/// EWMH states (`_NET_WM_STATE`) set on thumbnail windows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThumbnailWmStates {
+    /// Kept above other windows
+    pub above: bool,
+    /// Shown on every virtual desktop
+    pub sticky: bool,
+    pub skip_taskbar: bool,
+    pub skip_pager: bool,
+}
+
+impl Default for ThumbnailWmStates {
+    fn default() -> Self {
+        Self {
+            above: true,
+            sticky: true,
+            skip_taskbar: true,
+            skip_pager: true,
+        }
+    }
+}
+
 /// Per-character settings: position and thumbnail dimensions
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(from = "CharacterSettingsProxy", into = "CharacterSettingsProxy")]
@@ -71,16 +235,40 @@ pub struct CharacterSettings {
     pub y: i16,
     /// Thumbnail dimensions (0 = use auto-detect)
     pub dimensions: Dimensions,
+    /// Position and size as parts of a monitor, taking precedence over the pixels above
+    pub monitor_percent: Option<MonitorPercent>,
+    /// Top-left corner of the client window when the position was saved, so the thumbnail
+    /// can keep its offset from a client that comes up elsewhere
+    pub client_origin: Option<Position>,
 
     // -- Advanced Character Settings --
     pub alias: Option<String>,
     pub notes: Option<String>,
+    /// Path of a PNG portrait shown in the tooltip
+    pub portrait: Option<String>,
     pub override_active_border_color: Option<String>,
     pub override_inactive_border_color: Option<String>,
     pub override_active_border_size: Option<u16>,
     pub override_inactive_border_size: Option<u16>,
     pub override_text_color: Option<String>,
+    /// Hide when no EVE client has focus, overriding the profile setting
+    pub override_hide_not_focused: Option<bool>,
+    /// Show the thumbnail only while this cycle group is the active one
+    pub show_only_in_group: Option<String>,
     pub preview_mode: PreviewMode,
+    /// Status icons drawn on the thumbnail, in order
+    pub badges: Vec<Badge>,
+    /// Shell command run right before the client is focused
+    pub activate_before: Option<String>,
+    /// Shell command run right after the client is focused
+    pub activate_after: Option<String>,
+
+    // -- Bookkeeping --
+    /// When the character was last logged in (Unix seconds); `None` if not seen since
+    /// tracking began
+    pub last_seen: Option<i64>,
+    /// Hidden from the manager's lists (e.g. biomassed or sold alts)
+    pub archived: bool,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -92,10 +280,16 @@ struct CharacterSettingsProxy {
     #[serde(default)]
     height: u16,
     #[serde(default)]
+    monitor_percent: Option<MonitorPercent>,
+    #[serde(default)]
+    client_origin: Option<Position>,
+    #[serde(default)]
     alias: Option<String>,
     #[serde(default)]
     notes: Option<String>,
     #[serde(default)]
+    portrait: Option<String>,
+    #[serde(default)]
     override_active_border_color: Option<String>,
     #[serde(default)]
     override_inactive_border_color: Option<String>,
@@ -106,7 +300,21 @@ struct CharacterSettingsProxy {
     #[serde(default)]
     override_text_color: Option<String>,
     #[serde(default)]
+    override_hide_not_focused: Option<bool>,
+    #[serde(default)]
+    show_only_in_group: Option<String>,
+    #[serde(default)]
     preview_mode: PreviewMode,
+    #[serde(default)]
+    badges: Vec<Badge>,
+    #[serde(default)]
+    activate_before: Option<String>,
+    #[serde(default)]
+    activate_after: Option<String>,
+    #[serde(default)]
+    last_seen: Option<i64>,
+    #[serde(default)]
+    archived: bool,
 }
 
 impl From<CharacterSettings> for CharacterSettingsProxy {
@@ -116,14 +324,24 @@ impl From<CharacterSettings> for CharacterSettingsProxy {
             y: settings.y,
             width: settings.dimensions.width,
             height: settings.dimensions.height,
+            monitor_percent: settings.monitor_percent,
+            client_origin: settings.client_origin,
             alias: settings.alias,
             notes: settings.notes,
+            portrait: settings.portrait,
             override_active_border_color: settings.override_active_border_color,
             override_inactive_border_color: settings.override_inactive_border_color,
             override_active_border_size: settings.override_active_border_size,
             override_inactive_border_size: settings.override_inactive_border_size,
             override_text_color: settings.override_text_color,
+            override_hide_not_focused: settings.override_hide_not_focused,
+            show_only_in_group: settings.show_only_in_group,
             preview_mode: settings.preview_mode,
+            badges: settings.badges,
+            activate_before: settings.activate_before,
+            activate_after: settings.activate_after,
+            last_seen: settings.last_seen,
+            archived: settings.archived,
         }
     }
 }
@@ -137,14 +355,24 @@ impl From<CharacterSettingsProxy> for CharacterSettings {
                 width: proxy.width,
                 height: proxy.height,
             },
+            monitor_percent: proxy.monitor_percent,
+            client_origin: proxy.client_origin,
             alias: proxy.alias,
             notes: proxy.notes,
+            portrait: proxy.portrait,
             override_active_border_color: proxy.override_active_border_color,
             override_inactive_border_color: proxy.override_inactive_border_color,
             override_active_border_size: proxy.override_active_border_size,
             override_inactive_border_size: proxy.override_inactive_border_size,
             override_text_color: proxy.override_text_color,
+            override_hide_not_focused: proxy.override_hide_not_focused,
+            show_only_in_group: proxy.show_only_in_group,
             preview_mode: proxy.preview_mode,
+            badges: proxy.badges,
+            activate_before: proxy.activate_before,
+            activate_after: proxy.activate_after,
+            last_seen: proxy.last_seen,
+            archived: proxy.archived,
         }
     }
 }
@@ -155,20 +383,179 @@ impl CharacterSettings {
             x,
             y,
             dimensions: Dimensions::new(width, height),
+            monitor_percent: None,
+            client_origin: None,
             alias: None,
             notes: None,
+            portrait: None,
             override_active_border_color: None,
             override_inactive_border_color: None,
             override_active_border_size: None,
             override_inactive_border_size: None,
             override_text_color: None,
+            override_hide_not_focused: None,
+            show_only_in_group: None,
             preview_mode: PreviewMode::default(),
+            badges: Vec::new(),
+            activate_before: None,
+            activate_after: None,
+            last_seen: None,
+            archived: false,
         }
     }
 
     pub fn position(&self) -> Position {
         Position::new(self.x, self.y)
     }
+
+    /// Entries added only to track logins have no size, and no position yet
+    pub fn is_placed(&self) -> bool {
+        self.dimensions.width != 0 && self.dimensions.height != 0
+    }
+
+    /// Where the thumbnail goes for a client now at `client_origin`: the same offset from
+    /// the client as when the position was saved, or the saved position itself if either
+    /// corner is unknown
+    pub fn position_from_client(&self, client_origin: Option<Position>) -> Position {
+        match (self.client_origin, client_origin) {
+            (Some(saved), Some(current)) => self.position().relative_to(saved).offset(current),
+            _ => self.position(),
+        }
+    }
+
+    /// Take position and size from `monitor_percent` on `monitors`. Returns true if they changed.
+    pub fn apply_monitor_percent(&mut self, monitors: &[MonitorScale]) -> bool {
+        let Some((pos, size)) = self
+            .monitor_percent
+            .and_then(|percent| percent.resolve(monitors))
+        else {
+            return false;
+        };
+        let changed = (pos, size) != (self.position(), self.dimensions);
+        (self.x, self.y) = (pos.x, pos.y);
+        self.dimensions = size;
+        changed
+    }
+
+    /// Re-measure `monitor_percent` once the thumbnail was moved or resized away from it.
+    /// Returns true if it changed.
+    pub fn sync_monitor_percent(&mut self, monitors: &[MonitorScale]) -> bool {
+        let Some(percent) = self.monitor_percent else {
+            return false;
+        };
+        if percent.resolve(monitors) == Some((self.position(), self.dimensions)) {
+            return false;
+        }
+        let measured = MonitorPercent::measure(self.position(), self.dimensions, monitors);
+        if measured.is_none() || measured == self.monitor_percent {
+            return false;
+        }
+        self.monitor_percent = measured;
+        true
+    }
+
+    /// Whole days since the character was last seen, or `None` if never seen
+    pub fn days_since_seen(&self, now: i64) -> Option<i64> {
+        self.last_seen
+            .map(|seen| (now - seen).max(0) / SECONDS_PER_DAY)
+    }
+
+    /// Not seen for at least `days`. Characters never seen since tracking began don't count,
+    /// so upgrading doesn't flag every existing character at once.
+    pub fn is_unused(&self, now: i64, days: u32) -> bool {
+        self.days_since_seen(now)
+            .is_some_and(|since| since >= i64::from(days))
+    }
+
+    /// Take over what `other` sets and these settings leave unset (alias, notes, overrides,
+    /// badges...), keeping position and size. Returns the names of the settings taken over.
+    pub fn fill_from(&mut self, other: CharacterSettings) -> Vec<&'static str> {
+        fn fill<T>(
+            field: &mut Option<T>,
+            other: Option<T>,
+            name: &'static str,
+            taken: &mut Vec<&'static str>,
+        ) {
+            if field.is_none() && other.is_some() {
+                *field = other;
+                taken.push(name);
+            }
+        }
+
+        let mut taken = Vec::new();
+        fill(&mut self.alias, other.alias, "alias", &mut taken);
+        fill(&mut self.notes, other.notes, "notes", &mut taken);
+        fill(
+            &mut self.override_active_border_color,
+            other.override_active_border_color,
+            "active border color",
+            &mut taken,
+        );
+        fill(
+            &mut self.override_inactive_border_color,
+            other.override_inactive_border_color,
+            "inactive border color",
+            &mut taken,
+        );
+        fill(
+            &mut self.override_active_border_size,
+            other.override_active_border_size,
+            "active border size",
+            &mut taken,
+        );
+        fill(
+            &mut self.override_inactive_border_size,
+            other.override_inactive_border_size,
+            "inactive border size",
+            &mut taken,
+        );
+        fill(
+            &mut self.override_text_color,
+            other.override_text_color,
+            "text color",
+            &mut taken,
+        );
+        fill(
+            &mut self.override_hide_not_focused,
+            other.override_hide_not_focused,
+            "hide when unfocused",
+            &mut taken,
+        );
+        fill(
+            &mut self.show_only_in_group,
+            other.show_only_in_group,
+            "group visibility",
+            &mut taken,
+        );
+        fill(
+            &mut self.activate_before,
+            other.activate_before,
+            "action before activation",
+            &mut taken,
+        );
+        fill(
+            &mut self.activate_after,
+            other.activate_after,
+            "action after activation",
+            &mut taken,
+        );
+        if self.preview_mode == PreviewMode::default() && other.preview_mode != self.preview_mode {
+            self.preview_mode = other.preview_mode;
+            taken.push("preview mode");
+        }
+        let before = self.badges.len();
+        for badge in other.badges {
+            if !self.badges.contains(&badge) {
+                self.badges.push(badge);
+            }
+        }
+        if self.badges.len() > before {
+            taken.push("badges");
+        }
+        self.last_seen = self.last_seen.max(other.last_seen);
+        self.archived &= other.archived;
+        taken
+    }
 }
 
 #[cfg(test)]
@@ -253,6 +640,24 @@ mod tests {
         assert_eq!(pos.y, 250);
     }
 
+    #[test]
+    fn test_character_settings_position_from_client() {
+        let mut settings = CharacterSettings::new(150, 250, 800, 600);
+        let moved = Some(Position::new(1000, 0));
+        // Saved without a client origin: the position stays put
+        assert_eq!(
+            settings.position_from_client(moved),
+            Position::new(150, 250)
+        );
+
+        settings.client_origin = Some(Position::new(100, 200));
+        assert_eq!(
+            settings.position_from_client(moved),
+            Position::new(1050, 50)
+        );
+        assert_eq!(settings.position_from_client(None), Position::new(150, 250));
+    }
+
     #[test]
     fn test_character_settings_serialization() {
         let settings = CharacterSettings::new(50, 75, 1920, 1080);
@@ -265,6 +670,49 @@ mod tests {
         assert_eq!(deserialized.dimensions.height, settings.dimensions.height);
     }
 
+    #[test]
+    fn test_character_settings_monitor_percent() {
+        let monitor = |width, height| MonitorScale {
+            x: 0,
+            y: 0,
+            width,
+            height,
+            scale: 1.0,
+        };
+        let (wide, small) = ([monitor(2560, 1440)], [monitor(1920, 1080)]);
+        let mut settings = CharacterSettings::new(256, 144, 512, 288);
+        assert!(!settings.sync_monitor_percent(&wide));
+
+        settings.monitor_percent =
+            MonitorPercent::measure(settings.position(), settings.dimensions, &wide);
+        assert!(!settings.sync_monitor_percent(&wide));
+        assert!(settings.apply_monitor_percent(&small));
+        assert_eq!(
+            (settings.x, settings.y, settings.dimensions),
+            (192, 108, Dimensions::new(384, 216))
+        );
+
+        // Moving the thumbnail re-measures, staying put doesn't
+        assert!(!settings.sync_monitor_percent(&small));
+        settings.x = 960;
+        assert!(settings.sync_monitor_percent(&small));
+        assert_eq!(settings.monitor_percent.unwrap().x, 5000);
+    }
+
+    #[test]
+    fn test_character_settings_unused() {
+        let now = 100 * SECONDS_PER_DAY;
+        let mut settings = CharacterSettings::new(0, 0, 0, 0);
+        assert!(!settings.is_unused(now, 30));
+
+        settings.last_seen = Some(now - 29 * SECONDS_PER_DAY - 1);
+        assert_eq!(settings.days_since_seen(now), Some(29));
+        assert!(!settings.is_unused(now, 30));
+
+        settings.last_seen = Some(now - 30 * SECONDS_PER_DAY);
+        assert!(settings.is_unused(now, 30));
+    }
+
     #[test]
     fn test_character_settings_zero_dimensions() {
         // Zero dimensions mean "use auto-detect"
@@ -290,4 +738,39 @@ mod tests {
         let json_live = serde_json::to_string(&live).unwrap();
         assert_eq!(json_live, "\"live\"");
     }
+
+    #[test]
+    fn test_badges_serialization() {
+        let mut settings = CharacterSettings::new(0, 0, 480, 270);
+        settings.badges = vec![
+            Badge::Role(BadgeRole::Logistics),
+            Badge::Account(2),
+            Badge::Skipped,
+            Badge::Image("~/icons/fc.png".to_string()),
+        ];
+        let json = serde_json::to_string(&settings).unwrap();
+        assert!(json.contains(
+            "\"badges\":[{\"role\":\"logistics\"},{\"account\":2},\"skipped\",{\"image\":\"~/icons/fc.png\"}]"
+        ));
+        let deserialized: CharacterSettings = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, settings);
+
+        // Settings saved before badges existed load without any
+        let old: CharacterSettings = serde_json::from_str("{\"x\":1,\"y\":2}").unwrap();
+        assert!(old.badges.is_empty());
+    }
+
+    #[test]
+    fn test_skipped_style_serialization() {
+        assert_eq!(
+            serde_json::to_string(&SkippedStyle::Prefix).unwrap(),
+            "\"prefix\""
+        );
+        for style in SkippedStyle::ALL {
+            let json = serde_json::to_string(&style).unwrap();
+            assert_eq!(serde_json::from_str::<SkippedStyle>(&json).unwrap(), style);
+        }
+        assert!(!SkippedStyle::Dim.uses_color());
+        assert!(SkippedStyle::Stripes.uses_color());
+    }
 }
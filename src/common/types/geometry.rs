@@ -30,6 +30,22 @@ impl Position {
             y: tuple.1,
         }
     }
+
+    /// This position measured from the root instead of from `origin`
+    pub fn offset(self, origin: Position) -> Self {
+        Self::new(
+            self.x.saturating_add(origin.x),
+            self.y.saturating_add(origin.y),
+        )
+    }
+
+    /// This position measured from `origin` instead of from the root
+    pub fn relative_to(self, origin: Position) -> Self {
+        Self::new(
+            self.x.saturating_sub(origin.x),
+            self.y.saturating_sub(origin.y),
+        )
+    }
 }
 
 impl From<(i16, i16)> for Position {
@@ -113,6 +129,205 @@ impl TextOffset {
     }
 }
 
+/// Corner (or center) of the thumbnail the text offset is measured from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TextAnchor {
+    #[default]
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+    Center,
+}
+
+impl TextAnchor {
+    pub const ALL: [TextAnchor; 5] = [
+        TextAnchor::TopLeft,
+        TextAnchor::TopRight,
+        TextAnchor::BottomLeft,
+        TextAnchor::BottomRight,
+        TextAnchor::Center,
+    ];
+
+    /// Name shown in the Manager
+    pub fn label(self) -> &'static str {
+        match self {
+            TextAnchor::TopLeft => "Top Left",
+            TextAnchor::TopRight => "Top Right",
+            TextAnchor::BottomLeft => "Bottom Left",
+            TextAnchor::BottomRight => "Bottom Right",
+            TextAnchor::Center => "Center",
+        }
+    }
+
+    /// Top-left corner of `text` placed in `area` at `offset` from this anchor
+    ///
+    /// Offsets point inward from right and bottom anchors, and shift the text right and
+    /// down from the center.
+    pub fn place(self, offset: TextOffset, area: Dimensions, text: Dimensions) -> Position {
+        let free_x = i32::from(area.width) - i32::from(text.width);
+        let free_y = i32::from(area.height) - i32::from(text.height);
+        let (x, y) = (i32::from(offset.x), i32::from(offset.y));
+        let (x, y) = match self {
+            TextAnchor::TopLeft => (x, y),
+            TextAnchor::TopRight => (free_x - x, y),
+            TextAnchor::BottomLeft => (x, free_y - y),
+            TextAnchor::BottomRight => (free_x - x, free_y - y),
+            TextAnchor::Center => (free_x / 2 + x, free_y / 2 + y),
+        };
+        let clamp = |v: i32| v.clamp(i32::from(i16::MIN), i32::from(i16::MAX)) as i16;
+        Position::new(clamp(x), clamp(y))
+    }
+}
+
+/// Scale factor of one monitor, in root window coordinates
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MonitorScale {
+    pub x: i16,
+    pub y: i16,
+    pub width: u16,
+    pub height: u16,
+    pub scale: f32,
+}
+
+impl MonitorScale {
+    pub fn contains(&self, pos: Position) -> bool {
+        let (x, y) = (i32::from(pos.x), i32::from(pos.y));
+        x >= i32::from(self.x)
+            && y >= i32::from(self.y)
+            && x < i32::from(self.x) + i32::from(self.width)
+            && y < i32::from(self.y) + i32::from(self.height)
+    }
+
+    /// Where a window of `size` at `pos` on this monitor lands on `to`: the same offset from
+    /// the monitor's top-left corner, kept inside `to` where that is smaller
+    pub fn translate_to(&self, to: &MonitorScale, pos: Position, size: Dimensions) -> Position {
+        let offset = |pos: i16, from: i16| i32::from(pos) - i32::from(from);
+        let place = |offset: i32, start: i16, length: u16, window: u16| {
+            let max = (i32::from(length) - i32::from(window)).max(0);
+            let v = i32::from(start) + offset.clamp(0, max);
+            v.clamp(i32::from(i16::MIN), i32::from(i16::MAX)) as i16
+        };
+        Position::new(
+            place(offset(pos.x, self.x), to.x, to.width, size.width),
+            place(offset(pos.y, self.y), to.y, to.height, size.height),
+        )
+    }
+}
+
+/// Position and size in hundredths of a percent of one monitor, so a layout carries over to
+/// displays with other resolutions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MonitorPercent {
+    /// Index of the monitor counted left to right, then top to bottom; the first monitor
+    /// stands in for one the display doesn't have
+    pub monitor: u8,
+    pub x: u16,
+    pub y: u16,
+    pub width: u16,
+    pub height: u16,
+}
+
+impl MonitorPercent {
+    /// 100% in the units of the fields
+    pub const FULL: u16 = 10_000;
+
+    /// Monitors in the order `monitor` counts them
+    fn ordered(monitors: &[MonitorScale]) -> Vec<&MonitorScale> {
+        let mut ordered: Vec<_> = monitors.iter().collect();
+        ordered.sort_by_key(|m| (m.x, m.y));
+        ordered
+    }
+
+    /// Measure a window of `size` at `pos` against the monitor containing its top-left corner
+    pub fn measure(pos: Position, size: Dimensions, monitors: &[MonitorScale]) -> Option<Self> {
+        let ordered = Self::ordered(monitors);
+        let index = ordered.iter().position(|m| m.contains(pos)).unwrap_or(0);
+        let monitor = ordered.get(index)?;
+        let part = |value: f64, length: u16| {
+            if length == 0 {
+                return 0;
+            }
+            (value * f64::from(Self::FULL) / f64::from(length))
+                .round()
+                .clamp(0.0, f64::from(Self::FULL)) as u16
+        };
+        Some(Self {
+            monitor: u8::try_from(index).unwrap_or(u8::MAX),
+            x: part(f64::from(pos.x) - f64::from(monitor.x), monitor.width),
+            y: part(f64::from(pos.y) - f64::from(monitor.y), monitor.height),
+            width: part(f64::from(size.width), monitor.width),
+            height: part(f64::from(size.height), monitor.height),
+        })
+    }
+
+    /// Position and size in pixels on `monitors`
+    pub fn resolve(&self, monitors: &[MonitorScale]) -> Option<(Position, Dimensions)> {
+        let ordered = Self::ordered(monitors);
+        let monitor = ordered.get(usize::from(self.monitor)).or(ordered.first())?;
+        let pixels = |part: u16, length: u16| {
+            (f64::from(part) * f64::from(length) / f64::from(Self::FULL)).round()
+        };
+        let clamp = |v: f64| v.clamp(f64::from(i16::MIN), f64::from(i16::MAX)) as i16;
+        Some((
+            Position::new(
+                clamp(f64::from(monitor.x) + pixels(self.x, monitor.width)),
+                clamp(f64::from(monitor.y) + pixels(self.y, monitor.height)),
+            ),
+            // 0 stays 0 (auto-detect)
+            Dimensions::new(
+                pixels(self.width, monitor.width) as u16,
+                pixels(self.height, monitor.height) as u16,
+            ),
+        ))
+    }
+}
+
+/// HiDPI scale factors of the monitors of a display
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DisplayScales {
+    pub monitors: Vec<MonitorScale>,
+    /// Scale of the primary monitor, also used outside of every monitor
+    pub primary: f32,
+}
+
+impl Default for DisplayScales {
+    fn default() -> Self {
+        Self {
+            monitors: Vec::new(),
+            primary: 1.0,
+        }
+    }
+}
+
+impl DisplayScales {
+    /// Scale of the monitor containing `pos`
+    pub fn at(&self, pos: Position) -> f32 {
+        self.monitor_at(pos).map_or(self.primary, |m| m.scale)
+    }
+
+    /// Monitor containing `pos`, if any
+    pub fn monitor_at(&self, pos: Position) -> Option<&MonitorScale> {
+        self.monitors.iter().find(|m| m.contains(pos))
+    }
+}
+
+/// Scale factor for a DPI, in half steps from 1x (96 DPI) to 4x
+pub fn scale_for_dpi(dpi: f32) -> f32 {
+    ((dpi / 96.0) * 2.0).round().clamp(2.0, 8.0) / 2.0
+}
+
+/// `value` multiplied by `scale`, rounded and saturated to the type's range
+pub fn scale_u16(value: u16, scale: f32) -> u16 {
+    (f32::from(value) * scale).round() as u16
+}
+
+/// `value` multiplied by `scale`, rounded and saturated to the type's range
+pub fn scale_i16(value: i16, scale: f32) -> i16 {
+    (f32::from(value) * scale).round() as i16
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -144,6 +359,18 @@ mod tests {
         assert_eq!(tuple, (100, 200));
     }
 
+    #[test]
+    fn test_position_relative_to_origin() {
+        let origin = Position::new(1920, -40);
+        let pos = Position::new(2000, 100);
+        assert_eq!(pos.relative_to(origin), Position::new(80, 140));
+        assert_eq!(pos.relative_to(origin).offset(origin), pos);
+        assert_eq!(
+            Position::new(i16::MAX, 0).offset(origin),
+            Position::new(i16::MAX, -40)
+        );
+    }
+
     #[test]
     fn test_dimensions_creation() {
         let dims = Dimensions::new(640, 480);
@@ -203,4 +430,156 @@ mod tests {
         assert_eq!(offset2.x, 15);
         assert_eq!(offset2.y, 25);
     }
+
+    #[test]
+    fn test_scale_for_dpi() {
+        assert_eq!(scale_for_dpi(96.0), 1.0);
+        assert_eq!(scale_for_dpi(109.0), 1.0); // 27" 1440p
+        assert_eq!(scale_for_dpi(163.0), 1.5); // 27" 4K
+        assert_eq!(scale_for_dpi(192.0), 2.0);
+        assert_eq!(scale_for_dpi(72.0), 1.0);
+        assert_eq!(scale_for_dpi(1000.0), 4.0);
+    }
+
+    #[test]
+    fn test_display_scales_at() {
+        let scales = DisplayScales {
+            monitors: vec![
+                MonitorScale {
+                    x: 0,
+                    y: 0,
+                    width: 3840,
+                    height: 2160,
+                    scale: 2.0,
+                },
+                MonitorScale {
+                    x: 3840,
+                    y: 0,
+                    width: 1920,
+                    height: 1080,
+                    scale: 1.0,
+                },
+            ],
+            primary: 2.0,
+        };
+        assert_eq!(scales.at(Position::new(100, 100)), 2.0);
+        assert_eq!(scales.at(Position::new(4000, 100)), 1.0);
+        // Below the smaller monitor, outside of both
+        assert_eq!(scales.at(Position::new(4000, 1500)), 2.0);
+        assert_eq!(DisplayScales::default().at(Position::new(0, 0)), 1.0);
+    }
+
+    #[test]
+    fn test_monitor_translate_to() {
+        let left = MonitorScale {
+            x: 0,
+            y: 0,
+            width: 2560,
+            height: 1440,
+            scale: 1.0,
+        };
+        let right = MonitorScale {
+            x: 2560,
+            y: 0,
+            width: 1920,
+            height: 1080,
+            scale: 1.0,
+        };
+        let size = Dimensions::new(480, 270);
+
+        assert_eq!(
+            left.translate_to(&right, Position::new(100, 50), size),
+            Position::new(2660, 50)
+        );
+        assert_eq!(
+            right.translate_to(&left, Position::new(2660, 50), size),
+            Position::new(100, 50)
+        );
+        // Kept inside the smaller monitor
+        assert_eq!(
+            left.translate_to(&right, Position::new(2000, 1100), size),
+            Position::new(2560 + 1920 - 480, 1080 - 270)
+        );
+    }
+
+    #[test]
+    fn test_monitor_percent_carries_over_resolutions() {
+        let monitor = |x, width, height| MonitorScale {
+            x,
+            y: 0,
+            width,
+            height,
+            scale: 1.0,
+        };
+        // Listed right monitor first; indices count from the left
+        let wide = [monitor(2560, 2560, 1440), monitor(0, 2560, 1440)];
+        let small = [monitor(0, 1920, 1080), monitor(1920, 1920, 1080)];
+
+        let percent =
+            MonitorPercent::measure(Position::new(2816, 144), Dimensions::new(512, 288), &wide)
+                .unwrap();
+        assert_eq!(
+            percent,
+            MonitorPercent {
+                monitor: 1,
+                x: 1000,
+                y: 1000,
+                width: 2000,
+                height: 2000,
+            }
+        );
+        assert_eq!(
+            percent.resolve(&small),
+            Some((Position::new(2112, 108), Dimensions::new(384, 216)))
+        );
+
+        // A single monitor stands in for the missing second one
+        assert_eq!(
+            percent.resolve(&small[..1]),
+            Some((Position::new(192, 108), Dimensions::new(384, 216)))
+        );
+        assert_eq!(percent.resolve(&[]), None);
+    }
+
+    #[test]
+    fn test_scale_values() {
+        assert_eq!(scale_u16(270, 1.5), 405);
+        assert_eq!(scale_u16(60000, 2.0), u16::MAX);
+        assert_eq!(scale_i16(-10, 2.0), -20);
+    }
+
+    #[test]
+    fn test_text_anchor_place() {
+        let offset = TextOffset::from_border_edge(10, 5);
+        let area = Dimensions::new(480, 270);
+        let text = Dimensions::new(100, 20);
+
+        assert_eq!(
+            TextAnchor::TopLeft.place(offset, area, text),
+            Position::new(10, 5)
+        );
+        assert_eq!(
+            TextAnchor::TopRight.place(offset, area, text),
+            Position::new(370, 5)
+        );
+        assert_eq!(
+            TextAnchor::BottomLeft.place(offset, area, text),
+            Position::new(10, 245)
+        );
+        assert_eq!(
+            TextAnchor::BottomRight.place(offset, area, text),
+            Position::new(370, 245)
+        );
+        assert_eq!(
+            TextAnchor::Center.place(offset, area, text),
+            Position::new(200, 130)
+        );
+
+        // The same offset keeps a label in its corner at any thumbnail size
+        let small = Dimensions::new(240, 135);
+        assert_eq!(
+            TextAnchor::BottomRight.place(offset, small, text),
+            Position::new(130, 110)
+        );
+    }
 }
@@ -6,5 +6,11 @@ pub mod character;
 pub mod geometry;
 
 // Re-export specific types to maintain compatibility
-pub use character::{CharacterSettings, EveWindowType, PreviewMode, ThumbnailState};
-pub use geometry::{Dimensions, Position, TextOffset};
+pub use character::{
+    Badge, BadgeRole, CharacterSettings, EveWindowType, PreviewMode, SkippedStyle, ThumbnailState,
+    ThumbnailWmStates,
+};
+pub use geometry::{
+    Dimensions, DisplayScales, MonitorPercent, MonitorScale, Position, TextAnchor, TextOffset,
+    scale_for_dpi, scale_i16, scale_u16,
+};
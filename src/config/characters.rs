@@ -0,0 +1,189 @@
+//! Global character registry
+//!
+//! What identifies a character (alias, notes, tags, portrait) is kept once for all profiles.
+//! Profiles only keep what belongs to their layout: thumbnail positions, sizes, overrides and
+//! hotkeys. The daemon copies the identity onto the active profile's character settings.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::common::types::CharacterSettings;
+use crate::config::profile::Profile;
+
+/// Identity-level data about one character, shared by all profiles
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CharacterIdentity {
+    /// Display name drawn instead of the character name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// Free-form labels, matched by the character search
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Path of a portrait image
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub portrait: Option<String>,
+}
+
+impl CharacterIdentity {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Character identities by character name (global setting)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct CharacterRegistry(HashMap<String, CharacterIdentity>);
+
+impl CharacterRegistry {
+    pub fn get(&self, name: &str) -> Option<&CharacterIdentity> {
+        self.0.get(name)
+    }
+
+    /// Change the identity of `name`; identities left empty are removed
+    pub fn update(&mut self, name: &str, change: impl FnOnce(&mut CharacterIdentity)) {
+        let identity = self.0.entry(name.to_string()).or_default();
+        change(identity);
+        if identity.is_empty() {
+            self.0.remove(name);
+        }
+    }
+
+    /// Copy each identity the daemon shows (alias, notes, portrait) onto the settings of the
+    /// same character
+    pub fn apply_to(&self, settings: &mut HashMap<String, CharacterSettings>) {
+        for (name, identity) in &self.0 {
+            if let Some(settings) = settings.get_mut(name) {
+                settings.alias = identity.alias.clone();
+                settings.notes = identity.notes.clone();
+                settings.portrait = identity.portrait.clone();
+            }
+        }
+    }
+
+    /// Move the identity of `old` to `new` after a rename, replacing any identity of `new`
+    pub fn rename(&mut self, old: &str, new: &str) {
+        if old == new {
+            return;
+        }
+        if let Some(identity) = self.0.remove(old) {
+            self.0.insert(new.to_string(), identity);
+        }
+    }
+
+    /// Combine the identity of the duplicate entry `duplicate` into `keep`, which takes over
+    /// what only `duplicate` has. Returns the names of the fields taken over.
+    pub fn combine(&mut self, keep: &str, duplicate: &str) -> Vec<&'static str> {
+        let mut taken = Vec::new();
+        if keep == duplicate {
+            return taken;
+        }
+        let Some(other) = self.0.remove(duplicate) else {
+            return taken;
+        };
+        let identity = self.0.entry(keep.to_string()).or_default();
+        if identity.alias.is_none() && other.alias.is_some() {
+            identity.alias = other.alias;
+            taken.push("alias");
+        }
+        if identity.notes.is_none() && other.notes.is_some() {
+            identity.notes = other.notes;
+            taken.push("notes");
+        }
+        let before = identity.tags.len();
+        for tag in other.tags {
+            if !identity.tags.contains(&tag) {
+                identity.tags.push(tag);
+            }
+        }
+        if identity.tags.len() > before {
+            taken.push("tags");
+        }
+        if identity.portrait.is_none() && other.portrait.is_some() {
+            identity.portrait = other.portrait;
+            taken.push("portrait");
+        }
+        taken
+    }
+
+    /// Move aliases and notes stored in `profiles` (before the registry existed) into the
+    /// registry. The first profile's value wins where profiles disagree. Returns the number of
+    /// characters whose identity moved.
+    pub fn absorb(&mut self, profiles: &mut [Profile]) -> usize {
+        let mut moved = 0;
+        for profile in profiles {
+            for (name, settings) in &mut profile.character_thumbnails {
+                let alias = settings.alias.take();
+                let notes = settings.notes.take();
+                if alias.is_none() && notes.is_none() {
+                    continue;
+                }
+                let identity = self.0.entry(name.clone()).or_default();
+                if identity.alias.is_none() {
+                    identity.alias = alias;
+                }
+                if identity.notes.is_none() {
+                    identity.notes = notes;
+                }
+                moved += 1;
+            }
+        }
+        moved
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_absorb_moves_profile_aliases_into_registry() {
+        let mut first = Profile::default_with_name("First".to_string(), String::new());
+        let mut alice = CharacterSettings::new(0, 0, 100, 100);
+        alice.alias = Some("Main".to_string());
+        first
+            .character_thumbnails
+            .insert("Alice".to_string(), alice);
+        let mut second = Profile::default_with_name("Second".to_string(), String::new());
+        let mut alice = CharacterSettings::new(50, 50, 100, 100);
+        alice.alias = Some("Other".to_string());
+        alice.notes = Some("Cyno".to_string());
+        second
+            .character_thumbnails
+            .insert("Alice".to_string(), alice);
+        let mut profiles = vec![first, second];
+
+        let mut registry = CharacterRegistry::default();
+        assert_eq!(registry.absorb(&mut profiles), 2);
+        let identity = registry.get("Alice").unwrap();
+        assert_eq!(identity.alias.as_deref(), Some("Main"));
+        assert_eq!(identity.notes.as_deref(), Some("Cyno"));
+        assert!(
+            profiles
+                .iter()
+                .all(|p| p.character_thumbnails["Alice"].alias.is_none())
+        );
+        // Positions stay per profile
+        assert_eq!(profiles[1].character_thumbnails["Alice"].x, 50);
+
+        let mut settings =
+            HashMap::from([("Alice".to_string(), CharacterSettings::new(0, 0, 100, 100))]);
+        registry.apply_to(&mut settings);
+        assert_eq!(settings["Alice"].alias.as_deref(), Some("Main"));
+    }
+
+    #[test]
+    fn test_update_removes_empty_identities() {
+        let mut registry = CharacterRegistry::default();
+        registry.update("Alice", |identity| {
+            identity.tags = vec!["hauler".to_string()]
+        });
+        assert!(registry.get("Alice").is_some());
+        registry.update("Alice", |identity| identity.tags.clear());
+        assert!(registry.get("Alice").is_none());
+    }
+}
@@ -0,0 +1,178 @@
+//! EVE client window detection patterns
+//!
+//! Clients are recognized by their window title: `EVE - <character>` once logged in and
+//! `EVE` at the login screen. Localized clients, launcher wrappers (Steam, Lutris) and the
+//! China server client can title or class their windows differently, so the patterns are
+//! part of the global settings. Windows of exotic Wine prefixes whose titles match nothing
+//! can be marked as clients by their WM_CLASS.
+
+use serde::{Deserialize, Serialize};
+
+use crate::common::constants::eve;
+use crate::common::types::EveWindowType;
+
+/// Title and WM_CLASS patterns identifying EVE client windows
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EveDetection {
+    /// Title prefixes of logged-in clients; the rest of the title is the character name
+    pub title_prefixes: Vec<String>,
+    /// Exact titles of clients at the login screen
+    pub logged_out_titles: Vec<String>,
+    /// WM_CLASS values a client must have (case-insensitive); empty accepts any class
+    pub wm_classes: Vec<String>,
+    /// WM_CLASS values always treated as EVE clients (case-insensitive), whatever their title
+    pub client_classes: Vec<String>,
+}
+
+impl Default for EveDetection {
+    fn default() -> Self {
+        Self {
+            title_prefixes: vec![eve::WINDOW_TITLE_PREFIX.to_string()],
+            logged_out_titles: vec![eve::LOGGED_OUT_TITLE.to_string()],
+            wm_classes: Vec::new(),
+            client_classes: Vec::new(),
+        }
+    }
+}
+
+/// Whether `class` is one of `classes` (case-insensitive, blank entries ignored)
+fn class_listed(classes: &[String], class: &str) -> bool {
+    classes
+        .iter()
+        .any(|c| !c.trim().is_empty() && c.trim().eq_ignore_ascii_case(class))
+}
+
+impl EveDetection {
+    /// Whether `classify` needs the window's WM_CLASS
+    pub fn checks_class(&self) -> bool {
+        self.filters_class() || self.client_classes.iter().any(|c| !c.trim().is_empty())
+    }
+
+    /// Whether clients must have one of `wm_classes`
+    fn filters_class(&self) -> bool {
+        self.wm_classes.iter().any(|c| !c.trim().is_empty())
+    }
+
+    /// Whether a window of this WM_CLASS is marked as an EVE client
+    pub fn is_client_class(&self, wm_class: Option<&str>) -> bool {
+        wm_class.is_some_and(|class| class_listed(&self.client_classes, class))
+    }
+
+    /// Classify a window by its title and WM_CLASS. Blank patterns are ignored. Marked
+    /// client classes pass the WM_CLASS filter; windows of those classes whose title matches
+    /// no pattern are left to `is_client_class`.
+    pub fn classify(&self, title: &str, wm_class: Option<&str>) -> Option<EveWindowType> {
+        if self.filters_class() {
+            let class_matches = wm_class.is_some_and(|class| class_listed(&self.wm_classes, class))
+                || self.is_client_class(wm_class);
+            if !class_matches {
+                return None;
+            }
+        }
+
+        // Exact login-screen titles take precedence over a prefix that also matches them
+        if self
+            .logged_out_titles
+            .iter()
+            .any(|t| !t.is_empty() && t == title)
+        {
+            return Some(EveWindowType::LoggedOut);
+        }
+
+        // Longest matching prefix wins, so `EVE - ` beats a broader `EVE`
+        let name = self
+            .title_prefixes
+            .iter()
+            .filter(|prefix| !prefix.is_empty())
+            .filter_map(|prefix| title.strip_prefix(prefix.as_str()))
+            .min_by_key(|name| name.len());
+
+        // Steam's Proton container window carries the prefix but is not a client
+        name.filter(|name| !name.to_lowercase().contains("steam_app_"))
+            .map(|name| EveWindowType::LoggedIn(name.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_patterns() {
+        let detection = EveDetection::default();
+        assert_eq!(
+            detection.classify("EVE - Alice", None),
+            Some(EveWindowType::LoggedIn("Alice".to_string()))
+        );
+        assert_eq!(
+            detection.classify("EVE", Some("exefile.exe")),
+            Some(EveWindowType::LoggedOut)
+        );
+        assert_eq!(detection.classify("EVE - steam_app_8500", None), None);
+        assert_eq!(detection.classify("EVE Online Launcher", None), None);
+        assert_eq!(detection.classify("", None), None);
+    }
+
+    #[test]
+    fn test_custom_patterns() {
+        let detection = EveDetection {
+            title_prefixes: vec![
+                "星战前夜 - ".to_string(),
+                "EVE - ".to_string(),
+                String::new(),
+            ],
+            logged_out_titles: vec!["星战前夜".to_string(), String::new()],
+            wm_classes: vec!["exefile.exe".to_string()],
+            client_classes: Vec::new(),
+        };
+        assert_eq!(
+            detection.classify("星战前夜 - Alice", Some("ExeFile.exe")),
+            Some(EveWindowType::LoggedIn("Alice".to_string()))
+        );
+        assert_eq!(
+            detection.classify("星战前夜", Some("exefile.exe")),
+            Some(EveWindowType::LoggedOut)
+        );
+        // Class filter rejects look-alike titles from other programs
+        assert_eq!(detection.classify("EVE - Alice", Some("firefox")), None);
+        assert_eq!(detection.classify("EVE - Alice", None), None);
+        // Blank entries never match everything
+        assert_eq!(detection.classify("", Some("exefile.exe")), None);
+    }
+
+    #[test]
+    fn test_client_classes() {
+        let detection = EveDetection {
+            wm_classes: vec!["exefile.exe".to_string()],
+            client_classes: vec!["wine-eve".to_string()],
+            ..Default::default()
+        };
+        assert!(detection.checks_class());
+        // Marked classes pass the class filter and still get their character from the title
+        assert_eq!(
+            detection.classify("EVE - Alice", Some("Wine-EVE")),
+            Some(EveWindowType::LoggedIn("Alice".to_string()))
+        );
+        assert_eq!(detection.classify("Wine Desktop", Some("wine-eve")), None);
+        assert!(detection.is_client_class(Some("WINE-EVE")));
+        assert!(!detection.is_client_class(Some("firefox")));
+        assert!(!detection.is_client_class(None));
+    }
+
+    #[test]
+    fn test_longest_prefix_wins() {
+        let detection = EveDetection {
+            title_prefixes: vec!["EVE".to_string(), "EVE - ".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            detection.classify("EVE - Alice", None),
+            Some(EveWindowType::LoggedIn("Alice".to_string()))
+        );
+        assert_eq!(
+            detection.classify("EVE", None),
+            Some(EveWindowType::LoggedOut)
+        );
+    }
+}
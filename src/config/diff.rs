@@ -0,0 +1,446 @@
+//! Differences between the saved config and the one in memory
+//!
+//! Used to preview a save. Configs are compared through their JSON form, so every field is
+//! covered without listing them here. Thumbnail maps are compared per character, so a moved
+//! thumbnail can be kept out of a save while a settings change goes in.
+
+use std::collections::BTreeSet;
+
+use anyhow::{Context, Result};
+use serde_json::{Map, Value};
+
+use super::profile::Config;
+
+/// Profile keys holding per-character entries, diffed entry by entry
+const THUMBNAIL_KEYS: [&str; 2] = ["character_thumbnails", "custom_source_thumbnails"];
+
+/// Global keys holding manager window state rather than settings. Not listed as changes and
+/// always saved as they are in memory.
+const UI_STATE_KEYS: [&str; 5] = [
+    "window_width",
+    "window_height",
+    "window_maximized",
+    "last_tab",
+    "tab_scroll_offsets",
+];
+
+/// Longest value shown in a change description
+const MAX_VALUE_LEN: usize = 40;
+
+/// What a change touches
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChangeTarget {
+    /// A global setting
+    Global(String),
+    /// A whole profile, added or removed
+    Profile(String),
+    /// Order of the profile list
+    ProfileOrder,
+    /// A profile setting other than thumbnails
+    ProfileField { profile: String, key: String },
+    /// One entry of a profile's thumbnail map
+    Thumbnail {
+        profile: String,
+        key: &'static str,
+        name: String,
+    },
+}
+
+/// A single difference between two configs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigChange {
+    pub target: ChangeTarget,
+    pub description: String,
+}
+
+impl ConfigChange {
+    /// Profile the change belongs to, if any
+    pub fn profile(&self) -> Option<&str> {
+        match &self.target {
+            ChangeTarget::Global(_) | ChangeTarget::ProfileOrder => None,
+            ChangeTarget::Profile(profile)
+            | ChangeTarget::ProfileField { profile, .. }
+            | ChangeTarget::Thumbnail { profile, .. } => Some(profile),
+        }
+    }
+}
+
+/// Everything that differs between `saved` and `current`
+pub fn diff(saved: &Config, current: &Config) -> Result<Vec<ConfigChange>> {
+    let saved = to_value(saved)?;
+    let current = to_value(current)?;
+    let mut changes = Vec::new();
+
+    for key in keys(&saved["global"], &current["global"]) {
+        if UI_STATE_KEYS.contains(&key.as_str()) {
+            continue;
+        }
+        let (old, new) = (saved["global"].get(&key), current["global"].get(&key));
+        if old != new {
+            changes.push(ConfigChange {
+                description: describe_field(&key, old, new),
+                target: ChangeTarget::Global(key),
+            });
+        }
+    }
+
+    let saved_profiles = profiles(&saved);
+    let current_profiles = profiles(&current);
+
+    for (name, _) in &saved_profiles {
+        if !current_profiles.iter().any(|(n, _)| n == name) {
+            changes.push(ConfigChange {
+                target: ChangeTarget::Profile(name.clone()),
+                description: "Profile removed".to_string(),
+            });
+        }
+    }
+
+    for (name, new_profile) in &current_profiles {
+        let Some((_, old_profile)) = saved_profiles.iter().find(|(n, _)| n == name) else {
+            changes.push(ConfigChange {
+                target: ChangeTarget::Profile(name.clone()),
+                description: "Profile added".to_string(),
+            });
+            continue;
+        };
+
+        for key in keys(old_profile, new_profile) {
+            let (old, new) = (old_profile.get(&key), new_profile.get(&key));
+            if old == new {
+                continue;
+            }
+            match THUMBNAIL_KEYS.iter().find(|k| **k == key) {
+                Some(thumbnail_key) => {
+                    diff_thumbnails(name, thumbnail_key, old, new, &mut changes);
+                }
+                None => changes.push(ConfigChange {
+                    description: describe_field(&key, old, new),
+                    target: ChangeTarget::ProfileField {
+                        profile: name.clone(),
+                        key,
+                    },
+                }),
+            }
+        }
+    }
+
+    let common_order = |from: &[(String, &Value)], other: &[(String, &Value)]| {
+        from.iter()
+            .filter(|(name, _)| other.iter().any(|(n, _)| n == name))
+            .map(|(name, _)| name.clone())
+            .collect::<Vec<_>>()
+    };
+    if common_order(&saved_profiles, &current_profiles)
+        != common_order(&current_profiles, &saved_profiles)
+    {
+        changes.push(ConfigChange {
+            target: ChangeTarget::ProfileOrder,
+            description: "Profile order changed".to_string(),
+        });
+    }
+
+    Ok(changes)
+}
+
+/// `saved` with only the given changes taken from `current`
+pub fn apply(saved: &Config, current: &Config, changes: &[ConfigChange]) -> Result<Config> {
+    let mut out = to_value(saved)?;
+    let current = to_value(current)?;
+    let current_profiles = profiles(&current);
+    let find_current = |name: &str| {
+        current_profiles
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, p)| *p)
+    };
+
+    for key in UI_STATE_KEYS {
+        set_key(&mut out["global"], key, current["global"].get(key));
+    }
+
+    for change in changes {
+        match &change.target {
+            ChangeTarget::Global(key) => {
+                set_key(&mut out["global"], key, current["global"].get(key));
+            }
+            ChangeTarget::Profile(name) => {
+                let list = profile_list_mut(&mut out)?;
+                list.retain(|p| profile_name(p) != Some(name));
+                if let Some(profile) = find_current(name) {
+                    list.push(profile.clone());
+                }
+            }
+            ChangeTarget::ProfileField { profile, key } => {
+                if let (Some(target), Some(source)) =
+                    (profile_mut(&mut out, profile)?, find_current(profile))
+                {
+                    set_key(target, key, source.get(key));
+                }
+            }
+            ChangeTarget::Thumbnail { profile, key, name } => {
+                if let (Some(target), Some(source)) =
+                    (profile_mut(&mut out, profile)?, find_current(profile))
+                {
+                    if !target[*key].is_object() {
+                        target[*key] = Value::Object(Map::new());
+                    }
+                    set_key(&mut target[*key], name, source[*key].get(name));
+                }
+            }
+            ChangeTarget::ProfileOrder => {}
+        }
+    }
+
+    // Reorder last, once added and removed profiles are settled
+    if changes
+        .iter()
+        .any(|c| c.target == ChangeTarget::ProfileOrder)
+    {
+        let order: Vec<String> = current_profiles.iter().map(|(n, _)| n.clone()).collect();
+        profile_list_mut(&mut out)?.sort_by_key(|p| {
+            profile_name(p)
+                .and_then(|name| order.iter().position(|n| n == name))
+                .unwrap_or(usize::MAX)
+        });
+    }
+
+    serde_json::from_value(out).context("Failed to rebuild config from selected changes")
+}
+
+fn to_value(config: &Config) -> Result<Value> {
+    serde_json::to_value(config).context("Failed to serialize config for comparison")
+}
+
+/// Union of the object keys of `a` and `b`, sorted
+fn keys(a: &Value, b: &Value) -> BTreeSet<String> {
+    [a, b]
+        .into_iter()
+        .filter_map(Value::as_object)
+        .flat_map(|map| map.keys().cloned())
+        .collect()
+}
+
+fn profile_name(profile: &Value) -> Option<&str> {
+    profile["profile_name"].as_str()
+}
+
+fn profiles(config: &Value) -> Vec<(String, &Value)> {
+    config["profiles"]
+        .as_array()
+        .map(|list| {
+            list.iter()
+                .filter_map(|p| profile_name(p).map(|name| (name.to_string(), p)))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn profile_list_mut(config: &mut Value) -> Result<&mut Vec<Value>> {
+    config["profiles"]
+        .as_array_mut()
+        .context("Saved config has no profile list")
+}
+
+fn profile_mut<'a>(config: &'a mut Value, name: &str) -> Result<Option<&'a mut Value>> {
+    Ok(profile_list_mut(config)?
+        .iter_mut()
+        .find(|p| profile_name(p) == Some(name)))
+}
+
+/// Set `key` in object `target` to `value`, or remove it when `value` is `None`
+fn set_key(target: &mut Value, key: &str, value: Option<&Value>) {
+    if let Some(map) = target.as_object_mut() {
+        match value {
+            Some(value) => {
+                map.insert(key.to_string(), value.clone());
+            }
+            None => {
+                map.remove(key);
+            }
+        }
+    }
+}
+
+fn diff_thumbnails(
+    profile: &str,
+    key: &'static str,
+    old: Option<&Value>,
+    new: Option<&Value>,
+    changes: &mut Vec<ConfigChange>,
+) {
+    let kind = if key == "custom_source_thumbnails" {
+        "Source"
+    } else {
+        "Character"
+    };
+    let empty = Value::Object(Map::new());
+    let (old, new) = (old.unwrap_or(&empty), new.unwrap_or(&empty));
+
+    for name in keys(old, new) {
+        let description = match (old.get(&name), new.get(&name)) {
+            (Some(a), Some(b)) if a == b => continue,
+            (None, Some(_)) => format!("{} {} added", kind, name),
+            (Some(_), None) => format!("{} {} removed", kind, name),
+            (Some(a), Some(b)) => {
+                let changed: Vec<String> = keys(a, b)
+                    .into_iter()
+                    .filter(|k| a.get(k) != b.get(k))
+                    .collect();
+                if changed.iter().all(|k| k == "x" || k == "y") {
+                    format!(
+                        "{} {} moved ({}, {}) → ({}, {})",
+                        kind, name, a["x"], a["y"], b["x"], b["y"]
+                    )
+                } else {
+                    format!("{} {}: {} changed", kind, name, changed.join(", "))
+                }
+            }
+            (None, None) => continue,
+        };
+        changes.push(ConfigChange {
+            target: ChangeTarget::Thumbnail {
+                profile: profile.to_string(),
+                key,
+                name,
+            },
+            description,
+        });
+    }
+}
+
+fn describe_field(key: &str, old: Option<&Value>, new: Option<&Value>) -> String {
+    match (old, new) {
+        (Some(Value::Array(_) | Value::Object(_)), _)
+        | (_, Some(Value::Array(_) | Value::Object(_))) => {
+            format!("{} changed", key)
+        }
+        _ => format!("{}: {} → {}", key, show(old), show(new)),
+    }
+}
+
+fn show(value: Option<&Value>) -> String {
+    let text = match value {
+        None | Some(Value::Null) => "none".to_string(),
+        Some(Value::String(s)) => format!("\"{}\"", s),
+        Some(other) => other.to_string(),
+    };
+    if text.chars().count() > MAX_VALUE_LEN {
+        let cut: String = text.chars().take(MAX_VALUE_LEN).collect();
+        format!("{}…", cut)
+    } else {
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::types::CharacterSettings;
+    use crate::config::profile::Profile;
+
+    fn saved() -> Config {
+        let mut config = Config::default();
+        config.profiles[0].character_thumbnails.insert(
+            "Alice".to_string(),
+            CharacterSettings::new(10, 20, 300, 200),
+        );
+        config
+    }
+
+    #[test]
+    fn test_diff_lists_changes() {
+        let saved = saved();
+        let mut current = saved.clone();
+        current.profiles[0].thumbnail_opacity = 42;
+        current.profiles[0]
+            .character_thumbnails
+            .get_mut("Alice")
+            .unwrap()
+            .x = 99;
+        current
+            .profiles
+            .push(Profile::default_with_name("PvP".to_string(), String::new()));
+
+        let descriptions: Vec<String> = diff(&saved, &current)
+            .unwrap()
+            .into_iter()
+            .map(|c| c.description)
+            .collect();
+        assert_eq!(
+            descriptions,
+            [
+                "Character Alice moved (10, 20) → (99, 20)".to_string(),
+                format!(
+                    "thumbnail_opacity: {} → 42",
+                    saved.profiles[0].thumbnail_opacity
+                ),
+                "Profile added".to_string(),
+            ]
+        );
+        assert!(diff(&saved, &saved).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_window_state_is_not_a_change() {
+        let saved = saved();
+        let mut current = saved.clone();
+        current.global.window_width += 100;
+        current.global.last_tab = "logs".to_string();
+
+        assert!(diff(&saved, &current).unwrap().is_empty());
+        let merged = apply(&saved, &current, &[]).unwrap();
+        assert_eq!(merged.global.window_width, current.global.window_width);
+        assert_eq!(merged.global.last_tab, "logs");
+    }
+
+    #[test]
+    fn test_apply_keeps_unselected_changes_out() {
+        let saved = saved();
+        let mut current = saved.clone();
+        current.profiles[0].thumbnail_opacity = 42;
+        current.profiles[0]
+            .character_thumbnails
+            .get_mut("Alice")
+            .unwrap()
+            .x = 99;
+
+        let selected: Vec<ConfigChange> = diff(&saved, &current)
+            .unwrap()
+            .into_iter()
+            .filter(|c| matches!(c.target, ChangeTarget::ProfileField { .. }))
+            .collect();
+        let merged = apply(&saved, &current, &selected).unwrap();
+
+        assert_eq!(merged.profiles[0].thumbnail_opacity, 42);
+        assert_eq!(merged.profiles[0].character_thumbnails["Alice"].x, 10);
+    }
+
+    #[test]
+    fn test_apply_profile_changes() {
+        let mut saved = saved();
+        saved
+            .profiles
+            .push(Profile::default_with_name("PvP".to_string(), String::new()));
+        let mut current = saved.clone();
+        current.profiles.swap(0, 1);
+        current.profiles.push(Profile::default_with_name(
+            "Mining".to_string(),
+            String::new(),
+        ));
+
+        let changes = diff(&saved, &current).unwrap();
+        let merged = apply(&saved, &current, &changes).unwrap();
+        let names: Vec<&str> = merged
+            .profiles
+            .iter()
+            .map(|p| p.profile_name.as_str())
+            .collect();
+        let expected: Vec<&str> = current
+            .profiles
+            .iter()
+            .map(|p| p.profile_name.as_str())
+            .collect();
+        assert_eq!(names, expected);
+    }
+}
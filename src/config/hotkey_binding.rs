@@ -1,12 +1,48 @@
 //! Hotkey binding configuration and key code mapping
 
+use crate::input::keymap::Keymap;
 use evdev::KeyCode;
 use serde::de;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::str::FromStr;
 
+/// How a binding finds its key after the keyboard layout changes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyMatch {
+    /// The physical key it was captured on, whatever the layout prints on it
+    #[default]
+    Keycode,
+    /// Whichever key carries the captured symbol in the active layout
+    Keysym,
+}
+
+/// How a key has to be pressed to trigger a binding
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Gesture {
+    #[default]
+    Press,
+    /// Two presses in quick succession
+    DoubleTap,
+    /// Held down past the long-press time
+    LongPress,
+}
+
+impl Gesture {
+    pub const ALL: [Gesture; 3] = [Gesture::Press, Gesture::DoubleTap, Gesture::LongPress];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Gesture::Press => "Press",
+            Gesture::DoubleTap => "Double tap",
+            Gesture::LongPress => "Long press",
+        }
+    }
+}
+
 /// A keyboard hotkey binding with modifiers
-/// Serializes to/from object format: {"keys": [...], "source_devices": [...]}
+/// Serializes to/from object format: {"keys": [...], "source_devices": [...], "device": "..."}
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct HotkeyBinding {
     /// evdev key code (e.g., KEY_TAB = 15, KEY_F1 = 59)
@@ -27,6 +63,20 @@ pub struct HotkeyBinding {
     /// Input devices that contributed to this binding (e.g., keyboard, mouse)
     /// Used for auto-detection of which devices to listen to at runtime
     pub source_devices: Vec<String>,
+
+    /// Only fire when the key is pressed on this input device (evdev backend only).
+    /// `None` fires from any device.
+    pub device: Option<String>,
+
+    /// X keysym the active layout put on the key when it was captured, used to show the
+    /// key's printed label (e.g. "A" rather than "Q" on AZERTY)
+    pub keysym: Option<u32>,
+
+    /// Whether the binding follows the physical key or the captured symbol
+    pub match_by: KeyMatch,
+
+    /// Press, double tap or long press; one key can carry a binding for each
+    pub gesture: Gesture,
 }
 
 impl HotkeyBinding {
@@ -39,6 +89,10 @@ impl HotkeyBinding {
             alt,
             super_key,
             source_devices: Vec::new(),
+            device: None,
+            keysym: None,
+            match_by: KeyMatch::Keycode,
+            gesture: Gesture::Press,
         }
     }
 
@@ -58,6 +112,10 @@ impl HotkeyBinding {
             alt,
             super_key,
             source_devices,
+            device: None,
+            keysym: None,
+            match_by: KeyMatch::Keycode,
+            gesture: Gesture::Press,
         }
     }
 
@@ -78,9 +136,20 @@ impl HotkeyBinding {
             parts.push("Super".to_string());
         }
 
-        parts.push(key_code_to_name(self.key_code));
+        parts.push(self.key_name());
+
+        let name = parts.join("+");
+        match self.gesture {
+            Gesture::Press => name,
+            gesture => format!("{} ({})", name, gesture.label().to_lowercase()),
+        }
+    }
 
-        parts.join("+")
+    /// Name of the main key: its printed label if one was captured, otherwise its evdev name
+    pub fn key_name(&self) -> String {
+        self.keysym
+            .and_then(keysym_label)
+            .unwrap_or_else(|| key_code_to_name(self.key_code))
     }
 
     /// Check if this binding matches a key press with current modifier state
@@ -92,11 +161,46 @@ impl HotkeyBinding {
         alt: bool,
         super_key: bool,
     ) -> bool {
-        self.key_code == key_code
-            && self.ctrl == ctrl
-            && self.shift == shift
-            && self.alt == alt
-            && self.super_key == super_key
+        self.key_code == key_code && self.modifiers_match(ctrl, shift, alt, super_key)
+    }
+
+    pub fn modifiers_match(&self, ctrl: bool, shift: bool, alt: bool, super_key: bool) -> bool {
+        self.ctrl == ctrl && self.shift == shift && self.alt == alt && self.super_key == super_key
+    }
+
+    /// Key code to listen for under `keymap`. Bindings matching by symbol move to the key
+    /// carrying it; if no key does, or there is no keymap, the captured key is used.
+    pub fn resolved_key_code(&self, keymap: Option<&Keymap>) -> u16 {
+        match (self.match_by, self.keysym, keymap) {
+            (KeyMatch::Keysym, Some(keysym), Some(keymap)) => {
+                keymap.key_code(keysym).unwrap_or(self.key_code)
+            }
+            _ => self.key_code,
+        }
+    }
+
+    /// Whether a key press on `device_id` may trigger this binding
+    pub fn accepts_device(&self, device_id: &str) -> bool {
+        self.device
+            .as_deref()
+            .is_none_or(|device| device == device_id)
+    }
+
+    /// Whether this binding and `other` can fire on the same key press
+    pub fn overlaps(&self, other: &HotkeyBinding) -> bool {
+        let same_device = match (&self.device, &other.device) {
+            (Some(a), Some(b)) => a == b,
+            _ => true,
+        };
+        same_device
+            && self.gesture == other.gesture
+            && self.matches(
+                other.key_code,
+                other.ctrl,
+                other.shift,
+                other.alt,
+                other.super_key,
+            )
     }
 
     /// Convert to array format for JSON serialization
@@ -198,9 +302,23 @@ impl Serialize for HotkeyBinding {
         S: Serializer,
     {
         use serde::ser::SerializeStruct;
-        let mut state = serializer.serialize_struct("HotkeyBinding", 2)?;
+        let human_readable = serializer.is_human_readable();
+        let mut state = serializer.serialize_struct("HotkeyBinding", 6)?;
         state.serialize_field("keys", &self.to_key_array())?;
         state.serialize_field("source_devices", &self.source_devices)?;
+        // Bincode reads fields by position, so only JSON may leave them out
+        if self.device.is_some() || !human_readable {
+            state.serialize_field("device", &self.device)?;
+        }
+        if self.keysym.is_some() || !human_readable {
+            state.serialize_field("keysym", &self.keysym)?;
+        }
+        if self.match_by != KeyMatch::Keycode || !human_readable {
+            state.serialize_field("match_by", &self.match_by)?;
+        }
+        if self.gesture != Gesture::Press || !human_readable {
+            state.serialize_field("gesture", &self.gesture)?;
+        }
         state.end()
     }
 }
@@ -217,6 +335,14 @@ impl<'de> Deserialize<'de> for HotkeyBinding {
             keys: Vec<String>,
             #[serde(default)]
             source_devices: Vec<String>,
+            #[serde(default)]
+            device: Option<String>,
+            #[serde(default)]
+            keysym: Option<u32>,
+            #[serde(default)]
+            match_by: KeyMatch,
+            #[serde(default)]
+            gesture: Gesture,
         }
 
         if deserializer.is_human_readable() {
@@ -232,6 +358,10 @@ impl<'de> Deserialize<'de> for HotkeyBinding {
                     let mut binding =
                         HotkeyBinding::from_key_array(&obj.keys).map_err(de::Error::custom)?;
                     binding.source_devices = obj.source_devices;
+                    binding.device = obj.device;
+                    binding.keysym = obj.keysym;
+                    binding.match_by = obj.match_by;
+                    binding.gesture = obj.gesture;
                     Ok(binding)
                 }
                 HotkeyFormat::Array(keys) => {
@@ -242,12 +372,16 @@ impl<'de> Deserialize<'de> for HotkeyBinding {
         } else {
             // Binary format (Bincode) - Strictly object/struct
             // Since we control serialization, we know it's always the struct format
-            // keys then source_devices
+            // keys, source_devices, device, keysym, match_by then gesture
             // We can map it to the HotkeyObject struct
             let obj = HotkeyObject::deserialize(deserializer)?;
             let mut binding =
                 HotkeyBinding::from_key_array(&obj.keys).map_err(de::Error::custom)?;
             binding.source_devices = obj.source_devices;
+            binding.device = obj.device;
+            binding.keysym = obj.keysym;
+            binding.match_by = obj.match_by;
+            binding.gesture = obj.gesture;
             Ok(binding)
         }
     }
@@ -314,7 +448,7 @@ pub fn key_code_to_name(code: u16) -> String {
                 "ASTERISK" => "Numpad Asterisk".to_string(),
                 "SLASH" => "Numpad Slash".to_string(),
                 "DOT" => "Numpad Period".to_string(),
-                _ => format!("Numpad {}", rest),
+                _ => format!("Numpad {}", title_case(rest)),
             }
         }
 
@@ -325,24 +459,42 @@ pub fn key_code_to_name(code: u16) -> String {
         s if s.starts_with('F') && s.len() <= 3 => s.to_string(),
 
         // Everything else - convert underscores to spaces and title case
-        s => s
-            .replace('_', " ")
-            .split_whitespace()
-            .map(|word| {
-                let mut chars = word.chars();
-                match chars.next() {
-                    None => String::new(),
-                    Some(first) => first
-                        .to_uppercase()
-                        .chain(chars.as_str().to_lowercase().chars())
-                        .collect(),
-                }
-            })
-            .collect::<Vec<_>>()
-            .join(" "),
+        s => title_case(s),
     }
 }
 
+fn title_case(name: &str) -> String {
+    name.replace('_', " ")
+        .split_whitespace()
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                None => String::new(),
+                Some(first) => first
+                    .to_uppercase()
+                    .chain(chars.as_str().to_lowercase().chars())
+                    .collect(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Printed label of an X keysym (e.g. "A", "&", "É"), or `None` for keys better named by
+/// their evdev code: keypad keys, whose symbols would read like the top-row digits, and keys
+/// without a visible character
+pub fn keysym_label(keysym: u32) -> Option<String> {
+    let keysym = xkeysym::Keysym::new(keysym);
+    if keysym.is_keypad_key() || keysym.is_private_keypad_key() {
+        return None;
+    }
+    let ch = keysym.key_char()?;
+    if ch.is_control() || ch.is_whitespace() {
+        return None;
+    }
+    Some(ch.to_uppercase().collect())
+}
+
 /// Convert Linux input event code name (KEY_*) to evdev key code
 /// Uses evdev crate's KeyCode::from_str for robust parsing
 fn linux_name_to_key_code(name: &str) -> Option<u16> {
@@ -372,6 +524,11 @@ mod tests {
 
         let binding = HotkeyBinding::new(59, true, true, true, false);
         assert_eq!(binding.display_name(), "Ctrl+Shift+Alt+F1");
+
+        let mut binding = HotkeyBinding::new(15, false, false, false, false);
+        binding.gesture = Gesture::LongPress;
+        assert_eq!(binding.display_name(), "Tab (long press)");
+        assert!(!binding.overlaps(&HotkeyBinding::new(15, false, false, false, false)));
     }
 
     #[test]
@@ -465,6 +622,66 @@ mod tests {
         assert_eq!(deserialized.source_devices, vec!["device1", "device2"]);
     }
 
+    #[test]
+    fn test_device_restriction() {
+        let mut pad = HotkeyBinding::new(15, false, false, false, false);
+        pad.device = Some("usb-Macro_Pad-event-kbd".to_string());
+        assert!(pad.accepts_device("usb-Macro_Pad-event-kbd"));
+        assert!(!pad.accepts_device("usb-Keyboard-event-kbd"));
+
+        let json = serde_json::to_string(&pad).unwrap();
+        assert_eq!(
+            json,
+            r#"{"keys":["KEY_TAB"],"source_devices":[],"device":"usb-Macro_Pad-event-kbd"}"#
+        );
+        assert_eq!(serde_json::from_str::<HotkeyBinding>(&json).unwrap(), pad);
+
+        // The daemon receives bindings in the binary IPC format
+        let (tx, rx) = ipc_channel::ipc::channel::<HotkeyBinding>().unwrap();
+        tx.send(pad.clone()).unwrap();
+        assert_eq!(rx.recv().unwrap(), pad);
+
+        // Same keys on different devices never fire together
+        let mut keyboard = pad.clone();
+        keyboard.device = Some("usb-Keyboard-event-kbd".to_string());
+        assert!(!pad.overlaps(&keyboard));
+        assert!(pad.overlaps(&HotkeyBinding::new(15, false, false, false, false)));
+    }
+
+    #[test]
+    fn test_keysym_labels() {
+        // AZERTY: the key named KEY_Q carries "a", the KEY_1 key carries "&"
+        let mut binding = HotkeyBinding::new(16, true, false, false, false);
+        binding.keysym = Some(0x61);
+        assert_eq!(binding.display_name(), "Ctrl+A");
+        binding.key_code = 2;
+        binding.keysym = Some(0x26);
+        assert_eq!(binding.display_name(), "Ctrl+&");
+        assert_eq!(keysym_label(0xe9).as_deref(), Some("É"));
+
+        // Numpad digits never read like the top row, whatever the layout reports
+        assert_eq!(keysym_label(0xffb1), None); // KP_1
+        let mut binding = HotkeyBinding::new(79, false, false, false, false);
+        binding.keysym = Some(0xffb1);
+        assert_eq!(binding.display_name(), "Numpad 1");
+        assert_eq!(key_code_to_name(2), "1");
+        assert_eq!(key_code_to_name(117), "Numpad Equal"); // KEY_KPEQUAL
+
+        // Keys without a printable symbol keep their evdev name
+        assert_eq!(keysym_label(0x20), None); // space
+        assert_eq!(keysym_label(0xff09), None); // Tab
+
+        let json = serde_json::to_string(&binding).unwrap();
+        assert_eq!(
+            json,
+            r#"{"keys":["KEY_KP1"],"source_devices":[],"keysym":65457}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<HotkeyBinding>(&json).unwrap(),
+            binding
+        );
+    }
+
     #[test]
     fn test_evdev_keycode_conversion() {
         // Test that we can convert to/from KEY_* names
@@ -5,11 +5,23 @@
 //! and per-character thumbnail positions.
 
 pub mod backup;
+pub mod characters;
+pub mod detection;
+pub mod diff;
 pub mod hotkey_binding;
+pub mod power;
 pub mod profile;
 pub mod runtime;
 pub mod serialization;
+pub mod snapshots;
+pub mod timing;
+pub mod validation;
 
+pub use characters::{CharacterIdentity, CharacterRegistry};
+pub use detection::EveDetection;
 pub use hotkey_binding::HotkeyBinding;
+pub use power::LowPower;
 pub use profile::HotkeyBackendType;
 pub use runtime::{DaemonConfig, DisplayConfig};
+pub use snapshots::AfkSnapshots;
+pub use timing::Timing;
@@ -0,0 +1,114 @@
+//! Low-power mode settings
+//!
+//! Laptops running several clients on battery spend much of their power compositing
+//! thumbnails. In low-power mode the daemon redraws thumbnails at a capped rate, scales with
+//! nearest-neighbour instead of bilinear filtering and can leave all but the hovered
+//! thumbnail frozen.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// When low-power mode is in effect
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LowPowerMode {
+    #[default]
+    Off,
+    /// While UPower reports the machine running on battery
+    OnBattery,
+    /// Always (manual eco mode)
+    Always,
+}
+
+impl LowPowerMode {
+    pub const ALL: [LowPowerMode; 3] = [
+        LowPowerMode::Off,
+        LowPowerMode::OnBattery,
+        LowPowerMode::Always,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            LowPowerMode::Off => "Off",
+            LowPowerMode::OnBattery => "On battery",
+            LowPowerMode::Always => "Always (eco mode)",
+        }
+    }
+}
+
+/// Low-power mode settings (global)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LowPower {
+    pub mode: LowPowerMode,
+    /// Highest thumbnail refresh rate while active, in frames per second
+    pub refresh_fps: u32,
+    /// Only the thumbnail under the pointer keeps updating while active
+    pub pause_unhovered: bool,
+}
+
+impl Default for LowPower {
+    fn default() -> Self {
+        Self {
+            mode: LowPowerMode::Off,
+            refresh_fps: 5,
+            pause_unhovered: false,
+        }
+    }
+}
+
+impl LowPower {
+    /// Lowest and highest accepted `refresh_fps`
+    pub const FPS_RANGE: std::ops::RangeInclusive<u32> = 1..=30;
+
+    /// Whether low-power mode applies, given the current power source
+    pub fn is_active(&self, on_battery: bool) -> bool {
+        match self.mode {
+            LowPowerMode::Off => false,
+            LowPowerMode::OnBattery => on_battery,
+            LowPowerMode::Always => true,
+        }
+    }
+
+    /// Time between thumbnail redraws while active
+    pub fn frame_interval(&self) -> Duration {
+        let fps = self
+            .refresh_fps
+            .clamp(*Self::FPS_RANGE.start(), *Self::FPS_RANGE.end());
+        Duration::from_millis(1000 / fps as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_active_follows_mode() {
+        let mut low_power = LowPower::default();
+        assert!(!low_power.is_active(true));
+
+        low_power.mode = LowPowerMode::OnBattery;
+        assert!(low_power.is_active(true));
+        assert!(!low_power.is_active(false));
+
+        low_power.mode = LowPowerMode::Always;
+        assert!(low_power.is_active(false));
+    }
+
+    #[test]
+    fn test_frame_interval_is_clamped() {
+        let mut low_power = LowPower {
+            refresh_fps: 5,
+            ..LowPower::default()
+        };
+        assert_eq!(low_power.frame_interval(), Duration::from_millis(200));
+
+        low_power.refresh_fps = 0;
+        assert_eq!(low_power.frame_interval(), Duration::from_secs(1));
+
+        low_power.refresh_fps = 1000;
+        assert_eq!(low_power.frame_interval(), Duration::from_millis(33));
+    }
+}
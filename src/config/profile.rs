@@ -7,10 +7,14 @@ use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
-use tracing::info;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use tracing::{debug, info, warn};
 
-use crate::common::types::CharacterSettings;
+use crate::common::pattern;
+use crate::common::types::{
+    CharacterSettings, MonitorScale, Position, SkippedStyle, TextAnchor, ThumbnailWmStates,
+};
 
 /// A named group of characters for cycling
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,6 +31,18 @@ pub struct CycleGroup {
     pub cycle_list: Vec<CycleSlot>,
     pub hotkey_forward: Option<crate::config::HotkeyBinding>,
     pub hotkey_backward: Option<crate::config::HotkeyBinding>,
+    /// Hides and shows the thumbnails of the group's members (session-only)
+    #[serde(default)]
+    pub hotkey_toggle_visibility: Option<crate::config::HotkeyBinding>,
+    /// Accent color marking member thumbnails (hex, None = no marker)
+    #[serde(default)]
+    pub color: Option<String>,
+    /// Characters seen for the first time are appended to this group
+    #[serde(default)]
+    pub auto_include_new: bool,
+    /// Characters appended by `auto_include_new` get the next free number key as hotkey
+    #[serde(default)]
+    pub auto_include_hotkey: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -35,6 +51,9 @@ pub enum CycleSlot {
     Eve(String),
     #[serde(rename = "source")]
     Source(String),
+    /// All entries of another cycle group, in its order
+    #[serde(rename = "group")]
+    Group(String),
 }
 
 impl CycleGroup {
@@ -44,8 +63,66 @@ impl CycleGroup {
             cycle_list: Vec::new(),
             hotkey_forward: None,
             hotkey_backward: None,
+            hotkey_toggle_visibility: None,
+            auto_include_new: false,
+            auto_include_hotkey: false,
+            color: None,
         }
     }
+
+    /// Whether the EVE character or custom source `name` is listed directly in this group
+    /// (entries of referenced groups only count after `resolve_nested`)
+    pub fn contains(&self, name: &str) -> bool {
+        self.cycle_list.iter().any(|slot| match slot {
+            CycleSlot::Eve(n) | CycleSlot::Source(n) => n == name,
+            CycleSlot::Group(_) => false,
+        })
+    }
+
+    /// Copy of `groups` with every group slot replaced by the entries of the group it names.
+    ///
+    /// Each entry appears once per group, at its first position. References to missing
+    /// groups, and references back to a group already being expanded, add nothing.
+    pub fn resolve_nested(groups: &[CycleGroup]) -> Vec<CycleGroup> {
+        fn expand(
+            groups: &[CycleGroup],
+            group: &CycleGroup,
+            expanding: &mut Vec<String>,
+            out: &mut Vec<CycleSlot>,
+        ) {
+            expanding.push(group.name.clone());
+            for slot in &group.cycle_list {
+                match slot {
+                    CycleSlot::Group(name) => {
+                        if expanding.contains(name) {
+                            continue;
+                        }
+                        if let Some(nested) = groups.iter().find(|g| &g.name == name) {
+                            expand(groups, nested, expanding, out);
+                        }
+                    }
+                    entry => {
+                        if !out.contains(entry) {
+                            out.push(entry.clone());
+                        }
+                    }
+                }
+            }
+            expanding.pop();
+        }
+
+        groups
+            .iter()
+            .map(|group| {
+                let mut cycle_list = Vec::new();
+                expand(groups, group, &mut Vec::new(), &mut cycle_list);
+                CycleGroup {
+                    cycle_list,
+                    ..group.clone()
+                }
+            })
+            .collect()
+    }
 }
 
 // Helper for migrating legacy string list to CycleSlot::Eve
@@ -98,13 +175,52 @@ where
     deserializer.deserialize_seq(SlotsVisitor)
 }
 
+/// Separator between a rule alias and the instance number ("Discord #2")
+const INSTANCE_SEPARATOR: &str = " #";
+
+/// Split a custom source label into its alias and 1-based instance number.
+///
+/// Labels without an instance suffix are the first instance.
+pub fn split_instance_label(label: &str) -> (&str, usize) {
+    label
+        .rsplit_once(INSTANCE_SEPARATOR)
+        .and_then(|(alias, n)| Some((alias, n.parse::<usize>().ok().filter(|&n| n > 1)?)))
+        .unwrap_or((label, 1))
+}
+
+/// A custom source window that comes up whenever a character is activated, e.g. the market
+/// spreadsheet of a trader or the intel map of a scout
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompanionLink {
+    pub character: String,
+    /// Label of the custom source (rule alias, or an instance label like "Intel Map #2")
+    pub source: String,
+    /// Where to move the source window's top-left corner (root coordinates); `None` leaves
+    /// it where it is
+    #[serde(default)]
+    pub position: Option<crate::common::types::Position>,
+}
+
 /// Rule for identifying and naming arbitrary application windows
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CustomWindowRule {
-    /// Pattern to match window title (optional)
+    /// Pattern the window title must match (optional, case-insensitive)
     pub title_pattern: Option<String>,
-    /// Pattern to match window class/process (optional)
+    /// Pattern the window class must match (optional, case-insensitive)
     pub class_pattern: Option<String>,
+    /// Pattern the owning process name must match (optional, case-insensitive)
+    pub process_pattern: Option<String>,
+    /// Pattern excluding windows whose title matches (e.g. "Picture-in-Picture")
+    pub title_exclude_pattern: Option<String>,
+    /// Pattern excluding windows whose class matches
+    pub class_exclude_pattern: Option<String>,
+    /// The patterns are regular expressions. Off for rules from before regex support, whose
+    /// patterns are text found anywhere in the title or class.
+    #[serde(default)]
+    pub regex: bool,
+    /// Compiled regexes of the patterns (not saved)
+    #[serde(skip)]
+    pub(crate) compiled: pattern::PatternCache,
     /// Display name used as the identifier ("Character Name")
     pub alias: String,
 
@@ -139,6 +255,58 @@ pub struct CustomWindowRule {
     pub hotkey: Option<crate::config::HotkeyBinding>,
 }
 
+impl CustomWindowRule {
+    /// Label of the `index`-th window matching this rule (1-based).
+    ///
+    /// The first window keeps the bare alias so configs from before multi-instance
+    /// support keep their positions and cycle entries.
+    pub fn instance_label(&self, index: usize) -> String {
+        if index <= 1 {
+            self.alias.clone()
+        } else {
+            format!("{}{}{}", self.alias, INSTANCE_SEPARATOR, index)
+        }
+    }
+
+    /// Whether `label` is this rule's alias or one of its instance labels
+    pub fn owns_label(&self, label: &str) -> bool {
+        label == self.alias || split_instance_label(label).0 == self.alias
+    }
+
+    /// Whether matching this rule needs the owning process names
+    pub fn matches_process(&self) -> bool {
+        self.process_pattern
+            .as_deref()
+            .is_some_and(|p| !p.is_empty())
+    }
+
+    /// Whether a window with this title, WM_CLASS and owning process belongs to the rule.
+    ///
+    /// `processes` are the names of the owning process (see `process::process_names`).
+    /// Every include pattern that is set must match and no exclude pattern may match.
+    /// A rule without include patterns matches nothing.
+    pub fn matches(&self, title: &str, class: &str, processes: &[String]) -> bool {
+        fn set(pattern: &Option<String>) -> Option<&str> {
+            pattern.as_deref().filter(|p| !p.is_empty())
+        }
+
+        let title_pattern = set(&self.title_pattern);
+        let class_pattern = set(&self.class_pattern);
+        let process_pattern = set(&self.process_pattern);
+        if title_pattern.is_none() && class_pattern.is_none() && process_pattern.is_none() {
+            return false;
+        }
+
+        let is_match =
+            |pattern: &str, text: &str| self.compiled.is_match(pattern, self.regex, text);
+        title_pattern.is_none_or(|p| is_match(p, title))
+            && class_pattern.is_none_or(|p| is_match(p, class))
+            && process_pattern.is_none_or(|p| processes.iter().any(|n| is_match(p, n)))
+            && !set(&self.title_exclude_pattern).is_some_and(|p| is_match(p, title))
+            && !set(&self.class_exclude_pattern).is_some_and(|p| is_match(p, class))
+    }
+}
+
 /// Hotkey backend type selection
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -149,6 +317,89 @@ pub enum HotkeyBackendType {
     Evdev,
 }
 
+/// What activating a client on another virtual desktop does
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkspaceFollow {
+    /// Only request activation; the window manager decides (default)
+    #[default]
+    WindowManager,
+    /// Move the client to the current desktop before activating it
+    PullWindow,
+    /// Switch to the client's desktop before activating it
+    SwitchDesktop,
+}
+
+impl WorkspaceFollow {
+    pub const ALL: [WorkspaceFollow; 3] = [
+        WorkspaceFollow::WindowManager,
+        WorkspaceFollow::PullWindow,
+        WorkspaceFollow::SwitchDesktop,
+    ];
+
+    /// Name shown in the Manager
+    pub fn label(self) -> &'static str {
+        match self {
+            WorkspaceFollow::WindowManager => "Leave to window manager",
+            WorkspaceFollow::PullWindow => "Pull client to current desktop",
+            WorkspaceFollow::SwitchDesktop => "Switch to client's desktop",
+        }
+    }
+}
+
+/// Which clients the cycle hotkeys rotate through
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CycleMonitor {
+    /// Clients on every monitor (default)
+    #[default]
+    All,
+    /// Clients on the monitor containing the pointer
+    Pointer,
+    /// Clients on the monitor containing the focused window
+    Focused,
+}
+
+impl CycleMonitor {
+    pub const ALL: [CycleMonitor; 3] = [
+        CycleMonitor::All,
+        CycleMonitor::Pointer,
+        CycleMonitor::Focused,
+    ];
+
+    /// Name shown in the Manager
+    pub fn label(self) -> &'static str {
+        match self {
+            CycleMonitor::All => "All monitors",
+            CycleMonitor::Pointer => "Monitor with the pointer",
+            CycleMonitor::Focused => "Monitor with the focused window",
+        }
+    }
+}
+
+/// What saved thumbnail positions are measured from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PositionAnchor {
+    /// The top-left corner of the screen (default)
+    #[default]
+    Screen,
+    /// The top-left corner of the thumbnail's client window, so thumbnails follow their client
+    Client,
+}
+
+impl PositionAnchor {
+    pub const ALL: [PositionAnchor; 2] = [PositionAnchor::Screen, PositionAnchor::Client];
+
+    /// Name shown in the Manager
+    pub fn label(self) -> &'static str {
+        match self {
+            PositionAnchor::Screen => "Screen",
+            PositionAnchor::Client => "Client window",
+        }
+    }
+}
+
 /// Top-level configuration with profile support
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
@@ -173,6 +424,54 @@ pub struct GlobalSettings {
     pub backup_interval_days: u32,
     #[serde(default = "default_backup_retention_count")]
     pub backup_retention_count: u32,
+    /// Autostart entry launches only the preview daemon (`--daemon-only`) instead of the Manager
+    #[serde(default)]
+    pub autostart_daemon_only: bool,
+    /// Autostart entry launches the Manager minimized (`--minimized`)
+    #[serde(default)]
+    pub autostart_minimized: bool,
+    /// Log verbosity for the Manager and daemon (one of `logging::LEVELS`)
+    #[serde(default = "default_log_level")]
+    pub log_level: String,
+    /// Also write rotated log files under the config directory
+    #[serde(default)]
+    pub log_to_file: bool,
+    /// Title and WM_CLASS patterns identifying EVE client windows
+    #[serde(default)]
+    pub eve_detection: crate::config::EveDetection,
+    /// Language of the manager and thumbnail overlays
+    #[serde(default)]
+    pub language: crate::common::i18n::Language,
+    /// Manager window was maximized when last closed
+    #[serde(default)]
+    pub window_maximized: bool,
+    /// Manager tab open when last closed (`ManagerTab::key`)
+    #[serde(default)]
+    pub last_tab: String,
+    /// Vertical scroll offset of each manager tab, keyed by `ManagerTab::key`
+    #[serde(default)]
+    pub tab_scroll_offsets: HashMap<String, f32>,
+    /// Reduced thumbnail rendering on battery or on demand
+    #[serde(default)]
+    pub low_power: crate::config::LowPower,
+    /// Draw thumbnail damage in one pass per display refresh instead of per damage event
+    #[serde(default)]
+    pub vsync_redraws: bool,
+    /// Write each session's per-character focus time to a CSV file
+    #[serde(default)]
+    pub focus_stats_csv: bool,
+    /// Directory for source window screenshots; empty uses `~/Pictures/eve-screenshots`
+    #[serde(default)]
+    pub screenshot_dir: String,
+    /// Periodic thumbnail-sized snapshots of every client
+    #[serde(default)]
+    pub afk_snapshots: crate::config::AfkSnapshots,
+    /// Alias, notes, tags and portrait of each character, shared by all profiles
+    #[serde(default)]
+    pub characters: crate::config::CharacterRegistry,
+    /// Advanced polling, debounce and spawn offset settings
+    #[serde(default)]
+    pub timing: crate::config::Timing,
 }
 
 /// Profile - A complete set of visual and behavioral settings
@@ -192,6 +491,9 @@ pub struct Profile {
     /// Enable/disable thumbnail rendering entirely (daemon still runs for hotkeys)
     pub thumbnail_enabled: bool,
     pub thumbnail_opacity: u8,
+    /// Create thumbnails on a 32-bit ARGB visual and apply opacity while rendering, instead
+    /// of asking a compositor through `_NET_WM_WINDOW_OPACITY`
+    pub thumbnail_argb_visual: bool,
     pub thumbnail_active_border: bool,
     pub thumbnail_active_border_size: u16,
     pub thumbnail_active_border_color: String,
@@ -201,23 +503,72 @@ pub struct Profile {
     pub thumbnail_text_size: u16,
     pub thumbnail_text_x: i16,
     pub thumbnail_text_y: i16,
+    /// Corner of the thumbnail `thumbnail_text_x/y` are measured from
+    pub thumbnail_text_anchor: TextAnchor,
+    /// Corner of the thumbnail character badges are drawn at
+    pub thumbnail_badge_anchor: TextAnchor,
     pub thumbnail_text_font: String,
     pub thumbnail_text_color: String,
+    /// HiDPI scale of default sizes and text in percent (0 = detect per monitor)
+    pub thumbnail_scale_percent: u16,
+    /// How thumbnails of characters skipped in the cycle are marked
+    pub thumbnail_skipped_style: SkippedStyle,
+    pub thumbnail_skipped_color: String,
+    /// Cycle group whose order is shown as a numbered badge on thumbnails (empty = off)
+    pub thumbnail_cycle_order_group: String,
+    /// Highlight the thumbnail the next forward cycle press lands on
+    pub thumbnail_next_target_highlight: bool,
+    pub thumbnail_next_target_color: String,
 
     // Thumbnail behavior settings
     /// Automatically save thumbnail positions when dragged
     /// If disabled, positions can be manually saved via system tray menu
     pub thumbnail_auto_save_position: bool,
+    /// Persist in-memory thumbnail positions when the daemon is shut down (SIGTERM/SIGINT),
+    /// even if auto-save is disabled
+    pub thumbnail_save_on_exit: bool,
+    /// Periodically merge in-memory thumbnail positions into the config file (0 = disabled)
+    pub thumbnail_autosave_interval_secs: u32,
     pub thumbnail_snap_threshold: u16,
+    /// Spacing in pixels that dragged thumbnails also snap to next to others (0 = disabled)
+    pub thumbnail_snap_gap: u16,
+    /// Show a tooltip with alias, notes, cycle groups and hotkey while hovering a thumbnail
+    pub thumbnail_tooltips: bool,
     pub thumbnail_hide_not_focused: bool,
+    /// WM_CLASS names (case-insensitive) of windows that hide all thumbnails while focused,
+    /// e.g. OBS projectors or video players
+    pub thumbnail_do_not_disturb_classes: Vec<String>,
     /// When a new character logs in without saved coordinates, inherit the previous character's thumbnail position
     /// This keeps thumbnails in place when swapping characters on the same EVE client
     pub thumbnail_preserve_position_on_swap: bool,
+    /// Move a thumbnail along when its client window is moved to another monitor, keeping
+    /// its offset within the monitor
+    pub thumbnail_follow_source_monitor: bool,
+    /// What saved thumbnail positions are measured from
+    pub thumbnail_position_anchor: PositionAnchor,
+    /// Let the window manager manage thumbnail windows instead of bypassing it with
+    /// override-redirect (for tiling WMs where override-redirect windows misbehave over
+    /// fullscreen clients)
+    pub thumbnail_managed_windows: bool,
+    /// EWMH states set on thumbnail windows (above, sticky, skip taskbar, skip pager)
+    pub thumbnail_wm_states: ThumbnailWmStates,
 
     // Client behavior settings
     pub client_minimize_on_switch: bool,
     /// When minimized, show "MINIMIZED" text overlay
     pub client_minimize_show_overlay: bool,
+    /// Custom minimized overlay text (empty = localized "MINIMIZED")
+    pub client_minimize_overlay_text: String,
+    /// Minimized overlay font size in pixels (0 = thumbnail text size)
+    pub client_minimize_overlay_text_size: u16,
+    /// Minimized overlay text color as hex (empty = thumbnail text color)
+    pub client_minimize_overlay_text_color: String,
+    /// How much the minimized overlay darkens the last frame, in percent (100 = solid black)
+    pub client_minimize_overlay_dim: u8,
+    /// PNG shown instead of the minimized overlay text (empty = text)
+    pub client_minimize_overlay_icon: String,
+    /// What activating a client on another virtual desktop does
+    pub client_workspace_follow: WorkspaceFollow,
 
     // Hotkey settings (per-profile)
     /// Hotkey backend selection (X11 or evdev)
@@ -242,6 +593,22 @@ pub struct Profile {
     /// Reset cycle index to the beginning when switching between cycle groups
     pub hotkey_cycle_reset_index: bool,
 
+    /// Restrict the cycle hotkeys to clients on the current monitor
+    pub hotkey_cycle_monitor: CycleMonitor,
+
+    /// Briefly name the character a hotkey switched to, centered over its client
+    pub hotkey_switch_osd: bool,
+
+    /// Keep skipped characters skipped across daemon restarts
+    pub hotkey_skip_persist: bool,
+
+    /// Characters skipped from cycling, saved while `hotkey_skip_persist` is on
+    pub hotkey_skipped_characters: Vec<String>,
+
+    /// Account nickname of each client launch slot (first launched client first), shown on
+    /// its thumbnail while it is logged out. Empty entries use the "Client N" default.
+    pub client_account_labels: Vec<String>,
+
     /// Hotkey to switch to this profile (global)
     pub hotkey_profile_switch: Option<crate::config::HotkeyBinding>,
 
@@ -251,6 +618,30 @@ pub struct Profile {
     /// Hotkey to toggle visibility of all thumbnails (ephemeral)
     pub hotkey_toggle_previews: Option<crate::config::HotkeyBinding>,
 
+    /// Hotkey to save in-memory thumbnail positions to disk
+    pub hotkey_save_positions: Option<crate::config::HotkeyBinding>,
+
+    /// Hotkey to revert the most recent thumbnail drag
+    pub hotkey_undo_move: Option<crate::config::HotkeyBinding>,
+
+    /// Hotkey to cycle forward in the active cycle group
+    pub hotkey_active_forward: Option<crate::config::HotkeyBinding>,
+
+    /// Hotkey to cycle backward in the active cycle group
+    pub hotkey_active_backward: Option<crate::config::HotkeyBinding>,
+
+    /// Hotkey to make the next cycle group the active one
+    pub hotkey_next_group: Option<crate::config::HotkeyBinding>,
+
+    /// Hotkey to save a screenshot of the focused EVE client
+    pub hotkey_screenshot: Option<crate::config::HotkeyBinding>,
+
+    /// Hotkey to show or hide each character's hotkey on its thumbnail
+    pub hotkey_toggle_hotkey_overlay: Option<crate::config::HotkeyBinding>,
+
+    /// Hotkey to swap the focused client's thumbnail with the next one clicked
+    pub hotkey_swap_positions: Option<crate::config::HotkeyBinding>,
+
     /// Per-character hotkey assignments (character_name -> optional binding)
     /// Allows direct switching to specific characters with dedicated hotkeys
     /// Display order follows hotkey_cycle_group
@@ -264,6 +655,13 @@ pub struct Profile {
 
     /// Custom window matching rules for external applications
     pub custom_windows: Vec<CustomWindowRule>,
+
+    /// Custom source windows raised together with a character's client
+    pub companion_windows: Vec<CompanionLink>,
+
+    /// X display the daemon previews, e.g. `:1` for a nested Xephyr or `:0.1` for a second
+    /// screen (empty = $DISPLAY)
+    pub x11_display: String,
 }
 
 // Default value functions
@@ -292,6 +690,10 @@ pub(crate) fn default_backup_retention_count() -> u32 {
     crate::common::constants::config::backup::RETENTION_COUNT
 }
 
+pub(crate) fn default_log_level() -> String {
+    crate::common::constants::config::logs::LEVEL.to_string()
+}
+
 pub(crate) fn default_window_width() -> u16 {
     crate::common::constants::defaults::manager::WINDOW_WIDTH
 }
@@ -316,6 +718,14 @@ pub(crate) fn default_thumbnail_height() -> u16 {
     crate::common::constants::defaults::thumbnail::HEIGHT
 }
 
+pub(crate) fn default_badge_anchor() -> TextAnchor {
+    TextAnchor::TopRight // Default: opposite the character name
+}
+
+pub(crate) fn default_minimize_overlay_dim() -> u8 {
+    100 // Default: solid black, as before the dim setting existed
+}
+
 pub(crate) fn default_thumbnail_enabled() -> bool {
     true // Default: thumbnails enabled
 }
@@ -328,6 +738,14 @@ pub(crate) fn default_inactive_border_enabled() -> bool {
     false // Default: inactive borders disabled
 }
 
+pub(crate) fn default_next_target_color() -> String {
+    crate::common::constants::defaults::border::NEXT_TARGET_COLOR.to_string()
+}
+
+pub(crate) fn default_skipped_color() -> String {
+    crate::common::constants::defaults::skipped::COLOR.to_string()
+}
+
 pub(crate) fn default_inactive_border_color() -> String {
     crate::common::constants::defaults::border::INACTIVE_COLOR.to_string()
 }
@@ -351,6 +769,22 @@ pub(crate) fn default_auto_save_thumbnail_positions() -> bool {
     true
 }
 
+/// Give the `thumbnails` saved as monitor percentages their position and size on
+/// `monitors`. Thumbnails anchored to their client (`anchor`) keep their offset from it
+/// rather than a spot on a monitor, so they keep their pixels.
+pub fn apply_monitor_percent<'a>(
+    anchor: PositionAnchor,
+    thumbnails: impl Iterator<Item = &'a mut CharacterSettings>,
+    monitors: &[MonitorScale],
+) {
+    if anchor == PositionAnchor::Client {
+        return;
+    }
+    for settings in thumbnails {
+        settings.apply_monitor_percent(monitors);
+    }
+}
+
 fn default_profiles() -> Vec<Profile> {
     vec![Profile {
         profile_name: crate::common::constants::defaults::behavior::PROFILE_NAME.to_string(),
@@ -360,6 +794,7 @@ fn default_profiles() -> Vec<Profile> {
         thumbnail_default_height: default_thumbnail_height(),
         thumbnail_enabled: default_thumbnail_enabled(),
         thumbnail_opacity: crate::common::constants::defaults::thumbnail::OPACITY_PERCENT,
+        thumbnail_argb_visual: false,
         thumbnail_active_border: crate::common::constants::defaults::border::ENABLED,
         thumbnail_active_border_size: crate::common::constants::defaults::border::SIZE,
         thumbnail_active_border_color: crate::common::constants::defaults::border::ACTIVE_COLOR
@@ -370,30 +805,69 @@ fn default_profiles() -> Vec<Profile> {
         thumbnail_text_size: crate::common::constants::defaults::text::SIZE,
         thumbnail_text_x: crate::common::constants::defaults::text::OFFSET_X,
         thumbnail_text_y: crate::common::constants::defaults::text::OFFSET_Y,
+        thumbnail_text_anchor: TextAnchor::default(), // Default: top-left
+        thumbnail_badge_anchor: default_badge_anchor(),
         thumbnail_text_font: default_text_font_family(),
         thumbnail_text_color: crate::common::constants::defaults::text::COLOR.to_string(),
+        thumbnail_scale_percent: 0, // Default: detect per monitor
+        thumbnail_skipped_style: SkippedStyle::default(), // Default: red cross
+        thumbnail_skipped_color: default_skipped_color(),
+        thumbnail_cycle_order_group: String::new(), // Default: no cycle order badge
+        thumbnail_next_target_highlight: false,     // Default: off
+        thumbnail_next_target_color: default_next_target_color(),
         thumbnail_auto_save_position: default_auto_save_thumbnail_positions(),
+        thumbnail_save_on_exit: false, // Default: off (respect manual-save workflow)
+        thumbnail_autosave_interval_secs:
+            crate::common::constants::defaults::behavior::AUTOSAVE_INTERVAL_SECS,
         thumbnail_snap_threshold: default_snap_threshold(),
+        thumbnail_snap_gap: 0,     // Default: edge-to-edge only
+        thumbnail_tooltips: false, // Default: off
         thumbnail_hide_not_focused:
             crate::common::constants::defaults::behavior::HIDE_WHEN_NO_FOCUS,
+        thumbnail_do_not_disturb_classes: Vec::new(), // Default: none
         thumbnail_preserve_position_on_swap: default_preserve_thumbnail_position_on_swap(),
+        thumbnail_follow_source_monitor: false, // Default: off
+        thumbnail_position_anchor: PositionAnchor::default(), // Default: screen
+        thumbnail_managed_windows: false,       // Default: override-redirect
+        thumbnail_wm_states: ThumbnailWmStates::default(),
         client_minimize_on_switch:
             crate::common::constants::defaults::behavior::MINIMIZE_CLIENTS_ON_SWITCH,
         client_minimize_show_overlay: false, // Default: off (clean minimized look)
-        hotkey_backend: default_hotkey_backend(), // Default: X11 (secure, no permissions)
+        client_minimize_overlay_text: String::new(), // Default: localized "MINIMIZED"
+        client_minimize_overlay_text_size: 0, // Default: thumbnail text size
+        client_minimize_overlay_text_color: String::new(), // Default: thumbnail text color
+        client_minimize_overlay_dim: default_minimize_overlay_dim(),
+        client_minimize_overlay_icon: String::new(), // Default: text, no icon
+        client_workspace_follow: WorkspaceFollow::default(), // Default: window manager decides
+        hotkey_backend: default_hotkey_backend(),    // Default: X11 (secure, no permissions)
         hotkey_input_device: None, // Default: no device selected (only used by evdev backend)
         hotkey_logged_out_cycle: false, // Default: off
         hotkey_require_eve_focus:
             crate::common::constants::defaults::behavior::HOTKEY_REQUIRE_EVE_FOCUS,
         hotkey_cycle_reset_index: false,
+        hotkey_cycle_monitor: CycleMonitor::default(), // Default: all monitors
+        hotkey_switch_osd: false,
+        hotkey_skip_persist: false,
+        hotkey_skipped_characters: Vec::new(),
+        client_account_labels: Vec::new(), // Default: "Client N"
         hotkey_profile_switch: None,
-        hotkey_toggle_skip: None,     // User must configure
-        hotkey_toggle_previews: None, // User must configure
+        hotkey_toggle_skip: None,           // User must configure
+        hotkey_toggle_previews: None,       // User must configure
+        hotkey_save_positions: None,        // User must configure
+        hotkey_undo_move: None,             // User must configure
+        hotkey_active_forward: None,        // User must configure
+        hotkey_active_backward: None,       // User must configure
+        hotkey_next_group: None,            // User must configure
+        hotkey_screenshot: None,            // User must configure
+        hotkey_toggle_hotkey_overlay: None, // User must configure
+        hotkey_swap_positions: None,        // User must configure
         cycle_groups: vec![CycleGroup::default_group()],
         character_hotkeys: HashMap::new(),
         character_thumbnails: HashMap::new(),
         custom_source_thumbnails: HashMap::new(),
         custom_windows: Vec::new(),
+        companion_windows: Vec::new(),
+        x11_display: String::new(), // Default: $DISPLAY
     }]
 }
 
@@ -406,6 +880,22 @@ impl Default for GlobalSettings {
             backup_enabled: default_backup_enabled(),
             backup_interval_days: default_backup_interval_days(),
             backup_retention_count: default_backup_retention_count(),
+            autostart_daemon_only: false,
+            autostart_minimized: false,
+            log_level: default_log_level(),
+            log_to_file: false,
+            eve_detection: crate::config::EveDetection::default(),
+            language: crate::common::i18n::Language::default(),
+            window_maximized: false,
+            last_tab: String::new(),
+            tab_scroll_offsets: HashMap::new(),
+            low_power: crate::config::LowPower::default(),
+            vsync_redraws: false,
+            focus_stats_csv: false,
+            screenshot_dir: String::new(),
+            afk_snapshots: crate::config::AfkSnapshots::default(),
+            characters: crate::config::CharacterRegistry::default(),
+            timing: crate::config::Timing::default(),
         }
     }
 }
@@ -419,6 +909,19 @@ impl Profile {
         profile
     }
 
+    /// X display to connect to, or None for $DISPLAY
+    pub fn display(&self) -> Option<&str> {
+        Some(self.x11_display.trim()).filter(|d| !d.is_empty())
+    }
+
+    /// Whether focusing a window of this WM_CLASS hides all thumbnails
+    pub fn is_do_not_disturb_class(&self, class: &str) -> bool {
+        self.thumbnail_do_not_disturb_classes
+            .iter()
+            .map(|c| c.trim())
+            .any(|c| !c.is_empty() && c.eq_ignore_ascii_case(class))
+    }
+
     /// Update thumbnail position/dimensions if changed.
     /// Returns true if the configuration was modified, false otherwise.
     pub fn update_thumbnail_position(
@@ -462,6 +965,402 @@ impl Profile {
             true
         }
     }
+
+    /// Record where the client window of `name` was when its position was saved.
+    /// Returns true if that changed.
+    pub fn update_client_origin(
+        &mut self,
+        name: &str,
+        client_origin: Option<Position>,
+        is_custom: bool,
+    ) -> bool {
+        let map = if is_custom {
+            &mut self.custom_source_thumbnails
+        } else {
+            &mut self.character_thumbnails
+        };
+        match map.get_mut(name) {
+            Some(existing) if existing.client_origin != client_origin => {
+                existing.client_origin = client_origin;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Record that a character was logged in at `now` (Unix seconds), un-archiving it.
+    /// Characters new to this profile get an entry without a size (the daemon picks the
+    /// default one), so they show up as unused once they stop logging in.
+    /// Returns true if the entry was added.
+    pub fn mark_character_seen(&mut self, name: &str, now: i64) -> bool {
+        let added = !self.character_thumbnails.contains_key(name);
+        let settings = self
+            .character_thumbnails
+            .entry(name.to_string())
+            .or_insert_with(|| CharacterSettings::new(0, 0, 0, 0));
+        settings.last_seen = Some(now);
+        settings.archived = false;
+        added
+    }
+
+    /// Whether `name` was never logged in while this profile was active
+    pub fn is_new_character(&self, name: &str) -> bool {
+        self.character_thumbnails
+            .get(name)
+            .is_none_or(|settings| settings.last_seen.is_none())
+    }
+
+    /// Append the new character `name` to every group that auto-includes new characters,
+    /// binding it to the next free number key if one of them asks for that and it has no
+    /// hotkey yet. Returns true if the profile changed.
+    pub fn auto_include_character(&mut self, name: &str) -> bool {
+        let mut changed = false;
+        let mut wants_hotkey = false;
+        for group in &mut self.cycle_groups {
+            if group.auto_include_new && !group.contains(name) {
+                group.cycle_list.push(CycleSlot::Eve(name.to_string()));
+                wants_hotkey |= group.auto_include_hotkey;
+                changed = true;
+            }
+        }
+        if wants_hotkey
+            && !self.character_hotkeys.contains_key(name)
+            && let Some(binding) = self.free_number_key()
+        {
+            self.character_hotkeys.insert(name.to_string(), binding);
+        }
+        changed
+    }
+
+    /// First of the keys 1-9 and 0, without modifiers, that no hotkey of this profile uses
+    fn free_number_key(&self) -> Option<crate::config::HotkeyBinding> {
+        let singles = [
+            &self.hotkey_profile_switch,
+            &self.hotkey_toggle_skip,
+            &self.hotkey_toggle_previews,
+            &self.hotkey_save_positions,
+            &self.hotkey_undo_move,
+            &self.hotkey_active_forward,
+            &self.hotkey_active_backward,
+            &self.hotkey_next_group,
+            &self.hotkey_screenshot,
+            &self.hotkey_toggle_hotkey_overlay,
+            &self.hotkey_swap_positions,
+        ];
+        let groups = self.cycle_groups.iter().flat_map(|group| {
+            [
+                &group.hotkey_forward,
+                &group.hotkey_backward,
+                &group.hotkey_toggle_visibility,
+            ]
+        });
+        let used: Vec<&crate::config::HotkeyBinding> = singles
+            .into_iter()
+            .chain(groups)
+            .chain(self.custom_windows.iter().map(|rule| &rule.hotkey))
+            .flatten()
+            .chain(self.character_hotkeys.values())
+            .collect();
+
+        // KEY_1..KEY_9 are 2..10, KEY_0 is 11
+        (2..=11)
+            .map(|key_code| crate::config::HotkeyBinding::new(key_code, false, false, false, false))
+            .find(|candidate| {
+                !used
+                    .iter()
+                    .any(|b| b.matches(candidate.key_code, false, false, false, false))
+            })
+    }
+
+    /// Move everything known about `old` to `new`, e.g. after the character was renamed or
+    /// transferred: thumbnail position and overrides, hotkey and cycle group entries (at the
+    /// old entry's place). Then `old` is forgotten.
+    pub fn merge_character(&mut self, old: &str, new: &str) {
+        if old == new {
+            return;
+        }
+        if let Some(mut settings) = self.character_thumbnails.remove(old) {
+            if let Some(existing) = self.character_thumbnails.get(new) {
+                settings.last_seen = settings.last_seen.max(existing.last_seen);
+            }
+            self.character_thumbnails.insert(new.to_string(), settings);
+        }
+        if let Some(binding) = self.character_hotkeys.remove(old) {
+            self.character_hotkeys.insert(new.to_string(), binding);
+        }
+        for link in &mut self.companion_windows {
+            if link.character == old {
+                link.character = new.to_string();
+            }
+        }
+        self.replace_in_groups(old, new);
+    }
+
+    /// Entries of one character whose names differ only in case or whitespace (title parsing
+    /// quirks), as the entry to keep and the duplicates to combine into it. The most recently
+    /// seen entry is kept.
+    pub fn duplicate_characters(&self) -> Vec<(String, Vec<String>)> {
+        let mut by_key: HashMap<String, Vec<&String>> = HashMap::new();
+        for name in self.character_thumbnails.keys() {
+            by_key
+                .entry(collapse_whitespace(name).to_lowercase())
+                .or_default()
+                .push(name);
+        }
+
+        let mut duplicates: Vec<(String, Vec<String>)> = by_key
+            .into_values()
+            .filter(|names| names.len() > 1)
+            .map(|mut names| {
+                // Newest first; names without stray whitespace win ties
+                names.sort_by_key(|name| {
+                    (
+                        std::cmp::Reverse(self.character_thumbnails[*name].last_seen),
+                        collapse_whitespace(name) != **name,
+                        (*name).clone(),
+                    )
+                });
+                let keep = names[0].clone();
+                let rest = names[1..].iter().map(|name| (*name).clone()).collect();
+                (keep, rest)
+            })
+            .collect();
+        duplicates.sort();
+        duplicates
+    }
+
+    /// Combine the duplicate entry `duplicate` into `keep`. `keep` keeps its own settings,
+    /// hotkey and cycle group places and takes over those only `duplicate` has; then
+    /// `duplicate` is forgotten. Returns what was taken over.
+    pub fn combine_characters(&mut self, keep: &str, duplicate: &str) -> Vec<&'static str> {
+        let mut taken = Vec::new();
+        if keep == duplicate {
+            return taken;
+        }
+        if let Some(settings) = self.character_thumbnails.remove(duplicate) {
+            taken = self
+                .character_thumbnails
+                .entry(keep.to_string())
+                .or_insert_with(|| settings.clone())
+                .fill_from(settings);
+        }
+        if let Some(binding) = self.character_hotkeys.remove(duplicate)
+            && !self.character_hotkeys.contains_key(keep)
+        {
+            self.character_hotkeys.insert(keep.to_string(), binding);
+            taken.push("hotkey");
+        }
+        let duplicate_slot = CycleSlot::Eve(duplicate.to_string());
+        let keep_slot = CycleSlot::Eve(keep.to_string());
+        let mut joined = false;
+        for group in &mut self.cycle_groups {
+            if group.cycle_list.contains(&keep_slot) {
+                group.cycle_list.retain(|slot| *slot != duplicate_slot);
+                continue;
+            }
+            for slot in &mut group.cycle_list {
+                if *slot == duplicate_slot {
+                    *slot = keep_slot.clone();
+                    joined = true;
+                }
+            }
+        }
+        if joined {
+            taken.push("cycle groups");
+        }
+        taken
+    }
+
+    /// Put `new` at the place of `old` in every cycle group listing `old`, dropping any
+    /// other entry of `new` there
+    fn replace_in_groups(&mut self, old: &str, new: &str) {
+        let old_slot = CycleSlot::Eve(old.to_string());
+        let new_slot = CycleSlot::Eve(new.to_string());
+        for group in &mut self.cycle_groups {
+            if !group.cycle_list.contains(&old_slot) {
+                continue;
+            }
+            group.cycle_list.retain(|slot| *slot != new_slot);
+            for slot in &mut group.cycle_list {
+                if *slot == old_slot {
+                    *slot = new_slot.clone();
+                }
+            }
+        }
+    }
+
+    /// Forget a character: its thumbnail settings, hotkey and cycle group entries
+    pub fn remove_character(&mut self, name: &str) {
+        self.character_thumbnails.remove(name);
+        self.character_hotkeys.remove(name);
+        self.companion_windows
+            .retain(|link| link.character != name && link.source != name);
+        for group in &mut self.cycle_groups {
+            group.cycle_list.retain(|slot| match slot {
+                CycleSlot::Eve(n) | CycleSlot::Source(n) => n != name,
+                CycleSlot::Group(_) => true,
+            });
+        }
+    }
+
+    /// Revert thumbnails to `saved` (the profile on disk), keeping `last_seen` and
+    /// `archived` from this profile so they survive when positions are not auto-saved.
+    pub fn restore_saved_thumbnails(&mut self, saved: &Profile) {
+        let mut character_thumbnails = saved.character_thumbnails.clone();
+        for (name, settings) in &mut character_thumbnails {
+            if let Some(current) = self.character_thumbnails.get(name) {
+                settings.last_seen = current.last_seen;
+                settings.archived = current.archived;
+            }
+        }
+        self.character_thumbnails = character_thumbnails;
+        self.custom_source_thumbnails = saved.custom_source_thumbnails.clone();
+    }
+
+    /// Why `alias` can't be the display name of the custom source rule at `idx`, if it can't
+    pub fn custom_alias_conflict(&self, idx: usize, alias: &str) -> Option<String> {
+        if alias.trim().is_empty() {
+            return Some("Display name can't be empty".to_string());
+        }
+        self.custom_windows
+            .iter()
+            .enumerate()
+            .any(|(i, rule)| i != idx && rule.alias == alias)
+            .then(|| format!("Another source is already named '{}'", alias))
+    }
+
+    /// Carry a custom source's saved thumbnails and cycle slots over to a new rule alias.
+    ///
+    /// Instance labels keep their number ("Discord #2" becomes "Voice #2"). Entries already
+    /// saved under the new alias win over the moved ones.
+    /// Returns true if the profile was modified.
+    pub fn rename_custom_source(&mut self, old_alias: &str, new_alias: &str) -> bool {
+        if old_alias == new_alias || old_alias.is_empty() || new_alias.is_empty() {
+            return false;
+        }
+        let relabel = |label: &str| -> Option<String> {
+            if label == old_alias {
+                return Some(new_alias.to_string());
+            }
+            match split_instance_label(label) {
+                (alias, index) if alias == old_alias && index > 1 => {
+                    Some(format!("{}{}{}", new_alias, INSTANCE_SEPARATOR, index))
+                }
+                _ => None,
+            }
+        };
+
+        let mut changed = false;
+        let moved: Vec<(String, String)> = self
+            .custom_source_thumbnails
+            .keys()
+            .filter_map(|label| relabel(label).map(|new| (label.clone(), new)))
+            .collect();
+        for (old, new) in moved {
+            if let Some(settings) = self.custom_source_thumbnails.remove(&old) {
+                self.custom_source_thumbnails.entry(new).or_insert(settings);
+                changed = true;
+            }
+        }
+
+        for group in &mut self.cycle_groups {
+            for slot in &mut group.cycle_list {
+                if let CycleSlot::Source(label) = slot
+                    && let Some(new) = relabel(label)
+                {
+                    *label = new;
+                    changed = true;
+                }
+            }
+        }
+
+        for link in &mut self.companion_windows {
+            if let Some(new) = relabel(&link.source) {
+                link.source = new;
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /// Custom sources linked to come up with `character`
+    pub fn companions_of<'a>(
+        &'a self,
+        character: &'a str,
+    ) -> impl Iterator<Item = &'a CompanionLink> {
+        self.companion_windows
+            .iter()
+            .filter(move |link| link.character == character)
+    }
+
+    /// Give thumbnails saved as monitor percentages their position and size on `monitors`
+    pub fn apply_monitor_percent(&mut self, monitors: &[MonitorScale]) {
+        apply_monitor_percent(
+            self.thumbnail_position_anchor,
+            self.character_thumbnails
+                .values_mut()
+                .chain(self.custom_source_thumbnails.values_mut()),
+            monitors,
+        );
+    }
+
+    /// Re-measure the monitor percentages of thumbnails moved or resized since they were
+    /// applied. Returns true if the profile was modified.
+    pub fn sync_monitor_percent(&mut self, monitors: &[MonitorScale]) -> bool {
+        let mut changed = false;
+        if self.thumbnail_position_anchor == PositionAnchor::Client {
+            return changed;
+        }
+        for settings in self
+            .character_thumbnails
+            .values_mut()
+            .chain(self.custom_source_thumbnails.values_mut())
+        {
+            changed |= settings.sync_monitor_percent(monitors);
+        }
+        changed
+    }
+
+    /// Merge thumbnail positions tracked at runtime into this profile.
+    ///
+    /// Existing entries only have their position/dimensions updated so per-character settings
+    /// edited elsewhere are preserved; entries missing from the profile are added as-is.
+    /// Entries not present at runtime are left untouched.
+    /// Returns true if the profile was modified.
+    pub fn merge_thumbnail_positions(
+        &mut self,
+        character_thumbnails: &HashMap<String, CharacterSettings>,
+        custom_source_thumbnails: &HashMap<String, CharacterSettings>,
+    ) -> bool {
+        let mut changed = false;
+        for (is_custom, runtime) in [
+            (false, character_thumbnails),
+            (true, custom_source_thumbnails),
+        ] {
+            for (name, settings) in runtime {
+                let map = if is_custom {
+                    &mut self.custom_source_thumbnails
+                } else {
+                    &mut self.character_thumbnails
+                };
+                if !map.contains_key(name) {
+                    map.insert(name.clone(), settings.clone());
+                    changed = true;
+                    continue;
+                }
+                changed |= self.update_thumbnail_position(
+                    name,
+                    settings.x,
+                    settings.y,
+                    settings.dimensions.width,
+                    settings.dimensions.height,
+                    is_custom,
+                );
+                changed |= self.update_client_origin(name, settings.client_origin, is_custom);
+            }
+        }
+        changed
+    }
 }
 
 impl Default for Profile {
@@ -470,8 +1369,15 @@ impl Default for Profile {
     }
 }
 
+/// Config file given on the command line (`--config`), used instead of the default location
+static PATH_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
 impl Config {
     pub fn path() -> PathBuf {
+        if let Some(path) = Self::path_override() {
+            return path.to_path_buf();
+        }
+
         // Allow overriding config directory via env var (for testing isolation)
         if let Ok(dir) = std::env::var("EVE_PREVIEW_MANAGER_CONFIG_DIR") {
             let mut path = PathBuf::from(dir);
@@ -489,9 +1395,22 @@ impl Config {
         path
     }
 
+    /// Use `path` as the config file for the rest of the process. Only the first call has
+    /// an effect.
+    pub fn set_path_override(path: PathBuf) {
+        let _ = PATH_OVERRIDE.set(path);
+    }
+
+    /// Config file given on the command line, if any
+    pub fn path_override() -> Option<&'static Path> {
+        PATH_OVERRIDE.get().map(PathBuf::as_path)
+    }
+
     /// Load configuration from JSON file or create default
     pub fn load() -> Result<Self> {
-        Self::load_from(&Self::path())
+        let config = Self::load_from(&Self::path())?;
+        crate::common::crash_report::record_config(&config);
+        Ok(config)
     }
 
     /// Load configuration from a specific path
@@ -509,9 +1428,18 @@ impl Config {
         let contents = fs::read_to_string(config_path)
             .with_context(|| format!("Failed to read config from {:?}", config_path))?;
 
-        let config: Config = serde_json::from_str(&contents)
+        let mut config: Config = serde_json::from_str(&contents)
             .with_context(|| format!("Failed to parse JSON from {:?}", config_path))?;
 
+        // Aliases and notes used to be stored per profile
+        let migrated = config.global.characters.absorb(&mut config.profiles);
+        if migrated > 0 {
+            info!(
+                characters = migrated,
+                "Moved character aliases and notes into the global registry"
+            );
+        }
+
         info!(path = ?config_path, profile_count = config.profiles.len(), "Loaded config");
         Ok(config)
     }
@@ -528,12 +1456,114 @@ impl Config {
             .find(|p| p.profile_name == self.global.selected_profile)
     }
 
+    /// Make the profile named `name` the active one
+    pub fn select_profile(&mut self, name: &str) -> Result<()> {
+        if !self.profiles.iter().any(|p| p.profile_name == name) {
+            let available: Vec<&str> = self
+                .profiles
+                .iter()
+                .map(|p| p.profile_name.as_str())
+                .collect();
+            return Err(anyhow::anyhow!(
+                "Profile '{}' not found (available: {})",
+                name,
+                available.join(", ")
+            ));
+        }
+        self.global.selected_profile = name.to_string();
+        Ok(())
+    }
+
+    /// Check a name for a new profile, or for renaming the profile at `renaming`
+    pub fn validate_profile_name(&self, name: &str, renaming: Option<usize>) -> Result<()> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(anyhow::anyhow!("Profile name cannot be empty"));
+        }
+        let taken = self
+            .profiles
+            .iter()
+            .enumerate()
+            .any(|(idx, p)| Some(idx) != renaming && p.profile_name == name);
+        if taken {
+            return Err(anyhow::anyhow!("A profile named '{}' already exists", name));
+        }
+        Ok(())
+    }
+
+    /// First free name of the form "Name (copy)", "Name (copy 2)", ...
+    pub fn unique_copy_name(&self, name: &str) -> String {
+        (1..)
+            .map(|n| match n {
+                1 => format!("{} (copy)", name),
+                n => format!("{} (copy {})", name, n),
+            })
+            .find(|candidate| self.validate_profile_name(candidate, None).is_ok())
+            .expect("unbounded candidates")
+    }
+
+    /// Deep-copy the profile at `source_idx` under a new name, appended to the list.
+    /// Returns the index of the copy.
+    pub fn duplicate_profile(
+        &mut self,
+        source_idx: usize,
+        name: &str,
+        description: &str,
+    ) -> Result<usize> {
+        self.validate_profile_name(name, None)?;
+        let mut copy = self
+            .profiles
+            .get(source_idx)
+            .context("Profile to duplicate no longer exists")?
+            .clone();
+        copy.profile_name = name.trim().to_string();
+        copy.profile_description = description.to_string();
+        self.profiles.push(copy);
+        Ok(self.profiles.len() - 1)
+    }
+
+    /// Rename the profile at `idx`, keeping `selected_profile` pointing at it if it is active
+    pub fn rename_profile(&mut self, idx: usize, name: &str, description: &str) -> Result<()> {
+        self.validate_profile_name(name, Some(idx))?;
+        let profile = self
+            .profiles
+            .get_mut(idx)
+            .context("Profile to rename no longer exists")?;
+        if self.global.selected_profile == profile.profile_name {
+            self.global.selected_profile = name.trim().to_string();
+        }
+        profile.profile_name = name.trim().to_string();
+        profile.profile_description = description.to_string();
+        Ok(())
+    }
+
+    /// Move the profile at `from` to position `to`.
+    /// Returns false if either index is out of range.
+    pub fn move_profile(&mut self, from: usize, to: usize) -> bool {
+        if from >= self.profiles.len() || to >= self.profiles.len() {
+            return false;
+        }
+        let profile = self.profiles.remove(from);
+        self.profiles.insert(to, profile);
+        true
+    }
+
+    /// Index of the active profile, 0 if `selected_profile` names no profile
+    pub fn active_profile_idx(&self) -> usize {
+        self.profiles
+            .iter()
+            .position(|p| p.profile_name == self.global.selected_profile)
+            .unwrap_or(0)
+    }
+
     /// Save configuration to JSON file.
     ///
     /// Writes the current in-memory state directly to config.json.
     /// The Manager maintains authoritative state via IPC synchronization.
     pub fn save(&self) -> Result<()> {
-        self.save_to(&Self::path())
+        self.save_to(&Self::path())?;
+        crate::common::crash_report::record_config(self);
+        Ok(())
     }
 
     /// Save configuration to a specific path
@@ -547,12 +1577,56 @@ impl Config {
         let json_string =
             serde_json::to_string_pretty(self).context("Failed to serialize config to JSON")?;
 
-        fs::write(config_path, json_string)
-            .with_context(|| format!("Failed to write config to {:?}", config_path))?;
+        // Written next to the file and renamed over it, so a reader (or a crash) never sees a
+        // half-written config
+        let mut temp_name = config_path.file_name().unwrap_or_default().to_os_string();
+        temp_name.push(".tmp");
+        let temp_path = config_path.with_file_name(temp_name);
+        fs::write(&temp_path, json_string)
+            .with_context(|| format!("Failed to write config to {:?}", temp_path))?;
+        fs::rename(&temp_path, config_path)
+            .with_context(|| format!("Failed to replace config {:?}", config_path))?;
 
         info!(path = ?config_path, "Saved config");
         Ok(())
     }
+
+    /// Merge runtime thumbnail positions into `profile_name` of the config file at
+    /// `config_path`, saving it only if that changed anything.
+    ///
+    /// Returns `Ok(false)` if nothing needed saving or the profile is not on disk.
+    pub fn save_thumbnail_positions(
+        config_path: &Path,
+        profile_name: &str,
+        character_thumbnails: &HashMap<String, CharacterSettings>,
+        custom_source_thumbnails: &HashMap<String, CharacterSettings>,
+        monitors: &[MonitorScale],
+    ) -> Result<bool> {
+        let mut config =
+            Self::load_from(config_path).context("Failed to load config for position save")?;
+        let Some(profile) = config
+            .profiles
+            .iter_mut()
+            .find(|p| p.profile_name == profile_name)
+        else {
+            warn!(profile = %profile_name, "Active profile not found on disk, skipping position save");
+            return Ok(false);
+        };
+
+        let merged =
+            profile.merge_thumbnail_positions(character_thumbnails, custom_source_thumbnails);
+        let remeasured = profile.sync_monitor_percent(monitors);
+        if !merged && !remeasured {
+            debug!(profile = %profile_name, "Thumbnail positions already up to date");
+            return Ok(false);
+        }
+
+        config
+            .save_to(config_path)
+            .context("Failed to save thumbnail positions")?;
+        info!(profile = %profile_name, "Saved thumbnail positions");
+        Ok(true)
+    }
 }
 
 impl Default for Config {
@@ -564,10 +1638,41 @@ impl Default for Config {
     }
 }
 
+/// `name` with surrounding whitespace removed and inner runs of it collapsed to one space
+fn collapse_whitespace(name: &str) -> String {
+    name.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_resolve_nested_cycle_groups() {
+        let group = |name: &str, cycle_list: Vec<CycleSlot>| CycleGroup {
+            name: name.to_string(),
+            cycle_list,
+            ..CycleGroup::default_group()
+        };
+        let eve = |name: &str| CycleSlot::Eve(name.to_string());
+        let nested = |name: &str| CycleSlot::Group(name.to_string());
+        let groups = vec![
+            group("DPS", vec![eve("A"), eve("B")]),
+            group("Logi", vec![eve("B"), eve("C"), nested("All")]),
+            group(
+                "All",
+                vec![nested("DPS"), nested("Logi"), eve("A"), nested("Gone")],
+            ),
+        ];
+
+        let resolved = CycleGroup::resolve_nested(&groups);
+        assert_eq!(resolved[0].cycle_list, vec![eve("A"), eve("B")]);
+        // "All" refers back to "Logi" and to itself, which add nothing new
+        assert_eq!(resolved[1].cycle_list, vec![eve("B"), eve("C"), eve("A")]);
+        assert_eq!(resolved[2].cycle_list, vec![eve("A"), eve("B"), eve("C")]);
+        assert!(resolved[2].contains("C"));
+    }
+
     #[test]
     fn test_profile_default_with_name() {
         let profile =
@@ -587,6 +1692,298 @@ mod tests {
         assert!(profile.custom_source_thumbnails.is_empty());
     }
 
+    #[test]
+    fn test_profile_display() {
+        let mut profile = Profile::default_with_name("Test".to_string(), String::new());
+        assert_eq!(profile.display(), None);
+
+        profile.x11_display = "  ".to_string();
+        assert_eq!(profile.display(), None);
+
+        profile.x11_display = " :1 ".to_string();
+        assert_eq!(profile.display(), Some(":1"));
+    }
+
+    #[test]
+    fn test_custom_rule_matches() {
+        let mut rule: CustomWindowRule = serde_json::from_str(
+            r#"{"class_pattern": "firefox", "alias": "Browser", "regex": true}"#,
+        )
+        .unwrap();
+        assert!(rule.matches("Mozilla Firefox", "firefox", &[]));
+        assert!(rule.matches("Picture-in-Picture", "Firefox", &[]));
+        assert!(!rule.matches("Discord", "discord", &[]));
+
+        rule.title_exclude_pattern = Some("^picture-in-picture$".to_string());
+        assert!(rule.matches("Mozilla Firefox", "firefox", &[]));
+        assert!(!rule.matches("Picture-in-Picture", "firefox", &[]));
+
+        // Exclude patterns alone never select windows
+        rule.class_pattern = Some(String::new());
+        assert!(!rule.matches("Mozilla Firefox", "firefox", &[]));
+    }
+
+    #[test]
+    fn test_legacy_custom_rule_matches_text() {
+        // Rules from before regex support match their patterns as plain text
+        let rule: CustomWindowRule =
+            serde_json::from_str(r#"{"title_pattern": "[beta] (x86)", "alias": "App"}"#).unwrap();
+        assert!(!rule.regex);
+        assert!(rule.matches("Some App [Beta] (x86)", "app", &[]));
+        assert!(!rule.matches("Some App b (x86)", "app", &[]));
+    }
+
+    #[test]
+    fn test_custom_rule_matches_process() {
+        let rule: CustomWindowRule = serde_json::from_str(
+            r#"{"process_pattern": "^pyfa\\.exe$", "alias": "Pyfa", "regex": true}"#,
+        )
+        .unwrap();
+        assert!(rule.matches_process());
+        let wine = ["pyfa.exe".to_string(), "wine64-preloader".to_string()];
+        assert!(rule.matches("Pyfa", "explorer.exe", &wine));
+        assert!(!rule.matches("Pyfa", "explorer.exe", &["wine64-preloader".to_string()]));
+        assert!(!rule.matches("Pyfa", "explorer.exe", &[]));
+    }
+
+    #[test]
+    fn test_custom_rule_instance_labels() {
+        let rule: CustomWindowRule =
+            serde_json::from_str(r#"{"class_pattern": "discord", "alias": "Discord"}"#).unwrap();
+        assert_eq!(rule.instance_label(1), "Discord");
+        assert_eq!(rule.instance_label(2), "Discord #2");
+        assert!(rule.owns_label("Discord"));
+        assert!(rule.owns_label("Discord #3"));
+        assert!(!rule.owns_label("Discord #x"));
+        assert!(!rule.owns_label("Discord Canary"));
+
+        assert_eq!(split_instance_label("Discord #2"), ("Discord", 2));
+        assert_eq!(split_instance_label("Discord"), ("Discord", 1));
+        // "#1" is never generated, so it is part of the alias
+        assert_eq!(split_instance_label("Team #1"), ("Team #1", 1));
+    }
+
+    #[test]
+    fn test_rename_custom_source_moves_positions() {
+        let mut profile = Profile::default_with_name("Test".to_string(), String::new());
+        profile.custom_source_thumbnails.insert(
+            "Discord".to_string(),
+            CharacterSettings::new(10, 20, 300, 200),
+        );
+        profile.custom_source_thumbnails.insert(
+            "Discord #2".to_string(),
+            CharacterSettings::new(30, 40, 300, 200),
+        );
+        profile.custom_source_thumbnails.insert(
+            "Discord Canary".to_string(),
+            CharacterSettings::new(50, 60, 300, 200),
+        );
+        profile.cycle_groups[0].cycle_list = vec![
+            CycleSlot::Eve("Discord".to_string()),
+            CycleSlot::Source("Discord #2".to_string()),
+        ];
+
+        assert!(profile.rename_custom_source("Discord", "Voice"));
+        assert_eq!(profile.custom_source_thumbnails["Voice"].x, 10);
+        assert_eq!(profile.custom_source_thumbnails["Voice #2"].x, 30);
+        assert!(
+            profile
+                .custom_source_thumbnails
+                .contains_key("Discord Canary")
+        );
+        assert!(!profile.custom_source_thumbnails.contains_key("Discord"));
+        // EVE characters sharing the name are left alone
+        assert_eq!(
+            profile.cycle_groups[0].cycle_list,
+            [
+                CycleSlot::Eve("Discord".to_string()),
+                CycleSlot::Source("Voice #2".to_string()),
+            ]
+        );
+
+        assert!(!profile.rename_custom_source("Voice", "Voice"));
+    }
+
+    #[test]
+    fn test_custom_alias_conflict() {
+        let mut profile = Profile::default_with_name("Test".to_string(), String::new());
+        for alias in ["Discord", "Browser"] {
+            let rule: CustomWindowRule =
+                serde_json::from_str(&format!(r#"{{"alias": "{}"}}"#, alias)).unwrap();
+            profile.custom_windows.push(rule);
+        }
+
+        assert!(profile.custom_alias_conflict(0, "Voice").is_none());
+        // Keeping its own name is fine, taking another rule's is not
+        assert!(profile.custom_alias_conflict(0, "Discord").is_none());
+        assert!(profile.custom_alias_conflict(0, "Browser").is_some());
+        assert!(profile.custom_alias_conflict(0, "  ").is_some());
+    }
+
+    #[test]
+    fn test_restore_saved_thumbnails_keeps_bookkeeping() {
+        let mut saved = Profile::default_with_name("Test".to_string(), String::new());
+        saved.character_thumbnails.insert(
+            "Alice".to_string(),
+            CharacterSettings::new(10, 20, 300, 200),
+        );
+
+        let mut profile = saved.clone();
+        profile.update_thumbnail_position("Alice", 99, 99, 300, 200, false);
+        assert!(!profile.mark_character_seen("Alice", 1_700_000_000));
+        assert!(profile.mark_character_seen("Nobody", 1_700_000_000));
+        assert_eq!(
+            profile.character_thumbnails["Nobody"].last_seen,
+            Some(1_700_000_000)
+        );
+        profile.restore_saved_thumbnails(&saved);
+
+        let alice = &profile.character_thumbnails["Alice"];
+        assert_eq!((alice.x, alice.y), (10, 20));
+        assert_eq!(alice.last_seen, Some(1_700_000_000));
+    }
+
+    #[test]
+    fn test_remove_character() {
+        let mut profile = Profile::default_with_name("Test".to_string(), String::new());
+        profile
+            .character_thumbnails
+            .insert("Alice".to_string(), CharacterSettings::new(0, 0, 0, 0));
+        profile.character_hotkeys.insert(
+            "Alice".to_string(),
+            crate::config::HotkeyBinding::new(59, false, false, false, false),
+        );
+        profile.cycle_groups[0].cycle_list = vec![
+            CycleSlot::Eve("Alice".to_string()),
+            CycleSlot::Eve("Bob".to_string()),
+        ];
+
+        profile.remove_character("Alice");
+        assert!(profile.character_thumbnails.is_empty());
+        assert!(profile.character_hotkeys.is_empty());
+        assert_eq!(
+            profile.cycle_groups[0].cycle_list,
+            [CycleSlot::Eve("Bob".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_merge_character() {
+        let mut profile = Profile::default_with_name("Test".to_string(), String::new());
+        let mut old = CharacterSettings::new(10, 20, 300, 200);
+        old.alias = Some("Scout".to_string());
+        old.last_seen = Some(100);
+        profile
+            .character_thumbnails
+            .insert("Old Name".to_string(), old);
+        let mut new = CharacterSettings::new(0, 0, 0, 0);
+        new.last_seen = Some(200);
+        profile
+            .character_thumbnails
+            .insert("New Name".to_string(), new);
+        profile.character_hotkeys.insert(
+            "Old Name".to_string(),
+            crate::config::HotkeyBinding::new(59, false, false, false, false),
+        );
+        profile.cycle_groups[0].cycle_list = vec![
+            CycleSlot::Eve("Alice".to_string()),
+            CycleSlot::Eve("Old Name".to_string()),
+            CycleSlot::Eve("New Name".to_string()),
+        ];
+
+        profile.merge_character("Old Name", "New Name");
+        assert_eq!(profile.character_thumbnails.len(), 1);
+        let merged = &profile.character_thumbnails["New Name"];
+        assert_eq!((merged.x, merged.y), (10, 20));
+        assert_eq!(merged.alias.as_deref(), Some("Scout"));
+        assert_eq!(merged.last_seen, Some(200));
+        assert_eq!(profile.character_hotkeys["New Name"].key_code, 59);
+        assert!(!profile.character_hotkeys.contains_key("Old Name"));
+        assert_eq!(
+            profile.cycle_groups[0].cycle_list,
+            [
+                CycleSlot::Eve("Alice".to_string()),
+                CycleSlot::Eve("New Name".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_duplicate_characters_are_combined() {
+        let mut profile = Profile::default_with_name("Test".to_string(), String::new());
+        let mut configured = CharacterSettings::new(10, 20, 300, 200);
+        configured.alias = Some("Scout".to_string());
+        configured.last_seen = Some(100);
+        profile
+            .character_thumbnails
+            .insert("alice  smith".to_string(), configured);
+        let mut current = CharacterSettings::new(0, 0, 0, 0);
+        current.last_seen = Some(200);
+        profile
+            .character_thumbnails
+            .insert("Alice Smith".to_string(), current);
+        profile
+            .character_thumbnails
+            .insert("Bob".to_string(), CharacterSettings::new(0, 0, 0, 0));
+        profile.character_hotkeys.insert(
+            "alice  smith".to_string(),
+            crate::config::HotkeyBinding::new(59, false, false, false, false),
+        );
+        profile.cycle_groups[0].cycle_list = vec![
+            CycleSlot::Eve("alice  smith".to_string()),
+            CycleSlot::Eve("Bob".to_string()),
+        ];
+
+        let duplicates = profile.duplicate_characters();
+        assert_eq!(
+            duplicates,
+            [("Alice Smith".to_string(), vec!["alice  smith".to_string()])]
+        );
+
+        let taken = profile.combine_characters("Alice Smith", "alice  smith");
+        assert_eq!(taken, ["alias", "hotkey", "cycle groups"]);
+        let kept = &profile.character_thumbnails["Alice Smith"];
+        assert_eq!((kept.x, kept.y), (0, 0));
+        assert_eq!(kept.alias.as_deref(), Some("Scout"));
+        assert!(!profile.character_thumbnails.contains_key("alice  smith"));
+        assert_eq!(profile.character_hotkeys["Alice Smith"].key_code, 59);
+        assert_eq!(
+            profile.cycle_groups[0].cycle_list,
+            [
+                CycleSlot::Eve("Alice Smith".to_string()),
+                CycleSlot::Eve("Bob".to_string()),
+            ]
+        );
+        assert!(profile.duplicate_characters().is_empty());
+    }
+
+    #[test]
+    fn test_auto_include_character() {
+        let mut profile = Profile::default_with_name("Test".to_string(), String::new());
+        assert!(profile.is_new_character("Alice"));
+        assert!(!profile.auto_include_character("Alice"));
+
+        profile.cycle_groups[0].auto_include_new = true;
+        profile.cycle_groups[0].auto_include_hotkey = true;
+        // Key 1 is taken, so Alice gets 2
+        profile.hotkey_toggle_skip = Some(crate::config::HotkeyBinding::new(
+            2, false, false, false, false,
+        ));
+        assert!(profile.auto_include_character("Alice"));
+        assert_eq!(
+            profile.cycle_groups[0].cycle_list,
+            [CycleSlot::Eve("Alice".to_string())]
+        );
+        assert_eq!(profile.character_hotkeys["Alice"].key_code, 3);
+
+        // Already a member: nothing changes
+        assert!(!profile.auto_include_character("Alice"));
+
+        assert!(profile.is_new_character("Alice"));
+        profile.mark_character_seen("Alice", 1);
+        assert!(!profile.is_new_character("Alice"));
+    }
+
     #[test]
     fn test_config_default() {
         let config = Config::default();
@@ -606,6 +2003,51 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_profile_duplicate_rename_and_move() {
+        let mut config = Config::default();
+        let original = config.profiles[0].profile_name.clone();
+        config.profiles[0]
+            .character_thumbnails
+            .insert("Alice".to_string(), CharacterSettings::new(1, 2, 3, 4));
+
+        let copy_name = config.unique_copy_name(&original);
+        assert_eq!(copy_name, format!("{} (copy)", original));
+        let copy_idx = config.duplicate_profile(0, &copy_name, "").unwrap();
+        assert_eq!(config.profiles[copy_idx].character_thumbnails.len(), 1);
+        assert_eq!(
+            config.unique_copy_name(&original),
+            format!("{} (copy 2)", original)
+        );
+        assert!(config.duplicate_profile(0, &original, "").is_err());
+        assert!(config.duplicate_profile(0, "  ", "").is_err());
+
+        // Renaming the active profile keeps it active
+        assert!(config.rename_profile(0, &copy_name, "").is_err());
+        config.rename_profile(0, " PvP ", "Roams").unwrap();
+        assert_eq!(config.global.selected_profile, "PvP");
+        assert_eq!(config.profiles[0].profile_description, "Roams");
+
+        assert!(config.move_profile(0, 1));
+        assert_eq!(config.active_profile_idx(), 1);
+        assert!(!config.move_profile(0, 5));
+    }
+
+    #[test]
+    fn test_select_profile() {
+        let mut config = Config::default();
+        config
+            .profiles
+            .push(Profile::default_with_name("PvP".to_string(), String::new()));
+
+        config.select_profile("PvP").unwrap();
+        assert_eq!(config.active_profile_idx(), 1);
+
+        let err = config.select_profile("Mining").unwrap_err().to_string();
+        assert!(err.contains("PvP"));
+        assert_eq!(config.global.selected_profile, "PvP");
+    }
+
     #[test]
     fn test_profile_serialization() {
         let mut profile = Profile::default_with_name("Test".to_string(), String::new());
@@ -665,6 +2107,8 @@ mod tests {
             settings.backup_retention_count,
             crate::common::constants::config::backup::RETENTION_COUNT
         );
+        assert!(!settings.autostart_daemon_only);
+        assert!(!settings.autostart_minimized);
     }
 
     #[test]
@@ -697,6 +2141,9 @@ mod tests {
             profile.thumbnail_hide_not_focused,
             crate::common::constants::defaults::behavior::HIDE_WHEN_NO_FOCUS
         );
+        // Thumbnails keep every window state unless a profile turns one off
+        assert_eq!(profile.thumbnail_wm_states, ThumbnailWmStates::default());
+        assert!(profile.thumbnail_wm_states.above && profile.thumbnail_wm_states.skip_pager);
     }
 
     #[test]
@@ -807,6 +2254,54 @@ mod tests {
         // Load from isolated path
         let loaded = Config::load_from(&config_path).expect("Failed to load config from temp path");
         assert_eq!(loaded.global.selected_profile, "filesystem_test");
+
+        // Saves replace the file in one step and leave no temporary file behind
+        config.save_to(&config_path).unwrap();
+        let files: Vec<_> = std::fs::read_dir(temp_dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(files, ["config.json"]);
+    }
+
+    #[test]
+    fn test_save_thumbnail_positions_merges_into_file() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let config_path = temp_dir.path().join("config.json");
+        let mut config = Config::default();
+        let profile_name = config.profiles[0].profile_name.clone();
+        config.profiles[0].thumbnail_default_width = 123;
+        config.save_to(&config_path).unwrap();
+
+        let mut characters = HashMap::from([(
+            "Alice".to_string(),
+            CharacterSettings::new(10, 20, 300, 200),
+        )]);
+        let save = |characters: &HashMap<String, CharacterSettings>| {
+            Config::save_thumbnail_positions(
+                &config_path,
+                &profile_name,
+                characters,
+                &HashMap::new(),
+                &[],
+            )
+            .unwrap()
+        };
+        assert!(save(&characters));
+        // Nothing new to merge, so the file is left alone
+        assert!(!save(&characters));
+        // The client's corner is saved along with the position
+        characters.get_mut("Alice").unwrap().client_origin = Some(Position::new(0, 5));
+        assert!(save(&characters));
+
+        let loaded = Config::load_from(&config_path).unwrap();
+        assert_eq!(loaded.profiles[0].character_thumbnails["Alice"].x, 10);
+        assert_eq!(
+            loaded.profiles[0].character_thumbnails["Alice"].client_origin,
+            Some(Position::new(0, 5))
+        );
+        // Everything else on disk is kept
+        assert_eq!(loaded.profiles[0].thumbnail_default_width, 123);
     }
 
     #[test]
@@ -825,4 +2320,74 @@ mod tests {
             crate::common::constants::defaults::behavior::PROFILE_NAME
         );
     }
+
+    #[test]
+    fn test_merge_thumbnail_positions() {
+        let mut profile = Profile::default_with_name("Merge".to_string(), String::new());
+        let mut existing = CharacterSettings::new(0, 0, 100, 50);
+        existing.alias = Some("Main".to_string());
+        profile
+            .character_thumbnails
+            .insert("Alice".to_string(), existing);
+        profile
+            .character_thumbnails
+            .insert("Bob".to_string(), CharacterSettings::new(5, 5, 100, 50));
+
+        let mut runtime = HashMap::new();
+        runtime.insert(
+            "Alice".to_string(),
+            CharacterSettings::new(40, 60, 200, 100),
+        );
+        runtime.insert("Carol".to_string(), CharacterSettings::new(1, 2, 100, 50));
+
+        assert!(profile.merge_thumbnail_positions(&runtime, &HashMap::new()));
+
+        let alice = &profile.character_thumbnails["Alice"];
+        assert_eq!((alice.x, alice.y), (40, 60));
+        assert_eq!(alice.dimensions.width, 200);
+        // Per-character settings are kept
+        assert_eq!(alice.alias.as_deref(), Some("Main"));
+        // Entries only on disk are kept, new ones are added
+        assert_eq!(profile.character_thumbnails["Bob"].x, 5);
+        assert!(profile.character_thumbnails.contains_key("Carol"));
+
+        // Merging the same state again is a no-op
+        assert!(!profile.merge_thumbnail_positions(&runtime, &HashMap::new()));
+    }
+
+    #[test]
+    fn test_is_do_not_disturb_class() {
+        let mut profile = Profile::default_with_name("DND".to_string(), String::new());
+        assert!(!profile.is_do_not_disturb_class("obs"));
+
+        profile.thumbnail_do_not_disturb_classes =
+            vec!["OBS".to_string(), String::new(), " mpv ".to_string()];
+        assert!(profile.is_do_not_disturb_class("obs"));
+        assert!(profile.is_do_not_disturb_class("mpv"));
+        assert!(!profile.is_do_not_disturb_class(""));
+        assert!(!profile.is_do_not_disturb_class("firefox"));
+    }
+
+    #[test]
+    fn test_companion_links_follow_renames() {
+        let mut profile = Profile::default_with_name("Links".to_string(), String::new());
+        let link = |character: &str, source: &str| CompanionLink {
+            character: character.to_string(),
+            source: source.to_string(),
+            position: None,
+        };
+        profile.companion_windows = vec![link("Alice", "Discord #2"), link("Bob", "Discord")];
+
+        assert!(profile.rename_custom_source("Discord", "Voice"));
+        profile.merge_character("Alice", "Carol");
+        let sources: Vec<&str> = profile
+            .companions_of("Carol")
+            .map(|link| link.source.as_str())
+            .collect();
+        assert_eq!(sources, vec!["Voice #2"]);
+        assert_eq!(profile.companions_of("Alice").count(), 0);
+
+        profile.remove_character("Bob");
+        assert_eq!(profile.companion_windows, vec![link("Carol", "Voice #2")]);
+    }
 }
@@ -10,16 +10,34 @@ use tracing::{error, info};
 use x11rb::protocol::render::Color;
 
 use crate::common::color::{HexColor, Opacity};
-use crate::common::types::{CharacterSettings, Position, TextOffset};
+use crate::common::types::{
+    CharacterSettings, DisplayScales, Position, SkippedStyle, TextAnchor, TextOffset,
+    ThumbnailWmStates, scale_i16, scale_u16,
+};
 
 /// Snapshot of display settings for the renderer.
 #[derive(Debug, Clone)]
 pub struct DisplayConfig {
     pub enabled: bool,
     pub opacity: u32, // 0-255 mapped to 0-0xFFFFFFFF
+    /// Thumbnails use an ARGB visual and apply `opacity` themselves
+    pub argb_visual: bool,
     pub active_border_size: u16,
     pub active_border_color: Color,
     pub text_offset: TextOffset,
+    pub text_anchor: TextAnchor,
+    /// Corner the character badges are drawn at
+    pub badge_anchor: TextAnchor,
+    pub skipped_style: SkippedStyle,
+    pub skipped_color: u32,
+    /// 1-based position of each entry in the cycle group shown on thumbnails
+    pub cycle_order: HashMap<String, usize>,
+    /// Border color of the next cycle target (None = no highlight)
+    pub next_target_color: Option<Color>,
+    /// Accent color of the cycle group each entry belongs to
+    pub group_colors: HashMap<String, Color>,
+    /// Hotkey drawn large over each character's thumbnail (empty while the overlay is off)
+    pub hotkey_labels: HashMap<String, String>,
     pub text_color: u32,
     pub hide_when_no_focus: bool,
     pub inactive_border_enabled: bool,
@@ -30,6 +48,25 @@ pub struct DisplayConfig {
     pub inactive_border_color: Color,
     pub inactive_border_size: u16,
     pub minimized_overlay_enabled: bool,
+    /// Text of the minimized overlay (custom, or localized "MINIMIZED")
+    pub minimized_text: String,
+    /// Font size of the minimized overlay text in pixels, scaled for the display
+    pub minimized_text_size: f32,
+    pub minimized_text_color: u32,
+    /// Darkening of the last frame behind the minimized overlay, in percent
+    pub minimized_dim: u8,
+    /// PNG drawn instead of the minimized overlay text
+    pub minimized_icon: Option<String>,
+    /// Thumbnails are managed by the window manager instead of override-redirect
+    pub managed_windows: bool,
+    /// EWMH states set on thumbnail windows
+    pub wm_states: ThumbnailWmStates,
+    /// Offset of new thumbnails from their client's top-left corner
+    pub spawn_offset: i16,
+    /// Language of the overlay texts
+    pub language: crate::common::i18n::Language,
+    /// Account nickname of each client launch slot, from slot 1
+    pub account_labels: Vec<String>,
 }
 use serde::{Deserialize, Serialize};
 
@@ -44,19 +81,153 @@ pub struct DaemonConfig {
     pub custom_source_thumbnails: HashMap<String, CharacterSettings>,
     /// Flattened map of hotkey bindings to profile names
     pub profile_hotkeys: HashMap<crate::config::HotkeyBinding, String>,
+    /// EVE client detection patterns (global setting)
+    pub eve_detection: crate::config::EveDetection,
+    /// Language for overlay text (global setting)
+    pub language: crate::common::i18n::Language,
+    /// Low-power mode settings (global setting)
+    pub low_power: crate::config::LowPower,
+    /// Batch thumbnail redraws to the display refresh rate (global setting)
+    pub vsync_redraws: bool,
+    /// Write session focus statistics to CSV (global setting)
+    pub focus_stats_csv: bool,
+    /// Directory for source window screenshots, empty for the default (global setting)
+    pub screenshot_dir: String,
+    /// Periodic client snapshots (global setting)
+    pub afk_snapshots: crate::config::AfkSnapshots,
+    /// Character aliases and notes shared by all profiles (global setting)
+    pub characters: crate::config::CharacterRegistry,
+    /// Rescan interval and spawn offset (global setting)
+    pub timing: crate::config::Timing,
     // Ephemeral state: used to temporarily hide previews via hotkey
     pub runtime_hidden: bool,
+    // Ephemeral state: each thumbnail shows its character's hotkey, toggled via hotkey
+    pub runtime_hotkey_overlay: bool,
+    /// Monitor scale factors detected by the daemon (not part of the config)
+    #[serde(skip)]
+    pub display_scales: DisplayScales,
+    /// No compositing manager is running, so opacity is turned off (detected by the daemon,
+    /// not part of the config)
+    #[serde(skip)]
+    pub compositor_missing: bool,
 }
 
 impl DaemonConfig {
-    /// Get default thumbnail dimensions from profile settings
-    pub fn default_thumbnail_size(&self, _screen_width: u16, _screen_height: u16) -> (u16, u16) {
+    /// Build the daemon configuration for the selected profile of `config`,
+    /// using the thumbnail positions stored in that profile.
+    pub fn from_config(config: &crate::config::profile::Config) -> Self {
+        let profile = config.get_active_profile().cloned().unwrap_or_default();
+        let character_thumbnails = profile.character_thumbnails.clone();
+        let custom_source_thumbnails = profile.custom_source_thumbnails.clone();
+        Self::from_profile(
+            config,
+            profile,
+            character_thumbnails,
+            custom_source_thumbnails,
+        )
+    }
+
+    /// Build the daemon configuration for `profile` with explicit thumbnail maps.
+    ///
+    /// Entries in `character_thumbnails` that belong to a custom window rule are moved
+    /// to the custom source map, profile-switch hotkeys are collected from all
+    /// profiles in `config`, and cycle groups listing other groups are flattened.
+    pub fn from_profile(
+        config: &crate::config::profile::Config,
+        mut profile: crate::config::profile::Profile,
+        mut character_thumbnails: HashMap<String, CharacterSettings>,
+        mut custom_source_thumbnails: HashMap<String, CharacterSettings>,
+    ) -> Self {
+        // Filter based on custom rules in profile.
+        let move_keys: Vec<String> = character_thumbnails
+            .keys()
+            .filter(|key| profile.custom_windows.iter().any(|r| r.owns_label(key)))
+            .cloned()
+            .collect();
+
+        for key in move_keys {
+            if let Some(val) = character_thumbnails.remove(&key) {
+                custom_source_thumbnails.insert(key, val);
+            }
+        }
+
+        profile.cycle_groups =
+            crate::config::profile::CycleGroup::resolve_nested(&profile.cycle_groups);
+
+        // Build hotkeys for profile switching (requires looking at all profiles)
+        let mut profile_hotkeys = HashMap::new();
+        for p in &config.profiles {
+            if let Some(ref binding) = p.hotkey_profile_switch {
+                profile_hotkeys.insert(binding.clone(), p.profile_name.clone());
+            }
+        }
+
+        Self {
+            profile,
+            character_thumbnails,
+            custom_source_thumbnails,
+            profile_hotkeys,
+            eve_detection: config.global.eve_detection.clone(),
+            language: config.global.language,
+            low_power: config.global.low_power.clone(),
+            vsync_redraws: config.global.vsync_redraws,
+            focus_stats_csv: config.global.focus_stats_csv,
+            screenshot_dir: config.global.screenshot_dir.clone(),
+            afk_snapshots: config.global.afk_snapshots.clone(),
+            characters: config.global.characters.clone(),
+            timing: config.global.timing.clone(),
+            runtime_hidden: false,
+            runtime_hotkey_overlay: false,
+            display_scales: DisplayScales::default(),
+            compositor_missing: false,
+        }
+    }
+
+    /// Whether turning off opacity for a missing compositor changes how thumbnails look
+    pub fn uses_translucency(&self) -> bool {
+        self.profile.thumbnail_opacity < 100 || self.profile.thumbnail_argb_visual
+    }
+
+    /// The profile's scale override, if set
+    fn scale_override(&self) -> Option<f32> {
+        (self.profile.thumbnail_scale_percent > 0)
+            .then(|| f32::from(self.profile.thumbnail_scale_percent) / 100.0)
+    }
+
+    /// Scale of thumbnail text: the profile override, or the primary monitor's scale
+    pub fn text_scale(&self) -> f32 {
+        self.scale_override().unwrap_or(self.display_scales.primary)
+    }
+
+    /// Font size of thumbnail text in pixels, scaled for the display
+    pub fn text_size(&self) -> f32 {
+        f32::from(self.profile.thumbnail_text_size) * self.text_scale()
+    }
+
+    /// Default thumbnail dimensions from profile settings, scaled for the monitor at `at`
+    pub fn default_thumbnail_size(&self, at: Position) -> (u16, u16) {
+        let scale = self
+            .scale_override()
+            .unwrap_or_else(|| self.display_scales.at(at));
         (
-            self.profile.thumbnail_default_width,
-            self.profile.thumbnail_default_height,
+            scale_u16(self.profile.thumbnail_default_width, scale),
+            scale_u16(self.profile.thumbnail_default_height, scale),
         )
     }
 
+    /// Give thumbnails saved as monitor percentages their position and size on this display
+    pub fn apply_monitor_percent(&mut self) {
+        let monitors = &self.display_scales.monitors;
+        crate::config::profile::apply_monitor_percent(
+            self.profile.thumbnail_position_anchor,
+            self.character_thumbnails
+                .values_mut()
+                .chain(self.custom_source_thumbnails.values_mut()),
+            monitors,
+        );
+        self.profile.apply_monitor_percent(monitors);
+    }
+
     /// Build DisplayConfig from current settings
     pub fn build_display_config(&self) -> DisplayConfig {
         let active_border_color = HexColor::parse(&self.profile.thumbnail_active_border_color)
@@ -73,6 +244,13 @@ impl DaemonConfig {
                 HexColor::from_argb32(0xFF_FF_FF_FF).argb32()
             });
 
+        let skipped_color = HexColor::parse(&self.profile.thumbnail_skipped_color)
+            .map(|c| c.argb32())
+            .unwrap_or_else(|| {
+                error!(skipped_color = %self.profile.thumbnail_skipped_color, "Invalid skipped_color hex, using default");
+                0xFF_FF_00_00
+            });
+
         let inactive_border_color = HexColor::parse(&self.profile.thumbnail_inactive_border_color)
             .map(|c| c.to_x11_color())
             .unwrap_or_else(|| {
@@ -80,7 +258,12 @@ impl DaemonConfig {
                 HexColor::from_argb32(0x00000000).to_x11_color()
             });
 
-        let opacity = Opacity::from_percent(self.profile.thumbnail_opacity).to_argb32();
+        // Without a compositor, translucent windows would come out opaque or darkened anyway
+        let opacity = if self.compositor_missing {
+            Opacity::from_percent(100).to_argb32()
+        } else {
+            Opacity::from_percent(self.profile.thumbnail_opacity).to_argb32()
+        };
 
         let mut character_settings = self.profile.character_thumbnails.clone();
 
@@ -91,32 +274,20 @@ impl DaemonConfig {
         // If a custom source has a rule, we ensure its overrides are applied to the settings map.
         // This handles cases where a custom source hasn't been "saved" (moved) yet but has config rule overrides.
         for rule in &self.profile.custom_windows {
+            // Saved extra instances ("Discord #2") carry the rule's overrides as well
+            for (label, settings) in character_settings.iter_mut() {
+                if *label != rule.alias && rule.owns_label(label) {
+                    apply_rule_overrides(settings, rule);
+                }
+            }
+
             character_settings
                 .entry(rule.alias.clone())
                 .and_modify(|settings| {
-                    // Update existing settings with rule overrides if present (Rule takes precedence or fills gaps?)
-                    // Usually saved settings (user edits via context menu) should win,
+                    // Saved settings (user edits via context menu) would usually win,
                     // BUT for custom sources, the "Rule" IS the user edit for these overrides effectively.
                     // The UI writes to the Rule. So the Rule is authoritative for overrides.
-                    if rule.active_border_color.is_some() {
-                        settings.override_active_border_color = rule.active_border_color.clone();
-                    }
-                    if rule.inactive_border_color.is_some() {
-                        settings.override_inactive_border_color =
-                            rule.inactive_border_color.clone();
-                    }
-                    if rule.active_border_size.is_some() {
-                        settings.override_active_border_size = rule.active_border_size;
-                    }
-                    if rule.inactive_border_size.is_some() {
-                        settings.override_inactive_border_size = rule.inactive_border_size;
-                    }
-                    if rule.text_color.is_some() {
-                        settings.override_text_color = rule.text_color.clone();
-                    }
-                    if rule.preview_mode.is_some() {
-                        settings.preview_mode = rule.preview_mode.clone().unwrap_or_default();
-                    }
+                    apply_rule_overrides(settings, rule);
                 })
                 .or_insert_with(|| {
                     // Create minimal settings from rule
@@ -127,21 +298,35 @@ impl DaemonConfig {
                             rule.default_width,
                             rule.default_height,
                         ),
+                        monitor_percent: None,
+                        client_origin: None,
                         alias: None,
                         notes: None,
+                        portrait: None,
                         override_active_border_color: rule.active_border_color.clone(),
                         override_inactive_border_color: rule.inactive_border_color.clone(),
                         override_active_border_size: rule.active_border_size,
                         override_inactive_border_size: rule.inactive_border_size,
                         override_text_color: rule.text_color.clone(),
+                        override_hide_not_focused: None,
+                        show_only_in_group: None,
                         preview_mode: rule.preview_mode.clone().unwrap_or_default(),
+                        badges: Vec::new(),
+                        activate_before: None,
+                        activate_after: None,
+                        last_seen: None,
+                        archived: false,
                     }
                 });
         }
 
+        // 3. Aliases, notes and portraits come from the global character registry
+        self.characters.apply_to(&mut character_settings);
+
         DisplayConfig {
             enabled: self.profile.thumbnail_enabled,
             opacity,
+            argb_visual: self.profile.thumbnail_argb_visual && !self.compositor_missing,
             active_border_size: if self.profile.thumbnail_active_border {
                 self.profile.thumbnail_active_border_size
             } else {
@@ -149,9 +334,33 @@ impl DaemonConfig {
             },
             active_border_color,
             text_offset: TextOffset::from_border_edge(
-                self.profile.thumbnail_text_x,
-                self.profile.thumbnail_text_y,
+                scale_i16(self.profile.thumbnail_text_x, self.text_scale()),
+                scale_i16(self.profile.thumbnail_text_y, self.text_scale()),
             ),
+            text_anchor: self.profile.thumbnail_text_anchor,
+            badge_anchor: self.profile.thumbnail_badge_anchor,
+            skipped_style: self.profile.thumbnail_skipped_style,
+            skipped_color,
+            next_target_color: self
+                .profile
+                .thumbnail_next_target_highlight
+                .then(|| HexColor::parse(&self.profile.thumbnail_next_target_color))
+                .flatten()
+                .map(|c| c.to_x11_color()),
+            cycle_order: cycle_order(
+                &self.profile.cycle_groups,
+                &self.profile.thumbnail_cycle_order_group,
+            ),
+            group_colors: group_colors(&self.profile.cycle_groups),
+            hotkey_labels: if self.runtime_hotkey_overlay {
+                self.profile
+                    .character_hotkeys
+                    .iter()
+                    .map(|(name, binding)| (name.clone(), binding.display_name()))
+                    .collect()
+            } else {
+                HashMap::new()
+            },
             text_color,
             hide_when_no_focus: self.profile.thumbnail_hide_not_focused,
             inactive_border_enabled: self.profile.thumbnail_inactive_border,
@@ -162,6 +371,26 @@ impl DaemonConfig {
                 0
             },
             minimized_overlay_enabled: self.profile.client_minimize_show_overlay,
+            minimized_text: if self.profile.client_minimize_overlay_text.is_empty() {
+                crate::common::i18n::tr_in(self.language, "overlay-minimized", None)
+            } else {
+                self.profile.client_minimize_overlay_text.clone()
+            },
+            minimized_text_size: match self.profile.client_minimize_overlay_text_size {
+                0 => self.text_size(),
+                size => f32::from(size) * self.text_scale(),
+            },
+            minimized_text_color: HexColor::parse(&self.profile.client_minimize_overlay_text_color)
+                .map(|c| c.argb32())
+                .unwrap_or(text_color),
+            minimized_dim: self.profile.client_minimize_overlay_dim.min(100),
+            minimized_icon: Some(self.profile.client_minimize_overlay_icon.clone())
+                .filter(|path| !path.is_empty()),
+            managed_windows: self.profile.thumbnail_managed_windows,
+            wm_states: self.profile.thumbnail_wm_states,
+            spawn_offset: self.timing.spawn_offset(),
+            language: self.language,
+            account_labels: self.profile.client_account_labels.clone(),
             character_settings,
         }
     }
@@ -173,6 +402,7 @@ impl DaemonConfig {
         old_name: &str,
         new_name: &str,
         current_position: Position,
+        client_origin: Option<Position>,
         current_width: u16,
         current_height: u16,
     ) -> Result<Option<CharacterSettings>> {
@@ -195,6 +425,7 @@ impl DaemonConfig {
             // Update session state (position) while preserving user customization (style/mode).
             settings.x = current_position.x;
             settings.y = current_position.y;
+            settings.client_origin = client_origin;
             settings.dimensions =
                 crate::common::types::Dimensions::new(current_width, current_height);
 
@@ -207,6 +438,7 @@ impl DaemonConfig {
         if !new_name.is_empty()
             && let Ok(disk_config) = crate::config::profile::Config::load()
         {
+            self.characters = disk_config.global.characters.clone();
             let pd_name = &self.profile.profile_name;
             if let Some(disk_profile) = disk_config
                 .profiles
@@ -218,8 +450,6 @@ impl DaemonConfig {
                     .entry(new_name.to_string())
                     .and_modify(|mem_settings| {
                         mem_settings.preview_mode = disk_settings.preview_mode.clone();
-                        mem_settings.alias = disk_settings.alias.clone();
-                        mem_settings.notes = disk_settings.notes.clone();
                         mem_settings.override_active_border_color =
                             disk_settings.override_active_border_color.clone();
                         mem_settings.override_inactive_border_color =
@@ -230,6 +460,7 @@ impl DaemonConfig {
                             disk_settings.override_inactive_border_size;
                         mem_settings.override_text_color =
                             disk_settings.override_text_color.clone();
+                        mem_settings.badges = disk_settings.badges.clone();
                     })
                     .or_insert_with(|| disk_settings.clone());
             }
@@ -237,6 +468,7 @@ impl DaemonConfig {
 
         if !new_name.is_empty()
             && let Some(settings) = self.character_thumbnails.get(new_name)
+            && settings.is_placed()
         {
             info!(
                 character = %new_name,
@@ -253,6 +485,144 @@ impl DaemonConfig {
     }
 }
 
+impl DisplayConfig {
+    /// Settings for a thumbnail label. Extra custom source instances ("Discord #2")
+    /// without saved settings of their own use the first instance's overrides.
+    pub fn settings_for(
+        &self,
+        character_name: &str,
+    ) -> Option<&crate::common::types::CharacterSettings> {
+        self.character_settings.get(character_name).or_else(|| {
+            let (alias, index) = crate::config::profile::split_instance_label(character_name);
+            (index > 1)
+                .then(|| self.character_settings.get(alias))
+                .flatten()
+        })
+    }
+
+    /// Label of a logged-out thumbnail: the account nickname of its client's launch `slot`
+    /// (or "Client N"), followed by "(last: Name)" if a character was logged in on it before
+    pub fn logged_out_label(&self, slot: Option<usize>, last: Option<&str>) -> String {
+        use crate::common::i18n::tr_in_with;
+
+        let account = slot.map(|slot| {
+            self.account_labels
+                .get(slot.wrapping_sub(1))
+                .map(|label| label.trim())
+                .filter(|label| !label.is_empty())
+                .map(str::to_string)
+                .unwrap_or_else(|| {
+                    tr_in_with(
+                        self.language,
+                        "overlay-client-slot",
+                        &[("slot", slot.to_string())],
+                    )
+                })
+        });
+        let last = last.map(|name| {
+            tr_in_with(
+                self.language,
+                "overlay-last-character",
+                &[("name", name.to_string())],
+            )
+        });
+        account
+            .into_iter()
+            .chain(last)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Whether a thumbnail hides while no EVE client has focus, after its own override
+    pub fn hides_when_unfocused(&self, character_name: &str) -> bool {
+        self.settings_for(character_name)
+            .and_then(|s| s.override_hide_not_focused)
+            .unwrap_or(self.hide_when_no_focus)
+    }
+
+    /// Whether any thumbnail hides on focus loss, so focus changes need tracking
+    pub fn any_hides_when_unfocused(&self) -> bool {
+        self.hide_when_no_focus
+            || self
+                .character_settings
+                .values()
+                .any(|s| s.override_hide_not_focused == Some(true))
+    }
+}
+
+/// 1-based cycle positions of the entries of the group named `group_name` (empty if there is
+/// no such group). An entry listed twice keeps its first position.
+fn cycle_order(
+    groups: &[crate::config::profile::CycleGroup],
+    group_name: &str,
+) -> HashMap<String, usize> {
+    let mut order = HashMap::new();
+    if let Some(group) = groups.iter().find(|g| g.name == group_name) {
+        for (index, slot) in group.cycle_list.iter().enumerate() {
+            match slot {
+                crate::config::profile::CycleSlot::Eve(name)
+                | crate::config::profile::CycleSlot::Source(name) => {
+                    order.entry(name.clone()).or_insert(index + 1);
+                }
+                // Resolved in `from_profile`
+                crate::config::profile::CycleSlot::Group(_) => {}
+            }
+        }
+    }
+    order
+}
+
+/// Accent color of each entry's first group with a valid color
+fn group_colors(groups: &[crate::config::profile::CycleGroup]) -> HashMap<String, Color> {
+    let mut colors = HashMap::new();
+    for group in groups {
+        let Some(color) = group
+            .color
+            .as_deref()
+            .and_then(HexColor::parse)
+            .map(|c| c.to_x11_color())
+        else {
+            continue;
+        };
+        for slot in &group.cycle_list {
+            match slot {
+                crate::config::profile::CycleSlot::Eve(name)
+                | crate::config::profile::CycleSlot::Source(name) => {
+                    colors.entry(name.clone()).or_insert(color);
+                }
+                // Resolved in `from_profile`
+                crate::config::profile::CycleSlot::Group(_) => {}
+            }
+        }
+    }
+    colors
+}
+
+/// Copy the visual overrides a custom window rule sets onto thumbnail settings
+fn apply_rule_overrides(
+    settings: &mut crate::common::types::CharacterSettings,
+    rule: &crate::config::profile::CustomWindowRule,
+) {
+    if rule.active_border_color.is_some() {
+        settings.override_active_border_color = rule.active_border_color.clone();
+    }
+    if rule.inactive_border_color.is_some() {
+        settings.override_inactive_border_color = rule.inactive_border_color.clone();
+    }
+    if rule.active_border_size.is_some() {
+        settings.override_active_border_size = rule.active_border_size;
+    }
+    if rule.inactive_border_size.is_some() {
+        settings.override_inactive_border_size = rule.inactive_border_size;
+    }
+    if rule.text_color.is_some() {
+        settings.override_text_color = rule.text_color.clone();
+    }
+    if rule.preview_mode.is_some() {
+        settings.preview_mode = rule.preview_mode.clone().unwrap_or_default();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -277,6 +647,7 @@ mod tests {
                 thumbnail_default_width: 480,
                 thumbnail_default_height: 270,
                 thumbnail_opacity: opacity_percent,
+                thumbnail_argb_visual: false,
                 thumbnail_active_border: border_size > 0, // In tests, valid size > 0 implies enabled
                 thumbnail_active_border_size: border_size,
                 thumbnail_active_border_color: border_color.to_string(),
@@ -286,19 +657,43 @@ mod tests {
                 thumbnail_text_size: 18,
                 thumbnail_text_x: text_x,
                 thumbnail_text_y: text_y,
+                thumbnail_text_anchor: TextAnchor::TopLeft,
+                thumbnail_badge_anchor: TextAnchor::TopRight,
                 thumbnail_text_color: text_color.to_string(),
+                thumbnail_scale_percent: 0,
+                thumbnail_skipped_style: SkippedStyle::Cross,
+                thumbnail_skipped_color: "#FFFF0000".to_string(),
+                thumbnail_cycle_order_group: String::new(),
+                thumbnail_next_target_highlight: false,
+                thumbnail_next_target_color: "#FFA500".to_string(),
                 thumbnail_text_font: String::new(),
                 thumbnail_auto_save_position: false,
+                thumbnail_save_on_exit: false,
+                thumbnail_autosave_interval_secs: 0,
                 thumbnail_snap_threshold: snap_threshold,
+                thumbnail_snap_gap: 0,
+                thumbnail_tooltips: false,
                 thumbnail_hide_not_focused: hide_when_no_focus,
+                thumbnail_do_not_disturb_classes: Vec::new(),
                 thumbnail_preserve_position_on_swap: false,
+                thumbnail_follow_source_monitor: false,
+                thumbnail_position_anchor: crate::config::profile::PositionAnchor::Screen,
+                thumbnail_managed_windows: false,
+                thumbnail_wm_states: ThumbnailWmStates::default(),
                 client_minimize_on_switch: false,
                 hotkey_input_device: None,
                 hotkey_logged_out_cycle: false,
                 hotkey_require_eve_focus: true,
                 hotkey_cycle_reset_index: false,
+                hotkey_cycle_monitor: crate::config::profile::CycleMonitor::All,
+                hotkey_switch_osd: false,
+                hotkey_skip_persist: false,
+                hotkey_skipped_characters: Vec::new(),
+                client_account_labels: Vec::new(),
                 cycle_groups: vec![crate::config::profile::CycleGroup::default_group()],
                 custom_windows: Vec::new(),
+                companion_windows: Vec::new(),
+                x11_display: String::new(),
                 character_hotkeys: HashMap::new(),
                 hotkey_backend: crate::config::HotkeyBackendType::X11,
                 thumbnail_enabled: true,
@@ -307,15 +702,77 @@ mod tests {
                 hotkey_profile_switch: None,
                 hotkey_toggle_skip: None,
                 hotkey_toggle_previews: None,
+                hotkey_save_positions: None,
+                hotkey_undo_move: None,
+                hotkey_active_forward: None,
+                hotkey_active_backward: None,
+                hotkey_next_group: None,
+                hotkey_screenshot: None,
+                hotkey_toggle_hotkey_overlay: None,
+                hotkey_swap_positions: None,
                 client_minimize_show_overlay: false,
+                client_minimize_overlay_text: String::new(),
+                client_minimize_overlay_text_size: 0,
+                client_minimize_overlay_text_color: String::new(),
+                client_minimize_overlay_dim: 100,
+                client_minimize_overlay_icon: String::new(),
+                client_workspace_follow: crate::config::profile::WorkspaceFollow::WindowManager,
             },
             character_thumbnails: HashMap::new(),
             custom_source_thumbnails: HashMap::new(),
             profile_hotkeys: HashMap::new(),
+            eve_detection: crate::config::EveDetection::default(),
+            language: crate::common::i18n::Language::English,
+            low_power: crate::config::LowPower::default(),
+            vsync_redraws: false,
+            focus_stats_csv: false,
+            screenshot_dir: String::new(),
+            afk_snapshots: crate::config::AfkSnapshots::default(),
+            characters: crate::config::CharacterRegistry::default(),
+            timing: crate::config::Timing::default(),
             runtime_hidden: false,
+            runtime_hotkey_overlay: false,
+            display_scales: DisplayScales::default(),
+            compositor_missing: false,
         }
     }
 
+    #[test]
+    fn test_hidpi_scaling() {
+        let mut state = test_config(75, 3, "#FF00FF00", 15, 25, "#FFFFFFFF", true, 20);
+        state.display_scales = DisplayScales {
+            monitors: vec![crate::common::types::MonitorScale {
+                x: 1920,
+                y: 0,
+                width: 3840,
+                height: 2160,
+                scale: 2.0,
+            }],
+            primary: 1.0,
+        };
+
+        assert_eq!(
+            state.default_thumbnail_size(Position::new(0, 0)),
+            (480, 270)
+        );
+        assert_eq!(
+            state.default_thumbnail_size(Position::new(2000, 100)),
+            (960, 540)
+        );
+        assert_eq!(state.text_size(), 18.0);
+
+        // The profile override applies everywhere, text included
+        state.profile.thumbnail_scale_percent = 150;
+        assert_eq!(
+            state.default_thumbnail_size(Position::new(2000, 100)),
+            (720, 405)
+        );
+        assert_eq!(state.text_size(), 27.0);
+        let config = state.build_display_config();
+        assert_eq!(config.text_offset.x, 23);
+        assert_eq!(config.text_offset.y, 38);
+    }
+
     #[test]
     fn test_build_display_config_valid_colors() {
         let state = test_config(75, 3, "#FF00FF00", 15, 25, "#FFFFFFFF", true, 20);
@@ -331,6 +788,148 @@ mod tests {
         assert_eq!(config.active_border_color.blue, 0);
         assert_eq!(config.active_border_color.alpha, 65535);
         assert!(!config.minimized_overlay_enabled);
+        assert_eq!(config.minimized_text, "MINIMIZED");
+        assert_eq!(
+            config.logged_out_label(None, Some("Alice")),
+            "(last: Alice)"
+        );
+    }
+
+    #[test]
+    fn test_build_display_config_without_compositor() {
+        let mut state = test_config(75, 3, "#FF00FF00", 15, 25, "#FFFFFFFF", true, 20);
+        state.profile.thumbnail_argb_visual = true;
+        assert!(state.uses_translucency());
+
+        state.compositor_missing = true;
+        let config = state.build_display_config();
+        assert_eq!(config.opacity, 0xFF000000);
+        assert!(!config.argb_visual);
+    }
+
+    #[test]
+    fn test_build_display_config_cycle_order() {
+        use crate::config::profile::{CycleGroup, CycleSlot};
+
+        let mut state = test_config(75, 3, "#FF00FF00", 15, 25, "#FFFFFFFF", true, 20);
+        let mut group = CycleGroup::default_group();
+        group.name = "Fleet".to_string();
+        group.cycle_list = vec![
+            CycleSlot::Eve("Main".to_string()),
+            CycleSlot::Source("Browser".to_string()),
+            CycleSlot::Eve("Alt".to_string()),
+            CycleSlot::Eve("Main".to_string()),
+        ];
+        state.profile.cycle_groups = vec![CycleGroup::default_group(), group];
+
+        assert!(state.build_display_config().cycle_order.is_empty());
+
+        state.profile.thumbnail_cycle_order_group = "Fleet".to_string();
+        let order = state.build_display_config().cycle_order;
+        assert_eq!(order.len(), 3);
+        assert_eq!(order["Main"], 1);
+        assert_eq!(order["Browser"], 2);
+        assert_eq!(order["Alt"], 3);
+    }
+
+    #[test]
+    fn test_build_display_config_group_colors() {
+        use crate::config::profile::{CycleGroup, CycleSlot};
+
+        let mut state = test_config(75, 3, "#FF00FF00", 15, 25, "#FFFFFFFF", true, 20);
+        let mut logi = CycleGroup::default_group();
+        logi.name = "Logi".to_string();
+        logi.color = Some("#00FF00".to_string());
+        logi.cycle_list = vec![CycleSlot::Eve("Alt".to_string())];
+        let mut all = CycleGroup::default_group();
+        all.name = "All".to_string();
+        all.color = Some("not a color".to_string());
+        all.cycle_list = vec![
+            CycleSlot::Eve("Main".to_string()),
+            CycleSlot::Eve("Alt".to_string()),
+        ];
+        let mut dps = CycleGroup::default_group();
+        dps.name = "DPS".to_string();
+        dps.color = Some("#FF0000".to_string());
+        dps.cycle_list = vec![
+            CycleSlot::Eve("Main".to_string()),
+            CycleSlot::Eve("Alt".to_string()),
+        ];
+        state.profile.cycle_groups = vec![logi, all, dps];
+
+        let colors = state.build_display_config().group_colors;
+        assert_eq!(colors.len(), 2);
+        assert_eq!(colors["Alt"].green, 0xFFFF);
+        assert_eq!(colors["Alt"].red, 0);
+        assert_eq!(colors["Main"].red, 0xFFFF);
+    }
+
+    #[test]
+    fn test_build_display_config_hotkey_labels() {
+        let mut state = test_config(75, 3, "#FF00FF00", 15, 25, "#FFFFFFFF", true, 20);
+        state.profile.character_hotkeys.insert(
+            "Main".to_string(),
+            crate::config::HotkeyBinding::new(59, true, false, false, false),
+        );
+        assert!(state.build_display_config().hotkey_labels.is_empty());
+
+        state.runtime_hotkey_overlay = true;
+        let labels = state.build_display_config().hotkey_labels;
+        assert_eq!(labels.len(), 1);
+        assert_eq!(labels["Main"], "Ctrl+F1");
+    }
+
+    #[test]
+    fn test_build_display_config_minimized_overlay() {
+        let mut state = test_config(75, 3, "#FF00FF00", 15, 25, "#FFFFFFFF", true, 20);
+        let config = state.build_display_config();
+        assert_eq!(config.minimized_text_size, 18.0);
+        assert_eq!(config.minimized_text_color, 0xFFFFFFFF);
+        assert_eq!(config.minimized_dim, 100);
+        assert_eq!(config.minimized_icon, None);
+
+        state.profile.client_minimize_overlay_text = "AFK".to_string();
+        state.profile.client_minimize_overlay_text_size = 30;
+        state.profile.client_minimize_overlay_text_color = "#FF00FF00".to_string();
+        state.profile.client_minimize_overlay_dim = 150;
+        state.profile.client_minimize_overlay_icon = "~/icons/zzz.png".to_string();
+        state.profile.thumbnail_scale_percent = 200;
+        let config = state.build_display_config();
+        assert_eq!(config.minimized_text, "AFK");
+        assert_eq!(config.minimized_text_size, 60.0);
+        assert_eq!(config.minimized_text_color, 0xFF00FF00);
+        assert_eq!(config.minimized_dim, 100);
+        assert_eq!(config.minimized_icon.as_deref(), Some("~/icons/zzz.png"));
+    }
+
+    #[test]
+    fn test_hide_when_unfocused_override() {
+        let mut state = test_config(75, 3, "#FF00FF00", 15, 25, "#FFFFFFFF", true, 20);
+        let mut keep = CharacterSettings::new(0, 0, 100, 100);
+        keep.override_hide_not_focused = Some(false);
+        state
+            .profile
+            .character_thumbnails
+            .insert("Scout".to_string(), keep);
+
+        let config = state.build_display_config();
+        assert!(config.hides_when_unfocused("Miner"));
+        assert!(!config.hides_when_unfocused("Scout"));
+
+        state.profile.thumbnail_hide_not_focused = false;
+        let config = state.build_display_config();
+        assert!(!config.any_hides_when_unfocused());
+
+        let mut hide = CharacterSettings::new(0, 0, 100, 100);
+        hide.override_hide_not_focused = Some(true);
+        state
+            .profile
+            .character_thumbnails
+            .insert("Hauler".to_string(), hide);
+        let config = state.build_display_config();
+        assert!(config.any_hides_when_unfocused());
+        assert!(config.hides_when_unfocused("Hauler"));
+        assert!(!config.hides_when_unfocused("Miner"));
     }
 
     #[test]
@@ -360,6 +959,21 @@ mod tests {
         assert_eq!(config.active_border_color.alpha, 65535);
     }
 
+    #[test]
+    fn test_logged_out_label() {
+        let mut state = test_config(100, 5, "#FF00FF00", 10, 20, "#FFFFFFFF", false, 15);
+        state.profile.client_account_labels = vec!["Main".to_string(), " ".to_string()];
+
+        let config = state.build_display_config();
+        assert_eq!(
+            config.logged_out_label(Some(1), Some("Alice")),
+            "Main (last: Alice)"
+        );
+        assert_eq!(config.logged_out_label(Some(2), None), "Client 2");
+        assert_eq!(config.logged_out_label(Some(3), None), "Client 3");
+        assert_eq!(config.logged_out_label(None, None), "");
+    }
+
     #[test]
     fn test_handle_character_change_both_names() {
         let mut state = test_config(75, 3, "#FF00FF00", 10, 20, "#FFFFFFFF", false, 15);
@@ -370,7 +984,8 @@ mod tests {
         );
 
         let current_pos = Position::new(100, 200);
-        let result = state.handle_character_change("OldChar", "NewChar", current_pos, 480, 270);
+        let result =
+            state.handle_character_change("OldChar", "NewChar", current_pos, None, 480, 270);
 
         let old_settings = state.character_thumbnails.get("OldChar").unwrap();
         assert_eq!(old_settings.x, 100);
@@ -393,7 +1008,7 @@ mod tests {
         let mut state = test_config(75, 3, "#FF00FF00", 10, 20, "#FFFFFFFF", false, 15);
 
         let current_pos = Position::new(300, 400);
-        let result = state.handle_character_change("LoggingOut", "", current_pos, 480, 270);
+        let result = state.handle_character_change("LoggingOut", "", current_pos, None, 480, 270);
 
         let settings = state.character_thumbnails.get("LoggingOut").unwrap();
         assert_eq!(settings.x, 300);
@@ -411,7 +1026,7 @@ mod tests {
         let mut state = test_config(75, 3, "#FF00FF00", 10, 20, "#FFFFFFFF", false, 15);
 
         let current_pos = Position::new(700, 800);
-        let result = state.handle_character_change("", "BrandNewChar", current_pos, 480, 270);
+        let result = state.handle_character_change("", "BrandNewChar", current_pos, None, 480, 270);
 
         if let Ok(new_pos) = result {
             assert_eq!(new_pos, None);
@@ -423,11 +1038,11 @@ mod tests {
         let mut state = test_config(75, 3, "#FF00FF00", 10, 20, "#FFFFFFFF", false, 15);
 
         // 1. Verify handle_character_change doesn't insert empty old_name
-        let _ = state.handle_character_change("", "NewChar", Position::new(0, 0), 100, 100);
+        let _ = state.handle_character_change("", "NewChar", Position::new(0, 0), None, 100, 100);
         assert!(!state.character_thumbnails.contains_key(""));
 
         // 2. Verify it doesn't try to look up empty new_name
-        let _ = state.handle_character_change("OldChar", "", Position::new(0, 0), 100, 100);
+        let _ = state.handle_character_change("OldChar", "", Position::new(0, 0), None, 100, 100);
         assert!(!state.character_thumbnails.contains_key(""));
     }
 }
@@ -1,14 +1,15 @@
 use serde::Deserialize;
 use std::collections::HashMap;
 
-use crate::common::types::CharacterSettings;
+use crate::common::types::{CharacterSettings, SkippedStyle, TextAnchor, ThumbnailWmStates};
 use crate::config::profile::{
-    CustomWindowRule, CycleGroup, HotkeyBackendType, Profile,
-    default_auto_save_thumbnail_positions, default_border_enabled, default_border_size,
-    default_hotkey_backend, default_inactive_border_color, default_inactive_border_enabled,
-    default_preserve_thumbnail_position_on_swap, default_profile_name, default_snap_threshold,
-    default_text_font_family, default_thumbnail_enabled, default_thumbnail_height,
-    default_thumbnail_width,
+    CustomWindowRule, CycleGroup, CycleMonitor, HotkeyBackendType, PositionAnchor, Profile,
+    WorkspaceFollow, default_auto_save_thumbnail_positions, default_badge_anchor,
+    default_border_enabled, default_border_size, default_hotkey_backend,
+    default_inactive_border_color, default_inactive_border_enabled, default_minimize_overlay_dim,
+    default_next_target_color, default_preserve_thumbnail_position_on_swap, default_profile_name,
+    default_skipped_color, default_snap_threshold, default_text_font_family,
+    default_thumbnail_enabled, default_thumbnail_height, default_thumbnail_width,
 };
 
 /// Helper struct for migration during deserialization
@@ -25,6 +26,8 @@ struct ProfileHelper {
     #[serde(default = "default_thumbnail_enabled")]
     thumbnail_enabled: bool,
     thumbnail_opacity: u8,
+    #[serde(default)]
+    thumbnail_argb_visual: bool,
     #[serde(default = "default_border_enabled", alias = "thumbnail_border")]
     thumbnail_active_border: bool,
     #[serde(alias = "thumbnail_border_size")]
@@ -43,21 +46,67 @@ struct ProfileHelper {
     thumbnail_text_size: u16,
     thumbnail_text_x: i16,
     thumbnail_text_y: i16,
+    #[serde(default)]
+    thumbnail_text_anchor: TextAnchor,
+    #[serde(default = "default_badge_anchor")]
+    thumbnail_badge_anchor: TextAnchor,
     #[serde(default = "default_text_font_family")]
     thumbnail_text_font: String,
     thumbnail_text_color: String,
+    #[serde(default)]
+    thumbnail_scale_percent: u16,
+    #[serde(default)]
+    thumbnail_skipped_style: SkippedStyle,
+    #[serde(default = "default_skipped_color")]
+    thumbnail_skipped_color: String,
+    #[serde(default)]
+    thumbnail_cycle_order_group: String,
+    #[serde(default)]
+    thumbnail_next_target_highlight: bool,
+    #[serde(default = "default_next_target_color")]
+    thumbnail_next_target_color: String,
     #[serde(default = "default_auto_save_thumbnail_positions")]
     thumbnail_auto_save_position: bool,
+    #[serde(default)]
+    thumbnail_save_on_exit: bool,
+    #[serde(default)]
+    thumbnail_autosave_interval_secs: u32,
     #[serde(default = "default_snap_threshold")]
     thumbnail_snap_threshold: u16,
     #[serde(default)]
+    thumbnail_snap_gap: u16,
+    #[serde(default)]
+    thumbnail_tooltips: bool,
+    #[serde(default)]
     thumbnail_hide_not_focused: bool,
+    #[serde(default)]
+    thumbnail_do_not_disturb_classes: Vec<String>,
     #[serde(default = "default_preserve_thumbnail_position_on_swap")]
     thumbnail_preserve_position_on_swap: bool,
     #[serde(default)]
+    thumbnail_follow_source_monitor: bool,
+    #[serde(default)]
+    thumbnail_position_anchor: PositionAnchor,
+    #[serde(default)]
+    thumbnail_managed_windows: bool,
+    #[serde(default)]
+    thumbnail_wm_states: ThumbnailWmStates,
+    #[serde(default)]
     client_minimize_on_switch: bool,
     #[serde(default)]
     client_minimize_show_overlay: bool,
+    #[serde(default)]
+    client_minimize_overlay_text: String,
+    #[serde(default)]
+    client_minimize_overlay_text_size: u16,
+    #[serde(default)]
+    client_minimize_overlay_text_color: String,
+    #[serde(default = "default_minimize_overlay_dim")]
+    client_minimize_overlay_dim: u8,
+    #[serde(default)]
+    client_minimize_overlay_icon: String,
+    #[serde(default)]
+    client_workspace_follow: WorkspaceFollow,
     #[serde(default = "default_hotkey_backend")]
     hotkey_backend: HotkeyBackendType,
     #[serde(default)]
@@ -69,12 +118,38 @@ struct ProfileHelper {
     #[serde(default)]
     hotkey_cycle_reset_index: bool,
     #[serde(default)]
+    hotkey_cycle_monitor: CycleMonitor,
+    #[serde(default)]
+    hotkey_switch_osd: bool,
+    #[serde(default)]
+    hotkey_skip_persist: bool,
+    #[serde(default)]
+    hotkey_skipped_characters: Vec<String>,
+    #[serde(default)]
+    client_account_labels: Vec<String>,
+    #[serde(default)]
     hotkey_profile_switch: Option<crate::config::HotkeyBinding>,
     #[serde(default)]
     hotkey_toggle_skip: Option<crate::config::HotkeyBinding>,
     #[serde(default)]
     hotkey_toggle_previews: Option<crate::config::HotkeyBinding>,
     #[serde(default)]
+    hotkey_save_positions: Option<crate::config::HotkeyBinding>,
+    #[serde(default)]
+    hotkey_undo_move: Option<crate::config::HotkeyBinding>,
+    #[serde(default)]
+    hotkey_active_forward: Option<crate::config::HotkeyBinding>,
+    #[serde(default)]
+    hotkey_active_backward: Option<crate::config::HotkeyBinding>,
+    #[serde(default)]
+    hotkey_next_group: Option<crate::config::HotkeyBinding>,
+    #[serde(default)]
+    hotkey_screenshot: Option<crate::config::HotkeyBinding>,
+    #[serde(default)]
+    hotkey_toggle_hotkey_overlay: Option<crate::config::HotkeyBinding>,
+    #[serde(default)]
+    hotkey_swap_positions: Option<crate::config::HotkeyBinding>,
+    #[serde(default)]
     character_hotkeys: HashMap<String, crate::config::HotkeyBinding>,
     #[serde(default)]
     character_thumbnails: HashMap<String, CharacterSettings>,
@@ -83,6 +158,10 @@ struct ProfileHelper {
     custom_source_thumbnails: HashMap<String, CharacterSettings>,
     #[serde(default)]
     custom_windows: Vec<CustomWindowRule>,
+    #[serde(default)]
+    companion_windows: Vec<crate::config::profile::CompanionLink>,
+    #[serde(default)]
+    x11_display: String,
 
     // New field
     #[serde(default)]
@@ -114,7 +193,7 @@ impl From<ProfileHelper> for Profile {
                     .hotkey_cycle_group
                     .into_iter()
                     .map(|name| {
-                        if helper.custom_windows.iter().any(|w| w.alias == name) {
+                        if helper.custom_windows.iter().any(|w| w.owns_label(&name)) {
                             crate::config::profile::CycleSlot::Source(name)
                         } else {
                             crate::config::profile::CycleSlot::Eve(name)
@@ -123,6 +202,10 @@ impl From<ProfileHelper> for Profile {
                     .collect(),
                 hotkey_forward: helper.hotkey_cycle_forward,
                 hotkey_backward: helper.hotkey_cycle_backward,
+                hotkey_toggle_visibility: None,
+                auto_include_new: false,
+                auto_include_hotkey: false,
+                color: None,
             });
         }
 
@@ -135,16 +218,13 @@ impl From<ProfileHelper> for Profile {
         let mut character_thumbnails = helper.character_thumbnails;
         let mut custom_source_thumbnails = helper.custom_source_thumbnails;
 
-        let custom_aliases: Vec<String> = helper
-            .custom_windows
-            .iter()
-            .map(|w| w.alias.clone())
-            .collect();
+        // Custom source labels are a rule alias or one of its instance labels ("Discord #2")
+        let is_custom = |name: &str| helper.custom_windows.iter().any(|w| w.owns_label(name));
 
         // Move any entry that matches a custom alias to the correct map
         let keys_to_move: Vec<String> = character_thumbnails
             .keys()
-            .filter(|k| custom_aliases.contains(k))
+            .filter(|k| is_custom(k))
             .cloned()
             .collect();
 
@@ -155,11 +235,10 @@ impl From<ProfileHelper> for Profile {
         }
 
         // Fixup: Go through all cycle groups and ensure any entry matching a custom window is Source
-        let custom_aliases_set = custom_aliases; // move ownership
         for group in &mut cycle_groups {
             for slot in &mut group.cycle_list {
                 if let crate::config::profile::CycleSlot::Eve(name) = slot
-                    && custom_aliases_set.contains(name)
+                    && is_custom(name)
                 {
                     *slot = crate::config::profile::CycleSlot::Source(name.clone());
                 }
@@ -173,6 +252,7 @@ impl From<ProfileHelper> for Profile {
             thumbnail_default_height: helper.thumbnail_default_height,
             thumbnail_enabled: helper.thumbnail_enabled,
             thumbnail_opacity: helper.thumbnail_opacity,
+            thumbnail_argb_visual: helper.thumbnail_argb_visual,
             thumbnail_active_border: helper.thumbnail_active_border,
             thumbnail_active_border_size: helper.thumbnail_active_border_size,
             thumbnail_active_border_color: helper.thumbnail_active_border_color,
@@ -182,27 +262,65 @@ impl From<ProfileHelper> for Profile {
             thumbnail_text_size: helper.thumbnail_text_size,
             thumbnail_text_x: helper.thumbnail_text_x,
             thumbnail_text_y: helper.thumbnail_text_y,
+            thumbnail_text_anchor: helper.thumbnail_text_anchor,
+            thumbnail_badge_anchor: helper.thumbnail_badge_anchor,
             thumbnail_text_font: helper.thumbnail_text_font,
             thumbnail_text_color: helper.thumbnail_text_color,
+            thumbnail_scale_percent: helper.thumbnail_scale_percent,
+            thumbnail_skipped_style: helper.thumbnail_skipped_style,
+            thumbnail_skipped_color: helper.thumbnail_skipped_color,
+            thumbnail_cycle_order_group: helper.thumbnail_cycle_order_group,
+            thumbnail_next_target_highlight: helper.thumbnail_next_target_highlight,
+            thumbnail_next_target_color: helper.thumbnail_next_target_color,
             thumbnail_auto_save_position: helper.thumbnail_auto_save_position,
+            thumbnail_save_on_exit: helper.thumbnail_save_on_exit,
+            thumbnail_autosave_interval_secs: helper.thumbnail_autosave_interval_secs,
             thumbnail_snap_threshold: helper.thumbnail_snap_threshold,
+            thumbnail_snap_gap: helper.thumbnail_snap_gap,
+            thumbnail_tooltips: helper.thumbnail_tooltips,
             thumbnail_hide_not_focused: helper.thumbnail_hide_not_focused,
+            thumbnail_do_not_disturb_classes: helper.thumbnail_do_not_disturb_classes,
             thumbnail_preserve_position_on_swap: helper.thumbnail_preserve_position_on_swap,
+            thumbnail_follow_source_monitor: helper.thumbnail_follow_source_monitor,
+            thumbnail_position_anchor: helper.thumbnail_position_anchor,
+            thumbnail_managed_windows: helper.thumbnail_managed_windows,
+            thumbnail_wm_states: helper.thumbnail_wm_states,
             client_minimize_on_switch: helper.client_minimize_on_switch,
             client_minimize_show_overlay: helper.client_minimize_show_overlay,
+            client_minimize_overlay_text: helper.client_minimize_overlay_text,
+            client_minimize_overlay_text_size: helper.client_minimize_overlay_text_size,
+            client_minimize_overlay_text_color: helper.client_minimize_overlay_text_color,
+            client_minimize_overlay_dim: helper.client_minimize_overlay_dim,
+            client_minimize_overlay_icon: helper.client_minimize_overlay_icon,
+            client_workspace_follow: helper.client_workspace_follow,
             hotkey_backend: helper.hotkey_backend,
             hotkey_input_device: helper.hotkey_input_device,
             hotkey_logged_out_cycle: helper.hotkey_logged_out_cycle,
             hotkey_require_eve_focus: helper.hotkey_require_eve_focus,
             hotkey_cycle_reset_index: helper.hotkey_cycle_reset_index,
+            hotkey_cycle_monitor: helper.hotkey_cycle_monitor,
+            hotkey_switch_osd: helper.hotkey_switch_osd,
+            hotkey_skip_persist: helper.hotkey_skip_persist,
+            hotkey_skipped_characters: helper.hotkey_skipped_characters,
+            client_account_labels: helper.client_account_labels,
             hotkey_profile_switch: helper.hotkey_profile_switch,
             hotkey_toggle_skip: helper.hotkey_toggle_skip,
             hotkey_toggle_previews: helper.hotkey_toggle_previews,
+            hotkey_save_positions: helper.hotkey_save_positions,
+            hotkey_undo_move: helper.hotkey_undo_move,
+            hotkey_active_forward: helper.hotkey_active_forward,
+            hotkey_active_backward: helper.hotkey_active_backward,
+            hotkey_next_group: helper.hotkey_next_group,
+            hotkey_screenshot: helper.hotkey_screenshot,
+            hotkey_toggle_hotkey_overlay: helper.hotkey_toggle_hotkey_overlay,
+            hotkey_swap_positions: helper.hotkey_swap_positions,
             cycle_groups, // Use the migrated or valid groups
             character_hotkeys: helper.character_hotkeys,
             character_thumbnails,
             custom_source_thumbnails,
             custom_windows: helper.custom_windows,
+            companion_windows: helper.companion_windows,
+            x11_display: helper.x11_display,
         }
     }
 }
@@ -230,6 +348,8 @@ impl<'de> Deserialize<'de> for Profile {
                 #[serde(default = "default_thumbnail_enabled")]
                 pub thumbnail_enabled: bool,
                 pub thumbnail_opacity: u8,
+                #[serde(default)]
+                pub thumbnail_argb_visual: bool,
                 #[serde(default = "default_border_enabled", alias = "thumbnail_border")]
                 pub thumbnail_active_border: bool,
                 #[serde(alias = "thumbnail_border_size")]
@@ -248,21 +368,67 @@ impl<'de> Deserialize<'de> for Profile {
                 pub thumbnail_text_size: u16,
                 pub thumbnail_text_x: i16,
                 pub thumbnail_text_y: i16,
+                #[serde(default)]
+                pub thumbnail_text_anchor: TextAnchor,
+                #[serde(default = "default_badge_anchor")]
+                pub thumbnail_badge_anchor: TextAnchor,
                 #[serde(default = "default_text_font_family")]
                 pub thumbnail_text_font: String,
                 pub thumbnail_text_color: String,
+                #[serde(default)]
+                pub thumbnail_scale_percent: u16,
+                #[serde(default)]
+                pub thumbnail_skipped_style: SkippedStyle,
+                #[serde(default = "default_skipped_color")]
+                pub thumbnail_skipped_color: String,
+                #[serde(default)]
+                pub thumbnail_cycle_order_group: String,
+                #[serde(default)]
+                pub thumbnail_next_target_highlight: bool,
+                #[serde(default = "default_next_target_color")]
+                pub thumbnail_next_target_color: String,
                 #[serde(default = "default_auto_save_thumbnail_positions")]
                 pub thumbnail_auto_save_position: bool,
+                #[serde(default)]
+                pub thumbnail_save_on_exit: bool,
+                #[serde(default)]
+                pub thumbnail_autosave_interval_secs: u32,
                 #[serde(default = "default_snap_threshold")]
                 pub thumbnail_snap_threshold: u16,
                 #[serde(default)]
+                pub thumbnail_snap_gap: u16,
+                #[serde(default)]
+                pub thumbnail_tooltips: bool,
+                #[serde(default)]
                 pub thumbnail_hide_not_focused: bool,
+                #[serde(default)]
+                pub thumbnail_do_not_disturb_classes: Vec<String>,
                 #[serde(default = "default_preserve_thumbnail_position_on_swap")]
                 pub thumbnail_preserve_position_on_swap: bool,
                 #[serde(default)]
+                pub thumbnail_follow_source_monitor: bool,
+                #[serde(default)]
+                pub thumbnail_position_anchor: PositionAnchor,
+                #[serde(default)]
+                pub thumbnail_managed_windows: bool,
+                #[serde(default)]
+                pub thumbnail_wm_states: ThumbnailWmStates,
+                #[serde(default)]
                 pub client_minimize_on_switch: bool,
                 #[serde(default)]
                 pub client_minimize_show_overlay: bool,
+                #[serde(default)]
+                pub client_minimize_overlay_text: String,
+                #[serde(default)]
+                pub client_minimize_overlay_text_size: u16,
+                #[serde(default)]
+                pub client_minimize_overlay_text_color: String,
+                #[serde(default = "default_minimize_overlay_dim")]
+                pub client_minimize_overlay_dim: u8,
+                #[serde(default)]
+                pub client_minimize_overlay_icon: String,
+                #[serde(default)]
+                pub client_workspace_follow: WorkspaceFollow,
                 #[serde(default = "default_hotkey_backend")]
                 pub hotkey_backend: HotkeyBackendType,
                 #[serde(default)]
@@ -276,12 +442,38 @@ impl<'de> Deserialize<'de> for Profile {
                 #[serde(default)]
                 pub hotkey_cycle_reset_index: bool,
                 #[serde(default)]
+                pub hotkey_cycle_monitor: CycleMonitor,
+                #[serde(default)]
+                pub hotkey_switch_osd: bool,
+                #[serde(default)]
+                pub hotkey_skip_persist: bool,
+                #[serde(default)]
+                pub hotkey_skipped_characters: Vec<String>,
+                #[serde(default)]
+                pub client_account_labels: Vec<String>,
+                #[serde(default)]
                 pub hotkey_profile_switch: Option<crate::config::HotkeyBinding>,
                 #[serde(default)]
                 pub hotkey_toggle_skip: Option<crate::config::HotkeyBinding>,
                 #[serde(default)]
                 pub hotkey_toggle_previews: Option<crate::config::HotkeyBinding>,
                 #[serde(default)]
+                pub hotkey_save_positions: Option<crate::config::HotkeyBinding>,
+                #[serde(default)]
+                pub hotkey_undo_move: Option<crate::config::HotkeyBinding>,
+                #[serde(default)]
+                pub hotkey_active_forward: Option<crate::config::HotkeyBinding>,
+                #[serde(default)]
+                pub hotkey_active_backward: Option<crate::config::HotkeyBinding>,
+                #[serde(default)]
+                pub hotkey_next_group: Option<crate::config::HotkeyBinding>,
+                #[serde(default)]
+                pub hotkey_screenshot: Option<crate::config::HotkeyBinding>,
+                #[serde(default)]
+                pub hotkey_toggle_hotkey_overlay: Option<crate::config::HotkeyBinding>,
+                #[serde(default)]
+                pub hotkey_swap_positions: Option<crate::config::HotkeyBinding>,
+                #[serde(default)]
                 pub character_hotkeys: HashMap<String, crate::config::HotkeyBinding>,
                 #[serde(default)]
                 pub character_thumbnails: HashMap<String, CharacterSettings>,
@@ -289,6 +481,10 @@ impl<'de> Deserialize<'de> for Profile {
                 pub custom_source_thumbnails: HashMap<String, CharacterSettings>,
                 #[serde(default)]
                 pub custom_windows: Vec<CustomWindowRule>,
+                #[serde(default)]
+                pub companion_windows: Vec<crate::config::profile::CompanionLink>,
+                #[serde(default)]
+                pub x11_display: String,
             }
 
             #[derive(Deserialize)]
@@ -297,12 +493,17 @@ impl<'de> Deserialize<'de> for Profile {
                 pub cycle_list: Vec<CycleSlotBinary>,
                 pub hotkey_forward: Option<crate::config::HotkeyBinding>,
                 pub hotkey_backward: Option<crate::config::HotkeyBinding>,
+                pub hotkey_toggle_visibility: Option<crate::config::HotkeyBinding>,
+                pub color: Option<String>,
+                pub auto_include_new: bool,
+                pub auto_include_hotkey: bool,
             }
 
             #[derive(Deserialize)]
             pub enum CycleSlotBinary {
                 Eve(String),
                 Source(String),
+                Group(String),
             }
 
             let p = ProfileBinary::deserialize(deserializer)?;
@@ -321,10 +522,17 @@ impl<'de> Deserialize<'de> for Profile {
                             CycleSlotBinary::Source(n) => {
                                 crate::config::profile::CycleSlot::Source(n)
                             }
+                            CycleSlotBinary::Group(n) => {
+                                crate::config::profile::CycleSlot::Group(n)
+                            }
                         })
                         .collect(),
                     hotkey_forward: g.hotkey_forward,
                     hotkey_backward: g.hotkey_backward,
+                    hotkey_toggle_visibility: g.hotkey_toggle_visibility,
+                    color: g.color,
+                    auto_include_new: g.auto_include_new,
+                    auto_include_hotkey: g.auto_include_hotkey,
                 })
                 .collect();
 
@@ -335,6 +543,7 @@ impl<'de> Deserialize<'de> for Profile {
                 thumbnail_default_height: p.thumbnail_default_height,
                 thumbnail_enabled: p.thumbnail_enabled,
                 thumbnail_opacity: p.thumbnail_opacity,
+                thumbnail_argb_visual: p.thumbnail_argb_visual,
                 thumbnail_active_border: p.thumbnail_active_border,
                 thumbnail_active_border_size: p.thumbnail_active_border_size,
                 thumbnail_active_border_color: p.thumbnail_active_border_color,
@@ -344,27 +553,65 @@ impl<'de> Deserialize<'de> for Profile {
                 thumbnail_text_size: p.thumbnail_text_size,
                 thumbnail_text_x: p.thumbnail_text_x,
                 thumbnail_text_y: p.thumbnail_text_y,
+                thumbnail_text_anchor: p.thumbnail_text_anchor,
+                thumbnail_badge_anchor: p.thumbnail_badge_anchor,
                 thumbnail_text_font: p.thumbnail_text_font,
                 thumbnail_text_color: p.thumbnail_text_color,
+                thumbnail_scale_percent: p.thumbnail_scale_percent,
+                thumbnail_skipped_style: p.thumbnail_skipped_style,
+                thumbnail_skipped_color: p.thumbnail_skipped_color,
+                thumbnail_cycle_order_group: p.thumbnail_cycle_order_group,
+                thumbnail_next_target_highlight: p.thumbnail_next_target_highlight,
+                thumbnail_next_target_color: p.thumbnail_next_target_color,
                 thumbnail_auto_save_position: p.thumbnail_auto_save_position,
+                thumbnail_save_on_exit: p.thumbnail_save_on_exit,
+                thumbnail_autosave_interval_secs: p.thumbnail_autosave_interval_secs,
                 thumbnail_snap_threshold: p.thumbnail_snap_threshold,
+                thumbnail_snap_gap: p.thumbnail_snap_gap,
+                thumbnail_tooltips: p.thumbnail_tooltips,
                 thumbnail_hide_not_focused: p.thumbnail_hide_not_focused,
+                thumbnail_do_not_disturb_classes: p.thumbnail_do_not_disturb_classes,
                 thumbnail_preserve_position_on_swap: p.thumbnail_preserve_position_on_swap,
+                thumbnail_follow_source_monitor: p.thumbnail_follow_source_monitor,
+                thumbnail_position_anchor: p.thumbnail_position_anchor,
+                thumbnail_managed_windows: p.thumbnail_managed_windows,
+                thumbnail_wm_states: p.thumbnail_wm_states,
                 client_minimize_on_switch: p.client_minimize_on_switch,
                 client_minimize_show_overlay: p.client_minimize_show_overlay,
+                client_minimize_overlay_text: p.client_minimize_overlay_text,
+                client_minimize_overlay_text_size: p.client_minimize_overlay_text_size,
+                client_minimize_overlay_text_color: p.client_minimize_overlay_text_color,
+                client_minimize_overlay_dim: p.client_minimize_overlay_dim,
+                client_minimize_overlay_icon: p.client_minimize_overlay_icon,
+                client_workspace_follow: p.client_workspace_follow,
                 hotkey_backend: p.hotkey_backend,
                 hotkey_input_device: p.hotkey_input_device,
                 cycle_groups,
                 hotkey_logged_out_cycle: p.hotkey_logged_out_cycle,
                 hotkey_require_eve_focus: p.hotkey_require_eve_focus,
                 hotkey_cycle_reset_index: p.hotkey_cycle_reset_index,
+                hotkey_cycle_monitor: p.hotkey_cycle_monitor,
+                hotkey_switch_osd: p.hotkey_switch_osd,
+                hotkey_skip_persist: p.hotkey_skip_persist,
+                hotkey_skipped_characters: p.hotkey_skipped_characters,
+                client_account_labels: p.client_account_labels,
                 hotkey_profile_switch: p.hotkey_profile_switch,
                 hotkey_toggle_skip: p.hotkey_toggle_skip,
                 hotkey_toggle_previews: p.hotkey_toggle_previews,
+                hotkey_save_positions: p.hotkey_save_positions,
+                hotkey_undo_move: p.hotkey_undo_move,
+                hotkey_active_forward: p.hotkey_active_forward,
+                hotkey_active_backward: p.hotkey_active_backward,
+                hotkey_next_group: p.hotkey_next_group,
+                hotkey_screenshot: p.hotkey_screenshot,
+                hotkey_toggle_hotkey_overlay: p.hotkey_toggle_hotkey_overlay,
+                hotkey_swap_positions: p.hotkey_swap_positions,
                 character_hotkeys: p.character_hotkeys,
                 character_thumbnails: p.character_thumbnails,
                 custom_source_thumbnails: p.custom_source_thumbnails,
                 custom_windows: p.custom_windows,
+                companion_windows: p.companion_windows,
+                x11_display: p.x11_display,
             })
         }
     }
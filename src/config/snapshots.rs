@@ -0,0 +1,74 @@
+//! AFK snapshot settings
+//!
+//! While enabled, the daemon saves a thumbnail-sized picture of every client at a fixed
+//! interval and keeps only the newest ones per character, so what happened on an unwatched
+//! alt can be reviewed afterwards.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// AFK snapshot settings (global)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AfkSnapshots {
+    pub enabled: bool,
+    /// Minutes between snapshots
+    pub interval_minutes: u32,
+    /// Snapshots kept per character; older ones are deleted
+    pub retention: u32,
+}
+
+impl Default for AfkSnapshots {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_minutes: 5,
+            retention: 48,
+        }
+    }
+}
+
+impl AfkSnapshots {
+    /// Lowest and highest accepted `interval_minutes`
+    pub const INTERVAL_RANGE: std::ops::RangeInclusive<u32> = 1..=120;
+
+    /// Lowest and highest accepted `retention`
+    pub const RETENTION_RANGE: std::ops::RangeInclusive<u32> = 1..=1000;
+
+    /// Time between snapshots
+    pub fn interval(&self) -> Duration {
+        let minutes = self
+            .interval_minutes
+            .clamp(*Self::INTERVAL_RANGE.start(), *Self::INTERVAL_RANGE.end());
+        Duration::from_secs(u64::from(minutes) * 60)
+    }
+
+    /// Snapshots kept per character
+    pub fn retention_count(&self) -> usize {
+        self.retention
+            .clamp(*Self::RETENTION_RANGE.start(), *Self::RETENTION_RANGE.end()) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interval_and_retention_are_clamped() {
+        let mut snapshots = AfkSnapshots::default();
+        assert_eq!(snapshots.interval(), Duration::from_secs(300));
+        assert_eq!(snapshots.retention_count(), 48);
+
+        snapshots.interval_minutes = 0;
+        snapshots.retention = 0;
+        assert_eq!(snapshots.interval(), Duration::from_secs(60));
+        assert_eq!(snapshots.retention_count(), 1);
+
+        snapshots.interval_minutes = 10_000;
+        snapshots.retention = 1_000_000;
+        assert_eq!(snapshots.interval(), Duration::from_secs(120 * 60));
+        assert_eq!(snapshots.retention_count(), 1000);
+    }
+}
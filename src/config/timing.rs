@@ -0,0 +1,113 @@
+//! Advanced timing settings
+//!
+//! Intervals the Manager and daemon otherwise take from `constants`. The defaults suit most
+//! machines; very slow machines may want longer intervals, very fast ones shorter.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::common::constants::{input, manager_ui, positioning, scan};
+
+/// Advanced timing settings (global)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Timing {
+    /// How often the Manager polls the daemon for messages and redraws, in milliseconds
+    pub event_poll_ms: u64,
+    /// How often the Manager checks whether the daemon process is still alive, in milliseconds
+    pub health_check_ms: u64,
+    /// Shortest time between two saves of moved thumbnail positions, in milliseconds
+    pub save_debounce_ms: u64,
+    /// Seconds between the daemon's full window rescans
+    pub rescan_interval_secs: u64,
+    /// Distance in pixels from the client's top-left corner at which new thumbnails appear
+    pub spawn_offset: i16,
+    /// Longest gap between the taps of a double-tap hotkey, in milliseconds
+    pub double_tap_ms: u64,
+    /// How long a long-press hotkey has to be held, in milliseconds
+    pub long_press_ms: u64,
+}
+
+impl Default for Timing {
+    fn default() -> Self {
+        Self {
+            event_poll_ms: manager_ui::DAEMON_CHECK_INTERVAL_MS,
+            health_check_ms: manager_ui::DAEMON_CHECK_INTERVAL_MS,
+            save_debounce_ms: manager_ui::AUTO_SAVE_DELAY_MS,
+            rescan_interval_secs: scan::RESCAN_INTERVAL_SECS,
+            spawn_offset: positioning::DEFAULT_SPAWN_OFFSET,
+            double_tap_ms: input::DOUBLE_TAP_MS,
+            long_press_ms: input::LONG_PRESS_MS,
+        }
+    }
+}
+
+impl Timing {
+    /// Accepted range of the millisecond intervals
+    pub const MS_RANGE: std::ops::RangeInclusive<u64> = 50..=10_000;
+    /// Accepted range of `rescan_interval_secs`
+    pub const RESCAN_RANGE: std::ops::RangeInclusive<u64> = 5..=600;
+    /// Accepted range of `spawn_offset`
+    pub const SPAWN_OFFSET_RANGE: std::ops::RangeInclusive<i16> = 0..=500;
+
+    fn ms(value: u64) -> Duration {
+        Duration::from_millis(value.clamp(*Self::MS_RANGE.start(), *Self::MS_RANGE.end()))
+    }
+
+    pub fn event_poll(&self) -> Duration {
+        Self::ms(self.event_poll_ms)
+    }
+
+    pub fn health_check(&self) -> Duration {
+        Self::ms(self.health_check_ms)
+    }
+
+    pub fn save_debounce(&self) -> Duration {
+        Self::ms(self.save_debounce_ms)
+    }
+
+    pub fn double_tap(&self) -> Duration {
+        Self::ms(self.double_tap_ms)
+    }
+
+    pub fn long_press(&self) -> Duration {
+        Self::ms(self.long_press_ms)
+    }
+
+    pub fn rescan_interval(&self) -> Duration {
+        Duration::from_secs(
+            self.rescan_interval_secs
+                .clamp(*Self::RESCAN_RANGE.start(), *Self::RESCAN_RANGE.end()),
+        )
+    }
+
+    pub fn spawn_offset(&self) -> i16 {
+        self.spawn_offset.clamp(
+            *Self::SPAWN_OFFSET_RANGE.start(),
+            *Self::SPAWN_OFFSET_RANGE.end(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intervals_are_clamped() {
+        let mut timing = Timing::default();
+        assert_eq!(timing.event_poll(), Duration::from_millis(500));
+
+        timing.event_poll_ms = 0;
+        timing.rescan_interval_secs = 0;
+        timing.spawn_offset = -40;
+        assert_eq!(timing.event_poll(), Duration::from_millis(50));
+        assert_eq!(timing.rescan_interval(), Duration::from_secs(5));
+        assert_eq!(timing.spawn_offset(), 0);
+
+        let timing: Timing = serde_json::from_str(r#"{"save_debounce_ms": 250}"#).unwrap();
+        assert_eq!(timing.save_debounce(), Duration::from_millis(250));
+        assert_eq!(timing.health_check(), Duration::from_millis(500));
+    }
+}
@@ -0,0 +1,611 @@
+//! Config file validation
+//!
+//! Backs `eve-preview-manager check-config` and the Manager's "Validate" button. Finds what
+//! loading the config silently tolerates or rejects: unknown fields, invalid colors, cycle
+//! group members that point nowhere, hotkey conflicts and thumbnail sizes out of range.
+//! Every issue carries the JSON path of the offending value.
+
+use std::fmt;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use super::HotkeyBinding;
+use super::profile::{Config, CustomWindowRule, CycleGroup, CycleSlot, GlobalSettings, Profile};
+use crate::common::color::HexColor;
+use crate::common::constants::defaults::thumbnail;
+use crate::common::types::CharacterSettings;
+
+/// Keys older configs used for fields that have since been renamed. Still read, so not unknown.
+const LEGACY_KEYS: [&str; 6] = [
+    "thumbnail_border",
+    "thumbnail_border_size",
+    "thumbnail_border_color",
+    "thumbnail_inactive_border_size",
+    "characters",
+    "slots",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// The value is rejected or cannot work as intended
+    Error,
+    /// The value is ignored or likely a mistake
+    Warning,
+}
+
+impl Severity {
+    pub fn label(self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        }
+    }
+}
+
+/// A single problem found in the config
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Issue {
+    pub severity: Severity,
+    /// JSON path of the offending value, e.g. `$.profiles[0].thumbnail_text_color`
+    pub path: String,
+    pub message: String,
+}
+
+impl Issue {
+    fn error(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Error,
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+
+    fn warning(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Warning,
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {}: {}",
+            self.severity.label(),
+            self.path,
+            self.message
+        )
+    }
+}
+
+/// Check the config file at `path`. Fails only if the file cannot be read.
+pub fn check_file(path: &Path) -> Result<Vec<Issue>> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config from {:?}", path))?;
+    Ok(check_str(&contents))
+}
+
+/// Check the text of a config file, errors first
+pub fn check_str(contents: &str) -> Vec<Issue> {
+    let raw: Value = match serde_json::from_str(contents) {
+        Ok(raw) => raw,
+        Err(err) => return vec![Issue::error("$", format!("Invalid JSON: {err}"))],
+    };
+    let config: Config = match serde_json::from_value(raw.clone()) {
+        Ok(config) => config,
+        Err(err) => {
+            let mut issues = Vec::new();
+            locate_parse_errors(&raw, &mut issues);
+            if issues.is_empty() {
+                issues.push(Issue::error("$", err.to_string()));
+            }
+            return issues;
+        }
+    };
+
+    let mut issues = Vec::new();
+    if let Ok(known) = serde_json::to_value(&config) {
+        unknown_fields("$", &raw, &known, &mut issues);
+    }
+    invalid_colors("$", &raw, &mut issues);
+    issues.extend(check_config(&config));
+    issues.sort_by_key(|issue| issue.severity);
+    issues
+}
+
+/// Checks of a parsed config: cycle group members, hotkey conflicts and geometry
+fn check_config(config: &Config) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    for (index, profile) in config.profiles.iter().enumerate() {
+        let path = format!("$.profiles[{index}]");
+        cycle_group_members(&path, profile, &mut issues);
+        hotkey_conflicts(index, config, &mut issues);
+        geometry(&path, profile, &mut issues);
+    }
+    issues
+}
+
+/// Report what [`Config`] fails to parse, as precisely as possible
+fn locate_parse_errors(raw: &Value, issues: &mut Vec<Issue>) {
+    let Some(object) = raw.as_object() else {
+        issues.push(Issue::error("$", "Expected a JSON object"));
+        return;
+    };
+    if let Some(global) = object.get("global") {
+        field_errors::<GlobalSettings>(
+            "$.global",
+            global,
+            &GlobalSettings::default(),
+            |_, _, _| false,
+            issues,
+        );
+    }
+    match object.get("profiles") {
+        Some(Value::Array(profiles)) => {
+            for (index, profile) in profiles.iter().enumerate() {
+                field_errors::<Profile>(
+                    &format!("$.profiles[{index}]"),
+                    profile,
+                    &Profile::default(),
+                    profile_field_errors,
+                    issues,
+                );
+            }
+        }
+        Some(_) => issues.push(Issue::error("$.profiles", "Expected a list of profiles")),
+        None => {}
+    }
+}
+
+/// Report each field of `raw` that fails to parse as part of a `T`, by parsing it on top of
+/// `base`. `refine` may report a failing field more precisely and returns whether it did.
+/// If every field parses on its own, the object as a whole is reported (e.g. a missing field).
+fn field_errors<T: DeserializeOwned + serde::Serialize>(
+    path: &str,
+    raw: &Value,
+    base: &T,
+    refine: impl Fn(&str, &Value, &mut Vec<Issue>) -> bool,
+    issues: &mut Vec<Issue>,
+) {
+    let Some(object) = raw.as_object() else {
+        if let Err(err) = serde_json::from_value::<T>(raw.clone()) {
+            issues.push(Issue::error(path, err.to_string()));
+        }
+        return;
+    };
+    let Ok(Value::Object(base)) = serde_json::to_value(base) else {
+        return;
+    };
+    let before = issues.len();
+    for (key, value) in object {
+        let mut probe = base.clone();
+        probe.insert(key.clone(), value.clone());
+        if let Err(err) = serde_json::from_value::<T>(Value::Object(probe)) {
+            let path = child_path(path, key);
+            if !refine(&path, value, issues) {
+                issues.push(Issue::error(path, err.to_string()));
+            }
+        }
+    }
+    if issues.len() == before
+        && let Err(err) = serde_json::from_value::<T>(raw.clone())
+    {
+        issues.push(Issue::error(path, err.to_string()));
+    }
+}
+
+/// Narrow a failing profile field down to the entry of a map or list
+fn profile_field_errors(path: &str, value: &Value, issues: &mut Vec<Issue>) -> bool {
+    let key = path.rsplit('.').next().unwrap_or_default();
+    let before = issues.len();
+    match key {
+        "character_thumbnails" | "custom_source_thumbnails" => {
+            let base = CharacterSettings::new(0, 0, 0, 0);
+            for (entry_path, entry) in entries(path, value) {
+                field_errors(&entry_path, entry, &base, |_, _, _| false, issues);
+            }
+        }
+        "cycle_groups" => {
+            let base = CycleGroup::default_group();
+            for (entry_path, entry) in entries(path, value) {
+                field_errors(&entry_path, entry, &base, |_, _, _| false, issues);
+            }
+        }
+        "character_hotkeys" => {
+            for (entry_path, entry) in entries(path, value) {
+                entry_error::<HotkeyBinding>(&entry_path, entry, issues);
+            }
+        }
+        "custom_windows" => {
+            for (entry_path, entry) in entries(path, value) {
+                entry_error::<CustomWindowRule>(&entry_path, entry, issues);
+            }
+        }
+        _ => {}
+    }
+    issues.len() > before
+}
+
+/// Entries of a JSON object or array with their paths
+fn entries<'a>(path: &str, value: &'a Value) -> Vec<(String, &'a Value)> {
+    match value {
+        Value::Object(map) => map
+            .iter()
+            .map(|(key, entry)| (child_path(path, key), entry))
+            .collect(),
+        Value::Array(list) => list
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| (format!("{path}[{index}]"), entry))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn entry_error<T: DeserializeOwned>(path: &str, value: &Value, issues: &mut Vec<Issue>) {
+    if let Err(err) = serde_json::from_value::<T>(value.clone()) {
+        issues.push(Issue::error(path, err.to_string()));
+    }
+}
+
+/// Keys present in `raw` that do not survive a round trip through the config types
+fn unknown_fields(path: &str, raw: &Value, known: &Value, issues: &mut Vec<Issue>) {
+    match (raw, known) {
+        (Value::Object(raw), Value::Object(known)) => {
+            for (key, value) in raw {
+                match known.get(key) {
+                    Some(known) => unknown_fields(&child_path(path, key), value, known, issues),
+                    None if LEGACY_KEYS.contains(&key.as_str()) => {}
+                    None => issues.push(Issue::warning(
+                        child_path(path, key),
+                        "Unknown field, ignored",
+                    )),
+                }
+            }
+        }
+        (Value::Array(raw), Value::Array(known)) => {
+            for (index, (value, known)) in raw.iter().zip(known).enumerate() {
+                unknown_fields(&format!("{path}[{index}]"), value, known, issues);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Color strings (any key ending in `color`) that are not hex colors
+fn invalid_colors(path: &str, raw: &Value, issues: &mut Vec<Issue>) {
+    match raw {
+        Value::Object(map) => {
+            for (key, value) in map {
+                let child = child_path(path, key);
+                match value {
+                    Value::String(color)
+                        if key.ends_with("color")
+                            && !color.is_empty()
+                            && HexColor::parse(color).is_none() =>
+                    {
+                        issues.push(Issue::error(
+                            child,
+                            format!("Invalid color \"{color}\", expected #RRGGBB or #AARRGGBB"),
+                        ));
+                    }
+                    _ => invalid_colors(&child, value, issues),
+                }
+            }
+        }
+        Value::Array(list) => {
+            for (index, value) in list.iter().enumerate() {
+                invalid_colors(&format!("{path}[{index}]"), value, issues);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Cycle group entries naming characters, sources or groups the profile does not have
+fn cycle_group_members(path: &str, profile: &Profile, issues: &mut Vec<Issue>) {
+    for (group_index, group) in profile.cycle_groups.iter().enumerate() {
+        for (slot_index, slot) in group.cycle_list.iter().enumerate() {
+            let message = match slot {
+                CycleSlot::Eve(name) if !profile.character_thumbnails.contains_key(name) => {
+                    format!("Character \"{name}\" is unknown to this profile")
+                }
+                CycleSlot::Source(alias)
+                    if !profile
+                        .custom_windows
+                        .iter()
+                        .any(|rule| rule.alias == *alias) =>
+                {
+                    format!("No custom source is named \"{alias}\"")
+                }
+                CycleSlot::Group(name) if *name == group.name => {
+                    "Cycle group includes itself".to_string()
+                }
+                CycleSlot::Group(name) if !profile.cycle_groups.iter().any(|g| g.name == *name) => {
+                    format!("No cycle group is named \"{name}\"")
+                }
+                _ => continue,
+            };
+            issues.push(Issue::warning(
+                format!("{path}.cycle_groups[{group_index}].cycle_list[{slot_index}]"),
+                message,
+            ));
+        }
+    }
+}
+
+/// Bindings active while the profile at `index` runs that collide. Characters and custom
+/// sources may share a key (it cycles through them); any other pair is a conflict.
+fn hotkey_conflicts(index: usize, config: &Config, issues: &mut Vec<Issue>) {
+    let profile = &config.profiles[index];
+    let path = format!("$.profiles[{index}]");
+
+    // (path, binding, may share its key with other characters and sources)
+    let mut bindings: Vec<(String, &HotkeyBinding, bool)> = Vec::new();
+    let commands = [
+        (
+            "hotkey_toggle_skip".to_string(),
+            &profile.hotkey_toggle_skip,
+        ),
+        (
+            "hotkey_toggle_previews".to_string(),
+            &profile.hotkey_toggle_previews,
+        ),
+        (
+            "hotkey_save_positions".to_string(),
+            &profile.hotkey_save_positions,
+        ),
+        ("hotkey_undo_move".to_string(), &profile.hotkey_undo_move),
+        (
+            "hotkey_active_forward".to_string(),
+            &profile.hotkey_active_forward,
+        ),
+        (
+            "hotkey_active_backward".to_string(),
+            &profile.hotkey_active_backward,
+        ),
+        ("hotkey_next_group".to_string(), &profile.hotkey_next_group),
+        ("hotkey_screenshot".to_string(), &profile.hotkey_screenshot),
+        (
+            "hotkey_toggle_hotkey_overlay".to_string(),
+            &profile.hotkey_toggle_hotkey_overlay,
+        ),
+        (
+            "hotkey_swap_positions".to_string(),
+            &profile.hotkey_swap_positions,
+        ),
+    ];
+    let group_commands = profile
+        .cycle_groups
+        .iter()
+        .enumerate()
+        .flat_map(|(index, group)| {
+            [
+                ("hotkey_forward", &group.hotkey_forward),
+                ("hotkey_backward", &group.hotkey_backward),
+                ("hotkey_toggle_visibility", &group.hotkey_toggle_visibility),
+            ]
+            .map(|(field, binding)| (format!("cycle_groups[{index}].{field}"), binding))
+        });
+    for (field, binding) in commands.into_iter().chain(group_commands) {
+        if let Some(binding) = binding {
+            bindings.push((format!("{path}.{field}"), binding, false));
+        }
+    }
+    // Profile switch keys of every profile are active in each of them
+    for (other_index, other) in config.profiles.iter().enumerate() {
+        if let Some(binding) = &other.hotkey_profile_switch {
+            bindings.push((
+                format!("$.profiles[{other_index}].hotkey_profile_switch"),
+                binding,
+                false,
+            ));
+        }
+    }
+    let mut characters: Vec<_> = profile.character_hotkeys.iter().collect();
+    characters.sort_by_key(|(name, _)| name.as_str());
+    for (name, binding) in characters {
+        bindings.push((
+            child_path(&format!("{path}.character_hotkeys"), name),
+            binding,
+            true,
+        ));
+    }
+    for (rule_index, rule) in profile.custom_windows.iter().enumerate() {
+        if let Some(binding) = &rule.hotkey {
+            bindings.push((
+                format!("{path}.custom_windows[{rule_index}].hotkey"),
+                binding,
+                true,
+            ));
+        }
+    }
+
+    for (later, (path, binding, shared)) in bindings.iter().enumerate() {
+        let earlier = bindings[..later].iter().find(|(_, other, other_shared)| {
+            !(*shared && *other_shared) && binding.overlaps(other)
+        });
+        if let Some((other_path, _, _)) = earlier {
+            let issue = Issue::error(
+                path.clone(),
+                format!(
+                    "Hotkey {} is also bound at {other_path}",
+                    binding.display_name()
+                ),
+            );
+            // Profile switch keys are compared again for every profile
+            if !issues.contains(&issue) {
+                issues.push(issue);
+            }
+        }
+    }
+}
+
+/// Thumbnail sizes outside what the Manager allows
+fn geometry(path: &str, profile: &Profile, issues: &mut Vec<Issue>) {
+    let mut size = |path: String, width: u16, height: u16, unset_allowed: bool| {
+        let in_range = |value: u16, min: u16, max: u16| {
+            (unset_allowed && value == 0) || (min..=max).contains(&value)
+        };
+        if !in_range(width, thumbnail::MIN_WIDTH, thumbnail::MAX_WIDTH)
+            || !in_range(height, thumbnail::MIN_HEIGHT, thumbnail::MAX_HEIGHT)
+        {
+            issues.push(Issue::warning(
+                path,
+                format!(
+                    "Size {width}x{height} is outside {}x{} to {}x{}",
+                    thumbnail::MIN_WIDTH,
+                    thumbnail::MIN_HEIGHT,
+                    thumbnail::MAX_WIDTH,
+                    thumbnail::MAX_HEIGHT
+                ),
+            ));
+        }
+    };
+
+    size(
+        format!("{path}.thumbnail_default_width"),
+        profile.thumbnail_default_width,
+        profile.thumbnail_default_height,
+        false,
+    );
+    for key in ["character_thumbnails", "custom_source_thumbnails"] {
+        let map = if key == "character_thumbnails" {
+            &profile.character_thumbnails
+        } else {
+            &profile.custom_source_thumbnails
+        };
+        let mut names: Vec<_> = map.keys().collect();
+        names.sort();
+        for name in names {
+            let dimensions = map[name].dimensions;
+            // Zero means the profile's default size
+            size(
+                child_path(&format!("{path}.{key}"), name),
+                dimensions.width,
+                dimensions.height,
+                true,
+            );
+        }
+    }
+    for (index, rule) in profile.custom_windows.iter().enumerate() {
+        size(
+            format!("{path}.custom_windows[{index}].default_width"),
+            rule.default_width,
+            rule.default_height,
+            false,
+        );
+    }
+
+    if profile.thumbnail_opacity > 100 {
+        issues.push(Issue::warning(
+            format!("{path}.thumbnail_opacity"),
+            format!("Opacity {}% is above 100%", profile.thumbnail_opacity),
+        ));
+    }
+}
+
+/// Path of `key` inside the object at `path`, quoting keys that are not plain identifiers
+fn child_path(path: &str, key: &str) -> String {
+    let plain = key
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if plain {
+        format!("{path}.{key}")
+    } else {
+        format!("{path}[{}]", Value::String(key.to_string()))
+    }
+}
+
+/// Plain-text report, one issue per line
+pub fn format_report(issues: &[Issue]) -> String {
+    if issues.is_empty() {
+        return "No problems found".to_string();
+    }
+    let errors = issues
+        .iter()
+        .filter(|issue| issue.severity == Severity::Error)
+        .count();
+    let mut report: Vec<String> = issues.iter().map(Issue::to_string).collect();
+    report.push(format!(
+        "{} error(s), {} warning(s)",
+        errors,
+        issues.len() - errors
+    ));
+    report.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_json(edit: impl FnOnce(&mut Value)) -> String {
+        let mut config = Config::default();
+        config.profiles[0]
+            .character_thumbnails
+            .insert("Alice".to_string(), CharacterSettings::new(0, 0, 250, 140));
+        let mut value = serde_json::to_value(&config).unwrap();
+        edit(&mut value);
+        value.to_string()
+    }
+
+    #[test]
+    fn test_default_config_is_clean() {
+        assert_eq!(check_str(&config_json(|_| {})), Vec::new());
+    }
+
+    #[test]
+    fn test_reports_problems_with_paths() {
+        let contents = config_json(|config| {
+            let profile = &mut config["profiles"][0];
+            profile["thumbnail_text_colour"] = "#FFFFFF".into();
+            profile["thumbnail_text_color"] = "white".into();
+            profile["cycle_groups"][0]["cycle_list"] =
+                serde_json::json!([{"eve": "Alice"}, {"eve": "Bob"}]);
+            profile["hotkey_toggle_skip"] = serde_json::json!(["KEY_F1"]);
+            profile["character_hotkeys"]["Alice"] = serde_json::json!(["KEY_F1"]);
+            profile["character_thumbnails"]["Alice"]["width"] = 5000.into();
+        });
+        let issues: Vec<String> = check_str(&contents).iter().map(Issue::to_string).collect();
+
+        let expected = [
+            "error: $.profiles[0].thumbnail_text_color: Invalid color \"white\"",
+            "error: $.profiles[0].character_hotkeys.Alice: Hotkey F1 is also bound at $.profiles[0].hotkey_toggle_skip",
+            "warning: $.profiles[0].thumbnail_text_colour: Unknown field",
+            "warning: $.profiles[0].cycle_groups[0].cycle_list[1]: Character \"Bob\"",
+            "warning: $.profiles[0].character_thumbnails.Alice: Size 5000x140",
+        ];
+        for prefix in expected {
+            assert!(
+                issues.iter().any(|issue| issue.starts_with(prefix)),
+                "{prefix} missing from {issues:#?}"
+            );
+        }
+        assert_eq!(issues.len(), expected.len(), "{issues:#?}");
+    }
+
+    #[test]
+    fn test_locates_parse_errors() {
+        let contents = config_json(|config| {
+            config["profiles"][0]["character_thumbnails"]["Alice Two"]["x"] = "left".into();
+        });
+        let issues = check_str(&contents);
+        assert_eq!(issues.len(), 1, "{issues:#?}");
+        assert_eq!(
+            issues[0].path,
+            "$.profiles[0].character_thumbnails[\"Alice Two\"].x"
+        );
+
+        let issues = check_str("{\"global\": ");
+        assert_eq!(issues[0].path, "$");
+        assert!(issues[0].message.starts_with("Invalid JSON"));
+    }
+}
@@ -0,0 +1,276 @@
+//! Per-character activation actions
+//!
+//! Shell commands a character (or custom source) runs right before and right after its client
+//! is focused, e.g. to unmute its audio or switch an OBS scene. They run through `sh -c` with
+//! the character name and window ID in the environment. The switch waits a moment for the
+//! "before" command so it runs first, but never for long: slow commands, and all "after" ones,
+//! finish in the background.
+//!
+//! Companion windows (custom sources linked to a character in the profile) are raised, and
+//! moved if the link has a position, once the character's client is focused.
+//!
+//! A client on another virtual desktop is pulled over or the desktop switched before it is
+//! activated, as the profile's workspace follow mode says. Otherwise that is up to the window
+//! manager, and some just mark the client urgent.
+
+use std::collections::HashMap;
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use tracing::{debug, warn};
+use x11rb::protocol::xproto::Window;
+
+use super::thumbnail::Thumbnail;
+use crate::common::constants::activation::{BEFORE_TIMEOUT_MS, CHARACTER_ENV, WINDOW_ENV};
+use crate::common::constants::x11::ALL_DESKTOPS;
+use crate::common::types::Position;
+use crate::config::DaemonConfig;
+use crate::config::profile::WorkspaceFollow;
+use crate::x11::{
+    AppContext, get_current_desktop, get_window_desktop, move_window_to_desktop, raise_window,
+    switch_desktop, unminimize_window,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    Before,
+    After,
+}
+
+/// Command `name` runs at `stage`, if it has one
+fn command<'a>(config: &'a DaemonConfig, name: &str, stage: Stage) -> Option<&'a str> {
+    let settings = config
+        .character_thumbnails
+        .get(name)
+        .or_else(|| config.custom_source_thumbnails.get(name))?;
+    let command = match stage {
+        Stage::Before => settings.activate_before.as_deref(),
+        Stage::After => settings.activate_after.as_deref(),
+    }?;
+    (!command.trim().is_empty()).then_some(command)
+}
+
+/// Run the `stage` action of `name`, whose client is `window`. A `Before` action is waited
+/// for up to `BEFORE_TIMEOUT_MS`.
+pub fn run(config: &DaemonConfig, name: &str, window: Window, stage: Stage) {
+    let Some(command) = command(config, name, stage) else {
+        return;
+    };
+    debug!(character = name, stage = ?stage, command, "Running activation action");
+
+    let child = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .env(CHARACTER_ENV, name)
+        .env(WINDOW_ENV, window.to_string())
+        .stdin(Stdio::null())
+        .spawn();
+    let mut child = match child {
+        Ok(child) => child,
+        Err(e) => {
+            warn!(character = name, error = %e, "Failed to start activation action");
+            return;
+        }
+    };
+
+    // Reap the command off the event loop, telling a waiting switch when it is done
+    let (done_tx, done_rx) = mpsc::channel();
+    let owner = name.to_string();
+    std::thread::spawn(move || {
+        match child.wait() {
+            Ok(status) if !status.success() => {
+                warn!(character = %owner, stage = ?stage, status = %status, "Activation action failed")
+            }
+            Ok(_) => {}
+            Err(e) => {
+                warn!(character = %owner, error = %e, "Failed to wait for activation action")
+            }
+        }
+        let _ = done_tx.send(());
+    });
+
+    if stage == Stage::Before
+        && done_rx
+            .recv_timeout(Duration::from_millis(BEFORE_TIMEOUT_MS))
+            .is_err()
+    {
+        debug!(
+            character = name,
+            "Activation action still running, switching without it"
+        );
+    }
+}
+
+/// Desktop change needed before activating a client on `window_desktop`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DesktopChange {
+    /// Move the client to this (the current) desktop
+    Pull(u32),
+    /// Switch to this (the client's) desktop
+    Switch(u32),
+}
+
+fn desktop_change(
+    mode: WorkspaceFollow,
+    window_desktop: Option<u32>,
+    current: Option<u32>,
+) -> Option<DesktopChange> {
+    let (window_desktop, current) = (window_desktop?, current?);
+    if window_desktop == current || window_desktop == ALL_DESKTOPS {
+        return None;
+    }
+    match mode {
+        WorkspaceFollow::WindowManager => None,
+        WorkspaceFollow::PullWindow => Some(DesktopChange::Pull(current)),
+        WorkspaceFollow::SwitchDesktop => Some(DesktopChange::Switch(window_desktop)),
+    }
+}
+
+/// Bring `window` and the current desktop together before it is activated, if the profile
+/// asks for it and the window manager reports desktops
+pub fn follow_workspace(
+    ctx: &AppContext<'_>,
+    config: &DaemonConfig,
+    window: Window,
+    timestamp: u32,
+) {
+    let mode = config.profile.client_workspace_follow;
+    if mode == WorkspaceFollow::WindowManager {
+        return;
+    }
+    let window_desktop = get_window_desktop(ctx.conn, window, ctx.atoms);
+    let current = get_current_desktop(ctx.conn, ctx.screen, ctx.atoms);
+    let (window_desktop, current) = match (window_desktop, current) {
+        (Ok(window_desktop), Ok(current)) => (window_desktop, current),
+        (Err(e), _) | (_, Err(e)) => {
+            warn!(window = window, error = %e, "Failed to query virtual desktops");
+            return;
+        }
+    };
+
+    let result = match desktop_change(mode, window_desktop, current) {
+        None => return,
+        Some(DesktopChange::Pull(desktop)) => {
+            debug!(
+                window = window,
+                desktop, "Pulling client to current desktop"
+            );
+            move_window_to_desktop(ctx.conn, ctx.screen, ctx.atoms, window, desktop)
+        }
+        Some(DesktopChange::Switch(desktop)) => {
+            debug!(window = window, desktop, "Switching to client's desktop");
+            switch_desktop(ctx.conn, ctx.screen, ctx.atoms, desktop, timestamp)
+        }
+    };
+    if let Err(e) = result {
+        warn!(window = window, error = %e, "Failed to follow client to its desktop");
+    }
+}
+
+/// Windows of the custom sources linked to `name` that currently exist, with the position
+/// each is moved to
+pub fn companions(
+    config: &DaemonConfig,
+    eve_clients: &HashMap<Window, Thumbnail<'_>>,
+    name: &str,
+) -> Vec<(Window, Option<Position>)> {
+    if name.is_empty() {
+        return Vec::new();
+    }
+    config
+        .profile
+        .companions_of(name)
+        .filter_map(|link| {
+            let (&window, _) = eve_clients
+                .iter()
+                .find(|(_, thumbnail)| thumbnail.character_name == link.source)?;
+            Some((window, link.position))
+        })
+        .collect()
+}
+
+/// Bring companion windows up without taking the focus from the activated client
+pub fn raise_companions(ctx: &AppContext<'_>, companions: &[(Window, Option<Position>)]) {
+    for &(window, position) in companions {
+        debug!(window = window, position = ?position, "Raising companion window");
+        if let Err(e) = unminimize_window(ctx.conn, ctx.screen, ctx.atoms, window)
+            .and_then(|()| raise_window(ctx.conn, window, position))
+        {
+            warn!(window = window, error = %e, "Failed to raise companion window");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::types::CharacterSettings;
+
+    #[test]
+    fn test_command_lookup() {
+        let mut config = DaemonConfig::from_config(&crate::config::profile::Config::default());
+        let mut alice = CharacterSettings::new(0, 0, 100, 100);
+        alice.activate_before = Some("pactl set-sink-input-mute 42 0".to_string());
+        alice.activate_after = Some("  ".to_string());
+        config
+            .character_thumbnails
+            .insert("Alice".to_string(), alice);
+        let mut intel = CharacterSettings::new(0, 0, 100, 100);
+        intel.activate_after = Some("obs-cli scene switch Intel".to_string());
+        config
+            .custom_source_thumbnails
+            .insert("Intel Map".to_string(), intel);
+
+        assert_eq!(
+            command(&config, "Alice", Stage::Before),
+            Some("pactl set-sink-input-mute 42 0")
+        );
+        // Blank commands are ignored
+        assert_eq!(command(&config, "Alice", Stage::After), None);
+        assert_eq!(
+            command(&config, "Intel Map", Stage::After),
+            Some("obs-cli scene switch Intel")
+        );
+        assert_eq!(command(&config, "Bob", Stage::Before), None);
+    }
+
+    #[test]
+    fn test_before_action_finishes_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let marker = dir.path().join("ran");
+        let mut config = DaemonConfig::from_config(&crate::config::profile::Config::default());
+        let mut alice = CharacterSettings::new(0, 0, 100, 100);
+        alice.activate_before = Some(format!(
+            "sleep 0.1 && echo \"$EVE_PREVIEW_MANAGER_CHARACTER\" > '{}'",
+            marker.display()
+        ));
+        config
+            .character_thumbnails
+            .insert("Alice".to_string(), alice);
+
+        run(&config, "Alice", 1, Stage::Before);
+        assert_eq!(std::fs::read_to_string(&marker).unwrap(), "Alice\n");
+    }
+
+    #[test]
+    fn test_desktop_change() {
+        use WorkspaceFollow::*;
+        assert_eq!(
+            desktop_change(PullWindow, Some(2), Some(0)),
+            Some(DesktopChange::Pull(0))
+        );
+        assert_eq!(
+            desktop_change(SwitchDesktop, Some(2), Some(0)),
+            Some(DesktopChange::Switch(2))
+        );
+        assert_eq!(desktop_change(WindowManager, Some(2), Some(0)), None);
+        // Same desktop, sticky windows and WMs without desktops need nothing
+        assert_eq!(desktop_change(SwitchDesktop, Some(1), Some(1)), None);
+        assert_eq!(
+            desktop_change(PullWindow, Some(ALL_DESKTOPS), Some(1)),
+            None
+        );
+        assert_eq!(desktop_change(SwitchDesktop, None, Some(1)), None);
+    }
+}
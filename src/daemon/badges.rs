@@ -0,0 +1,135 @@
+//! Badge layout and image loading for thumbnail overlays
+//!
+//! Badges are square tiles drawn in a row from a corner of the thumbnail. Their size follows
+//! the text size, so they scale with the font and with HiDPI settings.
+
+use anyhow::Result;
+use std::collections::HashMap;
+
+use crate::common::constants::badge;
+use crate::common::image::decode_png;
+use crate::common::types::{Dimensions, Position, TextAnchor, TextOffset};
+
+/// Decoded images by path and tile size (None if the image failed to load)
+pub type ImageCache = HashMap<(String, u16), Option<Vec<u8>>>;
+
+/// Side of a badge tile for a font of `font_size` pixels
+pub fn tile_size(font_size: f32) -> u16 {
+    font_size.round() as u16 + badge::PADDING * 2
+}
+
+/// Top-left corners of `count` badges of side `size`, in a row placed at `anchor` in `area`
+pub fn layout(anchor: TextAnchor, count: usize, size: u16, area: Dimensions) -> Vec<Position> {
+    if count == 0 {
+        return Vec::new();
+    }
+    let row_width = (count as u32 * u32::from(size)
+        + (count as u32 - 1) * u32::from(badge::SPACING))
+    .min(u32::from(u16::MAX)) as u16;
+    let margin = if anchor == TextAnchor::Center {
+        0
+    } else {
+        badge::MARGIN
+    };
+    let row = anchor.place(
+        TextOffset::from_border_edge(margin, margin),
+        area,
+        Dimensions::new(row_width, size),
+    );
+    (0..count)
+        .map(|i| {
+            let step = i32::from(size) + i32::from(badge::SPACING);
+            let x = (i32::from(row.x) + i as i32 * step).min(i32::from(i16::MAX)) as i16;
+            Position::new(x, row.y)
+        })
+        .collect()
+}
+
+/// The PNG at `path` fitted into a `size` x `size` tile, as premultiplied ARGB32 pixels
+/// (BGRA byte order, ready for `PutImage` on a 32-bit pixmap)
+pub fn load_png(path: &str, size: u16) -> Result<Vec<u8>> {
+    let (rgba, width, height) = decode_png(path)?;
+    Ok(fit_to_tile(&rgba, width, height, size))
+}
+
+/// Nearest-neighbour scale of RGBA pixels into a transparent square tile, keeping the aspect
+/// ratio, as premultiplied BGRA
+fn fit_to_tile(rgba: &[u8], width: u32, height: u32, size: u16) -> Vec<u8> {
+    let size = u32::from(size);
+    let mut out = vec![0u8; (size * size * 4) as usize];
+    if width == 0 || height == 0 || size == 0 {
+        return out;
+    }
+
+    let longest = width.max(height);
+    let (dst_w, dst_h) = (
+        (width * size / longest).max(1),
+        (height * size / longest).max(1),
+    );
+    let (off_x, off_y) = ((size - dst_w) / 2, (size - dst_h) / 2);
+    for y in 0..dst_h {
+        let sy = y * height / dst_h;
+        for x in 0..dst_w {
+            let sx = x * width / dst_w;
+            let src = ((sy * width + sx) * 4) as usize;
+            let Some(pixel) = rgba.get(src..src + 4) else {
+                continue;
+            };
+            let (r, g, b, a) = (pixel[0], pixel[1], pixel[2], pixel[3]);
+            let premultiply = |c: u8| ((u16::from(c) * u16::from(a) + 127) / 255) as u8;
+            let dst = (((y + off_y) * size + x + off_x) * 4) as usize;
+            out[dst..dst + 4].copy_from_slice(&[premultiply(b), premultiply(g), premultiply(r), a]);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout_rows_from_anchor() {
+        let area = Dimensions::new(480, 270);
+        let size = 20;
+        let step = (size + badge::SPACING) as i16;
+        let m = badge::MARGIN;
+
+        let left = layout(TextAnchor::TopLeft, 3, size, area);
+        assert_eq!(
+            left,
+            vec![
+                Position::new(m, m),
+                Position::new(m + step, m),
+                Position::new(m + 2 * step, m),
+            ]
+        );
+
+        // The row ends at the right margin
+        let right = layout(TextAnchor::BottomRight, 2, size, area);
+        let last = right.last().unwrap();
+        assert_eq!(last.x + size as i16, 480 - m);
+        assert_eq!(last.y + size as i16, 270 - m);
+
+        assert!(layout(TextAnchor::Center, 0, size, area).is_empty());
+    }
+
+    #[test]
+    fn test_fit_to_tile_keeps_aspect_and_premultiplies() {
+        // 2x1 image: opaque red, half-transparent white
+        let rgba = [255, 0, 0, 255, 255, 255, 255, 128];
+        let tile = fit_to_tile(&rgba, 2, 1, 4);
+        let px = |x: usize, y: usize| &tile[(y * 4 + x) * 4..(y * 4 + x) * 4 + 4];
+
+        // Scaled to 4x2, centered vertically
+        assert_eq!(px(0, 0), &[0, 0, 0, 0]);
+        assert_eq!(px(0, 1), &[0, 0, 255, 255]);
+        assert_eq!(px(3, 2), &[128, 128, 128, 128]);
+        assert_eq!(px(0, 3), &[0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_tile_size() {
+        assert_eq!(tile_size(18.0), 18 + badge::PADDING * 2);
+    }
+}
@@ -0,0 +1,336 @@
+//! Rendering benchmark (`eve-preview-manager bench`)
+//!
+//! Creates synthetic source windows, attaches real thumbnails to them and repaints the
+//! sources every frame. Each frame goes through the same path as the daemon's event loop:
+//! wait for DamageNotify, update the thumbnail, subtract the damage and sync.
+
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use x11rb::connection::Connection;
+use x11rb::protocol::Event;
+use x11rb::protocol::damage::ConnectionExt as DamageExt;
+use x11rb::protocol::xproto::{
+    ConnectionExt, CreateGCAux, CreateWindowAux, EventMask, Rectangle, Window, WindowClass,
+};
+use x11rb::rust_connection::RustConnection;
+use x11rb::wrapper::ConnectionExt as _;
+
+use crate::common::constants::bench;
+use crate::common::types::{Dimensions, Position, PreviewMode};
+use crate::config::DaemonConfig;
+use crate::config::profile::Config;
+use crate::x11::{AppContext, CachedAtoms, CachedFormats};
+
+use super::font::FontRenderer;
+use super::thumbnail::Thumbnail;
+
+/// Benchmark parameters
+pub struct BenchOptions {
+    /// Thumbnail counts to measure
+    pub counts: Vec<usize>,
+    /// Thumbnail sizes to measure
+    pub sizes: Vec<Dimensions>,
+    /// Measured frames per run (after warm-up)
+    pub frames: u32,
+}
+
+/// Measurements of one count/size combination
+struct BenchRun {
+    count: usize,
+    size: Dimensions,
+    total: Duration,
+    /// Time from repainting the sources until all thumbnails were updated, per frame
+    latencies: Vec<Duration>,
+}
+
+impl BenchRun {
+    fn frames_per_sec(&self) -> f64 {
+        self.latencies.len() as f64 / self.total.as_secs_f64().max(f64::EPSILON)
+    }
+
+    fn thumbnails_per_sec(&self) -> f64 {
+        self.frames_per_sec() * self.count as f64
+    }
+}
+
+/// Parse a `WIDTHxHEIGHT` size argument
+pub fn parse_size(value: &str) -> Result<Dimensions, String> {
+    let (width, height) = value
+        .split_once(['x', 'X'])
+        .ok_or_else(|| format!("expected WIDTHxHEIGHT, got '{}'", value))?;
+    let width = width
+        .trim()
+        .parse::<u16>()
+        .map_err(|e| format!("invalid width '{}': {}", width, e))?;
+    let height = height
+        .trim()
+        .parse::<u16>()
+        .map_err(|e| format!("invalid height '{}': {}", height, e))?;
+    if width == 0 || height == 0 {
+        return Err(format!("size must be non-zero, got '{}'", value));
+    }
+    Ok(Dimensions::new(width, height))
+}
+
+/// Run all count/size combinations against the current X display and print a table
+pub fn run_bench(options: &BenchOptions) -> Result<()> {
+    let (conn, screen_num) = x11rb::connect(None)
+        .context("Failed to connect to X11 server. Is DISPLAY set correctly?")?;
+    let screen = &conn.setup().roots[screen_num];
+    conn.damage_query_version(1, 1)
+        .context("Failed to query DAMAGE extension version. Is DAMAGE extension available?")?;
+
+    let atoms = CachedAtoms::new(&conn).context("Failed to cache X11 atoms")?;
+    let formats = CachedFormats::new(&conn, screen).context("Failed to cache picture formats")?;
+    let ctx = AppContext {
+        conn: &conn,
+        screen,
+        atoms: &atoms,
+        formats: &formats,
+        touch: false,
+    };
+
+    // Default profile so results are comparable between machines
+    let daemon_config = DaemonConfig::from_config(&Config::default());
+    let display_config = daemon_config.build_display_config();
+    let font_renderer = FontRenderer::resolve_from_config(
+        &conn,
+        &daemon_config.profile.thumbnail_text_font,
+        daemon_config.text_size(),
+    )
+    .context("Failed to initialize font renderer")?;
+
+    println!(
+        "Source windows: {}x{}, {} frames per run ({} warm-up)",
+        bench::SOURCE_WIDTH,
+        bench::SOURCE_HEIGHT,
+        options.frames,
+        bench::WARMUP_FRAMES
+    );
+    println!(
+        "{:>6} {:>10} {:>9} {:>11} {:>9} {:>9} {:>9}",
+        "thumbs", "size", "fps", "thumbs/s", "p50 ms", "p95 ms", "max ms"
+    );
+
+    for &size in &options.sizes {
+        for &count in &options.counts {
+            let run = run_once(
+                &ctx,
+                &display_config,
+                &font_renderer,
+                count,
+                size,
+                options.frames,
+            )
+            .with_context(|| {
+                format!(
+                    "Benchmark failed for {} thumbnails at {}x{}",
+                    count, size.width, size.height
+                )
+            })?;
+            println!(
+                "{:>6} {:>10} {:>9.1} {:>11.1} {:>9.2} {:>9.2} {:>9.2}",
+                run.count,
+                format!("{}x{}", run.size.width, run.size.height),
+                run.frames_per_sec(),
+                run.thumbnails_per_sec(),
+                millis(percentile(&run.latencies, 50)),
+                millis(percentile(&run.latencies, 95)),
+                millis(percentile(&run.latencies, 100)),
+            );
+        }
+    }
+    Ok(())
+}
+
+fn run_once(
+    ctx: &AppContext,
+    display_config: &crate::config::DisplayConfig,
+    font_renderer: &FontRenderer,
+    count: usize,
+    size: Dimensions,
+    frames: u32,
+) -> Result<BenchRun> {
+    let conn = ctx.conn;
+    let sources = create_sources(ctx, count)?;
+
+    let mut thumbnails = Vec::with_capacity(count);
+    let columns = (ctx.screen.width_in_pixels / size.width).max(1) as usize;
+    for (i, &src) in sources.iter().enumerate() {
+        let position = Position::new(
+            ((i % columns) as u16 * size.width) as i16,
+            ((i / columns) as u16 * size.height) as i16,
+        );
+        thumbnails.push(Thumbnail::new(
+            ctx,
+            format!("Bench {}", i + 1),
+            src,
+            display_config,
+            font_renderer,
+            Some(position),
+            size,
+            PreviewMode::Live,
+        )?);
+    }
+    conn.sync()
+        .context("Failed to sync after creating thumbnails")?;
+
+    let gc = conn.generate_id()?;
+    conn.create_gc(gc, ctx.screen.root, &CreateGCAux::new())?;
+
+    let mut latencies = Vec::with_capacity(frames as usize);
+    let mut measured_total = Duration::ZERO;
+    let result = (|| -> Result<()> {
+        for frame in 0..bench::WARMUP_FRAMES + frames {
+            let start = Instant::now();
+            paint_sources(conn, gc, &sources, frame)?;
+            wait_for_damage(conn, &thumbnails)?;
+            for thumbnail in &thumbnails {
+                thumbnail.update(display_config, font_renderer)?;
+                conn.damage_subtract(thumbnail.damage(), 0u32, 0u32)?;
+            }
+            conn.sync().context("Failed to sync after frame")?;
+
+            if frame >= bench::WARMUP_FRAMES {
+                let elapsed = start.elapsed();
+                measured_total += elapsed;
+                latencies.push(elapsed);
+            }
+        }
+        Ok(())
+    })();
+
+    // Thumbnails free their resources on drop; sources are ours to destroy
+    drop(thumbnails);
+    let _ = conn.free_gc(gc);
+    for src in sources {
+        let _ = conn.destroy_window(src);
+    }
+    let _ = conn.sync();
+    result?;
+
+    Ok(BenchRun {
+        count,
+        size,
+        total: measured_total,
+        latencies,
+    })
+}
+
+/// Create mapped, unmanaged source windows standing in for EVE clients
+fn create_sources(ctx: &AppContext, count: usize) -> Result<Vec<Window>> {
+    let mut sources = Vec::with_capacity(count);
+    for i in 0..count {
+        let window = ctx.conn.generate_id()?;
+        let offset = (i as i16 % 16) * 20;
+        ctx.conn
+            .create_window(
+                ctx.screen.root_depth,
+                window,
+                ctx.screen.root,
+                offset,
+                offset,
+                bench::SOURCE_WIDTH,
+                bench::SOURCE_HEIGHT,
+                0,
+                WindowClass::INPUT_OUTPUT,
+                ctx.screen.root_visual,
+                &CreateWindowAux::new()
+                    .override_redirect(1)
+                    .background_pixel(ctx.screen.black_pixel)
+                    .event_mask(EventMask::NO_EVENT),
+            )
+            .context("Failed to create benchmark source window")?;
+        ctx.conn.map_window(window)?;
+        sources.push(window);
+    }
+    ctx.conn
+        .sync()
+        .context("Failed to sync after creating source windows")?;
+    Ok(sources)
+}
+
+/// Repaint every source with a color that changes per frame
+fn paint_sources(conn: &RustConnection, gc: u32, sources: &[Window], frame: u32) -> Result<()> {
+    let shade = frame % 256;
+    conn.change_gc(
+        gc,
+        &x11rb::protocol::xproto::ChangeGCAux::new()
+            .foreground((shade << 16) | ((255 - shade) << 8) | 0x40),
+    )?;
+    let rect = Rectangle {
+        x: 0,
+        y: 0,
+        width: bench::SOURCE_WIDTH,
+        height: bench::SOURCE_HEIGHT,
+    };
+    for &src in sources {
+        conn.poly_fill_rectangle(src, gc, &[rect])?;
+    }
+    conn.flush()?;
+    Ok(())
+}
+
+/// Block until every thumbnail's damage object has reported, as the event loop would
+fn wait_for_damage(conn: &RustConnection, thumbnails: &[Thumbnail]) -> Result<()> {
+    let mut pending: HashSet<_> = thumbnails.iter().map(|t| t.damage()).collect();
+    let deadline = Instant::now() + Duration::from_millis(bench::EVENT_TIMEOUT_MS);
+
+    while !pending.is_empty() {
+        match conn.poll_for_event()? {
+            Some(Event::DamageNotify(event)) => {
+                pending.remove(&event.damage);
+            }
+            Some(_) => {}
+            None if Instant::now() > deadline => {
+                anyhow::bail!(
+                    "Timed out waiting for damage events ({} of {} sources pending)",
+                    pending.len(),
+                    thumbnails.len()
+                );
+            }
+            None => std::thread::yield_now(),
+        }
+    }
+    Ok(())
+}
+
+/// Nearest-rank percentile (`pct` in 0..=100)
+fn percentile(samples: &[Duration], pct: u32) -> Duration {
+    if samples.is_empty() {
+        return Duration::ZERO;
+    }
+    let mut sorted = samples.to_vec();
+    sorted.sort();
+    let rank = (pct as usize * sorted.len()).div_ceil(100).max(1);
+    sorted[rank.min(sorted.len()) - 1]
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("250x140"), Ok(Dimensions::new(250, 140)));
+        assert_eq!(parse_size("480X270"), Ok(Dimensions::new(480, 270)));
+        assert!(parse_size("250").is_err());
+        assert!(parse_size("0x140").is_err());
+        assert!(parse_size("wide x 140").is_err());
+    }
+
+    #[test]
+    fn test_percentile() {
+        let samples: Vec<Duration> = (1..=100).map(Duration::from_millis).collect();
+        assert_eq!(percentile(&samples, 50), Duration::from_millis(50));
+        assert_eq!(percentile(&samples, 95), Duration::from_millis(95));
+        assert_eq!(percentile(&samples, 100), Duration::from_millis(100));
+        assert_eq!(percentile(&[], 50), Duration::ZERO);
+    }
+}
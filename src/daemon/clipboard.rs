@@ -0,0 +1,154 @@
+//! Clipboard ownership for copied thumbnail images
+//!
+//! While it holds a copied image the daemon owns the CLIPBOARD selection and answers
+//! `SelectionRequest` events with the PNG (`image/png`) or the list of targets. The image is
+//! dropped when another client takes the selection or the daemon exits.
+
+use anyhow::{Context, Result, bail};
+use x11rb::NONE;
+use x11rb::connection::{Connection, RequestConnection};
+use x11rb::protocol::xproto::{
+    AtomEnum, ConnectionExt, CreateWindowAux, EventMask, PropMode, SELECTION_NOTIFY_EVENT,
+    SelectionClearEvent, SelectionNotifyEvent, SelectionRequestEvent, Timestamp, Window,
+    WindowClass,
+};
+use x11rb::wrapper::ConnectionExt as WrapperExt;
+
+use crate::x11::AppContext;
+
+#[derive(Debug, Default)]
+pub struct Clipboard {
+    /// Selection owner, created on first copy and kept for the connection's lifetime
+    window: Option<Window>,
+    /// Image served while we own the selection
+    png: Option<Vec<u8>>,
+}
+
+impl Clipboard {
+    /// Take the CLIPBOARD selection and serve `png` from it
+    pub fn set_png(&mut self, ctx: &AppContext, png: Vec<u8>, time: Timestamp) -> Result<()> {
+        // Sent in a single ChangeProperty; incremental transfers are not implemented
+        let limit = ctx.conn.maximum_request_bytes().saturating_sub(64);
+        if png.len() > limit {
+            bail!(
+                "Image is too large for the clipboard ({} bytes, at most {})",
+                png.len(),
+                limit
+            );
+        }
+
+        let window = match self.window {
+            Some(window) => window,
+            None => {
+                let window = create_window(ctx)?;
+                self.window = Some(window);
+                window
+            }
+        };
+        ctx.conn
+            .set_selection_owner(window, ctx.atoms.clipboard, time)
+            .context("Failed to send SetSelectionOwner request")?;
+        let owner = ctx
+            .conn
+            .get_selection_owner(ctx.atoms.clipboard)
+            .context("Failed to send GetSelectionOwner request")?
+            .reply()
+            .context("Failed to get clipboard owner")?
+            .owner;
+        if owner != window {
+            bail!("Another client kept the clipboard");
+        }
+        self.png = Some(png);
+        Ok(())
+    }
+
+    /// Answer a client asking for the clipboard contents
+    pub fn handle_request(&self, ctx: &AppContext, event: &SelectionRequestEvent) -> Result<()> {
+        // Clients predating ICCCM 2 leave the property unset
+        let property = if event.property == NONE {
+            event.target
+        } else {
+            event.property
+        };
+        let png = self
+            .png
+            .as_ref()
+            .filter(|_| self.window == Some(event.owner) && event.selection == ctx.atoms.clipboard);
+
+        let stored = match png {
+            Some(_) if event.target == ctx.atoms.targets => {
+                ctx.conn
+                    .change_property32(
+                        PropMode::REPLACE,
+                        event.requestor,
+                        property,
+                        AtomEnum::ATOM,
+                        &[ctx.atoms.targets, ctx.atoms.image_png],
+                    )
+                    .context("Failed to send clipboard targets")?;
+                true
+            }
+            Some(png) if event.target == ctx.atoms.image_png => {
+                ctx.conn
+                    .change_property8(
+                        PropMode::REPLACE,
+                        event.requestor,
+                        property,
+                        ctx.atoms.image_png,
+                        png,
+                    )
+                    .context("Failed to send clipboard image")?;
+                true
+            }
+            _ => false,
+        };
+
+        let notify = SelectionNotifyEvent {
+            response_type: SELECTION_NOTIFY_EVENT,
+            sequence: 0,
+            time: event.time,
+            requestor: event.requestor,
+            selection: event.selection,
+            target: event.target,
+            property: if stored { property } else { NONE },
+        };
+        ctx.conn
+            .send_event(false, event.requestor, EventMask::NO_EVENT, notify)
+            .context("Failed to send SelectionNotify")?;
+        ctx.conn
+            .flush()
+            .context("Failed to flush clipboard reply")?;
+        Ok(())
+    }
+
+    /// Another client took the clipboard
+    pub fn handle_clear(&mut self, event: &SelectionClearEvent) {
+        if self.window == Some(event.owner) {
+            self.png = None;
+        }
+    }
+}
+
+/// Unmapped input-only window owning the selection
+fn create_window(ctx: &AppContext) -> Result<Window> {
+    let window = ctx
+        .conn
+        .generate_id()
+        .context("Failed to generate clipboard window ID")?;
+    ctx.conn
+        .create_window(
+            0,
+            window,
+            ctx.screen.root,
+            -1,
+            -1,
+            1,
+            1,
+            0,
+            WindowClass::INPUT_ONLY,
+            0,
+            &CreateWindowAux::new(),
+        )
+        .context("Failed to create clipboard window")?;
+    Ok(window)
+}
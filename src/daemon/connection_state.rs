@@ -0,0 +1,30 @@
+//! Per-connection daemon state
+//!
+//! Windows and selections the daemon owns on one X server connection, and gesture state
+//! referring to windows on it. Unlike `SessionState`, which survives reconnects and display
+//! switches, every connection starts with a fresh one: the X server frees a client's
+//! resources when its connection closes, so their IDs mean nothing on the next connection.
+
+use super::clipboard::Clipboard;
+use super::selection::RubberBand;
+use super::snap_guides::SnapGuides;
+use super::tooltip::Tooltip;
+use super::touch::TouchTracker;
+
+#[derive(Default)]
+pub struct ConnectionState {
+    /// Thumbnail image copied to the clipboard, served while we own the selection
+    pub clipboard: Clipboard,
+
+    /// Touches on thumbnails and the gesture they form
+    pub touch: TouchTracker,
+
+    /// Alignment guides shown while a thumbnail is dragged
+    pub snap_guides: SnapGuides,
+
+    /// Alias and notes of the hovered thumbnail
+    pub tooltip: Tooltip,
+
+    /// Ctrl-drag on the desktop selecting thumbnails
+    pub rubber_band: RubberBand,
+}
@@ -1,7 +1,8 @@
 //! Hotkey cycle state management
 //!
-//! Tracks active EVE windows and their cycle order for hotkey-based navigation.
-//! Only characters listed in the profile's cycle_group are included in cycling.
+//! Tracks active EVE windows and custom sources and their cycle order for hotkey-based
+//! navigation. Only entries listed in the profile's cycle groups are included in cycling;
+//! EVE and custom source slots share one order, keyed by character name or source label.
 
 use std::collections::{HashMap, HashSet};
 use tracing::{debug, warn};
@@ -14,11 +15,37 @@ struct GroupState {
     current_index: usize,
 }
 
+impl GroupState {
+    /// Index in `order` to continue cycling from: the current member's new position, or
+    /// if it left the group, the position just before the next member that stayed
+    fn reseeded_index(&self, order: &[String]) -> usize {
+        if order.is_empty() {
+            return 0;
+        }
+        let len = self.order.len();
+        (0..len)
+            .filter_map(|step| {
+                let name = &self.order[(self.current_index + step) % len];
+                let position = order.iter().position(|n| n == name)?;
+                Some(if step == 0 {
+                    position
+                } else {
+                    (position + order.len() - 1) % order.len()
+                })
+            })
+            .next()
+            .unwrap_or(0)
+    }
+}
+
 /// Maps character names to their window IDs and positions in cycle order
 pub struct CycleState {
     /// Active cycle groups: group_name -> GroupState
     groups: HashMap<String, GroupState>,
 
+    /// Group names in profile order, for rotating the selected group
+    group_order: Vec<String>,
+
     /// Currently focused active window (if any)
     /// Used to resolve starting position for cycling, especially for detached characters
     current_window: Option<Window>,
@@ -32,35 +59,67 @@ pub struct CycleState {
 
     /// The name of the cycle group that was last active (used for reset logic)
     last_active_group: Option<String>,
+
+    /// Windows the cycle hotkeys may land on (the current monitor's), `None` for all
+    cycle_windows: Option<HashSet<Window>>,
 }
 
 impl CycleState {
     pub fn new(cycle_groups: Vec<crate::config::profile::CycleGroup>) -> Self {
+        let mut state = Self {
+            groups: HashMap::new(),
+            group_order: Vec::new(),
+            current_window: None,
+            active_windows: HashMap::new(),
+            skipped_characters: HashSet::new(),
+            last_active_group: None,
+            cycle_windows: None,
+        };
+        state.set_groups(&cycle_groups);
+        state
+    }
+
+    /// Re-seed the cycle order after the profile's cycle groups changed (e.g. a group was
+    /// reordered in the Manager). Each group keeps its place: cycling continues after the
+    /// member that was current, wherever it now is. Windows and skips are kept.
+    pub fn set_groups(&mut self, cycle_groups: &[crate::config::profile::CycleGroup]) {
         let mut groups = HashMap::new();
         for group in cycle_groups {
+            // Group slots are resolved with the daemon config
+            let order: Vec<String> = group
+                .cycle_list
+                .iter()
+                .filter_map(|slot| match slot {
+                    crate::config::profile::CycleSlot::Eve(name) => Some(name.clone()),
+                    crate::config::profile::CycleSlot::Source(name) => Some(name.clone()),
+                    crate::config::profile::CycleSlot::Group(_) => None,
+                })
+                .collect();
+            let current_index = self
+                .groups
+                .get(&group.name)
+                .map_or(0, |old| old.reseeded_index(&order));
             groups.insert(
-                group.name,
+                group.name.clone(),
                 GroupState {
-                    order: group
-                        .cycle_list
-                        .iter()
-                        .map(|slot| match slot {
-                            crate::config::profile::CycleSlot::Eve(name) => name.clone(),
-                            crate::config::profile::CycleSlot::Source(name) => name.clone(),
-                        })
-                        .collect(),
-                    current_index: 0,
+                    order,
+                    current_index,
                 },
             );
         }
 
-        Self {
-            groups,
-            current_window: None,
-            active_windows: HashMap::new(),
-            skipped_characters: HashSet::new(),
-            last_active_group: None,
+        self.group_order = cycle_groups
+            .iter()
+            .map(|group| group.name.clone())
+            .collect();
+        if self
+            .last_active_group
+            .as_ref()
+            .is_some_and(|name| !groups.contains_key(name))
+        {
+            self.last_active_group = None;
         }
+        self.groups = groups;
     }
 
     /// Register a new EVE window (called from CreateNotify)
@@ -72,6 +131,16 @@ impl CycleState {
         // We track all windows here, but `cycle_forward/backward` logic filters internally based on the config.
     }
 
+    /// Whether `window` is tracked for any character
+    pub fn has_window(&self, window: Window) -> bool {
+        self.active_windows.values().any(|&w| w == window)
+    }
+
+    /// All tracked windows
+    pub fn windows(&self) -> impl Iterator<Item = Window> + '_ {
+        self.active_windows.values().copied()
+    }
+
     /// Remove window (called from DestroyNotify)
     pub fn remove_window(&mut self, window: Window) {
         // Find and remove from active_windows
@@ -129,6 +198,30 @@ impl CycleState {
         self.skipped_characters.contains(character_name)
     }
 
+    /// Skip or include a character (e.g. from the Manager). Returns whether it changed.
+    pub fn set_skipped(&mut self, character_name: &str, skipped: bool) -> bool {
+        if skipped {
+            self.skipped_characters.insert(character_name.to_string())
+        } else {
+            self.skipped_characters.remove(character_name)
+        }
+    }
+
+    /// Skipped characters, sorted by name
+    pub fn skipped(&self) -> Vec<String> {
+        let mut names: Vec<_> = self.skipped_characters.iter().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Limit the following cycle presses to `windows`, or lift the limit with `None`
+    ///
+    /// Characters whose window isn't listed are passed over like skipped ones. Per-character
+    /// hotkeys are not affected.
+    pub fn restrict_to(&mut self, windows: Option<HashSet<Window>>) {
+        self.cycle_windows = windows;
+    }
+
     /// Move to next character in specified group (forward cycle hotkey)
     /// Returns (window, character_name) to activate, or None if no active characters
     ///
@@ -188,8 +281,16 @@ impl CycleState {
                         continue;
                     }
 
+                    let allowed = |window: &Window| {
+                        self.cycle_windows
+                            .as_ref()
+                            .is_none_or(|windows| windows.contains(window))
+                    };
+
                     // Check active windows first
-                    if let Some(&window) = self.active_windows.get(character_name) {
+                    if let Some(&window) = self.active_windows.get(character_name)
+                        && allowed(&window)
+                    {
                         debug!(group = group_name, character = %character_name, index = group_state.current_index, "Cycling forward to logged-in character");
                         self.current_window = Some(window);
                         return Some((window, character_name.clone()));
@@ -197,9 +298,9 @@ impl CycleState {
 
                     // Check logged-out windows
                     if let Some(map) = logged_out_map
-                        && let Some((&window, _)) = map
-                            .iter()
-                            .find(|(_, last_char)| *last_char == character_name)
+                        && let Some((&window, _)) = map.iter().find(|(window, last_char)| {
+                            *last_char == character_name && allowed(window)
+                        })
                     {
                         debug!(group = group_name, character = %character_name, index = group_state.current_index, window = window, "Cycling forward to logged-out character");
                         self.current_window = Some(window);
@@ -219,6 +320,57 @@ impl CycleState {
         }
     }
 
+    /// Group cycled most recently, `None` before any cycling
+    pub fn active_group(&self) -> Option<&str> {
+        self.last_active_group.as_deref()
+    }
+
+    /// Group the active-group cycle hotkeys act on: the last cycled or selected group,
+    /// otherwise "Default", otherwise the first group
+    pub fn selected_group(&self) -> Option<&str> {
+        self.active_group()
+            .or_else(|| self.groups.contains_key("Default").then_some("Default"))
+            .or_else(|| self.group_order.first().map(String::as_str))
+    }
+
+    /// Select the group after the current one (group-switch hotkey) and return its name
+    ///
+    /// With `reset_on_switch`, the next forward cycle in the new group starts at its first
+    /// character, as if a group hotkey had been pressed.
+    pub fn switch_group(&mut self, reset_on_switch: bool) -> Option<&str> {
+        let current = self.selected_group()?;
+        let position = self.group_order.iter().position(|name| name == current);
+        let next =
+            self.group_order[position.map_or(0, |i| (i + 1) % self.group_order.len())].clone();
+
+        if reset_on_switch
+            && self.last_active_group.as_deref() != Some(next.as_str())
+            && let Some(group_state) = self.groups.get_mut(&next)
+        {
+            group_state.current_index = group_state.order.len().saturating_sub(1);
+        }
+        debug!(group = %next, "Selected cycle group");
+        self.last_active_group = Some(next);
+        self.active_group()
+    }
+
+    /// Character the next forward cycle press would land on, without moving there
+    ///
+    /// Looks at the selected group (see `selected_group`). Ignores the reset-on-switch
+    /// setting, since the group does not change.
+    pub fn next_target(&self, logged_out_map: Option<&HashMap<Window, String>>) -> Option<&str> {
+        let group_state = self.groups.get(self.selected_group()?)?;
+        let len = group_state.order.len();
+        (1..=len)
+            .map(|step| &group_state.order[(group_state.current_index + step) % len])
+            .find(|name| {
+                !self.skipped_characters.contains(*name)
+                    && (self.active_windows.contains_key(*name)
+                        || logged_out_map.is_some_and(|map| map.values().any(|last| last == *name)))
+            })
+            .map(String::as_str)
+    }
+
     /// Move to previous character in specified group (backward cycle hotkey)
     pub fn cycle_backward(
         &mut self,
@@ -266,16 +418,24 @@ impl CycleState {
                         continue;
                     }
 
-                    if let Some(&window) = self.active_windows.get(character_name) {
+                    let allowed = |window: &Window| {
+                        self.cycle_windows
+                            .as_ref()
+                            .is_none_or(|windows| windows.contains(window))
+                    };
+
+                    if let Some(&window) = self.active_windows.get(character_name)
+                        && allowed(&window)
+                    {
                         debug!(group = group_name, character = %character_name, index = group_state.current_index, "Cycling backward to logged-in character");
                         self.current_window = Some(window);
                         return Some((window, character_name.clone()));
                     }
 
                     if let Some(map) = logged_out_map
-                        && let Some((&window, _)) = map
-                            .iter()
-                            .find(|(_, last_char)| *last_char == character_name)
+                        && let Some((&window, _)) = map.iter().find(|(window, last_char)| {
+                            *last_char == character_name && allowed(window)
+                        })
                     {
                         debug!(group = group_name, character = %character_name, index = group_state.current_index, window = window, "Cycling backward to logged-out character");
                         self.current_window = Some(window);
@@ -524,6 +684,10 @@ mod tests {
             ],
             hotkey_forward: None,
             hotkey_backward: None,
+            hotkey_toggle_visibility: None,
+            auto_include_new: false,
+            auto_include_hotkey: false,
+            color: None,
         };
         let mut state = CycleState::new(vec![group1]);
         state.add_window("A".to_string(), 100);
@@ -535,6 +699,53 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_windows_follow_add_and_remove() {
+        let mut state = CycleState::new(Vec::new());
+        state.add_window("A".to_string(), 100);
+        state.add_window("B".to_string(), 200);
+        state.remove_window(100);
+
+        assert_eq!(state.windows().collect::<Vec<_>>(), vec![200]);
+    }
+
+    #[test]
+    fn test_cycle_mixes_characters_and_sources() {
+        use crate::config::profile::{CycleGroup, CycleSlot};
+        let group = CycleGroup {
+            name: "Fleet".to_string(),
+            cycle_list: vec![
+                CycleSlot::Eve("A".to_string()),
+                CycleSlot::Source("Intel Map".to_string()),
+                CycleSlot::Eve("B".to_string()),
+                CycleSlot::Source("Discord #2".to_string()),
+            ],
+            hotkey_forward: None,
+            hotkey_backward: None,
+            hotkey_toggle_visibility: None,
+            auto_include_new: false,
+            auto_include_hotkey: false,
+            color: None,
+        };
+        let mut state = CycleState::new(vec![group]);
+        state.add_window("A".to_string(), 100);
+        state.add_window("B".to_string(), 200);
+        state.add_window("Intel Map".to_string(), 300);
+        // "Discord #2" has no window and is passed over
+
+        let order: Vec<_> = (0..3)
+            .filter_map(|_| state.cycle_forward("Fleet", None, false))
+            .map(|(window, _)| window)
+            .collect();
+        assert_eq!(order, [300, 200, 100]);
+
+        state.toggle_skip("Intel Map");
+        assert_eq!(
+            state.cycle_forward("Fleet", None, false),
+            Some((200, "B".to_string()))
+        );
+    }
+
     #[test]
     fn test_cycle_reset_on_group_switch() {
         use crate::config::profile::CycleGroup;
@@ -547,6 +758,10 @@ mod tests {
             ],
             hotkey_forward: None,
             hotkey_backward: None,
+            hotkey_toggle_visibility: None,
+            auto_include_new: false,
+            auto_include_hotkey: false,
+            color: None,
         };
         let group2 = CycleGroup {
             name: "G2".to_string(),
@@ -556,6 +771,10 @@ mod tests {
             ],
             hotkey_forward: None,
             hotkey_backward: None,
+            hotkey_toggle_visibility: None,
+            auto_include_new: false,
+            auto_include_hotkey: false,
+            color: None,
         };
 
         let mut state = CycleState::new(vec![group1, group2]);
@@ -604,4 +823,165 @@ mod tests {
             Some((100, "A".to_string()))
         );
     }
+
+    #[test]
+    fn test_next_target_peeks_forward() {
+        use crate::config::profile::{CycleGroup, CycleSlot};
+        let mut group = CycleGroup::default_group();
+        group.cycle_list = vec![
+            CycleSlot::Eve("A".to_string()),
+            CycleSlot::Eve("B".to_string()),
+            CycleSlot::Eve("C".to_string()),
+        ];
+        let mut state = CycleState::new(vec![group]);
+        state.add_window("A".to_string(), 100);
+        state.add_window("C".to_string(), 300);
+
+        // B has no window, so the next press from A lands on C
+        assert_eq!(state.next_target(None), Some("C"));
+        assert_eq!(state.next_target(None), Some("C"));
+
+        let logged_out = HashMap::from([(200, "B".to_string())]);
+        assert_eq!(state.next_target(Some(&logged_out)), Some("B"));
+
+        assert_eq!(
+            state.cycle_forward("Default", None, false),
+            Some((300, "C".to_string()))
+        );
+        assert_eq!(state.next_target(None), Some("A"));
+
+        state.toggle_skip("A");
+        assert_eq!(state.next_target(None), Some("C"));
+    }
+
+    #[test]
+    fn test_switch_group_rotates_selected_group() {
+        use crate::config::profile::{CycleGroup, CycleSlot};
+        let mut first = CycleGroup::default_group();
+        first.name = "Miners".to_string();
+        first.cycle_list = vec![CycleSlot::Eve("A".to_string())];
+        let mut second = CycleGroup::default_group();
+        second.cycle_list = vec![
+            CycleSlot::Eve("B".to_string()),
+            CycleSlot::Eve("C".to_string()),
+            CycleSlot::Eve("D".to_string()),
+        ];
+        let mut state = CycleState::new(vec![first, second]);
+        for (name, window) in [("A", 100), ("B", 200), ("C", 300), ("D", 400)] {
+            state.add_window(name.to_string(), window);
+        }
+
+        // "Default" is selected before any cycling, even though it is not first
+        assert_eq!(state.selected_group(), Some("Default"));
+        assert_eq!(
+            state.cycle_forward("Default", None, false),
+            Some((300, "C".to_string()))
+        );
+
+        assert_eq!(state.switch_group(false), Some("Miners"));
+        assert_eq!(state.switch_group(false), Some("Default"));
+        // Without reset the group resumes where it was
+        assert_eq!(state.next_target(None), Some("D"));
+
+        state.switch_group(true);
+        assert_eq!(state.switch_group(true), Some("Default"));
+        assert_eq!(state.next_target(None), Some("B"));
+        assert_eq!(
+            state.cycle_forward("Default", None, true),
+            Some((200, "B".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_restrict_to_passes_over_other_windows() {
+        use crate::config::profile::{CycleGroup, CycleSlot};
+        let group = CycleGroup {
+            name: "Fleet".to_string(),
+            cycle_list: ["A", "B", "C"]
+                .iter()
+                .map(|name| CycleSlot::Eve(name.to_string()))
+                .collect(),
+            hotkey_forward: None,
+            hotkey_backward: None,
+            hotkey_toggle_visibility: None,
+            auto_include_new: false,
+            auto_include_hotkey: false,
+            color: None,
+        };
+        let mut state = CycleState::new(vec![group]);
+        for (window, name) in [(100, "A"), (200, "B"), (300, "C")] {
+            state.add_window(name.to_string(), window);
+        }
+
+        state.restrict_to(Some(HashSet::from([100, 300])));
+        let forward: Vec<Window> = (0..3)
+            .map(|_| state.cycle_forward("Fleet", None, false).unwrap().0)
+            .collect();
+        assert_eq!(forward, [300, 100, 300]);
+        assert_eq!(state.cycle_backward("Fleet", None, false).unwrap().0, 100);
+
+        state.restrict_to(Some(HashSet::new()));
+        assert_eq!(state.cycle_forward("Fleet", None, false), None);
+        state.restrict_to(None);
+        assert!(state.cycle_forward("Fleet", None, false).is_some());
+    }
+
+    #[test]
+    fn test_set_skipped() {
+        let mut state = CycleState::new(Vec::new());
+        assert!(state.set_skipped("B", true));
+        assert!(state.set_skipped("A", true));
+        assert!(!state.set_skipped("A", true));
+        assert_eq!(state.skipped(), ["A", "B"]);
+
+        assert!(!state.toggle_skip("B"));
+        assert!(state.set_skipped("A", false));
+        assert!(state.skipped().is_empty());
+    }
+
+    #[test]
+    fn test_set_groups_keeps_current_member() {
+        use crate::config::profile::{CycleGroup, CycleSlot};
+        let group = |names: &[&str]| CycleGroup {
+            name: "Fleet".to_string(),
+            cycle_list: names
+                .iter()
+                .map(|name| CycleSlot::Eve(name.to_string()))
+                .collect(),
+            hotkey_forward: None,
+            hotkey_backward: None,
+            hotkey_toggle_visibility: None,
+            auto_include_new: false,
+            auto_include_hotkey: false,
+            color: None,
+        };
+        let mut state = CycleState::new(vec![group(&["A", "B", "C", "D"])]);
+        for (window, name) in [(100, "A"), (200, "B"), (300, "C"), (400, "D")] {
+            state.add_window(name.to_string(), window);
+        }
+        state.toggle_skip("D");
+        assert_eq!(
+            state.cycle_forward("Fleet", None, false),
+            Some((200, "B".to_string()))
+        );
+
+        // B is still current, so the next press lands on what now follows it
+        state.set_groups(&[group(&["B", "D", "A", "C"])]);
+        assert_eq!(state.active_group(), Some("Fleet"));
+        assert!(state.is_skipped("D"));
+        assert_eq!(
+            state.cycle_forward("Fleet", None, false),
+            Some((100, "A".to_string()))
+        );
+
+        // A left the group: cycling continues with C, which followed it
+        state.set_groups(&[group(&["C", "B"])]);
+        assert_eq!(
+            state.cycle_forward("Fleet", None, false),
+            Some((300, "C".to_string()))
+        );
+
+        state.set_groups(&[]);
+        assert_eq!(state.active_group(), None);
+    }
 }
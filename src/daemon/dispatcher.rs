@@ -9,6 +9,7 @@ use x11rb::protocol::Event::{
 };
 use x11rb::protocol::xproto::*;
 
+use super::connection_state::ConnectionState;
 use super::cycle_state::CycleState;
 use super::session_state::SessionState;
 use super::thumbnail::Thumbnail;
@@ -26,12 +27,20 @@ pub struct EventContext<'a, 'b> {
     pub daemon_config: &'b mut DaemonConfig,
     pub eve_clients: &'b mut HashMap<Window, Thumbnail<'a>>,
     pub session_state: &'b mut SessionState,
+    pub connection: &'b mut ConnectionState,
     pub cycle_state: &'b mut CycleState,
     pub status_tx: &'b IpcSender<DaemonMessage>,
     pub font_renderer: &'b crate::daemon::font::FontRenderer,
     pub display_config: &'b crate::config::DisplayConfig,
 }
 
+impl EventContext<'_, '_> {
+    /// Damage is drawn on the frame tick (low-power mode or vsync'd redraws), not right away
+    pub fn batches_damage(&self) -> bool {
+        self.session_state.low_power || self.daemon_config.vsync_redraws
+    }
+}
+
 pub fn handle_event(ctx: &mut EventContext, event: Event) -> Result<()> {
     match event {
         DamageNotify(event) => handlers::window::handle_damage_notify(ctx, event),
@@ -43,16 +52,43 @@ pub fn handle_event(ctx: &mut EventContext, event: Event) -> Result<()> {
         Event::FocusOut(event) => handlers::state::handle_focus_out(ctx, event),
         Event::ButtonPress(event) => handlers::input::handle_button_press(ctx, event),
         Event::ButtonRelease(event) => handlers::input::handle_button_release(ctx, event),
-        Event::MotionNotify(event) => handlers::input::handle_motion_notify(ctx, event),
+        Event::MotionNotify(event) => {
+            handlers::input::handle_motion_notify(ctx, event);
+            Ok(())
+        }
+        Event::EnterNotify(event) => handlers::input::handle_enter_notify(ctx, event),
+        Event::LeaveNotify(event) => handlers::input::handle_leave_notify(ctx, event),
+        Event::XinputRawMotion(_) => {
+            handlers::input::handle_raw_motion(ctx);
+            Ok(())
+        }
+        Event::XinputTouchBegin(event) => handlers::input::handle_touch_begin(ctx, event),
+        Event::XinputTouchUpdate(event) => handlers::input::handle_touch_update(ctx, event),
+        Event::XinputTouchEnd(event) => handlers::input::handle_touch_end(ctx, event),
+        Event::VisibilityNotify(event) => handlers::window::handle_visibility_notify(ctx, event),
         PropertyNotify(event) => {
-            if event.atom == ctx.app_ctx.atoms.wm_name || event.atom == ctx.app_ctx.atoms.wm_class {
+            if event.atom == ctx.app_ctx.atoms.wm_name
+                || event.atom == ctx.app_ctx.atoms.net_wm_name
+                || event.atom == ctx.app_ctx.atoms.wm_class
+            {
                 handlers::window::handle_identity_update(ctx, event.window)
             } else if event.atom == ctx.app_ctx.atoms.net_wm_state {
                 handlers::state::handle_net_wm_state(ctx, event.window, event.atom)
+            } else if event.atom == ctx.app_ctx.atoms.net_active_window
+                && event.window == ctx.app_ctx.screen.root
+            {
+                handlers::state::handle_active_window_change(ctx)
             } else {
                 Ok(())
             }
         }
+        Event::SelectionRequest(event) => {
+            ctx.connection.clipboard.handle_request(ctx.app_ctx, &event)
+        }
+        Event::SelectionClear(event) => {
+            ctx.connection.clipboard.handle_clear(&event);
+            Ok(())
+        }
         Event::ReparentNotify(event) => {
             if let Some(thumbnail) = ctx.eve_clients.get_mut(&event.window) {
                 thumbnail.set_parent(Some(event.parent));
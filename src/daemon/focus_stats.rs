@@ -0,0 +1,181 @@
+//! Per-character focus time for the current session
+//!
+//! The main loop reports which character's client has focus after every batch of X events;
+//! time is attributed to that character until focus moves elsewhere. Snapshots go to the
+//! Manager periodically and, if enabled, to one CSV file per session.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+
+use crate::common::constants::config::focus_stats;
+use crate::common::ipc::FocusTime;
+use crate::config::profile::Config;
+
+#[derive(Debug, Default)]
+pub struct FocusStats {
+    /// Character focused right now and since when
+    current: Option<(String, Instant)>,
+    /// Focus time of past focus periods
+    totals: HashMap<String, Duration>,
+    /// Times each character gained focus
+    switches: HashMap<String, u32>,
+    /// This session's CSV file, named on first write
+    csv_path: Option<PathBuf>,
+}
+
+impl FocusStats {
+    /// Record that `name` has focus (`None`: no character does). Repeated calls for the
+    /// same character keep its running focus period.
+    pub fn focus(&mut self, name: Option<&str>, now: Instant) {
+        let name = name.filter(|n| !n.is_empty());
+        if self.current.as_ref().map(|(n, _)| n.as_str()) == name {
+            return;
+        }
+
+        if let Some((previous, since)) = self.current.take() {
+            *self.totals.entry(previous).or_default() += now.saturating_duration_since(since);
+        }
+        if let Some(name) = name {
+            *self.switches.entry(name.to_string()).or_default() += 1;
+            self.current = Some((name.to_string(), now));
+        }
+    }
+
+    /// Focus time per character up to `now`, most focused first
+    pub fn snapshot(&self, now: Instant) -> Vec<FocusTime> {
+        let mut totals = self.totals.clone();
+        if let Some((name, since)) = &self.current {
+            *totals.entry(name.clone()).or_default() += now.saturating_duration_since(*since);
+        }
+
+        let mut entries: Vec<FocusTime> = totals
+            .into_iter()
+            .map(|(name, time)| FocusTime {
+                switches: self.switches.get(&name).copied().unwrap_or(0),
+                focused_secs: time.as_secs(),
+                name,
+            })
+            .collect();
+        entries.sort_by(|a, b| {
+            b.focused_secs
+                .cmp(&a.focused_secs)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        entries
+    }
+
+    /// Rewrite this session's CSV file with `entries`
+    pub fn write_csv(&mut self, entries: &[FocusTime]) -> Result<PathBuf> {
+        let path = self
+            .csv_path
+            .get_or_insert_with(|| {
+                stats_dir().join(format!(
+                    "{}{}.{}",
+                    focus_stats::PREFIX,
+                    chrono::Local::now().format("%Y%m%d-%H%M%S"),
+                    focus_stats::EXTENSION
+                ))
+            })
+            .clone();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create stats directory {:?}", dir))?;
+        }
+        std::fs::write(&path, format_csv(entries))
+            .with_context(|| format!("Failed to write focus stats to {:?}", path))?;
+        Ok(path)
+    }
+}
+
+/// Directory holding session CSV files (next to the config file)
+fn stats_dir() -> PathBuf {
+    let mut path = Config::path();
+    path.pop(); // Remove filename
+    path.push(focus_stats::SUBDIR);
+    path
+}
+
+/// CSV with a header row; the share is of all focus time in the session
+fn format_csv(entries: &[FocusTime]) -> String {
+    let total: u64 = entries.iter().map(|e| e.focused_secs).sum();
+    let mut csv = String::from("character,focused_secs,share_percent,switches\n");
+    for entry in entries {
+        let share = if total == 0 {
+            0.0
+        } else {
+            entry.focused_secs as f64 * 100.0 / total as f64
+        };
+        let name = if entry.name.contains([',', '"']) {
+            format!("\"{}\"", entry.name.replace('"', "\"\""))
+        } else {
+            entry.name.clone()
+        };
+        let _ = writeln!(
+            csv,
+            "{},{},{:.1},{}",
+            name, entry.focused_secs, share, entry.switches
+        );
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_focus_time_accumulates_per_character() {
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        let mut stats = FocusStats::default();
+
+        stats.focus(Some("Alpha"), at(0));
+        stats.focus(Some("Alpha"), at(5)); // Still the same focus period
+        stats.focus(Some("Beta"), at(10));
+        stats.focus(None, at(13));
+        stats.focus(Some("Alpha"), at(20));
+
+        let snapshot = stats.snapshot(at(25));
+        assert_eq!(
+            snapshot,
+            vec![
+                FocusTime {
+                    name: "Alpha".to_string(),
+                    focused_secs: 15,
+                    switches: 2,
+                },
+                FocusTime {
+                    name: "Beta".to_string(),
+                    focused_secs: 3,
+                    switches: 1,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format_csv() {
+        let entries = vec![
+            FocusTime {
+                name: "Alpha".to_string(),
+                focused_secs: 30,
+                switches: 2,
+            },
+            FocusTime {
+                name: "Odd, \"Name\"".to_string(),
+                focused_secs: 10,
+                switches: 1,
+            },
+        ];
+        assert_eq!(
+            format_csv(&entries),
+            "character,focused_secs,share_percent,switches\n\
+             Alpha,30,75.0,2\n\
+             \"Odd, \"\"Name\"\"\",10,25.0,1\n"
+        );
+    }
+}
@@ -177,10 +177,10 @@ impl FontRenderer {
         }
     }
 
-    /// Render text to a BGRA bitmap (X11 optimized)
-    pub fn render_text(&self, text: &str, fg_color: u32) -> Result<RenderedText> {
+    /// Render text at `size` pixels (usually `self.size()`) to a BGRA bitmap (X11 optimized)
+    pub fn render_text(&self, text: &str, fg_color: u32, size: f32) -> Result<RenderedText> {
         match self {
-            Self::Fontdue { font, size, .. } => {
+            Self::Fontdue { font, .. } => {
                 if text.is_empty() {
                     return Ok(RenderedText {
                         width: 0,
@@ -195,7 +195,7 @@ impl FontRenderer {
                 let mut max_descent = 0i32;
 
                 for ch in text.chars() {
-                    let (metrics, bitmap) = font.rasterize(ch, *size);
+                    let (metrics, bitmap) = font.rasterize(ch, size);
                     let ascent = metrics.height as i32 + metrics.ymin;
                     let descent = -metrics.ymin;
                     max_ascent = max_ascent.max(ascent);
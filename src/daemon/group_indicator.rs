@@ -0,0 +1,54 @@
+//! On-screen indicator for the active cycle group
+//!
+//! Shown briefly at the top of the screen when the group-switch hotkey selects another cycle
+//! group.
+
+use anyhow::Result;
+
+use crate::common::constants::group_indicator;
+use crate::common::types::Position;
+use crate::x11::AppContext;
+
+use super::font::FontRenderer;
+use super::label::{LabelPopup, LabelStyle};
+
+const STYLE: LabelStyle = LabelStyle {
+    background: group_indicator::BACKGROUND_COLOR,
+    text_color: group_indicator::TEXT_COLOR,
+    padding: group_indicator::PADDING,
+    text_scale: group_indicator::TEXT_SCALE,
+};
+
+#[derive(Debug)]
+pub struct GroupIndicator {
+    popup: LabelPopup,
+}
+
+impl Default for GroupIndicator {
+    fn default() -> Self {
+        Self {
+            popup: LabelPopup::new(STYLE, "group indicator"),
+        }
+    }
+}
+
+impl GroupIndicator {
+    /// Show `text` centered at the top of the screen until `hide` is called
+    pub fn show(
+        &mut self,
+        ctx: &AppContext,
+        font_renderer: &FontRenderer,
+        text: &str,
+    ) -> Result<()> {
+        self.popup.show(ctx, font_renderer, &[text], None, |size| {
+            Position::new(
+                (ctx.screen.width_in_pixels.saturating_sub(size.width) / 2) as i16,
+                group_indicator::TOP_MARGIN,
+            )
+        })
+    }
+
+    pub fn hide(&mut self, ctx: &AppContext) -> Result<()> {
+        self.popup.hide(ctx)
+    }
+}
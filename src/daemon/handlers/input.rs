@@ -1,12 +1,22 @@
+use std::time::Instant;
+
 use anyhow::{Context, Result};
-use tracing::debug;
+use tracing::{debug, error, info};
+use x11rb::protocol::xinput;
 use x11rb::protocol::xproto::*;
 
+use super::super::activation::{self, Stage};
 use super::super::dispatcher::EventContext;
-use super::super::snapping::{self, Rect};
+use super::super::raw_motion;
+use super::super::screenshot;
+use super::super::session_state::MoveRecord;
+use super::super::snapping::{self, Guide, Rect};
 use super::super::thumbnail::Thumbnail;
+use super::super::tooltip;
+use super::super::touch::{self, Gesture};
 use crate::common::constants::mouse;
-use crate::common::types::Position;
+use crate::common::types::{Position, ThumbnailState};
+use crate::config::profile::CycleGroup;
 
 /// Handle ButtonPress events - start dragging or set current character
 #[tracing::instrument(skip(ctx), fields(window = event.event))]
@@ -25,74 +35,186 @@ pub fn handle_button_press(ctx: &mut EventContext, event: ButtonPressEvent) -> R
         .find(|(_, thumb)| thumb.is_hovered(event.root_x, event.root_y) && thumb.is_visible())
         .map(|(win, _)| *win);
 
+    // Clicking or dragging a thumbnail dismisses its tooltip
+    if let Err(e) = ctx.connection.tooltip.hide(ctx.app_ctx) {
+        debug!(error = ?e, "Failed to hide tooltip");
+    }
+
     let Some(clicked_window) = clicked_window else {
+        // Left-click on the desktop: Ctrl starts a rubber band, a plain click deselects all
+        if event.detail == mouse::BUTTON_LEFT {
+            if event.state.contains(KeyButMask::CONTROL) {
+                ctx.connection
+                    .rubber_band
+                    .begin(Position::new(event.root_x, event.root_y));
+            } else {
+                update_selection(ctx, |_| false);
+            }
+        }
         return Ok(()); // No thumbnail was clicked
     };
 
-    // For right-click drags, collect snap targets BEFORE getting mutable reference
-    let snap_targets = if event.detail == mouse::BUTTON_RIGHT {
-        ctx.eve_clients
-            .iter()
-            .filter(|(win, t)| **win != clicked_window && t.is_visible())
-            .filter_map(|(_, t)| {
-                ctx.app_ctx
-                    .conn
-                    .get_geometry(t.window())
-                    .ok()
-                    .and_then(|req| req.reply().ok())
-                    .map(|geom| Rect {
+    // Ctrl+left-click adds the thumbnail to the selection or takes it out
+    if event.detail == mouse::BUTTON_LEFT && event.state.contains(KeyButMask::CONTROL) {
+        update_selection(ctx, |t| t.selected != (t.src() == clicked_window));
+        return Ok(());
+    }
+
+    // Middle-click saves a screenshot of the client behind the thumbnail; with Shift it
+    // copies the thumbnail itself (overlays included) to the clipboard
+    if event.detail == mouse::BUTTON_MIDDLE {
+        if let Some(thumbnail) = ctx.eve_clients.get(&clicked_window) {
+            if event.state.contains(KeyButMask::SHIFT) {
+                copy_thumbnail(ctx, clicked_window, event.time);
+            } else {
+                screenshot::save_and_report(
+                    ctx.app_ctx,
+                    thumbnail.src(),
+                    &thumbnail.character_name,
+                    &ctx.daemon_config.screenshot_dir,
+                    ctx.status_tx,
+                );
+            }
+        }
+        return Ok(());
+    }
+
+    // Only allow dragging with right-click
+    if event.detail == mouse::BUTTON_RIGHT {
+        // Shift drags the whole cluster of adjacent thumbnails
+        let group = event.state.contains(KeyButMask::SHIFT);
+        start_drag(ctx, clicked_window, event.root_x, event.root_y, group)?;
+        ctx.session_state.pointer_drag = Some(clicked_window);
+        if let Err(e) = raw_motion::select(ctx.app_ctx.conn, ctx.app_ctx.screen.root, true) {
+            debug!(error = ?e, "Raw motion unavailable, dragging with core motion events");
+        }
+        return Ok(());
+    }
+
+    // Left-click sets current character for cycling, unless it picks a thumbnail to swap with
+    if event.detail == mouse::BUTTON_LEFT
+        && ctx.session_state.swap_source.is_none()
+        && let Some(thumbnail) = ctx.eve_clients.get(&clicked_window)
+    {
+        ctx.cycle_state.set_current(&thumbnail.character_name);
+        debug!(character = %thumbnail.character_name, "Set current character via click");
+    }
+    Ok(())
+}
+
+/// Start dragging the thumbnail of `src` from root position `x`/`y`
+///
+/// A selected thumbnail takes all other selected ones along. Otherwise, with `group`,
+/// thumbnails adjacent to it (directly or through others) move along.
+fn start_drag(ctx: &mut EventContext, src: Window, x: i16, y: i16, group: bool) -> Result<()> {
+    // Collect visible thumbnails BEFORE getting mutable reference
+    let others: Vec<(Window, Rect, bool)> = ctx
+        .eve_clients
+        .iter()
+        .filter(|(win, t)| **win != src && t.is_visible())
+        .filter_map(|(win, t)| {
+            ctx.app_ctx
+                .conn
+                .get_geometry(t.window())
+                .ok()
+                .and_then(|req| req.reply().ok())
+                .map(|geom| {
+                    let rect = Rect {
                         x: geom.x,
                         y: geom.y,
                         width: t.dimensions.width,
                         height: t.dimensions.height,
-                    })
-            })
+                    };
+                    (*win, rect, t.selected)
+                })
+        })
+        .collect();
+
+    let Some(thumbnail) = ctx.eve_clients.get_mut(&src) else {
+        return Ok(());
+    };
+    let geom = ctx
+        .app_ctx
+        .conn
+        .get_geometry(thumbnail.window())
+        .context("Failed to send geometry query on drag start")?
+        .reply()
+        .context(format!(
+            "Failed to get geometry on drag start for '{}'",
+            thumbnail.character_name
+        ))?;
+
+    // The dragged thumbnail comes first, so the cluster around it starts at index 0
+    let mut rects = vec![Rect {
+        x: geom.x,
+        y: geom.y,
+        width: thumbnail.dimensions.width,
+        height: thumbnail.dimensions.height,
+    }];
+    rects.extend(others.iter().map(|(_, rect, _)| *rect));
+    let members = if thumbnail.selected {
+        std::iter::once(0)
+            .chain((1..rects.len()).filter(|&i| others[i - 1].2))
             .collect()
+    } else if group {
+        let profile = &ctx.daemon_config.profile;
+        let tolerance = profile
+            .thumbnail_snap_gap
+            .max(profile.thumbnail_snap_threshold);
+        snapping::cluster(&rects, 0, tolerance)
     } else {
-        Vec::new() // No snap targets needed for left-click
+        vec![0]
     };
 
-    // Now get mutable reference to the clicked thumbnail
-    if let Some(thumbnail) = ctx.eve_clients.get_mut(&clicked_window) {
-        debug!(window = thumbnail.window(), character = %thumbnail.character_name, "ButtonPress on thumbnail");
-        let geom = ctx
-            .app_ctx
-            .conn
-            .get_geometry(thumbnail.window())
-            .context("Failed to send geometry query on button press")?
-            .reply()
-            .context(format!(
-                "Failed to get geometry on button press for '{}'",
-                thumbnail.character_name
-            ))?;
-        thumbnail.input_state.drag_start = Position::new(event.root_x, event.root_y);
-        thumbnail.input_state.win_start = Position::new(geom.x, geom.y);
-
-        // Only allow dragging with right-click
-        if event.detail == mouse::BUTTON_RIGHT {
-            // Store the pre-computed snap targets
-            thumbnail.input_state.snap_targets = snap_targets;
-            thumbnail.input_state.dragging = true;
-            debug!(
-                window = thumbnail.window(),
-                snap_target_count = thumbnail.input_state.snap_targets.len(),
-                "Started dragging thumbnail with cached snap targets"
-            );
+    thumbnail.input_state.drag_start = Position::new(x, y);
+    thumbnail.input_state.win_start = Position::new(geom.x, geom.y);
+    thumbnail.input_state.followers = members
+        .iter()
+        .filter(|&&i| i > 0)
+        .map(|&i| (others[i - 1].0, Position::new(rects[i].x, rects[i].y)))
+        .collect();
+    thumbnail.input_state.snap_targets = (1..rects.len())
+        .filter(|i| !members.contains(i))
+        .map(|i| rects[i])
+        .collect();
+    thumbnail.input_state.dragging = true;
+    debug!(
+        window = thumbnail.window(),
+        snap_target_count = thumbnail.input_state.snap_targets.len(),
+        follower_count = thumbnail.input_state.followers.len(),
+        "Started dragging thumbnail with cached snap targets"
+    );
+    Ok(())
+}
+
+/// Put the thumbnail of `src` on the clipboard as a PNG and report the result
+fn copy_thumbnail(ctx: &mut EventContext, src: Window, time: Timestamp) {
+    use crate::common::ipc::{DaemonError, DaemonErrorKind, DaemonMessage};
+
+    let Some(thumbnail) = ctx.eve_clients.get(&src) else {
+        return;
+    };
+    let name = thumbnail.character_name.clone();
+    let result = screenshot::encode_png(ctx.app_ctx, thumbnail.window(), None)
+        .and_then(|png| ctx.connection.clipboard.set_png(ctx.app_ctx, png, time));
+    let message = match result {
+        Ok(()) => {
+            info!(character = %name, "Copied thumbnail to clipboard");
+            DaemonMessage::Status(format!("Copied thumbnail of '{name}' to the clipboard"))
         }
-        // Left-click sets current character for cycling
-        if event.detail == mouse::BUTTON_LEFT {
-            ctx.cycle_state.set_current(&thumbnail.character_name);
-            debug!(character = %thumbnail.character_name, "Set current character via click");
+        Err(e) => {
+            error!(character = %name, error = ?e, "Failed to copy thumbnail to clipboard");
+            DaemonMessage::Error(DaemonError::new(
+                DaemonErrorKind::Clipboard,
+                format!("Failed to copy thumbnail of '{name}': {e:#}"),
+            ))
         }
-    }
-    Ok(())
+    };
+    let _ = ctx.status_tx.send(message);
 }
 
 /// Handle ButtonRelease events - focus window and save position after drag
 pub fn handle_button_release(ctx: &mut EventContext, event: ButtonReleaseEvent) -> Result<()> {
-    use crate::common::ipc::DaemonMessage;
-    use crate::x11::minimize_window;
-
     debug!(
         x = event.root_x,
         y = event.root_y,
@@ -100,6 +222,32 @@ pub fn handle_button_release(ctx: &mut EventContext, event: ButtonReleaseEvent)
         "ButtonRelease received"
     );
 
+    // Save position after drag ends (right-click release), wherever the pointer is now
+    if event.detail == mouse::BUTTON_RIGHT && ctx.session_state.pointer_drag.is_some() {
+        // Catch up with the pointer before the drag ends
+        if let Err(e) = flush_drag_motion(ctx) {
+            debug!(error = ?e, "Failed to apply final drag motion");
+        }
+        let _ = raw_motion::select(ctx.app_ctx.conn, ctx.app_ctx.screen.root, false);
+        if let Some(src) = ctx.session_state.pointer_drag.take() {
+            return end_drag(ctx, src);
+        }
+    }
+
+    // A rubber band selects every thumbnail it touches, on top of the current selection
+    if event.detail == mouse::BUTTON_LEFT && ctx.connection.rubber_band.is_active() {
+        let area = ctx
+            .connection
+            .rubber_band
+            .finish(ctx.app_ctx, Position::new(event.root_x, event.root_y))?;
+        if let Some(area) = area {
+            update_selection(ctx, |t| {
+                t.selected || (t.is_visible() && thumbnail_rect(t).is_near(&area, 0))
+            });
+        }
+        return Ok(());
+    }
+
     // First pass: identify the hovered thumbnail by the EVE window key
     let clicked_key = ctx.eve_clients
         .iter()
@@ -117,94 +265,102 @@ pub fn handle_button_release(ctx: &mut EventContext, event: ButtonReleaseEvent)
         return Ok(());
     };
 
-    let mut clicked_src: Option<Window> = None;
-    let is_left_click = event.detail == mouse::BUTTON_LEFT;
-
-    if let Some(thumbnail) = ctx.eve_clients.get_mut(&clicked_key) {
+    if let Some(thumbnail) = ctx.eve_clients.get(&clicked_key) {
         debug!(window = thumbnail.window(), character = %thumbnail.character_name, "ButtonRelease on thumbnail");
-        clicked_src = Some(thumbnail.src());
+    }
 
-        // Left-click focuses the window (dragging is right-click only)
-        if is_left_click {
-            thumbnail.focus(event.time).context(format!(
-                "Failed to focus window for '{}'",
-                thumbnail.character_name
-            ))?;
+    // Left-click focuses the window (dragging is right-click only, Ctrl+click selects).
+    // While a swap waits for its partner, the click picks the partner instead.
+    if event.detail == mouse::BUTTON_LEFT && !event.state.contains(KeyButMask::CONTROL) {
+        if let Some(source) = ctx.session_state.swap_source.take() {
+            return swap_positions(ctx, source, clicked_key);
         }
+        focus_client(ctx, clicked_key, event.time)?;
+    }
+    Ok(())
+}
 
-        // Save position after drag ends (right-click release)
-        if thumbnail.input_state.dragging {
-            let geom = ctx
-                .app_ctx
-                .conn
-                .get_geometry(thumbnail.window())
-                .context("Failed to send geometry query after drag")?
-                .reply()
-                .context(format!(
-                    "Failed to get geometry after drag for '{}'",
-                    thumbnail.character_name
-                ))?;
-
-            ctx.session_state
-                .update_window_position(thumbnail.window(), geom.x, geom.y);
-
-            if !thumbnail.character_name.is_empty() {
-                let settings = crate::common::types::CharacterSettings::new(
-                    geom.x,
-                    geom.y,
-                    thumbnail.dimensions.width,
-                    thumbnail.dimensions.height,
-                );
+/// Swap the positions of the thumbnails of `first` and `second` and save both; each move
+/// can be undone
+pub(crate) fn swap_positions(ctx: &mut EventContext, first: Window, second: Window) -> Result<()> {
+    let (Some(a), Some(b)) = (ctx.eve_clients.get(&first), ctx.eve_clients.get(&second)) else {
+        debug!("Swap partner is gone, nothing to swap");
+        return Ok(());
+    };
+    if first == second {
+        debug!(character = %a.character_name, "Swap picked the same thumbnail, cancelled");
+        return Ok(());
+    }
+    info!(
+        first = %a.character_name,
+        second = %b.character_name,
+        "Swapping thumbnail positions"
+    );
 
-                // Check if this is a Custom Source
-                let is_custom_source = ctx
-                    .daemon_config
-                    .profile
-                    .custom_windows
-                    .iter()
-                    .any(|rule| rule.alias == thumbnail.character_name);
-
-                if is_custom_source {
-                    ctx.daemon_config
-                        .custom_source_thumbnails
-                        .insert(thumbnail.character_name.clone(), settings);
-                } else {
-                    ctx.daemon_config
-                        .character_thumbnails
-                        .insert(thumbnail.character_name.clone(), settings);
-                }
+    let (from_a, from_b) = (a.current_position, b.current_position);
+    for (src, from, to) in [(first, from_a, from_b), (second, from_b, from_a)] {
+        if let Some(thumbnail) = ctx.eve_clients.get_mut(&src) {
+            thumbnail.reposition(to.x, to.y)?;
+        }
+        save_position(ctx, src, Some(from))?;
+    }
+    Ok(())
+}
 
-                let _ = ctx.status_tx.send(DaemonMessage::PositionChanged {
-                    name: thumbnail.character_name.clone(),
-                    x: geom.x,
-                    y: geom.y,
-                    width: thumbnail.dimensions.width,
-                    height: thumbnail.dimensions.height,
-                    is_custom: is_custom_source,
-                });
+/// Select the thumbnails `selected` picks and deselect the others, redrawing changed borders
+fn update_selection(ctx: &mut EventContext, selected: impl Fn(&Thumbnail) -> bool) {
+    for thumbnail in ctx.eve_clients.values_mut() {
+        let select = selected(thumbnail);
+        if thumbnail.selected == select {
+            continue;
+        }
+        thumbnail.selected = select;
+        debug!(character = %thumbnail.character_name, selected = select, "Selection changed");
+
+        if let ThumbnailState::Normal { focused } = thumbnail.state {
+            let skipped = ctx.cycle_state.is_skipped(&thumbnail.character_name);
+            if let Err(e) =
+                thumbnail.border(ctx.display_config, focused, skipped, ctx.font_renderer)
+            {
+                debug!(character = %thumbnail.character_name, error = ?e, "Failed to redraw selection");
             }
-
-            debug!(
-                window = thumbnail.window(),
-                x = geom.x,
-                y = geom.y,
-                "Sent PositionChanged IPC message after drag"
-            );
         }
+    }
+}
 
-        thumbnail.input_state.dragging = false;
-        thumbnail.input_state.snap_targets.clear();
+/// Area the thumbnail covers on screen
+fn thumbnail_rect(thumbnail: &Thumbnail) -> Rect {
+    Rect {
+        x: thumbnail.current_position.x,
+        y: thumbnail.current_position.y,
+        width: thumbnail.dimensions.width,
+        height: thumbnail.dimensions.height,
     }
+}
 
-    if is_left_click
-        && ctx.daemon_config.profile.client_minimize_on_switch
-        && let Some(clicked_src) = clicked_src
-    {
+/// Focus the client of the thumbnail of `src`, minimizing the others if configured
+fn focus_client(ctx: &mut EventContext, src: Window, time: Timestamp) -> Result<()> {
+    use crate::x11::minimize_window;
+
+    let Some(thumbnail) = ctx.eve_clients.get(&src) else {
+        return Ok(());
+    };
+    let clicked_src = thumbnail.src();
+    let name = &thumbnail.character_name;
+    activation::run(ctx.daemon_config, name, clicked_src, Stage::Before);
+    activation::follow_workspace(ctx.app_ctx, ctx.daemon_config, clicked_src, time);
+    thumbnail
+        .focus(time)
+        .context(format!("Failed to focus window for '{name}'"))?;
+    activation::run(ctx.daemon_config, name, clicked_src, Stage::After);
+    let companions = activation::companions(ctx.daemon_config, ctx.eve_clients, name);
+
+    if ctx.daemon_config.profile.client_minimize_on_switch {
         for other_window in ctx
             .eve_clients
             .values()
-            .filter(|t| t.src() != clicked_src)
             .map(|t| t.src())
+            .filter(|&w| w != clicked_src && !companions.iter().any(|&(c, _)| c == w))
         {
             if let Err(e) = minimize_window(
                 ctx.app_ctx.conn,
@@ -216,69 +372,213 @@ pub fn handle_button_release(ctx: &mut EventContext, event: ButtonReleaseEvent)
             }
         }
     }
+    activation::raise_companions(ctx.app_ctx, &companions);
+    Ok(())
+}
+
+/// Finish dragging or resizing the thumbnail of `src` and save its geometry
+fn end_drag(ctx: &mut EventContext, src: Window) -> Result<()> {
+    let Some(thumbnail) = ctx.eve_clients.get_mut(&src) else {
+        return Ok(());
+    };
+    let moved_from = thumbnail
+        .input_state
+        .dragging
+        .then_some(thumbnail.input_state.win_start);
+    let followers = std::mem::take(&mut thumbnail.input_state.followers);
+    thumbnail.input_state.dragging = false;
+    thumbnail.input_state.snap_targets.clear();
+    if let Err(e) = ctx.connection.snap_guides.hide(ctx.app_ctx) {
+        debug!(error = ?e, "Failed to hide snap guides");
+    }
 
+    save_position(ctx, src, moved_from)?;
+    for (follower, start) in followers {
+        save_position(ctx, follower, Some(start))?;
+    }
     Ok(())
 }
 
-/// Handle MotionNotify events - process drag motion with snapping
-#[tracing::instrument(skip(ctx), fields(window = event.event))]
-pub fn handle_motion_notify(ctx: &mut EventContext, event: MotionNotifyEvent) -> Result<()> {
+/// Save where the thumbnail of `src` is now and report it to the Manager
+///
+/// A thumbnail dragged away from `moved_from` can have the move undone.
+pub(crate) fn save_position(
+    ctx: &mut EventContext,
+    src: Window,
+    moved_from: Option<Position>,
+) -> Result<()> {
+    use crate::common::ipc::DaemonMessage;
+
+    let Some(thumbnail) = ctx.eve_clients.get(&src) else {
+        return Ok(());
+    };
+    let geom = ctx
+        .app_ctx
+        .conn
+        .get_geometry(thumbnail.window())
+        .context("Failed to send geometry query after drag")?
+        .reply()
+        .context(format!(
+            "Failed to get geometry after drag for '{}'",
+            thumbnail.character_name
+        ))?;
+
+    ctx.session_state
+        .update_window_position(thumbnail.window(), geom.x, geom.y);
+
+    if !thumbnail.character_name.is_empty() {
+        let settings = thumbnail.position_settings(Position::new(geom.x, geom.y));
+
+        // Check if this is a Custom Source
+        let is_custom_source = ctx
+            .daemon_config
+            .profile
+            .custom_windows
+            .iter()
+            .any(|rule| rule.owns_label(&thumbnail.character_name));
+
+        if is_custom_source {
+            ctx.daemon_config
+                .custom_source_thumbnails
+                .insert(thumbnail.character_name.clone(), settings);
+        } else {
+            ctx.daemon_config
+                .character_thumbnails
+                .insert(thumbnail.character_name.clone(), settings);
+        }
+
+        ctx.session_state
+            .mark_positions_dirty(ctx.daemon_config.profile.thumbnail_auto_save_position);
+
+        if let Some(previous) = moved_from
+            && (geom.x, geom.y) != (previous.x, previous.y)
+        {
+            ctx.session_state.record_move(MoveRecord {
+                window: src,
+                character_name: thumbnail.character_name.clone(),
+                is_custom: is_custom_source,
+                previous,
+            });
+        }
+
+        let _ = ctx.status_tx.send(DaemonMessage::PositionChanged {
+            name: thumbnail.character_name.clone(),
+            x: geom.x,
+            y: geom.y,
+            width: thumbnail.dimensions.width,
+            height: thumbnail.dimensions.height,
+            is_custom: is_custom_source,
+            client_origin: thumbnail.client_origin,
+        });
+    }
+
+    debug!(
+        window = thumbnail.window(),
+        x = geom.x,
+        y = geom.y,
+        "Sent PositionChanged IPC message after drag"
+    );
+    Ok(())
+}
+
+/// Handle MotionNotify events - note drag motion for the next `flush_drag_motion`
+pub fn handle_motion_notify(ctx: &mut EventContext, event: MotionNotifyEvent) {
     use tracing::trace;
 
     trace!(x = event.root_x, y = event.root_y, "MotionNotify received");
+    if ctx.connection.rubber_band.is_active()
+        && let Err(e) = ctx
+            .connection
+            .rubber_band
+            .update(ctx.app_ctx, Position::new(event.root_x, event.root_y))
+    {
+        debug!(error = ?e, "Failed to draw rubber band");
+    }
+    handle_raw_motion(ctx);
+}
 
-    // Find the dragging thumbnail
-    let dragging_window = ctx
-        .eve_clients
-        .iter()
-        .find(|(_, t)| t.input_state.dragging)
-        .map(|(win, _)| *win);
+/// Handle XInput RawMotion events, selected on the root window while dragging
+pub fn handle_raw_motion(ctx: &mut EventContext) {
+    if ctx.session_state.pointer_drag.is_some() {
+        ctx.session_state.drag_moved = true;
+    }
+}
 
-    let Some(dragging_window) = dragging_window else {
+/// Move the thumbnail dragged with the mouse to the pointer, once per batch of events
+///
+/// Only the pointer's current position matters, so motion queued behind other events is
+/// skipped instead of replayed.
+pub fn flush_drag_motion(ctx: &mut EventContext) -> Result<()> {
+    if !std::mem::take(&mut ctx.session_state.drag_moved) {
+        return Ok(());
+    }
+    let Some(src) = ctx.session_state.pointer_drag else {
         return Ok(());
     };
+    let pointer = ctx
+        .app_ctx
+        .conn
+        .query_pointer(ctx.app_ctx.screen.root)
+        .context("Failed to send pointer query while dragging")?
+        .reply()
+        .context("Failed to query pointer while dragging")?;
+    drag_to(ctx, src, pointer.root_x, pointer.root_y)
+}
 
+/// Move the dragged thumbnail of `src` for root position `x`/`y` and update the snap guides
+fn drag_to(ctx: &mut EventContext, src: Window, x: i16, y: i16) -> Result<()> {
     let snap_threshold = ctx.daemon_config.profile.thumbnail_snap_threshold;
+    let snap_gap = ctx.daemon_config.profile.thumbnail_snap_gap;
+    let screen = Rect {
+        x: 0,
+        y: 0,
+        width: ctx.app_ctx.screen.width_in_pixels,
+        height: ctx.app_ctx.screen.height_in_pixels,
+    };
+    let Some(thumbnail) = ctx.eve_clients.get_mut(&src) else {
+        return Ok(());
+    };
+    let guides =
+        handle_drag_motion(thumbnail, x, y, snap_threshold, snap_gap, screen).context(format!(
+            "Failed to handle drag motion for '{}'",
+            thumbnail.character_name
+        ))?;
+
+    // Adjacent thumbnails of a group drag keep their offsets to the dragged one
+    let offset = Position::new(
+        thumbnail.current_position.x - thumbnail.input_state.win_start.x,
+        thumbnail.current_position.y - thumbnail.input_state.win_start.y,
+    );
+    let followers = thumbnail.input_state.followers.clone();
+    for (follower, start) in followers {
+        if let Some(thumbnail) = ctx.eve_clients.get_mut(&follower) {
+            thumbnail.reposition(start.x + offset.x, start.y + offset.y)?;
+        }
+    }
 
-    let thumbnail = ctx
-        .eve_clients
-        .get_mut(&dragging_window)
-        .context("Dragging window not found in clients map")?;
-    let snap_targets = thumbnail.input_state.snap_targets.clone();
-
-    handle_drag_motion(
-        thumbnail,
-        &event,
-        &snap_targets,
-        thumbnail.dimensions.width,
-        thumbnail.dimensions.height,
-        snap_threshold,
-    )
-    .context(format!(
-        "Failed to handle drag motion for '{}'",
-        thumbnail.character_name
-    ))?;
-
+    if let Err(e) = ctx.connection.snap_guides.show(ctx.app_ctx, &guides) {
+        debug!(error = ?e, "Failed to show snap guides");
+    }
     Ok(())
 }
 
-/// Handle drag motion for a single thumbnail with snapping
+/// Handle drag motion for a single thumbnail with snapping, returning the guides to show
 fn handle_drag_motion(
     thumbnail: &mut Thumbnail,
-    event: &MotionNotifyEvent,
-    snap_targets: &[Rect],
-    _config_width: u16,
-    _config_height: u16,
+    x: i16,
+    y: i16,
     snap_threshold: u16,
-) -> Result<()> {
+    snap_gap: u16,
+    screen: Rect,
+) -> Result<Vec<Guide>> {
     use tracing::trace;
 
     if !thumbnail.input_state.dragging {
-        return Ok(());
+        return Ok(Vec::new());
     }
 
-    let dx = event.root_x - thumbnail.input_state.drag_start.x;
-    let dy = event.root_y - thumbnail.input_state.drag_start.y;
+    let dx = x - thumbnail.input_state.drag_start.x;
+    let dy = y - thumbnail.input_state.drag_start.y;
     let new_x = thumbnail.input_state.win_start.x + dx;
     let new_y = thumbnail.input_state.win_start.y + dy;
 
@@ -292,8 +592,13 @@ fn handle_drag_motion(
     let Position {
         x: final_x,
         y: final_y,
-    } = snapping::find_snap_position(dragged_rect, snap_targets, snap_threshold)
-        .unwrap_or_else(|| Position::new(new_x, new_y));
+    } = snapping::find_snap_position(
+        dragged_rect,
+        &thumbnail.input_state.snap_targets,
+        snap_threshold,
+        snap_gap,
+    )
+    .unwrap_or_else(|| Position::new(new_x, new_y));
 
     trace!(
         window = thumbnail.window(),
@@ -307,5 +612,160 @@ fn handle_drag_motion(
     // Always reposition (let X11 handle no-op if position unchanged)
     thumbnail.reposition(final_x, final_y)?;
 
+    let dropped = Rect {
+        x: final_x,
+        y: final_y,
+        ..dragged_rect
+    };
+    Ok(snapping::guides(
+        dropped,
+        &thumbnail.input_state.snap_targets,
+        screen,
+        snap_threshold,
+        snap_gap,
+    ))
+}
+
+/// Handle EnterNotify on a thumbnail - track it as hovered for low-power mode
+pub fn handle_enter_notify(ctx: &mut EventContext, event: EnterNotifyEvent) -> Result<()> {
+    let Some((&src, thumbnail)) = ctx
+        .eve_clients
+        .iter_mut()
+        .find(|(_, thumb)| thumb.window() == event.event)
+    else {
+        return Ok(());
+    };
+    ctx.session_state.hovered_thumbnail = Some(src);
+
+    // A paused thumbnail catches up as soon as the pointer reaches it
+    if ctx.session_state.low_power && thumbnail.damage_pending {
+        thumbnail.damage_pending = false;
+        thumbnail
+            .update(ctx.display_config, ctx.font_renderer)
+            .context(format!(
+                "Failed to update hovered thumbnail for '{}'",
+                thumbnail.character_name
+            ))?;
+    }
+
+    if ctx.daemon_config.profile.thumbnail_tooltips && ctx.session_state.pointer_drag.is_none() {
+        show_tooltip(ctx, src);
+    }
+    Ok(())
+}
+
+/// Show the tooltip of the thumbnail of `src`, if it has anything to say
+fn show_tooltip(ctx: &mut EventContext, src: Window) {
+    let Some(thumbnail) = ctx.eve_clients.get(&src) else {
+        return;
+    };
+    let name = &thumbnail.character_name;
+    let profile = &ctx.daemon_config.profile;
+    let settings = ctx.display_config.settings_for(name);
+    let lines = tooltip::lines(
+        name,
+        settings,
+        &CycleGroup::resolve_nested(&profile.cycle_groups),
+        profile.character_hotkeys.get(name),
+    );
+    let result = if lines.is_empty() {
+        ctx.connection.tooltip.hide(ctx.app_ctx)
+    } else {
+        ctx.connection.tooltip.show(
+            ctx.app_ctx,
+            ctx.font_renderer,
+            &lines,
+            tooltip::portrait(settings),
+            thumbnail_rect(thumbnail),
+        )
+    };
+    if let Err(e) = result {
+        debug!(character = %name, error = ?e, "Failed to show tooltip");
+    }
+}
+
+/// Handle LeaveNotify on a thumbnail
+pub fn handle_leave_notify(ctx: &mut EventContext, event: LeaveNotifyEvent) -> Result<()> {
+    let left = ctx
+        .session_state
+        .hovered_thumbnail
+        .and_then(|src| ctx.eve_clients.get(&src))
+        .is_some_and(|thumb| thumb.window() == event.event);
+    if left {
+        ctx.session_state.hovered_thumbnail = None;
+        if let Err(e) = ctx.connection.tooltip.hide(ctx.app_ctx) {
+            debug!(error = ?e, "Failed to hide tooltip");
+        }
+    }
+    Ok(())
+}
+
+/// Handle XInput TouchBegin on a thumbnail
+pub fn handle_touch_begin(ctx: &mut EventContext, event: xinput::TouchBeginEvent) -> Result<()> {
+    let Some((&src, thumbnail)) = ctx
+        .eve_clients
+        .iter()
+        .find(|(_, t)| t.window() == event.event)
+    else {
+        return Ok(());
+    };
+    let gestures = ctx.connection.touch.begin(
+        event.detail,
+        src,
+        thumbnail.dimensions,
+        touch_position(&event),
+        Instant::now(),
+    );
+    handle_gestures(ctx, gestures, event.time)
+}
+
+/// Handle XInput TouchUpdate
+pub fn handle_touch_update(ctx: &mut EventContext, event: xinput::TouchUpdateEvent) -> Result<()> {
+    let gestures =
+        ctx.connection
+            .touch
+            .update(event.detail, touch_position(&event), Instant::now());
+    handle_gestures(ctx, gestures, event.time)
+}
+
+/// Handle XInput TouchEnd
+pub fn handle_touch_end(ctx: &mut EventContext, event: xinput::TouchEndEvent) -> Result<()> {
+    let gestures = ctx.connection.touch.end(event.detail, Instant::now());
+    handle_gestures(ctx, gestures, event.time)
+}
+
+fn touch_position(event: &xinput::TouchBeginEvent) -> (f64, f64) {
+    (
+        touch::to_pixels(event.root_x),
+        touch::to_pixels(event.root_y),
+    )
+}
+
+/// Apply touch gestures the same way as the matching mouse actions
+fn handle_gestures(ctx: &mut EventContext, gestures: Vec<Gesture>, time: Timestamp) -> Result<()> {
+    for gesture in gestures {
+        match gesture {
+            Gesture::Tap { src } => {
+                if let Some(thumbnail) = ctx.eve_clients.get(&src) {
+                    ctx.cycle_state.set_current(&thumbnail.character_name);
+                }
+                focus_client(ctx, src, time)?;
+            }
+            Gesture::DragStart { src, x, y } => start_drag(ctx, src, x, y, false)?,
+            Gesture::DragMove { src, x, y } => drag_to(ctx, src, x, y)?,
+            Gesture::DragEnd { src } | Gesture::PinchEnd { src } => end_drag(ctx, src)?,
+            Gesture::Pinch { src, size } => {
+                if let Some(thumbnail) = ctx.eve_clients.get_mut(&src) {
+                    thumbnail.resize(size.width, size.height)?;
+                    thumbnail
+                        .update(ctx.display_config, ctx.font_renderer)
+                        .context(format!(
+                            "Failed to redraw resized thumbnail for '{}'",
+                            thumbnail.character_name
+                        ))?;
+                }
+            }
+        }
+    }
     Ok(())
 }
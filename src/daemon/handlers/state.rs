@@ -1,7 +1,7 @@
 use super::super::dispatcher::EventContext;
 use crate::common::types::ThumbnailState;
 use anyhow::{Context, Result};
-use tracing::debug;
+use tracing::{debug, info};
 use x11rb::protocol::xproto::*;
 
 /// Handle FocusIn events - update focused state and visibility
@@ -24,8 +24,18 @@ pub fn handle_focus_in(ctx: &mut EventContext, event: FocusInEvent) -> Result<()
         debug!("Cancelled pending focus loss hide");
     }
 
-    if ctx.display_config.hide_when_no_focus && ctx.eve_clients.values().any(|x| !x.is_visible()) {
+    let session_state = &*ctx.session_state;
+    let groups = &ctx.daemon_config.profile.cycle_groups;
+    let revealable = |thumbnail: &crate::daemon::thumbnail::Thumbnail| {
+        !thumbnail.is_visible()
+            && !session_state.is_thumbnail_hidden(&thumbnail.character_name, groups)
+    };
+    if ctx.display_config.any_hides_when_unfocused() && ctx.eve_clients.values().any(revealable) {
         for thumbnail in ctx.eve_clients.values_mut() {
+            // Thumbnails hidden from the tray or by group hotkey stay hidden
+            if session_state.is_thumbnail_hidden(&thumbnail.character_name, groups) {
+                continue;
+            }
             debug!(character = %thumbnail.character_name, "Revealing thumbnail due to focus change");
             thumbnail.visibility(true).context(format!(
                 "Failed to show thumbnail '{}' on focus",
@@ -84,7 +94,7 @@ pub fn handle_focus_out(ctx: &mut EventContext, event: FocusOutEvent) -> Result<
 
     debug!(window = event.event, "FocusOut received");
 
-    if ctx.display_config.hide_when_no_focus {
+    if ctx.display_config.any_hides_when_unfocused() {
         let was_active = ctx
             .eve_clients
             .get(&event.event)
@@ -105,6 +115,61 @@ pub fn handle_focus_out(ctx: &mut EventContext, event: FocusOutEvent) -> Result<
     Ok(())
 }
 
+/// Handle _NET_ACTIVE_WINDOW changes - hide all thumbnails while a window of a
+/// do-not-disturb class has focus
+pub fn handle_active_window_change(ctx: &mut EventContext) -> Result<()> {
+    let profile = &ctx.daemon_config.profile;
+    if profile.thumbnail_do_not_disturb_classes.is_empty() && !ctx.session_state.do_not_disturb {
+        return Ok(());
+    }
+
+    let conn = ctx.app_ctx.conn;
+    let active = crate::x11::get_active_window(conn, ctx.app_ctx.screen, ctx.app_ctx.atoms)
+        .context("Failed to query active window for do-not-disturb classes")?
+        .filter(|&window| window != 0);
+    let class = match active {
+        Some(window) if !ctx.eve_clients.contains_key(&window) => {
+            crate::x11::get_window_class(conn, window, ctx.app_ctx.atoms)?
+        }
+        _ => None,
+    };
+    let do_not_disturb = class
+        .as_deref()
+        .is_some_and(|class| profile.is_do_not_disturb_class(class));
+    if do_not_disturb == ctx.session_state.do_not_disturb {
+        return Ok(());
+    }
+    ctx.session_state.do_not_disturb = do_not_disturb;
+    info!(
+        class = class.as_deref().unwrap_or_default(),
+        hidden = do_not_disturb,
+        "Do-not-disturb window focus changed"
+    );
+
+    // Thumbnails that hide on focus loss come back only if an EVE client took focus
+    let eve_focused = active.is_some_and(|window| ctx.eve_clients.contains_key(&window));
+    let groups = &profile.cycle_groups;
+    for thumbnail in ctx.eve_clients.values_mut() {
+        let name = &thumbnail.character_name;
+        let visible = !ctx.daemon_config.runtime_hidden
+            && !ctx.session_state.is_thumbnail_hidden(name, groups)
+            && (eve_focused || !ctx.display_config.hides_when_unfocused(name));
+        thumbnail.visibility(visible).context(format!(
+            "Failed to update visibility of '{}'",
+            thumbnail.character_name
+        ))?;
+        if visible {
+            thumbnail
+                .update(ctx.display_config, ctx.font_renderer)
+                .context(format!(
+                    "Failed to update thumbnail '{}' after do-not-disturb",
+                    thumbnail.character_name
+                ))?;
+        }
+    }
+    Ok(())
+}
+
 pub fn handle_net_wm_state(ctx: &mut EventContext, window: Window, atom: Atom) -> Result<()> {
     if let Some(thumbnail) = ctx.eve_clients.get_mut(&window)
         && let Some(mut state) = ctx
@@ -5,7 +5,9 @@ use x11rb::protocol::damage::ConnectionExt as DamageExt;
 use x11rb::protocol::xproto::*;
 
 use super::super::dispatcher::EventContext;
+use crate::common::ipc::{CharacterEvent, CharacterEventKind, DaemonError, DaemonErrorKind};
 use crate::common::types::Position;
+use crate::config::profile::PositionAnchor;
 
 /// Handle DamageNotify events - update damaged thumbnail
 pub fn handle_damage_notify(
@@ -16,17 +18,32 @@ pub fn handle_damage_notify(
         return Ok(());
     }
 
+    let batched = ctx.batches_damage();
     if let Some(thumbnail) = ctx
         .eve_clients
-        .values()
+        .values_mut()
         .find(|thumbnail| thumbnail.damage() == event.damage)
     {
-        thumbnail
-            .update(ctx.display_config, ctx.font_renderer)
-            .context(format!(
-                "Failed to update thumbnail for damage event (damage={})",
-                event.damage
-            ))?;
+        if batched || thumbnail.occluded || thumbnail.reads_in_software() {
+            // Drawn on the next frame tick (rate-limited in software) or once uncovered
+            thumbnail.damage_pending = true;
+        } else {
+            if let Err(e) = thumbnail.update(ctx.display_config, ctx.font_renderer) {
+                let _ =
+                    ctx.status_tx
+                        .send(crate::common::ipc::DaemonMessage::Error(DaemonError::new(
+                            DaemonErrorKind::Composite,
+                            format!(
+                                "Failed to draw thumbnail of '{}': {e:#}",
+                                thumbnail.character_name
+                            ),
+                        )));
+                return Err(e.context(format!(
+                    "Failed to update thumbnail for damage event (damage={})",
+                    event.damage
+                )));
+            }
+        }
         ctx.app_ctx
             .conn
             .damage_subtract(event.damage, 0u32, 0u32)
@@ -42,6 +59,42 @@ pub fn handle_damage_notify(
     Ok(())
 }
 
+/// Handle VisibilityNotify on a thumbnail - pause drawing while it is fully covered.
+/// Compositing window managers usually report every window as unobscured, so this only
+/// saves work without a compositor.
+pub fn handle_visibility_notify(
+    ctx: &mut EventContext,
+    event: VisibilityNotifyEvent,
+) -> Result<()> {
+    let batched = ctx.batches_damage();
+    let Some(thumbnail) = ctx
+        .eve_clients
+        .values_mut()
+        .find(|thumbnail| thumbnail.window() == event.window)
+    else {
+        return Ok(());
+    };
+
+    let occluded = event.state == Visibility::FULLY_OBSCURED;
+    if thumbnail.occluded == occluded {
+        return Ok(());
+    }
+    thumbnail.occluded = occluded;
+    debug!(character = %thumbnail.character_name, occluded = occluded, "Thumbnail visibility changed");
+
+    // Catch up on damage missed while covered (unless the frame tick will)
+    if !occluded && thumbnail.damage_pending && !batched {
+        thumbnail.damage_pending = false;
+        thumbnail
+            .update(ctx.display_config, ctx.font_renderer)
+            .context(format!(
+                "Failed to update uncovered thumbnail for '{}'",
+                thumbnail.character_name
+            ))?;
+    }
+    Ok(())
+}
+
 /// Helper to process a window once it has been identified (used by Create, Map, and Property handlers)
 pub fn process_detected_window(
     ctx: &mut EventContext,
@@ -83,36 +136,48 @@ pub fn process_detected_window(
                 match geom_result {
                     Ok(geom) => {
                         if !thumbnail.character_name.is_empty() {
-                            let settings = crate::common::types::CharacterSettings::new(
-                                geom.x,
-                                geom.y,
-                                thumbnail.dimensions.width,
-                                thumbnail.dimensions.height,
-                            );
-
-                            if identity.is_eve {
-                                // Check if we already have settings for this character.
-                                // If so, update the geometry but PRESERVE the user's overrides (like preview_mode).
-                                // This fixes the issue where unminimizing a client (MapNotify) would reset it to Live mode.
-                                if let Some(existing) = ctx
-                                    .daemon_config
-                                    .character_thumbnails
-                                    .get_mut(&thumbnail.character_name)
-                                {
-                                    existing.x = settings.x;
-                                    existing.y = settings.y;
-                                    existing.dimensions = settings.dimensions;
-                                } else {
-                                    ctx.daemon_config
-                                        .character_thumbnails
-                                        .insert(thumbnail.character_name.clone(), settings.clone());
-                                }
+                            let settings =
+                                thumbnail.position_settings(Position::new(geom.x, geom.y));
+
+                            // Re-mapping (e.g. unminimize) reports the same geometry; only real moves are unsaved
+                            let saved = if identity.is_eve {
+                                &ctx.daemon_config.character_thumbnails
+                            } else {
+                                &ctx.daemon_config.custom_source_thumbnails
+                            };
+                            let position_changed =
+                                saved.get(&thumbnail.character_name).is_none_or(|s| {
+                                    s.x != settings.x
+                                        || s.y != settings.y
+                                        || s.client_origin != settings.client_origin
+                                        || s.dimensions.width != settings.dimensions.width
+                                        || s.dimensions.height != settings.dimensions.height
+                                });
+
+                            // Check if we already have settings for this character or source instance.
+                            // If so, update the geometry but PRESERVE the user's overrides (like preview_mode).
+                            // This fixes the issue where unminimizing a client (MapNotify) would reset it to Live mode.
+                            let thumbnails = if identity.is_eve {
+                                &mut ctx.daemon_config.character_thumbnails
                             } else {
-                                ctx.daemon_config
-                                    .custom_source_thumbnails
+                                &mut ctx.daemon_config.custom_source_thumbnails
+                            };
+                            if let Some(existing) = thumbnails.get_mut(&thumbnail.character_name) {
+                                existing.x = settings.x;
+                                existing.y = settings.y;
+                                existing.client_origin = settings.client_origin;
+                                existing.dimensions = settings.dimensions;
+                            } else {
+                                thumbnails
                                     .insert(thumbnail.character_name.clone(), settings.clone());
                             }
 
+                            if position_changed {
+                                ctx.session_state.mark_positions_dirty(
+                                    ctx.daemon_config.profile.thumbnail_auto_save_position,
+                                );
+                            }
+
                             let _ = ctx.status_tx.send(DaemonMessage::PositionChanged {
                                 name: thumbnail.character_name.clone(),
                                 x: settings.x,
@@ -120,6 +185,7 @@ pub fn process_detected_window(
                                 width: settings.dimensions.width,
                                 height: settings.dimensions.height,
                                 is_custom: !identity.is_eve,
+                                client_origin: settings.client_origin,
                             });
 
                             // Only send CharacterDetected if this is a new window (avoid spam from Create+Map)
@@ -128,6 +194,13 @@ pub fn process_detected_window(
                                     name: thumbnail.character_name.clone(),
                                     is_custom: !identity.is_eve,
                                 });
+                                if identity.is_eve && !thumbnail.character_name.is_empty() {
+                                    let _ = ctx.status_tx.send(DaemonMessage::CharacterEvent(
+                                        CharacterEvent::now(CharacterEventKind::Login(
+                                            thumbnail.character_name.clone(),
+                                        )),
+                                    ));
+                                }
                             }
 
                             // Force initial update for custom sources as they might not emit Damage events immediately
@@ -254,6 +327,10 @@ pub fn process_detected_window(
                     error = %e,
                     "Failed to create thumbnail"
                 );
+                let _ = ctx.status_tx.send(DaemonMessage::Error(DaemonError::new(
+                    DaemonErrorKind::Composite,
+                    format!("Failed to create thumbnail of '{}': {e:#}", identity.name),
+                )));
             }
         }
     }
@@ -276,6 +353,7 @@ pub fn handle_create_notify(ctx: &mut EventContext, event: CreateNotifyEvent) ->
         ctx.app_ctx,
         event.window,
         ctx.session_state,
+        &ctx.daemon_config.eve_detection,
         &ctx.daemon_config.profile.custom_windows,
     )
     .context(format!("Failed to identify window {}", event.window))?
@@ -295,6 +373,7 @@ pub fn handle_map_notify(ctx: &mut EventContext, event: MapNotifyEvent) -> Resul
         ctx.app_ctx,
         event.window,
         ctx.session_state,
+        &ctx.daemon_config.eve_detection,
         &ctx.daemon_config.profile.custom_windows,
     )
     .context(format!("Failed to identify window {}", event.window))?
@@ -304,6 +383,47 @@ pub fn handle_map_notify(ctx: &mut EventContext, event: MapNotifyEvent) -> Resul
     Ok(())
 }
 
+/// Full rescan of the window tree - pick up clients whose Create/Map events were missed
+/// (e.g. started while the daemon was restarting). Returns the number of windows detected.
+pub fn rescan_windows(ctx: &mut EventContext) -> Result<usize> {
+    use crate::daemon::window_detection::{candidate_windows, identify_window};
+
+    let mut detected = 0;
+    for window in candidate_windows(ctx.app_ctx)? {
+        // Known clients are kept up to date by their own events
+        if ctx.eve_clients.contains_key(&window)
+            || (!ctx.display_config.enabled && ctx.cycle_state.has_window(window))
+        {
+            continue;
+        }
+
+        let identity = match identify_window(
+            ctx.app_ctx,
+            window,
+            ctx.session_state,
+            &ctx.daemon_config.eve_detection,
+            &ctx.daemon_config.profile.custom_windows,
+        ) {
+            Ok(Some(identity)) => identity,
+            Ok(None) => continue,
+            Err(e) => {
+                // Usually the window vanished between the tree walk and the query
+                debug!(window = window, error = %e, "Skipping window during rescan");
+                continue;
+            }
+        };
+
+        info!(
+            window = window,
+            character = %identity.name,
+            "Rescan found untracked window"
+        );
+        process_detected_window(ctx, window, identity)?;
+        detected += 1;
+    }
+    Ok(detected)
+}
+
 /// Handle DestroyNotify events - remove destroyed window
 pub fn handle_destroy_notify(ctx: &mut EventContext, event: DestroyNotifyEvent) -> Result<()> {
     let window_to_remove = if ctx.eve_clients.contains_key(&event.window) {
@@ -323,7 +443,22 @@ pub fn handle_destroy_notify(ctx: &mut EventContext, event: DestroyNotifyEvent)
         );
         ctx.cycle_state.remove_window(win);
         ctx.session_state.remove_window(win);
-        ctx.eve_clients.remove(&win);
+        if let Some(thumbnail) = ctx.eve_clients.remove(&win)
+            && !thumbnail.character_name.is_empty()
+        {
+            let _ = ctx
+                .status_tx
+                .send(crate::common::ipc::DaemonMessage::CharacterLost {
+                    name: thumbnail.character_name.clone(),
+                });
+            let _ = ctx
+                .status_tx
+                .send(crate::common::ipc::DaemonMessage::CharacterEvent(
+                    CharacterEvent::now(CharacterEventKind::Closed(
+                        thumbnail.character_name.clone(),
+                    )),
+                ));
+        }
     } else {
         debug!(
             window = event.window,
@@ -342,12 +477,16 @@ pub fn handle_identity_update(ctx: &mut EventContext, window: Window) -> Result<
     // Check if the window is already tracked
     if ctx.eve_clients.contains_key(&window) {
         // Window is tracked. Check if it's an EVE window to handle character swaps/renames.
-        if let Some(eve_window) = is_window_eve(ctx.app_ctx.conn, window, ctx.app_ctx.atoms)
-            .context(format!(
-                "Failed to check if window {} is EVE client during property change",
-                window
-            ))?
-        {
+        if let Some(eve_window) = is_window_eve(
+            ctx.app_ctx.conn,
+            window,
+            ctx.app_ctx.atoms,
+            &ctx.daemon_config.eve_detection,
+        )
+        .context(format!(
+            "Failed to check if window {} is EVE client during property change",
+            window
+        ))? {
             // It IS an EVE window.
             // Re-borrow thumbnail mutably
             let thumbnail = ctx
@@ -376,6 +515,11 @@ pub fn handle_identity_update(ctx: &mut EventContext, window: Window) -> Result<
 
             ctx.cycle_state
                 .update_character(window, new_character_name.to_string());
+            if let Some(kind) = CharacterEventKind::from_change(&old_name, new_character_name) {
+                let _ = ctx
+                    .status_tx
+                    .send(DaemonMessage::CharacterEvent(CharacterEvent::now(kind)));
+            }
 
             let new_settings = ctx
                 .daemon_config
@@ -383,6 +527,7 @@ pub fn handle_identity_update(ctx: &mut EventContext, window: Window) -> Result<
                     &old_name,
                     new_character_name,
                     current_pos,
+                    thumbnail.client_origin,
                     thumbnail.dimensions.width,
                     thumbnail.dimensions.height,
                 )
@@ -400,12 +545,7 @@ pub fn handle_identity_update(ctx: &mut EventContext, window: Window) -> Result<
                         .profile
                         .thumbnail_preserve_position_on_swap
                     {
-                        crate::common::types::CharacterSettings::new(
-                            current_pos.x,
-                            current_pos.y,
-                            thumbnail.dimensions.width,
-                            thumbnail.dimensions.height,
-                        )
+                        thumbnail.position_settings(current_pos)
                     } else {
                         let src_geom = ctx
                             .app_ctx
@@ -415,17 +555,11 @@ pub fn handle_identity_update(ctx: &mut EventContext, window: Window) -> Result<
                             .reply()
                             .context("Failed to get source geometry reply for reset position")?;
 
-                        let default_x = src_geom.x
-                            + crate::common::constants::positioning::DEFAULT_SPAWN_OFFSET;
-                        let default_y = src_geom.y
-                            + crate::common::constants::positioning::DEFAULT_SPAWN_OFFSET;
+                        let offset = ctx.daemon_config.timing.spawn_offset();
+                        let default_x = src_geom.x + offset;
+                        let default_y = src_geom.y + offset;
 
-                        crate::common::types::CharacterSettings::new(
-                            default_x,
-                            default_y,
-                            thumbnail.dimensions.width,
-                            thumbnail.dimensions.height,
-                        )
+                        thumbnail.position_settings(Position::new(default_x, default_y))
                     };
 
                     ctx.daemon_config
@@ -437,6 +571,10 @@ pub fn handle_identity_update(ctx: &mut EventContext, window: Window) -> Result<
                         is_custom: false,
                     });
 
+                    ctx.session_state.mark_positions_dirty(
+                        ctx.daemon_config.profile.thumbnail_auto_save_position,
+                    );
+
                     let _ = ctx.status_tx.send(DaemonMessage::PositionChanged {
                         name: new_character_name.to_string(),
                         x: settings.x,
@@ -444,14 +582,16 @@ pub fn handle_identity_update(ctx: &mut EventContext, window: Window) -> Result<
                         width: settings.dimensions.width,
                         height: settings.dimensions.height,
                         is_custom: false, // EVE chars are never custom sources
+                        client_origin: settings.client_origin,
                     });
 
                     Some(settings)
                 };
 
                 if let Some(ref settings) = final_settings {
+                    let pos = settings.position_from_client(thumbnail.client_origin);
                     ctx.session_state
-                        .update_window_position(window, settings.x, settings.y);
+                        .update_window_position(window, pos.x, pos.y);
                 }
 
                 thumbnail
@@ -477,6 +617,14 @@ pub fn handle_identity_update(ctx: &mut EventContext, window: Window) -> Result<
                         .context("Failed to restore border after character change")?;
                 }
             } else {
+                ctx.session_state.update_last_character(window, &old_name);
+                thumbnail
+                    .set_last_character(
+                        Some(old_name.clone()),
+                        ctx.display_config,
+                        ctx.font_renderer,
+                    )
+                    .context("Failed to remember last character after logout")?;
                 thumbnail
                     .set_character_name(String::new(), None, ctx.display_config, ctx.font_renderer)
                     .context(format!(
@@ -484,6 +632,26 @@ pub fn handle_identity_update(ctx: &mut EventContext, window: Window) -> Result<
                         old_name
                     ))?;
             }
+
+            if !old_name.is_empty() {
+                let _ = ctx.status_tx.send(DaemonMessage::CharacterLost {
+                    name: old_name.clone(),
+                });
+            }
+
+            // Apply the tray and group visibility toggles of the character now on this window
+            let groups = &ctx.daemon_config.profile.cycle_groups;
+            let hidden = ctx
+                .session_state
+                .is_thumbnail_hidden(&thumbnail.character_name, groups);
+            if hidden || ctx.session_state.is_thumbnail_hidden(&old_name, groups) {
+                thumbnail
+                    .visibility(!hidden && !ctx.daemon_config.runtime_hidden)
+                    .context(format!(
+                        "Failed to update visibility after character change from '{}'",
+                        old_name
+                    ))?;
+            }
         } else {
             // Tracked, but not valid EVE window (likely Custom Source)
             // Implicitly ignore property updates for custom sources to prevent re-detection loops
@@ -494,6 +662,7 @@ pub fn handle_identity_update(ctx: &mut EventContext, window: Window) -> Result<
             ctx.app_ctx,
             window,
             ctx.session_state,
+            &ctx.daemon_config.eve_detection,
             &ctx.daemon_config.profile.custom_windows,
         )
         .context(format!(
@@ -523,5 +692,76 @@ pub fn handle_configure_notify(ctx: &mut EventContext, event: ConfigureNotifyEve
             "Updated source dimensions from ConfigureNotify"
         );
     }
+    match ctx.daemon_config.profile.thumbnail_position_anchor {
+        PositionAnchor::Client => follow_client(ctx, event.window)?,
+        PositionAnchor::Screen if ctx.daemon_config.profile.thumbnail_follow_source_monitor => {
+            follow_source_monitor(ctx, event.window)?
+        }
+        PositionAnchor::Screen => {}
+    }
     Ok(())
 }
+
+/// Keep the thumbnail of `window` at its offset from the client after the client moved
+fn follow_client(ctx: &mut EventContext, window: Window) -> Result<()> {
+    let Some(thumbnail) = ctx.eve_clients.get_mut(&window) else {
+        return Ok(());
+    };
+    let Some(origin) =
+        crate::x11::get_root_position(ctx.app_ctx.conn, window, ctx.app_ctx.screen.root)
+    else {
+        return Ok(());
+    };
+    let Some(previous) = thumbnail.client_origin.replace(origin) else {
+        return Ok(());
+    };
+    if previous == origin {
+        return Ok(());
+    }
+
+    let to = thumbnail
+        .current_position
+        .relative_to(previous)
+        .offset(origin);
+    debug!(
+        character = %thumbnail.character_name,
+        x = to.x,
+        y = to.y,
+        "Client moved, moving its thumbnail along"
+    );
+    thumbnail.reposition(to.x, to.y)?;
+    ctx.session_state
+        .update_window_position(thumbnail.window(), to.x, to.y);
+    Ok(())
+}
+
+/// Move the thumbnail of `window` onto the monitor its client was moved to
+fn follow_source_monitor(ctx: &mut EventContext, window: Window) -> Result<()> {
+    let Some(thumbnail) = ctx.eve_clients.get_mut(&window) else {
+        return Ok(());
+    };
+    let Some(monitor) =
+        crate::x11::get_window_center(ctx.app_ctx.conn, window, ctx.app_ctx.screen.root)
+            .and_then(|center| ctx.daemon_config.display_scales.monitor_at(center))
+            .copied()
+    else {
+        return Ok(());
+    };
+    let Some(previous) = thumbnail.source_monitor.replace(monitor) else {
+        return Ok(());
+    };
+    if previous == monitor {
+        return Ok(());
+    }
+
+    let from = thumbnail.current_position;
+    let to = previous.translate_to(&monitor, from, thumbnail.dimensions);
+    info!(
+        character = %thumbnail.character_name,
+        x = to.x,
+        y = to.y,
+        "Client moved to another monitor, moving its thumbnail along"
+    );
+    thumbnail.reposition(to.x, to.y)?;
+    super::input::save_position(ctx, window, Some(from))
+}
@@ -0,0 +1,628 @@
+//! End-to-end daemon tests against a headless X server
+//!
+//! Each test starts its own server through `crate::test_support` and is skipped when
+//! Xvfb is not installed. Most drive the daemon's handlers directly; the last one runs
+//! the whole daemon, talking to it over IPC the way a Manager does.
+
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use ipc_channel::ipc::{IpcOneShotServer, IpcReceiver, IpcSender};
+use tokio::sync::mpsc::UnboundedSender;
+use x11rb::connection::Connection;
+use x11rb::protocol::xproto::ConnectionExt;
+use x11rb::rust_connection::RustConnection;
+
+use crate::common::ipc::{BootstrapMessage, ConfigMessage, DaemonMessage};
+use crate::common::types::Position;
+use crate::config::profile::{Config, CycleGroup, CycleSlot};
+use crate::config::{DaemonConfig, EveDetection};
+use crate::test_support::{
+    XServer, create_eve_client, create_window, publish_client_list, set_title,
+};
+use crate::x11::{AppContext, CachedAtoms, CachedFormats};
+
+use super::connection_state::ConnectionState;
+use super::cycle_state::CycleState;
+use super::dispatcher::EventContext;
+use super::font::FontRenderer;
+use super::handlers::input::{flush_drag_motion, handle_raw_motion};
+use super::handlers::window::rescan_windows;
+use super::main_loop::{DaemonMode, DaemonSignal, ShutdownSignals, run_daemon_with_signals};
+use super::session_state::SessionState;
+use super::snapping::{Rect, find_snap_position};
+use super::window_detection::{identify_window, scan_eve_windows};
+
+/// Daemon resources for a connection, built the same way `run_daemon` does
+struct Fixture {
+    atoms: CachedAtoms,
+    formats: CachedFormats,
+    daemon_config: DaemonConfig,
+    font_renderer: FontRenderer,
+}
+
+impl Fixture {
+    fn new(conn: &RustConnection, screen_num: usize, config: &Config) -> Self {
+        let screen = &conn.setup().roots[screen_num];
+        let daemon_config = DaemonConfig::from_config(config);
+        let font_renderer = FontRenderer::resolve_from_config(
+            conn,
+            &daemon_config.profile.thumbnail_text_font,
+            daemon_config.text_size(),
+        )
+        .unwrap();
+        Self {
+            atoms: CachedAtoms::new(conn).unwrap(),
+            formats: CachedFormats::new(conn, screen).unwrap(),
+            daemon_config,
+            font_renderer,
+        }
+    }
+}
+
+#[test]
+fn test_initial_scan_detects_existing_clients() {
+    let Some(server) = XServer::start() else {
+        return;
+    };
+    let (conn, screen_num) = server.connect();
+    let screen = &conn.setup().roots[screen_num];
+
+    let alice = create_eve_client(&conn, screen, Some("Alice"), 0, 0).unwrap();
+    let bob = create_eve_client(&conn, screen, Some("Bob"), 200, 0).unwrap();
+    let browser = create_window(&conn, screen, "Firefox", "firefox").unwrap();
+    publish_client_list(&conn, screen.root, &[alice, bob, browser]).unwrap();
+
+    let mut fixture = Fixture::new(&conn, screen_num, &Config::default());
+    let ctx = AppContext {
+        conn: &conn,
+        screen,
+        atoms: &fixture.atoms,
+        formats: &fixture.formats,
+        touch: false,
+    };
+    let display_config = fixture.daemon_config.build_display_config();
+    let mut session = SessionState::new();
+
+    let clients = scan_eve_windows(
+        &ctx,
+        &display_config,
+        &fixture.font_renderer,
+        &mut fixture.daemon_config,
+        &mut session,
+    )
+    .unwrap();
+
+    assert_eq!(clients.len(), 2);
+    assert_eq!(clients[&alice].character_name, "Alice");
+    assert_eq!(clients[&bob].character_name, "Bob");
+    assert!(!clients.contains_key(&browser));
+
+    // First-time characters get their initial geometry recorded
+    assert!(
+        fixture
+            .daemon_config
+            .character_thumbnails
+            .contains_key("Alice")
+    );
+    assert!(
+        fixture
+            .daemon_config
+            .character_thumbnails
+            .contains_key("Bob")
+    );
+}
+
+#[test]
+fn test_rescan_picks_up_missed_client() {
+    let Some(server) = XServer::start() else {
+        return;
+    };
+    let (conn, screen_num) = server.connect();
+    let screen = &conn.setup().roots[screen_num];
+
+    // No _NET_CLIENT_LIST at all: the tree walk alone has to find the client
+    let alice = create_eve_client(&conn, screen, Some("Alice"), 0, 0).unwrap();
+
+    let mut fixture = Fixture::new(&conn, screen_num, &Config::default());
+    let ctx = AppContext {
+        conn: &conn,
+        screen,
+        atoms: &fixture.atoms,
+        formats: &fixture.formats,
+        touch: false,
+    };
+    let display_config = fixture.daemon_config.build_display_config();
+    let mut session = SessionState::new();
+    let mut clients = scan_eve_windows(
+        &ctx,
+        &display_config,
+        &fixture.font_renderer,
+        &mut fixture.daemon_config,
+        &mut session,
+    )
+    .unwrap();
+    assert!(clients.contains_key(&alice));
+
+    // Bob starts without the daemon seeing any events for it
+    let bob = create_eve_client(&conn, screen, Some("Bob"), 200, 0).unwrap();
+    let (status_tx, _status_rx) = ipc_channel::ipc::channel().unwrap();
+    let mut cycle = CycleState::new(Vec::new());
+    let mut connection = ConnectionState::default();
+    let mut context = EventContext {
+        app_ctx: &ctx,
+        daemon_config: &mut fixture.daemon_config,
+        eve_clients: &mut clients,
+        session_state: &mut session,
+        connection: &mut connection,
+        cycle_state: &mut cycle,
+        status_tx: &status_tx,
+        font_renderer: &fixture.font_renderer,
+        display_config: &display_config,
+    };
+
+    assert_eq!(rescan_windows(&mut context).unwrap(), 1);
+    assert_eq!(clients[&bob].character_name, "Bob");
+    assert!(cycle.has_window(bob));
+
+    // Already tracked clients are left alone
+    let mut context = EventContext {
+        app_ctx: &ctx,
+        daemon_config: &mut fixture.daemon_config,
+        eve_clients: &mut clients,
+        session_state: &mut session,
+        connection: &mut connection,
+        cycle_state: &mut cycle,
+        status_tx: &status_tx,
+        font_renderer: &fixture.font_renderer,
+        display_config: &display_config,
+    };
+    assert_eq!(rescan_windows(&mut context).unwrap(), 0);
+}
+
+#[test]
+fn test_login_updates_identity() {
+    let Some(server) = XServer::start() else {
+        return;
+    };
+    let (conn, screen_num) = server.connect();
+    let screen = &conn.setup().roots[screen_num];
+    let client = create_eve_client(&conn, screen, None, 0, 0).unwrap();
+
+    let fixture = Fixture::new(&conn, screen_num, &Config::default());
+    let ctx = AppContext {
+        conn: &conn,
+        screen,
+        atoms: &fixture.atoms,
+        formats: &fixture.formats,
+        touch: false,
+    };
+    let mut session = SessionState::new();
+
+    let identity = identify_window(&ctx, client, &mut session, &EveDetection::default(), &[])
+        .unwrap()
+        .expect("login screen is an EVE client");
+    assert!(identity.is_eve);
+    assert_eq!(identity.name, "");
+
+    set_title(&conn, client, "EVE - Alice").unwrap();
+    let identity = identify_window(&ctx, client, &mut session, &EveDetection::default(), &[])
+        .unwrap()
+        .expect("logged-in client");
+    assert_eq!(identity.name, "Alice");
+}
+
+#[test]
+fn test_cycle_follows_group_order() {
+    let Some(server) = XServer::start() else {
+        return;
+    };
+    let (conn, screen_num) = server.connect();
+    let screen = &conn.setup().roots[screen_num];
+
+    let names = ["Alice", "Bob", "Carol"];
+    let windows: Vec<_> = names
+        .iter()
+        .enumerate()
+        .map(|(i, name)| create_eve_client(&conn, screen, Some(name), i as i16 * 100, 0).unwrap())
+        .collect();
+    publish_client_list(&conn, screen.root, &windows).unwrap();
+
+    let mut config = Config::default();
+    config.profiles[0].cycle_groups = vec![CycleGroup {
+        name: "Fleet".to_string(),
+        cycle_list: vec![
+            CycleSlot::Eve("Carol".to_string()),
+            CycleSlot::Eve("Alice".to_string()),
+            CycleSlot::Eve("Bob".to_string()),
+        ],
+        hotkey_forward: None,
+        hotkey_backward: None,
+        hotkey_toggle_visibility: None,
+        auto_include_new: false,
+        auto_include_hotkey: false,
+        color: None,
+    }];
+    let mut fixture = Fixture::new(&conn, screen_num, &config);
+    let ctx = AppContext {
+        conn: &conn,
+        screen,
+        atoms: &fixture.atoms,
+        formats: &fixture.formats,
+        touch: false,
+    };
+    let display_config = fixture.daemon_config.build_display_config();
+    let mut session = SessionState::new();
+    let clients = scan_eve_windows(
+        &ctx,
+        &display_config,
+        &fixture.font_renderer,
+        &mut fixture.daemon_config,
+        &mut session,
+    )
+    .unwrap();
+
+    // Register detected clients as run_daemon does
+    let mut cycle = CycleState::new(fixture.daemon_config.profile.cycle_groups.clone());
+    for (window, thumbnail) in &clients {
+        cycle.add_window(thumbnail.character_name.clone(), *window);
+    }
+
+    let order: Vec<String> = (0..3)
+        .map(|_| cycle.cycle_forward("Fleet", None, false).unwrap().1)
+        .collect();
+    assert_eq!(order, ["Alice", "Bob", "Carol"]);
+
+    let (window, name) = cycle.cycle_backward("Fleet", None, false).unwrap();
+    assert_eq!(name, "Bob");
+    assert_eq!(window, windows[1]);
+}
+
+#[test]
+fn test_cycle_includes_custom_source() {
+    let Some(server) = XServer::start() else {
+        return;
+    };
+    let (conn, screen_num) = server.connect();
+    let screen = &conn.setup().roots[screen_num];
+
+    let alice = create_eve_client(&conn, screen, Some("Alice"), 0, 0).unwrap();
+    let bob = create_eve_client(&conn, screen, Some("Bob"), 100, 0).unwrap();
+    let mumble = create_window(&conn, screen, "Mumble", "mumble").unwrap();
+    publish_client_list(&conn, screen.root, &[alice, bob, mumble]).unwrap();
+
+    let mut config = Config::default();
+    config.profiles[0].custom_windows =
+        vec![serde_json::from_str(r#"{"class_pattern": "^mumble$", "alias": "Voice"}"#).unwrap()];
+    config.profiles[0].cycle_groups = vec![CycleGroup {
+        name: "Fleet".to_string(),
+        cycle_list: vec![
+            CycleSlot::Eve("Alice".to_string()),
+            CycleSlot::Source("Voice".to_string()),
+            CycleSlot::Eve("Bob".to_string()),
+        ],
+        hotkey_forward: None,
+        hotkey_backward: None,
+        hotkey_toggle_visibility: None,
+        auto_include_new: false,
+        auto_include_hotkey: false,
+        color: None,
+    }];
+    let mut fixture = Fixture::new(&conn, screen_num, &config);
+    let ctx = AppContext {
+        conn: &conn,
+        screen,
+        atoms: &fixture.atoms,
+        formats: &fixture.formats,
+        touch: false,
+    };
+    let display_config = fixture.daemon_config.build_display_config();
+    let mut session = SessionState::new();
+    let clients = scan_eve_windows(
+        &ctx,
+        &display_config,
+        &fixture.font_renderer,
+        &mut fixture.daemon_config,
+        &mut session,
+    )
+    .unwrap();
+    assert_eq!(clients[&mumble].character_name, "Voice");
+
+    let mut cycle = CycleState::new(fixture.daemon_config.profile.cycle_groups.clone());
+    for (window, thumbnail) in &clients {
+        cycle.add_window(thumbnail.character_name.clone(), *window);
+    }
+
+    let order: Vec<_> = (0..3)
+        .map(|_| cycle.cycle_forward("Fleet", None, false).unwrap().0)
+        .collect();
+    assert_eq!(order, [mumble, bob, alice]);
+}
+
+#[test]
+fn test_dragged_thumbnail_snaps_to_neighbour() {
+    let Some(server) = XServer::start() else {
+        return;
+    };
+    let (conn, screen_num) = server.connect();
+    let screen = &conn.setup().roots[screen_num];
+
+    let alice = create_eve_client(&conn, screen, Some("Alice"), 0, 0).unwrap();
+    let bob = create_eve_client(&conn, screen, Some("Bob"), 0, 0).unwrap();
+    publish_client_list(&conn, screen.root, &[alice, bob]).unwrap();
+
+    let mut fixture = Fixture::new(&conn, screen_num, &Config::default());
+    let ctx = AppContext {
+        conn: &conn,
+        screen,
+        atoms: &fixture.atoms,
+        formats: &fixture.formats,
+        touch: false,
+    };
+    let display_config = fixture.daemon_config.build_display_config();
+    let mut session = SessionState::new();
+    let mut clients = scan_eve_windows(
+        &ctx,
+        &display_config,
+        &fixture.font_renderer,
+        &mut fixture.daemon_config,
+        &mut session,
+    )
+    .unwrap();
+
+    let anchor = clients.get_mut(&alice).unwrap();
+    anchor.reposition(100, 100).unwrap();
+    let anchor_rect = Rect {
+        x: 100,
+        y: 100,
+        width: anchor.dimensions.width,
+        height: anchor.dimensions.height,
+    };
+
+    // Drop Bob's thumbnail a few pixels right of Alice's, as a drag would
+    let dragged = clients.get_mut(&bob).unwrap();
+    let drop_x = anchor_rect.right() + 5;
+    let dragged_rect = Rect {
+        x: drop_x,
+        y: 103,
+        width: dragged.dimensions.width,
+        height: dragged.dimensions.height,
+    };
+    let snapped = find_snap_position(
+        dragged_rect,
+        &[anchor_rect],
+        fixture.daemon_config.profile.thumbnail_snap_threshold,
+        fixture.daemon_config.profile.thumbnail_snap_gap,
+    )
+    .expect("within snap threshold");
+    dragged.reposition(snapped.x, snapped.y).unwrap();
+
+    let geometry = conn
+        .get_geometry(dragged.window())
+        .unwrap()
+        .reply()
+        .unwrap();
+    assert_eq!(geometry.x, anchor_rect.right());
+    assert_eq!(geometry.y, anchor_rect.top());
+}
+
+#[test]
+fn test_dragged_thumbnail_follows_pointer_once_per_batch() {
+    let Some(server) = XServer::start() else {
+        return;
+    };
+    let (conn, screen_num) = server.connect();
+    let screen = &conn.setup().roots[screen_num];
+
+    let alice = create_eve_client(&conn, screen, Some("Alice"), 0, 0).unwrap();
+    publish_client_list(&conn, screen.root, &[alice]).unwrap();
+
+    let mut fixture = Fixture::new(&conn, screen_num, &Config::default());
+    let ctx = AppContext {
+        conn: &conn,
+        screen,
+        atoms: &fixture.atoms,
+        formats: &fixture.formats,
+        touch: false,
+    };
+    let display_config = fixture.daemon_config.build_display_config();
+    let mut session = SessionState::new();
+    let mut clients = scan_eve_windows(
+        &ctx,
+        &display_config,
+        &fixture.font_renderer,
+        &mut fixture.daemon_config,
+        &mut session,
+    )
+    .unwrap();
+
+    // Right-button drag grabbed the thumbnail 10px in from its corner at the origin
+    let thumbnail = clients.get_mut(&alice).unwrap();
+    thumbnail.reposition(0, 0).unwrap();
+    thumbnail.input_state.dragging = true;
+    thumbnail.input_state.drag_start = Position::new(10, 10);
+    thumbnail.input_state.win_start = Position::new(0, 0);
+    let window = thumbnail.window();
+    session.pointer_drag = Some(alice);
+
+    let (status_tx, _status_rx) = ipc_channel::ipc::channel().unwrap();
+    let mut cycle = CycleState::new(Vec::new());
+    let mut connection = ConnectionState::default();
+    let mut context = EventContext {
+        app_ctx: &ctx,
+        daemon_config: &mut fixture.daemon_config,
+        eve_clients: &mut clients,
+        session_state: &mut session,
+        connection: &mut connection,
+        cycle_state: &mut cycle,
+        status_tx: &status_tx,
+        font_renderer: &fixture.font_renderer,
+        display_config: &display_config,
+    };
+
+    // Several queued motion events, then the pointer's final position
+    for _ in 0..3 {
+        handle_raw_motion(&mut context);
+    }
+    conn.warp_pointer(x11rb::NONE, screen.root, 0, 0, 0, 0, 60, 70)
+        .unwrap();
+    flush_drag_motion(&mut context).unwrap();
+    assert!(!context.session_state.drag_moved);
+
+    let geometry = conn.get_geometry(window).unwrap().reply().unwrap();
+    assert_eq!((geometry.x, geometry.y), (50, 60));
+}
+
+#[test]
+fn test_argb_thumbnail_applies_opacity_itself() {
+    let Some(server) = XServer::start() else {
+        return;
+    };
+    let (conn, screen_num) = server.connect();
+    let screen = &conn.setup().roots[screen_num];
+
+    let alice = create_eve_client(&conn, screen, Some("Alice"), 0, 0).unwrap();
+    publish_client_list(&conn, screen.root, &[alice]).unwrap();
+
+    let mut config = Config::default();
+    config.profiles[0].thumbnail_argb_visual = true;
+    config.profiles[0].thumbnail_opacity = 50;
+    let mut fixture = Fixture::new(&conn, screen_num, &config);
+    let ctx = AppContext {
+        conn: &conn,
+        screen,
+        atoms: &fixture.atoms,
+        formats: &fixture.formats,
+        touch: false,
+    };
+    let display_config = fixture.daemon_config.build_display_config();
+    let mut session = SessionState::new();
+    let clients = scan_eve_windows(
+        &ctx,
+        &display_config,
+        &fixture.font_renderer,
+        &mut fixture.daemon_config,
+        &mut session,
+    )
+    .unwrap();
+    let window = clients[&alice].window();
+
+    let geometry = conn.get_geometry(window).unwrap().reply().unwrap();
+    assert_eq!(geometry.depth, 32);
+    // No opacity hint for a compositor to apply a second time
+    let opacity = conn
+        .get_property(
+            false,
+            window,
+            fixture.atoms.net_wm_window_opacity,
+            x11rb::protocol::xproto::AtomEnum::CARDINAL,
+            0,
+            1,
+        )
+        .unwrap()
+        .reply()
+        .unwrap();
+    assert_eq!(opacity.value_len, 0);
+}
+
+/// Time the daemon gets to report something over IPC
+const DAEMON_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A daemon running on its own thread, stopped and joined when dropped
+struct TestDaemon {
+    status_rx: IpcReceiver<DaemonMessage>,
+    signal_tx: UnboundedSender<DaemonSignal>,
+    thread: Option<JoinHandle<()>>,
+    _config_tx: IpcSender<ConfigMessage>,
+}
+
+impl Drop for TestDaemon {
+    fn drop(&mut self) {
+        let _ = self.signal_tx.send(DaemonSignal::Terminate);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Start the daemon in managed mode on its own thread and hand it `config`. Signals come
+/// from the returned handle rather than the test process's handlers.
+fn spawn_daemon(config: &Config) -> TestDaemon {
+    let (server, server_name) = IpcOneShotServer::<BootstrapMessage>::new().unwrap();
+    let (signal_tx, signals) = ShutdownSignals::channel();
+    let thread = std::thread::spawn(move || {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let result = runtime.block_on(run_daemon_with_signals(
+            DaemonMode::Managed {
+                ipc_server: server_name,
+            },
+            signals,
+        ));
+        eprintln!("Test daemon exited: {:?}", result);
+    });
+    let (_, (config_tx, status_rx)) = server.accept().unwrap();
+    config_tx
+        .send(ConfigMessage::Full(Box::new(DaemonConfig::from_config(
+            config,
+        ))))
+        .unwrap();
+    TestDaemon {
+        status_rx,
+        signal_tx,
+        thread: Some(thread),
+        _config_tx: config_tx,
+    }
+}
+
+/// Read status messages until one `wanted` accepts; panics after `DAEMON_TIMEOUT`
+fn wait_for(status_rx: &IpcReceiver<DaemonMessage>, wanted: impl Fn(&DaemonMessage) -> bool) {
+    let deadline = Instant::now() + DAEMON_TIMEOUT;
+    loop {
+        let left = deadline.saturating_duration_since(Instant::now());
+        match status_rx.try_recv_timeout(left) {
+            Ok(message) if wanted(&message) => return,
+            Ok(_) => {}
+            Err(e) => panic!("Daemon did not send the expected message: {:?}", e),
+        }
+    }
+}
+
+fn detected(name: &str) -> impl Fn(&DaemonMessage) -> bool {
+    move |message| matches!(message, DaemonMessage::CharacterDetected { name: n, .. } if n == name)
+}
+
+#[test]
+fn test_run_daemon_reports_clients_over_ipc() {
+    let Some(server) = XServer::start() else {
+        return;
+    };
+    let (conn, screen_num) = server.connect();
+    let screen = &conn.setup().roots[screen_num];
+
+    let alice = create_eve_client(&conn, screen, Some("Alice"), 0, 0).unwrap();
+    publish_client_list(&conn, screen.root, &[alice]).unwrap();
+
+    let mut config = Config::default();
+    config.profiles[0].x11_display = server.display().to_string();
+    let daemon = spawn_daemon(&config);
+    let status_rx = &daemon.status_rx;
+
+    // Found by the initial scan
+    wait_for(status_rx, detected("Alice"));
+
+    // A client started later is picked up from its events
+    let bob = create_eve_client(&conn, screen, Some("Bob"), 200, 0).unwrap();
+    publish_client_list(&conn, screen.root, &[alice, bob]).unwrap();
+    wait_for(status_rx, detected("Bob"));
+
+    // Closing a client is reported too
+    conn.destroy_window(alice).unwrap();
+    publish_client_list(&conn, screen.root, &[bob]).unwrap();
+    wait_for(
+        status_rx,
+        |message| matches!(message, DaemonMessage::CharacterLost { name } if name == "Alice"),
+    );
+}
@@ -0,0 +1,398 @@
+//! Text labels drawn into window background pixmaps
+//!
+//! Used by the group indicator, the switch OSD and thumbnail tooltips. Lines are stacked top
+//! to bottom and left-aligned; the X server repaints the background, so no Expose handling is
+//! needed.
+
+use anyhow::{Context, Result};
+use x11rb::connection::Connection;
+use x11rb::protocol::render::{ConnectionExt as RenderExt, CreatePictureAux, PictOp};
+use x11rb::protocol::xproto::{
+    ChangeGCAux, ChangeWindowAttributesAux, Char2b, ConfigureWindowAux, ConnectionExt as XprotoExt,
+    CreateGCAux, ImageFormat, Pixmap, Rectangle, StackMode, Window,
+};
+
+use crate::common::constants::x11;
+use crate::common::types::{Dimensions, Position};
+use crate::x11::AppContext;
+
+use super::font::FontRenderer;
+
+/// Colors and spacing of a label
+#[derive(Debug, Clone, Copy)]
+pub struct LabelStyle {
+    /// Background (RGB)
+    pub background: u32,
+    /// Text color (ARGB)
+    pub text_color: u32,
+    /// Space between the text and the edge in pixels
+    pub padding: u16,
+    /// Text size relative to the thumbnail font size
+    pub text_scale: f32,
+}
+
+/// Square picture drawn left of the text
+#[derive(Debug, Clone, Copy)]
+pub struct LabelImage<'a> {
+    pub size: u16,
+    /// Premultiplied ARGB32 pixels (BGRA byte order)
+    pub pixels: &'a [u8],
+}
+
+/// Override-redirect window showing a label on top of everything
+#[derive(Debug)]
+pub struct LabelPopup {
+    style: LabelStyle,
+    /// Names the popup in error messages
+    what: &'static str,
+    /// Created on first use and kept (unmapped while hidden) for the connection's lifetime
+    window: Option<Window>,
+    shown: bool,
+}
+
+impl LabelPopup {
+    pub const fn new(style: LabelStyle, what: &'static str) -> Self {
+        Self {
+            style,
+            what,
+            window: None,
+            shown: false,
+        }
+    }
+
+    /// Show `lines` (and `image` left of them) with the popup's top-left corner where `place`
+    /// puts a label of the given size, until `hide` is called
+    pub fn show(
+        &mut self,
+        ctx: &AppContext,
+        font_renderer: &FontRenderer,
+        lines: &[&str],
+        image: Option<LabelImage>,
+        place: impl FnOnce(Dimensions) -> Position,
+    ) -> Result<()> {
+        let what = self.what;
+        let size = measure(ctx, font_renderer, lines, image, &self.style)?;
+        let position = place(size);
+
+        let window = match self.window {
+            Some(window) => window,
+            None => {
+                let window = crate::x11::create_overlay_window(
+                    ctx.conn,
+                    ctx.screen,
+                    self.style.background,
+                    what,
+                )?;
+                self.window = Some(window);
+                window
+            }
+        };
+        let pixmap = draw(ctx, font_renderer, lines, image, size, &self.style)?;
+        ctx.conn
+            .change_window_attributes(
+                window,
+                &ChangeWindowAttributesAux::new().background_pixmap(pixmap),
+            )
+            .context(format!("Failed to set {what} background"))?;
+        // The window keeps its own reference to the background
+        ctx.conn
+            .free_pixmap(pixmap)
+            .context(format!("Failed to free {what} pixmap"))?;
+
+        ctx.conn
+            .configure_window(
+                window,
+                &ConfigureWindowAux::new()
+                    .x(i32::from(position.x))
+                    .y(i32::from(position.y))
+                    .width(u32::from(size.width))
+                    .height(u32::from(size.height))
+                    .stack_mode(StackMode::ABOVE),
+            )
+            .context(format!("Failed to place {what}"))?;
+        ctx.conn
+            .clear_area(false, window, 0, 0, 0, 0)
+            .context(format!("Failed to repaint {what}"))?;
+        ctx.conn
+            .map_window(window)
+            .context(format!("Failed to map {what}"))?;
+        ctx.conn
+            .flush()
+            .context(format!("Failed to flush {what}"))?;
+        self.shown = true;
+        Ok(())
+    }
+
+    pub fn hide(&mut self, ctx: &AppContext) -> Result<()> {
+        if let Some(window) = self.window.filter(|_| self.shown) {
+            ctx.conn
+                .unmap_window(window)
+                .context(format!("Failed to unmap {}", self.what))?;
+            ctx.conn
+                .flush()
+                .context(format!("Failed to flush {}", self.what))?;
+        }
+        self.shown = false;
+        Ok(())
+    }
+}
+
+/// Size of the label `draw` draws for `lines` and `image`, padding included
+fn measure(
+    ctx: &AppContext,
+    font_renderer: &FontRenderer,
+    lines: &[&str],
+    image: Option<LabelImage>,
+    style: &LabelStyle,
+) -> Result<Dimensions> {
+    let mut width = 0u16;
+    let mut height = 0u16;
+    for line in lines {
+        let size = measure_line(ctx, font_renderer, line, style)?;
+        width = width.max(size.width);
+        height = height.saturating_add(size.height);
+    }
+    if let Some(image) = image {
+        width = width.saturating_add(image_width(image, style));
+        height = height.max(image.size);
+    }
+    Ok(Dimensions::new(
+        width.saturating_add(style.padding * 2),
+        height.saturating_add(style.padding * 2),
+    ))
+}
+
+fn measure_line(
+    ctx: &AppContext,
+    font_renderer: &FontRenderer,
+    text: &str,
+    style: &LabelStyle,
+) -> Result<Dimensions> {
+    if let Some(font_id) = font_renderer.x11_font_id() {
+        let extents = ctx
+            .conn
+            .query_text_extents(font_id, &core_font_chars(text))
+            .context("Failed to send text extents query")?
+            .reply()
+            .context("Failed to get text extents")?;
+        Ok(Dimensions::new(
+            extents.overall_width.max(0) as u16,
+            (extents.font_ascent + extents.font_descent).max(0) as u16,
+        ))
+    } else {
+        let rendered = font_renderer
+            .render_text(text, style.text_color, text_size(font_renderer, style))
+            .context("Failed to render label text")?;
+        Ok(Dimensions::new(
+            rendered.width as u16,
+            rendered.height as u16,
+        ))
+    }
+}
+
+/// Background pixmap of `size` with `image` and `lines` drawn on it
+fn draw(
+    ctx: &AppContext,
+    font_renderer: &FontRenderer,
+    lines: &[&str],
+    image: Option<LabelImage>,
+    size: Dimensions,
+    style: &LabelStyle,
+) -> Result<Pixmap> {
+    let pixmap = ctx
+        .conn
+        .generate_id()
+        .context("Failed to generate label pixmap ID")?;
+    ctx.conn
+        .create_pixmap(
+            ctx.screen.root_depth,
+            pixmap,
+            ctx.screen.root,
+            size.width,
+            size.height,
+        )
+        .context("Failed to create label pixmap")?;
+
+    let gc = ctx
+        .conn
+        .generate_id()
+        .context("Failed to generate label GC ID")?;
+    let mut gc_aux = CreateGCAux::new()
+        .foreground(style.background)
+        .background(style.background);
+    if let Some(font_id) = font_renderer.x11_font_id() {
+        gc_aux = gc_aux.font(font_id);
+    }
+    ctx.conn
+        .create_gc(gc, pixmap, &gc_aux)
+        .context("Failed to create label GC")?;
+    ctx.conn
+        .poly_fill_rectangle(
+            pixmap,
+            gc,
+            &[Rectangle {
+                x: 0,
+                y: 0,
+                width: size.width,
+                height: size.height,
+            }],
+        )
+        .context("Failed to fill label background")?;
+
+    let padding = style.padding as i16;
+    if let Some(image) = image.filter(|image| image.size > 0) {
+        composite_argb(
+            ctx,
+            pixmap,
+            image.pixels,
+            Dimensions::new(image.size, image.size),
+            Position::new(padding, padding),
+        )?;
+    }
+    let x = padding.saturating_add(image.map_or(0, |image| image_width(image, style)) as i16);
+    let mut y = padding;
+    if let Some(font_id) = font_renderer.x11_font_id() {
+        // X11 core font: fixed size, Latin-1 only
+        ctx.conn
+            .change_gc(
+                gc,
+                &ChangeGCAux::new().foreground(style.text_color & 0x00FFFFFF),
+            )
+            .context("Failed to set label text color")?;
+        for line in lines {
+            let extents = ctx
+                .conn
+                .query_text_extents(font_id, &core_font_chars(line))
+                .context("Failed to send text extents query")?
+                .reply()
+                .context("Failed to get text extents")?;
+            let bytes: Vec<u8> = core_font_chars(line).iter().map(|c| c.byte2).collect();
+            ctx.conn
+                .image_text8(pixmap, gc, x, y + extents.font_ascent, &bytes)
+                .context("Failed to draw label text")?;
+            y += extents.font_ascent + extents.font_descent;
+        }
+    } else {
+        for line in lines {
+            let rendered = font_renderer
+                .render_text(line, style.text_color, text_size(font_renderer, style))
+                .context("Failed to render label text")?;
+            if rendered.width > 0 && rendered.height > 0 {
+                composite_argb(
+                    ctx,
+                    pixmap,
+                    &rendered.data,
+                    Dimensions::new(rendered.width as u16, rendered.height as u16),
+                    Position::new(x, y),
+                )?;
+            }
+            y += rendered.height as i16;
+        }
+    }
+
+    ctx.conn.free_gc(gc).context("Failed to free label GC")?;
+    Ok(pixmap)
+}
+
+/// Width `image` takes up left of the text, including the space after it
+fn image_width(image: LabelImage, style: &LabelStyle) -> u16 {
+    image.size.saturating_add(style.padding)
+}
+
+/// Blend premultiplied ARGB32 pixels (text or an image) onto `target` at `offset`
+fn composite_argb(
+    ctx: &AppContext,
+    target: Pixmap,
+    data: &[u8],
+    size: Dimensions,
+    offset: Position,
+) -> Result<()> {
+    // Text can't be blended without an ARGB format; the label keeps its background
+    let Some(argb) = ctx.formats.argb else {
+        return Ok(());
+    };
+    let image = ctx
+        .conn
+        .generate_id()
+        .context("Failed to generate label image pixmap ID")?;
+    ctx.conn
+        .create_pixmap(x11::ARGB_DEPTH, image, target, size.width, size.height)
+        .context("Failed to create label image pixmap")?;
+    let gc = ctx
+        .conn
+        .generate_id()
+        .context("Failed to generate label image GC ID")?;
+    ctx.conn
+        .create_gc(gc, image, &CreateGCAux::new())
+        .context("Failed to create label image GC")?;
+    ctx.conn
+        .put_image(
+            ImageFormat::Z_PIXMAP,
+            image,
+            gc,
+            size.width,
+            size.height,
+            0,
+            0,
+            0,
+            x11::ARGB_DEPTH,
+            data,
+        )
+        .context("Failed to upload label image")?;
+
+    let source = ctx
+        .conn
+        .generate_id()
+        .context("Failed to generate label image picture ID")?;
+    ctx.conn
+        .render_create_picture(source, image, argb, &CreatePictureAux::new())
+        .context("Failed to create label image picture")?;
+    let destination = ctx
+        .conn
+        .generate_id()
+        .context("Failed to generate label picture ID")?;
+    ctx.conn
+        .render_create_picture(
+            destination,
+            target,
+            ctx.formats.rgb,
+            &CreatePictureAux::new(),
+        )
+        .context("Failed to create label picture")?;
+    ctx.conn
+        .render_composite(
+            PictOp::OVER,
+            source,
+            0u32,
+            destination,
+            0,
+            0,
+            0,
+            0,
+            offset.x,
+            offset.y,
+            size.width,
+            size.height,
+        )
+        .context("Failed to composite label image")?;
+
+    ctx.conn.render_free_picture(destination)?;
+    ctx.conn.render_free_picture(source)?;
+    ctx.conn.free_gc(gc)?;
+    ctx.conn.free_pixmap(image)?;
+    Ok(())
+}
+
+fn text_size(font_renderer: &FontRenderer, style: &LabelStyle) -> f32 {
+    font_renderer.size() * style.text_scale
+}
+
+/// Latin-1 characters for the X11 core font; others show as '?'
+fn core_font_chars(text: &str) -> Vec<Char2b> {
+    text.chars()
+        .map(|c| Char2b {
+            byte1: 0,
+            byte2: u8::try_from(u32::from(c)).unwrap_or(b'?'),
+        })
+        .collect()
+}
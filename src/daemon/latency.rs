@@ -0,0 +1,88 @@
+//! Hotkey switch latency
+//!
+//! Measures the time from a backend reading a hotkey press to `activate_window` completing,
+//! so the X11 and evdev backends can be compared and slow switching reported with numbers.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use crate::common::constants::input::LATENCY_SAMPLES;
+use crate::common::ipc::HotkeyLatency;
+use crate::config::profile::HotkeyBackendType;
+
+#[derive(Debug, Default)]
+pub struct LatencyTracker {
+    /// Backend of the recorded samples; switching backends starts over
+    backend: Option<HotkeyBackendType>,
+    samples: u32,
+    /// Most recent switches, oldest first
+    recent: VecDeque<Duration>,
+}
+
+impl LatencyTracker {
+    /// Record one switch and return the updated statistics
+    pub fn record(&mut self, backend: HotkeyBackendType, latency: Duration) -> HotkeyLatency {
+        if self.backend != Some(backend) {
+            *self = Self {
+                backend: Some(backend),
+                ..Self::default()
+            };
+        }
+        if self.recent.len() >= LATENCY_SAMPLES {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(latency);
+        self.samples = self.samples.saturating_add(1);
+
+        let mut sorted: Vec<Duration> = self.recent.iter().copied().collect();
+        sorted.sort();
+        // Nearest-rank percentile
+        let percentile = |p: usize| sorted[(sorted.len() * p).div_ceil(100).max(1) - 1];
+        HotkeyLatency {
+            backend,
+            samples: self.samples,
+            last: latency,
+            median: percentile(50),
+            p95: percentile(95),
+            max: sorted[sorted.len() - 1],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_summarizes_recent_switches() {
+        let mut tracker = LatencyTracker::default();
+        let mut summary = None;
+        for ms in (1..=20).rev() {
+            summary = Some(tracker.record(HotkeyBackendType::X11, Duration::from_millis(ms)));
+        }
+        let summary = summary.unwrap();
+        assert_eq!(summary.samples, 20);
+        assert_eq!(summary.last, Duration::from_millis(1));
+        assert_eq!(summary.median, Duration::from_millis(10));
+        assert_eq!(summary.p95, Duration::from_millis(19));
+        assert_eq!(summary.max, Duration::from_millis(20));
+
+        // A different backend starts a fresh series
+        let summary = tracker.record(HotkeyBackendType::Evdev, Duration::from_millis(7));
+        assert_eq!(summary.samples, 1);
+        assert_eq!(summary.max, Duration::from_millis(7));
+    }
+
+    #[test]
+    fn test_record_keeps_only_recent_samples() {
+        let mut tracker = LatencyTracker::default();
+        tracker.record(HotkeyBackendType::X11, Duration::from_secs(1));
+        let mut summary = None;
+        for _ in 0..LATENCY_SAMPLES {
+            summary = Some(tracker.record(HotkeyBackendType::X11, Duration::from_millis(2)));
+        }
+        let summary = summary.unwrap();
+        assert_eq!(summary.samples, LATENCY_SAMPLES as u32 + 1);
+        assert_eq!(summary.max, Duration::from_millis(2));
+    }
+}
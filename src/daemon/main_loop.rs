@@ -10,17 +10,29 @@ use x11rb::connection::Connection;
 use x11rb::protocol::damage::ConnectionExt as DamageExt;
 use x11rb::protocol::xproto::*;
 
-use crate::common::constants::eve;
-use crate::common::ipc::{BootstrapMessage, ConfigMessage, DaemonMessage};
+use crate::common::constants::{eve, group_indicator, switch_osd, x11 as x11_consts};
+use crate::common::ipc::{ConfigMessage, DaemonError, DaemonErrorKind, DaemonMessage};
+use crate::common::types::Position;
 use crate::config::DaemonConfig;
+use crate::config::power::LowPowerMode;
+use crate::config::profile::{CycleMonitor, PositionAnchor};
 use crate::input::listener::{self, CycleCommand, TimestampedCommand};
 use crate::x11::{AppContext, CachedAtoms, activate_window, minimize_window, unminimize_window};
-use ipc_channel::ipc::{self, IpcReceiver, IpcSender};
+use ipc_channel::ipc::IpcSender;
 
+use super::activation::{self, Stage};
+use super::connection_state::ConnectionState;
 use super::cycle_state::CycleState;
 use super::dispatcher::{EventContext, handle_event};
 use super::font;
+use super::group_indicator::GroupIndicator;
+use super::manager_link::{self, ManagerEvent};
+use super::persistence;
+use super::power::{self, BatteryMonitor};
 use super::session_state::SessionState;
+use super::snapshots::{self, SnapshotTimer};
+use super::switch_osd::SwitchOsd;
+use super::systemd;
 use super::thumbnail::Thumbnail;
 
 use std::collections::HashSet;
@@ -31,40 +43,94 @@ use x11rb::rust_connection::RustConnection;
 use crate::input::backend::AllowedWindows;
 
 struct HotkeyResources {
-    #[allow(dead_code)]
     handle: Option<Vec<JoinHandle<()>>>,
     rx: mpsc::Receiver<TimestampedCommand>,
     groups: HashMap<crate::config::HotkeyBinding, Vec<String>>,
+    /// Backend the listeners read presses with
+    backend: crate::config::HotkeyBackendType,
+    /// Why the listener failed to start, reported to the Manager once connected
+    startup_error: Option<String>,
+}
+
+impl HotkeyResources {
+    /// Whether every listener thread has exited (the X11 backend stops with its connection)
+    fn listeners_stopped(&self) -> bool {
+        self.handle
+            .as_ref()
+            .is_some_and(|handles| handles.iter().all(|h| h.is_finished()))
+    }
+
+    /// Close the command channel and wait for the listener threads to exit, so their key
+    /// grabs are gone before new listeners make theirs. Only the X11 backend watches the
+    /// channel; evdev listeners block on their devices.
+    fn stop(self) {
+        let Self { handle, rx, .. } = self;
+        drop(rx);
+        for handle in handle.into_iter().flatten() {
+            let _ = handle.join();
+        }
+    }
 }
 
 struct DaemonResources<'a> {
     config: DaemonConfig,
     session: SessionState,
     cycle: CycleState,
+    /// Started fresh on every connection, never carried over
+    connection: ConnectionState,
     eve_clients: HashMap<Window, Thumbnail<'a>>,
 }
 
-fn initialize_x11() -> Result<(
+/// State that outlives an X server connection
+struct CarriedState {
+    config: DaemonConfig,
+    session: SessionState,
+    cycle: CycleState,
+}
+
+/// Why the event loop returned
+enum LoopExit {
+    Shutdown,
+    /// The X server connection died; reconnect and continue with this state
+    ConnectionLost(Box<CarriedState>),
+    /// The profile now previews another X display, or thumbnails need another visual;
+    /// (re)connect with this state
+    DisplayChanged(Box<CarriedState>),
+}
+
+fn initialize_x11(
+    daemon_config: &DaemonConfig,
+) -> Result<(
     RustConnection,
     usize,
     CachedAtoms,
     crate::x11::CachedFormats,
+    bool,
 )> {
     // Initial screen metrics are required for auto-scaling thumbnails.
-    let (conn, screen_num) = x11rb::connect(None)
-        .context("Failed to connect to X11 server. Is DISPLAY set correctly?")?;
+    let display = daemon_config.profile.display();
+    let (conn, default_screen) = x11rb::connect(display).with_context(|| match display {
+        Some(name) => format!("Failed to connect to X11 display {name}"),
+        None => "Failed to connect to X11 server. Is DISPLAY set correctly?".to_string(),
+    })?;
+
+    // Pre-cache atoms once at startup
+    let atoms = CachedAtoms::new(&conn).context("Failed to cache X11 atoms at startup")?;
+
+    let display_name = display_name(daemon_config);
+    let screen_count = conn.setup().roots.len();
+    let screen_num = pick_screen(&conn, &atoms, daemon_config, default_screen);
 
     let screen = &conn.setup().roots[screen_num];
     debug!(
+        display = %display_name,
         screen = screen_num,
+        screens = screen_count,
         width = screen.width_in_pixels,
         height = screen.height_in_pixels,
         "Connected to X11 server"
     );
 
-    // Pre-cache atoms once at startup
-    let atoms = CachedAtoms::new(&conn).context("Failed to cache X11 atoms at startup")?;
-
     conn.damage_query_version(1, 1)
         .context("Failed to query DAMAGE extension version. Is DAMAGE extension available?")?;
 
@@ -72,6 +138,7 @@ fn initialize_x11() -> Result<(
         screen.root,
         &ChangeWindowAttributesAux::new().event_mask(
             EventMask::SUBSTRUCTURE_NOTIFY
+                | EventMask::PROPERTY_CHANGE // _NET_ACTIVE_WINDOW, for do-not-disturb classes
                 | EventMask::BUTTON_PRESS
                 | EventMask::BUTTON_RELEASE
                 | EventMask::POINTER_MOTION,
@@ -79,6 +146,9 @@ fn initialize_x11() -> Result<(
     )
     .context("Failed to set event mask on root window")?;
 
+    // Touch gestures on thumbnails need XInput 2.2
+    let touch = super::touch::init(&conn);
+
     // Pre-cache picture formats
     let formats = crate::x11::CachedFormats::new(&conn, screen)
         .context("Failed to cache picture formats at startup")?;
@@ -87,11 +157,38 @@ fn initialize_x11() -> Result<(
     // Note: Font renderer initialization is deferred until after config load
     // as it depends on user-configured font settings.
 
-    Ok((conn, screen_num, atoms, formats))
+    Ok((conn, screen_num, atoms, formats, touch))
+}
+
+/// Name of the X display the profile previews
+fn display_name(daemon_config: &DaemonConfig) -> String {
+    daemon_config
+        .profile
+        .display()
+        .map(str::to_string)
+        .or_else(|| std::env::var("DISPLAY").ok())
+        .unwrap_or_default()
+}
+
+/// Screen to preview: on a display with several screens the first one running EVE clients,
+/// unless the display name picks a screen; `default_screen` otherwise
+fn pick_screen(
+    conn: &RustConnection,
+    atoms: &CachedAtoms,
+    daemon_config: &DaemonConfig,
+    default_screen: usize,
+) -> usize {
+    if conn.setup().roots.len() > 1
+        && !crate::x11::display_names_screen(&display_name(daemon_config))
+    {
+        crate::x11::find_eve_screen(conn, atoms, &daemon_config.eve_detection)
+            .unwrap_or(default_screen)
+    } else {
+        default_screen
+    }
 }
 
 fn initialize_state(
-    _screen: &Screen,
     daemon_config: DaemonConfig,
 ) -> Result<(
     DaemonConfig,
@@ -112,7 +209,12 @@ fn initialize_state(
     );
 
     // Initialize cycle state from config
-    let cycle_state = CycleState::new(daemon_config.profile.cycle_groups.clone());
+    let mut cycle_state = CycleState::new(daemon_config.profile.cycle_groups.clone());
+    if daemon_config.profile.hotkey_skip_persist {
+        for name in &daemon_config.profile.hotkey_skipped_characters {
+            cycle_state.set_skipped(name, true);
+        }
+    }
 
     Ok((daemon_config, config, session_state, cycle_state))
 }
@@ -173,7 +275,7 @@ fn setup_hotkeys(daemon_config: &DaemonConfig, allowed_windows: AllowedWindows)
     }
 
     // Spawn hotkey listener (start if any hotkeys configured: cycle or per-character)
-    let cycle_hotkeys: Vec<(CycleCommand, crate::config::HotkeyBinding)> = daemon_config
+    let mut cycle_hotkeys: Vec<(CycleCommand, crate::config::HotkeyBinding)> = daemon_config
         .profile
         .cycle_groups
         .iter()
@@ -185,22 +287,57 @@ fn setup_hotkeys(daemon_config: &DaemonConfig, allowed_windows: AllowedWindows)
             if let Some(bwd) = &g.hotkey_backward {
                 hotkeys.push((CycleCommand::Backward(g.name.clone()), bwd.clone()));
             }
+            if let Some(toggle) = &g.hotkey_toggle_visibility {
+                hotkeys.push((
+                    CycleCommand::ToggleGroupVisibility(g.name.clone()),
+                    toggle.clone(),
+                ));
+            }
             hotkeys
         })
         .collect();
 
+    // Keys acting on whichever cycle group is active
+    let profile = &daemon_config.profile;
+    cycle_hotkeys.extend(
+        [
+            (CycleCommand::ActiveForward, &profile.hotkey_active_forward),
+            (
+                CycleCommand::ActiveBackward,
+                &profile.hotkey_active_backward,
+            ),
+            (CycleCommand::NextGroup, &profile.hotkey_next_group),
+        ]
+        .into_iter()
+        .filter_map(|(command, binding)| Some((command, binding.clone()?))),
+    );
+    if let Some(binding) = &profile.hotkey_screenshot {
+        cycle_hotkeys.push((CycleCommand::Screenshot, binding.clone()));
+    }
+    if let Some(binding) = &profile.hotkey_toggle_hotkey_overlay {
+        cycle_hotkeys.push((CycleCommand::ToggleHotkeyOverlay, binding.clone()));
+    }
+    if let Some(binding) = &profile.hotkey_swap_positions {
+        cycle_hotkeys.push((CycleCommand::SwapPositions, binding.clone()));
+    }
+
     let has_cycle_keys = !cycle_hotkeys.is_empty();
     let has_character_hotkeys = !character_hotkeys.is_empty();
     let _has_profile_hotkeys = !profile_hotkeys.is_empty();
     let has_profile_hotkeys = !profile_hotkeys.is_empty();
     let has_skip_key = daemon_config.profile.hotkey_toggle_skip.is_some();
     let has_toggle_previews_key = daemon_config.profile.hotkey_toggle_previews.is_some();
+    let has_save_positions_key = daemon_config.profile.hotkey_save_positions.is_some();
+    let has_undo_move_key = daemon_config.profile.hotkey_undo_move.is_some();
 
+    let mut startup_error = None;
     let hotkey_handle = if has_cycle_keys
         || has_character_hotkeys
         || has_profile_hotkeys
         || has_skip_key
         || has_toggle_previews_key
+        || has_save_positions_key
+        || has_undo_move_key
     {
         // Select backend based on functionality
         use crate::config::HotkeyBackendType;
@@ -212,6 +349,11 @@ fn setup_hotkeys(daemon_config: &DaemonConfig, allowed_windows: AllowedWindows)
             profile_hotkeys: profile_hotkeys.clone(),
             toggle_skip_key: daemon_config.profile.hotkey_toggle_skip.clone(),
             toggle_previews_key: daemon_config.profile.hotkey_toggle_previews.clone(),
+            save_positions_key: daemon_config.profile.hotkey_save_positions.clone(),
+            undo_move_key: daemon_config.profile.hotkey_undo_move.clone(),
+            display: daemon_config.profile.display().map(str::to_string),
+            double_tap: daemon_config.timing.double_tap(),
+            long_press: daemon_config.timing.long_press(),
         };
 
         match daemon_config.profile.hotkey_backend {
@@ -233,12 +375,15 @@ fn setup_hotkeys(daemon_config: &DaemonConfig, allowed_windows: AllowedWindows)
                             has_profile_hotkeys = has_profile_hotkeys,
                             has_skip_key = has_skip_key,
                             has_toggle_previews_key = has_toggle_previews_key,
+                            has_save_positions_key = has_save_positions_key,
+                            has_undo_move_key = has_undo_move_key,
                             "Hotkey support enabled"
                         );
                         Some(handle)
                     }
                     Err(e) => {
                         error!(error = %e, backend = "x11", "Failed to start hotkey listener");
+                        startup_error = Some(format!("Failed to grab hotkeys: {e:#}"));
                         None
                     }
                 }
@@ -247,6 +392,9 @@ fn setup_hotkeys(daemon_config: &DaemonConfig, allowed_windows: AllowedWindows)
                 info!("Using evdev hotkey backend (requires input group membership)");
                 if !crate::input::evdev_backend::EvdevBackend::is_available() {
                     listener::print_permission_error();
+                    startup_error = Some(
+                        "No readable input devices; add your user to the 'input' group".to_string(),
+                    );
                     None
                 } else {
                     match crate::input::evdev_backend::EvdevBackend::spawn(
@@ -265,6 +413,8 @@ fn setup_hotkeys(daemon_config: &DaemonConfig, allowed_windows: AllowedWindows)
                                 has_profile_hotkeys = has_profile_hotkeys,
                                 has_skip_key = has_skip_key,
                                 has_toggle_previews_key = has_toggle_previews_key,
+                                has_save_positions_key = has_save_positions_key,
+                                has_undo_move_key = has_undo_move_key,
                                 "Hotkey support enabled"
                             );
                             Some(handle)
@@ -272,6 +422,7 @@ fn setup_hotkeys(daemon_config: &DaemonConfig, allowed_windows: AllowedWindows)
                         Err(e) => {
                             error!(error = %e, backend = "evdev", "Failed to start hotkey listener");
                             listener::print_permission_error();
+                            startup_error = Some(format!("Failed to open input devices: {e:#}"));
                             None
                         }
                     }
@@ -287,6 +438,8 @@ fn setup_hotkeys(daemon_config: &DaemonConfig, allowed_windows: AllowedWindows)
         handle: hotkey_handle,
         rx: hotkey_rx,
         groups: hotkey_groups,
+        backend: daemon_config.profile.hotkey_backend,
+        startup_error,
     }
 }
 
@@ -297,33 +450,24 @@ async fn run_event_loop(
     mut display_config: crate::config::DisplayConfig,
     atoms: &CachedAtoms,
     formats: &crate::x11::CachedFormats,
+    touch: bool,
     mut font_renderer: crate::daemon::font::FontRenderer,
     mut resources: DaemonResources<'_>,
-    mut hotkey_rx: mpsc::Receiver<TimestampedCommand>,
-    hotkey_groups: HashMap<crate::config::HotkeyBinding, Vec<String>>,
-    mut sigusr1: tokio::signal::unix::Signal,
-    config_rx: IpcReceiver<ConfigMessage>,
-    status_tx: IpcSender<DaemonMessage>,
-    allowed_windows: AllowedWindows,
-) -> Result<()> {
+    hotkeys: &mut HotkeyResources,
+    signals: &mut ShutdownSignals,
+    manager_rx: &mut mpsc::Receiver<ManagerEvent>,
+    status_tx: &mut IpcSender<DaemonMessage>,
+    allowed_windows: &AllowedWindows,
+    watchdog: Option<std::time::Duration>,
+) -> Result<LoopExit> {
     debug!("Daemon running (async)");
-
-    // Wrap IPC receiver in something async-friendly?
-    // IpcReceiver is blocking. IPC-channel doesn't support async recv out of the box in a way that integrates with tokio::select! easily without a bridge.
-    // We should spawn a thread to bridge IPC messages to a tokio channel.
-    let (ipc_config_tx, mut ipc_config_rx_tokio) = mpsc::channel(1);
-
-    std::thread::spawn(move || {
-        while let Ok(msg) = config_rx.recv() {
-            if ipc_config_tx.blocking_send(msg).is_err() {
-                break; // Manager connection lost
-            }
-        }
-        // If config_rx fails (Manager side closed), this thread ends.
-        // We should probably explicitly terminate the daemon here if we want absolute safety.
-        error!("IPC Config channel closed - Manager process likely terminated. Exiting daemon.");
-        std::process::exit(1);
-    });
+    let HotkeyResources {
+        rx: hotkey_rx,
+        groups: hotkey_groups,
+        backend: hotkey_backend,
+        startup_error: hotkey_error,
+        ..
+    } = hotkeys;
 
     // Wrap X11 connection in AsyncFd for async polling
     // This allows us to wake up exactly when X11 has data, without busy polling
@@ -335,10 +479,84 @@ async fn run_event_loop(
     // Set the first tick to finish immediately? No, we can wait 3s for the first one.
     heartbeat_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
 
+    // Focus statistics for the Manager (and the session CSV file)
+    let mut focus_stats_interval = tokio::time::interval(std::time::Duration::from_secs(
+        crate::common::constants::config::focus_stats::REPORT_INTERVAL_SECS,
+    ));
+    focus_stats_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    // AFK snapshots of every client while enabled
+    let mut snapshot_interval = tokio::time::interval(std::time::Duration::from_secs(
+        crate::common::constants::config::snapshots::CHECK_INTERVAL_SECS,
+    ));
+    snapshot_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    let mut snapshot_timer = SnapshotTimer::default();
+
+    // systemd watchdog keep-alive (only polled when the unit sets WatchdogSec=)
+    let mut watchdog_interval =
+        tokio::time::interval(watchdog.unwrap_or(std::time::Duration::from_secs(86400)));
+    watchdog_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    // Periodic position save (rebuilt when the interval changes via config update)
+    let mut autosave_secs = resources.config.profile.thumbnail_autosave_interval_secs;
+    let mut autosave_interval = autosave_timer(autosave_secs);
+
+    // Full window rescan, catching clients whose Create/Map events were missed
+    let mut rescan_period = resources.config.timing.rescan_interval();
+    let mut rescan_interval = rescan_timer(rescan_period);
+
+    // Low-power mode: power source polling (first tick fires right away)
+    let mut battery = BatteryMonitor::spawn();
+    let mut on_battery = false;
+    let mut power_interval =
+        tokio::time::interval(std::time::Duration::from_secs(power::POLL_INTERVAL_SECS));
+    power_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    let active = resources.config.low_power.is_active(on_battery);
+    set_low_power(&mut resources, active, &display_config, &font_renderer);
+
+    // Skips restored from the config (or carried over a reconnect) show in the Manager
+    let _ = status_tx.send(DaemonMessage::SkippedCharacters(resources.cycle.skipped()));
+
+    // Frame tick drawing batched damage, at the display refresh rate unless low-power mode
+    // caps it (rebuilt when the period changes)
+    let refresh_rate = crate::x11::get_refresh_rate(conn, screen.root).unwrap_or(60.0);
+    debug!(refresh_rate = refresh_rate, "Display refresh rate");
+    let refresh_period = std::time::Duration::from_secs_f64(1.0 / refresh_rate.max(1.0));
+    let mut current_frame_period = frame_period(&resources, refresh_period);
+    let mut frame_interval = frame_timer(current_frame_period);
+
+    // Last unsaved-positions state reported to the Manager
+    let mut reported_dirty = false;
+
+    // Clients found by the initial scan were never reported individually
+    announce_clients(&resources, status_tx);
+    if let Some(message) = hotkey_error.take() {
+        let _ = status_tx.send(DaemonMessage::Error(DaemonError::new(
+            DaemonErrorKind::HotkeyGrab,
+            message,
+        )));
+    }
+
+    // Characters tied to a cycle group follow the group carried over from a previous connection
+    apply_active_group(&mut resources, &display_config, &font_renderer);
+
     // Timer for delayed thumbnail hiding (hysteresis)
     let hide_timer = tokio::time::sleep(tokio::time::Duration::from_secs(86400));
     tokio::pin!(hide_timer);
 
+    // Label naming the cycle group picked by the group-switch hotkey, hidden by its timer
+    let mut group_indicator = GroupIndicator::default();
+    let mut group_indicator_shown = false;
+    let group_indicator_timer = tokio::time::sleep(tokio::time::Duration::from_secs(86400));
+    tokio::pin!(group_indicator_timer);
+    let mut switch_osd = SwitchOsd::default();
+    let mut switch_osd_shown = false;
+    let switch_osd_timer = tokio::time::sleep(tokio::time::Duration::from_secs(86400));
+    tokio::pin!(switch_osd_timer);
+
+    let mut connection_lost = false;
+    let mut display_switched = false;
+
     loop {
         // Scope ctx to allow mutable borrow of font_renderer later
         {
@@ -348,15 +566,21 @@ async fn run_event_loop(
                 screen,
                 atoms,
                 formats,
+                touch,
             };
 
             // Process all pending X11 events without blocking to ensure the queue is drained
             // This prevents the event channel from filling up during heavy activity
-            while let Some(event) = ctx
-                .conn
-                .poll_for_event()
-                .context("Failed to poll for X11 event")?
-            {
+            loop {
+                let event = match ctx.conn.poll_for_event() {
+                    Ok(Some(event)) => event,
+                    Ok(None) => break,
+                    Err(e) => {
+                        error!(error = %e, "X11 connection lost");
+                        connection_lost = true;
+                        break;
+                    }
+                };
                 // Scope the mutable borrows for event handling
                 {
                     let mut context = EventContext {
@@ -364,20 +588,63 @@ async fn run_event_loop(
                         daemon_config: &mut resources.config,
                         eve_clients: &mut resources.eve_clients,
                         session_state: &mut resources.session,
+                        connection: &mut resources.connection,
                         cycle_state: &mut resources.cycle,
 
-                        status_tx: &status_tx,
+                        status_tx,
                         font_renderer: &font_renderer,
                         display_config: &display_config,
                     };
 
-                    let _ = handle_event(&mut context, event)
-                        .inspect_err(|err| error!(error = ?err, "Event handling error"));
+                    if let Err(err) = handle_event(&mut context, event) {
+                        if crate::x11::is_connection_lost(&err) {
+                            error!(error = ?err, "X11 connection lost while handling an event");
+                            connection_lost = true;
+                            break;
+                        }
+                        error!(error = ?err, "Event handling error");
+                    }
+                }
+            }
+
+            if connection_lost {
+                break;
+            }
+
+            // Dragged thumbnails follow the pointer once per batch (motion compression)
+            if resources.session.drag_moved {
+                let mut context = EventContext {
+                    app_ctx: &ctx,
+                    daemon_config: &mut resources.config,
+                    eve_clients: &mut resources.eve_clients,
+                    session_state: &mut resources.session,
+                    connection: &mut resources.connection,
+                    cycle_state: &mut resources.cycle,
+                    status_tx,
+                    font_renderer: &font_renderer,
+                    display_config: &display_config,
+                };
+                if let Err(err) = super::handlers::input::flush_drag_motion(&mut context) {
+                    if crate::x11::is_connection_lost(&err) {
+                        error!(error = ?err, "X11 connection lost while dragging");
+                        connection_lost = true;
+                        break;
+                    }
+                    error!(error = ?err, "Drag motion error");
                 }
             }
 
-            // Flush any pending requests to X server
-            let _ = ctx.conn.flush();
+            // Focus changes and new or closed clients move the next cycle target
+            refresh_next_target(&mut resources, &display_config, &font_renderer);
+            record_focus(&mut resources);
+
+            // Flush any pending requests to X server; requests from the select arms below
+            // that hit a dead connection fail here too
+            if let Err(e) = ctx.conn.flush() {
+                error!(error = %e, "X11 connection lost while flushing requests");
+                connection_lost = true;
+                break;
+            }
         }
 
         // Sync allowed windows with backend
@@ -409,6 +676,12 @@ async fn run_event_loop(
             }
         }
 
+        // Report unsaved position changes (only tracked while auto-save is off)
+        if resources.session.positions_dirty != reported_dirty {
+            reported_dirty = resources.session.positions_dirty;
+            let _ = status_tx.send(DaemonMessage::PositionsDirty(reported_dirty));
+        }
+
         // Update hide timer if deadline was set or changed
         if let Some(deadline) = resources.session.focus_loss_deadline {
             // Calculate duration until deadline
@@ -427,13 +700,25 @@ async fn run_event_loop(
             );
         }
 
+        // Software-scaled sources always draw their damage on the frame tick
+        let software_sources = resources
+            .eve_clients
+            .values()
+            .any(|t| t.reads_in_software());
+
+        let period = frame_period(&resources, refresh_period);
+        if period != current_frame_period {
+            current_frame_period = period;
+            frame_interval = frame_timer(current_frame_period);
+        }
+
         tokio::select! {
             biased;  // Process branches in order - prioritize hotkeys over heartbeat/IPC
 
             // 1. Handle Hotkey Commands (HIGHEST PRIORITY)
             // Checked first to minimize latency and prevent XWayland grab conflicts
             Some(msg) = hotkey_rx.recv() => {
-                 let TimestampedCommand { command, timestamp } = msg;
+                 let TimestampedCommand { command, timestamp, received } = msg;
 
                  // Reconstruct AppContext for hotkey handling (read-only borrow)
                 let ctx = AppContext {
@@ -442,6 +727,7 @@ async fn run_event_loop(
 
                     atoms,
                     formats,
+                    touch,
                 };
 
                 // NOTE: Logic gates hotkeys to only function when a tracked window has focus.
@@ -519,7 +805,38 @@ async fn run_event_loop(
                         );
                     }
 
-                    if let Some((window, character_name)) = handle_cycle_command(&command, &mut resources, &ctx, &font_renderer, &status_tx, &hotkey_groups) {
+                    let activated = handle_cycle_command(&command, &mut resources, &ctx, &font_renderer, status_tx, hotkey_groups);
+                    if matches!(command, CycleCommand::ToggleHotkeyOverlay) {
+                        display_config = resources.config.build_display_config();
+                        redraw_overlays(&mut resources, &display_config, &font_renderer);
+                    }
+                    apply_active_group(&mut resources, &display_config, &font_renderer);
+                    refresh_next_target(&mut resources, &display_config, &font_renderer);
+                    let indicator = match command {
+                        CycleCommand::NextGroup => resources
+                            .cycle
+                            .selected_group()
+                            .map(|group| format!("Cycle group: {group}")),
+                        CycleCommand::SwapPositions => resources
+                            .session
+                            .swap_source
+                            .and_then(|window| resources.eve_clients.get(&window))
+                            .map(|t| format!("Swap {}: click a thumbnail", t.character_name)),
+                        _ => None,
+                    };
+                    if let Some(text) = indicator {
+                        match group_indicator.show(&ctx, &font_renderer, &text) {
+                            Ok(()) => {
+                                group_indicator_shown = true;
+                                group_indicator_timer.as_mut().reset(
+                                    tokio::time::Instant::now()
+                                        + std::time::Duration::from_millis(group_indicator::DURATION_MS),
+                                );
+                            }
+                            Err(e) => warn!(error = ?e, "Failed to show cycle group indicator"),
+                        }
+                    }
+                    if let Some((window, character_name)) = activated {
                         let display_name = if character_name.is_empty() {
                             eve::LOGGED_OUT_DISPLAY_NAME
                         } else {
@@ -531,6 +848,9 @@ async fn run_event_loop(
                             "Activating window via hotkey"
                         );
 
+                        activation::run(&resources.config, &character_name, window, Stage::Before);
+                        activation::follow_workspace(&ctx, &resources.config, window, timestamp);
+
                         // NOTE: When minimize mode is enabled, unminimize the target window FIRST
                         // before calling activate_window. This ensures the window is restored from
                         // minimized state so it can properly receive keyboard focus.
@@ -542,8 +862,40 @@ async fn run_event_loop(
 
                         if let Err(e) = activate_window(ctx.conn, ctx.screen, ctx.atoms, window, timestamp) {
                             error!(window = window, error = %e, "Failed to activate window");
+                            if crate::x11::is_connection_lost(&e) {
+                                connection_lost = true;
+                                break;
+                            }
                         } else {
-                            debug!(window = window, "activate_window completed successfully");
+                            activation::run(&resources.config, &character_name, window, Stage::After);
+                            let companions = activation::companions(&resources.config, &resources.eve_clients, &character_name);
+                            let latency = received.elapsed();
+                            info!(
+                                window = window,
+                                backend = ?hotkey_backend,
+                                latency_ms = format!("{:.1}", latency.as_secs_f64() * 1000.0),
+                                "Hotkey switch completed"
+                            );
+                            let summary = resources.session.hotkey_latency.record(*hotkey_backend, latency);
+                            let _ = status_tx.send(DaemonMessage::HotkeyLatency(summary));
+
+                            if resources.config.profile.hotkey_switch_osd {
+                                let label = display_config
+                                    .character_settings
+                                    .get(&character_name)
+                                    .and_then(|s| s.alias.as_deref())
+                                    .unwrap_or(display_name);
+                                match switch_osd.show(&ctx, &font_renderer, &format!("→ {label}"), window) {
+                                    Ok(()) => {
+                                        switch_osd_shown = true;
+                                        switch_osd_timer.as_mut().reset(
+                                            tokio::time::Instant::now()
+                                                + std::time::Duration::from_millis(switch_osd::DURATION_MS),
+                                        );
+                                    }
+                                    Err(e) => warn!(error = ?e, "Failed to show switch OSD"),
+                                }
+                            }
 
                             if resources.config.profile.client_minimize_on_switch {
                                 // NOTE: Critical delay to prevent KWin focus thrashing. Without this,
@@ -557,7 +909,7 @@ async fn run_event_loop(
                                 let other_windows: Vec<Window> = resources.eve_clients
                                     .keys()
                                     .copied()
-                                    .filter(|w| *w != window)
+                                    .filter(|w| *w != window && !companions.iter().any(|(c, _)| c == w))
                                     .collect();
                                 for other_window in other_windows {
                                     if let Err(e) = minimize_window(ctx.conn, ctx.screen, ctx.atoms, other_window) {
@@ -568,7 +920,7 @@ async fn run_event_loop(
                                 // Minimize Manager GUI as well (to prevent focus stealing/clutter)
                                 // We search for "eve-preview-manager" class.
                                 // NOTE: Thumbnails are now "eve-preview-thumbnail", so this is safe/unique.
-                                let manager_window = crate::x11::get_client_list(ctx.conn, ctx.atoms)
+                                let manager_window = crate::x11::get_client_list(ctx.conn, ctx.screen.root, ctx.atoms)
                                     .ok()
                                     .and_then(|windows| {
                                         windows.into_iter().find(|&w| {
@@ -588,6 +940,8 @@ async fn run_event_loop(
                                     }
                                 }
                             }
+
+                            activation::raise_companions(&ctx, &companions);
                         }
                     } else {
                          // Simplify logging to avoid iterating all groups for a warn message
@@ -623,6 +977,10 @@ async fn run_event_loop(
             () = &mut hide_timer, if resources.session.focus_loss_deadline.is_some() => {
                 debug!("Executing delayed thumbnail hide");
                 for thumbnail in resources.eve_clients.values_mut() {
+                    // Characters can opt out of (or into) hiding
+                    if !display_config.hides_when_unfocused(&thumbnail.character_name) {
+                        continue;
+                    }
                     if let Err(e) = thumbnail.visibility(false) {
                         error!(error = %e, character = %thumbnail.character_name, "Failed to hide thumbnail on focus timeout");
                     }
@@ -631,7 +989,37 @@ async fn run_event_loop(
                 resources.session.focus_loss_deadline = None;
             }
 
-            // 4. Send Heartbeat (Lower priority - can wait)
+            // 4. Hide the cycle group indicator
+            () = &mut group_indicator_timer, if group_indicator_shown => {
+                let ctx = AppContext {
+                    conn,
+                    screen,
+                    atoms,
+                    formats,
+                    touch,
+                };
+                if let Err(e) = group_indicator.hide(&ctx) {
+                    warn!(error = ?e, "Failed to hide cycle group indicator");
+                }
+                group_indicator_shown = false;
+            }
+
+            // Hide the hotkey switch display
+            () = &mut switch_osd_timer, if switch_osd_shown => {
+                let ctx = AppContext {
+                    conn,
+                    screen,
+                    atoms,
+                    formats,
+                    touch,
+                };
+                if let Err(e) = switch_osd.hide(&ctx) {
+                    warn!(error = ?e, "Failed to hide switch OSD");
+                }
+                switch_osd_shown = false;
+            }
+
+            // 5. Send Heartbeat (Lower priority - can wait)
             _ = heartbeat_interval.tick() => {
                 if let Err(e) = status_tx.send(DaemonMessage::Heartbeat) {
                     error!(error = %e, "Failed to send heartbeat to Manager");
@@ -640,25 +1028,173 @@ async fn run_event_loop(
                 }
             }
 
-            // 4. Handle SIGUSR1 (Lower priority)
-            _ = sigusr1.recv() => {
-                info!("SIGUSR1 received - config is now managed by Manager via IPC");
-                let _ = status_tx.send(DaemonMessage::Status("SIGUSR1 received: Syncing config...".to_string()));
+            // 6. Report focus statistics
+            _ = focus_stats_interval.tick() => {
+                report_focus_stats(&mut resources, status_tx);
+            }
+
+            // 7. Save AFK snapshots when due
+            _ = snapshot_interval.tick(), if resources.config.afk_snapshots.enabled => {
+                if snapshot_timer.due(&resources.config.afk_snapshots, std::time::Instant::now()) {
+                    let ctx = AppContext {
+                        conn,
+                        screen,
+                        atoms,
+                        formats,
+                        touch,
+                    };
+                    save_snapshots(&resources, &ctx);
+                }
+            }
+
+            // 8. Keep the systemd watchdog fed
+            _ = watchdog_interval.tick(), if watchdog.is_some() => {
+                systemd::notify(systemd::WATCHDOG);
+            }
+
+            // 9. Periodically merge positions into the config file
+            _ = autosave_interval.tick(), if autosave_secs > 0 => {
+                match flush_positions(&mut resources, status_tx) {
+                    Ok(true) => debug!("Periodic position save completed"),
+                    Ok(false) => {}
+                    Err(e) => {
+                        error!(error = ?e, "Periodic position save failed");
+                        let _ = status_tx.send(DaemonMessage::Error(DaemonError::new(
+                            DaemonErrorKind::Save,
+                            format!("Failed to save thumbnail positions: {e:#}"),
+                        )));
+                    }
+                }
+            }
+
+            // 10. Follow the power source while low-power mode depends on it
+            _ = power_interval.tick(), if resources.config.low_power.mode == LowPowerMode::OnBattery => {
+                battery.request();
+            }
+            Some(reading) = battery.reading() => {
+                on_battery = reading;
+                let active = resources.config.low_power.is_active(on_battery);
+                set_low_power(&mut resources, active, &display_config, &font_renderer);
+            }
+
+            // 11. Draw damage batched for the frame tick
+            _ = frame_interval.tick(), if resources.session.low_power || resources.config.vsync_redraws || software_sources => {
+                draw_pending_damage(&mut resources, &display_config, &font_renderer);
+            }
+
+            // 12. Periodically rescan for missed clients
+            _ = rescan_interval.tick() => {
+                // Opacity was set up for the compositor state at startup
+                let missing = compositor_missing(conn, screen.root);
+                if missing != resources.config.compositor_missing && resources.config.uses_translucency() {
+                    info!(running = !missing, "Compositing manager started or stopped, rebuilding thumbnails");
+                    display_switched = true;
+                    break;
+                }
+                // Without clients here, follow EVE to another screen of the display
+                if resources.eve_clients.is_empty() {
+                    let current = conn.setup().roots.iter().position(|s| s.root == screen.root).unwrap_or_default();
+                    let picked = pick_screen(conn, atoms, &resources.config, current);
+                    if picked != current {
+                        info!(screen = picked, "EVE clients found on another screen, switching");
+                        display_switched = true;
+                        break;
+                    }
+                }
+                let ctx = AppContext {
+                    conn,
+                    screen,
+                    atoms,
+                    formats,
+                    touch,
+                };
+                let found = rescan_windows(&ctx, &mut resources, status_tx, &font_renderer, &display_config);
+                if found > 0 {
+                    info!(found = found, "Periodic rescan picked up missed windows");
+                }
             }
 
-            // 5. Handle IPC Config Updates (Lower priority - expensive operation)
-            Some(msg) = ipc_config_rx_tokio.recv() => {
+            // 13-14. Handle SIGTERM/SIGINT by leaving the loop and shutting down cleanly;
+            // SIGUSR1 only reports back
+            signal = signals.recv() => match signal {
+                DaemonSignal::Terminate => {
+                    info!("SIGTERM received - shutting down");
+                    break;
+                }
+                DaemonSignal::Interrupt => {
+                    info!("SIGINT received - shutting down");
+                    break;
+                }
+                DaemonSignal::User1 => {
+                    info!("SIGUSR1 received - config is now managed by Manager via IPC");
+                    let _ = status_tx.send(DaemonMessage::Status("SIGUSR1 received: Syncing config...".to_string()));
+                }
+            },
+
+            // 15. Handle IPC Config Updates (Lower priority - expensive operation)
+            Some(event) = manager_rx.recv() => {
+                let msg = match event {
+                    ManagerEvent::StatusSink { sink, attached } => {
+                        debug!(attached, "Manager status channel replaced");
+                        *status_tx = sink;
+                        resources.session.manager_attached = attached;
+                        // A newly attached Manager has not seen our unsaved-positions state yet
+                        let _ = status_tx.send(DaemonMessage::PositionsDirty(reported_dirty));
+                        let _ = status_tx.send(DaemonMessage::SkippedCharacters(resources.cycle.skipped()));
+                        announce_clients(&resources, status_tx);
+                        continue;
+                    }
+                    ManagerEvent::Config(msg) => msg,
+                    ManagerEvent::ManagerGone => {
+                        error!("IPC Config channel closed - Manager process likely terminated. Shutting down.");
+                        // Nobody is left to save positions for us
+                        resources.session.manager_attached = false;
+                        break;
+                    }
+                };
                 match msg {
                     ConfigMessage::Full(new_config) => {
                         let new_config = *new_config; // Unbox
                         info!("Received full config update via IPC");
 
-                        // Update DaemonConfig
+                        let detection_changed = new_config.eve_detection != resources.config.eve_detection;
+                        let display_changed = new_config.profile.display() != resources.config.profile.display();
+                        // A window's visual is fixed at creation
+                        let visual_changed = new_config.profile.thumbnail_argb_visual
+                            != resources.config.profile.thumbnail_argb_visual;
+                        let managed_changed = new_config.profile.thumbnail_managed_windows
+                            != resources.config.profile.thumbnail_managed_windows;
+                        let wm_states_changed = new_config.profile.thumbnail_wm_states
+                            != resources.config.profile.thumbnail_wm_states;
+                        let anchor_changed = new_config.profile.thumbnail_position_anchor
+                            != resources.config.profile.thumbnail_position_anchor;
+
+                        // Update DaemonConfig, keeping what was detected on this display
+                        let display_scales = std::mem::take(&mut resources.config.display_scales);
+                        let compositor_missing = resources.config.compositor_missing;
                         resources.config = new_config;
+                        resources.config.display_scales = display_scales;
+                        resources.config.compositor_missing = compositor_missing;
+                        resources.config.apply_monitor_percent();
+
+                        // Positions now reflect what the Manager considers saved
+                        resources.session.positions_dirty = false;
+
+                        // Thumbnails are rebuilt from scratch on the other display
+                        if display_changed {
+                            info!(display = ?resources.config.profile.display(), "X display changed");
+                            display_switched = true;
+                            break;
+                        }
+                        if visual_changed {
+                            info!(argb = resources.config.profile.thumbnail_argb_visual, "Thumbnail visual changed, rebuilding thumbnails");
+                            display_switched = true;
+                            break;
+                        }
 
                         // Only rebuild font renderer if font settings actually changed
                         let font_name = &resources.config.profile.thumbnail_text_font;
-                        let font_size = resources.config.profile.thumbnail_text_size as f32;
+                        let font_size = resources.config.text_size();
 
                         if !font_renderer.matches_config(font_name, font_size) {
                             debug!("Font settings changed, rebuilding renderer");
@@ -684,11 +1220,62 @@ async fn run_event_loop(
                         // Update CycleState (hotkeys)
                         // NOTE: Do NOT recreate CycleState here! It would wipe out active_windows tracking.
                         // CycleState is only created once at startup and maintains window state across config reloads.
+                        resources.cycle.set_groups(&resources.config.profile.cycle_groups);
+
+                        let new_autosave_secs = resources.config.profile.thumbnail_autosave_interval_secs;
+                        if new_autosave_secs != autosave_secs {
+                            debug!(interval_secs = new_autosave_secs, "Periodic position save interval changed");
+                            autosave_secs = new_autosave_secs;
+                            autosave_interval = autosave_timer(autosave_secs);
+                        }
+
+                        let new_rescan_period = resources.config.timing.rescan_interval();
+                        if new_rescan_period != rescan_period {
+                            debug!(interval_secs = new_rescan_period.as_secs(), "Window rescan interval changed");
+                            rescan_period = new_rescan_period;
+                            rescan_interval = rescan_timer(rescan_period);
+                        }
 
                         // Force redraw of all thumbnails with new settings
                         display_config = resources.config.build_display_config();
-                        for thumbnail in resources.eve_clients.values_mut() {
-                             let _ = thumbnail.update(&display_config, &font_renderer);
+                        if managed_changed {
+                            for thumbnail in resources.eve_clients.values() {
+                                if let Err(e) = thumbnail.set_managed(display_config.managed_windows) {
+                                    warn!(character = %thumbnail.character_name, error = %e, "Failed to switch thumbnail window management");
+                                }
+                            }
+                        }
+                        if wm_states_changed {
+                            for thumbnail in resources.eve_clients.values() {
+                                if let Err(e) = thumbnail.set_wm_states(display_config.wm_states) {
+                                    warn!(character = %thumbnail.character_name, error = %e, "Failed to update thumbnail window states");
+                                }
+                            }
+                        }
+                        if anchor_changed {
+                            reanchor_positions(&mut resources, conn, screen.root, status_tx);
+                        }
+                        redraw_overlays(&mut resources, &display_config, &font_renderer);
+                        apply_active_group(&mut resources, &display_config, &font_renderer);
+
+                        if resources.config.low_power.mode == LowPowerMode::OnBattery {
+                            // The last poll may predate switching to this mode
+                            power_interval.reset_immediately();
+                        }
+                        let active = resources.config.low_power.is_active(on_battery);
+                        set_low_power(&mut resources, active, &display_config, &font_renderer);
+
+                        // Clients matching the new patterns are picked up right away
+                        if detection_changed {
+                            let ctx = AppContext {
+                                conn,
+                                screen,
+                                atoms,
+                                formats,
+                                touch,
+                            };
+                            let found = rescan_windows(&ctx, &mut resources, status_tx, &font_renderer, &display_config);
+                            info!(found = found, "EVE detection patterns changed, rescanned windows");
                         }
 
                         info!("Full config updated");
@@ -744,6 +1331,35 @@ async fn run_event_loop(
                             if let Err(e) = thumb.resize(width, height) {
                                 error!(name = %name, error = %e, "Failed to resize thumbnail");
                             }
+
+                            // Keep runtime positions in step so later saves don't revert the move
+                            let thumbnails = if is_custom {
+                                &mut resources.config.custom_source_thumbnails
+                            } else {
+                                &mut resources.config.character_thumbnails
+                            };
+                            if let Some(settings) = thumbnails.get_mut(&name) {
+                                settings.x = x;
+                                settings.y = y;
+                                settings.dimensions = crate::common::types::Dimensions::new(width, height);
+                                // The Manager can't tell where the client is; report the corner
+                                // the new position is measured against if it moved
+                                if settings.client_origin != thumb.client_origin {
+                                    settings.client_origin = thumb.client_origin;
+                                    let _ = status_tx.send(DaemonMessage::PositionChanged {
+                                        name: name.clone(),
+                                        x,
+                                        y,
+                                        width,
+                                        height,
+                                        is_custom,
+                                        client_origin: thumb.client_origin,
+                                    });
+                                }
+                            }
+                            resources
+                                .session
+                                .mark_positions_dirty(resources.config.profile.thumbnail_auto_save_position);
                             info!(
                                 name = %name,
                                 x = x,
@@ -756,73 +1372,731 @@ async fn run_event_loop(
                             debug!(name = %name, is_custom = is_custom, "ThumbnailMove ignored: character not tracked");
                         }
                     }
+
+                    ConfigMessage::UndoMove => {
+                        undo_last_move(&mut resources, status_tx);
+                    }
+
+                    ConfigMessage::Rescan => {
+                        let ctx = AppContext {
+                            conn,
+                            screen,
+                            atoms,
+                            formats,
+                            touch,
+                        };
+                        let found = rescan_windows(&ctx, &mut resources, status_tx, &font_renderer, &display_config);
+                        info!(found = found, "Rescan requested by Manager");
+                        let _ = status_tx.send(DaemonMessage::Status(format!(
+                            "Window rescan complete: {} new window(s) found",
+                            found
+                        )));
+                    }
+
+                    ConfigMessage::SetCharacterHidden { name, hidden } => {
+                        if !resources.session.set_character_hidden(&name, hidden) {
+                            continue;
+                        }
+                        info!(name = %name, hidden = hidden, "Thumbnail visibility set by Manager");
+                        let visible = !resources.config.runtime_hidden
+                            && !resources
+                                .session
+                                .is_thumbnail_hidden(&name, &resources.config.profile.cycle_groups);
+                        for thumbnail in resources
+                            .eve_clients
+                            .values_mut()
+                            .filter(|t| t.character_name == name)
+                        {
+                            if let Err(e) = thumbnail.visibility(visible) {
+                                warn!(character = %name, error = %e, "Failed to update thumbnail visibility");
+                            } else if visible {
+                                let _ = thumbnail.update(&display_config, &font_renderer);
+                            }
+                        }
+                    }
+
+                    ConfigMessage::SetCharacterSkipped { name, skipped } => {
+                        if resources.cycle.set_skipped(&name, skipped) {
+                            info!(character = %name, skipped = skipped, "Skip status set by Manager");
+                            skip_changed(&mut resources, &name, &font_renderer, status_tx);
+                        }
+                    }
+
+                    ConfigMessage::CycleOrder(orders) => {
+                        info!(groups = orders.len(), "Received cycle group order via IPC");
+                        for (name, cycle_list) in orders {
+                            if let Some(group) = resources
+                                .config
+                                .profile
+                                .cycle_groups
+                                .iter_mut()
+                                .find(|group| group.name == name)
+                            {
+                                group.cycle_list = cycle_list;
+                            }
+                        }
+                        resources.cycle.set_groups(&resources.config.profile.cycle_groups);
+                        apply_active_group(&mut resources, &display_config, &font_renderer);
+                    }
                 }
             }
         }
     }
+
+    if connection_lost || display_switched {
+        let DaemonResources {
+            config,
+            session,
+            cycle,
+            eve_clients,
+            ..
+        } = resources;
+        // After a lost connection their windows are already gone; dropping them only logs
+        // failed requests
+        drop(eve_clients);
+        let _ = conn.flush();
+        let state = Box::new(CarriedState {
+            config,
+            session,
+            cycle,
+        });
+        return Ok(if connection_lost {
+            LoopExit::ConnectionLost(state)
+        } else {
+            LoopExit::DisplayChanged(state)
+        });
+    }
+
+    shutdown(conn, &mut resources, status_tx);
+    Ok(LoopExit::Shutdown)
+}
+
+/// Run a full window rescan, returning the number of newly detected windows
+fn rescan_windows<'a>(
+    ctx: &AppContext<'a>,
+    resources: &mut DaemonResources<'a>,
+    status_tx: &IpcSender<DaemonMessage>,
+    font_renderer: &font::FontRenderer,
+    display_config: &crate::config::DisplayConfig,
+) -> usize {
+    let mut context = EventContext {
+        app_ctx: ctx,
+        daemon_config: &mut resources.config,
+        eve_clients: &mut resources.eve_clients,
+        session_state: &mut resources.session,
+        connection: &mut resources.connection,
+        cycle_state: &mut resources.cycle,
+        status_tx,
+        font_renderer,
+        display_config,
+    };
+    let found = super::handlers::window::rescan_windows(&mut context)
+        .inspect_err(|err| error!(error = ?err, "Window rescan failed"))
+        .unwrap_or(0);
+    let _ = ctx.conn.flush();
+    found
+}
+
+/// Report every tracked client to the Manager, e.g. after it (re)connects
+fn announce_clients(resources: &DaemonResources<'_>, status_tx: &IpcSender<DaemonMessage>) {
+    for thumbnail in resources.eve_clients.values() {
+        let name = &thumbnail.character_name;
+        if name.is_empty() {
+            continue;
+        }
+        let _ = status_tx.send(DaemonMessage::CharacterDetected {
+            name: name.clone(),
+            is_custom: resources.config.custom_source_thumbnails.contains_key(name),
+        });
+    }
 }
 
-pub async fn run_daemon(ipc_server_name: String) -> Result<()> {
-    // 1. Initialize X11 connection and resources
-    let (conn, _screen_num, atoms, formats) =
-        initialize_x11().context("Failed to initialize X11")?;
-
-    // Re-acquire screen reference from connection (x11rb::connect returns screen index)
-    let screen = &conn.setup().roots[_screen_num];
-
-    // 2. Setup IPC and get initial config
-    debug!("Connecting to IPC server: {}", ipc_server_name);
-    let bootstrap_sender: IpcSender<BootstrapMessage> =
-        IpcSender::connect(ipc_server_name).context("Failed to connect to IPC server")?;
-
-    let (config_tx, config_rx) =
-        ipc::channel::<ConfigMessage>().context("Failed to create config IPC channel")?;
-    let (status_tx, status_rx) =
-        ipc::channel::<DaemonMessage>().context("Failed to create status IPC channel")?;
-
-    // Send the channels to the Manager
-    bootstrap_sender
-        .send((config_tx, status_rx))
-        .context("Failed to send bootstrap message")?;
-
-    debug!("Waiting for initial configuration...");
-    let initial_config = match config_rx.recv() {
-        Ok(ConfigMessage::Full(config)) => *config,
-        Ok(ConfigMessage::ThumbnailMove { .. }) => {
-            return Err(anyhow::anyhow!(
-                "Expected Full config on startup, got ThumbnailMove"
-            ));
+/// Redraw the borders of `name`'s thumbnails to show or hide the skip indicator, and report
+/// the skipped characters to the Manager
+fn skip_changed(
+    resources: &mut DaemonResources<'_>,
+    name: &str,
+    font_renderer: &crate::daemon::font::FontRenderer,
+    status_tx: &IpcSender<DaemonMessage>,
+) {
+    let skipped = resources.cycle.is_skipped(name);
+    let display_config = resources.config.build_display_config();
+    for thumbnail in resources
+        .eve_clients
+        .values_mut()
+        .filter(|t| t.character_name == name)
+    {
+        let focused = thumbnail.state.is_focused();
+        if let Err(e) = thumbnail.border(&display_config, focused, skipped, font_renderer) {
+            warn!(character = %name, error = %e, "Failed to update border after toggle skip");
+        }
+    }
+    let _ = status_tx.send(DaemonMessage::SkippedCharacters(resources.cycle.skipped()));
+}
+
+/// Start or stop following the clients of the thumbnails on screen after the position anchor
+/// changed. Their positions are saved where they are, with the client's corner when anchored
+/// to it; other saved positions stay screen coordinates and read the same in either mode.
+fn reanchor_positions(
+    resources: &mut DaemonResources<'_>,
+    conn: &RustConnection,
+    root: Window,
+    status_tx: &IpcSender<DaemonMessage>,
+) {
+    let anchor = resources.config.profile.thumbnail_position_anchor;
+    for (&src, thumbnail) in resources.eve_clients.iter_mut() {
+        thumbnail.client_origin = match anchor {
+            PositionAnchor::Screen => None,
+            PositionAnchor::Client => crate::x11::get_root_position(conn, src, root),
+        };
+        if thumbnail.character_name.is_empty() {
+            continue;
+        }
+
+        let pos = thumbnail.current_position;
+        let is_custom = resources
+            .config
+            .profile
+            .custom_windows
+            .iter()
+            .any(|rule| rule.owns_label(&thumbnail.character_name));
+        let map = if is_custom {
+            &mut resources.config.custom_source_thumbnails
+        } else {
+            &mut resources.config.character_thumbnails
+        };
+        if let Some(settings) = map.get_mut(&thumbnail.character_name) {
+            settings.x = pos.x;
+            settings.y = pos.y;
+            settings.client_origin = thumbnail.client_origin;
+        }
+        let _ = status_tx.send(DaemonMessage::PositionChanged {
+            name: thumbnail.character_name.clone(),
+            x: pos.x,
+            y: pos.y,
+            width: thumbnail.dimensions.width,
+            height: thumbnail.dimensions.height,
+            is_custom,
+            client_origin: thumbnail.client_origin,
+        });
+    }
+    resources
+        .session
+        .mark_positions_dirty(resources.config.profile.thumbnail_auto_save_position);
+    info!(anchor = ?anchor, "Thumbnail position anchor changed");
+}
+
+/// Move the most recently dragged thumbnail back to where it was.
+///
+/// Entries for thumbnails that no longer exist (client closed, character swapped) are skipped.
+fn undo_last_move(resources: &mut DaemonResources<'_>, status_tx: &IpcSender<DaemonMessage>) {
+    while let Some(record) = resources.session.pop_move() {
+        let Some(thumbnail) = resources
+            .eve_clients
+            .get_mut(&record.window)
+            .filter(|t| t.character_name == record.character_name)
+        else {
+            debug!(character = %record.character_name, "Skipping undo entry for missing thumbnail");
+            continue;
+        };
+
+        let (x, y) = (record.previous.x, record.previous.y);
+        if let Err(e) = thumbnail.reposition(x, y) {
+            error!(character = %record.character_name, error = %e, "Failed to undo thumbnail move");
+            return;
+        }
+        resources
+            .session
+            .update_window_position(thumbnail.window(), x, y);
+
+        let map = if record.is_custom {
+            &mut resources.config.custom_source_thumbnails
+        } else {
+            &mut resources.config.character_thumbnails
+        };
+        if let Some(settings) = map.get_mut(&record.character_name) {
+            settings.x = x;
+            settings.y = y;
+            settings.client_origin = thumbnail.client_origin;
+        }
+
+        resources
+            .session
+            .mark_positions_dirty(resources.config.profile.thumbnail_auto_save_position);
+
+        info!(character = %record.character_name, x, y, "Undid thumbnail move");
+        let _ = status_tx.send(DaemonMessage::PositionChanged {
+            name: record.character_name,
+            x,
+            y,
+            width: thumbnail.dimensions.width,
+            height: thumbnail.dimensions.height,
+            is_custom: record.is_custom,
+            client_origin: thumbnail.client_origin,
+        });
+        return;
+    }
+
+    debug!("Nothing to undo");
+}
+
+/// Save in-memory positions (through the Manager, if attached) and clear the
+/// unsaved-positions flag
+fn flush_positions(
+    resources: &mut DaemonResources<'_>,
+    status_tx: &IpcSender<DaemonMessage>,
+) -> Result<bool> {
+    let saved = persistence::save_positions(
+        &resources.config,
+        resources.session.manager_attached,
+        status_tx,
+    )?;
+    resources.session.positions_dirty = false;
+    Ok(saved)
+}
+
+/// Interval for periodic position saves; the first tick fires one full period from now
+fn autosave_timer(secs: u32) -> tokio::time::Interval {
+    // A disabled timer is never polled, but still needs a valid non-zero period
+    let period = std::time::Duration::from_secs(u64::from(secs.max(1)));
+    let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    interval
+}
+
+/// No compositing manager on the X display. Wayland compositors composite XWayland windows
+/// themselves.
+fn compositor_missing(conn: &RustConnection, root: Window) -> bool {
+    std::env::var_os("WAYLAND_DISPLAY").is_none() && !crate::x11::compositor_running(conn, root)
+}
+
+/// Interval for full window rescans; the first tick fires one full period from now
+fn rescan_timer(period: std::time::Duration) -> tokio::time::Interval {
+    let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    interval
+}
+
+/// Time between frame ticks: the display refresh, or the low-power cap while that is active
+fn frame_period(
+    resources: &DaemonResources<'_>,
+    refresh_period: std::time::Duration,
+) -> std::time::Duration {
+    if resources.session.low_power {
+        resources.config.low_power.frame_interval()
+    } else {
+        refresh_period
+    }
+}
+
+/// Interval drawing batched damage
+fn frame_timer(period: std::time::Duration) -> tokio::time::Interval {
+    let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    interval
+}
+
+/// Enter or leave low-power mode, switching every thumbnail's scaling filter
+fn set_low_power(
+    resources: &mut DaemonResources<'_>,
+    active: bool,
+    display_config: &crate::config::DisplayConfig,
+    font_renderer: &crate::daemon::font::FontRenderer,
+) {
+    if resources.session.low_power == active {
+        return;
+    }
+    resources.session.low_power = active;
+    info!(active = active, "Low-power mode changed");
+
+    for thumbnail in resources.eve_clients.values_mut() {
+        if let Err(e) = thumbnail.set_smoothing(!active) {
+            error!(error = ?e, character = %thumbnail.character_name, "Failed to switch scaling filter");
+        }
+        // Redraw with the new filter, which also catches up on deferred damage
+        thumbnail.damage_pending = false;
+        if let Err(e) = thumbnail.update(display_config, font_renderer) {
+            error!(error = ?e, character = %thumbnail.character_name, "Failed to redraw thumbnail");
+        }
+    }
+}
+
+/// Draw thumbnails with batched damage, skipping covered ones. With low-power `pause_unhovered`
+/// only the hovered one is drawn; the others catch up when the pointer reaches them or
+/// low-power mode ends.
+fn draw_pending_damage(
+    resources: &mut DaemonResources<'_>,
+    display_config: &crate::config::DisplayConfig,
+    font_renderer: &crate::daemon::font::FontRenderer,
+) {
+    let pause_unhovered = resources.session.low_power && resources.config.low_power.pause_unhovered;
+    let hovered = resources.session.hovered_thumbnail;
+    let now = std::time::Instant::now();
+    for (src, thumbnail) in resources.eve_clients.iter_mut() {
+        if !thumbnail.damage_pending
+            || thumbnail.occluded
+            || thumbnail.throttled(now)
+            || (pause_unhovered && hovered != Some(*src))
+        {
+            continue;
+        }
+        thumbnail.damage_pending = false;
+        if let Err(e) = thumbnail.update(display_config, font_renderer) {
+            error!(error = ?e, character = %thumbnail.character_name, "Failed to draw deferred damage");
+        }
+    }
+}
+
+/// Unix signals handled by the event loop
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum DaemonSignal {
+    Terminate,
+    Interrupt,
+    User1,
+}
+
+/// Where the event loop receives signals from: the process's own handlers, or a channel
+/// a test drives
+pub(crate) struct ShutdownSignals {
+    rx: mpsc::UnboundedReceiver<DaemonSignal>,
+}
+
+impl ShutdownSignals {
+    /// Register process-wide handlers; requires a running Tokio runtime
+    fn register() -> Result<Self> {
+        use tokio::signal::unix::{SignalKind, signal};
+
+        let mut sigterm =
+            signal(SignalKind::terminate()).context("Failed to register SIGTERM handler")?;
+        let mut sigint =
+            signal(SignalKind::interrupt()).context("Failed to register SIGINT handler")?;
+        let mut sigusr1 =
+            signal(SignalKind::user_defined1()).context("Failed to register SIGUSR1 handler")?;
+
+        let (tx, signals) = Self::channel();
+        tokio::spawn(async move {
+            loop {
+                let signal = tokio::select! {
+                    _ = sigterm.recv() => DaemonSignal::Terminate,
+                    _ = sigint.recv() => DaemonSignal::Interrupt,
+                    _ = sigusr1.recv() => DaemonSignal::User1,
+                };
+                if tx.send(signal).is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(signals)
+    }
+
+    /// Signals delivered through the returned sender instead of the process's handlers
+    pub(crate) fn channel() -> (mpsc::UnboundedSender<DaemonSignal>, Self) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        (tx, Self { rx })
+    }
+
+    /// Wait for the next signal; never resolves once every sender is gone
+    async fn recv(&mut self) -> DaemonSignal {
+        match self.rx.recv().await {
+            Some(signal) => signal,
+            None => std::future::pending().await,
+        }
+    }
+}
+
+/// Flush positions (if enabled) and release X11 resources before exiting
+fn shutdown(
+    conn: &RustConnection,
+    resources: &mut DaemonResources<'_>,
+    status_tx: &IpcSender<DaemonMessage>,
+) {
+    systemd::notify(systemd::STOPPING);
+
+    if resources.config.profile.thumbnail_save_on_exit
+        && let Err(e) = flush_positions(resources, status_tx)
+    {
+        error!(error = ?e, "Failed to save thumbnail positions on exit");
+    }
+
+    if resources.config.focus_stats_csv {
+        let stats = &mut resources.session.focus_stats;
+        let entries = stats.snapshot(std::time::Instant::now());
+        if !entries.is_empty()
+            && let Err(e) = stats.write_csv(&entries)
+        {
+            error!(error = ?e, "Failed to write focus statistics on exit");
+        }
+    }
+
+    // Dropping thumbnails destroys their windows, damage handles and pictures
+    let count = resources.eve_clients.len();
+    resources.eve_clients.clear();
+    if let Err(e) = conn.flush() {
+        warn!(error = %e, "Failed to flush X11 connection during shutdown");
+    }
+    info!(thumbnails = count, "Daemon shut down cleanly");
+}
+
+/// How the daemon was launched and where its configuration comes from
+pub enum DaemonMode {
+    /// Spawned by a Manager, which supplies configuration over its bootstrap server
+    Managed { ipc_server: String },
+    /// Headless (`--daemon-only`): configuration is read from disk and Managers may attach later
+    Standalone {
+        /// Profile to run instead of the one selected in the config (`--profile`)
+        profile: Option<String>,
+    },
+}
+
+pub async fn run_daemon(mode: DaemonMode) -> Result<()> {
+    // Registering requires the async runtime context
+    let signals = ShutdownSignals::register()?;
+    debug!("Registered SIGTERM/SIGINT/SIGUSR1 handlers");
+    run_daemon_with_signals(mode, signals).await
+}
+
+/// Run the daemon, taking SIGTERM/SIGINT/SIGUSR1 from `signals`
+pub(crate) async fn run_daemon_with_signals(
+    mode: DaemonMode,
+    mut signals: ShutdownSignals,
+) -> Result<()> {
+    // 1. Setup IPC and get initial config
+    let standalone = matches!(mode, DaemonMode::Standalone { .. });
+    let (manager_tx, mut manager_rx) = mpsc::channel(1);
+    let (initial_config, mut status_tx) = match mode {
+        DaemonMode::Managed { ipc_server } => {
+            let (config_rx, status_tx) = manager_link::connect_to_manager(ipc_server)?;
+
+            debug!("Waiting for initial configuration...");
+            let initial_config = match config_rx.recv() {
+                Ok(ConfigMessage::Full(config)) => *config,
+                Ok(other) => {
+                    return Err(anyhow::anyhow!(
+                        "Expected Full config on startup, got {:?}",
+                        other
+                    ));
+                }
+                Err(e) => return Err(anyhow::anyhow!("Failed to receive initial config: {}", e)),
+            };
+            debug!("Received initial configuration");
+
+            // IpcReceiver is blocking, so bridge it into the async event loop from a thread
+            manager_link::spawn_managed_bridge(config_rx, manager_tx);
+            (initial_config, status_tx)
+        }
+        DaemonMode::Standalone { profile } => {
+            let mut config = crate::config::profile::Config::load()
+                .context("Failed to load configuration for standalone daemon")?;
+            if let Some(name) = profile {
+                config.select_profile(&name)?;
+            }
+            info!(
+                profile = %config.global.selected_profile,
+                config = ?crate::config::profile::Config::path(),
+                "Running standalone daemon"
+            );
+
+            manager_link::spawn_attach_listener(manager_tx);
+            (
+                DaemonConfig::from_config(&config),
+                manager_link::detached_status_sink()?,
+            )
         }
-        Err(e) => return Err(anyhow::anyhow!("Failed to receive initial config: {}", e)),
     };
-    debug!("Received initial configuration");
 
     // 3. Initialize State from Config
-    let (mut daemon_config, config, mut session_state, mut cycle_state) =
-        initialize_state(screen, initial_config).context("Failed to initialize state")?;
+    let (daemon_config, _, session_state, cycle_state) =
+        initialize_state(initial_config).context("Failed to initialize state")?;
 
-    // 3. Setup Signal Handlers
-    // We do this here as it requires async runtime context
-    let sigusr1 = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::user_defined1())
-        .context("Failed to register SIGUSR1 handler")?;
-
-    debug!("Registered SIGUSR1 handler for manual position save");
+    // 2. Initialize X11 connection and resources on the profile's display
+    let mut x11 = initialize_x11(&daemon_config).context("Failed to initialize X11")?;
 
     // 4. Setup Hotkeys
     let allowed_windows = Arc::new(RwLock::new(HashSet::new()));
-    let hotkeys = setup_hotkeys(&daemon_config, allowed_windows.clone());
+    let mut hotkeys = setup_hotkeys(&daemon_config, allowed_windows.clone());
+
+    let watchdog = systemd::watchdog_interval();
+    if let Some(interval) = watchdog {
+        info!(
+            interval_ms = interval.as_millis(),
+            "systemd watchdog enabled"
+        );
+    }
+
+    // 5-8. Run against the X server, reconnecting whenever the connection dies or the
+    // profile moves to another display. Config, session and cycle state survive; thumbnails
+    // are rebuilt.
+    let mut state = CarriedState {
+        config: daemon_config,
+        session: SessionState {
+            // A spawning Manager stays for the daemon's lifetime
+            manager_attached: !standalone,
+            ..session_state
+        },
+        cycle: cycle_state,
+    };
+    let mut first_connection = true;
+    let mut same_display = true;
+    loop {
+        let (conn, screen_num, atoms, formats, touch) = x11;
+        let screen = &conn.setup().roots[screen_num];
+
+        if !first_connection {
+            // Window IDs of another display mean nothing on this one
+            let lost = if same_display {
+                forget_windows(&mut state, |window| !window_exists(&conn, window))
+            } else {
+                forget_windows(&mut state, |_| true)
+            };
+            for name in lost {
+                let _ = status_tx.send(DaemonMessage::CharacterLost { name });
+            }
+            // The X11 hotkey backend's listener stops with its connection, and grabs keys on
+            // the display it was started for
+            let display_moved =
+                !same_display && hotkeys.backend == crate::config::HotkeyBackendType::X11;
+            if display_moved || hotkeys.listeners_stopped() {
+                info!("Restarting hotkey listener");
+                hotkeys.stop();
+                hotkeys = setup_hotkeys(&state.config, allowed_windows.clone());
+            }
+        }
+
+        let exit = run_connection(
+            &conn,
+            screen,
+            &atoms,
+            &formats,
+            touch,
+            state,
+            &mut hotkeys,
+            &mut signals,
+            &mut manager_rx,
+            &mut status_tx,
+            &allowed_windows,
+            watchdog,
+            first_connection,
+        )
+        .await?;
+        first_connection = false;
+
+        let (carried, connection_lost) = match exit {
+            LoopExit::Shutdown => break,
+            LoopExit::ConnectionLost(carried) => (carried, true),
+            LoopExit::DisplayChanged(carried) => (carried, false),
+        };
+        state = *carried;
+        same_display = connection_lost;
+        drop(conn);
+
+        if connection_lost {
+            let _ = status_tx.send(DaemonMessage::Status(
+                "Lost the X server connection, reconnecting...".to_string(),
+            ));
+        }
+        match reconnect_x11(
+            &state.config,
+            connection_lost,
+            &mut signals,
+            &status_tx,
+            watchdog,
+        )
+        .await?
+        {
+            Some(connection) => {
+                if connection_lost {
+                    info!("Reconnected to X11 server");
+                    let _ = status_tx.send(DaemonMessage::Status(
+                        "Reconnected to the X server".to_string(),
+                    ));
+                }
+                x11 = connection;
+            }
+            None => {
+                // Shutdown requested while disconnected; there are no windows to clean up
+                systemd::notify(systemd::STOPPING);
+                if state.config.profile.thumbnail_save_on_exit
+                    && let Err(e) = persistence::save_positions(
+                        &state.config,
+                        state.session.manager_attached,
+                        &status_tx,
+                    )
+                {
+                    error!(error = ?e, "Failed to save thumbnail positions on exit");
+                }
+                break;
+            }
+        }
+    }
+
+    if standalone {
+        manager_link::remove_attach_endpoint();
+    }
+    Ok(())
+}
+
+/// Build thumbnails for one X server connection and run the event loop until it exits
+#[allow(clippy::too_many_arguments)]
+async fn run_connection(
+    conn: &RustConnection,
+    screen: &Screen,
+    atoms: &CachedAtoms,
+    formats: &crate::x11::CachedFormats,
+    touch: bool,
+    state: CarriedState,
+    hotkeys: &mut HotkeyResources,
+    signals: &mut ShutdownSignals,
+    manager_rx: &mut mpsc::Receiver<ManagerEvent>,
+    status_tx: &mut IpcSender<DaemonMessage>,
+    allowed_windows: &AllowedWindows,
+    watchdog: Option<std::time::Duration>,
+    notify_ready: bool,
+) -> Result<LoopExit> {
+    let CarriedState {
+        config: mut daemon_config,
+        session: mut session_state,
+        cycle: mut cycle_state,
+    } = state;
+    daemon_config.display_scales = crate::x11::get_display_scales(conn, screen.root);
+    daemon_config.apply_monitor_percent();
+    let _ = status_tx.send(DaemonMessage::Monitors(
+        daemon_config.display_scales.monitors.clone(),
+    ));
+    info!(
+        monitors = daemon_config.display_scales.monitors.len(),
+        primary_scale = daemon_config.display_scales.primary,
+        "Display scale detected"
+    );
+    daemon_config.compositor_missing = compositor_missing(conn, screen.root);
+    if daemon_config.compositor_missing {
+        warn!(
+            "No compositing manager running: thumbnails are drawn opaque, and covered parts of clients may show up black or stale"
+        );
+        if daemon_config.uses_translucency() {
+            let _ = status_tx.send(DaemonMessage::Error(DaemonError::new(
+                DaemonErrorKind::Compositor,
+                "Thumbnail opacity is off because no compositing manager is running. \
+                 Covered parts of clients may also show up black or stale.",
+            )));
+        }
+    }
+    let config = daemon_config.build_display_config();
 
     // 5. Initialize Font Renderer
     // This depends on config so it runs after config load
-    let font_renderer = font::FontRenderer::resolve_from_config(
-        &conn,
+    let font_renderer = match font::FontRenderer::resolve_from_config(
+        conn,
         &daemon_config.profile.thumbnail_text_font,
-        daemon_config.profile.thumbnail_text_size as f32,
+        daemon_config.text_size(),
     )
-    .context("Failed to initialize font renderer")?;
+    .context("Failed to initialize font renderer")
+    {
+        Ok(font_renderer) => font_renderer,
+        Err(e) => return setup_failed(e, daemon_config, session_state, cycle_state),
+    };
 
     info!(
-        size = daemon_config.profile.thumbnail_text_size,
+        size = daemon_config.text_size(),
         font = %daemon_config.profile.thumbnail_text_font,
         "Font renderer initialized"
     );
@@ -832,20 +2106,25 @@ pub async fn run_daemon(ipc_server_name: String) -> Result<()> {
     let mut eve_clients;
     {
         let ctx = AppContext {
-            conn: &conn,
+            conn,
             screen,
-            atoms: &atoms,
-            formats: &formats,
+            atoms,
+            formats,
+            touch,
         };
 
-        eve_clients = super::window_detection::scan_eve_windows(
+        eve_clients = match super::window_detection::scan_eve_windows(
             &ctx,
             &config,
             &font_renderer,
             &mut daemon_config,
             &mut session_state,
         )
-        .context("Failed to get initial list of EVE windows")?;
+        .context("Failed to get initial list of EVE windows")
+        {
+            Ok(eve_clients) => eve_clients,
+            Err(e) => return setup_failed(e, daemon_config, session_state, cycle_state),
+        };
     }
 
     // Register initial windows with cycle state
@@ -861,9 +2140,10 @@ pub async fn run_daemon(ipc_server_name: String) -> Result<()> {
 
     // Initialize border state for all windows (defaults to inactive/cleared)
     // This ensures inactive borders are drawn immediately on startup if enabled
-    let active_eve_window = crate::x11::get_active_eve_window(&conn, screen, &atoms)
-        .ok()
-        .flatten();
+    let active_eve_window =
+        crate::x11::get_active_eve_window(conn, screen, atoms, &daemon_config.eve_detection)
+            .ok()
+            .flatten();
 
     for (window, thumbnail) in eve_clients.iter_mut() {
         // Check if this window currently has focus
@@ -894,35 +2174,342 @@ pub async fn run_daemon(ipc_server_name: String) -> Result<()> {
         config: daemon_config,
         session: session_state,
         cycle: cycle_state,
+        connection: ConnectionState::default(),
         eve_clients,
     };
 
+    if notify_ready {
+        systemd::notify(systemd::READY);
+    }
+
     run_event_loop(
-        &conn,
+        conn,
         screen,
-        config.clone(),
-        &atoms,
-        &formats,
+        config,
+        atoms,
+        formats,
+        touch,
         font_renderer,
         resources,
-        hotkeys.rx,
-        hotkeys.groups,
-        sigusr1,
-        config_rx,
+        hotkeys,
+        signals,
+        manager_rx,
         status_tx,
         allowed_windows,
+        watchdog,
     )
     .await
 }
 
-fn handle_cycle_command(
-    command: &CycleCommand,
+/// A setup step of `run_connection` failed: reconnect if the X server went away, give up
+/// otherwise
+fn setup_failed(
+    error: anyhow::Error,
+    config: DaemonConfig,
+    session: SessionState,
+    cycle: CycleState,
+) -> Result<LoopExit> {
+    if !crate::x11::is_connection_lost(&error) {
+        return Err(error);
+    }
+    error!(error = ?error, "X11 connection lost while setting up");
+    Ok(LoopExit::ConnectionLost(Box::new(CarriedState {
+        config,
+        session,
+        cycle,
+    })))
+}
+
+/// Retry connecting to the profile's X display, first waiting a moment if the connection
+/// was lost. Returns None if a shutdown signal arrives first.
+async fn reconnect_x11(
+    daemon_config: &DaemonConfig,
+    wait_first: bool,
+    signals: &mut ShutdownSignals,
+    status_tx: &IpcSender<DaemonMessage>,
+    watchdog: Option<std::time::Duration>,
+) -> Result<
+    Option<(
+        RustConnection,
+        usize,
+        CachedAtoms,
+        crate::x11::CachedFormats,
+        bool,
+    )>,
+> {
+    let delay = std::time::Duration::from_secs(x11_consts::RECONNECT_DELAY_SECS);
+    for attempt in 1..=x11_consts::RECONNECT_ATTEMPTS {
+        if attempt > 1 || wait_first {
+            let sleep = tokio::time::sleep(delay);
+            tokio::pin!(sleep);
+            loop {
+                tokio::select! {
+                    _ = &mut sleep => break,
+                    signal = signals.recv() => match signal {
+                        DaemonSignal::Terminate | DaemonSignal::Interrupt => {
+                            info!(?signal, "Signal received while reconnecting - shutting down");
+                            return Ok(None);
+                        }
+                        DaemonSignal::User1 => {}
+                    },
+                }
+            }
+        }
+
+        // Stay alive for the Manager's and systemd's health checks while waiting
+        let _ = status_tx.send(DaemonMessage::Heartbeat);
+        if watchdog.is_some() {
+            systemd::notify(systemd::WATCHDOG);
+        }
+
+        match initialize_x11(daemon_config) {
+            Ok(connection) => return Ok(Some(connection)),
+            Err(e) => debug!(attempt = attempt, error = ?e, "Reconnect attempt failed"),
+        }
+    }
+    Err(anyhow::anyhow!(
+        "Could not connect to the X11 server after {} attempts",
+        x11_consts::RECONNECT_ATTEMPTS
+    ))
+}
+
+/// Whether `window` still exists on the server
+fn window_exists(conn: &RustConnection, window: Window) -> bool {
+    conn.get_window_attributes(window)
+        .ok()
+        .and_then(|cookie| cookie.reply().ok())
+        .is_some()
+}
+
+/// Drop session and cycle entries for the `stale` windows left over from a previous
+/// connection. Returns the characters that were forgotten.
+fn forget_windows(state: &mut CarriedState, stale: impl Fn(Window) -> bool) -> Vec<String> {
+    let known: HashSet<Window> = state
+        .session
+        .window_last_character
+        .keys()
+        .chain(state.session.window_positions.keys())
+        .copied()
+        .chain(state.cycle.windows())
+        .collect();
+
+    let stale: HashSet<Window> = known.into_iter().filter(|&window| stale(window)).collect();
+
+    let mut lost = Vec::new();
+    for &window in &stale {
+        if let Some(name) = state.session.window_last_character.get(&window) {
+            lost.push(name.clone());
+        }
+        state.session.remove_window(window);
+        state.cycle.remove_window(window);
+    }
+    state
+        .session
+        .move_history
+        .retain(|record| !stale.contains(&record.window));
+
+    debug!(
+        lost = lost.len(),
+        "Forgot windows of the previous X11 connection"
+    );
+    lost
+}
+
+/// Mark the thumbnail the next forward cycle press lands on, redrawing the ones that changed
+/// if the highlight is enabled
+fn refresh_next_target(
     resources: &mut DaemonResources<'_>,
+    display_config: &crate::config::DisplayConfig,
+    font_renderer: &crate::daemon::font::FontRenderer,
+) {
+    let logged_out_map = resources
+        .config
+        .profile
+        .hotkey_logged_out_cycle
+        .then_some(&resources.session.window_last_character);
+    let target = resources.cycle.next_target(logged_out_map);
+
+    for thumbnail in resources.eve_clients.values_mut() {
+        let next_target = target == Some(thumbnail.character_name.as_str());
+        if thumbnail.next_target == next_target {
+            continue;
+        }
+        thumbnail.next_target = next_target;
+
+        if display_config.next_target_color.is_some()
+            && let crate::common::types::ThumbnailState::Normal { focused } = thumbnail.state
+        {
+            let skipped = resources.cycle.is_skipped(&thumbnail.character_name);
+            if let Err(e) = thumbnail.border(display_config, focused, skipped, font_renderer) {
+                warn!(character = %thumbnail.character_name, error = %e, "Failed to redraw next cycle target");
+            }
+        }
+    }
+}
+
+/// Redraw the overlays (text, badges, cycle order, hotkeys) of all thumbnails after the
+/// display configuration changed
+fn redraw_overlays(
+    resources: &mut DaemonResources<'_>,
+    display_config: &crate::config::DisplayConfig,
+    font_renderer: &crate::daemon::font::FontRenderer,
+) {
+    for thumbnail in resources.eve_clients.values_mut() {
+        if let crate::common::types::ThumbnailState::Normal { focused } = thumbnail.state {
+            let skipped = resources.cycle.is_skipped(&thumbnail.character_name);
+            if let Err(e) = thumbnail.border(display_config, focused, skipped, font_renderer) {
+                warn!(character = %thumbnail.character_name, error = %e, "Failed to redraw overlay");
+            }
+        }
+        let _ = thumbnail.update(display_config, font_renderer);
+    }
+}
+
+/// Show only the characters tied to the active cycle group (and those tied to none),
+/// updating visibility if the set of hidden characters changed
+fn apply_active_group(
+    resources: &mut DaemonResources<'_>,
+    display_config: &crate::config::DisplayConfig,
+    font_renderer: &crate::daemon::font::FontRenderer,
+) {
+    if !resources.session.set_active_group(
+        resources.cycle.active_group(),
+        &resources.config.character_thumbnails,
+    ) {
+        return;
+    }
+    debug!(
+        group = resources.cycle.active_group().unwrap_or_default(),
+        hidden = resources.session.outside_active_group.len(),
+        "Active cycle group changed thumbnail visibility"
+    );
+
+    let groups = &resources.config.profile.cycle_groups;
+    let eve_focused = resources
+        .eve_clients
+        .values()
+        .any(|thumbnail| thumbnail.state.is_focused());
+    for thumbnail in resources.eve_clients.values_mut() {
+        let name = &thumbnail.character_name;
+        let visible = !resources.config.runtime_hidden
+            && !resources.session.is_thumbnail_hidden(name, groups)
+            && (eve_focused || !display_config.hides_when_unfocused(name));
+        if thumbnail.is_visible() == visible {
+            continue;
+        }
+        if let Err(e) = thumbnail.visibility(visible) {
+            warn!(character = %thumbnail.character_name, error = %e, "Failed to update visibility for active cycle group");
+        } else if visible {
+            let _ = thumbnail.update(display_config, font_renderer);
+        }
+    }
+}
+
+/// Attribute focus time to the character whose client has focus (custom sources excluded)
+fn record_focus(resources: &mut DaemonResources<'_>) {
+    let focused = resources.eve_clients.values().find_map(|thumbnail| {
+        let name = thumbnail.character_name.as_str();
+        (matches!(
+            thumbnail.state,
+            crate::common::types::ThumbnailState::Normal { focused: true }
+        ) && !resources.config.custom_source_thumbnails.contains_key(name))
+        .then_some(name)
+    });
+    resources
+        .session
+        .focus_stats
+        .focus(focused, std::time::Instant::now());
+}
+
+/// Save an AFK snapshot of every client at its thumbnail's size. Clients are read here and
+/// written in the background.
+fn save_snapshots(resources: &DaemonResources<'_>, ctx: &AppContext<'_>) {
+    let mut pending = Vec::new();
+    for (&window, thumbnail) in resources.eve_clients.iter() {
+        match snapshots::grab(ctx, window, &thumbnail.character_name, thumbnail.dimensions) {
+            Ok(snapshot) => pending.push(snapshot),
+            Err(e) => {
+                debug!(character = %thumbnail.character_name, error = ?e, "Skipped AFK snapshot")
+            }
+        }
+    }
+    snapshots::spawn_save(pending, resources.config.afk_snapshots.retention_count());
+}
+
+/// Send the session's focus statistics to the Manager and rewrite the CSV file if enabled
+fn report_focus_stats(resources: &mut DaemonResources<'_>, status_tx: &IpcSender<DaemonMessage>) {
+    let stats = &mut resources.session.focus_stats;
+    let entries = stats.snapshot(std::time::Instant::now());
+    if entries.is_empty() {
+        return;
+    }
+
+    if resources.config.focus_stats_csv
+        && let Err(e) = stats.write_csv(&entries)
+    {
+        warn!(error = ?e, "Failed to write focus statistics");
+    }
+    let _ = status_tx.send(DaemonMessage::FocusStats(entries));
+}
+
+/// Clients on the monitor the profile restricts cycling to, `None` to cycle through all
+///
+/// Falls back to all clients when the monitor can't be determined (no RandR monitors, no
+/// focused window).
+fn current_monitor_windows(
+    resources: &DaemonResources<'_>,
     ctx: &AppContext<'_>,
+) -> Option<HashSet<Window>> {
+    let anchor = match resources.config.profile.hotkey_cycle_monitor {
+        CycleMonitor::All => return None,
+        CycleMonitor::Pointer => ctx
+            .conn
+            .query_pointer(ctx.screen.root)
+            .ok()
+            .and_then(|cookie| cookie.reply().ok())
+            .map(|pointer| Position::new(pointer.root_x, pointer.root_y)),
+        CycleMonitor::Focused => crate::x11::get_active_window(ctx.conn, ctx.screen, ctx.atoms)
+            .ok()
+            .flatten()
+            .and_then(|window| crate::x11::get_window_center(ctx.conn, window, ctx.screen.root)),
+    };
+    let Some(monitor) = anchor.and_then(|pos| resources.config.display_scales.monitor_at(pos))
+    else {
+        debug!("Current monitor unknown, cycling through all clients");
+        return None;
+    };
+    Some(
+        resources
+            .eve_clients
+            .keys()
+            .copied()
+            .filter(|&window| {
+                crate::x11::get_window_center(ctx.conn, window, ctx.screen.root)
+                    .is_some_and(|center| monitor.contains(center))
+            })
+            .collect(),
+    )
+}
+
+fn handle_cycle_command<'a>(
+    command: &CycleCommand,
+    resources: &mut DaemonResources<'a>,
+    ctx: &AppContext<'a>,
     font_renderer: &crate::daemon::font::FontRenderer,
     status_tx: &IpcSender<DaemonMessage>,
     hotkey_groups: &HashMap<crate::config::HotkeyBinding, Vec<String>>,
 ) -> Option<(Window, String)> {
+    if matches!(
+        command,
+        CycleCommand::Forward(_)
+            | CycleCommand::Backward(_)
+            | CycleCommand::ActiveForward
+            | CycleCommand::ActiveBackward
+    ) {
+        let windows = current_monitor_windows(resources, ctx);
+        resources.cycle.restrict_to(windows);
+    }
+
     // Build logged-out map if feature is enabled in profile
     let logged_out_map = if resources.config.profile.hotkey_logged_out_cycle {
         Some(&resources.session.window_last_character)
@@ -947,6 +2534,66 @@ fn handle_cycle_command(
                 resources.config.profile.hotkey_cycle_reset_index,
             )
             .map(|(w, s)| (w, s.to_string())),
+        CycleCommand::ActiveForward | CycleCommand::ActiveBackward => {
+            let Some(group) = resources.cycle.selected_group().map(str::to_string) else {
+                warn!("Cannot cycle the active group: profile has no cycle groups");
+                return None;
+            };
+            let reset = resources.config.profile.hotkey_cycle_reset_index;
+            if matches!(command, CycleCommand::ActiveForward) {
+                resources.cycle.cycle_forward(&group, logged_out_map, reset)
+            } else {
+                resources
+                    .cycle
+                    .cycle_backward(&group, logged_out_map, reset)
+            }
+            .map(|(w, s)| (w, s.to_string()))
+        }
+        CycleCommand::NextGroup => {
+            let reset = resources.config.profile.hotkey_cycle_reset_index;
+            match resources.cycle.switch_group(reset) {
+                Some(group) => info!(group = %group, "Switched active cycle group"),
+                None => warn!("Cannot switch cycle group: profile has no cycle groups"),
+            }
+            None
+        }
+        CycleCommand::CharacterHotkey(binding) if resources.session.swap_source.is_some() => {
+            // A waiting swap takes the character as its partner instead of switching to it
+            let source = resources.session.swap_source?;
+            let names = hotkey_groups
+                .get(binding)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            let Some(partner) = resources
+                .eve_clients
+                .iter()
+                .find(|(window, t)| **window != source && names.contains(&t.character_name))
+                .map(|(window, _)| *window)
+            else {
+                warn!(
+                    binding = %binding.display_name(),
+                    "Cannot swap positions: no logged-in character has this hotkey"
+                );
+                return None;
+            };
+            resources.session.swap_source = None;
+            let display_config = resources.config.build_display_config();
+            let mut context = EventContext {
+                app_ctx: ctx,
+                daemon_config: &mut resources.config,
+                eve_clients: &mut resources.eve_clients,
+                session_state: &mut resources.session,
+                connection: &mut resources.connection,
+                cycle_state: &mut resources.cycle,
+                status_tx,
+                font_renderer,
+                display_config: &display_config,
+            };
+            if let Err(e) = super::handlers::input::swap_positions(&mut context, source, partner) {
+                error!(error = ?e, "Failed to swap thumbnail positions");
+            }
+            None
+        }
         CycleCommand::CharacterHotkey(binding) => {
             debug!(
                 binding = %binding.display_name(),
@@ -988,30 +2635,22 @@ fn handle_cycle_command(
             None
         }
         CycleCommand::ToggleSkip => {
-            // Identify focused window to determine which character to skip
-            let active_window = crate::x11::get_active_eve_window(ctx.conn, ctx.screen, ctx.atoms)
+            // Identify focused window to determine which character or custom source to skip
+            let active_window = crate::x11::get_active_window(ctx.conn, ctx.screen, ctx.atoms)
                 .ok()
                 .flatten();
 
             if let Some(window) = active_window {
-                if let Some(thumbnail) = resources.eve_clients.get_mut(&window) {
+                if let Some(thumbnail) = resources.eve_clients.get(&window) {
                     let char_name = thumbnail.character_name.clone();
                     let is_skipped = resources.cycle.toggle_skip(&char_name);
                     info!(character = %char_name, skipped = is_skipped, "Toggled skip status");
-
-                    // Force redraw of border to show/hide indicator
-                    let focused = thumbnail.state.is_focused();
-                    let display_config = resources.config.build_display_config();
-                    if let Err(e) =
-                        thumbnail.border(&display_config, focused, is_skipped, font_renderer)
-                    {
-                        warn!(character = %char_name, error = %e, "Failed to update border after toggle skip");
-                    }
+                    skip_changed(resources, &char_name, font_renderer, status_tx);
                 } else {
-                    warn!("Focused EVE window not found in client list");
+                    warn!("Cannot toggle skip: Focused window is not a tracked client");
                 }
             } else {
-                warn!("Cannot toggle skip: No EVE window focused");
+                warn!("Cannot toggle skip: No window focused");
             }
             None
         }
@@ -1022,13 +2661,19 @@ fn handle_cycle_command(
                 "Toggled previews visibility"
             );
 
-            // Force visibility update for all known thumbnails
+            // Force visibility update for all known thumbnails, keeping ones hidden from the
+            // tray or by group hotkey
             for thumbnail in resources.eve_clients.values_mut() {
-                if let Err(e) = thumbnail.visibility(!resources.config.runtime_hidden) {
+                let visible = !resources.config.runtime_hidden
+                    && !resources.session.is_thumbnail_hidden(
+                        &thumbnail.character_name,
+                        &resources.config.profile.cycle_groups,
+                    );
+                if let Err(e) = thumbnail.visibility(visible) {
                     warn!(character = %thumbnail.character_name, error = %e, "Failed to update visibility after toggle");
                 } else {
                     // Force update to ensure content is drawn if revealed
-                    if !resources.config.runtime_hidden {
+                    if visible {
                         let display_config = resources.config.build_display_config();
                         let _ = thumbnail.update(&display_config, font_renderer);
                     }
@@ -1036,5 +2681,97 @@ fn handle_cycle_command(
             }
             None
         }
+        CycleCommand::ToggleGroupVisibility(group) => {
+            let hidden = resources.session.toggle_group_hidden(group);
+            info!(group = %group, hidden = hidden, "Toggled cycle group visibility");
+
+            let groups = &resources.config.profile.cycle_groups;
+            let Some(members) = groups.iter().find(|g| &g.name == group) else {
+                warn!(group = %group, "Cannot toggle visibility: cycle group not found");
+                return None;
+            };
+            let display_config = resources.config.build_display_config();
+            for thumbnail in resources
+                .eve_clients
+                .values_mut()
+                .filter(|t| members.contains(&t.character_name))
+            {
+                // Members can still be hidden by another group, the tray or Toggle Previews
+                let visible = !resources.config.runtime_hidden
+                    && !resources
+                        .session
+                        .is_thumbnail_hidden(&thumbnail.character_name, groups);
+                if let Err(e) = thumbnail.visibility(visible) {
+                    warn!(character = %thumbnail.character_name, error = %e, "Failed to update visibility after group toggle");
+                } else if visible {
+                    let _ = thumbnail.update(&display_config, font_renderer);
+                }
+            }
+            None
+        }
+        CycleCommand::UndoMove => {
+            undo_last_move(resources, status_tx);
+            None
+        }
+        CycleCommand::ToggleHotkeyOverlay => {
+            resources.config.runtime_hotkey_overlay = !resources.config.runtime_hotkey_overlay;
+            info!(
+                shown = resources.config.runtime_hotkey_overlay,
+                "Toggled hotkey overlay"
+            );
+            // Redrawn by the caller with the rebuilt display config
+            None
+        }
+        CycleCommand::SwapPositions => {
+            let active_window = crate::x11::get_active_window(ctx.conn, ctx.screen, ctx.atoms)
+                .ok()
+                .flatten()
+                .filter(|window| resources.eve_clients.contains_key(window));
+            match active_window {
+                Some(window) => {
+                    let waiting = resources.session.toggle_swap(window);
+                    info!(waiting = waiting, "Toggled thumbnail position swap");
+                }
+                None if resources.session.swap_source.take().is_some() => {
+                    info!("Cancelled thumbnail position swap");
+                }
+                None => warn!("Cannot swap positions: Focused window is not a tracked client"),
+            }
+            None
+        }
+        CycleCommand::Screenshot => {
+            let active_window = crate::x11::get_active_window(ctx.conn, ctx.screen, ctx.atoms)
+                .ok()
+                .flatten();
+            match active_window.and_then(|window| resources.eve_clients.get(&window)) {
+                Some(thumbnail) => super::screenshot::save_and_report(
+                    ctx,
+                    thumbnail.src(),
+                    &thumbnail.character_name,
+                    &resources.config.screenshot_dir,
+                    status_tx,
+                ),
+                None => warn!("Cannot take screenshot: Focused window is not a tracked client"),
+            }
+            None
+        }
+        CycleCommand::SavePositions => {
+            match flush_positions(resources, status_tx) {
+                Ok(_) => {
+                    info!("Saved thumbnail positions via hotkey");
+                    let _ = status_tx.send(DaemonMessage::Status(
+                        "Thumbnail positions saved".to_string(),
+                    ));
+                }
+                Err(e) => {
+                    error!(error = ?e, "Failed to save thumbnail positions via hotkey");
+                    let _ = status_tx.send(DaemonMessage::Error(DaemonError::new(
+                        DaemonErrorKind::Save,
+                        format!("Failed to save thumbnail positions: {e:#}"),
+                    )));
+                }
+            }
+            None
+        }
     }
 }
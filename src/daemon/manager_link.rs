@@ -0,0 +1,159 @@
+//! IPC link between the daemon and the Manager
+//!
+//! A Manager-spawned daemon connects to the Manager's bootstrap server once and exits
+//! when the Manager goes away. A standalone (`--daemon-only`) daemon instead publishes an
+//! attach endpoint and accepts Managers as they come and go.
+
+use anyhow::{Context, Result};
+use ipc_channel::ipc::{self, IpcOneShotServer, IpcReceiver, IpcSender};
+use tokio::sync::mpsc;
+use tracing::{debug, error, info, trace};
+
+use crate::common::ipc::{
+    AttachMessage, BootstrapMessage, ConfigMessage, DaemonMessage, attach_endpoint_path,
+};
+
+/// Events forwarded from the IPC threads into the daemon event loop
+pub enum ManagerEvent {
+    /// Configuration message received from the Manager
+    Config(ConfigMessage),
+    /// Replace the channel used to send status messages (Manager attached or detached)
+    StatusSink {
+        sink: IpcSender<DaemonMessage>,
+        /// A Manager reads the channel, rather than a sink discarding everything
+        attached: bool,
+    },
+    /// The spawning Manager closed its config channel
+    ManagerGone,
+}
+
+/// Connect to a Manager's bootstrap server and hand over the IPC channels.
+pub fn connect_to_manager(
+    ipc_server_name: String,
+) -> Result<(IpcReceiver<ConfigMessage>, IpcSender<DaemonMessage>)> {
+    debug!("Connecting to IPC server: {}", ipc_server_name);
+    let bootstrap_sender: IpcSender<BootstrapMessage> =
+        IpcSender::connect(ipc_server_name).context("Failed to connect to IPC server")?;
+
+    let (config_tx, config_rx) =
+        ipc::channel::<ConfigMessage>().context("Failed to create config IPC channel")?;
+    let (status_tx, status_rx) =
+        ipc::channel::<DaemonMessage>().context("Failed to create status IPC channel")?;
+
+    // Send the channels to the Manager
+    bootstrap_sender
+        .send((config_tx, status_rx))
+        .context("Failed to send bootstrap message")?;
+
+    Ok((config_rx, status_tx))
+}
+
+/// Forward config messages from the Manager that spawned us.
+///
+/// The daemon's lifetime is tied to that Manager, so the process exits once the channel closes.
+pub fn spawn_managed_bridge(config_rx: IpcReceiver<ConfigMessage>, tx: mpsc::Sender<ManagerEvent>) {
+    std::thread::spawn(move || {
+        while let Ok(msg) = config_rx.recv() {
+            if tx.blocking_send(ManagerEvent::Config(msg)).is_err() {
+                break; // Event loop gone
+            }
+        }
+        // config_rx fails once the Manager side closed; the event loop shuts down then
+        let _ = tx.blocking_send(ManagerEvent::ManagerGone);
+    });
+}
+
+/// Accept Managers on the attach endpoint for as long as the daemon runs.
+///
+/// Each accepted Manager is bootstrapped like a spawning Manager would be. When it
+/// disconnects, status messages are discarded and the endpoint is published again.
+pub fn spawn_attach_listener(tx: mpsc::Sender<ManagerEvent>) {
+    std::thread::spawn(move || {
+        loop {
+            if let Err(e) = serve_one_manager(&tx) {
+                error!(error = ?e, "Manager attach failed");
+                // Avoid spinning if the endpoint cannot be published
+                std::thread::sleep(std::time::Duration::from_secs(1));
+            }
+            if tx.is_closed() {
+                break;
+            }
+
+            let Ok(sink) = detached_status_sink() else {
+                break;
+            };
+            if tx
+                .blocking_send(ManagerEvent::StatusSink {
+                    sink,
+                    attached: false,
+                })
+                .is_err()
+            {
+                break;
+            }
+        }
+        remove_attach_endpoint();
+    });
+}
+
+/// Create a status channel that discards everything sent to it while no Manager is attached.
+pub fn detached_status_sink() -> Result<IpcSender<DaemonMessage>> {
+    let (status_tx, status_rx) =
+        ipc::channel::<DaemonMessage>().context("Failed to create status IPC channel")?;
+
+    std::thread::spawn(move || {
+        while let Ok(msg) = status_rx.recv() {
+            trace!(?msg, "Discarding status message (no Manager attached)");
+        }
+    });
+
+    Ok(status_tx)
+}
+
+/// Remove the attach endpoint file, e.g. on shutdown.
+pub fn remove_attach_endpoint() {
+    let _ = std::fs::remove_file(attach_endpoint_path());
+}
+
+fn serve_one_manager(tx: &mpsc::Sender<ManagerEvent>) -> Result<()> {
+    let (server, server_name) =
+        IpcOneShotServer::<AttachMessage>::new().context("Failed to create attach IPC server")?;
+
+    let endpoint = attach_endpoint_path();
+    if let Some(parent) = endpoint.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create runtime directory {:?}", parent))?;
+    }
+    std::fs::write(&endpoint, &server_name)
+        .with_context(|| format!("Failed to write attach endpoint {:?}", endpoint))?;
+    debug!(path = ?endpoint, "Published attach endpoint");
+
+    let (_, manager_server_name) = server
+        .accept()
+        .context("Failed to accept Manager attach request")?;
+
+    // The one-shot server is consumed; hide it until the next one is ready
+    remove_attach_endpoint();
+
+    let (config_rx, status_tx) = connect_to_manager(manager_server_name)?;
+    info!("Manager attached");
+
+    if tx
+        .blocking_send(ManagerEvent::StatusSink {
+            sink: status_tx,
+            attached: true,
+        })
+        .is_err()
+    {
+        return Ok(());
+    }
+
+    while let Ok(msg) = config_rx.recv() {
+        if tx.blocking_send(ManagerEvent::Config(msg)).is_err() {
+            return Ok(());
+        }
+    }
+
+    info!("Manager detached - continuing without GUI");
+    Ok(())
+}
@@ -1,18 +1,44 @@
 //! Daemon main loop and runtime initialization
 
+mod activation;
+mod badges;
+mod bench;
+mod clipboard;
+mod connection_state;
 mod cycle_state;
 mod dispatcher;
+mod focus_stats;
 pub mod font;
+mod group_indicator;
+#[cfg(test)]
+mod headless_tests;
+mod label;
+mod latency;
 mod main_loop;
+mod manager_link;
 
 pub mod handlers;
 mod overlay;
+mod persistence;
+mod power;
+mod raw_motion;
 mod renderer;
+mod screenshot;
+mod selection;
 mod session_state;
+mod snap_guides;
 mod snapping;
+mod snapshots;
+mod software;
+mod switch_osd;
+mod systemd;
 mod thumbnail;
+mod tooltip;
+mod touch;
 pub mod window_detection;
 
 pub use crate::input::listener::list_input_devices;
+pub use bench::{BenchOptions, parse_size, run_bench};
 pub use font::{list_fonts, select_best_default_font};
-pub use main_loop::run_daemon;
+pub use main_loop::{DaemonMode, run_daemon};
+pub use screenshot::capture_screen;
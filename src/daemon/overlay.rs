@@ -1,18 +1,24 @@
 //! Overlay management for thumbnails (text and borders)
 
 use anyhow::{Context, Result};
-use tracing::error;
+use std::cell::RefCell;
+use tracing::{error, warn};
 use x11rb::connection::Connection;
 use x11rb::protocol::render::{ConnectionExt as RenderExt, CreatePictureAux, PictOp, Picture};
 use x11rb::protocol::xproto::{
-    Char2b, ConnectionExt as XprotoExt, CreateGCAux, Gcontext, ImageFormat, Pixmap,
+    ChangeGCAux, Char2b, ConnectionExt as XprotoExt, CreateGCAux, Gcontext, ImageFormat, Pixmap,
+    Rectangle, Segment,
 };
 use x11rb::rust_connection::RustConnection;
 
-use crate::common::constants::x11;
-use crate::common::types::Dimensions;
+use crate::common::color::HexColor;
+use crate::common::constants::{
+    badge, group_accent, hotkey_overlay, selection, skipped as skipped_indicator, x11,
+};
+use crate::common::types::{Badge, Dimensions, Position, SkippedStyle, TextAnchor, TextOffset};
 use crate::config::DisplayConfig;
 
+use super::badges;
 use super::font::FontRenderer;
 
 #[derive(Debug)]
@@ -31,7 +37,12 @@ pub struct OverlayRenderer<'a> {
     overlay_gc: Gcontext,           // Graphics context for text rendering
     active_border_fill: Picture,    // Solid color fill for active border
     inactive_border_fill: Picture,  // Solid color fill for inactive border
-    skipped_indicator_gc: Gcontext, // GC for drawing skipped indicator (Red X)
+    skipped_indicator_gc: Gcontext, // GC for drawing skipped indicator lines
+    badge_images: RefCell<badges::ImageCache>, // Decoded badge images
+    /// Launch slot of the EVE client, labelled with its account while it is logged out
+    client_slot: Option<usize>,
+    /// Character last logged in on the client, labelled while it is logged out
+    last_character: Option<String>,
 
     // === Borrowed Dependencies ===
     conn: &'a RustConnection,
@@ -39,6 +50,11 @@ pub struct OverlayRenderer<'a> {
 }
 
 impl<'a> OverlayRenderer<'a> {
+    /// The overlay has an alpha channel and can be composited over the thumbnail
+    pub fn has_alpha(&self) -> bool {
+        self.formats.argb.is_some()
+    }
+
     /// Creates a new `OverlayRenderer`.
     ///
     /// # Arguments
@@ -60,11 +76,12 @@ impl<'a> OverlayRenderer<'a> {
     ) -> Result<Self> {
         // ... (implementation of new)
         // Create overlay pixmap
+        let (depth, format) = formats.overlay();
         let overlay_pixmap = conn
             .generate_id()
             .context("Failed to generate ID for overlay pixmap")?;
         conn.create_pixmap(
-            x11::ARGB_DEPTH,
+            depth,
             overlay_pixmap,
             root,
             dimensions.width,
@@ -82,7 +99,7 @@ impl<'a> OverlayRenderer<'a> {
         conn.render_create_picture(
             overlay_picture,
             overlay_pixmap,
-            formats.argb,
+            format,
             &CreatePictureAux::new(),
         )
         .context(format!(
@@ -104,7 +121,7 @@ impl<'a> OverlayRenderer<'a> {
             character_name
         ))?;
 
-        // Create skipped indicator GC (Red)
+        // Create skipped indicator GC
         let skipped_indicator_gc = conn
             .generate_id()
             .context("Failed to generate ID for skipped indicator GC")?;
@@ -112,7 +129,7 @@ impl<'a> OverlayRenderer<'a> {
             skipped_indicator_gc,
             overlay_pixmap,
             &CreateGCAux::new()
-                .foreground(0xFFFF0000) // Opaque Red
+                .foreground(config.skipped_color)
                 .line_width(3), // Thicker lines for visibility
         )
         .context(format!(
@@ -147,6 +164,9 @@ impl<'a> OverlayRenderer<'a> {
             active_border_fill,
             inactive_border_fill,
             skipped_indicator_gc,
+            badge_images: RefCell::new(badges::ImageCache::new()),
+            client_slot: None,
+            last_character: None,
             conn,
             formats,
         };
@@ -166,16 +186,33 @@ impl<'a> OverlayRenderer<'a> {
                 character_name,
                 dimensions,
                 initial_border_size,
+                false,
                 font_renderer,
             )
             .context(format!(
                 "Failed to render initial name for '{}'",
                 character_name
             ))?;
+        renderer
+            .draw_badges(config, character_name, dimensions, false, font_renderer)
+            .context(format!(
+                "Failed to render initial badges for '{}'",
+                character_name
+            ))?;
 
         Ok(renderer)
     }
 
+    /// Set the character last logged in on the client, shown while it is logged out
+    pub fn set_last_character(&mut self, name: Option<String>) {
+        self.last_character = name;
+    }
+
+    /// Set the launch slot of the EVE client, whose account is shown while it is logged out
+    pub fn set_client_slot(&mut self, slot: Option<usize>) {
+        self.client_slot = slot;
+    }
+
     /// Resizes the overlay resources.
     ///
     /// This destroys the old pixmap/picture and creates new ones with the given dimensions.
@@ -184,16 +221,17 @@ impl<'a> OverlayRenderer<'a> {
         self.cleanup_overlay_resources();
 
         // Recreate resources with new dimensions
+        let (depth, format) = self.formats.overlay();
         let overlay_pixmap = self.conn.generate_id()?;
         self.conn
-            .create_pixmap(x11::ARGB_DEPTH, overlay_pixmap, root, width, height)?;
+            .create_pixmap(depth, overlay_pixmap, root, width, height)?;
         self.overlay_pixmap = overlay_pixmap;
 
         let overlay_picture = self.conn.generate_id()?;
         self.conn.render_create_picture(
             overlay_picture,
             overlay_pixmap,
-            self.formats.argb,
+            format,
             &CreatePictureAux::new(),
         )?;
         self.overlay_picture = overlay_picture;
@@ -203,26 +241,71 @@ impl<'a> OverlayRenderer<'a> {
 
     // ... (calculate_border_size unused here, implementation below)
 
-    /// Draws the skipped indicator (diagonal red lines)
-    pub fn draw_skipped_indicator(&self, dimensions: Dimensions) -> Result<()> {
+    /// Draws the full-size skipped indicator of `style` (cross, stripes or dim); the badge
+    /// and label prefix styles are drawn with the badges and the name.
+    pub fn draw_skipped_indicator(
+        &self,
+        style: SkippedStyle,
+        color: u32,
+        dimensions: Dimensions,
+    ) -> Result<()> {
         let w = dimensions.width as i16;
         let h = dimensions.height as i16;
 
-        let segments = [
-            x11rb::protocol::xproto::Segment {
-                x1: 0,
-                y1: 0,
-                x2: w,
-                y2: h,
-            },
-            x11rb::protocol::xproto::Segment {
-                x1: w,
-                y1: 0,
-                x2: 0,
-                y2: h,
-            },
-        ];
+        let segments: Vec<Segment> = match style {
+            SkippedStyle::Cross => vec![
+                Segment {
+                    x1: 0,
+                    y1: 0,
+                    x2: w,
+                    y2: h,
+                },
+                Segment {
+                    x1: w,
+                    y1: 0,
+                    x2: 0,
+                    y2: h,
+                },
+            ],
+            // Lines from the top edge down-left at 45 degrees, covering the whole area
+            SkippedStyle::Stripes => (0..=(i32::from(w) + i32::from(h)))
+                .step_by(skipped_indicator::STRIPE_SPACING as usize)
+                .map(|x| {
+                    let x = x.min(i32::from(i16::MAX)) as i16;
+                    Segment {
+                        x1: x,
+                        y1: 0,
+                        x2: x.saturating_sub(h),
+                        y2: h,
+                    }
+                })
+                .collect(),
+            SkippedStyle::Dim => {
+                let black = x11rb::protocol::render::Color {
+                    red: 0,
+                    green: 0,
+                    blue: 0,
+                    alpha: skipped_indicator::DIM_ALPHA,
+                };
+                self.conn
+                    .render_fill_rectangles(
+                        PictOp::OVER,
+                        self.overlay_picture,
+                        black,
+                        &[Rectangle {
+                            x: 0,
+                            y: 0,
+                            width: dimensions.width,
+                            height: dimensions.height,
+                        }],
+                    )
+                    .context("Failed to dim skipped thumbnail")?;
+                return Ok(());
+            }
+            SkippedStyle::Badge | SkippedStyle::Prefix => return Ok(()),
+        };
 
+        self.set_skipped_color(color)?;
         self.conn
             .poly_segment(self.overlay_pixmap, self.skipped_indicator_gc, &segments)
             .context("Failed to draw skipped indicator segments")?;
@@ -230,6 +313,17 @@ impl<'a> OverlayRenderer<'a> {
         Ok(())
     }
 
+    /// Sets the line color of skipped indicators, which can change with the profile
+    fn set_skipped_color(&self, color: u32) -> Result<()> {
+        self.conn
+            .change_gc(
+                self.skipped_indicator_gc,
+                &ChangeGCAux::new().foreground(color),
+            )
+            .context("Failed to set skipped indicator color")?;
+        Ok(())
+    }
+
     /// Calculates the effective border size implementation
     pub fn calculate_border_size(
         &self,
@@ -237,7 +331,7 @@ impl<'a> OverlayRenderer<'a> {
         character_name: &str,
         focused: bool,
     ) -> u16 {
-        if let Some(settings) = config.character_settings.get(character_name) {
+        if let Some(settings) = config.settings_for(character_name) {
             if focused {
                 settings
                     .override_active_border_size
@@ -279,32 +373,84 @@ impl<'a> OverlayRenderer<'a> {
     ///
     /// Handles both direct X11 text rendering (if core fonts are used) and
     /// client-side rendering (if TrueType fonts are used via `fontdue`).
+    /// With the label prefix skipped style, a `skipped` name is prefixed and drawn in the
+    /// skipped color.
     /// NOTE: This does NOT clear the background. You must call `clear_content_area` first.
     pub fn update_name(
         &self,
         config: &DisplayConfig,
         character_name: &str,
-        _dimensions: Dimensions,
+        dimensions: Dimensions,
         _border_size: u16,
+        skipped: bool,
         font_renderer: &FontRenderer,
     ) -> Result<()> {
         // Resolve settings overrides
-        let (display_name, text_color) =
-            if let Some(settings) = config.character_settings.get(character_name) {
-                let name = settings.alias.as_deref().unwrap_or(character_name);
-                let color = if let Some(hex_color) = &settings.override_text_color {
-                    crate::common::color::HexColor::parse(hex_color)
-                        .map(|c| c.argb32())
-                        .unwrap_or(config.text_color)
-                } else {
-                    config.text_color
-                };
-                (name, color)
+        let (display_name, text_color) = if let Some(settings) = config.settings_for(character_name)
+        {
+            // Display aliases belong to one label, never to sibling instances
+            let name = config
+                .character_settings
+                .get(character_name)
+                .and_then(|s| s.alias.as_deref())
+                .unwrap_or(character_name);
+            let color = if let Some(hex_color) = &settings.override_text_color {
+                crate::common::color::HexColor::parse(hex_color)
+                    .map(|c| c.argb32())
+                    .unwrap_or(config.text_color)
             } else {
-                (character_name, config.text_color)
+                config.text_color
             };
+            (std::borrow::Cow::Borrowed(name), color)
+        } else if character_name.is_empty() {
+            let label = config.logged_out_label(self.client_slot, self.last_character.as_deref());
+            (std::borrow::Cow::Owned(label), config.text_color)
+        } else {
+            (
+                std::borrow::Cow::Borrowed(character_name),
+                config.text_color,
+            )
+        };
+        let (display_name, text_color) = if skipped && config.skipped_style == SkippedStyle::Prefix
+        {
+            (
+                std::borrow::Cow::Owned(format!("{} {}", skipped_indicator::PREFIX, display_name)),
+                config.skipped_color,
+            )
+        } else {
+            (display_name, text_color)
+        };
 
         // Render text based on font renderer type
+        let position = |text: Dimensions| {
+            config
+                .text_anchor
+                .place(config.text_offset, dimensions, text)
+        };
+        self.draw_text(
+            &display_name,
+            text_color,
+            font_renderer.size(),
+            font_renderer,
+            position,
+        )
+        .context(format!(
+            "Failed to render name text for '{}'",
+            character_name
+        ))
+    }
+
+    /// Draws `text` at `size` pixels, at the top-left corner `place` picks for the text's
+    /// measured size. The X11 core font has a fixed size and draws Latin-1 only; other
+    /// characters show as '?'.
+    fn draw_text(
+        &self,
+        text: &str,
+        text_color: u32,
+        size: f32,
+        font_renderer: &FontRenderer,
+        place: impl FnOnce(Dimensions) -> Position,
+    ) -> Result<()> {
         if font_renderer.requires_direct_rendering() {
             // X11 fallback: direct rendering using ImageText8
             if let Some(font_id) = font_renderer.x11_font_id() {
@@ -323,129 +469,314 @@ impl<'a> OverlayRenderer<'a> {
                         self.overlay_pixmap,
                         &CreateGCAux::new().font(font_id).foreground(fg_pixel),
                     )
-                    .context(format!(
-                        "Failed to create GC for X11 text rendering for '{}'",
-                        character_name
-                    ))?;
+                    .context("Failed to create GC for X11 text rendering")?;
+
+                let text: Vec<u8> = text
+                    .chars()
+                    .map(|c| u8::try_from(u32::from(c)).unwrap_or(b'?'))
+                    .collect();
+
+                // Measure the text to place it
+                let extents = self
+                    .conn
+                    .query_text_extents(
+                        font_id,
+                        text.iter()
+                            .map(|&c| Char2b { byte1: 0, byte2: c })
+                            .collect::<Vec<_>>()
+                            .as_slice(),
+                    )
+                    .context("Failed to send text extents query")?
+                    .reply()
+                    .context("Failed to get text extents")?;
+                let position = place(Dimensions::new(
+                    extents.overall_width.max(0) as u16,
+                    font_renderer.size() as u16,
+                ));
 
                 // ImageText8 renders directly to drawable
                 self.conn
                     .image_text8(
                         self.overlay_pixmap,
                         gc,
-                        config.text_offset.x,
-                        config.text_offset.y + font_renderer.size() as i16, // Baseline adjustment
-                        display_name.as_bytes(),
+                        position.x,
+                        position.y + font_renderer.size() as i16, // Baseline adjustment
+                        &text,
                     )
-                    .context(format!(
-                        "Failed to render text via X11 for '{}'",
-                        character_name
-                    ))?;
+                    .context("Failed to render text via X11")?;
 
                 self.conn.free_gc(gc)?;
             }
         } else {
             // Fontdue: pre-rendered bitmap
             let rendered = font_renderer
-                .render_text(display_name, text_color)
-                .context(format!(
-                    "Failed to render text '{}' with font renderer",
-                    character_name
-                ))?;
+                .render_text(text, text_color, size)
+                .context("Failed to render text with font renderer")?;
 
             if rendered.width > 0 && rendered.height > 0 {
-                // Upload rendered text bitmap to X11
+                let size = Dimensions::new(rendered.width as u16, rendered.height as u16);
                 // rendered.data is already in BGRA format (Little Endian ARGB)
-                let text_pixmap = self
-                    .conn
-                    .generate_id()
-                    .context("Failed to generate ID for text pixmap")?;
-                self.conn
-                    .create_pixmap(
-                        x11::ARGB_DEPTH,
-                        text_pixmap,
-                        self.overlay_pixmap,
-                        rendered.width as u16,
-                        rendered.height as u16,
-                    )
-                    .context(format!(
-                        "Failed to create text pixmap for '{}'",
-                        character_name
-                    ))?;
+                self.composite_image(&rendered.data, size, place(size))?;
+            }
+        }
 
-                self.conn
-                    .put_image(
-                        ImageFormat::Z_PIXMAP,
-                        text_pixmap,
-                        self.overlay_gc,
-                        rendered.width as u16,
-                        rendered.height as u16,
-                        0,
-                        0,
-                        0,
-                        x11::ARGB_DEPTH,
-                        &rendered.data,
-                    )
-                    .context(format!(
-                        "Failed to upload text image for '{}'",
-                        character_name
-                    ))?;
+        Ok(())
+    }
 
-                // Create picture for the text pixmap
-                let text_picture = self
-                    .conn
-                    .generate_id()
-                    .context("Failed to generate ID for text picture")?;
-                self.conn
-                    .render_create_picture(
-                        text_picture,
-                        text_pixmap,
-                        self.formats.argb,
-                        &CreatePictureAux::new(),
-                    )
-                    .context(format!(
-                        "Failed to create text picture for '{}'",
-                        character_name
-                    ))?;
+    /// Uploads premultiplied ARGB32 pixels and composites them onto the overlay at `position`.
+    fn composite_image(&self, data: &[u8], size: Dimensions, position: Position) -> Result<()> {
+        // Without alpha the overlay is never shown
+        let Some(argb) = self.formats.argb else {
+            return Ok(());
+        };
+        let pixmap = self
+            .conn
+            .generate_id()
+            .context("Failed to generate ID for image pixmap")?;
+        self.conn
+            .create_pixmap(
+                x11::ARGB_DEPTH,
+                pixmap,
+                self.overlay_pixmap,
+                size.width,
+                size.height,
+            )
+            .context("Failed to create image pixmap")?;
 
-                // Composite text onto overlay
-                self.conn
-                    .render_composite(
-                        PictOp::OVER,
-                        text_picture,
-                        0u32,
-                        self.overlay_picture,
-                        0,
-                        0,
-                        0,
-                        0,
-                        config.text_offset.x,
-                        config.text_offset.y,
-                        rendered.width as u16,
-                        rendered.height as u16,
-                    )
-                    .context(format!(
-                        "Failed to composite text onto overlay for '{}'",
-                        character_name
-                    ))?;
+        self.conn
+            .put_image(
+                ImageFormat::Z_PIXMAP,
+                pixmap,
+                self.overlay_gc,
+                size.width,
+                size.height,
+                0,
+                0,
+                0,
+                x11::ARGB_DEPTH,
+                data,
+            )
+            .context("Failed to upload image")?;
 
-                // Cleanup
-                self.conn
-                    .render_free_picture(text_picture)
-                    .context("Failed to free text picture")?;
-                self.conn
-                    .free_pixmap(text_pixmap)
-                    .context("Failed to free text pixmap")?;
+        let picture = self
+            .conn
+            .generate_id()
+            .context("Failed to generate ID for image picture")?;
+        self.conn
+            .render_create_picture(picture, pixmap, argb, &CreatePictureAux::new())
+            .context("Failed to create image picture")?;
+
+        self.conn
+            .render_composite(
+                PictOp::OVER,
+                picture,
+                0u32,
+                self.overlay_picture,
+                0,
+                0,
+                0,
+                0,
+                position.x,
+                position.y,
+                size.width,
+                size.height,
+            )
+            .context("Failed to composite image onto overlay")?;
+
+        self.conn
+            .render_free_picture(picture)
+            .context("Failed to free image picture")?;
+        self.conn
+            .free_pixmap(pixmap)
+            .context("Failed to free image pixmap")?;
+        Ok(())
+    }
+
+    /// Draws the character's badges in a row at the configured corner, led by its cycle order
+    /// number if the profile shows one.
+    ///
+    /// The skipped badge only shows while `skipped` is set, and is added at the end of the row
+    /// with the corner badge skipped style. Images that fail to load are logged once and left
+    /// out.
+    pub fn draw_badges(
+        &self,
+        config: &DisplayConfig,
+        character_name: &str,
+        dimensions: Dimensions,
+        skipped: bool,
+        font_renderer: &FontRenderer,
+    ) -> Result<()> {
+        let configured = config
+            .settings_for(character_name)
+            .map(|settings| settings.badges.as_slice())
+            .unwrap_or_default();
+        let size = badges::tile_size(font_renderer.size());
+        let mut shown: Vec<&Badge> = configured
+            .iter()
+            .filter(|badge| skipped || **badge != Badge::Skipped)
+            .collect();
+        let skipped_badge = Badge::Skipped;
+        if skipped
+            && config.skipped_style == SkippedStyle::Badge
+            && !configured.contains(&skipped_badge)
+        {
+            shown.push(&skipped_badge);
+        }
+        let order = config.cycle_order.get(character_name).copied();
+        let positions = badges::layout(
+            config.badge_anchor,
+            shown.len() + usize::from(order.is_some()),
+            size,
+            dimensions,
+        );
+        let mut positions = positions.into_iter();
+
+        if let Some(order) = order
+            && let Some(position) = positions.next()
+        {
+            self.draw_tile(
+                &order.to_string(),
+                badge::CYCLE_ORDER_COLOR,
+                position,
+                size,
+                font_renderer,
+            )?;
+        }
+
+        for (entry, position) in shown.into_iter().zip(positions) {
+            match entry {
+                Badge::Role(role) => {
+                    self.draw_tile(role.letter(), role.color(), position, size, font_renderer)?;
+                }
+                Badge::Account(number) => {
+                    self.draw_tile(
+                        &number.to_string(),
+                        badge::ACCOUNT_COLOR,
+                        position,
+                        size,
+                        font_renderer,
+                    )?;
+                }
+                Badge::Skipped => {
+                    self.set_skipped_color(config.skipped_color)?;
+                    let (x, y) = (position.x, position.y);
+                    let end = size as i16 - 1;
+                    self.conn
+                        .poly_segment(
+                            self.overlay_pixmap,
+                            self.skipped_indicator_gc,
+                            &[
+                                Segment {
+                                    x1: x,
+                                    y1: y,
+                                    x2: x + end,
+                                    y2: y + end,
+                                },
+                                Segment {
+                                    x1: x + end,
+                                    y1: y,
+                                    x2: x,
+                                    y2: y + end,
+                                },
+                            ],
+                        )
+                        .context("Failed to draw skipped badge")?;
+                }
+                Badge::Image(path) => {
+                    let mut cache = self.badge_images.borrow_mut();
+                    let image = cache.entry((path.clone(), size)).or_insert_with(|| {
+                        badges::load_png(path, size)
+                            .inspect_err(|e| {
+                                warn!(character = %character_name, error = %e, "Failed to load badge image")
+                            })
+                            .ok()
+                    });
+                    if let Some(data) = image {
+                        self.composite_image(data, Dimensions::new(size, size), position)?;
+                    }
+                }
             }
         }
+        Ok(())
+    }
 
+    /// Draws the accent strip of the character's cycle group along the bottom edge, inside a
+    /// border of `border_size`
+    pub fn draw_group_accent(
+        &self,
+        config: &DisplayConfig,
+        character_name: &str,
+        dimensions: Dimensions,
+        border_size: u16,
+    ) -> Result<()> {
+        let Some(&color) = config.group_colors.get(character_name) else {
+            return Ok(());
+        };
+        let width = dimensions.width.saturating_sub(border_size * 2);
+        let height =
+            group_accent::STRIP_SIZE.min(dimensions.height.saturating_sub(border_size * 2));
+        self.conn
+            .render_fill_rectangles(
+                PictOp::SRC,
+                self.overlay_picture,
+                color,
+                &[Rectangle {
+                    x: border_size as i16,
+                    y: (dimensions.height.saturating_sub(border_size) - height) as i16,
+                    width,
+                    height,
+                }],
+            )
+            .context("Failed to draw cycle group accent")?;
         Ok(())
     }
 
+    /// Draws a badge tile of side `size` filled with `color`, with `text` centered on it
+    fn draw_tile(
+        &self,
+        text: &str,
+        color: u32,
+        position: Position,
+        size: u16,
+        font_renderer: &FontRenderer,
+    ) -> Result<()> {
+        self.conn
+            .render_fill_rectangles(
+                PictOp::SRC,
+                self.overlay_picture,
+                HexColor::from_argb32(color).to_x11_color(),
+                &[Rectangle {
+                    x: position.x,
+                    y: position.y,
+                    width: size,
+                    height: size,
+                }],
+            )
+            .context("Failed to fill badge tile")?;
+        self.draw_text(
+            text,
+            badge::TEXT_COLOR,
+            font_renderer.size(),
+            font_renderer,
+            |text| {
+                let p = TextAnchor::Center.place(
+                    TextOffset::default(),
+                    Dimensions::new(size, size),
+                    text,
+                );
+                Position::new(position.x + p.x, position.y + p.y)
+            },
+        )
+    }
+
     /// Draws the overlay content with strict Z-order:
-    /// 1. Skipped Indicator (Red X) - Bottom
-    /// 2. Text (Name) - Middle
+    /// 1. Skipped Indicator (cross, stripes or dim) - Bottom
+    /// 2. Text (Name), Badges and cycle group accent - Middle
     /// 3. Border - Top (covers everything at edges)
+    ///
+    /// An unfocused `next_target` gets the next cycle target highlight (if enabled) instead of
+    /// its inactive border.
+    #[allow(clippy::too_many_arguments)]
     pub fn draw_border(
         &self,
         config: &DisplayConfig,
@@ -453,6 +784,8 @@ impl<'a> OverlayRenderer<'a> {
         dimensions: Dimensions,
         focused: bool,
         skipped: bool,
+        next_target: bool,
+        selected: bool,
         font_renderer: &FontRenderer,
     ) -> Result<()> {
         // 1. Clear the entire overlay first (transparent background)
@@ -473,14 +806,33 @@ impl<'a> OverlayRenderer<'a> {
             )
             .context("Failed to clear overlay")?;
 
-        // 2. Draw skipped indicator (Red X)
-        // Drawn first so text appears on top of it
-        if skipped {
-            self.draw_skipped_indicator(dimensions)?;
+        // 2. Draw skipped indicator
+        // Drawn first so text appears on top of it. A skipped badge replaces the lines.
+        let skipped_badge = config
+            .settings_for(character_name)
+            .is_some_and(|s| s.badges.contains(&Badge::Skipped));
+        let lines = matches!(
+            config.skipped_style,
+            SkippedStyle::Cross | SkippedStyle::Stripes
+        );
+        if skipped && !(lines && skipped_badge) {
+            self.draw_skipped_indicator(config.skipped_style, config.skipped_color, dimensions)?;
         }
 
         // Determine effective border size and color source
-        let effective_size = self.calculate_border_size(config, character_name, focused);
+        let target_color = if selected {
+            Some(HexColor::from_argb32(selection::COLOR).to_x11_color())
+        } else {
+            config.next_target_color.filter(|_| next_target && !focused)
+        };
+        let effective_size = if target_color.is_some() {
+            match self.calculate_border_size(config, character_name, true) {
+                0 => crate::common::constants::defaults::border::SIZE,
+                size => size,
+            }
+        } else {
+            self.calculate_border_size(config, character_name, focused)
+        };
 
         // 3. Draw Text
         // We pass effective_size mainly if text positioning depended on it,
@@ -490,42 +842,57 @@ impl<'a> OverlayRenderer<'a> {
             character_name,
             dimensions,
             effective_size,
+            skipped,
             font_renderer,
         )
         .context(format!(
             "Failed to update name overlay for '{}'",
             character_name
         ))?;
+        self.draw_badges(config, character_name, dimensions, skipped, font_renderer)
+            .context(format!("Failed to draw badges for '{}'", character_name))?;
+        self.draw_group_accent(config, character_name, dimensions, effective_size)?;
+        if let Some(label) = config.hotkey_labels.get(character_name) {
+            self.draw_text(
+                label,
+                hotkey_overlay::TEXT_COLOR,
+                font_renderer.size() * hotkey_overlay::TEXT_SCALE,
+                font_renderer,
+                |text| TextAnchor::Center.place(TextOffset::default(), dimensions, text),
+            )
+            .context(format!(
+                "Failed to draw hotkey overlay for '{}'",
+                character_name
+            ))?;
+        }
 
         // 4. Draw Border (Top Layer)
         // Only if size > 0 and enabled
-        let should_draw_border = if focused {
+        let should_draw_border = if focused || target_color.is_some() {
             effective_size > 0
         } else {
             config.inactive_border_enabled && effective_size > 0
         };
 
         if should_draw_border {
-            let (fill_picture, temp_fill_id) =
-                if let Some(settings) = config.character_settings.get(character_name) {
-                    let override_color_hex = if focused {
-                        settings.override_active_border_color.as_ref()
-                    } else {
-                        settings.override_inactive_border_color.as_ref()
-                    };
-
-                    if let Some(hex) = override_color_hex {
-                        if let Some(color) =
-                            crate::common::color::HexColor::parse(hex).map(|c| c.to_x11_color())
-                        {
-                            let pid = self.conn.generate_id()?;
-                            self.conn.render_create_solid_fill(pid, color)?;
-                            (pid, Some(pid))
-                        } else if focused {
-                            (self.active_border_fill, None)
-                        } else {
-                            (self.inactive_border_fill, None)
-                        }
+            let (fill_picture, temp_fill_id) = if let Some(color) = target_color {
+                let pid = self.conn.generate_id()?;
+                self.conn.render_create_solid_fill(pid, color)?;
+                (pid, Some(pid))
+            } else if let Some(settings) = config.settings_for(character_name) {
+                let override_color_hex = if focused {
+                    settings.override_active_border_color.as_ref()
+                } else {
+                    settings.override_inactive_border_color.as_ref()
+                };
+
+                if let Some(hex) = override_color_hex {
+                    if let Some(color) =
+                        crate::common::color::HexColor::parse(hex).map(|c| c.to_x11_color())
+                    {
+                        let pid = self.conn.generate_id()?;
+                        self.conn.render_create_solid_fill(pid, color)?;
+                        (pid, Some(pid))
                     } else if focused {
                         (self.active_border_fill, None)
                     } else {
@@ -535,7 +902,12 @@ impl<'a> OverlayRenderer<'a> {
                     (self.active_border_fill, None)
                 } else {
                     (self.inactive_border_fill, None)
-                };
+                }
+            } else if focused {
+                (self.active_border_fill, None)
+            } else {
+                (self.inactive_border_fill, None)
+            };
 
             // Draw 4 strips for the border
             let w = dimensions.width as i16;
@@ -626,6 +998,8 @@ impl<'a> OverlayRenderer<'a> {
             dimensions,
             false,
             false,
+            false,
+            false,
             font_renderer,
         )
         .context(format!(
@@ -637,32 +1011,42 @@ impl<'a> OverlayRenderer<'a> {
             return Ok(());
         }
 
-        let extents = self
-            .conn
-            .query_text_extents(
-                self.overlay_gc,
-                b"MINIMIZED"
-                    .iter()
-                    .map(|&c| Char2b { byte1: 0, byte2: c })
-                    .collect::<Vec<_>>()
-                    .as_slice(),
-            )
-            .context("Failed to send text extents query for MINIMIZED text")?
-            .reply()
-            .context("Failed to get text extents for MINIMIZED text")?;
-        self.conn
-            .image_text8(
-                self.overlay_pixmap,
-                self.overlay_gc,
-                (dimensions.width as i16 - extents.overall_width as i16) / 2,
-                (dimensions.height as i16 + extents.font_ascent + extents.font_descent) / 2,
-                b"MINIMIZED",
-            )
-            .context(format!(
-                "Failed to render MINIMIZED text for '{}'",
-                character_name
-            ))?;
-        Ok(())
+        let centered =
+            |item: Dimensions| TextAnchor::Center.place(TextOffset::default(), dimensions, item);
+
+        if let Some(path) = &config.minimized_icon {
+            let size = (dimensions.width.min(dimensions.height) / 3).max(1);
+            let mut cache = self.badge_images.borrow_mut();
+            let icon = cache.entry((path.clone(), size)).or_insert_with(|| {
+                badges::load_png(path, size)
+                    .inspect_err(|e| {
+                        warn!(character = %character_name, error = %e, "Failed to load minimized overlay icon")
+                    })
+                    .ok()
+            });
+            // An icon that fails to load falls back to the text
+            if let Some(data) = icon {
+                let size = Dimensions::new(size, size);
+                return self
+                    .composite_image(data, size, centered(size))
+                    .context(format!(
+                        "Failed to draw minimized icon for '{}'",
+                        character_name
+                    ));
+            }
+        }
+
+        self.draw_text(
+            &config.minimized_text,
+            config.minimized_text_color,
+            config.minimized_text_size,
+            font_renderer,
+            centered,
+        )
+        .context(format!(
+            "Failed to render minimized text for '{}'",
+            character_name
+        ))
     }
 
     fn cleanup_overlay_resources(&self) {
@@ -0,0 +1,42 @@
+//! Writing daemon-side thumbnail positions back to the config file
+//!
+//! The Manager normally owns config.json. These helpers are used when the daemon has to
+//! persist positions itself (e.g. on shutdown). While a Manager is attached the positions are
+//! handed to it to save, so the two processes never write the file at the same time; without
+//! one they are merged into the on-disk config instead of overwriting it.
+
+use anyhow::{Context, Result};
+use ipc_channel::ipc::IpcSender;
+
+use crate::common::ipc::DaemonMessage;
+use crate::config::DaemonConfig;
+use crate::config::profile::Config;
+
+/// Persist the daemon's in-memory thumbnail positions of the active profile.
+///
+/// Returns `Ok(false)` if nothing needed saving. With a Manager attached the save is left to
+/// it and reported as done once sent.
+pub fn save_positions(
+    daemon_config: &DaemonConfig,
+    manager_attached: bool,
+    status_tx: &IpcSender<DaemonMessage>,
+) -> Result<bool> {
+    if manager_attached {
+        status_tx
+            .send(DaemonMessage::SavePositions {
+                profile: daemon_config.profile.profile_name.clone(),
+                character_thumbnails: daemon_config.character_thumbnails.clone(),
+                custom_source_thumbnails: daemon_config.custom_source_thumbnails.clone(),
+            })
+            .context("Failed to send thumbnail positions to the Manager")?;
+        return Ok(true);
+    }
+
+    Config::save_thumbnail_positions(
+        &Config::path(),
+        &daemon_config.profile.profile_name,
+        &daemon_config.character_thumbnails,
+        &daemon_config.custom_source_thumbnails,
+        &daemon_config.display_scales.monitors,
+    )
+}
@@ -0,0 +1,105 @@
+//! Battery detection for low-power mode
+//!
+//! Asks UPower on the system bus whether the machine runs on battery. Without UPower or
+//! access to the system bus (e.g. in a sandbox) the machine counts as on mains power.
+//!
+//! D-Bus calls can stall for as long as the bus timeout, so they run on a task of their own
+//! and the event loop only exchanges requests and readings with it.
+
+use anyhow::{Context, Result};
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn};
+
+/// How often the power source is polled while low-power mode follows the battery
+pub const POLL_INTERVAL_SECS: u64 = 15;
+
+/// Handle to the task polling UPower; the task ends when this is dropped
+pub struct BatteryMonitor {
+    requests: mpsc::Sender<()>,
+    readings: mpsc::Receiver<bool>,
+}
+
+impl BatteryMonitor {
+    /// Start the polling task; requires a running Tokio runtime
+    pub fn spawn() -> Self {
+        let (requests, mut request_rx) = mpsc::channel(1);
+        let (reading_tx, readings) = mpsc::channel(1);
+        tokio::spawn(async move {
+            let mut upower = UPower::default();
+            while request_rx.recv().await.is_some() {
+                let on_battery = upower.on_battery().await;
+                if reading_tx.send(on_battery).await.is_err() {
+                    break;
+                }
+            }
+        });
+        Self { requests, readings }
+    }
+
+    /// Ask for a fresh reading; ignored while the previous one is still pending
+    pub fn request(&self) {
+        let _ = self.requests.try_send(());
+    }
+
+    /// Wait for the next reading: whether the machine runs on battery
+    pub async fn reading(&mut self) -> Option<bool> {
+        self.readings.recv().await
+    }
+}
+
+/// Lazily connected UPower client, reconnecting after failures
+#[derive(Default)]
+struct UPower {
+    proxy: Option<zbus::Proxy<'static>>,
+    /// The last attempt failed and was reported; later failures are only logged at debug
+    failing: bool,
+}
+
+impl UPower {
+    async fn on_battery(&mut self) -> bool {
+        match self.read().await {
+            Ok(on_battery) => {
+                if std::mem::take(&mut self.failing) {
+                    info!("UPower reachable again, low-power mode follows the battery");
+                }
+                on_battery
+            }
+            Err(e) => {
+                // The bus may have restarted; connect afresh on the next poll
+                self.proxy = None;
+                if self.failing {
+                    debug!(error = ?e, "UPower still unavailable");
+                } else {
+                    warn!(error = ?e, "UPower unavailable, low-power mode will not follow the battery");
+                    self.failing = true;
+                }
+                false
+            }
+        }
+    }
+
+    async fn read(&mut self) -> Result<bool> {
+        if self.proxy.is_none() {
+            self.proxy = Some(connect().await?);
+        }
+        let proxy = self.proxy.as_ref().expect("connected above");
+        proxy
+            .get_property::<bool>("OnBattery")
+            .await
+            .context("Failed to read UPower OnBattery")
+    }
+}
+
+async fn connect() -> Result<zbus::Proxy<'static>> {
+    let conn = zbus::Connection::system()
+        .await
+        .context("Failed to connect to the system bus")?;
+    zbus::Proxy::new(
+        &conn,
+        "org.freedesktop.UPower",
+        "/org/freedesktop/UPower",
+        "org.freedesktop.UPower",
+    )
+    .await
+    .context("Failed to create UPower proxy")
+}
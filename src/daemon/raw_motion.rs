@@ -0,0 +1,29 @@
+//! XInput2 raw pointer motion while dragging a thumbnail
+//!
+//! Core `MotionNotify` events wait in the queue behind damage events from busy clients, so a
+//! dragged thumbnail falls behind the cursor. During a drag the daemon also listens to raw
+//! motion on the root window and, after each batch of events, moves the thumbnail once to where
+//! the pointer is now instead of replaying every queued position.
+
+use anyhow::{Context, Result};
+use x11rb::protocol::xinput::{self, ConnectionExt as XinputExt, XIEventMask};
+use x11rb::protocol::xproto::Window;
+use x11rb::rust_connection::RustConnection;
+
+/// Start or stop receiving raw motion events on `root`
+pub fn select(conn: &RustConnection, root: Window, enabled: bool) -> Result<()> {
+    let mask = if enabled {
+        XIEventMask::RAW_MOTION
+    } else {
+        XIEventMask::from(0u32)
+    };
+    conn.xinput_xi_select_events(
+        root,
+        &[xinput::EventMask {
+            deviceid: xinput::Device::ALL_MASTER.into(),
+            mask: vec![mask],
+        }],
+    )
+    .context("Failed to select raw motion events")?;
+    Ok(())
+}
@@ -1,28 +1,137 @@
 //! Daemon Thumbnail Renderer
 //!
 //! Handles low-level X11 window creation, rendering, and resource management.
+//!
+//! Source windows are scaled server-side with XRender, or in software when XRender has no
+//! format for their visual.
 
 use anyhow::{Context, Result};
-use tracing::{debug, error, info};
+use std::cell::Cell;
+use tracing::{debug, error, info, warn};
 use x11rb::connection::Connection;
+use x11rb::properties::{WmHints, WmSizeHints, WmSizeHintsSpecification};
 use x11rb::protocol::damage::{
     ConnectionExt as DamageExt, Damage, ReportLevel as DamageReportLevel,
 };
 use x11rb::protocol::render::{
-    ConnectionExt as RenderExt, CreatePictureAux, PictOp, Picture, Transform,
+    Color, ConnectionExt as RenderExt, CreatePictureAux, PictOp, Pictformat, Picture, Transform,
 };
 use x11rb::protocol::xproto::*;
 use x11rb::rust_connection::RustConnection;
 use x11rb::wrapper::ConnectionExt as WrapperExt;
 
 use crate::common::constants::x11;
-use crate::common::types::Dimensions;
+use crate::common::types::{Dimensions, ThumbnailWmStates};
 use crate::x11::{AppContext, to_fixed};
 
 use super::font::FontRenderer;
 use super::overlay::OverlayRenderer;
+use super::software::{PixelLayout, SoftwareSource};
 use crate::config::DisplayConfig;
 
+/// How the source window's content is read
+#[derive(Debug)]
+enum SourceReader {
+    /// Scaled server-side by XRender
+    Render(Picture),
+    /// No Render format matches the source; scaled on the CPU
+    Software(SoftwareSource),
+}
+
+/// Window title of the thumbnail previewing `character_name`
+pub fn thumbnail_title(character_name: &str) -> String {
+    format!("EPM: {}", character_name)
+}
+
+/// Stable WM_CLASS instance for `character_name`, e.g. `epm-my-pilot` for "My Pilot"
+///
+/// Lowercase ASCII alphanumerics are kept and every other run of characters becomes a single
+/// `-`, so the instance is safe to use in window manager rules.
+pub fn thumbnail_instance(character_name: &str) -> String {
+    let mut instance = String::from("epm-");
+    for c in character_name.chars() {
+        if c.is_ascii_alphanumeric() {
+            instance.push(c.to_ascii_lowercase());
+        } else if !instance.ends_with('-') {
+            instance.push('-');
+        }
+    }
+    while instance.len() > "epm-".len() && instance.ends_with('-') {
+        instance.pop();
+    }
+    instance
+}
+
+/// Set WM_CLASS, WM_NAME and `_NET_WM_NAME` of a thumbnail window
+fn set_names(
+    conn: &RustConnection,
+    atoms: &crate::x11::CachedAtoms,
+    window: Window,
+    character_name: &str,
+) -> Result<()> {
+    let class = format!(
+        "{}\0{}\0",
+        thumbnail_instance(character_name),
+        x11::THUMBNAIL_CLASS
+    );
+    conn.change_property8(
+        PropMode::REPLACE,
+        window,
+        atoms.wm_class,
+        AtomEnum::STRING,
+        class.as_bytes(),
+    )
+    .context(format!("Failed to set WM_CLASS for '{}'", character_name))?;
+
+    let title = thumbnail_title(character_name);
+    conn.change_property8(
+        PropMode::REPLACE,
+        window,
+        AtomEnum::WM_NAME,
+        AtomEnum::STRING,
+        title.as_bytes(),
+    )
+    .context(format!("Failed to set WM_NAME for '{}'", character_name))?;
+    conn.change_property8(
+        PropMode::REPLACE,
+        window,
+        atoms.net_wm_name,
+        atoms.utf8_string,
+        title.as_bytes(),
+    )
+    .context(format!(
+        "Failed to set _NET_WM_NAME for '{}'",
+        character_name
+    ))?;
+    Ok(())
+}
+
+/// Set `_NET_WM_STATE` of a thumbnail window to the enabled `wm_states`
+fn set_wm_states(
+    conn: &RustConnection,
+    atoms: &crate::x11::CachedAtoms,
+    window: Window,
+    wm_states: ThumbnailWmStates,
+) -> Result<()> {
+    let states: Vec<Atom> = [
+        (wm_states.above, atoms.net_wm_state_above),
+        (wm_states.sticky, atoms.net_wm_state_sticky),
+        (wm_states.skip_taskbar, atoms.net_wm_state_skip_taskbar),
+        (wm_states.skip_pager, atoms.net_wm_state_skip_pager),
+    ]
+    .into_iter()
+    .filter_map(|(enabled, atom)| enabled.then_some(atom))
+    .collect();
+    conn.change_property32(
+        PropMode::REPLACE,
+        window,
+        atoms.net_wm_state,
+        AtomEnum::ATOM,
+        &states,
+    )?;
+    Ok(())
+}
+
 #[derive(Debug)]
 /// Handles low-level X11 window creation, rendering, and resource management.
 ///
@@ -44,8 +153,15 @@ pub struct ThumbnailRenderer<'a> {
     root: Window,
 
     // === X11 Render Resources (private, owned resources) ===
-    src_picture: Picture,
+    source: SourceReader,
     dst_picture: Picture,
+    dst_format: Pictformat,
+    depth: u8,
+    /// Solid alpha picture the source is composited through on ARGB windows, and its alpha
+    /// (None = opacity is left to the compositor)
+    opacity_mask: Option<(Picture, u16)>,
+    /// Last frame kept while minimized, for the dimmed minimized overlay
+    frozen: Cell<Option<(Pixmap, Picture)>>,
 
     // === Overlay Renderer (handles text, border, pixmap) ===
     overlay: OverlayRenderer<'a>,
@@ -60,21 +176,43 @@ impl<'a> ThumbnailRenderer<'a> {
         self.parent = parent;
     }
 
-    /// Create and configure the X11 window
+    /// Create and configure the X11 window.
+    /// Unless `managed`, the window bypasses the window manager (override-redirect).
+    /// With `argb_visual`, the window is created on that visual and colormap instead of the
+    /// root visual.
+    #[allow(clippy::too_many_arguments)]
     fn create_window(
         ctx: &AppContext,
         character_name: &str,
         x: i16,
         y: i16,
         dimensions: Dimensions,
+        managed: bool,
+        argb_visual: Option<(Visualid, Colormap)>,
     ) -> Result<Window> {
         let window = ctx
             .conn
             .generate_id()
             .context("Failed to generate X11 window ID")?;
+        let (depth, visual, aux) = match argb_visual {
+            // A visual other than the parent's needs its own colormap and border pixel
+            Some((visual, colormap)) => (
+                x11::ARGB_DEPTH,
+                visual,
+                CreateWindowAux::new()
+                    .background_pixel(0)
+                    .border_pixel(0)
+                    .colormap(colormap),
+            ),
+            None => (
+                ctx.screen.root_depth,
+                ctx.screen.root_visual,
+                CreateWindowAux::new(),
+            ),
+        };
         ctx.conn
             .create_window(
-                ctx.screen.root_depth,
+                depth,
                 window,
                 ctx.screen.root,
                 x,
@@ -83,30 +221,39 @@ impl<'a> ThumbnailRenderer<'a> {
                 dimensions.height,
                 0,
                 WindowClass::INPUT_OUTPUT,
-                ctx.screen.root_visual,
-                &CreateWindowAux::new()
-                    .override_redirect(x11::OVERRIDE_REDIRECT)
+                visual,
+                &aux.override_redirect(if managed { 0 } else { x11::OVERRIDE_REDIRECT })
                     .event_mask(
                         EventMask::SUBSTRUCTURE_NOTIFY
                             | EventMask::BUTTON_PRESS
                             | EventMask::BUTTON_RELEASE
-                            | EventMask::POINTER_MOTION,
+                            | EventMask::POINTER_MOTION
+                            | EventMask::ENTER_WINDOW
+                            | EventMask::LEAVE_WINDOW
+                            | EventMask::VISIBILITY_CHANGE,
                     ),
             )
             .context(format!(
                 "Failed to create thumbnail window for '{}'",
                 character_name
             ))?;
+        super::touch::select_events(ctx, window)?;
 
         Ok(window)
     }
 
-    /// Setup window properties (opacity, WM_CLASS, always-on-top, PID)
+    /// Setup window properties (opacity, WM_CLASS, title, EWMH type and state, ICCCM hints, PID).
+    /// `opacity` is None when the window applies it itself.
+    #[allow(clippy::too_many_arguments)]
     fn setup_window_properties(
         ctx: &AppContext,
         window: Window,
-        opacity: u32,
+        opacity: Option<u32>,
+        wm_states: ThumbnailWmStates,
         character_name: &str,
+        x: i16,
+        y: i16,
+        dimensions: Dimensions,
     ) -> Result<()> {
         // Set PID so we can identify our own thumbnail windows
         let pid = std::process::id();
@@ -124,41 +271,74 @@ impl<'a> ThumbnailRenderer<'a> {
             ))?;
 
         // Set opacity
+        if let Some(opacity) = opacity {
+            ctx.conn
+                .change_property32(
+                    PropMode::REPLACE,
+                    window,
+                    ctx.atoms.net_wm_window_opacity,
+                    AtomEnum::CARDINAL,
+                    &[opacity],
+                )
+                .context(format!(
+                    "Failed to set window opacity for '{}'",
+                    character_name
+                ))?;
+        }
+
+        set_names(ctx.conn, ctx.atoms, window, character_name)?;
+
+        // Window managers keep utility windows floating and out of tiling layouts
         ctx.conn
             .change_property32(
                 PropMode::REPLACE,
                 window,
-                ctx.atoms.net_wm_window_opacity,
-                AtomEnum::CARDINAL,
-                &[opacity],
+                ctx.atoms.net_wm_window_type,
+                AtomEnum::ATOM,
+                &[ctx.atoms.net_wm_window_type_utility],
             )
             .context(format!(
-                "Failed to set window opacity for '{}'",
+                "Failed to set window type for '{}'",
                 character_name
             ))?;
 
-        // Set WM_CLASS
-        ctx.conn
-            .change_property8(
-                PropMode::REPLACE,
-                window,
-                ctx.atoms.wm_class,
-                AtomEnum::STRING,
-                b"eve-preview-thumbnail\0eve-preview-thumbnail\0",
-            )
-            .context(format!("Failed to set WM_CLASS for '{}'", character_name))?;
+        // By default always-on-top, on every desktop, and kept out of taskbars and pagers
+        set_wm_states(ctx.conn, ctx.atoms, window, wm_states).context(format!(
+            "Failed to set window state for '{}'",
+            character_name
+        ))?;
 
-        // Set always-on-top
+        // For managed windows: keep the requested geometry, never take focus, no decorations
+        WmSizeHints {
+            position: Some((WmSizeHintsSpecification::UserSpecified, x.into(), y.into())),
+            size: Some((
+                WmSizeHintsSpecification::UserSpecified,
+                dimensions.width.into(),
+                dimensions.height.into(),
+            )),
+            ..WmSizeHints::default()
+        }
+        .set_normal_hints(ctx.conn, window)
+        .context(format!(
+            "Failed to set WM_NORMAL_HINTS for '{}'",
+            character_name
+        ))?;
+        WmHints {
+            input: Some(false),
+            ..WmHints::default()
+        }
+        .set(ctx.conn, window)
+        .context(format!("Failed to set WM_HINTS for '{}'", character_name))?;
         ctx.conn
             .change_property32(
                 PropMode::REPLACE,
                 window,
-                ctx.atoms.net_wm_state,
-                AtomEnum::ATOM,
-                &[ctx.atoms.net_wm_state_above],
+                ctx.atoms.motif_wm_hints,
+                ctx.atoms.motif_wm_hints,
+                &[x11::MOTIF_HINTS_DECORATIONS, 0, 0, 0, 0],
             )
             .context(format!(
-                "Failed to set window always-on-top for '{}'",
+                "Failed to set _MOTIF_WM_HINTS for '{}'",
                 character_name
             ))?;
 
@@ -186,23 +366,111 @@ impl<'a> ThumbnailRenderer<'a> {
     }
 
     /// Create render pictures and resources
+    #[allow(clippy::too_many_arguments)]
     fn create_render_resources(
         ctx: &AppContext,
         window: Window,
         src: Window,
         src_depth: u8,
+        src_visual: Visualid,
+        src_format: Option<Pictformat>,
+        dst_format: Pictformat,
         character_name: &str,
-    ) -> Result<(Picture, Picture)> {
-        // Determine source format based on window depth
-        let src_format = if src_depth == 32 {
-            info!(character = %character_name, depth = src_depth, format = "ARGB32", "Using ARGB format for source window");
-            ctx.formats.argb
-        } else {
-            // Default to RGB (usually 24-bit)
-            // If it's not 32 or root depth, this might still be wrong, but it covers standard cases.
-            debug!(character = %character_name, depth = src_depth, format = "RGB24", "Using RGB format for source window");
-            ctx.formats.rgb
+    ) -> Result<(SourceReader, Picture)> {
+        let source = Self::create_source_reader(
+            ctx,
+            window,
+            src,
+            src_depth,
+            src_visual,
+            src_format,
+            character_name,
+        )?;
+
+        // Destination picture
+        let dst_picture = ctx
+            .conn
+            .generate_id()
+            .context("Failed to generate ID for destination picture")?;
+        ctx.conn
+            .render_create_picture(dst_picture, window, dst_format, &CreatePictureAux::new())
+            .context(format!(
+                "Failed to create destination picture for '{}'",
+                character_name
+            ))?;
+
+        Ok((source, dst_picture))
+    }
+
+    /// Visual of the source window
+    fn source_visual(ctx: &AppContext, src: Window, character_name: &str) -> Result<Visualid> {
+        Ok(ctx
+            .conn
+            .get_window_attributes(src)
+            .context("Failed to send attributes query for source window")?
+            .reply()
+            .context(format!(
+                "Failed to get attributes of source window for '{}'",
+                character_name
+            ))?
+            .visual)
+    }
+
+    /// Create a Render picture of the source window, or a software reader if Render has no
+    /// format (`src_format`) for its visual
+    fn create_source_reader(
+        ctx: &AppContext,
+        window: Window,
+        src: Window,
+        src_depth: u8,
+        src_visual: Visualid,
+        src_format: Option<Pictformat>,
+        character_name: &str,
+    ) -> Result<SourceReader> {
+        let Some(src_format) = src_format else {
+            let setup = ctx.conn.setup();
+            let layouts = (
+                PixelLayout::for_visual(setup, ctx.screen, src_depth, src_visual),
+                PixelLayout::for_visual(
+                    setup,
+                    ctx.screen,
+                    ctx.screen.root_depth,
+                    ctx.screen.root_visual,
+                ),
+            );
+            let (Some(src_layout), Some(dst_layout)) = layouts else {
+                return Err(anyhow::anyhow!(
+                    "No RENDER format or readable pixel layout for '{}' (depth {}, visual {:#x})",
+                    character_name,
+                    src_depth,
+                    src_visual
+                ));
+            };
+            warn!(
+                character = %character_name,
+                depth = src_depth,
+                visual = src_visual,
+                "No RENDER format for source window, using slower software scaling"
+            );
+            let gc = ctx
+                .conn
+                .generate_id()
+                .context("Failed to generate ID for graphics context")?;
+            ctx.conn
+                .create_gc(gc, window, &CreateGCAux::new())
+                .context(format!(
+                    "Failed to create graphics context for '{}'",
+                    character_name
+                ))?;
+            return Ok(SourceReader::Software(SoftwareSource {
+                src_layout,
+                dst_layout,
+                dst_depth: ctx.screen.root_depth,
+                gc,
+                last_draw: std::cell::Cell::new(None),
+            }));
         };
+        debug!(character = %character_name, depth = src_depth, format = src_format, "Using RENDER format for source window");
 
         // Source picture
         let src_picture = ctx
@@ -225,24 +493,56 @@ impl<'a> ThumbnailRenderer<'a> {
                 character_name
             ))?;
 
-        // Destination picture
-        let dst_picture = ctx
+        Ok(SourceReader::Render(src_picture))
+    }
+
+    /// Solid picture of the given alpha
+    fn create_opacity_mask(ctx: &AppContext, alpha: u16, character_name: &str) -> Result<Picture> {
+        let mask = ctx
             .conn
             .generate_id()
-            .context("Failed to generate ID for destination picture")?;
+            .context("Failed to generate ID for opacity mask")?;
         ctx.conn
-            .render_create_picture(
-                dst_picture,
-                window,
-                ctx.formats.rgb,
-                &CreatePictureAux::new(),
+            .render_create_solid_fill(
+                mask,
+                Color {
+                    red: 0,
+                    green: 0,
+                    blue: 0,
+                    alpha,
+                },
             )
             .context(format!(
-                "Failed to create destination picture for '{}'",
+                "Failed to create opacity mask for '{}'",
                 character_name
             ))?;
+        Ok(mask)
+    }
 
-        Ok((src_picture, dst_picture))
+    /// Operator for drawing the overlay and dimming onto the window. ATOP keeps the
+    /// window's alpha, which on ARGB windows is the opacity; on opaque windows it equals OVER.
+    fn blend_op(&self) -> PictOp {
+        if self.opacity_mask.is_some() {
+            PictOp::ATOP
+        } else {
+            PictOp::OVER
+        }
+    }
+
+    /// Switch source scaling between bilinear filtering and the cheaper nearest-neighbour
+    pub fn set_smoothing(&self, character_name: &str, smooth: bool) -> Result<()> {
+        // Software scaling is always nearest-neighbour
+        let SourceReader::Render(src_picture) = self.source else {
+            return Ok(());
+        };
+        let filter: &[u8] = if smooth { b"bilinear" } else { b"nearest" };
+        self.conn
+            .render_set_picture_filter(src_picture, filter, &[])
+            .context(format!(
+                "Failed to set scaling filter for '{}'",
+                character_name
+            ))?;
+        Ok(())
     }
 
     /// Create damage tracking for source window
@@ -289,8 +589,43 @@ impl<'a> ThumbnailRenderer<'a> {
         y: i16,
         dimensions: Dimensions,
     ) -> Result<Self> {
+        let src_visual = Self::source_visual(ctx, src, character_name)?;
+        let src_format = ctx
+            .formats
+            .for_window(src_visual, src_depth, ctx.screen.root_depth);
+
+        // The software reader only writes opaque root visual pixels, so ARGB windows need a
+        // Render format for the source
+        let argb_visual = if display_config.argb_visual {
+            match (ctx.formats.argb_visual, src_format) {
+                (Some(visual), Some(_)) => Some(visual),
+                (None, _) => {
+                    warn!(character = %character_name, "No ARGB visual available, opacity is left to the compositor");
+                    None
+                }
+                (Some(_), None) => {
+                    warn!(character = %character_name, "Source window needs software scaling, opacity is left to the compositor");
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        let (depth, dst_format) = match ctx.formats.argb.filter(|_| argb_visual.is_some()) {
+            Some(argb) => (x11::ARGB_DEPTH, argb),
+            None => (ctx.screen.root_depth, ctx.formats.rgb),
+        };
+
         // Create window and setup properties
-        let window = Self::create_window(ctx, character_name, x, y, dimensions)?;
+        let window = Self::create_window(
+            ctx,
+            character_name,
+            x,
+            y,
+            dimensions,
+            display_config.managed_windows,
+            argb_visual,
+        )?;
 
         // RAII guard to automatically destroy the window if initialization fails partially
         // This ensures we don't leak orphaned windows if we error out before returning the valid Thumbnail struct
@@ -325,11 +660,28 @@ impl<'a> ThumbnailRenderer<'a> {
             should_cleanup: true,
         };
 
-        Self::setup_window_properties(ctx, window, display_config.opacity, character_name)?;
+        Self::setup_window_properties(
+            ctx,
+            window,
+            argb_visual.is_none().then_some(display_config.opacity),
+            display_config.wm_states,
+            character_name,
+            x,
+            y,
+            dimensions,
+        )?;
 
         // Create rendering resources
-        let (src_picture, dst_picture) =
-            Self::create_render_resources(ctx, window, src, src_depth, character_name)?;
+        let (source, dst_picture) = Self::create_render_resources(
+            ctx,
+            window,
+            src,
+            src_depth,
+            src_visual,
+            src_format,
+            dst_format,
+            character_name,
+        )?;
 
         // Create overlay renderer
         let overlay = OverlayRenderer::new(
@@ -345,6 +697,17 @@ impl<'a> ThumbnailRenderer<'a> {
         // Setup damage tracking
         let damage = Self::create_damage_tracking(ctx, src, character_name)?;
 
+        let opacity_mask = match argb_visual {
+            Some(_) => {
+                let alpha = (display_config.opacity >> 16) as u16;
+                Some((
+                    Self::create_opacity_mask(ctx, alpha, character_name)?,
+                    alpha,
+                ))
+            }
+            None => None,
+        };
+
         let renderer = Self {
             // X11 Window Handles
             window,
@@ -380,8 +743,12 @@ impl<'a> ThumbnailRenderer<'a> {
             root: ctx.screen.root,
 
             // X11 Render Resources
-            src_picture,
+            source,
             dst_picture,
+            dst_format,
+            depth,
+            opacity_mask,
+            frozen: Cell::new(None),
 
             // Overlay
             overlay,
@@ -397,6 +764,67 @@ impl<'a> ThumbnailRenderer<'a> {
         Ok(renderer)
     }
 
+    /// Set the character last logged in on the client, labelled while it is logged out
+    pub fn set_last_character(&mut self, name: Option<String>) {
+        self.overlay.set_last_character(name);
+    }
+
+    /// Set the launch slot of the EVE client, whose account is labelled while it is logged out
+    pub fn set_client_slot(&mut self, slot: Option<usize>) {
+        self.overlay.set_client_slot(slot);
+    }
+
+    /// Name the window and its WM_CLASS instance after the character it previews
+    pub fn set_names(&self, character_name: &str) -> Result<()> {
+        set_names(self.conn, self.atoms, self.window, character_name)
+    }
+
+    /// Switch between window-manager-managed and override-redirect.
+    /// The attribute only takes effect on map, so a `mapped` window is remapped.
+    pub fn set_managed(&self, character_name: &str, managed: bool, mapped: bool) -> Result<()> {
+        if mapped {
+            self.unmap()?;
+        }
+        self.conn
+            .change_window_attributes(
+                self.window,
+                &ChangeWindowAttributesAux::new().override_redirect(if managed {
+                    0
+                } else {
+                    x11::OVERRIDE_REDIRECT
+                }),
+            )
+            .context(format!(
+                "Failed to change override-redirect for '{}'",
+                character_name
+            ))?;
+        if mapped {
+            self.map()?;
+        }
+        Ok(())
+    }
+
+    /// Replace the EWMH states of the window. Window managers only read them when a window
+    /// is mapped, so a `mapped` window is remapped.
+    pub fn set_wm_states(
+        &self,
+        character_name: &str,
+        wm_states: ThumbnailWmStates,
+        mapped: bool,
+    ) -> Result<()> {
+        if mapped {
+            self.unmap()?;
+        }
+        set_wm_states(self.conn, self.atoms, self.window, wm_states).context(format!(
+            "Failed to set window state for '{}'",
+            character_name
+        ))?;
+        if mapped {
+            self.map()?;
+        }
+        Ok(())
+    }
+
     /// Maps the thumbnail window, making it visible on screen.
     pub fn map(&self) -> Result<()> {
         self.conn.map_window(self.window)?;
@@ -422,6 +850,8 @@ impl<'a> ThumbnailRenderer<'a> {
     /// # Errors
     /// Returns an error if X11 composite operations fail.
     pub fn capture(&self, character_name: &str, dimensions: Dimensions) -> Result<()> {
+        self.release_frozen();
+
         // Query attributes to check map state
         let attr_cookie = self.conn.get_window_attributes(self.src)?;
         let attrs = attr_cookie.reply()?;
@@ -470,6 +900,26 @@ impl<'a> ThumbnailRenderer<'a> {
             return Ok(());
         }
 
+        let src_picture = match &self.source {
+            SourceReader::Render(picture) => *picture,
+            SourceReader::Software(software) => {
+                return software
+                    .draw(
+                        self.conn,
+                        self.src,
+                        src_width,
+                        src_height,
+                        self.window,
+                        dimensions.width,
+                        dimensions.height,
+                    )
+                    .context(format!(
+                        "Failed to scale source window in software for '{}'",
+                        character_name
+                    ));
+            }
+        };
+
         let transform = Transform {
             matrix11: to_fixed(src_width as f32 / dimensions.width as f32),
             matrix22: to_fixed(src_height as f32 / dimensions.height as f32),
@@ -477,13 +927,13 @@ impl<'a> ThumbnailRenderer<'a> {
             ..Default::default()
         };
         self.conn
-            .render_set_picture_transform(self.src_picture, transform)
+            .render_set_picture_transform(src_picture, transform)
             .context(format!("Failed to set transform for '{}'", character_name))?;
         self.conn
             .render_composite(
                 PictOp::SRC,
-                self.src_picture,
-                0u32,
+                src_picture,
+                self.opacity_mask.map_or(0, |(mask, _)| mask),
                 self.dst_picture,
                 0,
                 0,
@@ -508,6 +958,7 @@ impl<'a> ThumbnailRenderer<'a> {
         dimensions: Dimensions,
         color: x11rb::protocol::render::Color,
     ) -> Result<()> {
+        let color = self.with_opacity(color);
         let rect = x11rb::protocol::xproto::Rectangle {
             x: 0,
             y: 0,
@@ -524,11 +975,29 @@ impl<'a> ThumbnailRenderer<'a> {
         Ok(())
     }
 
+    /// `color` (premultiplied) scaled by the opacity of ARGB windows
+    fn with_opacity(&self, color: Color) -> Color {
+        let Some((_, alpha)) = self.opacity_mask else {
+            return color;
+        };
+        let alpha = u32::from(alpha);
+        let scale = |c: u16| (u32::from(c) * alpha / 0xffff) as u16;
+        Color {
+            red: scale(color.red),
+            green: scale(color.green),
+            blue: scale(color.blue),
+            alpha: scale(color.alpha),
+        }
+    }
+
     /// Draws the border and updates the name overlay.
     ///
     /// # Arguments
     /// * `focused` - If true, draws the border. If false, clears the border area.
-    /// * `skipped` - If true, draws the skipped indicator (diagonal red lines).
+    /// * `skipped` - If true, draws the configured skipped indicator.
+    /// * `next_target` - If true and not focused, draws the next cycle target highlight.
+    /// * `selected` - If true, draws the selection highlight instead of any other border.
+    #[allow(clippy::too_many_arguments)]
     pub fn border(
         &self,
         display_config: &DisplayConfig,
@@ -536,6 +1005,8 @@ impl<'a> ThumbnailRenderer<'a> {
         dimensions: Dimensions,
         focused: bool,
         skipped: bool,
+        next_target: bool,
+        selected: bool,
         font_renderer: &FontRenderer,
     ) -> Result<()> {
         self.overlay.draw_border(
@@ -544,6 +1015,8 @@ impl<'a> ThumbnailRenderer<'a> {
             dimensions,
             focused,
             skipped,
+            next_target,
+            selected,
             font_renderer,
         )?;
 
@@ -553,7 +1026,8 @@ impl<'a> ThumbnailRenderer<'a> {
 
     /// Renders the "MINIMIZED" state overlay.
     ///
-    /// Clears any existing border and draws the localized logic for minimized windows.
+    /// Clears any existing border and draws the configured text or icon for minimized windows,
+    /// over the last frame dimmed by `minimized_dim` percent.
     pub fn minimized(
         &self,
         display_config: &DisplayConfig,
@@ -564,17 +1038,52 @@ impl<'a> ThumbnailRenderer<'a> {
         self.overlay
             .draw_minimized(display_config, character_name, dimensions, font_renderer)?;
 
-        // Explicitly clear background to black using fill_static.
         // We cannot use self.update() here because it calls capture(), which correctly skips
-        // unmapped windows to prevent KWin crashes. However, for the minimized state,
-        // we WANT to clear the old content (frozen image).
+        // unmapped windows to prevent KWin crashes. Fully dimmed, the old content is simply
+        // cleared to black; otherwise it is frozen once and redrawn dimmed on every repaint.
+        let dim = u32::from(display_config.minimized_dim.min(100));
         let black = x11rb::protocol::render::Color {
             red: 0,
             green: 0,
             blue: 0,
-            alpha: 0xffff,
+            alpha: (dim * 0xffff / 100) as u16,
         };
-        self.fill_static(character_name, dimensions, black)?;
+        if dim >= 100 {
+            self.fill_static(character_name, dimensions, black)?;
+        } else {
+            let frozen = self.frozen_frame(character_name, dimensions)?;
+            self.conn
+                .render_composite(
+                    PictOp::SRC,
+                    frozen,
+                    0u32,
+                    self.dst_picture,
+                    0,
+                    0,
+                    0,
+                    0,
+                    0,
+                    0,
+                    dimensions.width,
+                    dimensions.height,
+                )
+                .context(format!(
+                    "Failed to draw frozen frame for '{}'",
+                    character_name
+                ))?;
+            let rect = Rectangle {
+                x: 0,
+                y: 0,
+                width: dimensions.width,
+                height: dimensions.height,
+            };
+            self.conn
+                .render_fill_rectangles(self.blend_op(), self.dst_picture, black, &[rect])
+                .context(format!(
+                    "Failed to dim minimized thumbnail for '{}'",
+                    character_name
+                ))?;
+        }
 
         self.overlay(character_name, dimensions).context(format!(
             "Failed to update minimized display for '{}'",
@@ -583,6 +1092,74 @@ impl<'a> ThumbnailRenderer<'a> {
         Ok(())
     }
 
+    /// Picture holding the thumbnail's last frame, copied from the window on first use
+    fn frozen_frame(&self, character_name: &str, dimensions: Dimensions) -> Result<Picture> {
+        if let Some((_, picture)) = self.frozen.get() {
+            return Ok(picture);
+        }
+
+        let pixmap = self
+            .conn
+            .generate_id()
+            .context("Failed to generate ID for frozen frame pixmap")?;
+        self.conn
+            .create_pixmap(
+                self.depth,
+                pixmap,
+                self.window,
+                dimensions.width,
+                dimensions.height,
+            )
+            .context(format!(
+                "Failed to create frozen frame pixmap for '{}'",
+                character_name
+            ))?;
+        let picture = self
+            .conn
+            .generate_id()
+            .context("Failed to generate ID for frozen frame picture")?;
+        self.conn
+            .render_create_picture(picture, pixmap, self.dst_format, &CreatePictureAux::new())
+            .context(format!(
+                "Failed to create frozen frame picture for '{}'",
+                character_name
+            ))?;
+        self.frozen.set(Some((pixmap, picture)));
+
+        self.conn
+            .render_composite(
+                PictOp::SRC,
+                self.dst_picture,
+                0u32,
+                picture,
+                0,
+                0,
+                0,
+                0,
+                0,
+                0,
+                dimensions.width,
+                dimensions.height,
+            )
+            .context(format!(
+                "Failed to copy frozen frame for '{}'",
+                character_name
+            ))?;
+        Ok(picture)
+    }
+
+    /// Frees the frozen frame, if any
+    fn release_frozen(&self) {
+        if let Some((pixmap, picture)) = self.frozen.take() {
+            if let Err(e) = self.conn.render_free_picture(picture) {
+                error!(picture = picture, error = %e, "Failed to free frozen frame picture");
+            }
+            if let Err(e) = self.conn.free_pixmap(pixmap) {
+                error!(pixmap = pixmap, error = %e, "Failed to free frozen frame pixmap");
+            }
+        }
+    }
+
     /// Updates the text overlay with the character name.
     pub fn update_name(
         &self,
@@ -611,15 +1188,29 @@ impl<'a> ThumbnailRenderer<'a> {
             character_name,
             dimensions,
             border_size,
+            false,
             font_renderer,
-        )
+        )?;
+        self.overlay.draw_badges(
+            display_config,
+            character_name,
+            dimensions,
+            false,
+            font_renderer,
+        )?;
+        self.overlay
+            .draw_group_accent(display_config, character_name, dimensions, border_size)
     }
 
     /// Composites the text/border overlay on top of the thumbnail content.
     pub fn overlay(&self, character_name: &str, dimensions: Dimensions) -> Result<()> {
+        // An opaque overlay would hide the content
+        if !self.overlay.has_alpha() {
+            return Ok(());
+        }
         self.conn
             .render_composite(
-                PictOp::OVER,
+                self.blend_op(),
                 self.overlay.overlay_picture,
                 0u32,
                 self.dst_picture,
@@ -639,6 +1230,16 @@ impl<'a> ThumbnailRenderer<'a> {
         Ok(())
     }
 
+    /// The source is scaled in software (see `software`)
+    pub fn reads_in_software(&self) -> bool {
+        matches!(self.source, SourceReader::Software(_))
+    }
+
+    /// A software-scaled source was read too recently to read it again at `now`
+    pub fn throttled(&self, now: std::time::Instant) -> bool {
+        matches!(&self.source, SourceReader::Software(software) if !software.due(now))
+    }
+
     /// Logic for full update cycle: capture source -> apply overlay.
     pub fn update(&self, character_name: &str, dimensions: Dimensions) -> Result<()> {
         self.capture(character_name, dimensions).context(format!(
@@ -743,6 +1344,9 @@ impl<'a> ThumbnailRenderer<'a> {
             )
             .context(format!("Failed to resize window for '{}'", character_name))?;
 
+        // The frozen frame no longer matches the window size
+        self.release_frozen();
+
         // Recreate overlay resources via helper
         self.overlay
             .resize(self.root, width, height)
@@ -769,12 +1373,25 @@ impl Drop for ThumbnailRenderer<'_> {
 
         // OverlayRenderer Drop will handle overlay resources
 
-        if let Err(e) = self.conn.render_free_picture(self.src_picture) {
-            error!(
-                picture = self.src_picture,
-                error = %e,
-                "Failed to free source picture"
-            );
+        self.release_frozen();
+
+        match &self.source {
+            SourceReader::Render(picture) => {
+                if let Err(e) = self.conn.render_free_picture(*picture) {
+                    error!(picture = picture, error = %e, "Failed to free source picture");
+                }
+            }
+            SourceReader::Software(software) => {
+                if let Err(e) = self.conn.free_gc(software.gc) {
+                    error!(gc = software.gc, error = %e, "Failed to free graphics context");
+                }
+            }
+        }
+
+        if let Some((mask, _)) = self.opacity_mask
+            && let Err(e) = self.conn.render_free_picture(mask)
+        {
+            error!(picture = mask, error = %e, "Failed to free opacity mask");
         }
 
         if let Err(e) = self.conn.render_free_picture(self.dst_picture) {
@@ -798,3 +1415,21 @@ impl Drop for ThumbnailRenderer<'_> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_thumbnail_title() {
+        assert_eq!(thumbnail_title("My Pilot"), "EPM: My Pilot");
+    }
+
+    #[test]
+    fn test_thumbnail_instance_is_stable_slug() {
+        assert_eq!(thumbnail_instance("My Pilot"), "epm-my-pilot");
+        assert_eq!(thumbnail_instance("  Jita--Trader 2 "), "epm-jita-trader-2");
+        assert_eq!(thumbnail_instance("Ünïcode"), "epm-n-code");
+        assert_eq!(thumbnail_instance(""), "epm-");
+    }
+}
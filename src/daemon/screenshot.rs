@@ -0,0 +1,278 @@
+//! Full-resolution screenshots of EVE client windows
+//!
+//! The source window (not its thumbnail) is read with core `GetImage` and written as a PNG.
+//! Minimized or unmapped clients have no contents to read and fail with an error. The same
+//! encoding copies a thumbnail's own contents to the clipboard.
+
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use ipc_channel::ipc::IpcSender;
+use tracing::{error, info};
+use x11rb::connection::Connection;
+use x11rb::protocol::xproto::{ConnectionExt, ImageFormat, Window};
+
+use crate::common::constants::config::screenshot;
+use crate::common::constants::eve;
+use crate::common::image::expand_path;
+use crate::common::ipc::{DaemonError, DaemonErrorKind, DaemonMessage};
+use crate::common::types::Dimensions;
+use crate::config::profile::Config;
+use crate::x11::AppContext;
+
+use super::software::{PixelLayout, scale, to_rgb};
+
+/// Capture `window` and report the saved file or the failure to the Manager
+pub fn save_and_report(
+    ctx: &AppContext,
+    window: Window,
+    name: &str,
+    dir: &str,
+    status_tx: &IpcSender<DaemonMessage>,
+) {
+    let message = match capture(ctx, window, name, dir) {
+        Ok(path) => {
+            info!(character = %name, path = %path.display(), "Saved screenshot");
+            DaemonMessage::Status(format!("Screenshot saved to {}", path.display()))
+        }
+        Err(e) => {
+            error!(character = %name, error = ?e, "Failed to save screenshot");
+            DaemonMessage::Error(DaemonError::new(
+                DaemonErrorKind::Screenshot,
+                format!("Failed to save screenshot of '{name}': {e:#}"),
+            ))
+        }
+    };
+    let _ = status_tx.send(message);
+}
+
+/// Save `window` as `<name>-<timestamp>.png` in `dir` and return the file's path
+///
+/// An empty `dir` uses the default directory.
+pub fn capture(ctx: &AppContext, window: Window, name: &str, dir: &str) -> Result<PathBuf> {
+    let png = encode_png(ctx, window, None)?;
+    let dir = directory(dir);
+    std::fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create screenshot directory {:?}", dir))?;
+    let path = dir.join(file_name(name, chrono::Local::now()));
+    std::fs::write(&path, png).with_context(|| format!("Failed to write screenshot {:?}", path))?;
+    Ok(path)
+}
+
+/// Packed 8-bit RGB, the pixel layout PNG expects
+const RGB24: PixelLayout = PixelLayout {
+    bits_per_pixel: 24,
+    scanline_pad: 8,
+    red_mask: 0x00FF_0000,
+    green_mask: 0x0000_FF00,
+    blue_mask: 0x0000_00FF,
+    lsb_first: false,
+};
+
+/// Current contents of `window` as a PNG file, scaled to `size` if given
+pub fn encode_png(ctx: &AppContext, window: Window, size: Option<Dimensions>) -> Result<Vec<u8>> {
+    WindowImage::grab(ctx, window)?.encode_png(size)
+}
+
+/// Raw contents of a window as read from the server, to be encoded away from the event loop
+pub struct WindowImage {
+    data: Vec<u8>,
+    layout: PixelLayout,
+    width: u16,
+    height: u16,
+}
+
+impl WindowImage {
+    /// Read the current contents of `window`
+    pub fn grab(ctx: &AppContext, window: Window) -> Result<Self> {
+        let geometry = ctx
+            .conn
+            .get_geometry(window)
+            .context("Failed to send geometry query for screenshot")?
+            .reply()
+            .context("Failed to get window geometry for screenshot")?;
+        let attributes = ctx
+            .conn
+            .get_window_attributes(window)
+            .context("Failed to send attributes query for screenshot")?
+            .reply()
+            .context("Failed to get window attributes for screenshot")?;
+        let layout = PixelLayout::for_visual(
+            ctx.conn.setup(),
+            ctx.screen,
+            geometry.depth,
+            attributes.visual,
+        )
+        .with_context(|| {
+            format!(
+                "Unsupported pixel format for screenshot (depth {}, visual {:#x})",
+                geometry.depth, attributes.visual
+            )
+        })?;
+
+        let image = ctx
+            .conn
+            .get_image(
+                ImageFormat::Z_PIXMAP,
+                window,
+                0,
+                0,
+                geometry.width,
+                geometry.height,
+                !0,
+            )
+            .context("Failed to send GetImage request")?
+            .reply()
+            .context("Failed to read window contents (is the client minimized?)")?;
+        Ok(Self {
+            data: image.data,
+            layout,
+            width: geometry.width,
+            height: geometry.height,
+        })
+    }
+
+    /// The image as a PNG file, scaled to `size` if given
+    pub fn encode_png(&self, size: Option<Dimensions>) -> Result<Vec<u8>> {
+        let (rgb, width, height) = match size {
+            Some(size) if size.width > 0 && size.height > 0 => (
+                scale(
+                    &self.data,
+                    &self.layout,
+                    self.width,
+                    self.height,
+                    &RGB24,
+                    size.width,
+                    size.height,
+                ),
+                size.width,
+                size.height,
+            ),
+            _ => (
+                to_rgb(&self.data, &self.layout, self.width, self.height),
+                self.width,
+                self.height,
+            ),
+        };
+
+        let mut png = Vec::new();
+        let mut encoder = png::Encoder::new(&mut png, u32::from(width), u32::from(height));
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.set_compression(png::Compression::Fast);
+        encoder
+            .write_header()
+            .and_then(|mut writer| writer.write_image_data(&rgb))
+            .context("Failed to encode screenshot")?;
+        Ok(png)
+    }
+}
+
+/// The whole X screen as packed RGB, scaled down to a preview
+#[derive(Debug)]
+pub struct ScreenCapture {
+    /// Size of the X screen, in screen pixels
+    pub screen: Dimensions,
+    /// Size of `rgb`
+    pub size: Dimensions,
+    pub rgb: Vec<u8>,
+}
+
+/// Capture the whole screen at most `max_width` pixels wide, for the Manager's calibration
+/// view. Opens a connection of its own.
+pub fn capture_screen(max_width: u16) -> Result<ScreenCapture> {
+    let (conn, screen_num) = x11rb::connect(None).context("Failed to connect to X11")?;
+    let screen = &conn.setup().roots[screen_num];
+    let layout =
+        PixelLayout::for_visual(conn.setup(), screen, screen.root_depth, screen.root_visual)
+            .context("Unsupported pixel format for screen capture")?;
+    let (width, height) = (screen.width_in_pixels, screen.height_in_pixels);
+
+    let image = conn
+        .get_image(ImageFormat::Z_PIXMAP, screen.root, 0, 0, width, height, !0)
+        .context("Failed to send GetImage request")?
+        .reply()
+        .context("Failed to read the screen contents")?;
+    let scaled_width = width.min(max_width).max(1);
+    let scaled_height = (u32::from(height) * u32::from(scaled_width) / u32::from(width.max(1)))
+        .clamp(1, u32::from(u16::MAX)) as u16;
+    Ok(ScreenCapture {
+        screen: Dimensions::new(width, height),
+        size: Dimensions::new(scaled_width, scaled_height),
+        rgb: scale(
+            &image.data,
+            &layout,
+            width,
+            height,
+            &RGB24,
+            scaled_width,
+            scaled_height,
+        ),
+    })
+}
+
+/// Configured screenshot directory, or the default one when `dir` is empty
+pub fn directory(dir: &str) -> PathBuf {
+    if !dir.trim().is_empty() {
+        return expand_path(dir.trim());
+    }
+    match dirs::picture_dir() {
+        Some(pictures) => pictures.join(screenshot::SUBDIR),
+        None => {
+            let mut path = Config::path();
+            path.pop(); // Remove filename
+            path.push(screenshot::SUBDIR);
+            path
+        }
+    }
+}
+
+/// `<name>-<timestamp>.png`, with characters unsafe in file names replaced
+fn file_name(name: &str, time: chrono::DateTime<chrono::Local>) -> String {
+    format!(
+        "{}-{}.{}",
+        safe_name(name),
+        time.format("%Y%m%d-%H%M%S"),
+        screenshot::EXTENSION
+    )
+}
+
+/// `name` usable as a file or directory name
+pub(super) fn safe_name(name: &str) -> String {
+    let name = if name.is_empty() {
+        eve::LOGGED_OUT_DISPLAY_NAME
+    } else {
+        name
+    };
+    name.chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_file_name_is_safe_and_timestamped() {
+        let time = chrono::Local.with_ymd_and_hms(2026, 3, 4, 5, 6, 7).unwrap();
+        assert_eq!(
+            file_name("Ms. Pilot/Alt", time),
+            "Ms__Pilot_Alt-20260304-050607.png"
+        );
+        assert_eq!(file_name("", time), "login_screen-20260304-050607.png");
+    }
+
+    #[test]
+    fn test_directory_uses_configured_path() {
+        assert_eq!(directory(" /tmp/shots "), PathBuf::from("/tmp/shots"));
+        assert!(directory("").ends_with(screenshot::SUBDIR));
+    }
+}
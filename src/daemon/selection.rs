@@ -0,0 +1,124 @@
+//! Thumbnail selection for bulk operations
+//!
+//! Ctrl+left-click toggles a thumbnail's `selected` flag, and Ctrl+left-dragging over empty
+//! desktop draws a rubber band that adds every thumbnail it touches. A plain left-click on the
+//! desktop clears the selection. Right-dragging a selected thumbnail moves all selected ones.
+
+use anyhow::{Context, Result};
+use x11rb::connection::Connection;
+use x11rb::protocol::xproto::{ConfigureWindowAux, ConnectionExt, StackMode, Window};
+
+use crate::common::constants::selection;
+use crate::common::types::Position;
+use crate::x11::AppContext;
+
+use super::snapping::Rect;
+
+#[derive(Debug, Default)]
+pub struct RubberBand {
+    /// Where the band drag started, while one is in progress
+    start: Option<Position>,
+    /// Top, bottom, left and right edges, created on first use
+    edges: Vec<Window>,
+}
+
+impl RubberBand {
+    pub fn is_active(&self) -> bool {
+        self.start.is_some()
+    }
+
+    pub fn begin(&mut self, position: Position) {
+        self.start = Some(position);
+    }
+
+    /// Stretch the band to `position`
+    pub fn update(&mut self, ctx: &AppContext, position: Position) -> Result<()> {
+        let Some(start) = self.start else {
+            return Ok(());
+        };
+        if self.edges.is_empty() {
+            for _ in 0..4 {
+                self.edges.push(crate::x11::create_overlay_window(
+                    ctx.conn,
+                    ctx.screen,
+                    selection::BAND_COLOR,
+                    "rubber band",
+                )?);
+            }
+        }
+        let rect = band_rect(start, position);
+        let thickness = selection::BAND_THICKNESS;
+        let edges = [
+            (rect.x, rect.y, rect.width, thickness),
+            (
+                rect.x,
+                rect.bottom().saturating_sub(thickness as i16),
+                rect.width,
+                thickness,
+            ),
+            (rect.x, rect.y, thickness, rect.height),
+            (
+                rect.right().saturating_sub(thickness as i16),
+                rect.y,
+                thickness,
+                rect.height,
+            ),
+        ];
+        for (&window, (x, y, width, height)) in self.edges.iter().zip(edges) {
+            ctx.conn
+                .configure_window(
+                    window,
+                    &ConfigureWindowAux::new()
+                        .x(i32::from(x))
+                        .y(i32::from(y))
+                        .width(u32::from(width.max(1)))
+                        .height(u32::from(height.max(1)))
+                        .stack_mode(StackMode::ABOVE),
+                )
+                .context("Failed to place rubber band")?;
+            ctx.conn
+                .map_window(window)
+                .context("Failed to map rubber band")?;
+        }
+        ctx.conn.flush().context("Failed to flush rubber band")?;
+        Ok(())
+    }
+
+    /// End the band drag at `position`, returning the area it covered
+    pub fn finish(&mut self, ctx: &AppContext, position: Position) -> Result<Option<Rect>> {
+        let Some(start) = self.start.take() else {
+            return Ok(None);
+        };
+        for &window in &self.edges {
+            ctx.conn
+                .unmap_window(window)
+                .context("Failed to unmap rubber band")?;
+        }
+        ctx.conn.flush().context("Failed to flush rubber band")?;
+        Ok(Some(band_rect(start, position)))
+    }
+}
+
+/// Rectangle spanned by two corners, in any order
+fn band_rect(a: Position, b: Position) -> Rect {
+    Rect {
+        x: a.x.min(b.x),
+        y: a.y.min(b.y),
+        width: a.x.abs_diff(b.x),
+        height: a.y.abs_diff(b.y),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_band_rect_from_any_corner() {
+        let rect = band_rect(Position::new(300, 50), Position::new(100, 250));
+        assert_eq!(
+            (rect.x, rect.y, rect.width, rect.height),
+            (100, 50, 200, 200)
+        );
+    }
+}
@@ -3,11 +3,26 @@
 //! Tracks window positions within the current X11 session. Used for preserving
 //! thumbnail positions when characters log out and for position inheritance.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 use tracing::{debug, info};
 use x11rb::protocol::xproto::Window;
 
+use super::focus_stats::FocusStats;
+use super::latency::LatencyTracker;
+use crate::common::constants::positioning::UNDO_HISTORY_LEN;
 use crate::common::types::{CharacterSettings, Position};
+use crate::config::profile::CycleGroup;
+
+/// A completed thumbnail drag that can be reverted
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoveRecord {
+    /// EVE/source window the thumbnail belongs to
+    pub window: Window,
+    pub character_name: String,
+    pub is_custom: bool,
+    /// Position before the drag started
+    pub previous: Position,
+}
 
 /// Runtime state for position tracking
 /// Window positions are session-only (not persisted to disk)
@@ -26,6 +41,56 @@ pub struct SessionState {
     /// Deadline for hiding thumbnails after focus loss (hysteresis)
     /// Prevents flickering when cycling through clients
     pub focus_loss_deadline: Option<std::time::Instant>,
+
+    /// In-memory thumbnail positions differ from disk (only tracked when auto-save is off)
+    pub positions_dirty: bool,
+
+    /// Recent thumbnail drags, most recent last (session-only)
+    pub move_history: VecDeque<MoveRecord>,
+
+    /// Window ID → (rule alias, instance number) for custom sources matching a rule
+    /// more than once. Numbers are stable while the window exists and reused after.
+    pub custom_instances: HashMap<Window, (String, usize)>,
+
+    /// Window ID → launch slot of each EVE client, the lowest number free when it appeared.
+    /// Account labels are saved by slot, since window IDs change with every launch.
+    pub client_slots: HashMap<Window, usize>,
+
+    /// Characters whose thumbnails were hidden from the tray menu (session-only)
+    pub hidden_characters: HashSet<String>,
+
+    /// Cycle groups whose thumbnails were hidden by hotkey (session-only)
+    pub hidden_groups: HashSet<String>,
+
+    /// Source window of the thumbnail being dragged with the mouse
+    pub pointer_drag: Option<Window>,
+
+    /// The pointer moved during that drag; the thumbnail follows once the event batch is done
+    pub drag_moved: bool,
+
+    /// Low-power mode is in effect: damage is drawn on the frame tick instead of right away
+    pub low_power: bool,
+
+    /// Source window of the thumbnail under the pointer
+    pub hovered_thumbnail: Option<Window>,
+
+    /// Source window of the thumbnail waiting to swap positions with the next one picked
+    pub swap_source: Option<Window>,
+
+    /// Focus time per character since the daemon started
+    pub focus_stats: FocusStats,
+
+    /// Recent hotkey switch latencies
+    pub hotkey_latency: LatencyTracker,
+
+    /// A do-not-disturb window class has focus, so all thumbnails are hidden
+    pub do_not_disturb: bool,
+
+    /// Characters shown only with a cycle group that is not the active one
+    pub outside_active_group: HashSet<String>,
+
+    /// A Manager is reading our status channel and saves positions on our behalf
+    pub manager_attached: bool,
 }
 
 impl SessionState {
@@ -33,6 +98,99 @@ impl SessionState {
         Self::default()
     }
 
+    /// Remember a completed drag so it can be undone, dropping the oldest beyond the limit
+    pub fn record_move(&mut self, record: MoveRecord) {
+        if self.move_history.len() >= UNDO_HISTORY_LEN {
+            self.move_history.pop_front();
+        }
+        self.move_history.push_back(record);
+    }
+
+    /// Take the most recent drag off the undo stack
+    pub fn pop_move(&mut self) -> Option<MoveRecord> {
+        self.move_history.pop_back()
+    }
+
+    /// Hide or show a character's thumbnail. Returns true if the state changed.
+    pub fn set_character_hidden(&mut self, name: &str, hidden: bool) -> bool {
+        if hidden {
+            self.hidden_characters.insert(name.to_string())
+        } else {
+            self.hidden_characters.remove(name)
+        }
+    }
+
+    /// Whether a character's thumbnail was hidden from the tray menu
+    pub fn is_character_hidden(&self, name: &str) -> bool {
+        !name.is_empty() && self.hidden_characters.contains(name)
+    }
+
+    /// Hide characters tied to a cycle group other than `active` (`None`: none was cycled
+    /// yet, so all are shown). Returns true if the hidden set changed.
+    pub fn set_active_group(
+        &mut self,
+        active: Option<&str>,
+        characters: &HashMap<String, CharacterSettings>,
+    ) -> bool {
+        let outside: HashSet<String> = characters
+            .iter()
+            .filter(|(_, settings)| {
+                active.is_some_and(|active| {
+                    settings
+                        .show_only_in_group
+                        .as_deref()
+                        .is_some_and(|group| group != active)
+                })
+            })
+            .map(|(name, _)| name.clone())
+            .collect();
+        if outside == self.outside_active_group {
+            return false;
+        }
+        self.outside_active_group = outside;
+        true
+    }
+
+    /// Flip a cycle group between hidden and shown. Returns true if it is now hidden.
+    pub fn toggle_group_hidden(&mut self, group: &str) -> bool {
+        if self.hidden_groups.remove(group) {
+            false
+        } else {
+            self.hidden_groups.insert(group.to_string());
+            true
+        }
+    }
+
+    /// Whether a thumbnail is hidden from the tray, by a hidden cycle group it belongs to,
+    /// while a do-not-disturb window has focus or because its group is not the active one
+    pub fn is_thumbnail_hidden(&self, name: &str, groups: &[CycleGroup]) -> bool {
+        self.do_not_disturb
+            || self.is_character_hidden(name)
+            || self.outside_active_group.contains(name)
+            || (!name.is_empty()
+                && groups
+                    .iter()
+                    .any(|g| self.hidden_groups.contains(&g.name) && g.contains(name)))
+    }
+
+    /// Wait for a thumbnail to swap positions with the one of `window`, or cancel the swap
+    /// already waiting. Returns true if a swap is now waiting.
+    pub fn toggle_swap(&mut self, window: Window) -> bool {
+        if self.swap_source.take().is_some() {
+            return false;
+        }
+        self.swap_source = Some(window);
+        true
+    }
+
+    /// Record a thumbnail position change.
+    /// With auto-save enabled the Manager persists every change, so nothing is tracked.
+    pub fn mark_positions_dirty(&mut self, auto_save: bool) {
+        if !auto_save {
+            self.positions_dirty = true;
+        }
+    }
+
     /// Get initial position for a thumbnail
     /// Priority: character position (from persistent state) > window position (if enabled) > None (use EVE window + offset)
     /// Window position only used for logged-out windows or if preserve_thumbnail_position_on_swap is enabled
@@ -91,6 +249,50 @@ impl SessionState {
     pub fn remove_window(&mut self, window: Window) {
         self.window_positions.remove(&window);
         self.window_last_character.remove(&window);
+        self.custom_instances.remove(&window);
+        self.client_slots.remove(&window);
+        if self.hovered_thumbnail == Some(window) {
+            self.hovered_thumbnail = None;
+        }
+        if self.swap_source == Some(window) {
+            self.swap_source = None;
+        }
+    }
+
+    /// Instance number of `window` among the windows matching the rule `alias`.
+    /// Keeps the window's current number, otherwise takes the lowest free one.
+    pub fn custom_instance(&mut self, window: Window, alias: &str) -> usize {
+        if let Some((existing, index)) = self.custom_instances.get(&window)
+            && existing == alias
+        {
+            return *index;
+        }
+
+        let taken: Vec<usize> = self
+            .custom_instances
+            .iter()
+            .filter(|&(&w, (a, _))| w != window && a == alias)
+            .map(|(_, &(_, index))| index)
+            .collect();
+        let index = (1..).find(|i| !taken.contains(i)).unwrap_or(1);
+        debug!(window = window, alias = %alias, index = index, "Assigned custom source instance");
+        self.custom_instances
+            .insert(window, (alias.to_string(), index));
+        index
+    }
+
+    /// Launch slot of the EVE client `window`. Keeps the window's slot, otherwise takes the
+    /// lowest free one.
+    pub fn client_slot(&mut self, window: Window) -> usize {
+        if let Some(&slot) = self.client_slots.get(&window) {
+            return slot;
+        }
+        let slot = (1..)
+            .find(|slot| !self.client_slots.values().any(|taken| taken == slot))
+            .unwrap_or(1);
+        debug!(window = window, slot = slot, "Assigned client launch slot");
+        self.client_slots.insert(window, slot);
+        slot
     }
 
     /// Update last known character for a window (called on character name change)
@@ -132,6 +334,22 @@ mod tests {
             window_positions: HashMap::from([(456, Position::new(300, 400))]),
             window_last_character: HashMap::new(),
             focus_loss_deadline: None,
+            positions_dirty: false,
+            move_history: VecDeque::new(),
+            custom_instances: HashMap::new(),
+            client_slots: HashMap::new(),
+            hidden_characters: HashSet::new(),
+            hidden_groups: HashSet::new(),
+            low_power: false,
+            hovered_thumbnail: None,
+            focus_stats: FocusStats::default(),
+            hotkey_latency: LatencyTracker::default(),
+            do_not_disturb: false,
+            outside_active_group: HashSet::new(),
+            manager_attached: false,
+            pointer_drag: None,
+            drag_moved: false,
+            swap_source: None,
         };
         let char_positions = HashMap::new();
 
@@ -146,6 +364,22 @@ mod tests {
             window_positions: HashMap::from([(789, Position::new(500, 600))]),
             window_last_character: HashMap::new(),
             focus_loss_deadline: None,
+            positions_dirty: false,
+            move_history: VecDeque::new(),
+            custom_instances: HashMap::new(),
+            client_slots: HashMap::new(),
+            hidden_characters: HashSet::new(),
+            hidden_groups: HashSet::new(),
+            low_power: false,
+            hovered_thumbnail: None,
+            focus_stats: FocusStats::default(),
+            hotkey_latency: LatencyTracker::default(),
+            do_not_disturb: false,
+            outside_active_group: HashSet::new(),
+            manager_attached: false,
+            pointer_drag: None,
+            drag_moved: false,
+            swap_source: None,
         };
         let char_positions = HashMap::new();
 
@@ -160,6 +394,22 @@ mod tests {
             window_positions: HashMap::new(),
             window_last_character: HashMap::new(),
             focus_loss_deadline: None,
+            positions_dirty: false,
+            move_history: VecDeque::new(),
+            custom_instances: HashMap::new(),
+            client_slots: HashMap::new(),
+            hidden_characters: HashSet::new(),
+            hidden_groups: HashSet::new(),
+            low_power: false,
+            hovered_thumbnail: None,
+            focus_stats: FocusStats::default(),
+            hotkey_latency: LatencyTracker::default(),
+            do_not_disturb: false,
+            outside_active_group: HashSet::new(),
+            manager_attached: false,
+            pointer_drag: None,
+            drag_moved: false,
+            swap_source: None,
         };
         let char_positions = HashMap::new();
 
@@ -174,6 +424,22 @@ mod tests {
             window_positions: HashMap::from([(111, Position::new(700, 800))]),
             window_last_character: HashMap::new(),
             focus_loss_deadline: None,
+            positions_dirty: false,
+            move_history: VecDeque::new(),
+            custom_instances: HashMap::new(),
+            client_slots: HashMap::new(),
+            hidden_characters: HashSet::new(),
+            hidden_groups: HashSet::new(),
+            low_power: false,
+            hovered_thumbnail: None,
+            focus_stats: FocusStats::default(),
+            hotkey_latency: LatencyTracker::default(),
+            do_not_disturb: false,
+            outside_active_group: HashSet::new(),
+            manager_attached: false,
+            pointer_drag: None,
+            drag_moved: false,
+            swap_source: None,
         };
         let char_positions = HashMap::new();
 
@@ -279,4 +545,130 @@ mod tests {
             Some(&Position::new(100, 200))
         );
     }
+
+    #[test]
+    fn test_mark_positions_dirty_only_without_auto_save() {
+        let mut state = SessionState::new();
+
+        state.mark_positions_dirty(true);
+        assert!(!state.positions_dirty);
+
+        state.mark_positions_dirty(false);
+        assert!(state.positions_dirty);
+    }
+
+    #[test]
+    fn test_custom_instances_are_stable_and_reused() {
+        let mut state = SessionState::new();
+
+        assert_eq!(state.custom_instance(10, "Discord"), 1);
+        assert_eq!(state.custom_instance(11, "Discord"), 2);
+        assert_eq!(state.custom_instance(12, "Discord"), 3);
+        assert_eq!(state.custom_instance(20, "Browser"), 1);
+        // Re-identifying a window keeps its number
+        assert_eq!(state.custom_instance(11, "Discord"), 2);
+
+        // Closing the second window frees its number for the next one
+        state.remove_window(11);
+        assert_eq!(state.custom_instance(13, "Discord"), 2);
+        assert_eq!(state.custom_instance(12, "Discord"), 3);
+    }
+
+    #[test]
+    fn test_client_slots_reuse_the_lowest_free_slot() {
+        let mut state = SessionState::new();
+        assert_eq!(state.client_slot(10), 1);
+        assert_eq!(state.client_slot(11), 2);
+        assert_eq!(state.client_slot(10), 1);
+
+        state.remove_window(10);
+        assert_eq!(state.client_slot(12), 1);
+        assert_eq!(state.client_slot(13), 3);
+    }
+
+    #[test]
+    fn test_move_history_is_bounded_lifo() {
+        let mut state = SessionState::new();
+        for i in 0..(UNDO_HISTORY_LEN + 2) {
+            state.record_move(MoveRecord {
+                window: i as Window,
+                character_name: format!("Char{}", i),
+                is_custom: false,
+                previous: Position::new(i as i16, 0),
+            });
+        }
+
+        assert_eq!(state.move_history.len(), UNDO_HISTORY_LEN);
+        let last = state.pop_move().unwrap();
+        assert_eq!(last.window, (UNDO_HISTORY_LEN + 1) as Window);
+        // Oldest entries were dropped
+        assert_eq!(state.move_history.front().unwrap().window, 2);
+    }
+
+    #[test]
+    fn test_toggle_swap() {
+        let mut state = SessionState::new();
+        assert!(state.toggle_swap(10));
+        assert_eq!(state.swap_source, Some(10));
+        // Pressed again, the swap is cancelled whichever client has focus
+        assert!(!state.toggle_swap(11));
+        assert_eq!(state.swap_source, None);
+
+        assert!(state.toggle_swap(10));
+        state.remove_window(10);
+        assert_eq!(state.swap_source, None);
+    }
+
+    #[test]
+    fn test_character_hidden_toggle() {
+        let mut state = SessionState::new();
+        assert!(state.set_character_hidden("Alice", true));
+        assert!(!state.set_character_hidden("Alice", true));
+        assert!(state.is_character_hidden("Alice"));
+        assert!(!state.is_character_hidden("Bob"));
+
+        assert!(state.set_character_hidden("Alice", false));
+        assert!(!state.is_character_hidden("Alice"));
+    }
+
+    #[test]
+    fn test_group_hidden_toggle() {
+        let mut group = CycleGroup::default_group();
+        group.name = "Industry".to_string();
+        group
+            .cycle_list
+            .push(crate::config::profile::CycleSlot::Eve("Miner".to_string()));
+        let groups = [group];
+
+        let mut state = SessionState::new();
+        assert!(state.toggle_group_hidden("Industry"));
+        assert!(state.is_thumbnail_hidden("Miner", &groups));
+        assert!(!state.is_thumbnail_hidden("Pilot", &groups));
+
+        assert!(!state.toggle_group_hidden("Industry"));
+        assert!(!state.is_thumbnail_hidden("Miner", &groups));
+    }
+
+    #[test]
+    fn test_set_active_group() {
+        let mut pvp = CharacterSettings::new(0, 0, 100, 50);
+        pvp.show_only_in_group = Some("PvP".to_string());
+        let characters = HashMap::from([
+            ("Brawler".to_string(), pvp),
+            ("Miner".to_string(), CharacterSettings::new(0, 0, 100, 50)),
+        ]);
+
+        let mut state = SessionState::new();
+        // Nothing cycled yet: everyone is shown
+        assert!(!state.set_active_group(None, &characters));
+        assert!(!state.is_thumbnail_hidden("Brawler", &[]));
+
+        assert!(state.set_active_group(Some("Industry"), &characters));
+        assert!(state.is_thumbnail_hidden("Brawler", &[]));
+        assert!(!state.is_thumbnail_hidden("Miner", &[]));
+
+        assert!(state.set_active_group(Some("PvP"), &characters));
+        assert!(!state.is_thumbnail_hidden("Brawler", &[]));
+        assert!(!state.set_active_group(Some("PvP"), &characters));
+    }
 }
@@ -0,0 +1,80 @@
+//! Alignment guides shown while dragging a thumbnail
+//!
+//! Each guide is a thin override-redirect window spanning the screen. Windows are created as
+//! needed, reused for later drags and unmapped when the drag ends.
+
+use anyhow::{Context, Result};
+use x11rb::connection::Connection;
+use x11rb::protocol::xproto::{ConfigureWindowAux, ConnectionExt, StackMode, Window};
+
+use crate::common::constants::snap_guides;
+use crate::x11::AppContext;
+
+use super::snapping::Guide;
+
+#[derive(Debug, Default)]
+pub struct SnapGuides {
+    /// One window per guide shown so far, kept for the connection's lifetime
+    windows: Vec<Window>,
+    /// Number of windows currently mapped
+    shown: usize,
+}
+
+impl SnapGuides {
+    /// Show exactly `guides`, hiding any others
+    pub fn show(&mut self, ctx: &AppContext, guides: &[Guide]) -> Result<()> {
+        while self.windows.len() < guides.len() {
+            self.windows.push(crate::x11::create_overlay_window(
+                ctx.conn,
+                ctx.screen,
+                snap_guides::COLOR,
+                "snap guide",
+            )?);
+        }
+        let screen_width = ctx.screen.width_in_pixels;
+        let screen_height = ctx.screen.height_in_pixels;
+        let thickness = snap_guides::THICKNESS;
+
+        for (guide, &window) in guides.iter().zip(&self.windows) {
+            let (x, y, width, height) = match *guide {
+                Guide::Vertical(x) => (clamp(x, screen_width), 0, thickness, screen_height),
+                Guide::Horizontal(y) => (0, clamp(y, screen_height), screen_width, thickness),
+            };
+            ctx.conn
+                .configure_window(
+                    window,
+                    &ConfigureWindowAux::new()
+                        .x(i32::from(x))
+                        .y(i32::from(y))
+                        .width(u32::from(width))
+                        .height(u32::from(height))
+                        .stack_mode(StackMode::ABOVE),
+                )
+                .context("Failed to place snap guide")?;
+            ctx.conn
+                .map_window(window)
+                .context("Failed to map snap guide")?;
+        }
+        for &window in &self.windows[guides.len()..self.shown.max(guides.len())] {
+            ctx.conn
+                .unmap_window(window)
+                .context("Failed to unmap snap guide")?;
+        }
+        self.shown = guides.len();
+        ctx.conn.flush().context("Failed to flush snap guides")?;
+        Ok(())
+    }
+
+    pub fn hide(&mut self, ctx: &AppContext) -> Result<()> {
+        if self.shown == 0 {
+            return Ok(());
+        }
+        self.show(ctx, &[])
+    }
+}
+
+/// Keep a guide at `line` on a screen `extent` pixels long
+fn clamp(line: i16, extent: u16) -> i16 {
+    let last = extent.saturating_sub(snap_guides::THICKNESS) as i16;
+    line.clamp(0, last.max(0))
+}
@@ -1,7 +1,9 @@
 //! Thumbnail edge snapping
 //!
 //! Calculates snap positions when dragging thumbnails near other thumbnails.
-//! Supports edge-to-edge and alignment snapping within a configurable threshold.
+//! Supports edge-to-edge and alignment snapping within a configurable threshold, and
+//! optionally snapping at a fixed gap from neighbouring thumbnails.
+//! `guides` lists the lines a dragged thumbnail is close to, for drawing while dragging.
 
 use crate::common::types::Position;
 
@@ -31,6 +33,30 @@ impl Rect {
         // Use saturating_add to prevent overflow when y + height > i16::MAX
         self.y.saturating_add(self.height as i16)
     }
+
+    pub fn center_x(&self) -> i16 {
+        self.x.saturating_add((self.width / 2) as i16)
+    }
+
+    pub fn center_y(&self) -> i16 {
+        self.y.saturating_add((self.height / 2) as i16)
+    }
+
+    /// Whether `other` touches or overlaps this rectangle grown by `tolerance` pixels
+    pub fn is_near(&self, other: &Rect, tolerance: u16) -> bool {
+        let tolerance = tolerance as i16;
+        self.left().saturating_sub(tolerance) <= other.right()
+            && other.left() <= self.right().saturating_add(tolerance)
+            && self.top().saturating_sub(tolerance) <= other.bottom()
+            && other.top() <= self.bottom().saturating_add(tolerance)
+    }
+}
+
+/// Screen-spanning alignment line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Guide {
+    Vertical(i16),
+    Horizontal(i16),
 }
 
 #[derive(Debug)]
@@ -41,7 +67,15 @@ struct SnapCandidate {
 
 /// Find the best snap position for a dragged thumbnail
 /// Returns position if snapping should occur, None otherwise
-pub fn find_snap_position(dragged: Rect, others: &[Rect], threshold: u16) -> Option<Position> {
+///
+/// A non-zero `gap` also snaps edges exactly `gap` pixels apart from the facing edges of
+/// `others`.
+pub fn find_snap_position(
+    dragged: Rect,
+    others: &[Rect],
+    threshold: u16,
+    gap: u16,
+) -> Option<Position> {
     if threshold == 0 {
         return None; // Snapping disabled
     }
@@ -49,12 +83,27 @@ pub fn find_snap_position(dragged: Rect, others: &[Rect], threshold: u16) -> Opt
     let mut best_x: Option<SnapCandidate> = None;
     let mut best_y: Option<SnapCandidate> = None;
     let threshold = threshold as i16;
+    let gap = gap as i16;
 
     for other in others {
         // Horizontal snapping (X-axis)
         // Edge-to-edge snapping (always allowed)
         check_snap(&mut best_x, dragged.left(), other.right(), threshold);
         check_snap(&mut best_x, dragged.right(), other.left(), threshold);
+        if gap > 0 {
+            check_snap(
+                &mut best_x,
+                dragged.left(),
+                other.right().saturating_add(gap),
+                threshold,
+            );
+            check_snap(
+                &mut best_x,
+                dragged.right(),
+                other.left().saturating_sub(gap),
+                threshold,
+            );
+        }
 
         // Alignment snapping (only if windows overlap or are close on Y-axis)
         // Check if there's vertical overlap or proximity
@@ -71,6 +120,20 @@ pub fn find_snap_position(dragged: Rect, others: &[Rect], threshold: u16) -> Opt
         // Edge-to-edge snapping (always allowed)
         check_snap(&mut best_y, dragged.top(), other.bottom(), threshold);
         check_snap(&mut best_y, dragged.bottom(), other.top(), threshold);
+        if gap > 0 {
+            check_snap(
+                &mut best_y,
+                dragged.top(),
+                other.bottom().saturating_add(gap),
+                threshold,
+            );
+            check_snap(
+                &mut best_y,
+                dragged.bottom(),
+                other.top().saturating_sub(gap),
+                threshold,
+            );
+        }
 
         // Alignment snapping (only if windows overlap or are close on X-axis)
         // Check if there's horizontal overlap or proximity
@@ -96,6 +159,79 @@ pub fn find_snap_position(dragged: Rect, others: &[Rect], threshold: u16) -> Opt
     }
 }
 
+/// Edges and centers of `others` and `screen` (plus the `gap` lines beside `others`) within
+/// `threshold` of the dragged rectangle's own edges or center
+pub fn guides(
+    dragged: Rect,
+    others: &[Rect],
+    screen: Rect,
+    threshold: u16,
+    gap: u16,
+) -> Vec<Guide> {
+    if threshold == 0 {
+        return Vec::new(); // Snapping disabled
+    }
+    let threshold = threshold as i16;
+    let gap = gap as i16;
+    let near = |lines: [i16; 3], line: i16| lines.iter().any(|l| (l - line).abs() <= threshold);
+    let dragged_x = [dragged.left(), dragged.center_x(), dragged.right()];
+    let dragged_y = [dragged.top(), dragged.center_y(), dragged.bottom()];
+
+    let mut guides = Vec::new();
+    for rect in others.iter().chain(std::iter::once(&screen)) {
+        for x in [rect.left(), rect.center_x(), rect.right()] {
+            if near(dragged_x, x) {
+                guides.push(Guide::Vertical(x));
+            }
+        }
+        for y in [rect.top(), rect.center_y(), rect.bottom()] {
+            if near(dragged_y, y) {
+                guides.push(Guide::Horizontal(y));
+            }
+        }
+    }
+    if gap > 0 {
+        for rect in others {
+            for x in [
+                rect.left().saturating_sub(gap),
+                rect.right().saturating_add(gap),
+            ] {
+                if near(dragged_x, x) {
+                    guides.push(Guide::Vertical(x));
+                }
+            }
+            for y in [
+                rect.top().saturating_sub(gap),
+                rect.bottom().saturating_add(gap),
+            ] {
+                if near(dragged_y, y) {
+                    guides.push(Guide::Horizontal(y));
+                }
+            }
+        }
+    }
+    guides.sort_unstable();
+    guides.dedup();
+    guides
+}
+
+/// Indices of `rects` linked to `rects[start]` through rectangles at most `tolerance`
+/// pixels apart, in ascending order
+pub fn cluster(rects: &[Rect], start: usize, tolerance: u16) -> Vec<usize> {
+    let mut members = vec![start];
+    let mut queue = vec![start];
+    while let Some(current) = queue.pop() {
+        for (index, rect) in rects.iter().enumerate() {
+            if !members.contains(&index) && rects[current].is_near(rect, tolerance) {
+                members.push(index);
+                queue.push(index);
+            }
+        }
+    }
+    members.sort_unstable();
+    members
+}
+
 fn check_snap(best: &mut Option<SnapCandidate>, edge: i16, target: i16, threshold: i16) {
     let distance = (edge - target).abs();
     if distance <= threshold {
@@ -132,7 +268,7 @@ mod tests {
             width: 50,
             height: 50,
         };
-        let result = find_snap_position(dragged, &[other], 0);
+        let result = find_snap_position(dragged, &[other], 0, 0);
         assert_eq!(result, None);
     }
 
@@ -151,7 +287,7 @@ mod tests {
             height: 50,
         };
         // Dragged right edge at 150, other left at 160 - distance 10, within threshold 15
-        let result = find_snap_position(dragged, &[other], 15);
+        let result = find_snap_position(dragged, &[other], 15, 0);
         assert_eq!(result, Some(Position::new(110, 100))); // Snapped: dragged.x moves by 10
     }
 
@@ -170,7 +306,7 @@ mod tests {
             height: 50,
         };
         // Dragged left edge at 100, other right at 90 - distance 10
-        let result = find_snap_position(dragged, &[other], 15);
+        let result = find_snap_position(dragged, &[other], 15, 0);
         assert_eq!(result, Some(Position::new(90, 100))); // Snapped: dragged.x moves to 90
     }
 
@@ -189,7 +325,7 @@ mod tests {
             width: 50,
             height: 50,
         };
-        let result = find_snap_position(dragged, &[other], 15);
+        let result = find_snap_position(dragged, &[other], 15, 0);
         assert_eq!(result, Some(Position::new(100, 100))); // X aligned to 100
     }
 
@@ -209,7 +345,7 @@ mod tests {
             height: 50,
         };
         // Dragged right: 150, other right: 145, distance 5
-        let result = find_snap_position(dragged, &[other], 15);
+        let result = find_snap_position(dragged, &[other], 15, 0);
         assert_eq!(result, Some(Position::new(95, 100))); // X moves by -5
     }
 
@@ -227,7 +363,7 @@ mod tests {
             width: 50,
             height: 50,
         };
-        let result = find_snap_position(dragged, &[other], 15);
+        let result = find_snap_position(dragged, &[other], 15, 0);
         assert_eq!(result, Some(Position::new(100, 110))); // Y snapped
     }
 
@@ -245,7 +381,7 @@ mod tests {
             width: 50,
             height: 50,
         };
-        let result = find_snap_position(dragged, &[other], 15);
+        let result = find_snap_position(dragged, &[other], 15, 0);
         assert_eq!(result, Some(Position::new(100, 90))); // Y snapped
     }
 
@@ -264,7 +400,7 @@ mod tests {
             width: 50,
             height: 50,
         };
-        let result = find_snap_position(dragged, &[other], 15);
+        let result = find_snap_position(dragged, &[other], 15, 0);
         assert_eq!(result, Some(Position::new(100, 100))); // Y aligned
     }
 
@@ -282,7 +418,7 @@ mod tests {
             width: 50,
             height: 50,
         };
-        let result = find_snap_position(dragged, &[other], 15);
+        let result = find_snap_position(dragged, &[other], 15, 0);
         assert_eq!(result, Some(Position::new(100, 100))); // Both X and Y snap
     }
 
@@ -300,7 +436,7 @@ mod tests {
             width: 50,
             height: 50,
         };
-        let result = find_snap_position(dragged, &[other], 15);
+        let result = find_snap_position(dragged, &[other], 15, 0);
         assert_eq!(result, None); // Too far to snap
     }
 
@@ -324,10 +460,118 @@ mod tests {
             width: 50,
             height: 50,
         }; // 15 pixels away
-        let result = find_snap_position(dragged, &[close, far], 20);
+        let result = find_snap_position(dragged, &[close, far], 20, 0);
         assert_eq!(result, Some(Position::new(105, 100))); // Snaps to closer one
     }
 
+    #[test]
+    fn test_guides_near_edges_centers_and_screen() {
+        let screen = Rect {
+            x: 0,
+            y: 0,
+            width: 1000,
+            height: 800,
+        };
+        let other = Rect {
+            x: 100,
+            y: 100,
+            width: 200,
+            height: 100,
+        };
+        // Left edge 5px from the other's right edge, top flush with the screen
+        let dragged = Rect {
+            x: 305,
+            y: 0,
+            width: 100,
+            height: 50,
+        };
+        assert_eq!(
+            guides(dragged, &[other], screen, 15, 0),
+            vec![Guide::Vertical(300), Guide::Horizontal(0)]
+        );
+
+        // Centered on the screen
+        let dragged = Rect {
+            x: 450,
+            y: 600,
+            width: 100,
+            height: 50,
+        };
+        assert_eq!(
+            guides(dragged, &[other], screen, 15, 0),
+            vec![Guide::Vertical(500)]
+        );
+        assert!(guides(dragged, &[other], screen, 0, 0).is_empty());
+
+        // An 8px gap adds the line beside the other thumbnail
+        let dragged = Rect {
+            x: 310,
+            y: 600,
+            width: 100,
+            height: 50,
+        };
+        assert_eq!(
+            guides(dragged, &[other], screen, 5, 8),
+            vec![Guide::Vertical(308)]
+        );
+    }
+
+    #[test]
+    fn test_snap_at_gap() {
+        let other = Rect {
+            x: 100,
+            y: 100,
+            width: 50,
+            height: 50,
+        };
+        // Left edge at 162, 12px right of the other's right edge: snaps to an 8px gap
+        let dragged = Rect {
+            x: 162,
+            y: 100,
+            width: 50,
+            height: 50,
+        };
+        assert_eq!(
+            find_snap_position(dragged, &[other], 5, 8),
+            Some(Position::new(158, 100))
+        );
+        // Without a gap only edge contact counts, which is too far
+        assert_eq!(find_snap_position(dragged, &[other], 5, 0), None);
+
+        // Below the other one, 7px too far for contact but 1px from the gap
+        let dragged = Rect {
+            x: 100,
+            y: 157,
+            width: 50,
+            height: 50,
+        };
+        assert_eq!(
+            find_snap_position(dragged, &[other], 5, 8),
+            Some(Position::new(100, 158))
+        );
+    }
+
+    #[test]
+    fn test_cluster_follows_adjacent_chain() {
+        let rect = |x, y| Rect {
+            x,
+            y,
+            width: 100,
+            height: 50,
+        };
+        // A row of three with 8px gaps, one thumbnail below the last, and a distant one
+        let rects = [
+            rect(0, 0),
+            rect(108, 0),
+            rect(216, 0),
+            rect(216, 58),
+            rect(600, 600),
+        ];
+        assert_eq!(cluster(&rects, 0, 8), vec![0, 1, 2, 3]);
+        assert_eq!(cluster(&rects, 0, 0), vec![0]);
+        assert_eq!(cluster(&rects, 4, 8), vec![4]);
+    }
+
     #[test]
     fn test_multiple_windows_independent_axes() {
         let dragged = Rect {
@@ -350,7 +594,7 @@ mod tests {
             width: 50,
             height: 50,
         };
-        let result = find_snap_position(dragged, &[snap_x, snap_y], 15);
+        let result = find_snap_position(dragged, &[snap_x, snap_y], 15, 0);
         assert_eq!(result, Some(Position::new(100, 200))); // X from first, Y from second
     }
 }
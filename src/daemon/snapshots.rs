@@ -0,0 +1,177 @@
+//! Timed AFK snapshots
+//!
+//! Every configured interval each client is saved at its thumbnail's size to
+//! `snapshots/<character>/<timestamp>.png` under the config directory. The source window is
+//! read rather than the thumbnail, so snapshots keep coming while thumbnails are hidden;
+//! minimized clients are skipped. Only the newest snapshots per character are kept.
+//!
+//! Windows are read on the event loop; scaling, encoding and writing happen on a blocking
+//! task so a round of snapshots doesn't hold up X event handling.
+
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use anyhow::{Context, Result};
+use tracing::{debug, info};
+use x11rb::protocol::xproto::Window;
+
+use crate::common::constants::config::snapshots;
+use crate::common::types::Dimensions;
+use crate::config::AfkSnapshots;
+use crate::config::profile::Config;
+use crate::x11::AppContext;
+
+use super::screenshot::{WindowImage, safe_name};
+
+#[derive(Debug, Default)]
+pub struct SnapshotTimer {
+    last: Option<Instant>,
+}
+
+impl SnapshotTimer {
+    /// Whether a round of snapshots is due; the first one is taken right away
+    pub fn due(&mut self, settings: &AfkSnapshots, now: Instant) -> bool {
+        if !settings.enabled {
+            self.last = None;
+            return false;
+        }
+        if self
+            .last
+            .is_some_and(|last| now.duration_since(last) < settings.interval())
+        {
+            return false;
+        }
+        self.last = Some(now);
+        true
+    }
+}
+
+/// Directory holding one snapshot subdirectory per character
+pub fn snapshots_dir() -> PathBuf {
+    let mut path = Config::path();
+    path.pop(); // Remove filename
+    path.push(snapshots::SUBDIR);
+    path
+}
+
+/// A client read for a round of snapshots, not yet encoded
+pub struct Pending {
+    name: String,
+    image: WindowImage,
+    size: Dimensions,
+}
+
+/// Read `window` for a snapshot of `name` at `size`
+pub fn grab(ctx: &AppContext, window: Window, name: &str, size: Dimensions) -> Result<Pending> {
+    Ok(Pending {
+        name: name.to_string(),
+        image: WindowImage::grab(ctx, window)?,
+        size,
+    })
+}
+
+/// Encode and write a round of snapshots on a blocking task, keeping the newest `keep` per
+/// character
+pub fn spawn_save(pending: Vec<Pending>, keep: usize) {
+    tokio::task::spawn_blocking(move || {
+        let total = pending.len();
+        let saved = pending
+            .iter()
+            .filter(|snapshot| match save(snapshot, keep) {
+                Ok(_) => true,
+                Err(e) => {
+                    debug!(character = %snapshot.name, error = ?e, "Skipped AFK snapshot");
+                    false
+                }
+            })
+            .count();
+        info!(saved = saved, total = total, "Saved AFK snapshots");
+    });
+}
+
+/// Write `snapshot`, pruning the character's older ones
+fn save(snapshot: &Pending, keep: usize) -> Result<PathBuf> {
+    let Pending { name, image, size } = snapshot;
+    let png = image.encode_png(Some(*size))?;
+    let dir = snapshots_dir().join(safe_name(name));
+    std::fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create snapshot directory {:?}", dir))?;
+    let path = dir.join(format!(
+        "{}.{}",
+        chrono::Local::now().format("%Y%m%d-%H%M%S"),
+        snapshots::EXTENSION
+    ));
+    std::fs::write(&path, png).with_context(|| format!("Failed to write snapshot {:?}", path))?;
+    prune(&dir, keep);
+    debug!(character = %name, path = %path.display(), "Saved AFK snapshot");
+    Ok(path)
+}
+
+/// Delete all but the newest `keep` snapshots in `dir`
+fn prune(dir: &Path, keep: usize) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|ext| ext == snapshots::EXTENSION)
+        })
+        .collect();
+    // Timestamped names sort oldest first
+    files.sort();
+    let excess = files.len().saturating_sub(keep);
+    if excess > 0 {
+        info!(dir = %dir.display(), count = excess, "Removing old AFK snapshots");
+    }
+    for old in &files[..excess] {
+        let _ = std::fs::remove_file(old);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_timer_waits_for_interval() {
+        let settings = AfkSnapshots {
+            enabled: true,
+            interval_minutes: 5,
+            ..AfkSnapshots::default()
+        };
+        let mut timer = SnapshotTimer::default();
+        let start = Instant::now();
+        assert!(timer.due(&settings, start));
+        assert!(!timer.due(&settings, start + Duration::from_secs(299)));
+        assert!(timer.due(&settings, start + Duration::from_secs(300)));
+
+        // Disabling resets, so re-enabling snapshots right away
+        let disabled = AfkSnapshots::default();
+        assert!(!timer.due(&disabled, start + Duration::from_secs(301)));
+        assert!(timer.due(&settings, start + Duration::from_secs(302)));
+    }
+
+    #[test]
+    fn test_prune_keeps_newest() {
+        let dir = tempfile::tempdir().unwrap();
+        for minute in 0..5 {
+            let name = format!("20260101-00{:02}00.{}", minute, snapshots::EXTENSION);
+            std::fs::write(dir.path().join(name), "png").unwrap();
+        }
+        std::fs::write(dir.path().join("notes.txt"), "keep me").unwrap();
+
+        prune(dir.path(), 2);
+        let mut left: Vec<String> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        left.sort();
+        assert_eq!(
+            left,
+            vec!["20260101-000300.png", "20260101-000400.png", "notes.txt"]
+        );
+    }
+}
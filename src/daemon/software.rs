@@ -0,0 +1,318 @@
+//! Software scaling for sources XRender cannot read
+//!
+//! Remote and virtual X servers (VNC, X2Go, Xvnc at depth 16) can run client windows at a
+//! depth or visual none of the server's Render picture formats describe. Such sources are
+//! read with core `GetImage`, scaled nearest-neighbour on the CPU and written to the thumbnail
+//! with `PutImage`. Far slower than XRender, but better than no thumbnail at all. Damage on
+//! such a source is drawn on the frame tick, at most every
+//! [`software_scaling::FRAME_INTERVAL_MS`].
+
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use x11rb::connection::RequestConnection;
+use x11rb::protocol::xproto::*;
+use x11rb::rust_connection::RustConnection;
+
+use crate::common::constants::software_scaling;
+
+/// How pixels of one depth and visual are laid out in a ZPixmap image
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PixelLayout {
+    pub bits_per_pixel: u8,
+    pub scanline_pad: u8,
+    pub red_mask: u32,
+    pub green_mask: u32,
+    pub blue_mask: u32,
+    pub lsb_first: bool,
+}
+
+impl PixelLayout {
+    /// Layout of `visual` at `depth`, or None for depths or visuals the server doesn't list
+    pub fn for_visual(setup: &Setup, screen: &Screen, depth: u8, visual: Visualid) -> Option<Self> {
+        let format = setup.pixmap_formats.iter().find(|f| f.depth == depth)?;
+        let visual = screen
+            .allowed_depths
+            .iter()
+            .filter(|d| d.depth == depth)
+            .flat_map(|d| d.visuals.iter())
+            .find(|v| v.visual_id == visual)?;
+        if !matches!(format.bits_per_pixel, 8 | 16 | 24 | 32) {
+            return None;
+        }
+        Some(Self {
+            bits_per_pixel: format.bits_per_pixel,
+            scanline_pad: format.scanline_pad,
+            red_mask: visual.red_mask,
+            green_mask: visual.green_mask,
+            blue_mask: visual.blue_mask,
+            lsb_first: setup.image_byte_order == ImageOrder::LSB_FIRST,
+        })
+    }
+
+    fn bytes_per_pixel(&self) -> usize {
+        self.bits_per_pixel as usize / 8
+    }
+
+    /// Bytes per image row, including scanline padding
+    pub fn stride(&self, width: u16) -> usize {
+        let pad = self.scanline_pad.max(8) as usize;
+        let bits = width as usize * self.bits_per_pixel as usize;
+        bits.div_ceil(pad) * pad / 8
+    }
+
+    fn read(&self, bytes: &[u8]) -> (u8, u8, u8) {
+        let raw = if self.lsb_first {
+            bytes
+                .iter()
+                .rev()
+                .fold(0u32, |acc, &b| (acc << 8) | b as u32)
+        } else {
+            bytes.iter().fold(0u32, |acc, &b| (acc << 8) | b as u32)
+        };
+        (
+            channel_from(raw, self.red_mask),
+            channel_from(raw, self.green_mask),
+            channel_from(raw, self.blue_mask),
+        )
+    }
+
+    fn write(&self, (r, g, b): (u8, u8, u8), out: &mut [u8]) {
+        let raw = channel_to(r, self.red_mask)
+            | channel_to(g, self.green_mask)
+            | channel_to(b, self.blue_mask);
+        let len = out.len();
+        for (i, byte) in out.iter_mut().enumerate() {
+            let shift = if self.lsb_first { i } else { len - 1 - i } * 8;
+            *byte = (raw >> shift) as u8;
+        }
+    }
+}
+
+/// 8-bit value of the channel selected by `mask`
+fn channel_from(raw: u32, mask: u32) -> u8 {
+    if mask == 0 {
+        return 0;
+    }
+    let max = mask >> mask.trailing_zeros();
+    let value = (raw & mask) >> mask.trailing_zeros();
+    (value * 255 / max) as u8
+}
+
+/// `value` placed in the channel selected by `mask`
+fn channel_to(value: u8, mask: u32) -> u32 {
+    if mask == 0 {
+        return 0;
+    }
+    let max = mask >> mask.trailing_zeros();
+    ((value as u32 * max + 127) / 255) << mask.trailing_zeros()
+}
+
+/// Nearest-neighbour scale of a ZPixmap image from one layout and size to another
+pub fn scale(
+    src: &[u8],
+    src_layout: &PixelLayout,
+    src_width: u16,
+    src_height: u16,
+    dst_layout: &PixelLayout,
+    dst_width: u16,
+    dst_height: u16,
+) -> Vec<u8> {
+    let src_stride = src_layout.stride(src_width);
+    let dst_stride = dst_layout.stride(dst_width);
+    let src_bpp = src_layout.bytes_per_pixel();
+    let dst_bpp = dst_layout.bytes_per_pixel();
+    let mut dst = vec![0u8; dst_stride * dst_height as usize];
+
+    for y in 0..dst_height as usize {
+        let sy = y * src_height as usize / dst_height as usize;
+        for x in 0..dst_width as usize {
+            let sx = x * src_width as usize / dst_width as usize;
+            let at = sy * src_stride + sx * src_bpp;
+            let Some(pixel) = src.get(at..at + src_bpp) else {
+                continue;
+            };
+            let out = y * dst_stride + x * dst_bpp;
+            dst_layout.write(src_layout.read(pixel), &mut dst[out..out + dst_bpp]);
+        }
+    }
+    dst
+}
+
+/// Unpack a ZPixmap image into tightly packed 8-bit RGB rows
+pub fn to_rgb(src: &[u8], layout: &PixelLayout, width: u16, height: u16) -> Vec<u8> {
+    let stride = layout.stride(width);
+    let bpp = layout.bytes_per_pixel();
+    let mut rgb = vec![0u8; width as usize * height as usize * 3];
+    for (i, out) in rgb.chunks_exact_mut(3).enumerate() {
+        let at = i / width as usize * stride + i % width as usize * bpp;
+        if let Some(pixel) = src.get(at..at + bpp) {
+            let (r, g, b) = layout.read(pixel);
+            out.copy_from_slice(&[r, g, b]);
+        }
+    }
+    rgb
+}
+
+/// Reads a source window in software and draws it scaled into the thumbnail
+#[derive(Debug)]
+pub struct SoftwareSource {
+    pub src_layout: PixelLayout,
+    pub dst_layout: PixelLayout,
+    pub dst_depth: u8,
+    pub gc: Gcontext,
+    /// When the source was last read
+    pub last_draw: Cell<Option<Instant>>,
+}
+
+impl SoftwareSource {
+    /// Enough time has passed since the last read to read the source again
+    pub fn due(&self, now: Instant) -> bool {
+        self.last_draw.get().is_none_or(|last| {
+            now.saturating_duration_since(last)
+                >= Duration::from_millis(software_scaling::FRAME_INTERVAL_MS)
+        })
+    }
+
+    /// Copy `src` (of the given size) into `dst`, scaled to `dst_width` x `dst_height`
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw(
+        &self,
+        conn: &RustConnection,
+        src: Window,
+        src_width: u16,
+        src_height: u16,
+        dst: Window,
+        dst_width: u16,
+        dst_height: u16,
+    ) -> Result<()> {
+        self.last_draw.set(Some(Instant::now()));
+        let image = conn
+            .get_image(ImageFormat::Z_PIXMAP, src, 0, 0, src_width, src_height, !0)
+            .context("Failed to send GetImage request")?
+            .reply()
+            .context("Failed to read source window image")?;
+        let scaled = scale(
+            &image.data,
+            &self.src_layout,
+            src_width,
+            src_height,
+            &self.dst_layout,
+            dst_width,
+            dst_height,
+        );
+
+        // Split into bands of rows that fit the server's request size limit
+        let stride = self.dst_layout.stride(dst_width);
+        let max_bytes = conn.maximum_request_bytes().saturating_sub(64);
+        let rows_per_request = (max_bytes / stride.max(1)).clamp(1, dst_height.max(1) as usize);
+        for (band, rows) in scaled.chunks(rows_per_request * stride).enumerate() {
+            let y = band * rows_per_request;
+            conn.put_image(
+                ImageFormat::Z_PIXMAP,
+                dst,
+                self.gc,
+                dst_width,
+                (rows.len() / stride) as u16,
+                0,
+                y as i16,
+                0,
+                self.dst_depth,
+                rows,
+            )
+            .context("Failed to send PutImage request")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RGB565: PixelLayout = PixelLayout {
+        bits_per_pixel: 16,
+        scanline_pad: 32,
+        red_mask: 0xF800,
+        green_mask: 0x07E0,
+        blue_mask: 0x001F,
+        lsb_first: true,
+    };
+
+    const XRGB32: PixelLayout = PixelLayout {
+        bits_per_pixel: 32,
+        scanline_pad: 32,
+        red_mask: 0x00FF_0000,
+        green_mask: 0x0000_FF00,
+        blue_mask: 0x0000_00FF,
+        lsb_first: true,
+    };
+
+    #[test]
+    fn test_to_rgb_skips_row_padding() {
+        // 1x2 RGB565 image: each 2-byte row is padded to 4 bytes
+        let mut image = [0u8; 8];
+        RGB565.write((255, 0, 0), &mut image[0..2]);
+        RGB565.write((0, 0, 255), &mut image[4..6]);
+        assert_eq!(to_rgb(&image, &RGB565, 1, 2), vec![255, 0, 0, 0, 0, 255]);
+    }
+
+    #[test]
+    fn test_reads_are_rate_limited() {
+        let source = SoftwareSource {
+            src_layout: RGB565,
+            dst_layout: XRGB32,
+            dst_depth: 24,
+            gc: 0,
+            last_draw: Cell::new(None),
+        };
+        let start = Instant::now();
+        assert!(source.due(start));
+
+        source.last_draw.set(Some(start));
+        let interval = Duration::from_millis(software_scaling::FRAME_INTERVAL_MS);
+        assert!(!source.due(start + interval / 2));
+        assert!(source.due(start + interval));
+    }
+
+    #[test]
+    fn test_stride_includes_padding() {
+        assert_eq!(RGB565.stride(3), 8);
+        assert_eq!(RGB565.stride(4), 8);
+        assert_eq!(XRGB32.stride(3), 12);
+    }
+
+    #[test]
+    fn test_pixel_round_trip_across_depths() {
+        let mut pixel = [0u8; 2];
+        RGB565.write((255, 0, 255), &mut pixel);
+        assert_eq!(u16::from_le_bytes(pixel), 0xF81F);
+        assert_eq!(RGB565.read(&pixel), (255, 0, 255));
+
+        let mut wide = [0u8; 4];
+        XRGB32.write(RGB565.read(&pixel), &mut wide);
+        assert_eq!(u32::from_le_bytes(wide), 0x00FF_00FF);
+    }
+
+    #[test]
+    fn test_scale_picks_nearest_pixels() {
+        // 2x2 source: red, green / blue, white
+        let mut src = vec![0u8; XRGB32.stride(2) * 2];
+        for (i, rgb) in [(255, 0, 0), (0, 255, 0), (0, 0, 255), (255, 255, 255)]
+            .into_iter()
+            .enumerate()
+        {
+            XRGB32.write(rgb, &mut src[i * 4..i * 4 + 4]);
+        }
+
+        let dst = scale(&src, &XRGB32, 2, 2, &RGB565, 4, 4);
+        let stride = RGB565.stride(4);
+        let at = |x: usize, y: usize| RGB565.read(&dst[y * stride + x * 2..y * stride + x * 2 + 2]);
+        assert_eq!(at(0, 0), (255, 0, 0));
+        assert_eq!(at(1, 1), (255, 0, 0));
+        assert_eq!(at(3, 0), (0, 255, 0));
+        assert_eq!(at(0, 3), (0, 0, 255));
+        assert_eq!(at(3, 3), (255, 255, 255));
+    }
+}
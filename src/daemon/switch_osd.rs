@@ -0,0 +1,108 @@
+//! On-screen display for hotkey switches
+//!
+//! Shown briefly centered over the client a hotkey activated, naming the character, for
+//! users whose thumbnails sit on another monitor.
+
+use anyhow::Result;
+use x11rb::protocol::xproto::{ConnectionExt as XprotoExt, Window};
+
+use crate::common::constants::switch_osd;
+use crate::common::types::{Dimensions, Position};
+use crate::x11::AppContext;
+
+use super::font::FontRenderer;
+use super::label::{LabelPopup, LabelStyle};
+use super::snapping::Rect;
+
+const STYLE: LabelStyle = LabelStyle {
+    background: switch_osd::BACKGROUND_COLOR,
+    text_color: switch_osd::TEXT_COLOR,
+    padding: switch_osd::PADDING,
+    text_scale: switch_osd::TEXT_SCALE,
+};
+
+#[derive(Debug)]
+pub struct SwitchOsd {
+    popup: LabelPopup,
+}
+
+impl Default for SwitchOsd {
+    fn default() -> Self {
+        Self {
+            popup: LabelPopup::new(STYLE, "switch OSD"),
+        }
+    }
+}
+
+impl SwitchOsd {
+    /// Show `text` centered over `client` (or the screen, if its geometry is unknown) until
+    /// `hide` is called
+    pub fn show(
+        &mut self,
+        ctx: &AppContext,
+        font_renderer: &FontRenderer,
+        text: &str,
+        client: Window,
+    ) -> Result<()> {
+        let screen = Rect {
+            x: 0,
+            y: 0,
+            width: ctx.screen.width_in_pixels,
+            height: ctx.screen.height_in_pixels,
+        };
+        let area = client_rect(ctx, client).unwrap_or(screen);
+        self.popup
+            .show(ctx, font_renderer, &[text], None, |size| center(area, size))
+    }
+
+    pub fn hide(&mut self, ctx: &AppContext) -> Result<()> {
+        self.popup.hide(ctx)
+    }
+}
+
+/// Area of `client` in root coordinates
+fn client_rect(ctx: &AppContext, client: Window) -> Option<Rect> {
+    let geometry = ctx.conn.get_geometry(client).ok()?.reply().ok()?;
+    let position = crate::x11::get_root_position(ctx.conn, client, ctx.screen.root)?;
+    Some(Rect {
+        x: position.x,
+        y: position.y,
+        width: geometry.width,
+        height: geometry.height,
+    })
+}
+
+/// Top-left corner of a window of `size` centered on `area`
+fn center(area: Rect, size: Dimensions) -> Position {
+    let x = i32::from(area.x) + (i32::from(area.width) - i32::from(size.width)) / 2;
+    let y = i32::from(area.y) + (i32::from(area.height) - i32::from(size.height)) / 2;
+    Position::new(x.max(0) as i16, y.max(0) as i16)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_center_on_client() {
+        let client = Rect {
+            x: 1920,
+            y: 0,
+            width: 1920,
+            height: 1080,
+        };
+        assert_eq!(
+            center(client, Dimensions::new(200, 40)),
+            Position::new(2780, 520)
+        );
+
+        // Larger than the client: kept on screen
+        let small = Rect {
+            x: 0,
+            y: 0,
+            width: 100,
+            height: 20,
+        };
+        assert_eq!(center(small, Dimensions::new(200, 40)), Position::new(0, 0));
+    }
+}
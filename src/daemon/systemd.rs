@@ -0,0 +1,128 @@
+//! systemd service integration
+//!
+//! Implements the small subset of the `sd_notify` protocol needed to run the daemon
+//! as a `Type=notify` user unit: readiness, shutdown and watchdog keep-alives.
+//! All calls are no-ops when the daemon was not started by systemd.
+
+use std::ffi::OsStr;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::net::UnixDatagram;
+use std::time::Duration;
+
+use tracing::{debug, warn};
+
+use crate::common::constants::systemd;
+
+/// Daemon finished startup and is processing events
+pub const READY: &str = "READY=1";
+
+/// Daemon is shutting down
+pub const STOPPING: &str = "STOPPING=1";
+
+/// Watchdog keep-alive
+pub const WATCHDOG: &str = "WATCHDOG=1";
+
+/// Send a state update to the service manager.
+///
+/// Returns `true` if the message was delivered, `false` if not running under systemd
+/// or the socket could not be reached.
+pub fn notify(state: &str) -> bool {
+    let Some(socket_path) = std::env::var_os(systemd::NOTIFY_SOCKET_ENV) else {
+        return false;
+    };
+
+    match send_to_socket(&socket_path, state) {
+        Ok(()) => {
+            debug!(state = %state, "Sent sd_notify message");
+            true
+        }
+        Err(e) => {
+            warn!(state = %state, error = %e, "Failed to send sd_notify message");
+            false
+        }
+    }
+}
+
+/// Interval at which `WATCHDOG=1` must be sent, if the unit enables `WatchdogSec=`.
+///
+/// Pings are scheduled at half the configured timeout, as recommended by systemd.
+pub fn watchdog_interval() -> Option<Duration> {
+    parse_watchdog_interval(
+        std::env::var(systemd::WATCHDOG_USEC_ENV).ok().as_deref(),
+        std::env::var(systemd::WATCHDOG_PID_ENV).ok().as_deref(),
+        std::process::id(),
+    )
+}
+
+fn parse_watchdog_interval(
+    usec: Option<&str>,
+    pid: Option<&str>,
+    own_pid: u32,
+) -> Option<Duration> {
+    // WATCHDOG_PID is optional; if set, the watchdog only applies to that process
+    if let Some(pid) = pid
+        && pid.trim().parse::<u32>().ok() != Some(own_pid)
+    {
+        return None;
+    }
+
+    let usec = usec?.trim().parse::<u64>().ok().filter(|&u| u > 0)?;
+    Some(Duration::from_micros(usec / 2))
+}
+
+fn send_to_socket(socket_path: &OsStr, state: &str) -> std::io::Result<()> {
+    let socket = UnixDatagram::unbound()?;
+
+    // Paths starting with '@' refer to the Linux abstract socket namespace
+    if let Some(name) = socket_path.as_bytes().strip_prefix(b"@") {
+        use std::os::linux::net::SocketAddrExt;
+        let addr = std::os::unix::net::SocketAddr::from_abstract_name(name)?;
+        socket.send_to_addr(state.as_bytes(), &addr)?;
+    } else {
+        socket.send_to(state.as_bytes(), socket_path)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_watchdog_interval_is_half_timeout() {
+        let interval = parse_watchdog_interval(Some("10000000"), None, 42);
+        assert_eq!(interval, Some(Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn test_watchdog_interval_respects_pid() {
+        assert_eq!(
+            parse_watchdog_interval(Some("2000000"), Some("42"), 42),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(
+            parse_watchdog_interval(Some("2000000"), Some("7"), 42),
+            None
+        );
+    }
+
+    #[test]
+    fn test_watchdog_interval_invalid() {
+        assert_eq!(parse_watchdog_interval(None, None, 42), None);
+        assert_eq!(parse_watchdog_interval(Some("0"), None, 42), None);
+        assert_eq!(parse_watchdog_interval(Some("abc"), None, 42), None);
+    }
+
+    #[test]
+    fn test_send_to_path_socket() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notify.sock");
+        let receiver = UnixDatagram::bind(&path).unwrap();
+
+        send_to_socket(path.as_os_str(), READY).unwrap();
+
+        let mut buf = [0u8; 64];
+        let len = receiver.recv(&mut buf).unwrap();
+        assert_eq!(&buf[..len], READY.as_bytes());
+    }
+}
@@ -8,8 +8,7 @@ use tracing::debug;
 use x11rb::protocol::damage::Damage;
 use x11rb::protocol::xproto::{ConnectionExt, Window};
 
-use crate::common::constants::positioning;
-use crate::common::types::{Dimensions, Position, ThumbnailState};
+use crate::common::types::{CharacterSettings, Dimensions, MonitorScale, Position, ThumbnailState};
 use crate::config::DisplayConfig;
 use crate::x11::AppContext;
 
@@ -23,6 +22,8 @@ pub struct InputState {
     pub drag_start: Position,
     pub win_start: Position,
     pub snap_targets: Vec<Rect>, // Cached snap targets computed when drag starts
+    /// Thumbnails moved along in a group drag, with their positions when it started
+    pub followers: Vec<(Window, Position)>,
 }
 
 #[derive(Debug)]
@@ -41,6 +42,18 @@ pub struct Thumbnail<'a> {
     pub hidden: bool, // Tracks if hidden by "hide_when_no_focus"
     pub input_state: InputState,
     pub preview_mode: crate::common::types::PreviewMode,
+    /// Source damage not drawn yet (low-power mode defers it to the frame tick)
+    pub damage_pending: bool,
+    /// Thumbnail window is completely covered by other windows, so drawing it is wasted
+    pub occluded: bool,
+    /// The next forward cycle press lands on this thumbnail's client
+    pub next_target: bool,
+    /// Part of the user's selection (Ctrl+click or rubber band)
+    pub selected: bool,
+    /// Monitor the source window was last seen on, for thumbnails that follow their client
+    pub source_monitor: Option<MonitorScale>,
+    /// Top-left corner of the source window while thumbnails keep their offset from it
+    pub client_origin: Option<Position>,
 
     // === Geometry (public, immutable after creation) ===
     pub dimensions: Dimensions,
@@ -95,11 +108,11 @@ impl<'a> Thumbnail<'a> {
                 src, character_name
             ))?;
 
-        // Use saved position OR top-left of EVE window with 20px padding
+        // Use saved position OR top-left of EVE window with the configured padding
         let Position { x, y } = position.unwrap_or_else(|| {
             Position::new(
-                src_geom.x + positioning::DEFAULT_SPAWN_OFFSET,
-                src_geom.y + positioning::DEFAULT_SPAWN_OFFSET,
+                src_geom.x + display_config.spawn_offset,
+                src_geom.y + display_config.spawn_offset,
             )
         });
         debug!(
@@ -129,6 +142,12 @@ impl<'a> Thumbnail<'a> {
             hidden: false,
             input_state: InputState::default(),
             preview_mode,
+            damage_pending: false,
+            occluded: false,
+            next_target: false,
+            selected: false,
+            source_monitor: None,
+            client_origin: None,
             dimensions,
             current_position: Position::new(x, y),
             renderer,
@@ -152,6 +171,16 @@ impl<'a> Thumbnail<'a> {
         self.renderer.damage
     }
 
+    /// Returns whether the source is scaled in software, drawn rate-limited on the frame tick.
+    pub fn reads_in_software(&self) -> bool {
+        self.renderer.reads_in_software()
+    }
+
+    /// Returns whether a software-scaled source must wait before it is read again.
+    pub fn throttled(&self, now: std::time::Instant) -> bool {
+        self.renderer.throttled(now)
+    }
+
     /// Returns the parent window ID, if known.
     pub fn parent(&self) -> Option<Window> {
         self.renderer.parent
@@ -162,6 +191,23 @@ impl<'a> Thumbnail<'a> {
         self.renderer.set_parent(parent);
     }
 
+    /// Scale with bilinear filtering (`true`) or nearest-neighbour for low-power mode.
+    pub fn set_smoothing(&self, smooth: bool) -> Result<()> {
+        self.renderer.set_smoothing(&self.character_name, smooth)
+    }
+
+    /// Let the window manager manage the thumbnail window (`true`) or bypass it.
+    pub fn set_managed(&self, managed: bool) -> Result<()> {
+        self.renderer
+            .set_managed(&self.character_name, managed, self.is_visible())
+    }
+
+    /// Replace the EWMH states of the thumbnail window
+    pub fn set_wm_states(&self, wm_states: crate::common::types::ThumbnailWmStates) -> Result<()> {
+        self.renderer
+            .set_wm_states(&self.character_name, wm_states, self.is_visible())
+    }
+
     /// Checks if the thumbnail is currently visible (mapped and not hidden).
     pub fn is_visible(&self) -> bool {
         !self.hidden
@@ -212,6 +258,14 @@ impl<'a> Thumbnail<'a> {
         // No-op
     }
 
+    /// Settings saving `pos` as this thumbnail's position, along with its client's corner
+    pub fn position_settings(&self, pos: Position) -> CharacterSettings {
+        CharacterSettings {
+            client_origin: self.client_origin,
+            ..CharacterSettings::new(pos.x, pos.y, self.dimensions.width, self.dimensions.height)
+        }
+    }
+
     /// Moves the thumbnail to a new position updates the cached state.
     pub fn reposition(&mut self, x: i16, y: i16) -> Result<()> {
         self.renderer.reposition(&self.character_name, x, y)?;
@@ -242,7 +296,7 @@ impl<'a> Thumbnail<'a> {
         Ok(())
     }
 
-    /// Updates the thumbnail border based on focus state.
+    /// Updates the thumbnail border based on focus state, the next cycle target and selection.
     pub fn border(
         &self,
         display_config: &DisplayConfig,
@@ -256,6 +310,8 @@ impl<'a> Thumbnail<'a> {
             self.dimensions,
             focused,
             skipped,
+            self.next_target,
+            self.selected,
             font_renderer,
         )
     }
@@ -335,11 +391,13 @@ impl<'a> Thumbnail<'a> {
         font_renderer: &FontRenderer,
     ) -> Result<()> {
         self.character_name = new_name;
+        self.renderer.set_names(&self.character_name)?;
 
         // NOTE: Resize must precede update_name because it regenerates the overlay pixmap.
 
         if let Some(settings) = new_settings {
-            self.reposition(settings.x, settings.y).context(format!(
+            let pos = settings.position_from_client(self.client_origin);
+            self.reposition(pos.x, pos.y).context(format!(
                 "Failed to reposition after character change to '{}'",
                 self.character_name
             ))?;
@@ -372,6 +430,35 @@ impl<'a> Thumbnail<'a> {
         Ok(())
     }
 
+    /// Set the launch slot of the EVE client. It takes effect with the next redraw, see
+    /// `set_last_character`.
+    pub fn set_client_slot(&mut self, slot: usize) {
+        self.renderer.set_client_slot(Some(slot));
+    }
+
+    /// Remember the character last logged in on the client. A logged-out thumbnail is labelled
+    /// with its account and "(last: Name)" and redrawn right away.
+    pub fn set_last_character(
+        &mut self,
+        name: Option<String>,
+        display_config: &DisplayConfig,
+        font_renderer: &FontRenderer,
+    ) -> Result<()> {
+        self.renderer.set_last_character(name);
+        if !self.character_name.is_empty() {
+            return Ok(());
+        }
+        self.renderer
+            .update_name(
+                display_config,
+                &self.character_name,
+                self.dimensions,
+                font_renderer,
+            )
+            .context("Failed to update logged-out label")?;
+        self.update(display_config, font_renderer)
+    }
+
     /// Checks if a screen coordinate point is inside the thumbnail's bounds.
     ///
     /// Uses cached `current_position` to avoid synchronous X11 roundtrip.
@@ -0,0 +1,206 @@
+//! Thumbnail tooltips
+//!
+//! While the pointer is over a thumbnail (and tooltips are enabled in the profile) a small
+//! label below it shows the character's portrait, alias, notes, cycle groups and hotkey. Near
+//! the bottom of the screen it goes above the thumbnail instead.
+
+use anyhow::Result;
+use tracing::warn;
+
+use crate::common::constants::tooltip;
+use crate::common::types::{CharacterSettings, Dimensions, Position};
+use crate::config::HotkeyBinding;
+use crate::config::profile::CycleGroup;
+use crate::x11::AppContext;
+
+use super::badges::{self, ImageCache};
+use super::font::FontRenderer;
+use super::label::{LabelImage, LabelPopup, LabelStyle};
+use super::snapping::Rect;
+
+const STYLE: LabelStyle = LabelStyle {
+    background: tooltip::BACKGROUND_COLOR,
+    text_color: tooltip::TEXT_COLOR,
+    padding: tooltip::PADDING,
+    text_scale: tooltip::TEXT_SCALE,
+};
+
+#[derive(Debug)]
+pub struct Tooltip {
+    popup: LabelPopup,
+    portraits: ImageCache, // Decoded portraits
+}
+
+impl Default for Tooltip {
+    fn default() -> Self {
+        Self {
+            popup: LabelPopup::new(STYLE, "tooltip"),
+            portraits: ImageCache::new(),
+        }
+    }
+}
+
+impl Tooltip {
+    /// Show `lines`, with the PNG at `portrait` left of them, next to the thumbnail covering
+    /// `thumbnail`
+    pub fn show(
+        &mut self,
+        ctx: &AppContext,
+        font_renderer: &FontRenderer,
+        lines: &[String],
+        portrait: Option<&str>,
+        thumbnail: Rect,
+    ) -> Result<()> {
+        let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+        let size = tooltip::PORTRAIT_SIZE;
+        let image = portrait.and_then(|path| {
+            self.portraits
+                .entry((path.to_string(), size))
+                .or_insert_with(|| {
+                    badges::load_png(path, size)
+                        .inspect_err(|e| warn!(error = %e, "Failed to load portrait"))
+                        .ok()
+                })
+                .as_deref()
+        });
+        let image = image.map(|pixels| LabelImage { size, pixels });
+        let screen = Dimensions::new(ctx.screen.width_in_pixels, ctx.screen.height_in_pixels);
+        self.popup.show(ctx, font_renderer, &lines, image, |size| {
+            place(thumbnail, size, screen)
+        })
+    }
+
+    pub fn hide(&mut self, ctx: &AppContext) -> Result<()> {
+        self.popup.hide(ctx)
+    }
+}
+
+/// Portrait path from `settings`, if one is set
+pub fn portrait(settings: Option<&CharacterSettings>) -> Option<&str> {
+    settings
+        .and_then(|s| s.portrait.as_deref())
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+}
+
+/// Tooltip text for `name`; empty when there is nothing beyond the name to show (a portrait
+/// counts, so a character with only a portrait gets its name shown next to it)
+pub fn lines(
+    name: &str,
+    settings: Option<&CharacterSettings>,
+    groups: &[CycleGroup],
+    hotkey: Option<&HotkeyBinding>,
+) -> Vec<String> {
+    let alias = settings
+        .and_then(|s| s.alias.as_deref())
+        .map(str::trim)
+        .filter(|a| !a.is_empty());
+    let mut lines = Vec::new();
+    let member_of: Vec<&str> = groups
+        .iter()
+        .filter(|g| g.contains(name))
+        .map(|g| g.name.as_str())
+        .collect();
+    if !member_of.is_empty() {
+        lines.push(format!("Groups: {}", member_of.join(", ")));
+    }
+    if let Some(hotkey) = hotkey {
+        lines.push(format!("Hotkey: {}", hotkey.display_name()));
+    }
+    if let Some(notes) = settings.and_then(|s| s.notes.as_deref()) {
+        lines.extend(
+            notes
+                .lines()
+                .filter(|l| !l.trim().is_empty())
+                .map(str::to_string),
+        );
+    }
+    if alias.is_none() && lines.is_empty() && portrait(settings).is_none() {
+        return lines;
+    }
+    let title = match alias {
+        Some(alias) => format!("{alias} ({name})"),
+        None => name.to_string(),
+    };
+    std::iter::once(title).chain(lines).collect()
+}
+
+/// Below `thumbnail`, or above it when there is no room, kept on the screen
+fn place(thumbnail: Rect, size: Dimensions, screen: Dimensions) -> Position {
+    let below = thumbnail.bottom().saturating_add(tooltip::GAP);
+    let y = if i32::from(below) + i32::from(size.height) <= i32::from(screen.height) {
+        below
+    } else {
+        thumbnail
+            .top()
+            .saturating_sub(tooltip::GAP)
+            .saturating_sub(size.height as i16)
+    };
+    let max_x = screen.width.saturating_sub(size.width) as i16;
+    let max_y = screen.height.saturating_sub(size.height) as i16;
+    Position::new(thumbnail.x.clamp(0, max_x), y.clamp(0, max_y))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::profile::CycleSlot;
+
+    #[test]
+    fn test_lines_list_alias_groups_hotkey_and_notes() {
+        let mut settings = CharacterSettings::new(0, 0, 100, 100);
+        settings.alias = Some("Scout".to_string());
+        settings.notes = Some("Cyno alt\n\nKeep docked".to_string());
+        let groups = [CycleGroup {
+            name: "Miners".to_string(),
+            cycle_list: vec![CycleSlot::Eve("Alice".to_string())],
+            ..CycleGroup::default_group()
+        }];
+        let hotkey = HotkeyBinding::new(59, true, false, false, false);
+
+        assert_eq!(
+            lines("Alice", Some(&settings), &groups, Some(&hotkey)),
+            vec![
+                "Scout (Alice)".to_string(),
+                "Groups: Miners".to_string(),
+                format!("Hotkey: {}", hotkey.display_name()),
+                "Cyno alt".to_string(),
+                "Keep docked".to_string(),
+            ]
+        );
+
+        // Without an alias the name leads; with nothing to add there is no tooltip
+        assert_eq!(
+            lines("Alice", None, &groups, None),
+            vec!["Alice".to_string(), "Groups: Miners".to_string()]
+        );
+        assert!(lines("Bob", None, &groups, None).is_empty());
+
+        let mut settings = CharacterSettings::new(0, 0, 100, 100);
+        settings.portrait = Some("~/bob.png".to_string());
+        assert_eq!(portrait(Some(&settings)), Some("~/bob.png"));
+        assert_eq!(
+            lines("Bob", Some(&settings), &groups, None),
+            vec!["Bob".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_place_flips_above_near_bottom() {
+        let screen = Dimensions::new(1000, 800);
+        let size = Dimensions::new(200, 60);
+        let thumbnail = Rect {
+            x: 900,
+            y: 100,
+            width: 100,
+            height: 100,
+        };
+        assert_eq!(place(thumbnail, size, screen), Position::new(800, 204));
+
+        let thumbnail = Rect {
+            y: 700,
+            ..thumbnail
+        };
+        assert_eq!(place(thumbnail, size, screen), Position::new(800, 636));
+    }
+}
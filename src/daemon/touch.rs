@@ -0,0 +1,375 @@
+//! Touchscreen gestures on thumbnails
+//!
+//! Thumbnails select XInput 2.2 touch events, which also stops the server from emulating
+//! pointer clicks for touches on them. `TouchTracker` turns the raw touch stream into
+//! gestures: a tap focuses the client, a long press followed by movement drags the
+//! thumbnail, and two fingers on one thumbnail pinch-resize it.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use tracing::{debug, info};
+use x11rb::protocol::xinput::{self, ConnectionExt as XinputExt, Fp1616, XIEventMask};
+use x11rb::protocol::xproto::Window;
+use x11rb::rust_connection::RustConnection;
+
+use crate::common::constants::defaults::thumbnail;
+use crate::common::constants::touch::{LONG_PRESS_MS, TAP_SLOP};
+use crate::common::types::Dimensions;
+use crate::x11::AppContext;
+
+/// Announce XInput 2.2 support to the server; touch events need it
+pub fn init(conn: &RustConnection) -> bool {
+    let supported = conn
+        .xinput_xi_query_version(2, 2)
+        .ok()
+        .and_then(|cookie| cookie.reply().ok())
+        .is_some_and(|reply| (reply.major_version, reply.minor_version) >= (2, 2));
+    info!(supported = supported, "XInput 2.2 touch support");
+    supported
+}
+
+/// Receive touch events for `window` (a thumbnail) when the server supports them
+pub fn select_events(ctx: &AppContext, window: Window) -> Result<()> {
+    if !ctx.touch {
+        return Ok(());
+    }
+    ctx.conn
+        .xinput_xi_select_events(
+            window,
+            &[xinput::EventMask {
+                deviceid: xinput::Device::ALL_MASTER.into(),
+                mask: vec![
+                    XIEventMask::TOUCH_BEGIN | XIEventMask::TOUCH_UPDATE | XIEventMask::TOUCH_END,
+                ],
+            }],
+        )
+        .context("Failed to select touch events")?;
+    Ok(())
+}
+
+/// Root coordinate of a touch event in pixels
+pub fn to_pixels(value: Fp1616) -> f64 {
+    f64::from(value) / 65536.0
+}
+
+/// What a finished or ongoing touch sequence means for a thumbnail (keyed by source window)
+#[derive(Debug, Clone, PartialEq)]
+pub enum Gesture {
+    /// Short touch without movement
+    Tap {
+        src: Window,
+    },
+    /// Long press started moving; `x`/`y` is where the finger went down
+    DragStart {
+        src: Window,
+        x: i16,
+        y: i16,
+    },
+    DragMove {
+        src: Window,
+        x: i16,
+        y: i16,
+    },
+    DragEnd {
+        src: Window,
+    },
+    /// New thumbnail size while two fingers pinch
+    Pinch {
+        src: Window,
+        size: Dimensions,
+    },
+    PinchEnd {
+        src: Window,
+    },
+}
+
+#[derive(Debug)]
+struct Touch {
+    src: Window,
+    /// Thumbnail size when the finger went down
+    size: Dimensions,
+    start: (f64, f64),
+    current: (f64, f64),
+    started: Instant,
+    /// Part of a drag, pinch or swipe; can no longer become a tap
+    consumed: bool,
+}
+
+#[derive(Debug)]
+enum Active {
+    Drag {
+        touch: u32,
+    },
+    Pinch {
+        touches: [u32; 2],
+        start_distance: f64,
+        base: Dimensions,
+    },
+}
+
+#[derive(Debug, Default)]
+pub struct TouchTracker {
+    touches: HashMap<u32, Touch>,
+    /// At most one drag or pinch at a time
+    active: Option<Active>,
+}
+
+impl TouchTracker {
+    /// Finger `id` went down on the thumbnail of `src`, which is `size` large
+    pub fn begin(
+        &mut self,
+        id: u32,
+        src: Window,
+        size: Dimensions,
+        position: (f64, f64),
+        now: Instant,
+    ) -> Vec<Gesture> {
+        let mut gestures = Vec::new();
+        let partner = self
+            .touches
+            .iter()
+            .find(|(_, t)| t.src == src)
+            .map(|(&other, t)| (other, t.current));
+
+        self.touches.insert(
+            id,
+            Touch {
+                src,
+                size,
+                start: position,
+                current: position,
+                started: now,
+                consumed: false,
+            },
+        );
+
+        // A second finger on the same thumbnail turns any drag into a pinch
+        if let Some((other, other_position)) = partner
+            && !matches!(self.active, Some(Active::Pinch { .. }))
+        {
+            if let Some(Active::Drag { touch }) = &self.active
+                && let Some(dragged) = self.touches.get(touch)
+            {
+                gestures.push(Gesture::DragEnd { src: dragged.src });
+            }
+            let base = self.touches[&other].size;
+            for touch in [id, other] {
+                if let Some(t) = self.touches.get_mut(&touch) {
+                    t.consumed = true;
+                }
+            }
+            self.active = Some(Active::Pinch {
+                touches: [other, id],
+                start_distance: distance(other_position, position).max(1.0),
+                base,
+            });
+        }
+        gestures
+    }
+
+    /// Finger `id` moved
+    pub fn update(&mut self, id: u32, position: (f64, f64), now: Instant) -> Vec<Gesture> {
+        let Some(touch) = self.touches.get_mut(&id) else {
+            return Vec::new();
+        };
+        touch.current = position;
+        let src = touch.src;
+
+        match &self.active {
+            Some(Active::Drag { touch: dragged }) if *dragged == id => {
+                vec![Gesture::DragMove {
+                    src,
+                    x: round(position.0),
+                    y: round(position.1),
+                }]
+            }
+            Some(Active::Pinch {
+                touches,
+                start_distance,
+                base,
+            }) if touches.contains(&id) => {
+                let (Some(a), Some(b)) =
+                    (self.touches.get(&touches[0]), self.touches.get(&touches[1]))
+                else {
+                    return Vec::new();
+                };
+                let scale = distance(a.current, b.current) / start_distance;
+                vec![Gesture::Pinch {
+                    src,
+                    size: scaled(*base, scale),
+                }]
+            }
+            Some(_) => Vec::new(),
+            None => self.start_drag(id, now),
+        }
+    }
+
+    /// Drag with finger `id` if it was held long enough before moving
+    fn start_drag(&mut self, id: u32, now: Instant) -> Vec<Gesture> {
+        let Some(touch) = self.touches.get_mut(&id) else {
+            return Vec::new();
+        };
+        if touch.consumed || distance(touch.start, touch.current) < TAP_SLOP {
+            return Vec::new();
+        }
+        touch.consumed = true;
+        if now.duration_since(touch.started) < Duration::from_millis(LONG_PRESS_MS) {
+            // A quick swipe is neither a tap nor a drag
+            return Vec::new();
+        }
+        self.active = Some(Active::Drag { touch: id });
+        vec![
+            Gesture::DragStart {
+                src: touch.src,
+                x: round(touch.start.0),
+                y: round(touch.start.1),
+            },
+            Gesture::DragMove {
+                src: touch.src,
+                x: round(touch.current.0),
+                y: round(touch.current.1),
+            },
+        ]
+    }
+
+    /// Finger `id` lifted
+    pub fn end(&mut self, id: u32, now: Instant) -> Vec<Gesture> {
+        let Some(touch) = self.touches.remove(&id) else {
+            return Vec::new();
+        };
+        match &self.active {
+            Some(Active::Drag { touch: dragged }) if *dragged == id => {
+                self.active = None;
+                vec![Gesture::DragEnd { src: touch.src }]
+            }
+            Some(Active::Pinch { touches, .. }) if touches.contains(&id) => {
+                self.active = None;
+                vec![Gesture::PinchEnd { src: touch.src }]
+            }
+            _ if !touch.consumed
+                && now.duration_since(touch.started) < Duration::from_millis(LONG_PRESS_MS) =>
+            {
+                debug!(src = touch.src, "Thumbnail tapped");
+                vec![Gesture::Tap { src: touch.src }]
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+fn distance(a: (f64, f64), b: (f64, f64)) -> f64 {
+    (a.0 - b.0).hypot(a.1 - b.1)
+}
+
+fn round(value: f64) -> i16 {
+    value
+        .round()
+        .clamp(f64::from(i16::MIN), f64::from(i16::MAX)) as i16
+}
+
+/// `base` scaled by `scale`, keeping its aspect ratio within the allowed thumbnail sizes
+fn scaled(base: Dimensions, scale: f64) -> Dimensions {
+    let width = f64::from(base.width.max(1));
+    let height = f64::from(base.height.max(1));
+    let min_scale =
+        (f64::from(thumbnail::MIN_WIDTH) / width).max(f64::from(thumbnail::MIN_HEIGHT) / height);
+    let max_scale =
+        (f64::from(thumbnail::MAX_WIDTH) / width).min(f64::from(thumbnail::MAX_HEIGHT) / height);
+    let scale = scale.clamp(min_scale, max_scale.max(min_scale));
+    Dimensions::new(
+        (width * scale).round() as u16,
+        (height * scale).round() as u16,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SRC: Window = 42;
+    const SIZE: Dimensions = Dimensions {
+        width: 400,
+        height: 200,
+    };
+
+    #[test]
+    fn test_short_touch_is_tap() {
+        let mut tracker = TouchTracker::default();
+        let start = Instant::now();
+        assert!(tracker.begin(1, SRC, SIZE, (10.0, 10.0), start).is_empty());
+        assert!(tracker.update(1, (13.0, 12.0), start).is_empty());
+        assert_eq!(
+            tracker.end(1, start + Duration::from_millis(100)),
+            vec![Gesture::Tap { src: SRC }]
+        );
+
+        // Held too long: not a tap
+        tracker.begin(2, SRC, SIZE, (10.0, 10.0), start);
+        assert!(tracker.end(2, start + Duration::from_secs(2)).is_empty());
+    }
+
+    #[test]
+    fn test_long_press_then_move_drags() {
+        let mut tracker = TouchTracker::default();
+        let start = Instant::now();
+        let held = start + Duration::from_millis(LONG_PRESS_MS);
+        tracker.begin(1, SRC, SIZE, (10.0, 10.0), start);
+        assert_eq!(
+            tracker.update(1, (60.0, 10.0), held),
+            vec![
+                Gesture::DragStart {
+                    src: SRC,
+                    x: 10,
+                    y: 10
+                },
+                Gesture::DragMove {
+                    src: SRC,
+                    x: 60,
+                    y: 10
+                },
+            ]
+        );
+        assert_eq!(
+            tracker.update(1, (70.5, 20.0), held),
+            vec![Gesture::DragMove {
+                src: SRC,
+                x: 71,
+                y: 20
+            }]
+        );
+        assert_eq!(tracker.end(1, held), vec![Gesture::DragEnd { src: SRC }]);
+
+        // A quick swipe does nothing
+        tracker.begin(2, SRC, SIZE, (10.0, 10.0), start);
+        assert!(tracker.update(2, (60.0, 10.0), start).is_empty());
+        assert!(tracker.end(2, start).is_empty());
+    }
+
+    #[test]
+    fn test_pinch_resizes_keeping_aspect_ratio() {
+        let mut tracker = TouchTracker::default();
+        let start = Instant::now();
+        tracker.begin(1, SRC, SIZE, (0.0, 0.0), start);
+        assert!(tracker.begin(2, SRC, SIZE, (100.0, 0.0), start).is_empty());
+        assert_eq!(
+            tracker.update(2, (150.0, 0.0), start),
+            vec![Gesture::Pinch {
+                src: SRC,
+                size: Dimensions::new(600, 300)
+            }]
+        );
+        // Clamped to the smallest thumbnail
+        assert_eq!(
+            tracker.update(2, (1.0, 0.0), start),
+            vec![Gesture::Pinch {
+                src: SRC,
+                size: Dimensions::new(50, 25)
+            }]
+        );
+        assert_eq!(tracker.end(1, start), vec![Gesture::PinchEnd { src: SRC }]);
+        // The remaining finger is no tap
+        assert!(tracker.end(2, start).is_empty());
+    }
+}
@@ -6,12 +6,17 @@ use x11rb::connection::Connection;
 use x11rb::protocol::xproto::*;
 
 use crate::common::constants;
-use crate::common::types::Dimensions;
+use crate::common::process::process_names;
+use crate::common::types::{Dimensions, Position};
 use crate::config::DaemonConfig;
 use crate::config::DisplayConfig;
-use crate::config::profile::CustomWindowRule;
-use crate::x11::{AppContext, get_window_class, is_window_eve, is_window_minimized};
-use std::collections::HashMap;
+use crate::config::EveDetection;
+use crate::config::profile::{CustomWindowRule, PositionAnchor};
+use crate::x11::{
+    AppContext, get_client_list, get_window_class, get_window_pid, get_window_tree, is_window_eve,
+    is_window_minimized,
+};
+use std::collections::{HashMap, HashSet};
 
 use super::session_state::SessionState;
 use super::thumbnail::Thumbnail;
@@ -30,11 +35,19 @@ pub fn identify_window(
     ctx: &AppContext,
     window: Window,
     state: &mut SessionState,
+    detection: &EveDetection,
     custom_rules: &[CustomWindowRule],
 ) -> Result<Option<WindowIdentity>> {
-    // Check for EVE Client identity first (Standard/Steam/Wine) using robust detection
-    if let Some(eve_window) = check_eve_window_internal(ctx, window, state)? {
+    // Skip our own windows: thumbnails carry titles custom rules could match
+    let pid = get_window_pid(ctx.conn, window, ctx.atoms)?;
+    if pid.is_some_and(|p| p == std::process::id()) {
+        return Ok(None);
+    }
+
+    // Check for EVE Client identity first (Standard/Steam/Wine) using the configured patterns
+    if let Some(eve_window) = check_eve_window_internal(ctx, window, state, detection)? {
         let name = eve_window;
+        state.custom_instances.remove(&window);
         return Ok(Some(WindowIdentity {
             name,
             is_eve: true,
@@ -113,56 +126,45 @@ pub fn identify_window(
         }
     };
 
-    for rule in custom_rules {
-        // Validation: If a pattern (title/class) is defined in the rule,
-        // it acts as a strict filter that MUST match the window.
-        let matches_title = rule
-            .title_pattern
-            .as_ref()
-            .map(|p| wm_name.to_lowercase().contains(&p.to_lowercase()))
-            .unwrap_or(false);
-
-        let matches_class = rule
-            .class_pattern
-            .as_ref()
-            .map(|p| wm_class.to_lowercase().contains(&p.to_lowercase()))
-            .unwrap_or(false); // If rule has class pattern, it MUST match
-
-        // Logic: Rule matches if...
-        // - Title defined AND matches (AND Class is None OR matches)
-        // - Class defined AND matches (AND Title is None OR matches)
-        // Essentially, whatever criteria are defined must be satisfied.
-
-        let mut matched = true;
-
-        if rule.title_pattern.is_some() && !matches_title {
-            matched = false;
-        }
-        if rule.class_pattern.is_some() && !matches_class {
-            matched = false;
-        }
-        // If neither is defined, it's a catch-all? No, Manager enforces at least one.
-        if rule.title_pattern.is_none() && rule.class_pattern.is_none() {
-            matched = false;
-        }
+    // Only read /proc when a rule asks for the process
+    let processes = if custom_rules.iter().any(CustomWindowRule::matches_process) {
+        get_window_pid(ctx.conn, window, ctx.atoms)
+            .ok()
+            .flatten()
+            .map(process_names)
+            .unwrap_or_default()
+    } else {
+        Vec::new()
+    };
 
-        if matched {
+    for rule in custom_rules {
+        // Whatever include patterns the rule defines must match; exclude patterns veto
+        if rule.matches(&wm_name, &wm_class, &processes) {
             debug!(
                 window = window,
                 alias = %rule.alias,
                 title = %wm_name,
                 class = %wm_class,
+                processes = ?processes,
                 "Identified Custom Source"
             );
-            state.update_last_character(window, &rule.alias);
+            // Every matching window gets its own label unless the rule is limited to one
+            let name = if rule.limit {
+                state.custom_instances.remove(&window);
+                rule.alias.clone()
+            } else {
+                rule.instance_label(state.custom_instance(window, &rule.alias))
+            };
+            state.update_last_character(window, &name);
             return Ok(Some(WindowIdentity {
-                name: rule.alias.clone(),
+                name,
                 is_eve: false,
                 rule: Some(rule.clone()),
             }));
         }
     }
 
+    state.custom_instances.remove(&window);
     Ok(None)
 }
 
@@ -171,40 +173,15 @@ fn check_eve_window_internal(
     ctx: &AppContext,
     window: Window,
     state: &mut SessionState,
+    detection: &EveDetection,
 ) -> Result<Option<String>> {
-    // 1. Get PID (Optimization to skip own windows)
-    let pid_atom = ctx.atoms.net_wm_pid;
-    let pid = if let Ok(prop) = ctx
-        .conn
-        .get_property(false, window, pid_atom, AtomEnum::CARDINAL, 0, 1)
-        .context(format!("Failed to query _NET_WM_PID for {}", window))?
-        .reply()
-    {
-        if !prop.value.is_empty() {
-            Some(u32::from_ne_bytes(
-                prop.value[0..constants::x11::PID_PROPERTY_SIZE]
-                    .try_into()
-                    .unwrap_or([0; 4]),
-            ))
-        } else {
-            None
-        }
-    } else {
-        None
-    };
-
-    // Skip our own windows to avoid recursion
-    if pid.is_some_and(|p| p == std::process::id()) {
-        return Ok(None);
-    }
-
-    // 2. Title Verification
+    // Title Verification
     ctx.conn.change_window_attributes(
         window,
         &ChangeWindowAttributesAux::new().event_mask(EventMask::PROPERTY_CHANGE),
     )?;
 
-    if let Some(eve_window) = is_window_eve(ctx.conn, window, ctx.atoms)? {
+    if let Some(eve_window) = is_window_eve(ctx.conn, window, ctx.atoms, detection)? {
         let character_name = eve_window.character_name().to_string();
 
         debug!(
@@ -242,7 +219,13 @@ pub fn check_and_create_window<'a>(
     let identity = if let Some(id) = known_identity {
         id
     } else {
-        match identify_window(ctx, window, state, &daemon_config.profile.custom_windows)? {
+        match identify_window(
+            ctx,
+            window,
+            state,
+            &daemon_config.eve_detection,
+            &daemon_config.profile.custom_windows,
+        )? {
             Some(id) => id,
             None => return Ok(None),
         }
@@ -382,13 +365,23 @@ pub fn check_and_create_window<'a>(
         &daemon_config.profile.custom_source_thumbnails
     };
 
+    // Thumbnails anchored to their client keep their saved offset from it
+    let client_origin = (daemon_config.profile.thumbnail_position_anchor == PositionAnchor::Client)
+        .then(|| crate::x11::get_root_position(ctx.conn, window, ctx.screen.root))
+        .flatten();
+    let saved_position = |settings: &crate::common::types::CharacterSettings| {
+        settings.position_from_client(client_origin)
+    };
+
     // Priority 1: Runtime Settings (active session changes)
     // Priority 2: Profile Settings (saved on disk)
     // Priority 3: Inheritance / Session State
-    let position = if let Some(settings) = settings_map.get(&character_name) {
-        Some(settings.position())
-    } else if let Some(settings) = profile_map.get(&character_name) {
-        Some(settings.position())
+    // Entries without a size only track logins and don't place the thumbnail
+    let placed = |settings: &&crate::common::types::CharacterSettings| settings.is_placed();
+    let position = if let Some(settings) = settings_map.get(&character_name).filter(placed) {
+        Some(saved_position(settings))
+    } else if let Some(settings) = profile_map.get(&character_name).filter(placed) {
+        Some(saved_position(settings))
     } else {
         // Pass empty map to enforce inheritance/fallback logic only
         state.get_position(
@@ -399,6 +392,11 @@ pub fn check_and_create_window<'a>(
         )
     };
 
+    // Default sizes are scaled for the monitor the thumbnail appears on
+    let spawn_point = position
+        .or_else(|| crate::x11::get_root_position(ctx.conn, window, ctx.screen.root))
+        .unwrap_or_default();
+
     // Determine effective settings for dimensions and mode
     let effective_settings = settings_map
         .get(&character_name)
@@ -411,8 +409,7 @@ pub fn check_and_create_window<'a>(
             Dimensions::new(rule.default_width, rule.default_height)
         } else if settings.dimensions.width == 0 || settings.dimensions.height == 0 {
             // Auto-detect EVE default if saved dims are invalid
-            let (w, h) = daemon_config
-                .default_thumbnail_size(ctx.screen.width_in_pixels, ctx.screen.height_in_pixels);
+            let (w, h) = daemon_config.default_thumbnail_size(spawn_point);
             Dimensions::new(w, h)
         } else {
             settings.dimensions
@@ -436,8 +433,7 @@ pub fn check_and_create_window<'a>(
             )
         } else {
             // Auto-detect EVE default
-            let (w, h) = daemon_config
-                .default_thumbnail_size(ctx.screen.width_in_pixels, ctx.screen.height_in_pixels);
+            let (w, h) = daemon_config.default_thumbnail_size(spawn_point);
             (
                 Dimensions::new(w, h),
                 crate::common::types::PreviewMode::default(),
@@ -460,7 +456,32 @@ pub fn check_and_create_window<'a>(
         character_name, window
     ))?;
 
-    // Check minimized state
+    thumbnail.client_origin = client_origin;
+    if state.low_power {
+        thumbnail.set_smoothing(false)?;
+    }
+
+    // Logged-out clients are labelled with their account, and keep their "(last: Name)"
+    // label when found again (e.g. after a profile reload)
+    if identity.is_eve {
+        thumbnail.set_client_slot(state.client_slot(window));
+        if character_name.is_empty() {
+            let last = state.window_last_character.get(&window).cloned();
+            thumbnail.set_last_character(last, display_config, font_renderer)?;
+        }
+    }
+
+    if daemon_config.profile.thumbnail_follow_source_monitor {
+        thumbnail.source_monitor = crate::x11::get_window_center(ctx.conn, window, ctx.screen.root)
+            .and_then(|center| daemon_config.display_scales.monitor_at(center))
+            .copied();
+    }
+
+    // Keep thumbnails hidden from the tray menu or by group hotkey hidden when they log back in
+    if state.is_thumbnail_hidden(&character_name, &daemon_config.profile.cycle_groups) {
+        thumbnail.visibility(false)?;
+    }
+
     // Check minimized state
     let is_minimized = is_window_minimized(ctx.conn, window, ctx.atoms).unwrap_or(false);
 
@@ -480,6 +501,23 @@ pub fn check_and_create_window<'a>(
     Ok(Some(thumbnail))
 }
 
+/// Every window a full scan looks at: `_NET_CLIENT_LIST` first (ordered by the window
+/// manager), then the window tree, which also holds clients that existed before the
+/// window manager or that it does not list
+pub fn candidate_windows(ctx: &AppContext) -> Result<Vec<Window>> {
+    // Absent without an EWMH window manager; the tree walk still finds the clients
+    let mut windows = get_client_list(ctx.conn, ctx.screen.root, ctx.atoms).unwrap_or_else(|e| {
+        debug!(error = %e, "No usable _NET_CLIENT_LIST, relying on window tree");
+        Vec::new()
+    });
+    let mut seen: HashSet<Window> = windows.iter().copied().collect();
+
+    let tree = get_window_tree(ctx.conn, ctx.screen.root, constants::scan::TREE_DEPTH)
+        .context("Failed to walk window tree")?;
+    windows.extend(tree.into_iter().filter(|w| seen.insert(*w)));
+    Ok(windows)
+}
+
 /// Initial scan for existing EVE windows to populate thumbnails
 pub fn scan_eve_windows<'a>(
     ctx: &AppContext<'a>,
@@ -488,24 +526,7 @@ pub fn scan_eve_windows<'a>(
     daemon_config: &mut DaemonConfig,
     state: &mut SessionState,
 ) -> Result<HashMap<Window, Thumbnail<'a>>> {
-    let net_client_list = ctx.atoms.net_client_list;
-    let prop = ctx
-        .conn
-        .get_property(
-            false,
-            ctx.screen.root,
-            net_client_list,
-            AtomEnum::WINDOW,
-            0,
-            u32::MAX,
-        )
-        .context("Failed to query _NET_CLIENT_LIST property")?
-        .reply()
-        .context("Failed to get window list from X11 server")?;
-    let windows: Vec<u32> = prop
-        .value32()
-        .ok_or_else(|| anyhow::anyhow!("Invalid return from _NET_CLIENT_LIST"))?
-        .collect();
+    let windows = candidate_windows(ctx)?;
 
     let mut eve_clients = HashMap::new();
     for w in windows {
@@ -535,12 +556,7 @@ pub fn scan_eve_windows<'a>(
                     Ok(geom) => {
                         // Update character_thumbnails in memory (skip logged-out clients with empty name)
                         if !eve.character_name.is_empty() {
-                            let settings = crate::common::types::CharacterSettings::new(
-                                geom.x,
-                                geom.y,
-                                eve.dimensions.width,
-                                eve.dimensions.height,
-                            );
+                            let settings = eve.position_settings(Position::new(geom.x, geom.y));
 
                             // Route settings to the correct map based on whether this alias matches a Custom Rule.
                             // This ensures separation even if originally detected as a generic client.
@@ -548,7 +564,7 @@ pub fn scan_eve_windows<'a>(
                                 .profile
                                 .custom_windows
                                 .iter()
-                                .any(|r| r.alias == eve.character_name);
+                                .any(|r| r.owns_label(&eve.character_name));
 
                             if is_custom_alias {
                                 // NOTE: specific check to preserve existing overrides (like preview_mode)
@@ -559,6 +575,7 @@ pub fn scan_eve_windows<'a>(
                                 {
                                     existing.x = settings.x;
                                     existing.y = settings.y;
+                                    existing.client_origin = settings.client_origin;
                                     existing.dimensions = settings.dimensions;
                                 } else {
                                     daemon_config
@@ -571,6 +588,7 @@ pub fn scan_eve_windows<'a>(
                             {
                                 existing.x = settings.x;
                                 existing.y = settings.y;
+                                existing.client_origin = settings.client_origin;
                                 existing.dimensions = settings.dimensions;
                             } else {
                                 daemon_config
@@ -10,6 +10,9 @@ use std::thread::JoinHandle;
 use tokio::sync::mpsc::Sender;
 
 use crate::config::HotkeyBinding;
+use crate::config::hotkey_binding::KeyMatch;
+use crate::input::gesture::{self, GestureInput};
+use crate::input::keymap::Keymap;
 use crate::input::listener::TimestampedCommand;
 
 /// Capabilities and limitations of a hotkey backend
@@ -38,6 +41,54 @@ pub struct HotkeyConfiguration {
     pub profile_hotkeys: Vec<HotkeyBinding>,
     pub toggle_skip_key: Option<HotkeyBinding>,
     pub toggle_previews_key: Option<HotkeyBinding>,
+    pub save_positions_key: Option<HotkeyBinding>,
+    pub undo_move_key: Option<HotkeyBinding>,
+    /// X display to grab keys on (X11 backend only, None = $DISPLAY)
+    pub display: Option<String>,
+    /// Double-tap window and long-press time for bindings with those gestures
+    pub double_tap: std::time::Duration,
+    pub long_press: std::time::Duration,
+}
+
+impl HotkeyConfiguration {
+    /// Every binding in the configuration
+    pub fn bindings(&self) -> impl Iterator<Item = &HotkeyBinding> {
+        self.cycle_hotkeys
+            .iter()
+            .map(|(_, binding)| binding)
+            .chain(&self.character_hotkeys)
+            .chain(&self.profile_hotkeys)
+            .chain(&self.toggle_skip_key)
+            .chain(&self.toggle_previews_key)
+            .chain(&self.save_positions_key)
+            .chain(&self.undo_move_key)
+    }
+
+    /// Start the gesture dispatcher if any binding needs it. Its handle joins the backend's.
+    pub fn gesture_dispatcher(
+        &self,
+        sender: &Sender<TimestampedCommand>,
+    ) -> Option<(std::sync::mpsc::Sender<GestureInput>, JoinHandle<()>)> {
+        gesture::uses_gestures(self.bindings())
+            .then(|| gesture::spawn_dispatcher(sender.clone(), self.double_tap, self.long_press))
+    }
+
+    /// Keyboard mapping for bindings that match by symbol; `None` if no binding does
+    pub fn keymap(&self) -> Option<Keymap> {
+        if !self
+            .bindings()
+            .any(|binding| binding.match_by == KeyMatch::Keysym)
+        {
+            return None;
+        }
+        x11rb::connect(self.display.as_deref())
+            .map_err(anyhow::Error::from)
+            .and_then(|(conn, _)| Keymap::query(&conn))
+            .inspect_err(|e| {
+                tracing::warn!(error = %e, "Hotkeys bound by symbol will use their captured keys")
+            })
+            .ok()
+    }
 }
 
 /// Thread-safe set of allowed active window IDs (tracked clients)
@@ -14,20 +14,31 @@
 
 use anyhow::{Context, Result};
 use evdev::{Device, EventType, KeyCode};
-use std::sync::Arc;
+use std::collections::HashSet;
+use std::sync::{Arc, PoisonError, RwLock, Weak};
 use std::thread::{self, JoinHandle};
+use std::time::Duration;
 use tokio::sync::mpsc::Sender;
 use tracing::{debug, error, info, warn};
+use x11rb::connection::Connection;
+use x11rb::protocol::Event;
+use x11rb::protocol::xproto::Mapping;
 
 use crate::common::constants::{input, paths, permissions};
+use crate::config::HotkeyBinding;
 use crate::input::backend::{
     AllowedWindows, BackendCapabilities, HotkeyBackend, HotkeyConfiguration,
 };
 use crate::input::device_detection;
+use crate::input::gesture::{GestureActions, GestureInput};
+use crate::input::keymap::Keymap;
 use crate::input::listener::{CycleCommand, TimestampedCommand};
 
 pub struct EvdevBackend;
 
+/// Keyboard mapping shared by the listener threads, replaced when the layout changes
+type SharedKeymap = Arc<RwLock<Option<Keymap>>>;
+
 impl HotkeyBackend for EvdevBackend {
     fn spawn(
         sender: Sender<TimestampedCommand>,
@@ -96,19 +107,61 @@ fn spawn_listener_impl(
 
             let mut required_devices = std::collections::HashSet::new();
             for (_, binding) in &config.cycle_hotkeys {
-                required_devices.extend(binding.source_devices.iter().cloned());
+                required_devices.extend(
+                    binding
+                        .source_devices
+                        .iter()
+                        .chain(&binding.device)
+                        .cloned(),
+                );
             }
             for binding in &config.character_hotkeys {
-                required_devices.extend(binding.source_devices.iter().cloned());
+                required_devices.extend(
+                    binding
+                        .source_devices
+                        .iter()
+                        .chain(&binding.device)
+                        .cloned(),
+                );
             }
             for binding in &config.profile_hotkeys {
-                required_devices.extend(binding.source_devices.iter().cloned());
+                required_devices.extend(
+                    binding
+                        .source_devices
+                        .iter()
+                        .chain(&binding.device)
+                        .cloned(),
+                );
             }
             if let Some(ref skip) = config.toggle_skip_key {
-                required_devices.extend(skip.source_devices.iter().cloned());
+                required_devices.extend(skip.source_devices.iter().chain(&skip.device).cloned());
             }
             if let Some(ref toggle_previews) = config.toggle_previews_key {
-                required_devices.extend(toggle_previews.source_devices.iter().cloned());
+                required_devices.extend(
+                    toggle_previews
+                        .source_devices
+                        .iter()
+                        .chain(&toggle_previews.device)
+                        .cloned(),
+                );
+            }
+            if let Some(ref save_positions) = config.save_positions_key {
+                required_devices.extend(
+                    save_positions
+                        .source_devices
+                        .iter()
+                        .chain(&save_positions.device)
+                        .cloned(),
+                );
+            }
+            if let Some(ref undo_move) = config.undo_move_key {
+                required_devices.extend(
+                    undo_move
+                        .source_devices
+                        .iter()
+                        .chain(&undo_move.device)
+                        .cloned(),
+                );
             }
 
             if required_devices.is_empty() {
@@ -168,17 +221,25 @@ fn spawn_listener_impl(
     // Share all device paths so each listener can query modifier state from all devices
     let all_device_paths = Arc::new(all_device_paths);
 
+    // evdev sees no layout changes, so the X server's mapping is watched separately for
+    // bindings by symbol
+    let keymap: SharedKeymap = Arc::new(RwLock::new(config.keymap()));
+
     let cycle_configured = !config.cycle_hotkeys.is_empty();
     let has_character_hotkeys = !config.character_hotkeys.is_empty();
     let has_profile_hotkeys = !config.profile_hotkeys.is_empty();
     let has_skip_key = config.toggle_skip_key.is_some();
     let has_toggle_previews_key = config.toggle_previews_key.is_some();
+    let has_save_positions_key = config.save_positions_key.is_some();
+    let has_undo_move_key = config.undo_move_key.is_some();
 
     if cycle_configured
         || has_character_hotkeys
         || has_profile_hotkeys
         || has_skip_key
         || has_toggle_previews_key
+        || has_save_positions_key
+        || has_undo_move_key
     {
         info!(
             cycle_hotkey_count = config.cycle_hotkeys.len(),
@@ -186,6 +247,8 @@ fn spawn_listener_impl(
             profile_hotkey_count = config.profile_hotkeys.len(),
             has_skip_key = has_skip_key,
             has_toggle_previews_key = has_toggle_previews_key,
+            has_save_positions_key = has_save_positions_key,
+            has_undo_move_key = has_undo_move_key,
             device_count = devices.len(),
             "Starting hotkey listeners"
         );
@@ -194,14 +257,44 @@ fn spawn_listener_impl(
         return Ok(Vec::new());
     }
 
+    let gestures = config.gesture_dispatcher(&sender).map(|(tx, handle)| {
+        handles.push(handle);
+        tx
+    });
+
+    if keymap
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .is_some()
+    {
+        let display = config.display.clone();
+        let keymap = Arc::downgrade(&keymap);
+        handles.push(thread::spawn(move || {
+            if let Err(e) = watch_keymap(display.as_deref(), keymap) {
+                warn!(error = %e, "Hotkeys bound by symbol will not follow layout changes");
+            }
+        }));
+    }
+
     for (device, device_path) in devices {
         let sender = sender.clone();
+        let gestures = gestures.clone();
         let config = config.clone();
         let all_device_paths = Arc::clone(&all_device_paths);
+        let keymap = Arc::clone(&keymap);
 
         let handle = thread::spawn(move || {
-            info!(device = ?device.name(), path = %device_path.display(), "Hotkey listener started");
-            if let Err(e) = listen_for_hotkeys(device, sender, config, all_device_paths) {
+            let device_id = device_detection::extract_device_id(&device_path);
+            info!(device = ?device.name(), path = %device_path.display(), device_id = %device_id, "Hotkey listener started");
+            if let Err(e) = listen_for_hotkeys(
+                device,
+                sender,
+                config,
+                all_device_paths,
+                device_id,
+                keymap,
+                gestures,
+            ) {
                 error!(error = %e, "Hotkey listener error");
             }
         });
@@ -217,9 +310,16 @@ fn listen_for_hotkeys(
     sender: Sender<TimestampedCommand>,
     config: HotkeyConfiguration,
     all_device_paths: Arc<Vec<std::path::PathBuf>>,
+    device_id: String,
+    keymap: SharedKeymap,
+    gestures: Option<std::sync::mpsc::Sender<GestureInput>>,
 ) -> Result<()> {
+    // Keys handed to the gesture dispatcher, whose release it needs to see
+    let mut gesture_keys_down = HashSet::new();
     loop {
         let events = device.fetch_events().context("Failed to fetch events")?;
+        let mapping = keymap.read().unwrap_or_else(PoisonError::into_inner);
+        let keymap = mapping.as_ref();
 
         let mut potential_hotkey_presses = Vec::new();
 
@@ -234,34 +334,53 @@ fn listen_for_hotkeys(
 
             debug!(key_code = key_code, value = event.value(), "Key event");
 
+            if event.value() == input::KEY_RELEASE
+                && gesture_keys_down.remove(&key_code)
+                && let Some(gestures) = &gestures
+            {
+                gestures
+                    .send(GestureInput::Release { key: key_code })
+                    .context("Failed to send hotkey gesture")?;
+            }
+
             // Collect non-modifier key presses that might be hotkeys
             if pressed {
                 let is_cycle_key = config
                     .cycle_hotkeys
                     .iter()
-                    .any(|(_, hk)| hk.key_code == key_code);
+                    .any(|(_, hk)| hk.resolved_key_code(keymap) == key_code);
                 let is_character_key = config
                     .character_hotkeys
                     .iter()
-                    .any(|hk| hk.key_code == key_code);
+                    .any(|hk| hk.resolved_key_code(keymap) == key_code);
                 let is_profile_key = config
                     .profile_hotkeys
                     .iter()
-                    .any(|hk| hk.key_code == key_code);
+                    .any(|hk| hk.resolved_key_code(keymap) == key_code);
                 let is_skip_key = config
                     .toggle_skip_key
                     .as_ref()
-                    .is_some_and(|k| k.key_code == key_code);
+                    .is_some_and(|k| k.resolved_key_code(keymap) == key_code);
                 let is_toggle_previews_key = config
                     .toggle_previews_key
                     .as_ref()
-                    .is_some_and(|k| k.key_code == key_code);
+                    .is_some_and(|k| k.resolved_key_code(keymap) == key_code);
+                let is_save_positions_key = config
+                    .save_positions_key
+                    .as_ref()
+                    .is_some_and(|k| k.resolved_key_code(keymap) == key_code);
+                let is_undo_move_key = config
+                    .undo_move_key
+                    .as_ref()
+                    .is_some_and(|k| k.resolved_key_code(keymap) == key_code);
 
                 if is_cycle_key
                     || is_character_key
                     || is_profile_key
                     || is_skip_key
                     || is_toggle_previews_key
+                    || is_save_positions_key
+                    || is_undo_move_key
                 {
                     // Capture timestamp from the event
                     let timestamp = event.timestamp();
@@ -269,13 +388,13 @@ fn listen_for_hotkeys(
                         .duration_since(std::time::UNIX_EPOCH)
                         .unwrap_or_default()
                         .as_millis() as u32;
-                    potential_hotkey_presses.push((key_code, millis));
+                    potential_hotkey_presses.push((key_code, millis, std::time::Instant::now()));
                 }
             }
         }
 
         // For each potential hotkey, query current modifier state from ALL devices
-        for (key_code, timestamp) in potential_hotkey_presses {
+        for (key_code, timestamp, received) in potential_hotkey_presses {
             // Query modifier state across all devices to handle cross-device hotkeys
             // (e.g., Shift held on keyboard + Mouse Button pressed on mouse)
             let mut ctrl_pressed = false;
@@ -298,115 +417,122 @@ fn listen_for_hotkeys(
                 }
             }
 
-            // Check cycle hotkeys first
-            let mut handled = false;
-            let mut command_to_send = None;
-
-            for (cmd, binding) in &config.cycle_hotkeys {
-                if binding.matches(
-                    key_code,
-                    ctrl_pressed,
-                    shift_pressed,
-                    alt_pressed,
-                    super_pressed,
-                ) {
-                    info!(
-                        binding = %binding.display_name(),
-                        command = ?cmd,
-                        "Cycle hotkey pressed, sending command"
-                    );
-                    command_to_send = Some(cmd.clone());
-                    handled = true;
-                    break;
-                }
-            }
-
-            if !handled
-                && let Some(ref skip_key) = config.toggle_skip_key
-                && skip_key.matches(
-                    key_code,
-                    ctrl_pressed,
-                    shift_pressed,
-                    alt_pressed,
-                    super_pressed,
-                )
-            {
-                info!(
-                    binding = %skip_key.display_name(),
-                    "Toggle skip hotkey pressed, sending command"
-                );
-                command_to_send = Some(CycleCommand::ToggleSkip);
-                handled = true;
-            }
-
-            if !handled
-                && let Some(ref toggle_previews_key) = config.toggle_previews_key
-                && toggle_previews_key.matches(
-                    key_code,
-                    ctrl_pressed,
-                    shift_pressed,
-                    alt_pressed,
-                    super_pressed,
-                )
-            {
-                info!(
-                    binding = %toggle_previews_key.display_name(),
-                    "Toggle previews hotkey pressed, sending command"
-                );
-                command_to_send = Some(CycleCommand::TogglePreviews);
-                handled = true;
-            }
-
-            if !handled {
-                // Check per-character hotkeys
-                for char_hotkey in &config.character_hotkeys {
-                    if char_hotkey.matches(
-                        key_code,
+            // Bindings restricted to another device ignore presses on this one
+            let fires = |binding: &HotkeyBinding| {
+                binding.accepts_device(&device_id)
+                    && binding.resolved_key_code(keymap) == key_code
+                    && binding.modifiers_match(
                         ctrl_pressed,
                         shift_pressed,
                         alt_pressed,
                         super_pressed,
-                    ) {
-                        info!(
-                            binding = %char_hotkey.display_name(),
-                            "Per-character hotkey pressed, sending command"
-                        );
-                        command_to_send = Some(CycleCommand::CharacterHotkey(char_hotkey.clone()));
-                        break; // Only send one command per keypress
-                    }
-                }
-            }
+                    )
+            };
 
-            if !handled && command_to_send.is_none() {
-                // Check profile hotkeys
-                for profile_hotkey in &config.profile_hotkeys {
-                    if profile_hotkey.matches(
-                        key_code,
-                        ctrl_pressed,
-                        shift_pressed,
-                        alt_pressed,
-                        super_pressed,
-                    ) {
-                        info!(
-                            binding = %profile_hotkey.display_name(),
-                            "Profile hotkey pressed, sending command"
-                        );
-                        command_to_send = Some(CycleCommand::ProfileHotkey(profile_hotkey.clone()));
-                        break; // Only send one command per keypress
-                    }
-                }
-            }
+            let actions = GestureActions::from_fn(|gesture| {
+                find_command(&config, |binding| {
+                    binding.gesture == gesture && fires(binding)
+                })
+            });
 
-            if let Some(command) = command_to_send {
-                let timestamped_command = TimestampedCommand { command, timestamp };
+            if let Some(command) = actions.immediate() {
+                info!(command = ?command, "Hotkey pressed, sending command");
+                let timestamped_command = TimestampedCommand {
+                    command: command.clone(),
+                    timestamp,
+                    received,
+                };
                 sender
                     .blocking_send(timestamped_command)
                     .context("Failed to send hotkey command")?;
+            } else if !actions.is_empty()
+                && let Some(gestures) = &gestures
+            {
+                gesture_keys_down.insert(key_code);
+                gestures
+                    .send(GestureInput::Press {
+                        key: key_code,
+                        actions: Box::new(actions),
+                        timestamp,
+                    })
+                    .context("Failed to send hotkey gesture")?;
             }
         }
     }
 }
 
+/// Replace `keymap` whenever the X server announces a new keyboard mapping, as the X11
+/// backend does for its own grabs. Ends once the listeners sharing `keymap` are gone.
+fn watch_keymap(display: Option<&str>, keymap: Weak<RwLock<Option<Keymap>>>) -> Result<()> {
+    // Every client receives MappingNotify, no event selection needed
+    let (conn, _) = x11rb::connect(display).context("Failed to connect to X11 server")?;
+    let poll = Duration::from_millis(input::KEYMAP_POLL_MS);
+    loop {
+        while let Some(event) = conn
+            .poll_for_event()
+            .context("Failed to poll for X11 events")?
+        {
+            let Event::MappingNotify(notify) = event else {
+                continue;
+            };
+            if notify.request != Mapping::KEYBOARD {
+                continue;
+            }
+            let Some(keymap) = keymap.upgrade() else {
+                return Ok(());
+            };
+            match Keymap::query(&conn) {
+                Ok(new) => {
+                    *keymap.write().unwrap_or_else(PoisonError::into_inner) = Some(new);
+                    info!("Keyboard mapping changed, moved hotkeys bound by symbol");
+                }
+                Err(e) => warn!(error = %e, "Failed to read new keyboard mapping"),
+            }
+        }
+        if keymap.strong_count() == 0 {
+            return Ok(());
+        }
+        thread::sleep(poll);
+    }
+}
+
+/// Command of the first binding `fires` accepts, checking cycle hotkeys first, then the
+/// other hotkeys, per-character and profile hotkeys
+fn find_command(
+    config: &HotkeyConfiguration,
+    fires: impl Fn(&HotkeyBinding) -> bool,
+) -> Option<CycleCommand> {
+    if let Some((command, _)) = config
+        .cycle_hotkeys
+        .iter()
+        .find(|(_, binding)| fires(binding))
+    {
+        return Some(command.clone());
+    }
+
+    let others = [
+        (&config.toggle_skip_key, CycleCommand::ToggleSkip),
+        (&config.toggle_previews_key, CycleCommand::TogglePreviews),
+        (&config.save_positions_key, CycleCommand::SavePositions),
+        (&config.undo_move_key, CycleCommand::UndoMove),
+    ];
+    if let Some((_, command)) = others
+        .into_iter()
+        .find(|(binding, _)| binding.as_ref().is_some_and(&fires))
+    {
+        return Some(command);
+    }
+
+    if let Some(binding) = config.character_hotkeys.iter().find(|b| fires(b)) {
+        return Some(CycleCommand::CharacterHotkey(binding.clone()));
+    }
+    config
+        .profile_hotkeys
+        .iter()
+        .find(|b| fires(b))
+        .map(|binding| CycleCommand::ProfileHotkey(binding.clone()))
+}
+
 /// Check if hotkeys are available (user has input group permissions)
 pub fn check_permissions() -> bool {
     std::fs::read_dir(paths::DEV_INPUT).is_ok()
@@ -0,0 +1,348 @@
+//! Double-tap and long-press hotkeys
+//!
+//! A key with only a plain press binding fires the moment it goes down. Once a key also
+//! carries a double-tap or long-press binding, the press is ambiguous until the key is
+//! released, pressed again or held long enough, so backends hand such keys to a dispatcher
+//! thread that waits for the gesture to complete before sending its command.
+
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use tokio::sync::mpsc::Sender;
+use tracing::{debug, error, info};
+
+use crate::config::HotkeyBinding;
+use crate::config::hotkey_binding::Gesture;
+use crate::input::listener::{CycleCommand, TimestampedCommand};
+
+/// Commands one key sends for each gesture
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GestureActions {
+    pub press: Option<CycleCommand>,
+    pub double_tap: Option<CycleCommand>,
+    pub long_press: Option<CycleCommand>,
+}
+
+impl GestureActions {
+    /// Collect the command of each gesture, e.g. by matching the bindings of a pressed key
+    pub fn from_fn(mut command: impl FnMut(Gesture) -> Option<CycleCommand>) -> Self {
+        Self {
+            press: command(Gesture::Press),
+            double_tap: command(Gesture::DoubleTap),
+            long_press: command(Gesture::LongPress),
+        }
+    }
+
+    pub fn get_mut(&mut self, gesture: Gesture) -> &mut Option<CycleCommand> {
+        match gesture {
+            Gesture::Press => &mut self.press,
+            Gesture::DoubleTap => &mut self.double_tap,
+            Gesture::LongPress => &mut self.long_press,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.press.is_none() && self.double_tap.is_none() && self.long_press.is_none()
+    }
+
+    /// The press command, if the key has no other gesture and can fire right away
+    pub fn immediate(&self) -> Option<&CycleCommand> {
+        if self.double_tap.is_none() && self.long_press.is_none() {
+            self.press.as_ref()
+        } else {
+            None
+        }
+    }
+}
+
+/// Key events a backend reports for keys with gesture bindings
+#[derive(Debug)]
+pub enum GestureInput {
+    Press {
+        key: u16,
+        actions: Box<GestureActions>,
+        /// X11-compatible timestamp of the press, passed on with the command
+        timestamp: u32,
+    },
+    Release {
+        key: u16,
+    },
+}
+
+#[derive(Debug)]
+struct Pending {
+    key: u16,
+    actions: GestureActions,
+    timestamp: u32,
+    pressed_at: Instant,
+    /// Set once released; the key then waits for a second tap
+    released_at: Option<Instant>,
+    /// A gesture already fired; the rest of this press is ignored
+    done: bool,
+}
+
+/// Turns presses and releases into gesture commands
+#[derive(Debug)]
+pub struct GestureDetector {
+    double_tap: Duration,
+    long_press: Duration,
+    pending: Option<Pending>,
+}
+
+impl GestureDetector {
+    pub fn new(double_tap: Duration, long_press: Duration) -> Self {
+        Self {
+            double_tap,
+            long_press,
+            pending: None,
+        }
+    }
+
+    pub fn press(
+        &mut self,
+        key: u16,
+        actions: GestureActions,
+        timestamp: u32,
+        now: Instant,
+    ) -> Vec<(CycleCommand, u32)> {
+        let mut fired = Vec::new();
+        if let Some(pending) = &mut self.pending
+            && pending.key == key
+        {
+            match pending.released_at {
+                // Auto-repeat while held
+                None => return fired,
+                Some(_) if !pending.done && pending.actions.double_tap.is_some() => {
+                    fired.extend(pending.actions.double_tap.clone().map(|c| (c, timestamp)));
+                    pending.released_at = None;
+                    pending.done = true;
+                    return fired;
+                }
+                Some(_) => {}
+            }
+        }
+
+        // Another key interrupts a waiting tap, which then counts as a plain press
+        fired.extend(self.finish());
+        self.pending = Some(Pending {
+            key,
+            actions,
+            timestamp,
+            pressed_at: now,
+            released_at: None,
+            done: false,
+        });
+        fired
+    }
+
+    pub fn release(&mut self, key: u16, now: Instant) -> Vec<(CycleCommand, u32)> {
+        let Some(pending) = &mut self.pending else {
+            return Vec::new();
+        };
+        if pending.key != key || pending.released_at.is_some() {
+            return Vec::new();
+        }
+        if pending.done {
+            self.pending = None;
+            return Vec::new();
+        }
+        if pending.actions.double_tap.is_some() {
+            pending.released_at = Some(now);
+            return Vec::new();
+        }
+        self.finish().into_iter().collect()
+    }
+
+    /// Fire gestures whose time ran out by `now`
+    pub fn poll(&mut self, now: Instant) -> Vec<(CycleCommand, u32)> {
+        let Some(pending) = &mut self.pending else {
+            return Vec::new();
+        };
+        match pending.released_at {
+            None if !pending.done && now.duration_since(pending.pressed_at) >= self.long_press => {
+                pending.done = true;
+                pending
+                    .actions
+                    .long_press
+                    .clone()
+                    .map(|c| (c, pending.timestamp))
+                    .into_iter()
+                    .collect()
+            }
+            Some(released_at) if now.duration_since(released_at) >= self.double_tap => {
+                self.finish().into_iter().collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// When `poll` next has something to do
+    pub fn next_deadline(&self) -> Option<Instant> {
+        let pending = self.pending.as_ref()?;
+        match pending.released_at {
+            None if !pending.done && pending.actions.long_press.is_some() => {
+                Some(pending.pressed_at + self.long_press)
+            }
+            None => None,
+            Some(released_at) => Some(released_at + self.double_tap),
+        }
+    }
+
+    /// Drop the pending key, firing its press command unless another gesture already fired
+    fn finish(&mut self) -> Option<(CycleCommand, u32)> {
+        let pending = self.pending.take()?;
+        if pending.done {
+            return None;
+        }
+        pending.actions.press.map(|c| (c, pending.timestamp))
+    }
+}
+
+/// Whether any binding needs the gesture dispatcher
+pub fn uses_gestures<'a>(mut bindings: impl Iterator<Item = &'a HotkeyBinding>) -> bool {
+    bindings.any(|binding| binding.gesture != Gesture::Press)
+}
+
+/// Spawn the thread that resolves gestures and forwards their commands to `sender`
+pub fn spawn_dispatcher(
+    sender: Sender<TimestampedCommand>,
+    double_tap: Duration,
+    long_press: Duration,
+) -> (mpsc::Sender<GestureInput>, thread::JoinHandle<()>) {
+    let (tx, rx) = mpsc::channel();
+    let handle = thread::spawn(move || {
+        let mut detector = GestureDetector::new(double_tap, long_press);
+        loop {
+            let input = match detector.next_deadline() {
+                Some(deadline) => {
+                    match rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                        Ok(input) => Some(input),
+                        Err(mpsc::RecvTimeoutError::Timeout) => None,
+                        Err(mpsc::RecvTimeoutError::Disconnected) => break,
+                    }
+                }
+                None => match rx.recv() {
+                    Ok(input) => Some(input),
+                    Err(_) => break,
+                },
+            };
+
+            let now = Instant::now();
+            let fired = match input {
+                Some(GestureInput::Press {
+                    key,
+                    actions,
+                    timestamp,
+                }) => {
+                    debug!(key = key, "Gesture key pressed");
+                    detector.press(key, *actions, timestamp, now)
+                }
+                Some(GestureInput::Release { key }) => detector.release(key, now),
+                None => detector.poll(now),
+            };
+
+            for (command, timestamp) in fired {
+                info!(command = ?command, "Hotkey gesture completed, sending command");
+                let command = TimestampedCommand {
+                    command,
+                    timestamp,
+                    // The wait for the gesture is intentional, so latency counts from here
+                    received: Instant::now(),
+                };
+                if let Err(e) = sender.blocking_send(command) {
+                    error!(error = %e, "Failed to send hotkey command");
+                    return;
+                }
+            }
+        }
+    });
+    (tx, handle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TAP: Duration = Duration::from_millis(250);
+    const LONG: Duration = Duration::from_millis(500);
+
+    fn actions() -> GestureActions {
+        GestureActions {
+            press: Some(CycleCommand::ActiveForward),
+            double_tap: Some(CycleCommand::ActiveBackward),
+            long_press: Some(CycleCommand::TogglePreviews),
+        }
+    }
+
+    fn commands(fired: Vec<(CycleCommand, u32)>) -> Vec<CycleCommand> {
+        fired.into_iter().map(|(command, _)| command).collect()
+    }
+
+    #[test]
+    fn test_single_tap_fires_after_double_tap_window() {
+        let mut detector = GestureDetector::new(TAP, LONG);
+        let start = Instant::now();
+        let ms = |n| start + Duration::from_millis(n);
+
+        assert!(detector.press(15, actions(), 1, start).is_empty());
+        assert!(detector.release(15, ms(80)).is_empty());
+        assert_eq!(detector.next_deadline(), Some(ms(330)));
+        assert!(detector.poll(ms(200)).is_empty());
+        assert_eq!(
+            commands(detector.poll(ms(330))),
+            vec![CycleCommand::ActiveForward]
+        );
+        assert_eq!(detector.next_deadline(), None);
+    }
+
+    #[test]
+    fn test_double_tap_and_long_press() {
+        let mut detector = GestureDetector::new(TAP, LONG);
+        let start = Instant::now();
+        let ms = |n| start + Duration::from_millis(n);
+
+        detector.press(15, actions(), 1, start);
+        detector.release(15, ms(80));
+        assert_eq!(
+            commands(detector.press(15, actions(), 2, ms(150))),
+            vec![CycleCommand::ActiveBackward]
+        );
+        // Neither the second release nor the wait fires anything else
+        assert!(detector.release(15, ms(200)).is_empty());
+        assert!(detector.poll(ms(1000)).is_empty());
+
+        detector.press(15, actions(), 3, ms(2000));
+        // Auto-repeat while held is ignored
+        assert!(detector.press(15, actions(), 4, ms(2300)).is_empty());
+        assert_eq!(
+            commands(detector.poll(ms(2500))),
+            vec![CycleCommand::TogglePreviews]
+        );
+        assert!(detector.release(15, ms(2700)).is_empty());
+        assert_eq!(detector.next_deadline(), None);
+    }
+
+    #[test]
+    fn test_press_without_double_tap_fires_on_release() {
+        let mut detector = GestureDetector::new(TAP, LONG);
+        let start = Instant::now();
+        let ms = |n| start + Duration::from_millis(n);
+        let mut only_long = actions();
+        only_long.double_tap = None;
+
+        detector.press(15, only_long.clone(), 1, start);
+        assert_eq!(
+            commands(detector.release(15, ms(100))),
+            vec![CycleCommand::ActiveForward]
+        );
+
+        // Another key interrupts a pending tap
+        detector.press(15, actions(), 2, ms(1000));
+        detector.release(15, ms(1050));
+        assert_eq!(
+            commands(detector.press(16, only_long, 3, ms(1100))),
+            vec![CycleCommand::ActiveForward]
+        );
+    }
+}
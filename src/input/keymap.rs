@@ -0,0 +1,137 @@
+//! X keyboard mapping lookups
+//!
+//! Bindings store evdev key codes, i.e. the physical key. The X server's keyboard mapping
+//! tells which symbol the active layout puts on that key, so captured bindings can be shown
+//! with the label printed on the user's keyboard rather than its US QWERTY name.
+
+use std::collections::HashMap;
+
+use anyhow::{Context, Result};
+use x11rb::connection::Connection;
+use x11rb::protocol::xproto::ConnectionExt;
+
+/// X keycodes are evdev key codes offset by 8
+const X11_KEYCODE_OFFSET: u16 = 8;
+
+/// Snapshot of the X server's keycode to keysym table
+pub struct Keymap {
+    min_keycode: u8,
+    keysyms_per_keycode: u8,
+    keysyms: Vec<u32>,
+    /// Lowest evdev code carrying each unshifted or shifted keysym
+    key_codes: HashMap<u32, u16>,
+}
+
+impl Keymap {
+    fn new(min_keycode: u8, keysyms_per_keycode: u8, keysyms: Vec<u32>) -> Self {
+        let mut key_codes = HashMap::new();
+        let per = keysyms_per_keycode as usize;
+        if per > 0 {
+            // Unshifted symbols take precedence over shifted ones
+            for column in 0..per.min(2) {
+                for (index, row) in keysyms.chunks_exact(per).enumerate() {
+                    let keycode = min_keycode as u16 + index as u16;
+                    if let Some(key_code) = keycode.checked_sub(X11_KEYCODE_OFFSET)
+                        && row[column] != xkeysym::NO_SYMBOL.raw()
+                    {
+                        key_codes.entry(row[column]).or_insert(key_code);
+                    }
+                }
+            }
+        }
+        Self {
+            min_keycode,
+            keysyms_per_keycode,
+            keysyms,
+            key_codes,
+        }
+    }
+
+    pub fn query(conn: &impl Connection) -> Result<Self> {
+        let setup = conn.setup();
+        let (min_keycode, max_keycode) = (setup.min_keycode, setup.max_keycode);
+        let reply = conn
+            .get_keyboard_mapping(min_keycode, max_keycode - min_keycode + 1)
+            .context("Failed to request keyboard mapping")?
+            .reply()
+            .context("Failed to get keyboard mapping")?;
+        Ok(Self::new(
+            min_keycode,
+            reply.keysyms_per_keycode,
+            reply.keysyms,
+        ))
+    }
+
+    /// evdev code of the key the layout puts `keysym` on
+    pub fn key_code(&self, keysym: u32) -> Option<u16> {
+        self.key_codes.get(&keysym).copied()
+    }
+
+    /// Unshifted keysym the layout puts on the key with this evdev code
+    pub fn keysym(&self, key_code: u16) -> Option<u32> {
+        let keycode = key_code.checked_add(X11_KEYCODE_OFFSET)?;
+        let index = keycode.checked_sub(self.min_keycode as u16)? as usize;
+        let per = self.keysyms_per_keycode as usize;
+        if per == 0 || (index + 1) * per > self.keysyms.len() {
+            return None;
+        }
+        xkeysym::keysym(
+            xkeysym::KeyCode::new(keycode as u32),
+            0,
+            xkeysym::KeyCode::new(self.min_keycode as u32),
+            self.keysyms_per_keycode,
+            &self.keysyms,
+        )
+        .map(|keysym| keysym.raw())
+        .filter(|&keysym| keysym != xkeysym::NO_SYMBOL.raw())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keysym_lookup() {
+        // Two keysyms per keycode starting at X keycode 10 (evdev KEY_1): AZERTY's "&"/"1"
+        // and the numpad's KP_End/KP_1
+        let keymap = Keymap::new(10, 2, vec![0x26, 0x31, 0xff9c, 0xffb1]);
+        assert_eq!(keymap.keysym(2), Some(0x26));
+        assert_eq!(keymap.keysym(3), Some(0xff9c));
+        // Outside the table
+        assert_eq!(keymap.keysym(1), None);
+        assert_eq!(keymap.keysym(4), None);
+
+        // Reverse lookup finds shifted symbols too
+        assert_eq!(keymap.key_code(0x26), Some(2));
+        assert_eq!(keymap.key_code(0x31), Some(2));
+        assert_eq!(keymap.key_code(0x61), None);
+    }
+
+    #[test]
+    fn test_binding_follows_symbol() {
+        use crate::config::HotkeyBinding;
+        use crate::config::hotkey_binding::KeyMatch;
+
+        // "&" captured on KEY_1, then the layout moves it to KEY_2
+        let mut binding = HotkeyBinding::new(2, false, false, false, false);
+        binding.keysym = Some(0x26);
+        let moved = Keymap::new(10, 2, vec![0x31, 0x21, 0x26, 0x32]);
+
+        assert_eq!(binding.resolved_key_code(Some(&moved)), 2);
+        binding.match_by = KeyMatch::Keysym;
+        assert_eq!(binding.resolved_key_code(Some(&moved)), 3);
+        assert_eq!(binding.resolved_key_code(None), 2);
+
+        // A symbol missing from the layout falls back to the captured key
+        binding.keysym = Some(0x61);
+        assert_eq!(binding.resolved_key_code(Some(&moved)), 2);
+
+        let json = serde_json::to_string(&binding).unwrap();
+        assert!(json.ends_with(r#""keysym":97,"match_by":"keysym"}"#));
+        assert_eq!(
+            serde_json::from_str::<HotkeyBinding>(&json).unwrap(),
+            binding
+        );
+    }
+}
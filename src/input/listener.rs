@@ -15,6 +15,14 @@ pub enum CycleCommand {
     Forward(String),
     /// Cycle backward in the specified group
     Backward(String),
+    /// Cycle forward in the currently selected group
+    ActiveForward,
+    /// Cycle backward in the currently selected group
+    ActiveBackward,
+    /// Select the next cycle group for `ActiveForward`/`ActiveBackward`
+    NextGroup,
+    /// Hide or show the thumbnails of the specified group (ephemeral)
+    ToggleGroupVisibility(String),
     /// Triggered when a character-specific hotkey is pressed, carrying its binding configuration for context
     CharacterHotkey(HotkeyBinding),
     /// Triggered when a profile switch hotkey is pressed
@@ -23,6 +31,17 @@ pub enum CycleCommand {
     ToggleSkip,
     /// Triggered when the toggle previews hotkey is pressed (ephemeral)
     TogglePreviews,
+    /// Triggered when the save positions hotkey is pressed
+    SavePositions,
+    /// Triggered when the undo move hotkey is pressed
+    UndoMove,
+    /// Save a screenshot of the focused EVE client
+    Screenshot,
+    /// Show or hide each character's hotkey on its thumbnail (ephemeral)
+    ToggleHotkeyOverlay,
+    /// Swap the focused client's thumbnail with the next one clicked or picked by character
+    /// hotkey; pressed again it cancels
+    SwapPositions,
 }
 
 /// A wrapper around CycleCommand that includes the timestamp of the input event
@@ -31,6 +50,8 @@ pub struct TimestampedCommand {
     pub command: CycleCommand,
     /// X11-compatible timestamp (milliseconds)
     pub timestamp: u32,
+    /// When the backend read the input event, for measuring hotkey latency
+    pub received: std::time::Instant,
 }
 
 /// Print helpful error message if evdev permissions are missing
@@ -3,5 +3,7 @@
 pub mod backend;
 pub mod device_detection;
 pub mod evdev_backend;
+pub mod gesture;
+pub mod keymap;
 pub mod listener;
 pub mod x11_backend;
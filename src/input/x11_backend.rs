@@ -11,7 +11,6 @@
 use anyhow::{Context, Result};
 use std::collections::HashMap;
 use std::os::unix::io::AsRawFd;
-use std::sync::Arc;
 use std::thread::{self, JoinHandle};
 use tokio::sync::mpsc::Sender;
 use tracing::{debug, error, info, warn};
@@ -22,9 +21,12 @@ use x11rb::protocol::xproto::*;
 use x11rb::rust_connection::RustConnection;
 
 use crate::config::HotkeyBinding;
+use crate::config::hotkey_binding::KeyMatch;
 use crate::input::backend::{
     AllowedWindows, BackendCapabilities, HotkeyBackend, HotkeyConfiguration,
 };
+use crate::input::gesture::{GestureActions, GestureInput};
+use crate::input::keymap::Keymap;
 use crate::input::listener::{CycleCommand, TimestampedCommand};
 
 pub struct X11Backend;
@@ -43,8 +45,17 @@ impl HotkeyBackend for X11Backend {
         let has_profile = !config.profile_hotkeys.is_empty();
         let has_skip = config.toggle_skip_key.is_some();
         let has_toggle_previews = config.toggle_previews_key.is_some();
-
-        if !has_cycle && !has_character && !has_profile && !has_skip && !has_toggle_previews {
+        let has_save_positions = config.save_positions_key.is_some();
+        let has_undo_move = config.undo_move_key.is_some();
+
+        if !has_cycle
+            && !has_character
+            && !has_profile
+            && !has_skip
+            && !has_toggle_previews
+            && !has_save_positions
+            && !has_undo_move
+        {
             info!("No hotkeys configured - X11 listener will not be started");
             return Ok(Vec::new());
         }
@@ -53,17 +64,28 @@ impl HotkeyBackend for X11Backend {
             has_cycle_keys = has_cycle,
             has_skip_key = has_skip,
             has_toggle_previews_key = has_toggle_previews,
+            has_save_positions_key = has_save_positions,
+            has_undo_move_key = has_undo_move,
             character_hotkey_count = config.character_hotkeys.len(),
             "Starting X11 hotkey listener"
         );
 
+        let mut handles = Vec::new();
+        let gestures = config.gesture_dispatcher(&sender).map(|(tx, handle)| {
+            handles.push(handle);
+            tx
+        });
+
         let handle = thread::spawn(move || {
-            if let Err(e) = run_x11_listener(sender, config, require_eve_focus, allowed_windows) {
+            if let Err(e) =
+                run_x11_listener(sender, config, require_eve_focus, allowed_windows, gestures)
+            {
                 error!(error = %e, "X11 hotkey listener error");
             }
         });
+        handles.push(handle);
 
-        Ok(vec![handle])
+        Ok(handles)
     }
 
     fn is_available() -> bool {
@@ -92,107 +114,32 @@ fn run_x11_listener(
     config: HotkeyConfiguration,
     require_eve_focus: bool,
     allowed_windows: AllowedWindows,
+    gestures: Option<std::sync::mpsc::Sender<GestureInput>>,
 ) -> Result<()> {
     // Connect to X11
-    let (conn, screen_num) =
-        x11rb::connect(None).context("Failed to connect to X11 for hotkey listening")?;
+    let (conn, screen_num) = x11rb::connect(config.display.as_deref())
+        .context("Failed to connect to X11 for hotkey listening")?;
 
     let screen = &conn.setup().roots[screen_num];
     let root = screen.root;
 
     debug!("X11 hotkey listener connected to display");
 
-    // Build a map of (keycode, modifiers) -> CycleCommand
-    let mut hotkey_map: HashMap<(Keycode, ModMask), CycleCommand> = HashMap::new();
-
-    // Register cycle hotkeys
-    let cycle_hotkeys = Arc::new(config.cycle_hotkeys);
-    for (command, cycle_hotkey) in cycle_hotkeys.iter() {
-        if let Some((keycode, modmask)) = evdev_to_x11_key(cycle_hotkey) {
-            register_hotkey(&conn, root, keycode, modmask)?;
-            hotkey_map.insert((keycode, modmask), command.clone());
-            debug!(
-                binding = %cycle_hotkey.display_name(),
-                x11_keycode = keycode,
-                modmask = ?modmask,
-                command = ?command,
-                "Registered cycle hotkey"
-            );
-        } else {
-            warn!(binding = %cycle_hotkey.display_name(), "Failed to map cycle hotkey to X11");
-        }
-    }
+    // Bindings matching by symbol need the layout to find their key
+    let mut keymap = config.keymap();
+    let follows_layout = config
+        .bindings()
+        .any(|binding| binding.match_by == KeyMatch::Keysym);
 
-    // Register toggle skip hotkey
-    if let Some(ref skip_key) = config.toggle_skip_key {
-        if let Some((keycode, modmask)) = evdev_to_x11_key(skip_key) {
-            register_hotkey(&conn, root, keycode, modmask)?;
-            hotkey_map.insert((keycode, modmask), CycleCommand::ToggleSkip);
-            debug!(
-                binding = %skip_key.display_name(),
-                x11_keycode = keycode,
-                modmask = ?modmask,
-                "Registered toggle skip hotkey"
-            );
-        } else {
-            warn!(binding = %skip_key.display_name(), "Failed to map toggle skip key to X11");
-        }
+    let mut hotkey_map = build_hotkey_map(&config, keymap.as_ref());
+    for (keycode, modmask) in hotkey_map.keys() {
+        register_hotkey(&conn, root, *keycode, *modmask)?;
     }
 
-    // Register toggle previews hotkey
-    if let Some(ref toggle_previews_key) = config.toggle_previews_key {
-        if let Some((keycode, modmask)) = evdev_to_x11_key(toggle_previews_key) {
-            register_hotkey(&conn, root, keycode, modmask)?;
-            hotkey_map.insert((keycode, modmask), CycleCommand::TogglePreviews);
-            debug!(
-                binding = %toggle_previews_key.display_name(),
-                x11_keycode = keycode,
-                modmask = ?modmask,
-                "Registered toggle previews hotkey"
-            );
-        } else {
-            warn!(binding = %toggle_previews_key.display_name(), "Failed to map toggle previews key to X11");
-        }
-    }
-
-    // Register character hotkeys
-    let character_hotkeys = Arc::new(config.character_hotkeys);
-    for char_hotkey in character_hotkeys.iter() {
-        if let Some((keycode, modmask)) = evdev_to_x11_key(char_hotkey) {
-            register_hotkey(&conn, root, keycode, modmask)?;
-            hotkey_map.insert(
-                (keycode, modmask),
-                CycleCommand::CharacterHotkey(char_hotkey.clone()),
-            );
-            debug!(
-                binding = %char_hotkey.display_name(),
-                x11_keycode = keycode,
-                modmask = ?modmask,
-                "Registered character hotkey"
-            );
-        } else {
-            warn!(binding = %char_hotkey.display_name(), "Failed to map character hotkey to X11");
-        }
-    }
-
-    // Register profile hotkeys
-    let profile_hotkeys = Arc::new(config.profile_hotkeys);
-    for profile_hotkey in profile_hotkeys.iter() {
-        if let Some((keycode, modmask)) = evdev_to_x11_key(profile_hotkey) {
-            register_hotkey(&conn, root, keycode, modmask)?;
-            hotkey_map.insert(
-                (keycode, modmask),
-                CycleCommand::ProfileHotkey(profile_hotkey.clone()),
-            );
-            debug!(
-                binding = %profile_hotkey.display_name(),
-                x11_keycode = keycode,
-                modmask = ?modmask,
-                "Registered profile hotkey"
-            );
-        } else {
-            warn!(binding = %profile_hotkey.display_name(), "Failed to map profile hotkey to X11");
-        }
+    // Gestures need to tell held keys from taps, so auto-repeat must not send releases
+    let mut gesture_keys_down = std::collections::HashSet::new();
+    if gestures.is_some() {
+        enable_detectable_auto_repeat(&conn);
     }
 
     conn.flush().context("Failed to flush X11 connection")?;
@@ -211,6 +158,13 @@ fn run_x11_listener(
 
     // Event loop - block on X11 fd with timeout for focus checking
     loop {
+        // The daemon restarts hotkeys by dropping the receiver; returning closes the
+        // connection, which releases the grabs
+        if sender.is_closed() {
+            debug!("Hotkey receiver closed, stopping X11 hotkey listener");
+            return Ok(());
+        }
+
         // Use poll() to block with 250ms timeout
         // This gives us:
         // - Zero CPU usage when idle (thread sleeps in kernel)
@@ -326,7 +280,8 @@ fn run_x11_listener(
                         let modmask = normalize_modmask(key_event.state);
 
                         // Look up the hotkey
-                        if let Some(command) = hotkey_map.get(&(key_event.detail, modmask)) {
+                        let actions = hotkey_map.get(&(key_event.detail, modmask));
+                        if let Some(command) = actions.and_then(GestureActions::immediate) {
                             debug!(
                                 keycode = key_event.detail,
                                 modmask = ?modmask,
@@ -337,11 +292,24 @@ fn run_x11_listener(
                             let timestamped_command = TimestampedCommand {
                                 command: command.clone(),
                                 timestamp: key_event.time,
+                                received: std::time::Instant::now(),
                             };
 
                             if let Err(e) = sender.blocking_send(timestamped_command) {
                                 error!(error = %e, "Failed to send hotkey command");
                             }
+                        } else if let Some(actions) = actions
+                            && let Some(gestures) = &gestures
+                        {
+                            gesture_keys_down.insert(key_event.detail);
+                            let input = GestureInput::Press {
+                                key: key_event.detail as u16,
+                                actions: Box::new(actions.clone()),
+                                timestamp: key_event.time,
+                            };
+                            if gestures.send(input).is_err() {
+                                error!("Gesture dispatcher stopped");
+                            }
                         } else {
                             debug!(
                                 keycode = key_event.detail,
@@ -350,12 +318,40 @@ fn run_x11_listener(
                             );
                         }
                     }
-                    Event::MappingNotify(_) => {
-                        // Keyboard mapping changed, we should re-register hotkeys
-                        // For now, just log it - full implementation would rebuild the map
-                        warn!(
-                            "Keyboard mapping changed - hotkeys may not work correctly until restart"
-                        );
+                    Event::KeyRelease(key_event) => {
+                        if gesture_keys_down.remove(&key_event.detail)
+                            && let Some(gestures) = &gestures
+                            && gestures
+                                .send(GestureInput::Release {
+                                    key: key_event.detail as u16,
+                                })
+                                .is_err()
+                        {
+                            error!("Gesture dispatcher stopped");
+                        }
+                    }
+                    Event::MappingNotify(notify) => {
+                        // Physical-key bindings stay put; bindings by symbol move with it
+                        if notify.request != Mapping::KEYBOARD || !follows_layout {
+                            continue;
+                        }
+                        keymap = Keymap::query(&conn)
+                            .inspect_err(
+                                |e| warn!(error = %e, "Failed to read new keyboard mapping"),
+                            )
+                            .ok();
+                        let new_map = build_hotkey_map(&config, keymap.as_ref());
+                        if hotkeys_grabbed {
+                            for (keycode, modmask) in hotkey_map.keys() {
+                                ungrab_hotkey(&conn, root, *keycode, *modmask)?;
+                            }
+                            for (keycode, modmask) in new_map.keys() {
+                                register_hotkey(&conn, root, *keycode, *modmask)?;
+                            }
+                            conn.flush()?;
+                        }
+                        hotkey_map = new_map;
+                        info!("Keyboard mapping changed, moved hotkeys bound by symbol");
                     }
                     _ => {
                         // Ignore other events
@@ -421,6 +417,83 @@ fn get_window_class_sync(conn: &RustConnection, window: Window) -> Result<String
     }
 }
 
+/// Ask the server to send no releases for auto-repeated presses (XKB detectable auto-repeat)
+fn enable_detectable_auto_repeat(conn: &RustConnection) {
+    use x11rb::protocol::xkb::{self, ConnectionExt as _};
+
+    let flag = xkb::PerClientFlag::DETECTABLE_AUTO_REPEAT;
+    let result = conn
+        .xkb_use_extension(1, 0)
+        .map_err(anyhow::Error::from)
+        .and_then(|cookie| Ok(cookie.reply()?))
+        .and_then(|_| {
+            let none = xkb::BoolCtrl::from(0u32);
+            Ok(conn
+                .xkb_per_client_flags(xkb::ID::USE_CORE_KBD.into(), flag, flag, none, none, none)?
+                .reply()?)
+        });
+    match result {
+        Ok(reply) if reply.supported.contains(flag) => {
+            debug!("Detectable auto-repeat enabled for hotkey gestures")
+        }
+        Ok(_) => warn!("X server lacks detectable auto-repeat; held gesture keys may act as taps"),
+        Err(e) => warn!(error = %e, "Failed to enable detectable auto-repeat"),
+    }
+}
+
+/// Map each binding's (keycode, modifiers) under `keymap` to the command of each gesture
+fn build_hotkey_map(
+    config: &HotkeyConfiguration,
+    keymap: Option<&Keymap>,
+) -> HashMap<(Keycode, ModMask), GestureActions> {
+    let commands = config
+        .cycle_hotkeys
+        .iter()
+        .map(|(command, binding)| (binding, command.clone()))
+        .chain(
+            [
+                (&config.toggle_skip_key, CycleCommand::ToggleSkip),
+                (&config.toggle_previews_key, CycleCommand::TogglePreviews),
+                (&config.save_positions_key, CycleCommand::SavePositions),
+                (&config.undo_move_key, CycleCommand::UndoMove),
+            ]
+            .into_iter()
+            .filter_map(|(binding, command)| Some((binding.as_ref()?, command))),
+        )
+        .chain(
+            config
+                .character_hotkeys
+                .iter()
+                .map(|binding| (binding, CycleCommand::CharacterHotkey(binding.clone()))),
+        )
+        .chain(
+            config
+                .profile_hotkeys
+                .iter()
+                .map(|binding| (binding, CycleCommand::ProfileHotkey(binding.clone()))),
+        );
+
+    let mut hotkey_map: HashMap<_, GestureActions> = HashMap::new();
+    for (binding, command) in commands {
+        let Some((keycode, modmask)) = evdev_to_x11_key(binding, keymap) else {
+            warn!(binding = %binding.display_name(), command = ?command, "Failed to map hotkey to X11");
+            continue;
+        };
+        debug!(
+            binding = %binding.display_name(),
+            x11_keycode = keycode,
+            modmask = ?modmask,
+            command = ?command,
+            "Registered hotkey"
+        );
+        *hotkey_map
+            .entry((keycode, modmask))
+            .or_default()
+            .get_mut(binding.gesture) = Some(command);
+    }
+    hotkey_map
+}
+
 /// Helper to ungrab a hotkey (reverse of register_hotkey)
 fn ungrab_hotkey(
     conn: &RustConnection,
@@ -501,9 +574,12 @@ fn normalize_modmask(state: KeyButMask) -> ModMask {
 }
 
 /// Convert evdev key binding to X11 keycode and modifier mask
-fn evdev_to_x11_key(binding: &HotkeyBinding) -> Option<(Keycode, ModMask)> {
+fn evdev_to_x11_key(
+    binding: &HotkeyBinding,
+    keymap: Option<&Keymap>,
+) -> Option<(Keycode, ModMask)> {
     // Convert evdev keycode to X11 keycode
-    let x11_keycode = evdev_keycode_to_x11(binding.key_code)?;
+    let x11_keycode = evdev_keycode_to_x11(binding.resolved_key_code(keymap))?;
 
     // Build modifier mask
     let mut modmask = ModMask::from(0u16);
@@ -561,17 +637,17 @@ mod tests {
     fn test_evdev_to_x11_binding() {
         // Simple key (Tab)
         let binding = HotkeyBinding::new(15, false, false, false, false);
-        let result = evdev_to_x11_key(&binding);
+        let result = evdev_to_x11_key(&binding, None);
         assert_eq!(result, Some((23, ModMask::from(0u16))));
 
         // With Shift
         let binding = HotkeyBinding::new(15, false, true, false, false);
-        let result = evdev_to_x11_key(&binding);
+        let result = evdev_to_x11_key(&binding, None);
         assert_eq!(result, Some((23, ModMask::SHIFT)));
 
         // With Ctrl+Alt
         let binding = HotkeyBinding::new(59, true, false, true, false);
-        let result = evdev_to_x11_key(&binding);
+        let result = evdev_to_x11_key(&binding, None);
         assert_eq!(result, Some((67, ModMask::CONTROL | ModMask::M1)));
     }
 
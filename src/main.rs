@@ -5,11 +5,18 @@ mod config;
 mod daemon;
 mod input;
 mod manager;
+#[cfg(test)]
+mod test_support;
 mod x11;
 
+use std::path::PathBuf;
+
 use anyhow::Result;
 use clap::Parser;
-use tracing_subscriber::FmtSubscriber;
+
+use crate::common::constants::config::logs;
+use crate::common::logging;
+use crate::config::profile::{Config, GlobalSettings};
 
 #[derive(Parser, Debug)]
 #[command(name = "eve-preview-manager")]
@@ -22,6 +29,32 @@ struct Cli {
     /// Enable debug mode with verbose logging and system diagnostics
     #[arg(long, global = true)]
     debug: bool,
+
+    /// Log verbosity (overrides `--debug`, `RUST_LOG` and the configured level)
+    #[arg(long, global = true, value_name = "LEVEL", value_parser = logging::LEVELS)]
+    log_level: Option<String>,
+
+    /// Also write daily-rotated log files, optionally into DIR
+    /// (default: the `logs` directory next to the config file)
+    #[arg(long, global = true, value_name = "DIR", num_args = 0..=1)]
+    log_file: Option<Option<PathBuf>>,
+
+    /// Use this config file instead of the default location
+    #[arg(long, global = true, value_name = "PATH")]
+    config: Option<PathBuf>,
+
+    /// Run only the preview daemon without the GUI (e.g. as a systemd user service).
+    /// The Manager attaches to the running daemon when opened.
+    #[arg(long, visible_alias = "preview")]
+    daemon_only: bool,
+
+    /// Profile the daemon runs, instead of the one selected in the config
+    #[arg(long, value_name = "NAME", requires = "daemon_only")]
+    profile: Option<String>,
+
+    /// Start the Manager hidden in the tray (e.g. when launched on login)
+    #[arg(long)]
+    minimized: bool,
 }
 
 #[derive(clap::Subcommand, Debug)]
@@ -33,47 +66,136 @@ enum Commands {
         #[arg(long)]
         ipc_server: String,
     },
+
+    /// Measure thumbnail rendering performance with synthetic source windows
+    Bench {
+        /// Thumbnail counts to measure (comma-separated)
+        #[arg(long, value_delimiter = ',', default_value = "1,4,8,16")]
+        counts: Vec<usize>,
+
+        /// Thumbnail sizes to measure, as WIDTHxHEIGHT (comma-separated)
+        #[arg(long, value_delimiter = ',', default_value = "250x140,480x270", value_parser = daemon::parse_size)]
+        sizes: Vec<common::types::Dimensions>,
+
+        /// Frames measured per run
+        #[arg(long, default_value_t = 120)]
+        frames: u32,
+    },
+
+    /// Check the config file for unknown fields, invalid colors, dangling cycle group
+    /// members, hotkey conflicts and out-of-range sizes. Exits with 1 if errors are found.
+    CheckConfig,
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
 
-    // Initialize logging
-    let filter_directives = if cli.debug {
-        // Debug mode: detailed logs for our app, but keep noisy libraries (x11rb) at info
-        "info,eve_preview_manager=debug"
-    } else {
-        "info,winit=warn"
-    };
-
-    let filter = tracing_subscriber::EnvFilter::try_from_default_env()
-        .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new(filter_directives));
+    if let Some(path) = &cli.config {
+        Config::set_path_override(path.clone());
+    }
 
-    let subscriber = FmtSubscriber::builder().with_env_filter(filter).finish();
-    tracing::subscriber::set_global_default(subscriber).expect("Failed to set tracing subscriber");
+    init_logging(&cli)?;
+    common::crash_report::install_panic_hook(process_name(&cli));
 
     match cli.command {
         Some(Commands::Daemon { ipc_server }) => {
             // Start the dedicated daemon process to isolate X11 rendering and overlay management
-            // Initialize Tokio runtime for the daemon
-            let rt = tokio::runtime::Builder::new_current_thread()
-                .enable_all()
-                .build()
-                .expect("Failed to build Tokio runtime");
+            let rt = build_daemon_runtime();
 
             rt.block_on(async {
-                if let Err(e) = daemon::run_daemon(ipc_server).await {
-                    eprintln!("Daemon error: {e}");
+                if let Err(e) = daemon::run_daemon(daemon::DaemonMode::Managed { ipc_server }).await
+                {
+                    eprintln!("Daemon error: {e:?}");
+                    // Non-zero exit lets the Manager treat this as a crash and restart us
+                    std::process::exit(1);
                 }
             });
             Ok(())
         }
+        Some(Commands::Bench {
+            counts,
+            sizes,
+            frames,
+        }) => daemon::run_bench(&daemon::BenchOptions {
+            counts,
+            sizes,
+            frames,
+        }),
+        Some(Commands::CheckConfig) => {
+            let path = Config::path();
+            let issues = config::validation::check_file(&path)?;
+            println!("{}", path.display());
+            println!("{}", config::validation::format_report(&issues));
+            if issues
+                .iter()
+                .any(|issue| issue.severity == config::validation::Severity::Error)
+            {
+                std::process::exit(1);
+            }
+            Ok(())
+        }
+        None if cli.daemon_only => {
+            // Headless daemon: report failures through the exit code so systemd can restart us
+            if cli.debug {
+                crate::common::debug::log_system_info();
+            }
+            let rt = build_daemon_runtime();
+            rt.block_on(daemon::run_daemon(daemon::DaemonMode::Standalone {
+                profile: cli.profile,
+            }))
+        }
         None => {
             // Default mode: launch the configuration Manager which manages the daemon lifecycle
             if cli.debug {
                 crate::common::debug::log_system_info();
             }
-            manager::run_manager(cli.debug)
+            manager::run_manager(cli.debug, cli.minimized)
         }
     }
 }
+
+/// Initialize logging from the command line, falling back to the configured settings.
+///
+/// A Manager-spawned daemon only honours its command line; the Manager forwards its settings.
+fn init_logging(cli: &Cli) -> Result<()> {
+    let is_managed_daemon = matches!(cli.command, Some(Commands::Daemon { .. }));
+    let global = if is_managed_daemon || !Config::path().exists() {
+        GlobalSettings::default()
+    } else {
+        Config::load().map(|c| c.global).unwrap_or_default()
+    };
+
+    let env_filter = std::env::var("RUST_LOG").ok();
+    let directives = logging::filter_directives(
+        cli.log_level.as_deref(),
+        env_filter.as_deref(),
+        cli.debug,
+        &global.log_level,
+    );
+
+    let log_dir = match &cli.log_file {
+        Some(Some(dir)) => Some(dir.clone()),
+        Some(None) => Some(logging::default_log_dir()),
+        None if global.log_to_file => Some(logging::default_log_dir()),
+        None => None,
+    };
+
+    logging::init(&directives, log_dir.as_deref(), process_name(cli))
+}
+
+/// Name of this process in log file and crash report filenames
+fn process_name(cli: &Cli) -> &'static str {
+    if cli.daemon_only || matches!(cli.command, Some(Commands::Daemon { .. })) {
+        logs::DAEMON_PREFIX
+    } else {
+        logs::MANAGER_PREFIX
+    }
+}
+
+/// Initialize the single-threaded Tokio runtime used by the daemon
+fn build_daemon_runtime() -> tokio::runtime::Runtime {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("Failed to build Tokio runtime")
+}
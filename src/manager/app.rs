@@ -1,7 +1,7 @@
 //! Application manager - primary interface for configuration and daemon control
 
+use std::collections::HashSet;
 use std::sync::{Arc, Mutex};
-use std::time::Duration;
 
 use anyhow::{Result, anyhow};
 use eframe::{NativeOptions, egui};
@@ -31,17 +31,33 @@ struct ManagerApp {
     visual_settings_state: components::visual_settings::VisualSettingsState,
     characters_state: components::characters::CharactersState,
     sources_state: components::sources::SourcesTab,
+    layout_editor_state: components::layout_editor::LayoutEditorState,
+    save_preview_state: components::save_preview::SavePreviewState,
+    log_viewer_state: components::log_viewer::LogViewerState,
+    diagnostics_state: components::diagnostics::DiagnosticsState,
+    crash_report_state: components::crash_report_dialog::CrashReportDialogState,
     #[cfg(target_os = "linux")]
     shutdown_signal: std::sync::Arc<tokio::sync::Notify>,
     #[cfg(target_os = "linux")]
     update_signal: std::sync::Arc<tokio::sync::Notify>,
 
     active_tab: ManagerTab,
+    /// Tabs whose saved scroll offset has been applied this session
+    restored_scroll_tabs: HashSet<ManagerTab>,
+
+    /// Hide the window to the tray on the first frame (`--minimized`)
+    start_minimized: bool,
 }
 
 impl ManagerApp {
-    fn new(cc: &eframe::CreationContext<'_>, config: Config, debug_mode: bool) -> Self {
+    fn new(
+        cc: &eframe::CreationContext<'_>,
+        config: Config,
+        debug_mode: bool,
+        start_minimized: bool,
+    ) -> Self {
         debug!("Initializing Manager (debug_mode={})", debug_mode);
+        crate::common::i18n::set_language(config.global.language);
 
         // Run auto-backup if enabled
         if config.global.backup_enabled {
@@ -102,7 +118,7 @@ impl ManagerApp {
                 let is_flatpak = std::env::var("FLATPAK_ID").is_ok();
                 let tray = AppTray {
                     state: state_clone,
-                    ctx,
+                    ctx: ctx.clone(),
                     is_flatpak,
                 };
 
@@ -136,6 +152,8 @@ impl ManagerApp {
                     }
                     Err(e) => {
                         error!(error = ?e, "Failed to create tray icon (D-Bus unavailable?)");
+                        // Without a tray a window started hidden could never be shown again
+                        show_manager_window(&ctx);
                     }
                 }
             });
@@ -152,6 +170,9 @@ impl ManagerApp {
         let hotkey_settings_state = components::hotkey_settings::HotkeySettingsState::default();
         let visual_settings_state = components::visual_settings::VisualSettingsState::default();
 
+        let active_tab =
+            ManagerTab::from_key(&config.global.last_tab).unwrap_or(ManagerTab::Behavior);
+
         let mut characters_state = components::characters::CharactersState::default();
         characters_state.load_from_profile(&config.profiles[selected_profile_idx]);
 
@@ -166,7 +187,14 @@ impl ManagerApp {
             visual_settings_state,
             characters_state,
             sources_state: components::sources::SourcesTab::default(),
-            active_tab: ManagerTab::Behavior,
+            layout_editor_state: components::layout_editor::LayoutEditorState::default(),
+            save_preview_state: components::save_preview::SavePreviewState::default(),
+            log_viewer_state: components::log_viewer::LogViewerState::default(),
+            diagnostics_state: components::diagnostics::DiagnosticsState::default(),
+            crash_report_state: components::crash_report_dialog::CrashReportDialogState::load(),
+            active_tab,
+            restored_scroll_tabs: HashSet::new(),
+            start_minimized,
         };
 
         #[cfg(not(target_os = "linux"))]
@@ -178,7 +206,14 @@ impl ManagerApp {
             visual_settings_state,
             characters_state,
             sources_state: components::sources::SourcesTab::default(),
-            active_tab: ManagerTab::Behavior,
+            layout_editor_state: components::layout_editor::LayoutEditorState::default(),
+            save_preview_state: components::save_preview::SavePreviewState::default(),
+            log_viewer_state: components::log_viewer::LogViewerState::default(),
+            diagnostics_state: components::diagnostics::DiagnosticsState::default(),
+            crash_report_state: components::crash_report_dialog::CrashReportDialogState::load(),
+            active_tab,
+            restored_scroll_tabs: HashSet::new(),
+            start_minimized,
         };
 
         app
@@ -199,11 +234,25 @@ impl eframe::App for ManagerApp {
         };
         let state = &mut *state_guard;
 
+        if std::mem::take(&mut self.start_minimized) {
+            // The tray's activate/"Show Manager" entry brings the window back
+            #[cfg(target_os = "linux")]
+            ctx.send_viewport_cmd(egui::ViewportCommand::Visible(false));
+            #[cfg(not(target_os = "linux"))]
+            ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(true));
+        }
+
         let old_profile_idx = state.selected_profile_idx;
+        let old_positions_dirty = state.positions_dirty;
+        let old_detected_characters = state.detected_characters.clone();
         state.poll_daemon();
 
+        // Refresh the tray menu when the profile, unsaved-positions state or characters changed
         #[cfg(target_os = "linux")]
-        if state.selected_profile_idx != old_profile_idx {
+        if state.selected_profile_idx != old_profile_idx
+            || state.positions_dirty != old_positions_dirty
+            || state.detected_characters != old_detected_characters
+        {
             self.update_signal.notify_one();
         }
 
@@ -221,8 +270,13 @@ impl eframe::App for ManagerApp {
             (content_rect.width() as u16, content_rect.height() as u16)
         };
 
+        // Track maximize state; keep the restored size rather than the maximized one
+        let maximized = viewport_info.maximized.unwrap_or(false);
+        state.config.global.window_maximized = maximized;
+
         // Update config if size changed (will be saved on exit)
-        if new_width > 0
+        if !maximized
+            && new_width > 0
             && new_height > 0
             && (new_width != state.config.global.window_width
                 || new_height != state.config.global.window_height)
@@ -238,6 +292,59 @@ impl eframe::App for ManagerApp {
             return;
         }
 
+        // Undo/redo shortcuts (text fields keep Ctrl+Z for their own undo)
+        if !ctx.wants_keyboard_input() {
+            let (undo, redo) = ctx.input_mut(|i| {
+                // Shift variant first: plain Ctrl+Z would also match Ctrl+Shift+Z
+                let redo = i.consume_shortcut(&egui::KeyboardShortcut::new(
+                    egui::Modifiers::COMMAND | egui::Modifiers::SHIFT,
+                    egui::Key::Z,
+                )) || i.consume_shortcut(&egui::KeyboardShortcut::new(
+                    egui::Modifiers::COMMAND,
+                    egui::Key::Y,
+                ));
+                let undo = i.consume_shortcut(&egui::KeyboardShortcut::new(
+                    egui::Modifiers::COMMAND,
+                    egui::Key::Z,
+                ));
+                (undo, redo)
+            });
+            if undo {
+                state.undo_profile_edit();
+            } else if redo {
+                state.redo_profile_edit();
+            }
+        }
+
+        // Tab and search shortcuts
+        let (next_tab, previous_tab, find) = ctx.input_mut(|i| {
+            let previous_tab = i.consume_shortcut(&egui::KeyboardShortcut::new(
+                egui::Modifiers::COMMAND | egui::Modifiers::SHIFT,
+                egui::Key::Tab,
+            ));
+            let next_tab = i.consume_shortcut(&egui::KeyboardShortcut::new(
+                egui::Modifiers::COMMAND,
+                egui::Key::Tab,
+            ));
+            let find = i.consume_shortcut(&egui::KeyboardShortcut::new(
+                egui::Modifiers::COMMAND,
+                egui::Key::F,
+            ));
+            (next_tab, previous_tab, find)
+        });
+        if next_tab || previous_tab {
+            self.active_tab = self.active_tab.cycle(next_tab);
+        }
+        if find {
+            // Tabs without a search field jump to the character search
+            if self.active_tab == ManagerTab::Logs {
+                self.log_viewer_state.focus_filter();
+            } else {
+                self.active_tab = ManagerTab::Characters;
+                self.characters_state.focus_search();
+            }
+        }
+
         let mut action = ProfileAction::None;
 
         // Global Header Panel (Fixed at top)
@@ -248,11 +355,16 @@ impl eframe::App for ManagerApp {
                 state,
                 &mut self.active_tab,
                 &mut self.profile_selector,
+                &mut self.save_preview_state,
                 #[cfg(target_os = "linux")]
                 &self.update_signal,
             );
         });
 
+        components::crash_dialog::render(ctx, state);
+        components::crash_report_dialog::render(ctx, &mut self.crash_report_state);
+        components::toasts::render(ctx, &mut state.toasts);
+
         // Handle Actions
         match action {
             ProfileAction::SwitchProfile => {
@@ -289,9 +401,22 @@ impl eframe::App for ManagerApp {
             ProfileAction::None => {}
         }
 
+        // Undo baseline, recorded only if a tab reports an edit
+        state.sync_profile_history();
+        let mut profile_edited = false;
+
+        // Each tab scrolls independently; the saved offset is applied on first show
+        let tab_key = self.active_tab.key();
+        let mut scroll_area = egui::ScrollArea::vertical().id_salt(("tab_scroll", tab_key));
+        if self.restored_scroll_tabs.insert(self.active_tab)
+            && let Some(offset) = state.config.global.tab_scroll_offsets.get(tab_key)
+        {
+            scroll_area = scroll_area.vertical_scroll_offset(*offset);
+        }
+
         // Main Content Body
         egui::CentralPanel::default().show(ctx, |ui| {
-            egui::ScrollArea::vertical().show(ui, |ui| {
+            let scroll_output = scroll_area.show(ui, |ui| {
                 let current_profile = &mut state.config.profiles[state.selected_profile_idx];
 
                 match self.active_tab {
@@ -306,6 +431,7 @@ impl eframe::App for ManagerApp {
                             BehaviorSettingsAction::SettingsChanged => {
                                 state.settings_changed = true;
                                 state.config_status_message = None;
+                                profile_edited = true;
                             }
                             BehaviorSettingsAction::RestoreTriggered => {
                                 // Reload config from disk (disk was just updated by restore)
@@ -329,6 +455,7 @@ impl eframe::App for ManagerApp {
                         ) {
                             state.settings_changed = true;
                             state.config_status_message = None;
+                            profile_edited = true;
                         }
                     }
                     ManagerTab::Hotkeys => {
@@ -339,17 +466,24 @@ impl eframe::App for ManagerApp {
                         ) {
                             state.settings_changed = true;
                             state.config_status_message = None;
+                            profile_edited = true;
                         }
                     }
                     ManagerTab::Characters => {
+                        self.characters_state.skipped = state
+                            .ipc_config_tx
+                            .is_some()
+                            .then(|| state.skipped_characters.clone());
                         if components::characters::ui(
                             ui,
                             current_profile,
+                            &mut state.config.global.characters,
                             &mut self.characters_state,
                             &mut self.hotkey_settings_state,
                         ) {
                             state.settings_changed = true;
                             state.config_status_message = None;
+                            profile_edited = true;
                         }
                     }
                     ManagerTab::Sources => {
@@ -357,16 +491,93 @@ impl eframe::App for ManagerApp {
                             ui,
                             current_profile,
                             &mut self.hotkey_settings_state,
+                        ) {
+                            state.settings_changed = true;
+                            state.config_status_message = None;
+                            profile_edited = true;
+                        }
+                    }
+                    ManagerTab::Layout => {
+                        for moved in components::layout_editor::ui(
+                            ui,
+                            current_profile,
+                            &mut self.layout_editor_state,
+                        ) {
+                            state.apply_layout_move(&moved);
+                            // A whole drag is one undo step
+                            if moved.finished {
+                                state.record_profile_edit();
+                            } else {
+                                state.history.pin();
+                            }
+                        }
+                    }
+                    ManagerTab::Diagnostics => {
+                        components::diagnostics::ui(
+                            ui,
+                            current_profile,
+                            state.hotkey_latency.as_ref(),
+                            &mut self.diagnostics_state,
+                        );
+                    }
+                    ManagerTab::Stats => {
+                        if components::stats::ui(
+                            ui,
+                            &state.focus_stats,
+                            &mut state.character_events,
+                            &mut state.config.global,
                         ) {
                             state.settings_changed = true;
                             state.config_status_message = None;
                         }
                     }
+                    ManagerTab::Logs => {
+                        components::log_viewer::ui(
+                            ui,
+                            &state.daemon_log,
+                            state.external_daemon,
+                            &mut self.log_viewer_state,
+                        );
+                    }
                 }
             });
+
+            let offset = scroll_output.state.offset.y;
+            let offsets = &mut state.config.global.tab_scroll_offsets;
+            if offsets.get(tab_key) != Some(&offset) {
+                offsets.insert(tab_key.to_string(), offset);
+            }
         });
 
-        ctx.request_repaint_after(Duration::from_millis(DAEMON_CHECK_INTERVAL_MS));
+        // Remember the open tab (saved on exit)
+        if state.config.global.last_tab != tab_key {
+            state.config.global.last_tab = tab_key.to_string();
+        }
+
+        for (name, skipped) in std::mem::take(&mut self.characters_state.skip_requests) {
+            state.set_character_skipped(&name, skipped);
+        }
+
+        if profile_edited {
+            let groups = |profile: &crate::config::profile::Profile| {
+                profile
+                    .cycle_groups
+                    .iter()
+                    .map(|group| (group.name.clone(), group.cycle_list.clone()))
+                    .collect::<Vec<_>>()
+            };
+            let groups_changed = state.history.baseline().is_none_or(|before| {
+                groups(&state.config.profiles[state.selected_profile_idx]) != groups(before)
+            });
+            state.record_profile_edit();
+            if groups_changed {
+                state.push_cycle_order();
+            }
+            // The profile may have started or stopped remembering skips
+            state.persist_skipped_characters();
+        }
+
+        ctx.request_repaint_after(state.config.global.timing.event_poll());
     }
 
     fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
@@ -394,7 +605,16 @@ impl eframe::App for ManagerApp {
     }
 }
 
-pub fn run_manager(debug_mode: bool) -> Result<()> {
+/// Shows, restores and focuses the Manager window (e.g. after starting hidden in the tray)
+#[cfg(target_os = "linux")]
+pub(crate) fn show_manager_window(ctx: &egui::Context) {
+    ctx.send_viewport_cmd(egui::ViewportCommand::Visible(true));
+    ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(false));
+    ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+    ctx.request_repaint();
+}
+
+pub fn run_manager(debug_mode: bool, start_minimized: bool) -> Result<()> {
     // Load config to get window dimensions
     let config = Config::load().unwrap_or_default();
     let window_width = config.global.window_width as f32;
@@ -422,6 +642,7 @@ pub fn run_manager(debug_mode: bool) -> Result<()> {
 
     let mut viewport_builder = egui::ViewportBuilder::default()
         .with_inner_size([window_width, window_height])
+        .with_maximized(config.global.window_maximized)
         .with_title("EVE Preview Manager - v".to_string() + env!("CARGO_PKG_VERSION"));
 
     if let Some(icon_data) = icon {
@@ -436,7 +657,14 @@ pub fn run_manager(debug_mode: bool) -> Result<()> {
     eframe::run_native(
         &format!("EVE Preview Manager - v{}", env!("CARGO_PKG_VERSION")),
         options,
-        Box::new(move |cc| Ok(Box::new(ManagerApp::new(cc, config, debug_mode)))),
+        Box::new(move |cc| {
+            Ok(Box::new(ManagerApp::new(
+                cc,
+                config,
+                debug_mode,
+                start_minimized,
+            )))
+        }),
     )
     .map_err(|err| anyhow!("Failed to launch Manager: {err}"))
 }
@@ -0,0 +1,126 @@
+//! XDG autostart entry management
+//!
+//! Installs or removes `~/.config/autostart/com.evepreview.manager.desktop` so the
+//! Manager (optionally hidden in the tray) or the headless daemon is launched on login.
+
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+use tracing::info;
+
+use crate::common::constants::autostart;
+use crate::config::profile::GlobalSettings;
+
+/// Location of the autostart entry
+pub fn entry_path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(autostart::DIR)
+        .join(autostart::FILENAME)
+}
+
+/// Whether the autostart entry is currently installed
+pub fn is_installed() -> bool {
+    entry_path().exists()
+}
+
+/// Write (or rewrite) the autostart entry according to the global settings
+pub fn install(global: &GlobalSettings) -> Result<()> {
+    let path = entry_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create autostart directory {:?}", parent))?;
+    }
+
+    let entry = desktop_entry(&exec_line(&launch_program()?, global));
+    std::fs::write(&path, entry)
+        .with_context(|| format!("Failed to write autostart entry {:?}", path))?;
+
+    info!(path = ?path, daemon_only = global.autostart_daemon_only, "Installed autostart entry");
+    Ok(())
+}
+
+/// Remove the autostart entry if present
+pub fn remove() -> Result<()> {
+    let path = entry_path();
+    if path.exists() {
+        std::fs::remove_file(&path)
+            .with_context(|| format!("Failed to remove autostart entry {:?}", path))?;
+        info!(path = ?path, "Removed autostart entry");
+    }
+    Ok(())
+}
+
+/// Command used to launch this installation (Flatpak or native binary)
+fn launch_program() -> Result<String> {
+    if let Ok(app_id) = std::env::var("FLATPAK_ID") {
+        return Ok(format!("flatpak run {}", app_id));
+    }
+
+    let exe = std::env::current_exe().context("Failed to resolve executable path")?;
+    let exe = exe.to_string_lossy();
+    // Desktop entry Exec keys require quoting for arguments containing whitespace
+    if exe.contains(char::is_whitespace) {
+        Ok(format!(
+            "\"{}\"",
+            exe.replace('\\', "\\\\").replace('"', "\\\"")
+        ))
+    } else {
+        Ok(exe.into_owned())
+    }
+}
+
+fn exec_line(program: &str, global: &GlobalSettings) -> String {
+    if global.autostart_daemon_only {
+        format!("{} --daemon-only", program)
+    } else if global.autostart_minimized {
+        format!("{} --minimized", program)
+    } else {
+        program.to_string()
+    }
+}
+
+fn desktop_entry(exec: &str) -> String {
+    format!(
+        "[Desktop Entry]\n\
+         Type=Application\n\
+         Name=EVE Preview Manager\n\
+         Comment=EVE Online window preview manager\n\
+         Exec={}\n\
+         Icon=com.evepreview.manager\n\
+         Terminal=false\n\
+         X-GNOME-Autostart-enabled=true\n",
+        exec
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exec_line_variants() {
+        let mut global = GlobalSettings::default();
+        assert_eq!(exec_line("/usr/bin/epm", &global), "/usr/bin/epm");
+
+        global.autostart_minimized = true;
+        assert_eq!(
+            exec_line("/usr/bin/epm", &global),
+            "/usr/bin/epm --minimized"
+        );
+
+        // Daemon-only has no window to minimize
+        global.autostart_daemon_only = true;
+        assert_eq!(
+            exec_line("/usr/bin/epm", &global),
+            "/usr/bin/epm --daemon-only"
+        );
+    }
+
+    #[test]
+    fn test_desktop_entry_contains_exec() {
+        let entry = desktop_entry("/usr/bin/epm --minimized");
+        assert!(entry.starts_with("[Desktop Entry]\n"));
+        assert!(entry.contains("\nExec=/usr/bin/epm --minimized\n"));
+        assert!(entry.contains("\nType=Application\n"));
+    }
+}
@@ -0,0 +1,235 @@
+//! Hotkey cheat sheet for the current profile
+//!
+//! Lists cycle group, character, custom source and other hotkeys as Markdown (for pasting
+//! into chat or notes) or as a standalone HTML page that prints cleanly.
+
+use std::fmt::Write as _;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+
+use crate::common::constants::config::cheat_sheet;
+use crate::config::HotkeyBinding;
+use crate::config::profile::{Config, CycleSlot, Profile};
+
+/// A titled table of (what, key) rows
+struct Section {
+    title: &'static str,
+    rows: Vec<(String, String)>,
+}
+
+/// Sections with at least one bound hotkey, in display order
+fn sections(profile: &Profile) -> Vec<Section> {
+    let key = |binding: &HotkeyBinding| binding.display_name();
+
+    let mut groups = Vec::new();
+    for group in &profile.cycle_groups {
+        let members: Vec<&str> = group
+            .cycle_list
+            .iter()
+            .map(|slot| match slot {
+                CycleSlot::Eve(name) | CycleSlot::Source(name) | CycleSlot::Group(name) => {
+                    name.as_str()
+                }
+            })
+            .collect();
+        let members = if members.is_empty() {
+            String::new()
+        } else {
+            format!(" ({})", members.join(", "))
+        };
+        for (action, binding) in [
+            ("forward", &group.hotkey_forward),
+            ("backward", &group.hotkey_backward),
+            ("show/hide", &group.hotkey_toggle_visibility),
+        ] {
+            if let Some(binding) = binding {
+                groups.push((
+                    format!("{} {}{}", group.name, action, members),
+                    key(binding),
+                ));
+            }
+        }
+    }
+
+    let mut characters: Vec<(String, String)> = profile
+        .character_hotkeys
+        .iter()
+        .map(|(name, binding)| (name.clone(), key(binding)))
+        .collect();
+    characters.sort();
+
+    let sources = profile
+        .custom_windows
+        .iter()
+        .filter_map(|rule| Some((rule.alias.clone(), key(rule.hotkey.as_ref()?))))
+        .collect();
+
+    let other = [
+        ("Active group forward", &profile.hotkey_active_forward),
+        ("Active group backward", &profile.hotkey_active_backward),
+        ("Next cycle group", &profile.hotkey_next_group),
+        ("Toggle skip", &profile.hotkey_toggle_skip),
+        ("Toggle previews", &profile.hotkey_toggle_previews),
+        ("Save positions", &profile.hotkey_save_positions),
+        ("Undo move", &profile.hotkey_undo_move),
+        ("Screenshot focused client", &profile.hotkey_screenshot),
+        (
+            "Toggle hotkey overlay",
+            &profile.hotkey_toggle_hotkey_overlay,
+        ),
+        ("Swap thumbnail positions", &profile.hotkey_swap_positions),
+        ("Load this profile", &profile.hotkey_profile_switch),
+    ]
+    .into_iter()
+    .filter_map(|(action, binding)| Some((action.to_string(), key(binding.as_ref()?))))
+    .collect();
+
+    [
+        Section {
+            title: "Cycle Groups",
+            rows: groups,
+        },
+        Section {
+            title: "Characters",
+            rows: characters,
+        },
+        Section {
+            title: "Custom Sources",
+            rows: sources,
+        },
+        Section {
+            title: "Other",
+            rows: other,
+        },
+    ]
+    .into_iter()
+    .filter(|section| !section.rows.is_empty())
+    .collect()
+}
+
+pub fn to_markdown(profile: &Profile) -> String {
+    let mut out = format!("# Hotkeys: {}\n", profile.profile_name);
+    let sections = sections(profile);
+    if sections.is_empty() {
+        out.push_str("\nNo hotkeys bound.\n");
+    }
+    for section in sections {
+        let _ = write!(
+            out,
+            "\n## {}\n\n| Action | Key |\n| --- | --- |\n",
+            section.title
+        );
+        for (action, key) in section.rows {
+            let _ = writeln!(
+                out,
+                "| {} | `{}` |",
+                action.replace('|', "\\|"),
+                key.replace('`', "'")
+            );
+        }
+    }
+    out
+}
+
+pub fn to_html(profile: &Profile) -> String {
+    let title = format!("Hotkeys: {}", escape_html(&profile.profile_name));
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n<style>\n\
+         body {{ font-family: sans-serif; margin: 2em; }}\n\
+         table {{ border-collapse: collapse; margin-bottom: 1.5em; break-inside: avoid; }}\n\
+         th, td {{ border: 1px solid #999; padding: 0.3em 0.8em; text-align: left; }}\n\
+         kbd {{ font-family: monospace; font-weight: bold; }}\n\
+         </style>\n</head>\n<body>\n<h1>{title}</h1>\n"
+    );
+    let sections = sections(profile);
+    if sections.is_empty() {
+        out.push_str("<p>No hotkeys bound.</p>\n");
+    }
+    for section in sections {
+        let _ = write!(
+            out,
+            "<h2>{}</h2>\n<table>\n<tr><th>Action</th><th>Key</th></tr>\n",
+            section.title
+        );
+        for (action, key) in section.rows {
+            let _ = writeln!(
+                out,
+                "<tr><td>{}</td><td><kbd>{}</kbd></td></tr>",
+                escape_html(&action),
+                escape_html(&key)
+            );
+        }
+        out.push_str("</table>\n");
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+/// Write the HTML cheat sheet next to the config file and return its path
+pub fn save_html(profile: &Profile) -> Result<PathBuf> {
+    let mut path = Config::path();
+    path.pop(); // Remove filename
+    path.push(cheat_sheet::SUBDIR);
+    std::fs::create_dir_all(&path)
+        .with_context(|| format!("Failed to create cheat sheet directory {:?}", path))?;
+
+    let name: String = profile
+        .profile_name
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect();
+    path.push(format!("{}.{}", name, cheat_sheet::EXTENSION));
+    std::fs::write(&path, to_html(profile))
+        .with_context(|| format!("Failed to write cheat sheet to {:?}", path))?;
+    Ok(path)
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn binding(key_code: u16) -> HotkeyBinding {
+        HotkeyBinding::new(key_code, true, false, false, false)
+    }
+
+    #[test]
+    fn test_markdown_lists_bound_hotkeys() {
+        let mut profile = Profile::default_with_name("PvP".to_string(), String::new());
+        profile.cycle_groups[0].cycle_list = vec![
+            CycleSlot::Eve("Main".to_string()),
+            CycleSlot::Eve("Alt".to_string()),
+        ];
+        profile.cycle_groups[0].hotkey_forward = Some(binding(15));
+        profile
+            .character_hotkeys
+            .insert("Main".to_string(), binding(2));
+
+        let markdown = to_markdown(&profile);
+        assert!(markdown.starts_with("# Hotkeys: PvP\n"));
+        assert!(markdown.contains(&format!(
+            "| Default forward (Main, Alt) | `{}` |",
+            binding(15).display_name()
+        )));
+        assert!(markdown.contains(&format!("| Main | `{}` |", binding(2).display_name())));
+        assert!(!markdown.contains("## Custom Sources"));
+        assert!(!markdown.contains("## Other"));
+    }
+
+    #[test]
+    fn test_html_escapes_names() {
+        let mut profile = Profile::default_with_name("<Fleet & Co>".to_string(), String::new());
+        profile.hotkey_toggle_skip = Some(binding(31));
+
+        let html = to_html(&profile);
+        assert!(html.contains("<h1>Hotkeys: &lt;Fleet &amp; Co&gt;</h1>"));
+        assert!(html.contains("<tr><td>Toggle skip</td>"));
+    }
+}
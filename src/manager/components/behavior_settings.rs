@@ -1,8 +1,17 @@
 //! Behavior settings component (per-profile settings)
 
 use crate::common::constants::manager_ui::*;
+use crate::common::i18n::{self, Language, tr};
+use crate::common::logging;
 use crate::config::backup::BackupManager;
-use crate::config::profile::{GlobalSettings, Profile};
+use crate::config::power::{LowPower, LowPowerMode};
+use crate::config::profile::{
+    CycleMonitor, GlobalSettings, PositionAnchor, Profile, WorkspaceFollow,
+};
+use crate::config::{AfkSnapshots, EveDetection, Timing};
+use crate::manager::autostart;
+use crate::manager::utils::{format_hex_color, parse_hex_color};
+use crate::manager::x11_utils::{WindowInfo, get_running_applications};
 
 use chrono::{DateTime, Local};
 use eframe::egui;
@@ -22,6 +31,10 @@ pub struct BehaviorSettingsState {
     pub show_delete_confirm: bool, // For manual deletion
     pub status_message: Option<String>,
     pub status_type: Option<egui::Color32>,
+    pub autostart_installed: bool,
+    pub autostart_error: Option<String>,
+    /// Windows offered by the "mark as EVE client" picker; `None` until first opened
+    pub running_apps: Option<Result<Vec<WindowInfo>, String>>,
 }
 
 impl BehaviorSettingsState {
@@ -33,6 +46,9 @@ impl BehaviorSettingsState {
             show_delete_confirm: false,
             status_message: None,
             status_type: None,
+            autostart_installed: autostart::is_installed(),
+            autostart_error: None,
+            running_apps: None,
         }
     }
 
@@ -79,6 +95,88 @@ impl Default for BehaviorSettingsState {
     }
 }
 
+/// Text, size, color, dim and icon of the minimized overlay. Returns true if anything changed.
+fn minimized_overlay_ui(ui: &mut egui::Ui, profile: &mut Profile) -> bool {
+    let mut changed = false;
+
+    ui.horizontal(|ui| {
+        ui.label("Text:");
+        changed |= ui
+            .add(
+                egui::TextEdit::singleline(&mut profile.client_minimize_overlay_text)
+                    .hint_text("MINIMIZED")
+                    .desired_width(120.0),
+            )
+            .on_hover_text("Leave empty for the translated 'MINIMIZED'")
+            .changed();
+    });
+
+    ui.horizontal(|ui| {
+        ui.label("Size:");
+        changed |= ui
+            .add(
+                egui::DragValue::new(&mut profile.client_minimize_overlay_text_size)
+                    .range(0..=96)
+                    .custom_formatter(|n, _| {
+                        if n == 0.0 {
+                            "Text size".to_string()
+                        } else {
+                            format!("{} px", n)
+                        }
+                    }),
+            )
+            .on_hover_text("0 uses the thumbnail text size")
+            .changed();
+    });
+
+    ui.horizontal(|ui| {
+        ui.label("Color:");
+        changed |= ui
+            .add(
+                egui::TextEdit::singleline(&mut profile.client_minimize_overlay_text_color)
+                    .hint_text(profile.thumbnail_text_color.as_str())
+                    .desired_width(100.0),
+            )
+            .on_hover_text("Leave empty for the thumbnail text color")
+            .changed();
+
+        // Color picker starts from the effective color
+        let current = if profile.client_minimize_overlay_text_color.is_empty() {
+            &profile.thumbnail_text_color
+        } else {
+            &profile.client_minimize_overlay_text_color
+        };
+        if let Ok(mut color) = parse_hex_color(current)
+            && ui.color_edit_button_srgba(&mut color).changed()
+        {
+            profile.client_minimize_overlay_text_color = format_hex_color(color);
+            changed = true;
+        }
+    });
+
+    ui.horizontal(|ui| {
+        ui.label("Dim:");
+        changed |= ui
+            .add(egui::Slider::new(&mut profile.client_minimize_overlay_dim, 0..=100).suffix("%"))
+            .on_hover_text("How much the last frame is darkened; 100% hides it completely")
+            .changed();
+    });
+
+    ui.horizontal(|ui| {
+        ui.label("Icon:");
+        changed |= ui
+            .add(
+                egui::TextEdit::singleline(&mut profile.client_minimize_overlay_icon)
+                    .hint_text("/path/to/icon.png")
+                    .desired_width(160.0),
+            )
+            .on_hover_text("PNG shown instead of the text; leave empty to show the text")
+            .changed();
+    });
+
+    changed
+}
+
 pub fn ui(
     ui: &mut egui::Ui,
     profile: &mut Profile,
@@ -105,6 +203,11 @@ pub fn ui(
                         "Show 'MINIMIZED' text overlay").changed() {
                         action = BehaviorSettingsAction::SettingsChanged;
                     }
+                    if profile.client_minimize_show_overlay
+                        && minimized_overlay_ui(ui, profile)
+                    {
+                        action = BehaviorSettingsAction::SettingsChanged;
+                    }
                 });
             }
 
@@ -115,6 +218,31 @@ pub fn ui(
 
             ui.add_space(ITEM_SPACING);
 
+            // Clients on other virtual desktops
+            ui.horizontal(|ui| {
+                ui.label("Client on another desktop:");
+                egui::ComboBox::from_id_salt("workspace_follow")
+                    .selected_text(profile.client_workspace_follow.label())
+                    .show_ui(ui, |ui| {
+                        for mode in WorkspaceFollow::ALL {
+                            if ui
+                                .selectable_value(&mut profile.client_workspace_follow, mode, mode.label())
+                                .changed()
+                            {
+                                action = BehaviorSettingsAction::SettingsChanged;
+                            }
+                        }
+                    });
+            });
+
+            ui.label(egui::RichText::new(
+                "What switching to a client on another virtual desktop does. \
+                 Some window managers only mark such a client urgent")
+                .small()
+                .weak());
+
+            ui.add_space(ITEM_SPACING);
+
             // Hide when no focus
             if ui.checkbox(&mut profile.thumbnail_hide_not_focused,
                 "Hide thumbnails when EVE loses focus").changed() {
@@ -128,6 +256,32 @@ pub fn ui(
 
             ui.add_space(ITEM_SPACING);
 
+            if ui.checkbox(&mut profile.thumbnail_tooltips,
+                "Show tooltips on thumbnails").changed() {
+                action = BehaviorSettingsAction::SettingsChanged;
+            }
+
+            ui.label(egui::RichText::new(
+                "Hovering a thumbnail shows the character's alias, notes, cycle groups and hotkey")
+                .small()
+                .weak());
+
+            ui.add_space(ITEM_SPACING);
+
+            // Do-not-disturb window classes
+            ui.label("Hide all thumbnails while these windows are focused:");
+            if pattern_list_edit(ui, "do_not_disturb_classes", &mut profile.thumbnail_do_not_disturb_classes) {
+                action = BehaviorSettingsAction::SettingsChanged;
+            }
+
+            ui.label(egui::RichText::new(
+                "One WM_CLASS per line (case-insensitive), e.g. obs, mpv or a screen share picker. \
+                 The Sources tab lists the classes of running applications")
+                .small()
+                .weak());
+
+            ui.add_space(ITEM_SPACING);
+
             // Auto-save thumbnail positions
             if ui.checkbox(
                 &mut profile.thumbnail_auto_save_position,
@@ -143,6 +297,39 @@ pub fn ui(
 
             ui.add_space(ITEM_SPACING);
 
+            // Save positions on daemon shutdown
+            if ui.checkbox(
+                &mut profile.thumbnail_save_on_exit,
+                "Save thumbnail positions on exit"
+            ).changed() {
+                action = BehaviorSettingsAction::SettingsChanged;
+            }
+
+            ui.label(egui::RichText::new(
+                "Positions are saved when the preview daemon shuts down, even with auto-save disabled")
+                .small()
+                .weak());
+
+            ui.add_space(ITEM_SPACING);
+
+            // Periodic position save
+            ui.horizontal(|ui| {
+                ui.label("Periodic Position Save:");
+                if ui.add(egui::Slider::new(
+                    &mut profile.thumbnail_autosave_interval_secs,
+                    0..=crate::common::constants::defaults::behavior::MAX_AUTOSAVE_INTERVAL_SECS)
+                    .suffix(" s")).changed() {
+                    action = BehaviorSettingsAction::SettingsChanged;
+                }
+            });
+
+            ui.label(egui::RichText::new(
+                "Merges moved thumbnail positions into the config at this interval (0 = disabled)")
+                .small()
+                .weak());
+
+            ui.add_space(ITEM_SPACING);
+
             // Cycle Group Reset Behavior
             if ui.checkbox(
                 &mut profile.hotkey_cycle_reset_index,
@@ -158,6 +345,43 @@ pub fn ui(
 
             ui.add_space(ITEM_SPACING);
 
+            ui.horizontal(|ui| {
+                ui.label("Cycle through clients on:");
+                egui::ComboBox::from_id_salt("cycle_monitor")
+                    .selected_text(profile.hotkey_cycle_monitor.label())
+                    .show_ui(ui, |ui| {
+                        for monitor in CycleMonitor::ALL {
+                            if ui
+                                .selectable_value(&mut profile.hotkey_cycle_monitor, monitor, monitor.label())
+                                .changed()
+                            {
+                                action = BehaviorSettingsAction::SettingsChanged;
+                            }
+                        }
+                    });
+            });
+
+            ui.label(egui::RichText::new(
+                "Cycle hotkeys pass over clients on other monitors. Character hotkeys still reach every client")
+                .small()
+                .weak());
+
+            ui.add_space(ITEM_SPACING);
+
+            if ui.checkbox(
+                &mut profile.hotkey_skip_persist,
+                "Remember skipped characters"
+            ).changed() {
+                action = BehaviorSettingsAction::SettingsChanged;
+            }
+
+            ui.label(egui::RichText::new(
+                "Characters skipped by hotkey or in the Characters tab stay skipped after the daemon restarts")
+                .small()
+                .weak());
+
+            ui.add_space(ITEM_SPACING);
+
             // Preserve thumbnail position on character swap
             if ui.checkbox(&mut profile.thumbnail_preserve_position_on_swap,
                 "New characters inherit thumbnail position").changed() {
@@ -171,6 +395,81 @@ pub fn ui(
 
             ui.add_space(ITEM_SPACING);
 
+            ui.horizontal(|ui| {
+                ui.label("Thumbnail positions measured from:");
+                egui::ComboBox::from_id_salt("position_anchor")
+                    .selected_text(profile.thumbnail_position_anchor.label())
+                    .show_ui(ui, |ui| {
+                        for anchor in PositionAnchor::ALL {
+                            if ui
+                                .selectable_value(&mut profile.thumbnail_position_anchor, anchor, anchor.label())
+                                .changed()
+                            {
+                                action = BehaviorSettingsAction::SettingsChanged;
+                            }
+                        }
+                    });
+            });
+
+            ui.label(egui::RichText::new(
+                "Measured from the client window, thumbnails keep their offset from the client's \
+                 top-left corner when it moves")
+                .small()
+                .weak());
+
+            ui.add_space(ITEM_SPACING);
+
+            ui.add_enabled_ui(profile.thumbnail_position_anchor == PositionAnchor::Screen, |ui| {
+                if ui.checkbox(&mut profile.thumbnail_follow_source_monitor,
+                    "Thumbnails follow their client to other monitors").changed() {
+                    action = BehaviorSettingsAction::SettingsChanged;
+                }
+            });
+
+            ui.label(egui::RichText::new(
+                "Moving a client to another monitor moves its thumbnail to the same spot there")
+                .small()
+                .weak());
+
+            ui.add_space(ITEM_SPACING);
+
+            // Managed thumbnail windows
+            if ui.checkbox(&mut profile.thumbnail_managed_windows,
+                "Let the window manager manage thumbnails").changed() {
+                action = BehaviorSettingsAction::SettingsChanged;
+            }
+
+            ui.label(egui::RichText::new(
+                "For tiling window managers (i3, bspwm) where thumbnails misbehave over fullscreen clients. \
+                 Thumbnails are utility windows the WM should float; add a rule if yours tiles them.")
+                .small()
+                .weak());
+
+            ui.add_space(ITEM_SPACING);
+
+            // EWMH states of thumbnail windows
+            ui.label("Thumbnail window states:");
+            ui.horizontal_wrapped(|ui| {
+                let states = &mut profile.thumbnail_wm_states;
+                for (value, label) in [
+                    (&mut states.above, "Above others"),
+                    (&mut states.sticky, "On all desktops"),
+                    (&mut states.skip_taskbar, "Skip taskbar"),
+                    (&mut states.skip_pager, "Skip pager"),
+                ] {
+                    if ui.checkbox(value, label).changed() {
+                        action = BehaviorSettingsAction::SettingsChanged;
+                    }
+                }
+            });
+
+            ui.label(egui::RichText::new(
+                "Hints for the window manager; turn off the ones your WM handles badly.")
+                .small()
+                .weak());
+
+            ui.add_space(ITEM_SPACING);
+
             // Snap threshold
             ui.horizontal(|ui| {
                 ui.label("Thumbnail Snap Distance:");
@@ -184,6 +483,39 @@ pub fn ui(
                 "Distance for edge/corner snapping (0 = disabled)")
                 .small()
                 .weak());
+
+            ui.add_space(ITEM_SPACING);
+
+            ui.horizontal(|ui| {
+                ui.label("Thumbnail Snap Gap:");
+                if ui.add(egui::Slider::new(&mut profile.thumbnail_snap_gap, 0..=50)
+                    .suffix(" px")).changed() {
+                    action = BehaviorSettingsAction::SettingsChanged;
+                }
+            });
+
+            ui.label(egui::RichText::new(
+                "Also snap this far apart from neighbouring thumbnails, for evenly spaced rows (0 = edge-to-edge only)")
+                .small()
+                .weak());
+
+            ui.add_space(ITEM_SPACING);
+
+            // X display whose clients get previews
+            ui.horizontal(|ui| {
+                ui.label("X Display:");
+                if ui.add(egui::TextEdit::singleline(&mut profile.x11_display)
+                    .hint_text("$DISPLAY")
+                    .desired_width(120.0)).changed() {
+                    action = BehaviorSettingsAction::SettingsChanged;
+                }
+            });
+
+            ui.label(egui::RichText::new(
+                "Display running the clients, e.g. :1 for a nested Xephyr or :0.1 for a second screen. \
+                 Empty uses $DISPLAY; on a multi-screen display the screen with EVE clients is used.")
+                .small()
+                .weak());
         });
 
         // Right Column: Backup Settings
@@ -343,9 +675,443 @@ pub fn ui(
                  ui.label(egui::RichText::new(msg).color(color));
             }
         });
+
+        columns[1].add_space(ITEM_SPACING);
+
+        // Right Column: Startup Settings
+        columns[1].group(|ui| {
+            ui.label(egui::RichText::new("Startup").strong());
+            ui.add_space(ITEM_SPACING);
+
+            let mut entry_changed = false;
+
+            if ui.checkbox(&mut state.autostart_installed, "Start on login").changed() {
+                entry_changed = true;
+            }
+
+            ui.add_enabled_ui(state.autostart_installed, |ui| {
+                ui.indent("autostart_indent", |ui| {
+                    if ui.checkbox(&mut global.autostart_daemon_only,
+                        "Start previews only (no manager window or tray)").changed() {
+                        entry_changed = true;
+                        action = BehaviorSettingsAction::SettingsChanged;
+                    }
+
+                    ui.add_enabled_ui(!global.autostart_daemon_only, |ui| {
+                        if ui.checkbox(&mut global.autostart_minimized,
+                            "Start minimized to tray").changed() {
+                            entry_changed = true;
+                            action = BehaviorSettingsAction::SettingsChanged;
+                        }
+                    });
+                });
+            });
+
+            ui.label(egui::RichText::new(
+                "Managed via an XDG autostart entry. Opening the manager later attaches to a previews-only daemon")
+                .small()
+                .weak());
+
+            // The autostart entry lives outside the config file, so apply it immediately
+            if entry_changed {
+                let result = if state.autostart_installed {
+                    autostart::install(global)
+                } else {
+                    autostart::remove()
+                };
+                state.autostart_error = result.err().map(|e| format!("Autostart update failed: {}", e));
+                state.autostart_installed = autostart::is_installed();
+            }
+
+            if let Some(err) = &state.autostart_error {
+                ui.label(egui::RichText::new(err).color(COLOR_ERROR));
+            }
+        });
+
+        columns[1].add_space(ITEM_SPACING);
+
+        // Right Column: Language
+        columns[1].group(|ui| {
+            ui.label(egui::RichText::new(tr("settings-language")).strong());
+            ui.add_space(ITEM_SPACING);
+
+            egui::ComboBox::from_id_salt("language")
+                .selected_text(global.language.label())
+                .show_ui(ui, |ui| {
+                    for language in Language::ALL {
+                        if ui
+                            .selectable_value(&mut global.language, language, language.label())
+                            .changed()
+                        {
+                            i18n::set_language(global.language);
+                            action = BehaviorSettingsAction::SettingsChanged;
+                        }
+                    }
+                });
+
+            ui.label(egui::RichText::new(tr("settings-language-hint"))
+                .small()
+                .weak());
+        });
+
+        columns[1].add_space(ITEM_SPACING);
+
+        // Right Column: Rendering
+        columns[1].group(|ui| {
+            ui.label(egui::RichText::new("Rendering").strong());
+            ui.add_space(ITEM_SPACING);
+
+            if ui.checkbox(&mut global.vsync_redraws, "Sync redraws to the display refresh rate").changed() {
+                action = BehaviorSettingsAction::SettingsChanged;
+            }
+
+            ui.label(egui::RichText::new(
+                "Updates all damaged thumbnails together once per refresh instead of on every \
+                 client frame, which tears less and costs less with many clients")
+                .small()
+                .weak());
+        });
+
+        columns[1].add_space(ITEM_SPACING);
+
+        // Right Column: Screenshots
+        columns[1].group(|ui| {
+            ui.label(egui::RichText::new("Screenshots").strong());
+            ui.add_space(ITEM_SPACING);
+
+            ui.horizontal(|ui| {
+                ui.label("Save to:");
+                if ui.add(
+                    egui::TextEdit::singleline(&mut global.screenshot_dir)
+                        .hint_text("~/Pictures/eve-screenshots")
+                ).changed() {
+                    action = BehaviorSettingsAction::SettingsChanged;
+                }
+            });
+
+            ui.label(egui::RichText::new(
+                "Full-size screenshots of EVE clients, taken with the Screenshot hotkey \
+                 (focused client) or by middle-clicking a thumbnail")
+                .small()
+                .weak());
+        });
+
+        columns[1].add_space(ITEM_SPACING);
+
+        // Right Column: Low-Power Mode
+        columns[1].group(|ui| {
+            ui.label(egui::RichText::new("Low-Power Mode").strong());
+            ui.add_space(ITEM_SPACING);
+
+            let low_power = &mut global.low_power;
+
+            ui.horizontal(|ui| {
+                ui.label("Enabled:");
+                egui::ComboBox::from_id_salt("low_power_mode")
+                    .selected_text(low_power.mode.label())
+                    .show_ui(ui, |ui| {
+                        for mode in LowPowerMode::ALL {
+                            if ui
+                                .selectable_value(&mut low_power.mode, mode, mode.label())
+                                .changed()
+                            {
+                                action = BehaviorSettingsAction::SettingsChanged;
+                            }
+                        }
+                    });
+            });
+
+            ui.add_enabled_ui(low_power.mode != LowPowerMode::Off, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Refresh rate:");
+                    if ui
+                        .add(
+                            egui::DragValue::new(&mut low_power.refresh_fps)
+                                .range(LowPower::FPS_RANGE)
+                                .suffix(" fps"),
+                        )
+                        .changed()
+                    {
+                        action = BehaviorSettingsAction::SettingsChanged;
+                    }
+                });
+
+                if ui
+                    .checkbox(&mut low_power.pause_unhovered, "Only update the hovered thumbnail")
+                    .changed()
+                {
+                    action = BehaviorSettingsAction::SettingsChanged;
+                }
+            });
+
+            ui.label(egui::RichText::new(
+                "Caps thumbnail redraws and uses cheaper scaling to save battery. \
+                 \"On battery\" follows UPower")
+                .small()
+                .weak());
+        });
+
+        columns[1].add_space(ITEM_SPACING);
+
+        // Right Column: AFK Snapshots
+        columns[1].group(|ui| {
+            ui.label(egui::RichText::new("AFK Snapshots").strong());
+            ui.add_space(ITEM_SPACING);
+
+            let snapshots = &mut global.afk_snapshots;
+
+            if ui.checkbox(&mut snapshots.enabled, "Save periodic snapshots of every client").changed() {
+                action = BehaviorSettingsAction::SettingsChanged;
+            }
+
+            ui.add_enabled_ui(snapshots.enabled, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Every:");
+                    if ui
+                        .add(
+                            egui::DragValue::new(&mut snapshots.interval_minutes)
+                                .range(AfkSnapshots::INTERVAL_RANGE)
+                                .suffix(" min"),
+                        )
+                        .changed()
+                    {
+                        action = BehaviorSettingsAction::SettingsChanged;
+                    }
+                    ui.label("Keep:");
+                    if ui
+                        .add(
+                            egui::DragValue::new(&mut snapshots.retention)
+                                .range(AfkSnapshots::RETENTION_RANGE)
+                                .suffix(" per character"),
+                        )
+                        .changed()
+                    {
+                        action = BehaviorSettingsAction::SettingsChanged;
+                    }
+                });
+            });
+
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new(
+                    "Thumbnail-sized pictures for reviewing unwatched alts; the oldest are deleted")
+                    .small()
+                    .weak());
+                if ui.small_button("📂 Open Folder").clicked() {
+                    let mut path = crate::config::profile::Config::path();
+                    path.pop(); // Remove filename
+                    path.push(crate::common::constants::config::snapshots::SUBDIR);
+                    let _ = std::fs::create_dir_all(&path);
+                    if let Err(e) = std::process::Command::new("xdg-open").arg(&path).spawn() {
+                        tracing::warn!(path = ?path, error = %e, "Failed to run xdg-open");
+                    }
+                }
+            });
+        });
+
+        columns[1].add_space(ITEM_SPACING);
+
+        // Right Column: Logging Settings
+        columns[1].group(|ui| {
+            ui.label(egui::RichText::new("Logging").strong());
+            ui.add_space(ITEM_SPACING);
+
+            ui.horizontal(|ui| {
+                ui.label("Log level:");
+                egui::ComboBox::from_id_salt("log_level")
+                    .selected_text(global.log_level.as_str())
+                    .show_ui(ui, |ui| {
+                        for level in logging::LEVELS {
+                            if ui
+                                .selectable_value(&mut global.log_level, level.to_string(), level)
+                                .changed()
+                            {
+                                action = BehaviorSettingsAction::SettingsChanged;
+                            }
+                        }
+                    });
+            });
+
+            if ui.checkbox(&mut global.log_to_file, "Write log files").changed() {
+                action = BehaviorSettingsAction::SettingsChanged;
+            }
+
+            ui.label(egui::RichText::new(format!(
+                "Rotated daily in {}. Applies the next time the daemon or manager starts",
+                logging::default_log_dir().display()))
+                .small()
+                .weak());
+        });
+
+        columns[1].add_space(ITEM_SPACING);
+
+        // Right Column: EVE Client Detection
+        columns[1].group(|ui| {
+            ui.label(egui::RichText::new("EVE Client Detection").strong());
+            ui.add_space(ITEM_SPACING);
+
+            let detection = &mut global.eve_detection;
+
+            ui.label("Logged-in title prefixes (one per line):");
+            if pattern_list_edit(ui, "detection_title_prefixes", &mut detection.title_prefixes) {
+                action = BehaviorSettingsAction::SettingsChanged;
+            }
+
+            ui.label("Login screen titles:");
+            if pattern_list_edit(ui, "detection_logged_out_titles", &mut detection.logged_out_titles) {
+                action = BehaviorSettingsAction::SettingsChanged;
+            }
+
+            ui.label("Required WM_CLASS (empty accepts any):");
+            if pattern_list_edit(ui, "detection_wm_classes", &mut detection.wm_classes) {
+                action = BehaviorSettingsAction::SettingsChanged;
+            }
+
+            ui.label("Always treat these WM_CLASS values as EVE clients:");
+            if pattern_list_edit(ui, "detection_client_classes", &mut detection.client_classes) {
+                action = BehaviorSettingsAction::SettingsChanged;
+            }
+
+            // Picker filling the list above from the running windows
+            let mut refresh = false;
+            egui::ComboBox::from_id_salt("detection_mark_client")
+                .selected_text("Mark a running window as EVE...")
+                .width(ui.available_width())
+                .show_ui(ui, |ui| {
+                    if ui.button("🔄 Refresh List").clicked() || state.running_apps.is_none() {
+                        refresh = true;
+                    }
+                    match &state.running_apps {
+                        Some(Ok(apps)) => {
+                            for app in apps {
+                                let marked = detection.is_client_class(Some(&app.class));
+                                let text = format!("{} ({})", app.class, app.title);
+                                if ui.add_enabled(!marked, egui::Button::new(text).frame(false)).clicked() {
+                                    detection.client_classes.push(app.class.clone());
+                                    action = BehaviorSettingsAction::SettingsChanged;
+                                }
+                            }
+                        }
+                        Some(Err(e)) => {
+                            ui.colored_label(COLOR_ERROR, e);
+                        }
+                        None => {}
+                    }
+                });
+            if refresh {
+                state.running_apps = Some(
+                    get_running_applications()
+                        .map(|mut apps| {
+                            apps.retain(|app| !app.class.is_empty());
+                            apps.sort_by(|a, b| a.class.cmp(&b.class).then(a.title.cmp(&b.title)));
+                            apps.dedup_by(|a, b| a.class == b.class);
+                            apps
+                        })
+                        .map_err(|e| format!("Failed to list windows: {}", e)),
+                );
+            }
+
+            ui.label(egui::RichText::new(
+                "Text after a prefix is the character name, so keep trailing spaces (\"EVE - \"). \
+                 Add entries for localized or China server clients. Marked classes whose titles \
+                 match no prefix count as clients at the login screen")
+                .small()
+                .weak());
+
+            if *detection != EveDetection::default() && ui.button("Reset to Defaults").clicked() {
+                *detection = EveDetection::default();
+                action = BehaviorSettingsAction::SettingsChanged;
+            }
+        });
+
+        columns[1].add_space(ITEM_SPACING);
+
+        // Right Column: Advanced Timing
+        columns[1].group(|ui| {
+            egui::CollapsingHeader::new(egui::RichText::new("Advanced Timing").strong())
+                .id_salt("advanced_timing")
+                .show(ui, |ui| {
+                    let timing = &mut global.timing;
+                    let fields: [(&str, &mut u64, &str); 5] = [
+                        ("Daemon event poll:", &mut timing.event_poll_ms, " ms"),
+                        ("Daemon health check:", &mut timing.health_check_ms, " ms"),
+                        ("Position save debounce:", &mut timing.save_debounce_ms, " ms"),
+                        ("Hotkey double tap window:", &mut timing.double_tap_ms, " ms"),
+                        ("Hotkey long press after:", &mut timing.long_press_ms, " ms"),
+                    ];
+                    for (label, value, suffix) in fields {
+                        ui.horizontal(|ui| {
+                            ui.label(label);
+                            if ui
+                                .add(egui::DragValue::new(value).range(Timing::MS_RANGE).suffix(suffix))
+                                .changed()
+                            {
+                                action = BehaviorSettingsAction::SettingsChanged;
+                            }
+                        });
+                    }
+
+                    ui.horizontal(|ui| {
+                        ui.label("Window rescan:");
+                        if ui
+                            .add(
+                                egui::DragValue::new(&mut timing.rescan_interval_secs)
+                                    .range(Timing::RESCAN_RANGE)
+                                    .suffix(" s"),
+                            )
+                            .changed()
+                        {
+                            action = BehaviorSettingsAction::SettingsChanged;
+                        }
+                    });
+
+                    ui.horizontal(|ui| {
+                        ui.label("New thumbnail offset:");
+                        if ui
+                            .add(
+                                egui::DragValue::new(&mut timing.spawn_offset)
+                                    .range(Timing::SPAWN_OFFSET_RANGE)
+                                    .suffix(" px"),
+                            )
+                            .changed()
+                        {
+                            action = BehaviorSettingsAction::SettingsChanged;
+                        }
+                    });
+
+                    ui.label(egui::RichText::new(
+                        "Longer intervals lighten the load on slow machines; shorter ones make \
+                         the Manager react faster")
+                        .small()
+                        .weak());
+
+                    if *timing != Timing::default() && ui.button("Reset to Defaults").clicked() {
+                        *timing = Timing::default();
+                        action = BehaviorSettingsAction::SettingsChanged;
+                    }
+                });
+        });
     });
 
     ui.add_space(SECTION_SPACING);
 
     action
 }
+
+/// Multi-line editor for a list of patterns, one per line
+fn pattern_list_edit(ui: &mut egui::Ui, id: &str, patterns: &mut Vec<String>) -> bool {
+    let mut text = patterns.join("\n");
+    let changed = ui
+        .add(
+            egui::TextEdit::multiline(&mut text)
+                .id_salt(id)
+                .desired_rows(2)
+                .desired_width(f32::INFINITY)
+                .font(egui::TextStyle::Monospace),
+        )
+        .changed();
+    if changed {
+        // Keep blank lines while typing; detection ignores them
+        *patterns = text.split('\n').map(str::to_string).collect();
+    }
+    changed
+}
@@ -1,6 +1,10 @@
 use super::CharactersState;
+use super::filter::{self, CharacterFilter};
 use crate::common::constants::manager_ui::*;
-use crate::config::profile::Profile;
+use crate::common::constants::tooltip;
+use crate::common::types::{Badge, BadgeRole, CharacterSettings, MonitorPercent, Position};
+use crate::config::CharacterRegistry;
+use crate::config::profile::{CompanionLink, PositionAnchor, Profile};
 use crate::manager::components::hotkey_settings::HotkeySettingsState;
 use eframe::egui;
 
@@ -15,6 +19,7 @@ pub struct ThemeDefaults {
 pub fn render_character_editor_column(
     ui: &mut egui::Ui,
     profile: &mut Profile,
+    registry: &mut CharacterRegistry,
     state: &mut CharactersState,
     hotkey_state: &mut HotkeySettingsState,
     changed: &mut bool,
@@ -27,6 +32,51 @@ pub fn render_character_editor_column(
     );
     ui.add_space(ITEM_SPACING);
 
+    ui.horizontal(|ui| {
+        let search = ui.add(
+            egui::TextEdit::singleline(&mut state.filter_text)
+                .hint_text("🔍 Search name, alias, notes, tags...")
+                .desired_width(180.0),
+        );
+        if std::mem::take(&mut state.focus_search) {
+            search.request_focus();
+        }
+        egui::ComboBox::from_id_salt("char_filter")
+            .selected_text(state.filter.label())
+            .show_ui(ui, |ui| {
+                for filter in CharacterFilter::ALL {
+                    if ui
+                        .selectable_value(&mut state.filter, filter, filter.label())
+                        .changed()
+                    {
+                        state.confirm_bulk_delete = false;
+                    }
+                }
+            });
+        if state.filter == CharacterFilter::Unused {
+            ui.label("for");
+            ui.add(
+                egui::DragValue::new(&mut state.unused_days)
+                    .range(1..=3650)
+                    .suffix(" days"),
+            );
+        }
+        if (!state.filter_text.is_empty() || state.filter != CharacterFilter::All)
+            && ui.small_button("✖").on_hover_text("Clear filter").clicked()
+        {
+            state.filter_text.clear();
+            state.filter = CharacterFilter::All;
+        }
+        if ui
+            .button("Find Duplicates")
+            .on_hover_text("Entries of one character that differ only in case or spacing")
+            .clicked()
+        {
+            state.show_duplicates_popup = true;
+        }
+    });
+    ui.add_space(ITEM_SPACING);
+
     // Capture defaults before mutable borrow of profile
     let defaults = ThemeDefaults {
         active_border_color: profile.thumbnail_active_border_color.clone(),
@@ -40,11 +90,66 @@ pub fn render_character_editor_column(
         .id_salt("char_editor_scroll")
         .show(ui, |ui| {
             // Get all known characters (keys from character_thumbnails)
-            let mut char_names: Vec<String> =
-                profile.character_thumbnails.keys().cloned().collect();
+            let now = chrono::Utc::now().timestamp();
+            let total = profile.character_thumbnails.len();
+            let mut char_names: Vec<String> = profile
+                .character_thumbnails
+                .iter()
+                .filter(|(name, settings)| {
+                    filter::matches(
+                        name,
+                        settings,
+                        registry.get(name),
+                        profile,
+                        &state.filter_text,
+                        state.filter,
+                        now,
+                        state.unused_days,
+                    )
+                })
+                .map(|(name, _)| name.clone())
+                .collect();
             // Case-insensitive sort
             char_names.sort_by_key(|a| a.to_lowercase());
             let mut to_delete = Vec::new();
+            let mut to_merge = None;
+            // Merge targets include characters hidden by the filter
+            let mut known: Vec<String> = profile.character_thumbnails.keys().cloned().collect();
+            known.sort_by_key(|a| a.to_lowercase());
+            // Custom sources a character can be linked to, including numbered instances
+            let mut sources: Vec<String> = profile
+                .custom_windows
+                .iter()
+                .map(|rule| rule.alias.clone())
+                .chain(profile.custom_source_thumbnails.keys().cloned())
+                .collect();
+            sources.sort_by_key(|a| a.to_lowercase());
+            sources.dedup();
+
+            let shown = char_names.len();
+            if shown < total {
+                ui.label(
+                    egui::RichText::new(format!("Showing {} of {} characters", shown, total))
+                        .small()
+                        .weak(),
+                );
+            }
+
+            if shown > 0
+                && matches!(
+                    state.filter,
+                    CharacterFilter::Unused | CharacterFilter::Archived
+                )
+            {
+                render_bulk_actions(ui, profile, state, &char_names, &mut to_delete, changed);
+            }
+
+            // Members of nested groups get their accent color too
+            let resolved_groups =
+                crate::config::profile::CycleGroup::resolve_nested(&profile.cycle_groups);
+
+            // Thumbnails anchored to their client keep an offset from it, not a spot on a monitor
+            let client_anchored = profile.thumbnail_position_anchor == PositionAnchor::Client;
 
             for character in char_names {
                 // Ensure CharacterSettings entry exists
@@ -64,8 +169,12 @@ pub fn render_character_editor_column(
 
                     ui.label(&character);
 
+                    for group in resolved_groups.iter().filter(|g| g.contains(&character)) {
+                        super::list::group_swatch(ui, group);
+                    }
+
                     // Show Alias in parentheses
-                    if let Some(alias) = &settings.alias
+                    if let Some(alias) = registry.get(&character).and_then(|i| i.alias.as_ref())
                         && !alias.is_empty()
                     {
                         ui.label(egui::RichText::new(format!("({})", alias)));
@@ -76,6 +185,21 @@ pub fn render_character_editor_column(
                         ui.label(egui::RichText::new(format!("[{}]", binding.display_name())));
                     }
 
+                    // Archive Button
+                    let (archive_icon, archive_hint) = if settings.archived {
+                        ("↩", "Unarchive Character")
+                    } else {
+                        ("📦", "Archive Character (hide from lists)")
+                    };
+                    if ui
+                        .small_button(archive_icon)
+                        .on_hover_text(archive_hint)
+                        .clicked()
+                    {
+                        settings.archived = !settings.archived;
+                        *changed = true;
+                    }
+
                     // Delete Button
                     if ui
                         .small_button("🗑")
@@ -95,25 +219,29 @@ pub fn render_character_editor_column(
                             .num_columns(2)
                             .spacing([10.0, 4.0])
                             .show(ui, |ui| {
-                                // Alias
+                                // Identity (shared by all profiles)
+                                let identity = registry.get(&character).cloned().unwrap_or_default();
+
                                 ui.label("Alias:");
-                                let mut alias = settings.alias.clone().unwrap_or_default();
+                                let mut alias = identity.alias.unwrap_or_default();
                                 if ui
                                     .add(
                                         egui::TextEdit::singleline(&mut alias)
                                             .hint_text("Display Name"),
                                     )
+                                    .on_hover_text("Shared by all profiles")
                                     .changed()
                                 {
-                                    settings.alias =
-                                        if alias.is_empty() { None } else { Some(alias) };
+                                    registry.update(&character, |i| {
+                                        i.alias = Some(alias).filter(|a| !a.is_empty());
+                                    });
                                     *changed = true;
                                 }
                                 ui.end_row();
 
                                 // Notes
                                 ui.label("Notes:");
-                                let mut notes = settings.notes.clone().unwrap_or_default();
+                                let mut notes = identity.notes.unwrap_or_default();
                                 if ui
                                     .add(
                                         egui::TextEdit::multiline(&mut notes)
@@ -122,12 +250,73 @@ pub fn render_character_editor_column(
                                     )
                                     .changed()
                                 {
-                                    settings.notes =
-                                        if notes.is_empty() { None } else { Some(notes) };
+                                    registry.update(&character, |i| {
+                                        i.notes = Some(notes).filter(|n| !n.is_empty());
+                                    });
+                                    *changed = true;
+                                }
+                                ui.end_row();
+
+                                ui.label("Tags:");
+                                let mut tags = identity.tags.join(",");
+                                if ui
+                                    .add(
+                                        egui::TextEdit::singleline(&mut tags)
+                                            .hint_text("Comma-separated, e.g. hauler, cyno"),
+                                    )
+                                    .changed()
+                                {
+                                    // Keep spacing while typing; the search ignores it
+                                    registry.update(&character, |i| {
+                                        i.tags = if tags.is_empty() {
+                                            Vec::new()
+                                        } else {
+                                            tags.split(',').map(str::to_string).collect()
+                                        };
+                                    });
+                                    *changed = true;
+                                }
+                                ui.end_row();
+
+                                ui.label("Portrait:");
+                                let mut portrait = identity.portrait.unwrap_or_default();
+                                if ui
+                                    .add(
+                                        egui::TextEdit::singleline(&mut portrait)
+                                            .hint_text("Path to an image file"),
+                                    )
+                                    .changed()
+                                {
+                                    registry.update(&character, |i| {
+                                        i.portrait = Some(portrait.clone()).filter(|p| !p.is_empty());
+                                    });
                                     *changed = true;
                                 }
                                 ui.end_row();
 
+                                let portrait = portrait.trim();
+                                if !portrait.is_empty() {
+                                    ui.label("");
+                                    match state.portrait(ui.ctx(), &character, portrait) {
+                                        Some(texture) => {
+                                            let side = f32::from(tooltip::PORTRAIT_SIZE);
+                                            ui.add(
+                                                egui::Image::new(texture)
+                                                    .fit_to_exact_size(egui::vec2(side, side))
+                                                    .maintain_aspect_ratio(true),
+                                            );
+                                        }
+                                        None => {
+                                            ui.colored_label(COLOR_ERROR, "Could not load image");
+                                        }
+                                    }
+                                    ui.end_row();
+                                }
+
+                                ui.label("Last seen:");
+                                ui.label(last_seen_text(settings.days_since_seen(now)));
+                                ui.end_row();
+
                                 // Hotkey Binding
                                 ui.label("Hotkey:");
                                 ui.horizontal(|ui| {
@@ -171,6 +360,164 @@ pub fn render_character_editor_column(
                                 });
                                 ui.end_row();
 
+                                ui.label("Badges:");
+                                render_badges(ui, &character, settings, changed);
+                                ui.end_row();
+
+                                ui.label("Companion Windows:").on_hover_text(
+                                    "Custom sources raised together with this character's client",
+                                );
+                                render_companions(
+                                    ui,
+                                    &character,
+                                    &mut profile.companion_windows,
+                                    &sources,
+                                    changed,
+                                );
+                                ui.end_row();
+
+                                // Renamed or transferred characters keep their setup
+                                ui.label("Renamed To:");
+                                ui.horizontal(|ui| {
+                                    let target =
+                                        state.merge_targets.entry(character.clone()).or_default();
+                                    egui::ComboBox::from_id_salt(("merge_into", &character))
+                                        .selected_text(if target.is_empty() {
+                                            "Choose character"
+                                        } else {
+                                            target.as_str()
+                                        })
+                                        .show_ui(ui, |ui| {
+                                            for other in known.iter().filter(|n| **n != character)
+                                            {
+                                                ui.selectable_value(target, other.clone(), other);
+                                            }
+                                        });
+                                    if ui
+                                        .add_enabled(!target.is_empty(), egui::Button::new("Merge"))
+                                        .on_hover_text(
+                                            "Move this character's position, overrides, hotkey and cycle groups to the new name, then remove it",
+                                        )
+                                        .clicked()
+                                    {
+                                        to_merge = Some((character.clone(), target.clone()));
+                                        *changed = true;
+                                    }
+                                });
+                                ui.end_row();
+
+                                // Position and size as parts of the monitor
+                                ui.label("Scale With Monitor:");
+                                ui.horizontal(|ui| {
+                                    let mut scaled = settings.monitor_percent.is_some();
+                                    if ui
+                                        .add_enabled(
+                                            !client_anchored,
+                                            egui::Checkbox::without_text(&mut scaled),
+                                        )
+                                        .on_hover_text(
+                                            "Save position and size as percentages of the monitor, so the layout fits displays with other resolutions",
+                                        )
+                                        .changed()
+                                    {
+                                        settings.monitor_percent = if scaled {
+                                            MonitorPercent::measure(
+                                                settings.position(),
+                                                settings.dimensions,
+                                                &crate::manager::x11_utils::monitor_scales(),
+                                            )
+                                        } else {
+                                            None
+                                        };
+                                        *changed = true;
+                                    }
+                                    if let Some(percent) = settings.monitor_percent {
+                                        let pct = |v: u16| f32::from(v) / 100.0;
+                                        ui.label(
+                                            egui::RichText::new(format!(
+                                                "{:.2}% × {:.2}% at {:.2}%, {:.2}% of monitor {}",
+                                                pct(percent.width),
+                                                pct(percent.height),
+                                                pct(percent.x),
+                                                pct(percent.y),
+                                                u16::from(percent.monitor) + 1,
+                                            ))
+                                            .small()
+                                            .weak(),
+                                        );
+                                    }
+                                });
+                                ui.end_row();
+
+                                // Visibility tied to the active cycle group
+                                ui.label("Show Only With Group:");
+                                let selected = settings
+                                    .show_only_in_group
+                                    .clone()
+                                    .unwrap_or_else(|| "Any group".to_string());
+                                egui::ComboBox::from_id_salt(("show_only_in_group", &character))
+                                    .selected_text(selected)
+                                    .show_ui(ui, |ui| {
+                                        let options = std::iter::once(None).chain(
+                                            profile
+                                                .cycle_groups
+                                                .iter()
+                                                .map(|g| Some(g.name.clone())),
+                                        );
+                                        for option in options {
+                                            let label = option
+                                                .clone()
+                                                .unwrap_or_else(|| "Any group".to_string());
+                                            if ui
+                                                .selectable_value(
+                                                    &mut settings.show_only_in_group,
+                                                    option,
+                                                    label,
+                                                )
+                                                .changed()
+                                            {
+                                                *changed = true;
+                                            }
+                                        }
+                                    })
+                                    .response
+                                    .on_hover_text(
+                                        "Show the thumbnail only while this cycle group is the one last cycled with a hotkey",
+                                    );
+                                ui.end_row();
+
+                                // Shell commands run around activation
+                                for (label, action, hint) in [
+                                    (
+                                        "Before Activation:",
+                                        &mut settings.activate_before,
+                                        "e.g. pactl set-sink-input-mute 42 0",
+                                    ),
+                                    (
+                                        "After Activation:",
+                                        &mut settings.activate_after,
+                                        "e.g. obs-cmd scene switch Main",
+                                    ),
+                                ] {
+                                    ui.label(label);
+                                    let mut command = action.clone().unwrap_or_default();
+                                    if ui
+                                        .add(
+                                            egui::TextEdit::singleline(&mut command)
+                                                .hint_text(hint),
+                                        )
+                                        .on_hover_text(
+                                            "Shell command run when this character is focused by hotkey or click. \
+                                             $EVE_PREVIEW_MANAGER_CHARACTER and $EVE_PREVIEW_MANAGER_WINDOW hold its name and window ID.",
+                                        )
+                                        .changed()
+                                    {
+                                        *action = Some(command).filter(|c| !c.is_empty());
+                                        *changed = true;
+                                    }
+                                    ui.end_row();
+                                }
+
                                 // Overrides Section
                                 render_overrides_section(
                                     ui, &character, settings, &defaults, state, changed,
@@ -184,14 +531,21 @@ pub fn render_character_editor_column(
 
             // Perform deferred deletion
             for char_to_delete in to_delete {
-                profile.character_thumbnails.remove(&char_to_delete);
-                profile.character_hotkeys.remove(&char_to_delete);
-                for group in &mut profile.cycle_groups {
-                    group.cycle_list.retain(|slot| match slot {
-                        crate::config::profile::CycleSlot::Eve(name) => name != &char_to_delete,
-                        crate::config::profile::CycleSlot::Source(name) => name != &char_to_delete,
-                    });
-                }
+                profile.remove_character(&char_to_delete);
+            }
+            if let Some((old, new)) = to_merge {
+                profile.merge_character(&old, &new);
+                registry.rename(&old, &new);
+                state.merge_targets.remove(&old);
+                state.expanded_rows.remove(&old);
+            }
+
+            if shown == 0 && total > 0 {
+                ui.label(
+                    egui::RichText::new("No characters match the filter.")
+                        .weak()
+                        .italics(),
+                );
             }
 
             if profile.character_thumbnails.is_empty() {
@@ -203,9 +557,285 @@ pub fn render_character_editor_column(
                     .italics(),
                 );
             }
+
+            ui.add_space(ITEM_SPACING);
+            render_account_labels(ui, profile, changed);
         });
 }
 
+/// Account nicknames of the client launch slots, shown on logged-out thumbnails
+fn render_account_labels(ui: &mut egui::Ui, profile: &mut Profile, changed: &mut bool) {
+    egui::CollapsingHeader::new("Logged-Out Clients")
+        .id_salt("account_labels")
+        .show(ui, |ui| {
+            ui.label(
+                egui::RichText::new(
+                    "Clients are numbered in the order they were launched (a closed client's \
+                     number goes to the next one launched). While logged out, a client's \
+                     thumbnail shows the account name given to its number.",
+                )
+                .small()
+                .weak(),
+            );
+            let mut remove = None;
+            egui::Grid::new("account_labels_grid")
+                .num_columns(3)
+                .spacing([ITEM_SPACING, ITEM_SPACING / 2.0])
+                .show(ui, |ui| {
+                    for (i, label) in profile.client_account_labels.iter_mut().enumerate() {
+                        ui.label(format!("Client {}:", i + 1));
+                        if ui
+                            .add(
+                                egui::TextEdit::singleline(label)
+                                    .hint_text(format!("Client {}", i + 1))
+                                    .desired_width(160.0),
+                            )
+                            .changed()
+                        {
+                            *changed = true;
+                        }
+                        if ui.small_button("✖").on_hover_text("Remove").clicked() {
+                            remove = Some(i);
+                        }
+                        ui.end_row();
+                    }
+                });
+            if let Some(i) = remove {
+                // Later clients keep their numbers
+                if i + 1 == profile.client_account_labels.len() {
+                    profile.client_account_labels.pop();
+                } else {
+                    profile.client_account_labels[i].clear();
+                }
+                *changed = true;
+            }
+            if ui.button("➕ Add Client").clicked() {
+                profile.client_account_labels.push(String::new());
+                *changed = true;
+            }
+        });
+}
+
+/// Archive/unarchive or delete every character shown by the current filter
+fn render_bulk_actions(
+    ui: &mut egui::Ui,
+    profile: &mut Profile,
+    state: &mut CharactersState,
+    char_names: &[String],
+    to_delete: &mut Vec<String>,
+    changed: &mut bool,
+) {
+    let archive = state.filter != CharacterFilter::Archived;
+    ui.horizontal(|ui| {
+        let label = if archive {
+            format!("📦 Archive {}", char_names.len())
+        } else {
+            format!("↩ Unarchive {}", char_names.len())
+        };
+        if ui.button(label).clicked() {
+            for name in char_names {
+                if let Some(settings) = profile.character_thumbnails.get_mut(name) {
+                    settings.archived = archive;
+                }
+            }
+            *changed = true;
+        }
+
+        if state.confirm_bulk_delete {
+            ui.colored_label(COLOR_WARNING, format!("Delete {}?", char_names.len()));
+            if ui.button("Confirm").clicked() {
+                to_delete.extend(char_names.iter().cloned());
+                state.confirm_bulk_delete = false;
+                *changed = true;
+            }
+            if ui.button("Cancel").clicked() {
+                state.confirm_bulk_delete = false;
+            }
+        } else if ui
+            .button(format!("🗑 Delete {}", char_names.len()))
+            .on_hover_text("Remove these characters with their hotkeys and cycle group entries")
+            .clicked()
+        {
+            state.confirm_bulk_delete = true;
+        }
+    });
+    ui.label(
+        egui::RichText::new(
+            "Archived characters keep their settings but are hidden from lists until they log in again.",
+        )
+        .small()
+        .weak(),
+    );
+    ui.add_space(ITEM_SPACING);
+}
+
+/// Edit the character's badges: change each in place, remove it, or add a new one
+fn render_badges(
+    ui: &mut egui::Ui,
+    character_name: &str,
+    settings: &mut CharacterSettings,
+    changed: &mut bool,
+) {
+    ui.vertical(|ui| {
+        let mut remove = None;
+        for (i, badge) in settings.badges.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                ui.label(badge.label());
+                match badge {
+                    Badge::Role(role) => {
+                        egui::ComboBox::from_id_salt(("badge_role", character_name, i))
+                            .selected_text(role.label())
+                            .show_ui(ui, |ui| {
+                                for option in BadgeRole::ALL {
+                                    if ui.selectable_value(role, option, option.label()).changed() {
+                                        *changed = true;
+                                    }
+                                }
+                            });
+                    }
+                    Badge::Account(number) => {
+                        if ui.add(egui::DragValue::new(number).range(0..=99)).changed() {
+                            *changed = true;
+                        }
+                    }
+                    Badge::Skipped => {
+                        ui.label(
+                            egui::RichText::new("shown while skipped in the cycle")
+                                .small()
+                                .weak(),
+                        );
+                    }
+                    Badge::Image(path) => {
+                        if ui
+                            .add(
+                                egui::TextEdit::singleline(path)
+                                    .hint_text("/path/to/icon.png")
+                                    .desired_width(180.0),
+                            )
+                            .changed()
+                        {
+                            *changed = true;
+                        }
+                    }
+                }
+                if ui.small_button("✖").on_hover_text("Remove badge").clicked() {
+                    remove = Some(i);
+                }
+            });
+        }
+        if let Some(i) = remove {
+            settings.badges.remove(i);
+            *changed = true;
+        }
+
+        egui::ComboBox::from_id_salt(("badge_add", character_name))
+            .selected_text("➕ Add badge")
+            .show_ui(ui, |ui| {
+                let options = [
+                    Badge::Role(BadgeRole::default()),
+                    Badge::Account(1),
+                    Badge::Skipped,
+                    Badge::Image(String::new()),
+                ];
+                for badge in options {
+                    if ui.selectable_label(false, badge.label()).clicked() {
+                        settings.badges.push(badge);
+                        *changed = true;
+                    }
+                }
+            });
+    });
+}
+
+/// Links from `character` to custom sources, each optionally moved to a fixed position
+fn render_companions(
+    ui: &mut egui::Ui,
+    character: &str,
+    links: &mut Vec<CompanionLink>,
+    sources: &[String],
+    changed: &mut bool,
+) {
+    ui.vertical(|ui| {
+        let mut remove = None;
+        for (i, link) in links.iter_mut().enumerate() {
+            if link.character != character {
+                continue;
+            }
+            ui.horizontal(|ui| {
+                ui.label(&link.source);
+                let mut moves = link.position.is_some();
+                if ui
+                    .checkbox(&mut moves, "Move to")
+                    .on_hover_text("Move the window's top-left corner here when it is raised")
+                    .changed()
+                {
+                    link.position = moves.then(Position::default);
+                    *changed = true;
+                }
+                if let Some(position) = &mut link.position {
+                    let x = ui.add(egui::DragValue::new(&mut position.x).prefix("x: "));
+                    let y = ui.add(egui::DragValue::new(&mut position.y).prefix("y: "));
+                    if x.changed() || y.changed() {
+                        *changed = true;
+                    }
+                }
+                if ui
+                    .small_button("✖")
+                    .on_hover_text("Unlink window")
+                    .clicked()
+                {
+                    remove = Some(i);
+                }
+            });
+        }
+        if let Some(i) = remove {
+            links.remove(i);
+            *changed = true;
+        }
+
+        let linked: Vec<&str> = links
+            .iter()
+            .filter(|link| link.character == character)
+            .map(|link| link.source.as_str())
+            .collect();
+        let available: Vec<&String> = sources
+            .iter()
+            .filter(|source| !linked.contains(&source.as_str()))
+            .collect();
+        if sources.is_empty() {
+            ui.label(
+                egui::RichText::new("Add a custom source in the Sources tab first")
+                    .small()
+                    .weak(),
+            );
+        } else if !available.is_empty() {
+            egui::ComboBox::from_id_salt(("companion_add", character))
+                .selected_text("➕ Link window")
+                .show_ui(ui, |ui| {
+                    for source in available {
+                        if ui.selectable_label(false, source).clicked() {
+                            links.push(CompanionLink {
+                                character: character.to_string(),
+                                source: source.clone(),
+                                position: None,
+                            });
+                            *changed = true;
+                        }
+                    }
+                });
+        }
+    });
+}
+
+fn last_seen_text(days: Option<i64>) -> String {
+    match days {
+        None => "Not yet seen".to_string(),
+        Some(0) => "Today".to_string(),
+        Some(1) => "1 day ago".to_string(),
+        Some(days) => format!("{} days ago", days),
+    }
+}
+
 pub fn render_overrides_section(
     ui: &mut egui::Ui,
     character_name: &str,
@@ -410,6 +1040,34 @@ pub fn render_overrides_section(
             });
         }
 
+        // Hide When Unfocused
+        ui.horizontal(|ui| {
+            ui.label("Hide When Unfocused:");
+            let label = |value: Option<bool>| match value {
+                None => "Profile default",
+                Some(false) => "Always show",
+                Some(true) => "Hide",
+            };
+            egui::ComboBox::from_id_salt(("hide_not_focused", character_name))
+                .selected_text(label(settings.override_hide_not_focused))
+                .show_ui(ui, |ui| {
+                    for value in [None, Some(false), Some(true)] {
+                        if ui
+                            .selectable_value(
+                                &mut settings.override_hide_not_focused,
+                                value,
+                                label(value),
+                            )
+                            .changed()
+                        {
+                            *changed = true;
+                        }
+                    }
+                });
+        })
+        .response
+        .on_hover_text("Override \"Hide thumbnails when EVE loses focus\" for this character");
+
         // Preview Mode (Static Mode)
         ui.horizontal(|ui| {
             ui.label("Static Mode:");
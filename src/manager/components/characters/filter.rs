@@ -0,0 +1,190 @@
+//! Search and filter for the character list
+
+use crate::common::types::CharacterSettings;
+use crate::config::CharacterIdentity;
+use crate::config::profile::{CycleSlot, Profile};
+
+/// Narrows the character list beyond the search text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CharacterFilter {
+    #[default]
+    All,
+    HasHotkey,
+    NoHotkey,
+    InCycleGroup,
+    NotInCycleGroup,
+    /// Not seen for the configured number of days
+    Unused,
+    /// Archived characters, which every other filter hides
+    Archived,
+}
+
+impl CharacterFilter {
+    pub const ALL: [CharacterFilter; 7] = [
+        CharacterFilter::All,
+        CharacterFilter::HasHotkey,
+        CharacterFilter::NoHotkey,
+        CharacterFilter::InCycleGroup,
+        CharacterFilter::NotInCycleGroup,
+        CharacterFilter::Unused,
+        CharacterFilter::Archived,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CharacterFilter::All => "All characters",
+            CharacterFilter::HasHotkey => "Has hotkey",
+            CharacterFilter::NoHotkey => "No hotkey",
+            CharacterFilter::InCycleGroup => "In a cycle group",
+            CharacterFilter::NotInCycleGroup => "In no cycle group",
+            CharacterFilter::Unused => "Unused",
+            CharacterFilter::Archived => "Archived",
+        }
+    }
+}
+
+/// Whether a character passes the search text and filter.
+///
+/// The search is case-insensitive and matches the name and the alias, notes and tags of
+/// `identity`. Every whitespace-separated word must match.
+/// Archived characters only show under [`CharacterFilter::Archived`]; `now` and
+/// `unused_days` decide what counts as [`CharacterFilter::Unused`].
+#[allow(clippy::too_many_arguments)]
+pub fn matches(
+    name: &str,
+    settings: &CharacterSettings,
+    identity: Option<&CharacterIdentity>,
+    profile: &Profile,
+    query: &str,
+    filter: CharacterFilter,
+    now: i64,
+    unused_days: u32,
+) -> bool {
+    if settings.archived != (filter == CharacterFilter::Archived) {
+        return false;
+    }
+
+    let passes_filter = match filter {
+        CharacterFilter::All => true,
+        CharacterFilter::HasHotkey => profile.character_hotkeys.contains_key(name),
+        CharacterFilter::NoHotkey => !profile.character_hotkeys.contains_key(name),
+        CharacterFilter::InCycleGroup => in_cycle_group(name, profile),
+        CharacterFilter::NotInCycleGroup => !in_cycle_group(name, profile),
+        CharacterFilter::Unused => settings.is_unused(now, unused_days),
+        CharacterFilter::Archived => true,
+    };
+    if !passes_filter {
+        return false;
+    }
+
+    let haystack = std::iter::once(name)
+        .chain(identity.and_then(|i| i.alias.as_deref()))
+        .chain(identity.and_then(|i| i.notes.as_deref()))
+        .chain(
+            identity
+                .iter()
+                .flat_map(|i| i.tags.iter().map(String::as_str)),
+        )
+        .collect::<Vec<_>>()
+        .join("\n")
+        .to_lowercase();
+
+    query
+        .to_lowercase()
+        .split_whitespace()
+        .all(|word| haystack.contains(word))
+}
+
+fn in_cycle_group(name: &str, profile: &Profile) -> bool {
+    profile.cycle_groups.iter().any(|group| {
+        group
+            .cycle_list
+            .iter()
+            .any(|slot| *slot == CycleSlot::Eve(name.to_string()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile() -> Profile {
+        let mut profile = Profile::default_with_name("Test".to_string(), String::new());
+        profile.cycle_groups[0].cycle_list = vec![CycleSlot::Eve("Alice".to_string())];
+        profile.character_hotkeys.insert(
+            "Bob".to_string(),
+            crate::config::HotkeyBinding::new(59, false, false, false, false),
+        );
+        profile
+    }
+
+    #[test]
+    fn test_search_matches_name_alias_notes_and_tags() {
+        let profile = profile();
+        let settings = CharacterSettings::new(0, 0, 0, 0);
+        let identity = CharacterIdentity {
+            alias: Some("Scout".to_string()),
+            notes: Some("Cloaky #hauler".to_string()),
+            tags: vec!["Jita".to_string()],
+            portrait: None,
+        };
+        let search = |query| {
+            matches(
+                "Alice",
+                &settings,
+                Some(&identity),
+                &profile,
+                query,
+                CharacterFilter::All,
+                0,
+                30,
+            )
+        };
+
+        assert!(search(""));
+        assert!(search("ali"));
+        assert!(search("scout"));
+        assert!(search("#HAULER alice"));
+        assert!(search("jita"));
+        assert!(!search("#miner"));
+    }
+
+    #[test]
+    fn test_filters() {
+        let profile = profile();
+        let settings = CharacterSettings::new(0, 0, 0, 0);
+        let shown = |name, filter| matches(name, &settings, None, &profile, "", filter, 0, 30);
+
+        assert!(shown("Bob", CharacterFilter::HasHotkey));
+        assert!(!shown("Alice", CharacterFilter::HasHotkey));
+        assert!(shown("Alice", CharacterFilter::NoHotkey));
+        assert!(shown("Alice", CharacterFilter::InCycleGroup));
+        assert!(shown("Bob", CharacterFilter::NotInCycleGroup));
+        assert!(!shown("Alice", CharacterFilter::NotInCycleGroup));
+    }
+
+    #[test]
+    fn test_unused_and_archived() {
+        const DAY: i64 = 24 * 60 * 60;
+        let profile = profile();
+        let now = 100 * DAY;
+        let mut settings = CharacterSettings::new(0, 0, 0, 0);
+        let shown = |settings: &CharacterSettings, filter| {
+            matches("Alice", settings, None, &profile, "", filter, now, 30)
+        };
+
+        settings.last_seen = Some(now - 40 * DAY);
+        assert!(shown(&settings, CharacterFilter::Unused));
+        assert!(shown(&settings, CharacterFilter::All));
+        assert!(!shown(&settings, CharacterFilter::Archived));
+
+        settings.archived = true;
+        assert!(!shown(&settings, CharacterFilter::Unused));
+        assert!(!shown(&settings, CharacterFilter::All));
+        assert!(shown(&settings, CharacterFilter::Archived));
+
+        settings.archived = false;
+        settings.last_seen = Some(now - DAY);
+        assert!(!shown(&settings, CharacterFilter::Unused));
+    }
+}
@@ -35,6 +35,28 @@ pub fn render_cycle_group_column(
                 let response = ui.add(text_edit);
 
                 if response.lost_focus() || ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                    // The cycle order badge follows its group
+                    let old_name = &profile.cycle_groups[idx].name;
+                    if profile.thumbnail_cycle_order_group == *old_name {
+                        profile.thumbnail_cycle_order_group = state.rename_buffer.clone();
+                    }
+                    // So do characters shown only with it
+                    for settings in profile.character_thumbnails.values_mut() {
+                        if settings.show_only_in_group.as_ref() == Some(old_name) {
+                            settings.show_only_in_group = Some(state.rename_buffer.clone());
+                        }
+                    }
+                    // And groups listing it
+                    let old_slot = crate::config::profile::CycleSlot::Group(old_name.clone());
+                    for group in &mut profile.cycle_groups {
+                        for slot in &mut group.cycle_list {
+                            if *slot == old_slot {
+                                *slot = crate::config::profile::CycleSlot::Group(
+                                    state.rename_buffer.clone(),
+                                );
+                            }
+                        }
+                    }
                     profile.cycle_groups[idx].name = state.rename_buffer.clone();
                     state.renaming_group_idx = None;
                     *changed = true;
@@ -55,11 +77,14 @@ pub fn render_cycle_group_column(
                 .selected_text(&profile.cycle_groups[state.selected_cycle_group_index].name)
                 .show_ui(ui, |ui| {
                     for (idx, group) in profile.cycle_groups.iter().enumerate() {
-                        ui.selectable_value(
-                            &mut state.selected_cycle_group_index,
-                            idx,
-                            &group.name,
-                        );
+                        ui.horizontal(|ui| {
+                            group_swatch(ui, group);
+                            ui.selectable_value(
+                                &mut state.selected_cycle_group_index,
+                                idx,
+                                &group.name,
+                            );
+                        });
                     }
                 });
 
@@ -112,9 +137,13 @@ pub fn render_cycle_group_column(
                     .on_hover_text("Delete Group")
                     .clicked()
                 {
-                    profile
+                    let removed = profile
                         .cycle_groups
                         .remove(state.selected_cycle_group_index);
+                    let removed_slot = crate::config::profile::CycleSlot::Group(removed.name);
+                    for group in &mut profile.cycle_groups {
+                        group.cycle_list.retain(|slot| *slot != removed_slot);
+                    }
                     if state.selected_cycle_group_index >= profile.cycle_groups.len() {
                         state.selected_cycle_group_index =
                             profile.cycle_groups.len().saturating_sub(1);
@@ -188,6 +217,94 @@ pub fn render_cycle_group_column(
         }
     });
 
+    ui.horizontal(|ui| {
+        // Show/Hide the group's thumbnails
+        ui.label("Show/Hide:");
+
+        if let Some(binding) = &current_group.hotkey_toggle_visibility {
+            ui.label(egui::RichText::new(binding.display_name()).strong());
+        } else {
+            ui.label(egui::RichText::new("Not set").weak());
+        }
+
+        let id_str_vis = format!("GROUP:{}:VIS", state.selected_cycle_group_index);
+        let bind_text_vis = if hotkey_state.is_capturing_for(&id_str_vis) {
+            "Capturing..."
+        } else {
+            "⌨ Bind"
+        };
+
+        if ui.button(bind_text_vis).clicked() {
+            hotkey_state.start_key_capture_for_character(id_str_vis, profile.hotkey_backend);
+        }
+
+        if current_group.hotkey_toggle_visibility.is_some() && ui.small_button("✖").clicked() {
+            current_group.hotkey_toggle_visibility = None;
+            *changed = true;
+        }
+    });
+    ui.label(
+        egui::RichText::new(
+            "Show/Hide hides this group's thumbnails until pressed again or the daemon restarts.",
+        )
+        .small()
+        .weak(),
+    );
+
+    ui.add_space(ITEM_SPACING);
+    ui.horizontal(|ui| {
+        let mut has_color = current_group.color.is_some();
+        if ui
+            .checkbox(&mut has_color, "Accent Color:")
+            .on_hover_text("Marks member thumbnails with a strip in this color")
+            .changed()
+        {
+            current_group.color = has_color.then(|| "#3498DB".to_string());
+            *changed = true;
+        }
+
+        if let Some(color_str) = &mut current_group.color {
+            let text_edit = egui::TextEdit::singleline(color_str).desired_width(100.0);
+            if ui.add(text_edit).changed() {
+                *changed = true;
+            }
+
+            // Color picker button
+            if let Ok(mut color) = crate::manager::utils::parse_hex_color(color_str)
+                && ui.color_edit_button_srgba(&mut color).changed()
+            {
+                *color_str = crate::manager::utils::format_hex_color(color);
+                *changed = true;
+            }
+        }
+    });
+
+    ui.add_space(ITEM_SPACING);
+    ui.horizontal(|ui| {
+        if ui
+            .checkbox(
+                &mut current_group.auto_include_new,
+                "Auto-include new characters",
+            )
+            .on_hover_text("Characters logged in for the first time are added to this group")
+            .changed()
+        {
+            *changed = true;
+        }
+        ui.add_enabled_ui(current_group.auto_include_new, |ui| {
+            if ui
+                .checkbox(
+                    &mut current_group.auto_include_hotkey,
+                    "Assign next free number key",
+                )
+                .on_hover_text("Binds the first unused key of 1-9 and 0 to the new character")
+                .changed()
+            {
+                *changed = true;
+            }
+        });
+    });
+
     ui.add_space(ITEM_SPACING);
     ui.separator();
     ui.add_space(ITEM_SPACING);
@@ -196,12 +313,44 @@ pub fn render_cycle_group_column(
     ui.horizontal(|ui| {
         ui.label(egui::RichText::new("Characters").strong());
         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            // Other groups, so overlapping lists are kept in one place
+            let selected = state.selected_cycle_group_index;
+            let addable: Vec<String> = profile
+                .cycle_groups
+                .iter()
+                .enumerate()
+                .filter(|&(idx, group)| {
+                    idx != selected
+                        && !profile.cycle_groups[selected].cycle_list.contains(
+                            &crate::config::profile::CycleSlot::Group(group.name.clone()),
+                        )
+                })
+                .map(|(_, group)| group.name.clone())
+                .collect();
+            ui.add_enabled_ui(!addable.is_empty(), |ui| {
+                ui.menu_button("➕ Add Group", |ui| {
+                    for name in addable {
+                        if ui.button(&name).clicked() {
+                            profile.cycle_groups[selected]
+                                .cycle_list
+                                .push(crate::config::profile::CycleSlot::Group(name));
+                            *changed = true;
+                            ui.close();
+                        }
+                    }
+                })
+                .response
+                .on_hover_text("Include every character of another group");
+            });
+
             if ui.button("➕ Add Chars").clicked() {
                 state.show_add_characters_popup = true;
                 state.character_selections.clear();
-                // Add EVE characters
-                for char_name in profile.character_thumbnails.keys() {
-                    state.character_selections.insert(char_name.clone(), false);
+                // Add EVE characters (archived ones stay hidden)
+                for (char_name, settings) in &profile.character_thumbnails {
+                    if !settings.archived {
+                        state.character_selections.insert(char_name.clone(), false);
+                    }
                 }
                 // Add Custom Sources
                 for source in &profile.custom_windows {
@@ -210,10 +359,18 @@ pub fn render_cycle_group_column(
                         .character_selections
                         .insert(source.alias.clone(), false);
                 }
+                // Add extra instances of Custom Sources seen before ("Discord #2")
+                for label in profile.custom_source_thumbnails.keys() {
+                    if profile.custom_windows.iter().any(|r| r.owns_label(label)) {
+                        state.character_selections.insert(label.clone(), false);
+                    }
+                }
             }
         });
     });
 
+    // Referenced groups show their accent color in the list
+    let groups = profile.cycle_groups.clone();
     let current_group = &mut profile.cycle_groups[state.selected_cycle_group_index];
 
     egui::ScrollArea::vertical()
@@ -247,6 +404,15 @@ pub fn render_cycle_group_column(
                                             ui.colored_label(egui::Color32::LIGHT_BLUE, "Source");
                                             ui.label(name);
                                         }
+                                        crate::config::profile::CycleSlot::Group(name) => {
+                                            ui.colored_label(egui::Color32::LIGHT_GREEN, "Group");
+                                            if let Some(group) =
+                                                groups.iter().find(|g| &g.name == name)
+                                            {
+                                                group_swatch(ui, group);
+                                            }
+                                            ui.label(name);
+                                        }
                                     }
                                 });
                             });
@@ -262,6 +428,7 @@ pub fn render_cycle_group_column(
                                         to_delete = Some(row_idx);
                                         *changed = true;
                                     }
+                                    skip_toggle(ui, slot, state);
                                 },
                             );
                             drag_source.response
@@ -321,3 +488,42 @@ pub fn render_cycle_group_column(
             }
         });
 }
+
+/// Button skipping a member while cycling, shown while the daemon runs
+fn skip_toggle(
+    ui: &mut egui::Ui,
+    slot: &crate::config::profile::CycleSlot,
+    state: &mut CharactersState,
+) {
+    let name = match slot {
+        crate::config::profile::CycleSlot::Eve(name)
+        | crate::config::profile::CycleSlot::Source(name) => name,
+        crate::config::profile::CycleSlot::Group(_) => return,
+    };
+    let Some(skipped) = state.skipped.as_ref().map(|s| s.contains(name)) else {
+        return;
+    };
+    let hover = if skipped {
+        "Skipped while cycling. Click to include it again."
+    } else {
+        "Skip while cycling (like the Toggle Skip hotkey)"
+    };
+    if ui
+        .selectable_label(skipped, "⏭")
+        .on_hover_text(hover)
+        .clicked()
+    {
+        state.skip_requests.push((name.clone(), !skipped));
+    }
+}
+
+/// Small square in the group's accent color (nothing if it has none)
+pub(super) fn group_swatch(ui: &mut egui::Ui, group: &crate::config::profile::CycleGroup) {
+    if let Some(color) = group
+        .color
+        .as_deref()
+        .and_then(|hex| crate::manager::utils::parse_hex_color(hex).ok())
+    {
+        ui.colored_label(color, "■").on_hover_text(&group.name);
+    }
+}
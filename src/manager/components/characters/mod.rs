@@ -3,9 +3,13 @@ use crate::config::profile::Profile;
 use eframe::egui;
 
 mod editor;
+mod filter;
 mod list;
 mod modals;
 
+/// Days without a login before a character counts as unused
+const DEFAULT_UNUSED_DAYS: u32 = 30;
+
 /// State for character management UI
 pub struct CharactersState {
     pub(crate) show_add_characters_popup: bool,
@@ -15,6 +19,25 @@ pub struct CharactersState {
     pub(crate) selected_cycle_group_index: usize,
     pub(crate) renaming_group_idx: Option<usize>,
     pub(crate) rename_buffer: String,
+    /// Search text for the character list
+    pub(crate) filter_text: String,
+    pub(crate) filter: filter::CharacterFilter,
+    /// Threshold for the "Unused" filter
+    pub(crate) unused_days: u32,
+    /// Bulk delete clicked once and awaiting confirmation
+    pub(crate) confirm_bulk_delete: bool,
+    /// Focus the search field on the next frame (Ctrl+F)
+    pub(crate) focus_search: bool,
+    /// New name chosen to merge each character into after a rename
+    pub(crate) merge_targets: std::collections::HashMap<String, String>,
+    pub(crate) show_duplicates_popup: bool,
+    /// Characters the running daemon skips while cycling (None while it isn't running)
+    pub(crate) skipped: Option<std::collections::BTreeSet<String>>,
+    /// Skip toggles clicked this frame, for the daemon
+    pub(crate) skip_requests: Vec<(String, bool)>,
+    /// Portrait previews by character, with the path they were loaded from (None if the image
+    /// failed to load)
+    pub(crate) portraits: std::collections::HashMap<String, (String, Option<egui::TextureHandle>)>,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -36,9 +59,51 @@ impl CharactersState {
             selected_cycle_group_index: 0,
             renaming_group_idx: None,
             rename_buffer: String::new(),
+            filter_text: String::new(),
+            filter: filter::CharacterFilter::default(),
+            unused_days: DEFAULT_UNUSED_DAYS,
+            confirm_bulk_delete: false,
+            focus_search: false,
+            merge_targets: std::collections::HashMap::new(),
+            show_duplicates_popup: false,
+            skipped: None,
+            skip_requests: Vec::new(),
+            portraits: std::collections::HashMap::new(),
         }
     }
 
+    /// Focus the search field on the next frame
+    pub fn focus_search(&mut self) {
+        self.focus_search = true;
+    }
+
+    /// Preview texture of `character`'s portrait at `path`, reloaded only when the path changes
+    pub(crate) fn portrait(
+        &mut self,
+        ctx: &egui::Context,
+        character: &str,
+        path: &str,
+    ) -> Option<&egui::TextureHandle> {
+        let entry = self
+            .portraits
+            .entry(character.to_string())
+            .or_insert_with(|| (String::new(), None));
+        if entry.0 != path {
+            let texture = crate::common::image::decode_png(path)
+                .inspect_err(|e| tracing::debug!(path, error = ?e, "Failed to load portrait"))
+                .ok()
+                .map(|(rgba, width, height)| {
+                    let image = egui::ColorImage::from_rgba_unmultiplied(
+                        [width as usize, height as usize],
+                        &rgba,
+                    );
+                    ctx.load_texture(format!("portrait:{character}"), image, Default::default())
+                });
+            *entry = (path.to_string(), texture);
+        }
+        entry.1.as_ref()
+    }
+
     pub fn load_from_profile(&mut self, _profile: &Profile) {
         self.cached_overrides.clear();
     }
@@ -53,6 +118,7 @@ impl Default for CharactersState {
 pub fn ui(
     ui: &mut egui::Ui,
     profile: &mut Profile,
+    registry: &mut crate::config::CharacterRegistry,
     state: &mut CharactersState,
     hotkey_state: &mut crate::manager::components::hotkey_settings::HotkeySettingsState,
 ) -> bool {
@@ -62,12 +128,16 @@ pub fn ui(
         state.selected_cycle_group_index = 0;
     }
 
-    render_two_column_layout(ui, profile, state, hotkey_state, &mut changed);
+    render_two_column_layout(ui, profile, registry, state, hotkey_state, &mut changed);
 
     if state.show_add_characters_popup {
         modals::render_add_characters_modal(ui.ctx(), profile, state, &mut changed);
     }
 
+    if state.show_duplicates_popup {
+        modals::render_duplicates_modal(ui.ctx(), profile, registry, state, &mut changed);
+    }
+
     if hotkey_state.is_dialog_open() {
         changed |= crate::manager::components::hotkey_settings::render_key_capture_modal(
             ui,
@@ -82,6 +152,7 @@ pub fn ui(
 fn render_two_column_layout(
     ui: &mut egui::Ui,
     profile: &mut Profile,
+    registry: &mut crate::config::CharacterRegistry,
     state: &mut CharactersState,
     hotkey_state: &mut crate::manager::components::hotkey_settings::HotkeySettingsState,
     changed: &mut bool,
@@ -92,7 +163,14 @@ fn render_two_column_layout(
     ui.horizontal_top(|ui| {
         ui.allocate_ui(egui::vec2(left_width, ui.available_height()), |ui| {
             ui.vertical(|ui| {
-                editor::render_character_editor_column(ui, profile, state, hotkey_state, changed);
+                editor::render_character_editor_column(
+                    ui,
+                    profile,
+                    registry,
+                    state,
+                    hotkey_state,
+                    changed,
+                );
             });
         });
 
@@ -69,10 +69,11 @@ pub fn render_add_characters_modal(
                                 current_group.cycle_list.iter().any(|s| match s {
                                     crate::config::profile::CycleSlot::Eve(n) => n == &name,
                                     crate::config::profile::CycleSlot::Source(n) => n == &name,
+                                    crate::config::profile::CycleSlot::Group(_) => false,
                                 });
 
                             let is_custom_source =
-                                profile.custom_windows.iter().any(|r| r.alias == name);
+                                profile.custom_windows.iter().any(|r| r.owns_label(&name));
 
                             let display_name = if is_custom_source {
                                 format!("[Source] {}", name)
@@ -105,11 +106,12 @@ pub fn render_add_characters_modal(
                             let already_exists = current_group.cycle_list.iter().any(|s| match s {
                                 crate::config::profile::CycleSlot::Eve(n) => n == name,
                                 crate::config::profile::CycleSlot::Source(n) => n == name,
+                                crate::config::profile::CycleSlot::Group(_) => false,
                             });
 
                             if !already_exists {
                                 let is_source =
-                                    profile.custom_windows.iter().any(|r| r.alias == *name);
+                                    profile.custom_windows.iter().any(|r| r.owns_label(name));
                                 let slot = if is_source {
                                     crate::config::profile::CycleSlot::Source(name.clone())
                                 } else {
@@ -137,3 +139,91 @@ pub fn render_add_characters_modal(
         state.show_add_characters_popup = false;
     }
 }
+
+/// Entries of one character that differ only in case or spacing, with what combining each
+/// set takes over
+pub fn render_duplicates_modal(
+    ctx: &egui::Context,
+    profile: &mut Profile,
+    registry: &mut crate::config::CharacterRegistry,
+    state: &mut CharactersState,
+    changed: &mut bool,
+) {
+    let mut open = true;
+    egui::Window::new("Duplicate Characters")
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+        .show(ctx, |ui| {
+            ui.set_min_width(360.0);
+            let duplicates = profile.duplicate_characters();
+            if duplicates.is_empty() {
+                ui.label("No duplicate characters found.");
+                return;
+            }
+            ui.label(
+                egui::RichText::new(
+                    "The most recently seen entry is kept and takes over the settings only the others have.",
+                )
+                .small()
+                .weak(),
+            );
+            ui.add_space(ITEM_SPACING);
+
+            let mut combine = Vec::new();
+            for (keep, others) in &duplicates {
+                // Preview on copies of the profile and registry
+                let mut preview = profile.clone();
+                let mut preview_registry = registry.clone();
+                let mut taken: Vec<&str> = others
+                    .iter()
+                    .flat_map(|other| {
+                        let mut taken = preview.combine_characters(keep, other);
+                        taken.extend(preview_registry.combine(keep, other));
+                        taken
+                    })
+                    .collect();
+                taken.sort_unstable();
+                taken.dedup();
+
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new(format!("\"{keep}\"")).strong());
+                    let others_text: Vec<String> =
+                        others.iter().map(|other| format!("\"{other}\"")).collect();
+                    ui.label(format!("← {}", others_text.join(", ")));
+                    if ui.small_button("Merge").clicked() {
+                        combine.push((keep.clone(), others.clone()));
+                    }
+                });
+                let summary = if taken.is_empty() {
+                    "Nothing to take over; the duplicates are removed".to_string()
+                } else {
+                    format!("Takes over: {}", taken.join(", "))
+                };
+                ui.label(egui::RichText::new(summary).small().weak());
+                ui.add_space(ITEM_SPACING / 2.0);
+            }
+
+            if duplicates.len() > 1 {
+                ui.separator();
+                if ui.button("Merge All").clicked() {
+                    combine = duplicates.clone();
+                }
+            }
+
+            for (keep, others) in combine {
+                for other in others {
+                    profile.combine_characters(&keep, &other);
+                    registry.combine(&keep, &other);
+                    state.expanded_rows.remove(&other);
+                    state.merge_targets.remove(&other);
+                }
+                *changed = true;
+            }
+        });
+
+    if !open {
+        state.show_duplicates_popup = false;
+    }
+}
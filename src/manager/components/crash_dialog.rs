@@ -0,0 +1,79 @@
+//! Dialog shown when the daemon keeps crashing
+
+use eframe::egui;
+use std::time::Instant;
+
+use crate::common::constants::manager_ui::*;
+use crate::manager::state::SharedState;
+
+/// Renders the crash dialog once the session crash count reaches the threshold
+pub fn render(ctx: &egui::Context, state: &mut SharedState) {
+    let crashes = state.restart_backoff.crash_count;
+    if crashes < DAEMON_CRASH_DIALOG_THRESHOLD || crashes <= state.crash_dialog_acknowledged {
+        return;
+    }
+
+    let mut dismissed = false;
+    let mut restart_now = false;
+
+    egui::Window::new("Preview Daemon Crashed")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.colored_label(
+                COLOR_ERROR,
+                format!("The preview daemon crashed {} times this session.", crashes),
+            );
+
+            if let Some(at) = state.restart_backoff.next_restart {
+                let secs = at.saturating_duration_since(Instant::now()).as_secs() + 1;
+                ui.label(format!("Restarting automatically in {}s.", secs));
+            } else if state.daemon.is_some() {
+                ui.label("The daemon has been restarted.");
+            }
+
+            ui.add_space(ITEM_SPACING);
+
+            egui::CollapsingHeader::new("View log")
+                .default_open(false)
+                .show(ui, |ui| {
+                    let lines = state.daemon_log.snapshot();
+                    egui::ScrollArea::vertical()
+                        .max_height(250.0)
+                        .max_width(600.0)
+                        .stick_to_bottom(true)
+                        .show(ui, |ui| {
+                            if lines.is_empty() {
+                                ui.label(
+                                    egui::RichText::new("No daemon output captured")
+                                        .small()
+                                        .weak(),
+                                );
+                            }
+                            for line in &lines {
+                                ui.label(egui::RichText::new(line).monospace().small());
+                            }
+                        });
+                });
+
+            ui.add_space(ITEM_SPACING);
+
+            ui.horizontal(|ui| {
+                if ui.button("Restart Now").clicked() {
+                    restart_now = true;
+                }
+                if ui.button("Dismiss").clicked() {
+                    dismissed = true;
+                }
+            });
+        });
+
+    if restart_now {
+        state.restart_daemon();
+        dismissed = true;
+    }
+    if dismissed {
+        state.crash_dialog_acknowledged = crashes;
+    }
+}
@@ -0,0 +1,100 @@
+//! Dialog offering crash reports left by a previous session
+
+use eframe::egui;
+use std::path::{Path, PathBuf};
+use tracing::{error, warn};
+
+use crate::common::constants::manager_ui::*;
+use crate::common::crash_report;
+
+/// Crash reports not yet shown to the user
+pub struct CrashReportDialogState {
+    reports: Vec<PathBuf>,
+    error: Option<String>,
+}
+
+impl CrashReportDialogState {
+    /// Collect reports written since the last time the dialog was acknowledged
+    pub fn load() -> Self {
+        Self {
+            reports: crash_report::unseen_reports(),
+            error: None,
+        }
+    }
+}
+
+pub fn render(ctx: &egui::Context, state: &mut CrashReportDialogState) {
+    let Some(latest) = state.reports.last().cloned() else {
+        return;
+    };
+
+    let mut dismissed = false;
+
+    egui::Window::new("Previous Crash Detected")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            let text = if state.reports.len() == 1 {
+                "EVE Preview Manager crashed during a previous session.".to_string()
+            } else {
+                format!(
+                    "EVE Preview Manager crashed {} times since you last checked.",
+                    state.reports.len()
+                )
+            };
+            ui.colored_label(COLOR_ERROR, text);
+            ui.label("A crash report was saved to:");
+            ui.label(egui::RichText::new(latest.display().to_string()).monospace());
+            ui.label(
+                egui::RichText::new(
+                    "Character and profile names are redacted. Attach the report when filing an issue",
+                )
+                .small()
+                .weak(),
+            );
+
+            if let Some(err) = &state.error {
+                ui.colored_label(COLOR_ERROR, err);
+            }
+
+            ui.add_space(ITEM_SPACING);
+
+            ui.horizontal(|ui| {
+                if ui.button("Open Report").clicked() {
+                    match open_path(&latest) {
+                        Ok(()) => dismissed = true,
+                        Err(e) => state.error = Some(e),
+                    }
+                }
+                if ui.button("Open Folder").clicked() {
+                    match open_path(&crash_report::report_dir()) {
+                        Ok(()) => dismissed = true,
+                        Err(e) => state.error = Some(e),
+                    }
+                }
+                if ui.button("Dismiss").clicked() {
+                    dismissed = true;
+                }
+            });
+        });
+
+    if dismissed {
+        if let Err(e) = crash_report::mark_seen(&latest) {
+            error!(error = ?e, "Failed to mark crash reports as seen");
+        }
+        state.reports.clear();
+    }
+}
+
+/// Open a file or directory with the desktop's default application
+fn open_path(path: &Path) -> Result<(), String> {
+    std::process::Command::new("xdg-open")
+        .arg(path)
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| {
+            warn!(path = ?path, error = %e, "Failed to run xdg-open");
+            format!("Could not open {}: {}", path.display(), e)
+        })
+}
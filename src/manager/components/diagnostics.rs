@@ -0,0 +1,152 @@
+//! Diagnostics tab: environment checks with hints
+
+use crate::common::constants::manager_ui::*;
+use crate::common::ipc::HotkeyLatency;
+use crate::config::profile::{Config, Profile};
+use crate::config::validation::{self, Issue, Severity};
+use crate::manager::diagnostics::{self, Check, CheckStatus};
+use eframe::egui;
+
+/// State for the diagnostics UI
+#[derive(Default)]
+pub struct DiagnosticsState {
+    /// Results of the last run; `None` until the tab is first opened
+    checks: Option<Vec<Check>>,
+    /// Result of the last config file validation; `None` until "Validate" is clicked
+    config_issues: Option<Result<Vec<Issue>, String>>,
+}
+
+pub fn ui(
+    ui: &mut egui::Ui,
+    profile: &Profile,
+    latency: Option<&HotkeyLatency>,
+    state: &mut DiagnosticsState,
+) {
+    let checks = state
+        .checks
+        .get_or_insert_with(|| diagnostics::run(profile));
+    // Latency is live, so it is not part of the cached results
+    let latency_check = diagnostics::latency_check(latency);
+
+    ui.group(|ui| {
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new("System Diagnostics").strong());
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.button("Copy Report").clicked() {
+                    let mut report = checks.clone();
+                    report.push(latency_check.clone());
+                    ui.ctx().copy_text(diagnostics::format_report(&report));
+                }
+                if ui.button("Run Again").clicked() {
+                    *checks = diagnostics::run(profile);
+                }
+            });
+        });
+
+        ui.label(
+            egui::RichText::new(
+                "Checks the display server, compositor, hotkey permissions and fonts used by the preview daemon",
+            )
+            .small()
+            .weak(),
+        );
+        ui.add_space(ITEM_SPACING);
+
+        egui::Grid::new("diagnostics_grid")
+            .num_columns(3)
+            .spacing([ITEM_SPACING * 2.0, ITEM_SPACING])
+            .striped(true)
+            .show(ui, |ui| {
+                for check in checks.iter().chain(std::iter::once(&latency_check)) {
+                    let color = match check.status {
+                        CheckStatus::Ok => COLOR_SUCCESS,
+                        CheckStatus::Info => ui.visuals().weak_text_color(),
+                        CheckStatus::Warning => COLOR_WARNING,
+                        CheckStatus::Error => COLOR_ERROR,
+                    };
+                    ui.label(
+                        egui::RichText::new(check.status.label())
+                            .monospace()
+                            .strong()
+                            .color(color),
+                    );
+                    ui.label(egui::RichText::new(&check.name).strong());
+                    ui.vertical(|ui| {
+                        ui.label(&check.detail);
+                        if let Some(hint) = &check.hint {
+                            ui.label(egui::RichText::new(hint).small().weak());
+                        }
+                    });
+                    ui.end_row();
+                }
+            });
+    });
+
+    ui.add_space(ITEM_SPACING);
+    render_config_validation(ui, state);
+}
+
+fn render_config_validation(ui: &mut egui::Ui, state: &mut DiagnosticsState) {
+    ui.group(|ui| {
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new("Config File").strong());
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if let Some(Ok(issues)) = &state.config_issues
+                    && ui.button("Copy Report").clicked()
+                {
+                    ui.ctx().copy_text(validation::format_report(issues));
+                }
+                if ui.button("Validate").clicked() {
+                    state.config_issues =
+                        Some(validation::check_file(&Config::path()).map_err(|e| format!("{e:#}")));
+                }
+            });
+        });
+
+        ui.label(
+            egui::RichText::new(
+                "Checks the saved config for unknown fields, invalid colors, dangling cycle group members, hotkey conflicts and out-of-range sizes",
+            )
+            .small()
+            .weak(),
+        );
+        ui.add_space(ITEM_SPACING);
+
+        match &state.config_issues {
+            None => {}
+            Some(Err(err)) => {
+                ui.colored_label(COLOR_ERROR, err);
+            }
+            Some(Ok(issues)) if issues.is_empty() => {
+                ui.colored_label(COLOR_SUCCESS, "No problems found");
+            }
+            Some(Ok(issues)) => {
+                egui::Grid::new("config_validation_grid")
+                    .num_columns(2)
+                    .spacing([ITEM_SPACING * 2.0, ITEM_SPACING])
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for issue in issues {
+                            let (label, color) = match issue.severity {
+                                Severity::Error => (CheckStatus::Error.label(), COLOR_ERROR),
+                                Severity::Warning => (CheckStatus::Warning.label(), COLOR_WARNING),
+                            };
+                            ui.label(
+                                egui::RichText::new(label)
+                                    .monospace()
+                                    .strong()
+                                    .color(color),
+                            );
+                            ui.vertical(|ui| {
+                                ui.label(&issue.message);
+                                ui.label(egui::RichText::new(&issue.path).monospace().small().weak());
+                            });
+                            ui.end_row();
+                        }
+                    });
+            }
+        }
+    });
+}
@@ -6,7 +6,9 @@ use tokio::sync::Notify;
 use tracing::error;
 
 use crate::common::constants::manager_ui::*;
+use crate::common::i18n::{tr, tr_with};
 use crate::manager::components::profile_selector::{ProfileAction, ProfileSelector};
+use crate::manager::components::save_preview::{self, SavePreviewState};
 use crate::manager::state::core::SaveMode;
 use crate::manager::state::{ManagerTab, SharedState, StatusMessage};
 
@@ -17,6 +19,7 @@ pub fn render(
     state: &mut SharedState,
     active_tab: &mut ManagerTab,
     profile_selector: &mut ProfileSelector,
+    save_preview_state: &mut SavePreviewState,
     #[cfg(target_os = "linux")] update_signal: &Arc<Notify>,
 ) -> ProfileAction {
     let mut action = ProfileAction::None;
@@ -26,7 +29,19 @@ pub fn render(
         // Left side: Status indicators
         ui.colored_label(state.daemon_status.color(), state.daemon_status.label());
         if let Some(child) = &state.daemon {
-            ui.label(format!("(PID: {})", child.id()));
+            ui.label(tr_with(
+                "header-daemon-pid",
+                &[("pid", child.id().to_string())],
+            ));
+        } else if let Some(at) = state.restart_backoff.next_restart {
+            let secs = at
+                .saturating_duration_since(std::time::Instant::now())
+                .as_secs()
+                + 1;
+            ui.label(tr_with(
+                "header-restarting-in",
+                &[("seconds", secs.to_string())],
+            ));
         }
         if let Some(message) = &state.status_message {
             ui.add_space(10.0);
@@ -39,9 +54,53 @@ pub fn render(
 
             // Render in reverse order (Right -> Left)
 
+            // 7. Logs
+            if ui
+                .add(egui::Button::new(tr("tab-logs")).selected(*active_tab == ManagerTab::Logs))
+                .clicked()
+            {
+                *active_tab = ManagerTab::Logs;
+            }
+            ui.add_space(5.0);
+
+            // 6b. Stats
+            if ui
+                .add(egui::Button::new(tr("tab-stats")).selected(*active_tab == ManagerTab::Stats))
+                .clicked()
+            {
+                *active_tab = ManagerTab::Stats;
+            }
+            ui.add_space(5.0);
+
+            // 6. Diagnostics
+            if ui
+                .add(
+                    egui::Button::new(tr("tab-diagnostics"))
+                        .selected(*active_tab == ManagerTab::Diagnostics),
+                )
+                .clicked()
+            {
+                *active_tab = ManagerTab::Diagnostics;
+            }
+            ui.add_space(5.0);
+
+            // 5b. Layout
+            if ui
+                .add(
+                    egui::Button::new(tr("tab-layout")).selected(*active_tab == ManagerTab::Layout),
+                )
+                .clicked()
+            {
+                *active_tab = ManagerTab::Layout;
+            }
+            ui.add_space(5.0);
+
             // 5. Sources
             if ui
-                .add(egui::Button::new("Sources").selected(*active_tab == ManagerTab::Sources))
+                .add(
+                    egui::Button::new(tr("tab-sources"))
+                        .selected(*active_tab == ManagerTab::Sources),
+                )
                 .clicked()
             {
                 *active_tab = ManagerTab::Sources;
@@ -51,7 +110,8 @@ pub fn render(
             // 4. Characters
             if ui
                 .add(
-                    egui::Button::new("Characters").selected(*active_tab == ManagerTab::Characters),
+                    egui::Button::new(tr("tab-characters"))
+                        .selected(*active_tab == ManagerTab::Characters),
                 )
                 .clicked()
             {
@@ -62,7 +122,8 @@ pub fn render(
             // 2. Appearance
             if ui
                 .add(
-                    egui::Button::new("Appearance").selected(*active_tab == ManagerTab::Appearance),
+                    egui::Button::new(tr("tab-appearance"))
+                        .selected(*active_tab == ManagerTab::Appearance),
                 )
                 .clicked()
             {
@@ -72,7 +133,10 @@ pub fn render(
 
             // 3. Hotkeys
             if ui
-                .add(egui::Button::new("Hotkeys").selected(*active_tab == ManagerTab::Hotkeys))
+                .add(
+                    egui::Button::new(tr("tab-hotkeys"))
+                        .selected(*active_tab == ManagerTab::Hotkeys),
+                )
                 .clicked()
             {
                 *active_tab = ManagerTab::Hotkeys;
@@ -81,7 +145,10 @@ pub fn render(
 
             // 1. Behavior
             if ui
-                .add(egui::Button::new("Behavior").selected(*active_tab == ManagerTab::Behavior))
+                .add(
+                    egui::Button::new(tr("tab-behavior"))
+                        .selected(*active_tab == ManagerTab::Behavior),
+                )
                 .clicked()
             {
                 *active_tab = ManagerTab::Behavior;
@@ -105,37 +172,75 @@ pub fn render(
         // 2. Right: Save & Discard Buttons
         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
             // Discard button
-            if ui.button("✖ Discard Changes").clicked() {
+            if ui.button(tr("header-discard")).clicked() {
                 state.discard_changes();
             }
 
-            // Save button
-            if ui.button("💾 Save & Apply").clicked() {
-                if let Err(err) = state.save_config(SaveMode::Explicit) {
-                    error!(error = ?err, "Failed to save config");
-                    state.status_message = Some(StatusMessage {
-                        text: format!("Save failed: {err}"),
-                        color: COLOR_ERROR,
-                    });
-                } else {
-                    state.reload_daemon_config();
+            // Save button (reviews the changes first when there are any)
+            let save_shortcut = ctx.input_mut(|i| {
+                i.consume_shortcut(&egui::KeyboardShortcut::new(
+                    egui::Modifiers::COMMAND,
+                    egui::Key::S,
+                ))
+            });
+            if (ui
+                .button(tr("header-save"))
+                .on_hover_text(tr("header-save-hint"))
+                .clicked()
+                || save_shortcut)
+                && !save_preview_state.is_open()
+                && !save_preview_state.open(&state.config)
+            {
+                let result = state.save_config(SaveMode::Explicit);
+                finish_save(
+                    state,
+                    result,
                     #[cfg(target_os = "linux")]
-                    update_signal.notify_one();
-                }
+                    update_signal,
+                );
+            }
+
+            ui.add_space(ITEM_SPACING);
+
+            // Undo/Redo buttons
+            let profile = &state.config.profiles[state.selected_profile_idx];
+            let (can_undo, can_redo) = (
+                state.history.can_undo(profile),
+                state.history.can_redo(profile),
+            );
+            if ui
+                .add_enabled(can_redo, egui::Button::new(tr("header-redo")))
+                .on_hover_text(tr("header-redo-hint"))
+                .clicked()
+            {
+                state.redo_profile_edit();
+            }
+            if ui
+                .add_enabled(can_undo, egui::Button::new(tr("header-undo")))
+                .on_hover_text(tr("header-undo-hint"))
+                .clicked()
+            {
+                state.undo_profile_edit();
             }
         });
     });
 
     // Row 2: Profile Actions | Config Status
     ui.horizontal(|ui| {
-        profile_selector.render_buttons(ui, &state.config, state.selected_profile_idx);
+        let buttons_action =
+            profile_selector.render_buttons(ui, &mut state.config, &mut state.selected_profile_idx);
+        if !matches!(buttons_action, ProfileAction::None) {
+            action = buttons_action;
+        }
 
         // Status text aligned to the right
         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
             if let Some(message) = &state.config_status_message {
                 ui.colored_label(message.color, &message.text);
             } else if state.settings_changed {
-                ui.colored_label(COLOR_WARNING, "Unsaved changes");
+                ui.colored_label(COLOR_WARNING, tr("header-unsaved-changes"));
+            } else if state.positions_dirty {
+                ui.colored_label(COLOR_WARNING, tr("header-unsaved-positions"));
             }
         });
     });
@@ -150,5 +255,34 @@ pub fn render(
         action = dialog_action;
     }
 
+    if let Some(reviewed) = save_preview::render(ctx, save_preview_state, &state.config) {
+        let result = reviewed.and_then(|config| state.save_reviewed_config(config));
+        finish_save(
+            state,
+            result,
+            #[cfg(target_os = "linux")]
+            update_signal,
+        );
+    }
+
     action
 }
+
+/// Report a failed save, or push the saved config to the daemon and tray
+fn finish_save(
+    state: &mut SharedState,
+    result: anyhow::Result<()>,
+    #[cfg(target_os = "linux")] update_signal: &Arc<Notify>,
+) {
+    if let Err(err) = result {
+        error!(error = ?err, "Failed to save config");
+        state.status_message = Some(StatusMessage {
+            text: format!("Save failed: {err}"),
+            color: COLOR_ERROR,
+        });
+    } else {
+        state.reload_daemon_config();
+        #[cfg(target_os = "linux")]
+        update_signal.notify_one();
+    }
+}
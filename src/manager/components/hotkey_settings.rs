@@ -2,7 +2,9 @@
 
 use crate::common::constants::manager_ui::*;
 use crate::config::HotkeyBackendType;
+use crate::config::hotkey_binding::{Gesture, KeyMatch};
 use crate::config::profile::Profile;
+use crate::manager::diagnostics::{self, Check, CheckStatus};
 use crate::manager::key_capture::{self, CaptureResult, CaptureState};
 use eframe::egui;
 use std::sync::mpsc::Receiver;
@@ -11,6 +13,14 @@ use std::sync::mpsc::Receiver;
 enum CaptureTarget {
     ToggleSkip,         // Hotkey to temporarily skip current character
     TogglePreviews,     // Hotkey to toggle thumbnail visibility
+    SavePositions,      // Hotkey to save thumbnail positions to disk
+    UndoMove,           // Hotkey to revert the last thumbnail drag
+    ActiveForward,      // Hotkey to cycle forward in the active cycle group
+    ActiveBackward,     // Hotkey to cycle backward in the active cycle group
+    NextGroup,          // Hotkey to make the next cycle group active
+    Screenshot,         // Hotkey to screenshot the focused EVE client
+    HotkeyOverlay,      // Hotkey to show each character's hotkey on its thumbnail
+    SwapPositions,      // Hotkey to swap the focused thumbnail with the next one clicked
     Profile,            // Hotkey to switch to this profile
     Character(String),  // Character name for per-character hotkey
     CustomRule(String), // Custom Window Rule alias (Custom Source Hotkey)
@@ -31,6 +41,16 @@ pub struct HotkeySettingsState {
     current_capture_state: Option<CaptureState>,
     capture_result: Option<CaptureResult>,
     capture_error: Option<String>,
+
+    /// Result of the last cheat sheet export
+    cheat_sheet_status: Option<Result<String, String>>,
+
+    // evdev permission self-check
+    /// Results of the evdev access checks; `None` until the evdev backend is shown
+    evdev_checks: Option<Vec<Check>>,
+    test_read_rx: Option<Receiver<Result<String, String>>>,
+    /// Device that produced a key event in the last test read, or why none did
+    test_read_result: Option<Result<String, String>>,
 }
 
 impl HotkeySettingsState {
@@ -55,6 +75,10 @@ impl HotkeySettingsState {
             current_capture_state: None,
             capture_result: None,
             capture_error: None,
+            cheat_sheet_status: None,
+            evdev_checks: None,
+            test_read_rx: None,
+            test_read_result: None,
         }
     }
 
@@ -145,6 +169,103 @@ impl Default for HotkeySettingsState {
     }
 }
 
+/// Binding name plus the device it is restricted to and whether it follows the layout
+fn binding_label(binding: &crate::config::HotkeyBinding) -> String {
+    let mut label = binding.display_name();
+    if binding.match_by == KeyMatch::Keysym {
+        label.push_str(" (by symbol)");
+    }
+    if let Some(device_id) = &binding.device {
+        label.push_str(&format!(" (only {})", friendly_device_name(device_id)));
+    }
+    label
+}
+
+/// Format a /dev/input/by-id device ID to be more readable
+fn friendly_device_name(device_id: &str) -> String {
+    device_id
+        .replace("-event-kbd", " (Keyboard)")
+        .replace("-event-mouse", " (Mouse)")
+        .replace("_", " ")
+        .replace("-", " ")
+}
+
+/// Guided diagnosis of evdev device access with a test read
+fn render_evdev_access(ui: &mut egui::Ui, state: &mut HotkeySettingsState) {
+    if let Some(rx) = &state.test_read_rx {
+        match rx.try_recv() {
+            Ok(result) => {
+                state.test_read_result = Some(result);
+                state.test_read_rx = None;
+            }
+            Err(std::sync::mpsc::TryRecvError::Empty) => {
+                ui.ctx()
+                    .request_repaint_after(std::time::Duration::from_millis(100));
+            }
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => state.test_read_rx = None,
+        }
+    }
+
+    let checks = state
+        .evdev_checks
+        .get_or_insert_with(diagnostics::evdev_access);
+    for check in checks.iter() {
+        let color = match check.status {
+            CheckStatus::Ok => COLOR_SUCCESS,
+            CheckStatus::Info => ui.visuals().weak_text_color(),
+            CheckStatus::Warning => COLOR_WARNING,
+            CheckStatus::Error => COLOR_ERROR,
+        };
+        ui.horizontal_wrapped(|ui| {
+            ui.label(
+                egui::RichText::new(check.status.label())
+                    .monospace()
+                    .strong()
+                    .color(color),
+            );
+            ui.label(format!("{}: {}", check.name, check.detail));
+        });
+        if let Some(hint) = &check.hint {
+            ui.label(egui::RichText::new(hint).small().weak());
+        }
+    }
+
+    ui.horizontal(|ui| {
+        if ui.button("Check Again").clicked() {
+            *checks = diagnostics::evdev_access();
+        }
+        let reading = state.test_read_rx.is_some();
+        if ui
+            .add_enabled(!reading, egui::Button::new("Test Read"))
+            .on_hover_text(format!(
+                "Press any key within {} seconds to confirm evdev events arrive",
+                key_capture::TEST_READ_TIMEOUT.as_secs()
+            ))
+            .clicked()
+        {
+            state.test_read_result = None;
+            state.test_read_rx = Some(key_capture::start_test_read());
+        }
+        if reading {
+            ui.spinner();
+            ui.label(egui::RichText::new("Press any key...").small());
+        }
+    });
+
+    match &state.test_read_result {
+        Some(Ok(device)) => {
+            ui.colored_label(
+                COLOR_SUCCESS,
+                format!("Received a key event from {}", device),
+            );
+        }
+        Some(Err(e)) => {
+            ui.colored_label(COLOR_ERROR, format!("Test read failed: {}", e));
+        }
+        None => {}
+    }
+}
+
 /// Renders hotkey settings UI and returns true if changes were made
 pub fn ui(ui: &mut egui::Ui, profile: &mut Profile, state: &mut HotkeySettingsState) -> bool {
     let mut changed = false;
@@ -196,6 +317,8 @@ pub fn ui(ui: &mut egui::Ui, profile: &mut Profile, state: &mut HotkeySettingsSt
                 }
                 HotkeyBackendType::Evdev => {
                     ui.label(egui::RichText::new("⚠ Security Warning: evdev backend requires 'input' group membership.").small());
+                    ui.add_space(ITEM_SPACING / 2.0);
+                    render_evdev_access(ui, state);
                 }
             }
 
@@ -277,9 +400,54 @@ pub fn ui(ui: &mut egui::Ui, profile: &mut Profile, state: &mut HotkeySettingsSt
                     changed = true;
                 }
                 ui.label(egui::RichText::new("Characters that log out will remain in the cycle").small().weak());
+
+                ui.add_space(ITEM_SPACING);
+
+                if ui.checkbox(&mut profile.hotkey_switch_osd, "Show switched-to character on screen").changed() {
+                    changed = true;
+                }
+                ui.label(egui::RichText::new("Briefly name the character over its client after each hotkey switch").small().weak());
             });
         });
 
+        columns[0].add_space(ITEM_SPACING);
+        columns[0].group(|ui| {
+            ui.set_min_width(ui.available_width());
+            ui.label(egui::RichText::new("Cheat Sheet").strong());
+            ui.label(egui::RichText::new("All hotkeys of this profile on one page, for printing or sharing").small().weak());
+            ui.add_space(ITEM_SPACING);
+
+            ui.horizontal(|ui| {
+                if ui.button("📋 Copy Markdown").clicked() {
+                    ui.ctx().copy_text(crate::manager::cheat_sheet::to_markdown(profile));
+                    state.cheat_sheet_status = Some(Ok("Copied to clipboard".to_string()));
+                }
+                if ui.button("💾 Save HTML").on_hover_text("Printable page; opened in your browser").clicked() {
+                    state.cheat_sheet_status = Some(match crate::manager::cheat_sheet::save_html(profile) {
+                        Ok(path) => {
+                            if let Err(e) = std::process::Command::new("xdg-open").arg(&path).spawn() {
+                                tracing::warn!(path = ?path, error = %e, "Failed to run xdg-open");
+                            }
+                            Ok(format!("Saved to {}", path.display()))
+                        }
+                        Err(e) => {
+                            tracing::error!(error = ?e, "Failed to save hotkey cheat sheet");
+                            Err(format!("{e:#}"))
+                        }
+                    });
+                }
+            });
+            match &state.cheat_sheet_status {
+                Some(Ok(message)) => {
+                    ui.label(egui::RichText::new(message).small().color(COLOR_SUCCESS));
+                }
+                Some(Err(message)) => {
+                    ui.label(egui::RichText::new(message).small().color(COLOR_ERROR));
+                }
+                None => {}
+            }
+        });
+
         // --- Column 2: Profile Settings ---
         columns[1].group(|ui| {
             ui.set_min_width(ui.available_width());
@@ -298,7 +466,7 @@ pub fn ui(ui: &mut egui::Ui, profile: &mut Profile, state: &mut HotkeySettingsSt
 
                  ui.horizontal(|ui| {
                     let binding_text = profile.hotkey_profile_switch.as_ref()
-                        .map(|b| b.display_name())
+                        .map(binding_label)
                         .unwrap_or_else(|| "Not set".to_string());
 
                     let color = if profile.hotkey_profile_switch.is_none() {
@@ -332,7 +500,7 @@ pub fn ui(ui: &mut egui::Ui, profile: &mut Profile, state: &mut HotkeySettingsSt
 
                  ui.horizontal(|ui| {
                     let binding_text = profile.hotkey_toggle_skip.as_ref()
-                        .map(|b| b.display_name())
+                        .map(binding_label)
                         .unwrap_or_else(|| "Not set".to_string());
 
                     // Use default text color if set, weak if not (optional feature)
@@ -366,7 +534,7 @@ pub fn ui(ui: &mut egui::Ui, profile: &mut Profile, state: &mut HotkeySettingsSt
 
                  ui.horizontal(|ui| {
                     let binding_text = profile.hotkey_toggle_previews.as_ref()
-                        .map(|b| b.display_name())
+                        .map(binding_label)
                         .unwrap_or_else(|| "Not set".to_string());
 
                     let color = if profile.hotkey_toggle_previews.is_none() {
@@ -389,6 +557,270 @@ pub fn ui(ui: &mut egui::Ui, profile: &mut Profile, state: &mut HotkeySettingsSt
                  ui.add_space(ITEM_SPACING);
                  ui.label(egui::RichText::new("Show/Hide all thumbnails (resets to visible on restart).").weak().small());
 
+                 ui.add_space(ITEM_SPACING);
+                 ui.separator();
+                 ui.add_space(ITEM_SPACING);
+
+                 // Save Positions Hotkey
+                 ui.label("Save Positions Hotkey:");
+                 ui.add_space(ITEM_SPACING / 2.0);
+
+                 ui.horizontal(|ui| {
+                    let binding_text = profile.hotkey_save_positions.as_ref()
+                        .map(binding_label)
+                        .unwrap_or_else(|| "Not set".to_string());
+
+                    let color = if profile.hotkey_save_positions.is_none() {
+                         ui.style().visuals.weak_text_color()
+                    } else {
+                        ui.style().visuals.text_color()
+                    };
+
+                    ui.label(egui::RichText::new(binding_text).strong().color(color));
+
+                    if ui.button("⌨ Bind").clicked() {
+                        state.start_key_capture(CaptureTarget::SavePositions, profile.hotkey_backend);
+                    }
+
+                    if profile.hotkey_save_positions.is_some() && ui.small_button("✖").on_hover_text("Clear binding").clicked() {
+                        profile.hotkey_save_positions = None;
+                        changed = true;
+                    }
+                 });
+                 ui.add_space(ITEM_SPACING);
+                 ui.label(egui::RichText::new("Save current thumbnail positions to disk (useful with auto-save disabled).").weak().small());
+
+                 ui.add_space(ITEM_SPACING);
+                 ui.separator();
+                 ui.add_space(ITEM_SPACING);
+
+                 // Undo Move Hotkey
+                 ui.label("Undo Move Hotkey:");
+                 ui.add_space(ITEM_SPACING / 2.0);
+
+                 ui.horizontal(|ui| {
+                    let binding_text = profile.hotkey_undo_move.as_ref()
+                        .map(binding_label)
+                        .unwrap_or_else(|| "Not set".to_string());
+
+                    let color = if profile.hotkey_undo_move.is_none() {
+                         ui.style().visuals.weak_text_color()
+                    } else {
+                        ui.style().visuals.text_color()
+                    };
+
+                    ui.label(egui::RichText::new(binding_text).strong().color(color));
+
+                    if ui.button("⌨ Bind").clicked() {
+                        state.start_key_capture(CaptureTarget::UndoMove, profile.hotkey_backend);
+                    }
+
+                    if profile.hotkey_undo_move.is_some() && ui.small_button("✖").on_hover_text("Clear binding").clicked() {
+                        profile.hotkey_undo_move = None;
+                        changed = true;
+                    }
+                 });
+                 ui.add_space(ITEM_SPACING);
+                 ui.label(egui::RichText::new("Move the last dragged thumbnail back to where it was.").weak().small());
+
+                 ui.add_space(ITEM_SPACING);
+                 ui.separator();
+                 ui.add_space(ITEM_SPACING);
+
+                 // Active Group Forward Hotkey
+                 ui.label("Active Group Forward Hotkey:");
+                 ui.add_space(ITEM_SPACING / 2.0);
+
+                 ui.horizontal(|ui| {
+                    let binding_text = profile.hotkey_active_forward.as_ref()
+                        .map(binding_label)
+                        .unwrap_or_else(|| "Not set".to_string());
+
+                    let color = if profile.hotkey_active_forward.is_none() {
+                         ui.style().visuals.weak_text_color()
+                    } else {
+                        ui.style().visuals.text_color()
+                    };
+
+                    ui.label(egui::RichText::new(binding_text).strong().color(color));
+
+                    if ui.button("⌨ Bind").clicked() {
+                        state.start_key_capture(CaptureTarget::ActiveForward, profile.hotkey_backend);
+                    }
+
+                    if profile.hotkey_active_forward.is_some() && ui.small_button("✖").on_hover_text("Clear binding").clicked() {
+                        profile.hotkey_active_forward = None;
+                        changed = true;
+                    }
+                 });
+                 ui.add_space(ITEM_SPACING);
+                 ui.label(egui::RichText::new("Cycle forward in the active cycle group.").weak().small());
+
+                 ui.add_space(ITEM_SPACING);
+                 ui.separator();
+                 ui.add_space(ITEM_SPACING);
+
+                 // Active Group Backward Hotkey
+                 ui.label("Active Group Backward Hotkey:");
+                 ui.add_space(ITEM_SPACING / 2.0);
+
+                 ui.horizontal(|ui| {
+                    let binding_text = profile.hotkey_active_backward.as_ref()
+                        .map(binding_label)
+                        .unwrap_or_else(|| "Not set".to_string());
+
+                    let color = if profile.hotkey_active_backward.is_none() {
+                         ui.style().visuals.weak_text_color()
+                    } else {
+                        ui.style().visuals.text_color()
+                    };
+
+                    ui.label(egui::RichText::new(binding_text).strong().color(color));
+
+                    if ui.button("⌨ Bind").clicked() {
+                        state.start_key_capture(CaptureTarget::ActiveBackward, profile.hotkey_backend);
+                    }
+
+                    if profile.hotkey_active_backward.is_some() && ui.small_button("✖").on_hover_text("Clear binding").clicked() {
+                        profile.hotkey_active_backward = None;
+                        changed = true;
+                    }
+                 });
+                 ui.add_space(ITEM_SPACING);
+                 ui.label(egui::RichText::new("Cycle backward in the active cycle group.").weak().small());
+
+                 ui.add_space(ITEM_SPACING);
+                 ui.separator();
+                 ui.add_space(ITEM_SPACING);
+
+                 // Next Cycle Group Hotkey
+                 ui.label("Next Cycle Group Hotkey:");
+                 ui.add_space(ITEM_SPACING / 2.0);
+
+                 ui.horizontal(|ui| {
+                    let binding_text = profile.hotkey_next_group.as_ref()
+                        .map(binding_label)
+                        .unwrap_or_else(|| "Not set".to_string());
+
+                    let color = if profile.hotkey_next_group.is_none() {
+                         ui.style().visuals.weak_text_color()
+                    } else {
+                        ui.style().visuals.text_color()
+                    };
+
+                    ui.label(egui::RichText::new(binding_text).strong().color(color));
+
+                    if ui.button("⌨ Bind").clicked() {
+                        state.start_key_capture(CaptureTarget::NextGroup, profile.hotkey_backend);
+                    }
+
+                    if profile.hotkey_next_group.is_some() && ui.small_button("✖").on_hover_text("Clear binding").clicked() {
+                        profile.hotkey_next_group = None;
+                        changed = true;
+                    }
+                 });
+                 ui.add_space(ITEM_SPACING);
+                 ui.label(egui::RichText::new("Make the next cycle group the active one and name it on screen.").weak().small());
+
+                 ui.add_space(ITEM_SPACING);
+                 ui.separator();
+                 ui.add_space(ITEM_SPACING);
+
+                 // Screenshot Hotkey
+                 ui.label("Screenshot Hotkey:");
+                 ui.add_space(ITEM_SPACING / 2.0);
+
+                 ui.horizontal(|ui| {
+                    let binding_text = profile.hotkey_screenshot.as_ref()
+                        .map(binding_label)
+                        .unwrap_or_else(|| "Not set".to_string());
+
+                    let color = if profile.hotkey_screenshot.is_none() {
+                         ui.style().visuals.weak_text_color()
+                    } else {
+                        ui.style().visuals.text_color()
+                    };
+
+                    ui.label(egui::RichText::new(binding_text).strong().color(color));
+
+                    if ui.button("⌨ Bind").clicked() {
+                        state.start_key_capture(CaptureTarget::Screenshot, profile.hotkey_backend);
+                    }
+
+                    if profile.hotkey_screenshot.is_some() && ui.small_button("✖").on_hover_text("Clear binding").clicked() {
+                        profile.hotkey_screenshot = None;
+                        changed = true;
+                    }
+                 });
+                 ui.add_space(ITEM_SPACING);
+                 ui.label(egui::RichText::new("Save a full-size screenshot of the focused EVE client. Middle-clicking a thumbnail does the same for its client.").weak().small());
+
+                 ui.add_space(ITEM_SPACING);
+                 ui.separator();
+                 ui.add_space(ITEM_SPACING);
+
+                 // Hotkey Overlay Hotkey
+                 ui.label("Hotkey Overlay Hotkey:");
+                 ui.add_space(ITEM_SPACING / 2.0);
+
+                 ui.horizontal(|ui| {
+                    let binding_text = profile.hotkey_toggle_hotkey_overlay.as_ref()
+                        .map(binding_label)
+                        .unwrap_or_else(|| "Not set".to_string());
+
+                    let color = if profile.hotkey_toggle_hotkey_overlay.is_none() {
+                         ui.style().visuals.weak_text_color()
+                    } else {
+                        ui.style().visuals.text_color()
+                    };
+
+                    ui.label(egui::RichText::new(binding_text).strong().color(color));
+
+                    if ui.button("⌨ Bind").clicked() {
+                        state.start_key_capture(CaptureTarget::HotkeyOverlay, profile.hotkey_backend);
+                    }
+
+                    if profile.hotkey_toggle_hotkey_overlay.is_some() && ui.small_button("✖").on_hover_text("Clear binding").clicked() {
+                        profile.hotkey_toggle_hotkey_overlay = None;
+                        changed = true;
+                    }
+                 });
+                 ui.add_space(ITEM_SPACING);
+                 ui.label(egui::RichText::new("Show or hide each character's hotkey in large text on its thumbnail.").weak().small());
+
+                 ui.add_space(ITEM_SPACING);
+                 ui.separator();
+                 ui.add_space(ITEM_SPACING);
+
+                 // Swap Positions Hotkey
+                 ui.label("Swap Positions Hotkey:");
+                 ui.add_space(ITEM_SPACING / 2.0);
+
+                 ui.horizontal(|ui| {
+                    let binding_text = profile.hotkey_swap_positions.as_ref()
+                        .map(binding_label)
+                        .unwrap_or_else(|| "Not set".to_string());
+
+                    let color = if profile.hotkey_swap_positions.is_none() {
+                         ui.style().visuals.weak_text_color()
+                    } else {
+                        ui.style().visuals.text_color()
+                    };
+
+                    ui.label(egui::RichText::new(binding_text).strong().color(color));
+
+                    if ui.button("⌨ Bind").clicked() {
+                        state.start_key_capture(CaptureTarget::SwapPositions, profile.hotkey_backend);
+                    }
+
+                    if profile.hotkey_swap_positions.is_some() && ui.small_button("✖").on_hover_text("Clear binding").clicked() {
+                        profile.hotkey_swap_positions = None;
+                        changed = true;
+                    }
+                 });
+                 ui.add_space(ITEM_SPACING);
+                 ui.label(egui::RichText::new("Swap the focused client's thumbnail with the next thumbnail you left-click, or with the character whose hotkey you press next. Press again to cancel.").weak().small());
+
 
                  if profile.hotkey_backend == HotkeyBackendType::Evdev {
                       ui.add_space(ITEM_SPACING);
@@ -452,12 +884,20 @@ pub fn render_key_capture_modal(
     egui::Window::new("⌨ Capture Key")
         .collapsible(false)
         .resizable(false)
-        .fixed_size([370.0, 280.0])
+        .fixed_size([370.0, 360.0])
         .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
         .show(ui.ctx(), |ui| {
             let target_name = match state.capture_target {
                 Some(CaptureTarget::ToggleSkip) => "Toggle Skip".to_string(),
                 Some(CaptureTarget::TogglePreviews) => "Toggle Previews".to_string(),
+                Some(CaptureTarget::SavePositions) => "Save Positions".to_string(),
+                Some(CaptureTarget::UndoMove) => "Undo Move".to_string(),
+                Some(CaptureTarget::ActiveForward) => "Active Group Forward".to_string(),
+                Some(CaptureTarget::ActiveBackward) => "Active Group Backward".to_string(),
+                Some(CaptureTarget::NextGroup) => "Next Cycle Group".to_string(),
+                Some(CaptureTarget::Screenshot) => "Screenshot".to_string(),
+                Some(CaptureTarget::HotkeyOverlay) => "Hotkey Overlay".to_string(),
+                Some(CaptureTarget::SwapPositions) => "Swap Positions".to_string(),
                 Some(CaptureTarget::Profile) => "Switch to Profile".to_string(),
                 Some(CaptureTarget::Character(ref name)) => format!("Character: {}", name),
                 Some(CaptureTarget::CustomRule(ref alias)) => format!("Custom Source: {}", alias),
@@ -489,22 +929,22 @@ pub fn render_key_capture_modal(
 
             // Reserve space for device list (shown after capture)
             // This prevents the modal from shifting when devices are displayed
-            if let Some(CaptureResult::Captured(binding)) = &state.capture_result {
+            if let Some(CaptureResult::Captured(binding)) = &mut state.capture_result {
                 if !binding.source_devices.is_empty() {
                     ui.spacing_mut().item_spacing.y = 2.0;
-                    ui.label(egui::RichText::new("Detected on:").weak().small());
-                    for device_id in &binding.source_devices {
-                        // Format device ID to be more readable
-                        let friendly_device = device_id
-                            .replace("-event-kbd", " (Keyboard)")
-                            .replace("-event-mouse", " (Mouse)")
-                            .replace("_", " ")
-                            .replace("-", " ");
-                        ui.label(
-                            egui::RichText::new(format!("  • {}", friendly_device))
-                                .weak()
-                                .small(),
-                        );
+                    ui.label(
+                        egui::RichText::new("Detected on (pick one to only trigger from it):")
+                            .weak()
+                            .small(),
+                    );
+                    ui.radio_value(
+                        &mut binding.device,
+                        None,
+                        egui::RichText::new("Any device").small(),
+                    );
+                    for device_id in binding.source_devices.clone() {
+                        let label = egui::RichText::new(friendly_device_name(&device_id)).small();
+                        ui.radio_value(&mut binding.device, Some(device_id), label);
                     }
                     ui.spacing_mut().item_spacing.y = 4.0;
                 } else {
@@ -522,6 +962,49 @@ pub fn render_key_capture_modal(
                 ui.spacing_mut().item_spacing.y = 4.0;
             }
 
+            // Physical key or layout symbol (only known when the layout could be read)
+            if let Some(CaptureResult::Captured(binding)) = &mut state.capture_result
+                && binding.keysym.is_some()
+            {
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new("Follow:").weak().small());
+                    ui.radio_value(
+                        &mut binding.match_by,
+                        KeyMatch::Keycode,
+                        egui::RichText::new("Physical key").small(),
+                    )
+                    .on_hover_text(
+                        "Keeps working on the same key when you switch keyboard layouts",
+                    );
+                    ui.radio_value(
+                        &mut binding.match_by,
+                        KeyMatch::Keysym,
+                        egui::RichText::new("Key symbol").small(),
+                    )
+                    .on_hover_text(
+                        "Moves to whichever key carries this symbol in the active layout",
+                    );
+                });
+            }
+
+            if let Some(CaptureResult::Captured(binding)) = &mut state.capture_result {
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new("Trigger on:").weak().small());
+                    for gesture in Gesture::ALL {
+                        ui.radio_value(
+                            &mut binding.gesture,
+                            gesture,
+                            egui::RichText::new(gesture.label()).small(),
+                        );
+                    }
+                })
+                .response
+                .on_hover_text(
+                    "A key can carry one command per gesture. Once it has a double tap, its \
+                     plain press waits out the double tap window before firing.",
+                );
+            }
+
             ui.add_space(ITEM_SPACING);
 
             // Instructions
@@ -574,6 +1057,38 @@ pub fn render_key_capture_modal(
                                     profile.hotkey_toggle_previews = Some(binding_clone);
                                     changed = true;
                                 }
+                                Some(CaptureTarget::SavePositions) => {
+                                    profile.hotkey_save_positions = Some(binding_clone);
+                                    changed = true;
+                                }
+                                Some(CaptureTarget::UndoMove) => {
+                                    profile.hotkey_undo_move = Some(binding_clone);
+                                    changed = true;
+                                }
+                                Some(CaptureTarget::ActiveForward) => {
+                                    profile.hotkey_active_forward = Some(binding_clone);
+                                    changed = true;
+                                }
+                                Some(CaptureTarget::ActiveBackward) => {
+                                    profile.hotkey_active_backward = Some(binding_clone);
+                                    changed = true;
+                                }
+                                Some(CaptureTarget::NextGroup) => {
+                                    profile.hotkey_next_group = Some(binding_clone);
+                                    changed = true;
+                                }
+                                Some(CaptureTarget::Screenshot) => {
+                                    profile.hotkey_screenshot = Some(binding_clone);
+                                    changed = true;
+                                }
+                                Some(CaptureTarget::HotkeyOverlay) => {
+                                    profile.hotkey_toggle_hotkey_overlay = Some(binding_clone);
+                                    changed = true;
+                                }
+                                Some(CaptureTarget::SwapPositions) => {
+                                    profile.hotkey_swap_positions = Some(binding_clone);
+                                    changed = true;
+                                }
                                 Some(CaptureTarget::Profile) => {
                                     profile.hotkey_profile_switch = Some(binding_clone);
                                     changed = true;
@@ -581,7 +1096,7 @@ pub fn render_key_capture_modal(
                                 Some(CaptureTarget::Character(ref char_name)) => {
                                     // Check for special Cycle Group binding protocol
                                     if char_name.starts_with("GROUP:") {
-                                        // Format: GROUP:<index>:FWD or GROUP:<index>:BWD
+                                        // Format: GROUP:<index>:FWD, GROUP:<index>:BWD or GROUP:<index>:VIS
                                         let parts: Vec<&str> = char_name.split(':').collect();
                                         #[allow(clippy::collapsible_if)]
                                         if parts.len() == 3 {
@@ -599,6 +1114,12 @@ pub fn render_key_capture_modal(
                                                             Some(binding_clone);
                                                         changed = true;
                                                     }
+                                                    "VIS" => {
+                                                        profile.cycle_groups[idx]
+                                                            .hotkey_toggle_visibility =
+                                                            Some(binding_clone);
+                                                        changed = true;
+                                                    }
                                                     _ => {}
                                                 }
                                             }
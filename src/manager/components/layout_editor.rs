@@ -0,0 +1,626 @@
+//! Layout tab: monitors and saved thumbnails drawn to scale
+//!
+//! Thumbnails are dragged with the mouse; every move is written to the profile and sent to
+//! the daemon so the real thumbnail follows live. For a first setup, calibration numbers the
+//! characters and places one thumbnail per click, over a screenshot of the desktop.
+
+use crate::common::constants::manager_ui::*;
+use crate::config::profile::{PositionAnchor, Profile};
+use crate::manager::layout_snippet;
+use crate::manager::x11_utils::{MonitorInfo, get_monitors};
+use eframe::egui;
+
+/// Tallest the layout canvas gets, in points
+const CANVAS_MAX_HEIGHT: f32 = 420.0;
+
+/// Widest the calibration screenshot is kept, in pixels
+const CALIBRATION_CAPTURE_WIDTH: u16 = 1600;
+
+/// A thumbnail moved in the layout editor
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayoutMove {
+    pub name: String,
+    pub is_custom: bool,
+    pub x: i16,
+    pub y: i16,
+    pub width: u16,
+    pub height: u16,
+    /// The drag has ended
+    pub finished: bool,
+}
+
+/// Thumbnail being dragged, with its unrounded position in screen pixels
+struct Drag {
+    name: String,
+    is_custom: bool,
+    x: f32,
+    y: f32,
+    moved: bool,
+}
+
+/// Click-to-place setup: characters are numbered and their thumbnails moved one after
+/// another to where the monitor view is clicked
+struct Calibration {
+    /// Characters in the order they are placed
+    order: Vec<String>,
+    /// Index into `order` of the character the next click places
+    next: usize,
+    /// Screenshot drawn under the thumbnails, with the screen size it covers
+    backdrop: Option<(egui::TextureHandle, egui::Vec2)>,
+}
+
+/// A saved thumbnail as drawn on the canvas
+struct Entry {
+    name: String,
+    is_custom: bool,
+    rect: egui::Rect,
+}
+
+/// State for the layout editor UI
+#[derive(Default)]
+pub struct LayoutEditorState {
+    /// Monitors of the X screen; `None` until the tab is first opened
+    monitors: Option<Vec<MonitorInfo>>,
+    error_msg: Option<String>,
+    drag: Option<Drag>,
+    /// Paste box for a layout snippet is open
+    import_open: bool,
+    import_text: String,
+    /// Result of the last copy or import
+    snippet_status: Option<Result<String, String>>,
+    calibration: Option<Calibration>,
+}
+
+impl LayoutEditorState {
+    fn refresh_monitors(&mut self) {
+        match get_monitors() {
+            Ok(monitors) => {
+                self.monitors = Some(monitors);
+                self.error_msg = None;
+            }
+            Err(e) => {
+                self.monitors = Some(Vec::new());
+                self.error_msg = Some(format!("Failed to query monitors: {}", e));
+            }
+        }
+    }
+}
+
+/// Characters in the order calibration places them: by name, leaving out archived ones
+fn calibration_order(profile: &Profile) -> Vec<String> {
+    let mut order: Vec<String> = profile
+        .character_thumbnails
+        .iter()
+        .filter(|(_, settings)| !settings.archived)
+        .map(|(name, _)| name.clone())
+        .collect();
+    order.sort();
+    order
+}
+
+/// Top-left corner of a thumbnail of `size` centered on `spot`
+fn centered_on(spot: egui::Pos2, size: egui::Vec2) -> (i16, i16) {
+    let min = spot - size / 2.0;
+    let clamp = |v: f32| v.round().clamp(i16::MIN as f32, i16::MAX as f32) as i16;
+    (clamp(min.x), clamp(min.y))
+}
+
+/// Screenshot of the desktop as a texture, with the screen size it covers
+fn capture_backdrop(ctx: &egui::Context) -> anyhow::Result<(egui::TextureHandle, egui::Vec2)> {
+    let capture = crate::daemon::capture_screen(CALIBRATION_CAPTURE_WIDTH)?;
+    let image = egui::ColorImage::from_rgb(
+        [capture.size.width.into(), capture.size.height.into()],
+        &capture.rgb,
+    );
+    let texture = ctx.load_texture("calibration_backdrop", image, Default::default());
+    let screen = egui::vec2(capture.screen.width.into(), capture.screen.height.into());
+    Ok((texture, screen))
+}
+
+/// Smallest rectangle (in screen pixels) holding all monitors and thumbnails
+fn layout_bounds(monitors: &[MonitorInfo], entries: &[Entry]) -> Option<egui::Rect> {
+    monitors
+        .iter()
+        .map(|m| {
+            egui::Rect::from_min_size(
+                egui::pos2(m.x as f32, m.y as f32),
+                egui::vec2(m.width as f32, m.height as f32),
+            )
+        })
+        .chain(entries.iter().map(|e| e.rect))
+        .reduce(|a, b| a.union(b))
+        .filter(|bounds| bounds.width() > 0.0 && bounds.height() > 0.0)
+}
+
+/// Returns the thumbnails moved this frame
+pub fn ui(
+    ui: &mut egui::Ui,
+    profile: &mut Profile,
+    state: &mut LayoutEditorState,
+) -> Vec<LayoutMove> {
+    if state.monitors.is_none() {
+        state.refresh_monitors();
+    }
+
+    let mut moved = Vec::new();
+
+    ui.group(|ui| {
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new("Monitor Layout").strong());
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.button("Refresh Monitors").clicked() {
+                    state.refresh_monitors();
+                }
+                let screen_anchored = profile.thumbnail_position_anchor == PositionAnchor::Screen;
+                if ui
+                    .add_enabled(
+                        screen_anchored && state.calibration.is_none(),
+                        egui::Button::new("🎯 Calibrate"),
+                    )
+                    .on_hover_text(
+                        "Number the characters and click where each thumbnail goes, over a screenshot of the desktop",
+                    )
+                    .on_disabled_hover_text(
+                        "Not available while positions are measured from the client window",
+                    )
+                    .clicked()
+                {
+                    let backdrop = match capture_backdrop(ui.ctx()) {
+                        Ok(backdrop) => Some(backdrop),
+                        Err(e) => {
+                            state.snippet_status = Some(Err(format!(
+                                "No screenshot, calibrating without it: {:#}",
+                                e
+                            )));
+                            None
+                        }
+                    };
+                    state.calibration = Some(Calibration {
+                        order: calibration_order(profile),
+                        next: 0,
+                        backdrop,
+                    });
+                }
+                if ui
+                    .button("📥 Import Layout")
+                    .on_hover_text("Apply a layout snippet shared by someone else")
+                    .clicked()
+                {
+                    state.import_open = !state.import_open;
+                }
+                if ui
+                    .button("📋 Copy Layout")
+                    .on_hover_text("Copy every thumbnail's position and size as a snippet to share")
+                    .clicked()
+                {
+                    ui.ctx().copy_text(layout_snippet::export(profile));
+                    let count =
+                        profile.character_thumbnails.len() + profile.custom_source_thumbnails.len();
+                    state.snippet_status =
+                        Some(Ok(format!("Copied layout of {} thumbnails", count)));
+                }
+            });
+        });
+        ui.label(
+            egui::RichText::new(
+                "Drag thumbnails to move them; running previews follow live. \
+                 Save thumbnail positions to keep the layout.",
+            )
+            .small()
+            .weak(),
+        );
+        if let Some(err) = &state.error_msg {
+            ui.colored_label(COLOR_ERROR, err);
+        }
+        if state.import_open {
+            ui.add_space(ITEM_SPACING);
+            ui.label("Paste a layout snippet; thumbnails are matched by character or source name:");
+            ui.add(
+                egui::TextEdit::multiline(&mut state.import_text)
+                    .desired_rows(3)
+                    .desired_width(f32::INFINITY)
+                    .code_editor(),
+            );
+            ui.horizontal(|ui| {
+                if ui.button("Apply").clicked() {
+                    state.snippet_status =
+                        Some(match layout_snippet::import(profile, &state.import_text) {
+                            Ok(import) => {
+                                let mut message = format!(
+                                    "Moved {} thumbnails ({} already in place)",
+                                    import.moves.len(),
+                                    import.unchanged
+                                );
+                                if !import.unknown.is_empty() {
+                                    message.push_str(&format!(
+                                        "; not in this profile: {}",
+                                        import.unknown.join(", ")
+                                    ));
+                                }
+                                moved.extend(import.moves);
+                                state.import_open = false;
+                                state.import_text.clear();
+                                Ok(message)
+                            }
+                            Err(e) => Err(format!("{:#}", e)),
+                        });
+                }
+                if ui.button("Cancel").clicked() {
+                    state.import_open = false;
+                }
+            });
+        }
+        match &state.snippet_status {
+            Some(Ok(message)) => {
+                ui.colored_label(COLOR_SUCCESS, message);
+            }
+            Some(Err(err)) => {
+                ui.colored_label(COLOR_ERROR, err);
+            }
+            None => {}
+        }
+        if let Some(calibration) = &mut state.calibration {
+            ui.add_space(ITEM_SPACING);
+            let mut finished = false;
+            ui.horizontal(|ui| {
+                match calibration.order.get(calibration.next) {
+                    Some(name) => {
+                        ui.label(
+                            egui::RichText::new(format!(
+                                "Click where thumbnail #{} ({}) goes",
+                                calibration.next + 1,
+                                name
+                            ))
+                            .strong(),
+                        );
+                    }
+                    None => {
+                        ui.label("No characters to place.");
+                    }
+                }
+                if ui
+                    .add_enabled(calibration.next > 0, egui::Button::new("◀ Back"))
+                    .clicked()
+                {
+                    calibration.next -= 1;
+                }
+                if ui.button("Skip ▶").clicked() {
+                    calibration.next += 1;
+                }
+                if ui.button("Done").clicked() {
+                    finished = true;
+                }
+            });
+            if finished || calibration.next >= calibration.order.len() {
+                let placed = calibration.next.min(calibration.order.len());
+                state.snippet_status = Some(Ok(format!(
+                    "Calibration done, {} of {} characters visited",
+                    placed,
+                    calibration.order.len()
+                )));
+                state.calibration = None;
+            }
+        }
+        ui.add_space(ITEM_SPACING);
+
+        let monitors = state.monitors.clone().unwrap_or_default();
+        let mut entries: Vec<Entry> = [
+            (false, &profile.character_thumbnails),
+            (true, &profile.custom_source_thumbnails),
+        ]
+        .into_iter()
+        .flat_map(|(is_custom, thumbnails)| {
+            thumbnails
+                .iter()
+                .filter(|(_, settings)| settings.is_placed())
+                .map(move |(name, settings)| Entry {
+                name: name.clone(),
+                is_custom,
+                rect: egui::Rect::from_min_size(
+                    egui::pos2(settings.x as f32, settings.y as f32),
+                    egui::vec2(
+                        settings.dimensions.width as f32,
+                        settings.dimensions.height as f32,
+                    ),
+                ),
+            })
+        })
+        .collect();
+        // Stable paint order, with the dragged thumbnail on top
+        entries.sort_by(|a, b| a.name.cmp(&b.name).then(a.is_custom.cmp(&b.is_custom)));
+        if let Some(drag) = &state.drag
+            && let Some(idx) = entries
+                .iter()
+                .position(|e| e.name == drag.name && e.is_custom == drag.is_custom)
+        {
+            let dragged = entries.remove(idx);
+            entries.push(dragged);
+        }
+
+        let Some(bounds) = layout_bounds(&monitors, &entries) else {
+            ui.label("No monitors detected.");
+            return;
+        };
+
+        let available_width = ui.available_width();
+        let scale = (available_width / bounds.width()).min(CANVAS_MAX_HEIGHT / bounds.height());
+        let calibrating = state.calibration.is_some();
+        let sense = if calibrating {
+            egui::Sense::click()
+        } else {
+            egui::Sense::hover()
+        };
+        let (canvas, painter) = ui.allocate_painter(bounds.size() * scale, sense);
+        let to_canvas = |rect: egui::Rect| {
+            egui::Rect::from_min_size(
+                canvas.rect.min + (rect.min - bounds.min) * scale,
+                rect.size() * scale,
+            )
+        };
+
+        let visuals = ui.visuals().clone();
+        let backdrop = state
+            .calibration
+            .as_ref()
+            .and_then(|calibration| calibration.backdrop.as_ref());
+        if let Some((texture, screen)) = backdrop {
+            painter.image(
+                texture.id(),
+                to_canvas(egui::Rect::from_min_size(egui::Pos2::ZERO, *screen)),
+                egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
+                egui::Color32::WHITE,
+            );
+        }
+        for monitor in &monitors {
+            let rect = to_canvas(egui::Rect::from_min_size(
+                egui::pos2(monitor.x as f32, monitor.y as f32),
+                egui::vec2(monitor.width as f32, monitor.height as f32),
+            ));
+            if backdrop.is_none() {
+                painter.rect_filled(rect, 2.0, visuals.extreme_bg_color);
+            }
+            painter.rect_stroke(
+                rect,
+                2.0,
+                visuals.widgets.noninteractive.fg_stroke,
+                egui::StrokeKind::Inside,
+            );
+            let label = if monitor.primary {
+                format!(
+                    "{} (primary) {}x{}",
+                    monitor.name, monitor.width, monitor.height
+                )
+            } else {
+                format!("{} {}x{}", monitor.name, monitor.width, monitor.height)
+            };
+            painter.text(
+                rect.left_bottom() + egui::vec2(4.0, -4.0),
+                egui::Align2::LEFT_BOTTOM,
+                label,
+                egui::FontId::proportional(11.0),
+                visuals.weak_text_color(),
+            );
+        }
+
+        for entry in &entries {
+            let rect = to_canvas(entry.rect);
+            let id = ui
+                .id()
+                .with(("layout_thumbnail", entry.is_custom, &entry.name));
+            // Calibration places thumbnails by clicking the canvas instead
+            let sense = if calibrating {
+                egui::Sense::hover()
+            } else {
+                egui::Sense::drag()
+            };
+            let response = ui.interact(rect, id, sense);
+
+            if response.drag_started() {
+                state.drag = Some(Drag {
+                    name: entry.name.clone(),
+                    is_custom: entry.is_custom,
+                    x: entry.rect.min.x,
+                    y: entry.rect.min.y,
+                    moved: false,
+                });
+            }
+
+            let is_dragged = state
+                .drag
+                .as_ref()
+                .is_some_and(|d| d.name == entry.name && d.is_custom == entry.is_custom);
+
+            if is_dragged
+                && response.dragged()
+                && let Some(drag) = state.drag.as_mut()
+            {
+                let delta = response.drag_delta() / scale;
+                drag.x += delta.x;
+                drag.y += delta.y;
+                let x = drag.x.round().clamp(i16::MIN as f32, i16::MAX as f32) as i16;
+                let y = drag.y.round().clamp(i16::MIN as f32, i16::MAX as f32) as i16;
+                let (width, height) = (entry.rect.width() as u16, entry.rect.height() as u16);
+                if profile.update_thumbnail_position(
+                    &entry.name,
+                    x,
+                    y,
+                    width,
+                    height,
+                    entry.is_custom,
+                ) {
+                    drag.moved = true;
+                    moved.push(LayoutMove {
+                        name: entry.name.clone(),
+                        is_custom: entry.is_custom,
+                        x,
+                        y,
+                        width,
+                        height,
+                        finished: false,
+                    });
+                }
+            }
+
+            if is_dragged
+                && response.drag_stopped()
+                && let Some(drag) = state.drag.take()
+                && drag.moved
+            {
+                let x = drag.x.round().clamp(i16::MIN as f32, i16::MAX as f32) as i16;
+                let y = drag.y.round().clamp(i16::MIN as f32, i16::MAX as f32) as i16;
+                moved.push(LayoutMove {
+                    name: drag.name,
+                    is_custom: drag.is_custom,
+                    x,
+                    y,
+                    width: entry.rect.width() as u16,
+                    height: entry.rect.height() as u16,
+                    finished: true,
+                });
+            }
+
+            let fill = if entry.is_custom {
+                COLOR_WARNING.gamma_multiply(0.5)
+            } else {
+                visuals.selection.bg_fill.gamma_multiply(0.7)
+            };
+            let stroke = if is_dragged || response.hovered() {
+                visuals.widgets.active.fg_stroke
+            } else {
+                visuals.widgets.inactive.fg_stroke
+            };
+            painter.rect_filled(rect, 1.0, fill);
+            painter.rect_stroke(rect, 1.0, stroke, egui::StrokeKind::Inside);
+            painter.with_clip_rect(rect.intersect(canvas.rect)).text(
+                rect.left_top() + egui::vec2(3.0, 2.0),
+                egui::Align2::LEFT_TOP,
+                &entry.name,
+                egui::FontId::proportional(10.0),
+                visuals.strong_text_color(),
+            );
+            if let Some(calibration) = &state.calibration
+                && !entry.is_custom
+                && let Some(number) = calibration.order.iter().position(|n| n == &entry.name)
+            {
+                let color = if number == calibration.next {
+                    COLOR_SUCCESS
+                } else {
+                    visuals.strong_text_color()
+                };
+                painter.with_clip_rect(rect.intersect(canvas.rect)).text(
+                    rect.center(),
+                    egui::Align2::CENTER_CENTER,
+                    format!("#{}", number + 1),
+                    egui::FontId::proportional(16.0),
+                    color,
+                );
+            }
+
+            if response.hovered() || is_dragged {
+                ui.ctx().set_cursor_icon(if is_dragged {
+                    egui::CursorIcon::Grabbing
+                } else {
+                    egui::CursorIcon::Grab
+                });
+            }
+            if !is_dragged {
+                let kind = if entry.is_custom {
+                    "Custom source"
+                } else {
+                    "Character"
+                };
+                response.on_hover_text(format!(
+                    "{}: {}\nPosition: {}, {}",
+                    kind, entry.name, entry.rect.min.x, entry.rect.min.y
+                ));
+            }
+        }
+
+        if let Some(calibration) = &mut state.calibration
+            && canvas.clicked()
+            && let Some(pointer) = canvas.interact_pointer_pos()
+            && let Some(name) = calibration.order.get(calibration.next)
+            && let Some(settings) = profile.character_thumbnails.get(name)
+        {
+            let spot = bounds.min + (pointer - canvas.rect.min) / scale;
+            let (width, height) = (settings.dimensions.width, settings.dimensions.height);
+            let (x, y) = centered_on(spot, egui::vec2(width.into(), height.into()));
+            if profile.update_thumbnail_position(name, x, y, width, height, false) {
+                moved.push(LayoutMove {
+                    name: name.clone(),
+                    is_custom: false,
+                    x,
+                    y,
+                    width,
+                    height,
+                    finished: true,
+                });
+            }
+            calibration.next += 1;
+        }
+        if calibrating {
+            ui.ctx().set_cursor_icon(egui::CursorIcon::Crosshair);
+        }
+
+        ui.add_space(ITEM_SPACING);
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new("■").color(visuals.selection.bg_fill.gamma_multiply(0.7)));
+            ui.label(egui::RichText::new("Characters").small());
+            ui.add_space(ITEM_SPACING);
+            ui.label(egui::RichText::new("■").color(COLOR_WARNING.gamma_multiply(0.5)));
+            ui.label(egui::RichText::new("Custom sources").small());
+        });
+    });
+
+    moved
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitor(x: i16, y: i16, width: u16, height: u16) -> MonitorInfo {
+        MonitorInfo {
+            name: "DP-1".to_string(),
+            x,
+            y,
+            width,
+            height,
+            primary: false,
+        }
+    }
+
+    #[test]
+    fn test_layout_bounds_cover_monitors_and_offscreen_thumbnails() {
+        let monitors = [monitor(0, 0, 1920, 1080), monitor(1920, 0, 2560, 1440)];
+        let entries = [Entry {
+            name: "Alice".to_string(),
+            is_custom: false,
+            rect: egui::Rect::from_min_size(egui::pos2(-100.0, 50.0), egui::vec2(250.0, 140.0)),
+        }];
+
+        let bounds = layout_bounds(&monitors, &entries).unwrap();
+        assert_eq!(bounds.min, egui::pos2(-100.0, 0.0));
+        assert_eq!(bounds.max, egui::pos2(4480.0, 1440.0));
+
+        assert_eq!(layout_bounds(&[], &[]), None);
+    }
+
+    #[test]
+    fn test_calibration_places_by_name_centered_on_click() {
+        let mut profile = Profile::default_with_name("Test".to_string(), String::new());
+        for (name, archived) in [("Charlie", false), ("Alice", false), ("Bob", true)] {
+            let mut settings = crate::common::types::CharacterSettings::new(0, 0, 480, 270);
+            settings.archived = archived;
+            profile
+                .character_thumbnails
+                .insert(name.to_string(), settings);
+        }
+
+        assert_eq!(calibration_order(&profile), vec!["Alice", "Charlie"]);
+        assert_eq!(
+            centered_on(egui::pos2(2000.0, 135.0), egui::vec2(480.0, 270.0)),
+            (1760, 0)
+        );
+    }
+}
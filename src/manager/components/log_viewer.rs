@@ -0,0 +1,117 @@
+//! Daemon log viewer tab
+
+use crate::common::constants::manager_ui::*;
+use crate::manager::state::daemon_log::{DaemonLog, LogLevel};
+use eframe::egui;
+
+/// State for the log viewer UI
+pub struct LogViewerState {
+    min_level: LogLevel,
+    filter_text: String,
+    auto_scroll: bool,
+    /// Focus the filter field on the next frame (Ctrl+F)
+    focus_filter: bool,
+}
+
+impl Default for LogViewerState {
+    fn default() -> Self {
+        Self {
+            min_level: LogLevel::Info,
+            filter_text: String::new(),
+            auto_scroll: true,
+            focus_filter: false,
+        }
+    }
+}
+
+impl LogViewerState {
+    /// Focus the filter field on the next frame
+    pub fn focus_filter(&mut self) {
+        self.focus_filter = true;
+    }
+}
+
+pub fn ui(ui: &mut egui::Ui, log: &DaemonLog, external_daemon: bool, state: &mut LogViewerState) {
+    ui.group(|ui| {
+        ui.label(egui::RichText::new("Daemon Log").strong());
+        ui.add_space(ITEM_SPACING);
+
+        ui.horizontal(|ui| {
+            ui.label("Level:");
+            egui::ComboBox::from_id_salt("log_level_filter")
+                .selected_text(state.min_level.label())
+                .show_ui(ui, |ui| {
+                    for level in LogLevel::ALL {
+                        ui.selectable_value(&mut state.min_level, level, level.label());
+                    }
+                });
+
+            ui.add_space(ITEM_SPACING);
+            ui.label("Filter:");
+            let filter = ui.add(
+                egui::TextEdit::singleline(&mut state.filter_text)
+                    .hint_text("Text to match")
+                    .desired_width(180.0),
+            );
+            if std::mem::take(&mut state.focus_filter) {
+                filter.request_focus();
+            }
+
+            ui.add_space(ITEM_SPACING);
+            ui.checkbox(&mut state.auto_scroll, "Follow");
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.button("Clear").clicked() {
+                    log.clear();
+                }
+                if ui.button("Copy").clicked() {
+                    ui.ctx().copy_text(log.snapshot().join("\n"));
+                }
+            });
+        });
+
+        ui.label(
+            egui::RichText::new(if external_daemon {
+                "Attached to a standalone daemon: only messages it sends to the Manager are shown. Use journalctl for its full output."
+            } else {
+                "Output of the preview daemon started by this Manager. Lines without a level are always shown."
+            })
+            .small()
+            .weak(),
+        );
+
+        ui.add_space(ITEM_SPACING);
+
+        let filter = state.filter_text.to_lowercase();
+        let lines = log.snapshot();
+        let visible: Vec<(Option<LogLevel>, &String)> = lines
+            .iter()
+            .map(|line| (LogLevel::parse_line(line), line))
+            .filter(|(level, line)| {
+                level.is_none_or(|level| level >= state.min_level)
+                    && (filter.is_empty() || line.to_lowercase().contains(&filter))
+            })
+            .collect();
+
+        egui::ScrollArea::both()
+            .id_salt("daemon_log_scroll")
+            .max_height(ui.available_height().max(300.0))
+            .auto_shrink([false, false])
+            .stick_to_bottom(state.auto_scroll)
+            .show(ui, |ui| {
+                if visible.is_empty() {
+                    ui.label(egui::RichText::new("No log lines to show").weak());
+                }
+                for (level, line) in visible {
+                    let text = egui::RichText::new(line.as_str()).monospace().small();
+                    let text = match level {
+                        Some(LogLevel::Error) => text.color(COLOR_ERROR),
+                        Some(LogLevel::Warn) => text.color(COLOR_WARNING),
+                        Some(LogLevel::Debug | LogLevel::Trace) => text.weak(),
+                        _ => text,
+                    };
+                    ui.label(text);
+                }
+            });
+    });
+}
@@ -1,8 +1,16 @@
 pub mod behavior_settings;
 pub mod characters;
+pub mod crash_dialog;
+pub mod crash_report_dialog;
+pub mod diagnostics;
 pub mod header;
 pub mod hotkey_settings;
+pub mod layout_editor;
+pub mod log_viewer;
 pub mod profile_selector;
+pub mod save_preview;
 pub mod sources;
+pub mod stats;
+pub mod toasts;
 pub mod tray;
 pub mod visual_settings;
@@ -13,6 +13,8 @@ pub struct ProfileSelector {
     /// Index of the profile we are performing an action on (Edit/Duplicate/Delete)
     /// This might be different from selected_idx (active profile) if user is editing a non-active profile
     action_target_idx: Option<usize>,
+    /// Validation error shown in the open New/Duplicate/Edit dialog
+    dialog_error: Option<String>,
 }
 
 impl ProfileSelector {
@@ -26,6 +28,7 @@ impl ProfileSelector {
             show_edit_dialog: false,
             pending_profile_idx: None,
             action_target_idx: None,
+            dialog_error: None,
         }
     }
 
@@ -90,15 +93,23 @@ impl ProfileSelector {
         action
     }
 
-    /// Render the profile management buttons (New, Duplicate, Edit, Delete)
-    pub fn render_buttons(&mut self, ui: &mut egui::Ui, config: &Config, selected_idx: usize) {
+    /// Render the profile management buttons (New, Duplicate, Edit, Delete, Move)
+    pub fn render_buttons(
+        &mut self,
+        ui: &mut egui::Ui,
+        config: &mut Config,
+        selected_idx: &mut usize,
+    ) -> ProfileAction {
+        let mut action = ProfileAction::None;
+
         // Determine which profile is visually selected in the dropdown
         // If pending_profile_idx is None, it means the dropdown shows the active profile (selected_idx)
-        let target_idx = self.pending_profile_idx.unwrap_or(selected_idx);
+        let target_idx = self.pending_profile_idx.unwrap_or(*selected_idx);
 
         ui.horizontal(|ui| {
             if ui.button("➕ New").clicked() {
                 self.show_new_dialog = true;
+                self.dialog_error = None;
                 self.edit_profile_name.clear();
                 self.edit_profile_desc.clear();
                 // New profile doesn't target an existing index
@@ -113,8 +124,9 @@ impl ProfileSelector {
                 .clicked()
             {
                 self.show_duplicate_dialog = true;
+                self.dialog_error = None;
                 let current = &config.profiles[target_idx];
-                self.edit_profile_name = format!("{} (copy)", current.profile_name);
+                self.edit_profile_name = config.unique_copy_name(&current.profile_name);
                 self.edit_profile_desc = current.profile_description.clone();
                 self.action_target_idx = Some(target_idx);
             }
@@ -124,6 +136,7 @@ impl ProfileSelector {
                 .clicked()
             {
                 self.show_edit_dialog = true;
+                self.dialog_error = None;
                 let current = &config.profiles[target_idx];
                 self.edit_profile_name = current.profile_name.clone();
                 self.edit_profile_desc = current.profile_description.clone();
@@ -139,10 +152,41 @@ impl ProfileSelector {
                 self.action_target_idx = Some(target_idx);
             }
 
+            // Reorder the profile shown in the dropdown
+            let mut move_to = None;
+            if ui
+                .add_enabled(target_idx > 0, egui::Button::new("⬆"))
+                .on_hover_text("Move profile up")
+                .clicked()
+            {
+                move_to = Some(target_idx - 1);
+            }
+            if ui
+                .add_enabled(
+                    target_idx + 1 < config.profiles.len(),
+                    egui::Button::new("⬇"),
+                )
+                .on_hover_text("Move profile down")
+                .clicked()
+            {
+                move_to = Some(target_idx + 1);
+            }
+            if let Some(to) = move_to
+                && config.move_profile(target_idx, to)
+            {
+                *selected_idx = config.active_profile_idx();
+                if self.pending_profile_idx.is_some() {
+                    self.pending_profile_idx = Some(to);
+                }
+                action = ProfileAction::ProfileUpdated;
+            }
+
             if config.profiles.len() == 1 {
                 ui.label("(Cannot delete last profile)");
             }
         });
+
+        action
     }
 
     /// Render just the modal dialogs (called separately from context level)
@@ -167,7 +211,7 @@ impl ProfileSelector {
 
         if self.show_edit_dialog {
             let target_idx = self.action_target_idx.unwrap_or(*selected_idx);
-            action = self.edit_profile_dialog(ctx, config, selected_idx, target_idx);
+            action = self.edit_profile_dialog(ctx, config, target_idx);
         }
 
         if self.show_delete_confirm {
@@ -203,18 +247,24 @@ impl ProfileSelector {
                 ui.label("Description (optional):");
                 ui.text_edit_singleline(&mut self.edit_profile_desc);
 
+                self.render_dialog_error(ui);
                 ui.add_space(ITEM_SPACING);
 
                 ui.horizontal(|ui| {
-                    if ui.button("Create").clicked() && !self.edit_profile_name.is_empty() {
-                        // Create new profile from default template
-                        let new_profile = Profile::default_with_name(
-                            self.edit_profile_name.clone(),
-                            self.edit_profile_desc.clone(),
-                        );
-                        config.profiles.push(new_profile);
-                        action = ProfileAction::ProfileCreated;
-                        self.show_new_dialog = false;
+                    if ui.button("Create").clicked() {
+                        match config.validate_profile_name(&self.edit_profile_name, None) {
+                            Ok(()) => {
+                                // Create new profile from default template
+                                let new_profile = Profile::default_with_name(
+                                    self.edit_profile_name.trim().to_string(),
+                                    self.edit_profile_desc.clone(),
+                                );
+                                config.profiles.push(new_profile);
+                                action = ProfileAction::ProfileCreated;
+                                self.show_new_dialog = false;
+                            }
+                            Err(e) => self.dialog_error = Some(e.to_string()),
+                        }
                     }
 
                     if ui.button("Cancel").clicked() {
@@ -244,17 +294,22 @@ impl ProfileSelector {
                 ui.label("Description (optional):");
                 ui.text_edit_singleline(&mut self.edit_profile_desc);
 
+                self.render_dialog_error(ui);
                 ui.add_space(ITEM_SPACING);
 
                 ui.horizontal(|ui| {
-                    if ui.button("Duplicate").clicked() && !self.edit_profile_name.is_empty() {
-                        let mut new_profile = config.profiles[source_idx].clone();
-                        new_profile.profile_name = self.edit_profile_name.clone();
-                        new_profile.profile_description = self.edit_profile_desc.clone();
-                        config.profiles.push(new_profile);
-
-                        action = ProfileAction::ProfileCreated;
-                        self.show_duplicate_dialog = false;
+                    if ui.button("Duplicate").clicked() {
+                        match config.duplicate_profile(
+                            source_idx,
+                            &self.edit_profile_name,
+                            &self.edit_profile_desc,
+                        ) {
+                            Ok(_) => {
+                                action = ProfileAction::ProfileCreated;
+                                self.show_duplicate_dialog = false;
+                            }
+                            Err(e) => self.dialog_error = Some(e.to_string()),
+                        }
                     }
 
                     if ui.button("Cancel").clicked() {
@@ -270,7 +325,6 @@ impl ProfileSelector {
         &mut self,
         ctx: &egui::Context,
         config: &mut Config,
-        active_idx: &mut usize,
         target_idx: usize,
     ) -> ProfileAction {
         let mut action = ProfileAction::None;
@@ -285,21 +339,23 @@ impl ProfileSelector {
                 ui.label("Description (optional):");
                 ui.text_edit_singleline(&mut self.edit_profile_desc);
 
+                self.render_dialog_error(ui);
                 ui.add_space(ITEM_SPACING);
 
                 ui.horizontal(|ui| {
-                    if ui.button("Save").clicked() && !self.edit_profile_name.is_empty() {
-                        let profile = &mut config.profiles[target_idx];
-                        profile.profile_name = self.edit_profile_name.clone();
-                        profile.profile_description = self.edit_profile_desc.clone();
-
-                        // Only update global selection if we modified the active profile
-                        if target_idx == *active_idx {
-                            config.global.selected_profile = profile.profile_name.clone();
+                    if ui.button("Save").clicked() {
+                        // Also points the global selection at the new name if the profile is active
+                        match config.rename_profile(
+                            target_idx,
+                            &self.edit_profile_name,
+                            &self.edit_profile_desc,
+                        ) {
+                            Ok(()) => {
+                                action = ProfileAction::ProfileUpdated;
+                                self.show_edit_dialog = false;
+                            }
+                            Err(e) => self.dialog_error = Some(e.to_string()),
                         }
-
-                        action = ProfileAction::ProfileUpdated;
-                        self.show_edit_dialog = false;
                     }
 
                     if ui.button("Cancel").clicked() {
@@ -311,6 +367,12 @@ impl ProfileSelector {
         action
     }
 
+    fn render_dialog_error(&self, ui: &mut egui::Ui) {
+        if let Some(err) = &self.dialog_error {
+            ui.colored_label(COLOR_ERROR, err);
+        }
+    }
+
     fn delete_confirm_dialog(
         &mut self,
         ctx: &egui::Context,
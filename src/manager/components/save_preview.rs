@@ -0,0 +1,150 @@
+//! Review dialog listing what "Save & Apply" is about to write
+//!
+//! Each change against the config on disk can be unchecked; unchecked changes are reverted
+//! instead of saved, so e.g. thumbnail positions written by the daemon aren't clobbered by a
+//! stale in-memory copy.
+
+use anyhow::Result;
+use eframe::egui;
+use tracing::warn;
+
+use crate::common::constants::manager_ui::*;
+use crate::config::diff::{self, ConfigChange};
+use crate::config::profile::Config;
+
+/// Tallest the change list gets before scrolling, in points
+const LIST_MAX_HEIGHT: f32 = 320.0;
+
+struct Preview {
+    saved: Config,
+    changes: Vec<(ConfigChange, bool)>,
+}
+
+/// State for the save preview dialog
+#[derive(Default)]
+pub struct SavePreviewState {
+    preview: Option<Preview>,
+}
+
+impl SavePreviewState {
+    pub fn is_open(&self) -> bool {
+        self.preview.is_some()
+    }
+
+    /// Open the dialog for the changes in `current`.
+    /// Returns false if there is nothing to review, in which case the caller saves directly.
+    pub fn open(&mut self, current: &Config) -> bool {
+        let saved = match Config::load() {
+            Ok(saved) => saved,
+            Err(e) => {
+                warn!(error = ?e, "Failed to load saved config for preview");
+                return false;
+            }
+        };
+        match diff::diff(&saved, current) {
+            Ok(changes) if !changes.is_empty() => {
+                self.preview = Some(Preview {
+                    saved,
+                    changes: changes.into_iter().map(|c| (c, true)).collect(),
+                });
+                true
+            }
+            Ok(_) => false,
+            Err(e) => {
+                warn!(error = ?e, "Failed to compare config for preview");
+                false
+            }
+        }
+    }
+}
+
+/// Renders the dialog. Once the user confirms, returns `current` with the unchecked changes
+/// reverted to their saved values.
+pub fn render(
+    ctx: &egui::Context,
+    state: &mut SavePreviewState,
+    current: &Config,
+) -> Option<Result<Config>> {
+    let preview = state.preview.as_mut()?;
+
+    let mut confirmed = false;
+    let mut cancelled = false;
+
+    egui::Window::new("Review Changes")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.label("These changes will be written to the configuration file.");
+            ui.label(
+                egui::RichText::new("Unchecked changes are discarded.")
+                    .small()
+                    .weak(),
+            );
+            ui.add_space(ITEM_SPACING);
+
+            egui::ScrollArea::vertical()
+                .max_height(LIST_MAX_HEIGHT)
+                .show(ui, |ui| {
+                    let mut group: Option<Option<String>> = None;
+                    for (change, selected) in &mut preview.changes {
+                        let scope = change.profile().map(str::to_string);
+                        if group.as_ref() != Some(&scope) {
+                            ui.add_space(4.0);
+                            let heading = match &scope {
+                                Some(profile) => format!("Profile: {}", profile),
+                                None => "Global".to_string(),
+                            };
+                            ui.label(egui::RichText::new(heading).strong());
+                            group = Some(scope);
+                        }
+                        ui.checkbox(selected, &change.description);
+                    }
+                });
+
+            ui.add_space(ITEM_SPACING);
+
+            let selected = preview.changes.iter().filter(|(_, s)| *s).count();
+            ui.horizontal(|ui| {
+                if ui.button("Select All").clicked() {
+                    preview.changes.iter_mut().for_each(|(_, s)| *s = true);
+                }
+                if ui.button("Select None").clicked() {
+                    preview.changes.iter_mut().for_each(|(_, s)| *s = false);
+                }
+                ui.add_space(ITEM_SPACING);
+                if ui
+                    .add_enabled(
+                        selected > 0,
+                        egui::Button::new(format!(
+                            "💾 Save {} of {}",
+                            selected,
+                            preview.changes.len()
+                        )),
+                    )
+                    .clicked()
+                {
+                    confirmed = true;
+                }
+                if ui.button("Cancel").clicked() {
+                    cancelled = true;
+                }
+            });
+        });
+
+    if cancelled {
+        state.preview = None;
+        return None;
+    }
+    if !confirmed {
+        return None;
+    }
+
+    let preview = state.preview.take()?;
+    let selected: Vec<ConfigChange> = preview
+        .changes
+        .into_iter()
+        .filter_map(|(change, selected)| selected.then_some(change))
+        .collect();
+    Some(diff::apply(&preview.saved, current, &selected))
+}
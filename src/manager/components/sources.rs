@@ -1,7 +1,21 @@
+use crate::common::constants::manager_ui::{COLOR_SUCCESS, COLOR_WARNING};
+use crate::common::pattern;
 use crate::config::profile::CustomWindowRule;
 use crate::manager::x11_utils::{WindowInfo, get_running_applications};
 use egui::{ScrollArea, Ui};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+
+/// Explains the two ways patterns match
+const REGEX_HINT: &str = "If checked, patterns are regular expressions (e.g. ^discord$).\n\
+    Otherwise a pattern matches wherever its text appears in the title, class or process name.";
+
+/// Matching windows listed in a rule's preview
+const MATCH_PREVIEW_LIMIT: usize = 5;
+
+/// Explains the single instance option and the labels extra windows get otherwise
+const MULTI_INSTANCE_HINT: &str = "If checked, only the first matching window will be previewed.\n\
+    Otherwise every matching window gets its own thumbnail, position and cycle entry, \
+    labelled \"Alias #2\", \"Alias #3\" and so on.";
 
 pub struct SourcesTab {
     // Component state
@@ -11,6 +25,10 @@ pub struct SourcesTab {
     error_msg: Option<String>,
     // Track expanded rows for editing: index -> expanded
     expanded_rows: HashSet<usize>,
+    /// Display names being typed, by rule index; applied when the field loses focus
+    alias_edits: HashMap<usize, String>,
+    /// Why the last display name edit was rejected, by rule index
+    alias_error: Option<(usize, String)>,
 }
 
 impl Default for SourcesTab {
@@ -19,6 +37,11 @@ impl Default for SourcesTab {
             new_rule: CustomWindowRule {
                 title_pattern: None,
                 class_pattern: None,
+                process_pattern: None,
+                title_exclude_pattern: None,
+                class_exclude_pattern: None,
+                regex: true,
+                compiled: Default::default(),
                 alias: String::new(),
                 default_width: crate::common::constants::defaults::thumbnail::WIDTH,
                 default_height: crate::common::constants::defaults::thumbnail::HEIGHT,
@@ -38,11 +61,30 @@ impl Default for SourcesTab {
             selected_app_idx: None,
             error_msg: None,
             expanded_rows: HashSet::new(),
+            alias_edits: HashMap::new(),
+            alias_error: None,
         }
     }
 }
 
 impl SourcesTab {
+    fn refresh_running_apps(&mut self) {
+        match get_running_applications() {
+            Ok(mut apps) => {
+                // Filter out EVE clients to prevent duplication/confusion
+                apps.retain(|app| !app.title.starts_with("EVE - ") && app.title != "EVE");
+
+                // Dedup logic based on class+title
+                apps.dedup_by(|a, b| a.class == b.class && a.title == b.title);
+                self.running_apps = Some(apps);
+                self.error_msg = None;
+            }
+            Err(e) => {
+                self.error_msg = Some(format!("Failed to list apps: {}", e));
+            }
+        }
+    }
+
     pub fn ui(
         &mut self,
         ui: &mut Ui,
@@ -76,6 +118,8 @@ impl SourcesTab {
                 }
 
                 let mut remove_idx = None;
+                let mut alias_committed = None;
+                let mut refresh_apps = false;
 
                 for (idx, rule) in profile.custom_windows.iter_mut().enumerate() {
                     let is_expanded = self.expanded_rows.contains(&idx);
@@ -101,6 +145,15 @@ impl SourcesTab {
                             if let Some(t) = &rule.title_pattern {
                                 details.push(format!("Title: {}", t));
                             }
+                            if let Some(p) = &rule.process_pattern {
+                                details.push(format!("Process: {}", p));
+                            }
+                            if let Some(c) = &rule.class_exclude_pattern {
+                                details.push(format!("Not class: {}", c));
+                            }
+                            if let Some(t) = &rule.title_exclude_pattern {
+                                details.push(format!("Not title: {}", t));
+                            }
                             ui.label(egui::RichText::new(details.join(", ")).weak());
                         }
 
@@ -125,37 +178,62 @@ impl SourcesTab {
                                 .show(ui, |ui| {
                                     // Alias
                                     ui.label("Display Name:");
-                                    if ui.text_edit_singleline(&mut rule.alias).changed() {
-                                        changed = true;
-                                    }
+                                    // Renamed once the edit is done, so positions aren't
+                                    // moved to every half-typed name
+                                    let mut alias = self
+                                        .alias_edits
+                                        .get(&idx)
+                                        .cloned()
+                                        .unwrap_or_else(|| rule.alias.clone());
+                                    ui.vertical(|ui| {
+                                        let response = ui.text_edit_singleline(&mut alias);
+                                        if response.lost_focus() {
+                                            self.alias_edits.remove(&idx);
+                                            let cancelled =
+                                                ui.input(|i| i.key_pressed(egui::Key::Escape));
+                                            if !cancelled && alias != rule.alias {
+                                                alias_committed = Some((idx, alias));
+                                            }
+                                        } else if response.has_focus() {
+                                            self.alias_edits.insert(idx, alias);
+                                        }
+                                        if let Some((_, error)) =
+                                            self.alias_error.as_ref().filter(|(i, _)| *i == idx)
+                                        {
+                                            ui.colored_label(COLOR_WARNING, error);
+                                        }
+                                    });
+                                    ui.end_row();
+
+                                    // Include / Exclude Patterns
+                                    ui.label("Patterns:");
+                                    changed |= ui
+                                        .checkbox(&mut rule.regex, "Regular expressions")
+                                        .on_hover_text(REGEX_HINT)
+                                        .changed();
                                     ui.end_row();
+                                    let regex = rule.regex;
 
-                                    // Class Pattern
                                     ui.label("Class Pattern:");
-                                    let mut class_text =
-                                        rule.class_pattern.clone().unwrap_or_default();
-                                    if ui.text_edit_singleline(&mut class_text).changed() {
-                                        rule.class_pattern = if class_text.is_empty() {
-                                            None
-                                        } else {
-                                            Some(class_text)
-                                        };
-                                        changed = true;
-                                    }
+                                    changed |= pattern_edit(ui, &mut rule.class_pattern, regex);
                                     ui.end_row();
 
-                                    // Title Pattern
                                     ui.label("Title Pattern:");
-                                    let mut title_text =
-                                        rule.title_pattern.clone().unwrap_or_default();
-                                    if ui.text_edit_singleline(&mut title_text).changed() {
-                                        rule.title_pattern = if title_text.is_empty() {
-                                            None
-                                        } else {
-                                            Some(title_text)
-                                        };
-                                        changed = true;
-                                    }
+                                    changed |= pattern_edit(ui, &mut rule.title_pattern, regex);
+                                    ui.end_row();
+
+                                    ui.label("Process Pattern:");
+                                    changed |= pattern_edit(ui, &mut rule.process_pattern, regex);
+                                    ui.end_row();
+
+                                    ui.label("Exclude Class:");
+                                    changed |=
+                                        pattern_edit(ui, &mut rule.class_exclude_pattern, regex);
+                                    ui.end_row();
+
+                                    ui.label("Exclude Title:");
+                                    changed |=
+                                        pattern_edit(ui, &mut rule.title_exclude_pattern, regex);
                                     ui.end_row();
 
                                     // Hotkey
@@ -589,22 +667,50 @@ impl SourcesTab {
 
                                     // Limit
                                     ui.label("Limit:");
-                                    if ui.checkbox(&mut rule.limit, "Single Instance").changed() {
+                                    if ui
+                                        .checkbox(&mut rule.limit, "Single Instance")
+                                        .on_hover_text(MULTI_INSTANCE_HINT)
+                                        .changed()
+                                    {
                                         changed = true;
                                     }
                                     ui.end_row();
                                 });
+                            ui.add_space(4.0);
+                            refresh_apps |= match_preview(ui, rule, self.running_apps.as_deref());
                             ui.add_space(8.0);
                         });
                     }
                     ui.separator();
                 }
 
+                // Saved positions and cycle slots follow the rule to its new name
+                if let Some((idx, new_alias)) = alias_committed {
+                    match profile.custom_alias_conflict(idx, &new_alias) {
+                        Some(error) => self.alias_error = Some((idx, error)),
+                        None => {
+                            let old_alias = std::mem::replace(
+                                &mut profile.custom_windows[idx].alias,
+                                new_alias.clone(),
+                            );
+                            profile.rename_custom_source(&old_alias, &new_alias);
+                            self.alias_error = None;
+                            changed = true;
+                        }
+                    }
+                }
+
                 if let Some(idx) = remove_idx {
                     profile.custom_windows.remove(idx);
                     self.expanded_rows.remove(&idx);
+                    self.alias_edits.clear();
+                    self.alias_error = None;
                     changed = true;
                 }
+
+                if refresh_apps {
+                    self.refresh_running_apps();
+                }
             });
         });
 
@@ -677,22 +783,7 @@ impl SourcesTab {
                 }
 
                 if trigger_refresh {
-                    match get_running_applications() {
-                        Ok(mut apps) => {
-                            // Filter out EVE clients to prevent duplication/confusion
-                            apps.retain(|app| {
-                                !app.title.starts_with("EVE - ") && app.title != "EVE"
-                            });
-
-                            // Dedup logic based on class+title
-                            apps.dedup_by(|a, b| a.class == b.class && a.title == b.title);
-                            self.running_apps = Some(apps);
-                            self.error_msg = None;
-                        }
-                        Err(e) => {
-                            self.error_msg = Some(format!("Failed to list apps: {}", e));
-                        }
-                    }
+                    self.refresh_running_apps();
                 }
             });
             ui.separator();
@@ -706,45 +797,65 @@ impl SourcesTab {
                     ui.end_row();
 
                     ui.label("Window Class Pattern:");
-                    let mut class_text = self.new_rule.class_pattern.clone().unwrap_or_default();
-                    if ui.text_edit_singleline(&mut class_text).changed() {
-                        self.new_rule.class_pattern = if class_text.is_empty() {
-                            None
-                        } else {
-                            Some(class_text)
-                        };
-                    }
+                    pattern_edit(ui, &mut self.new_rule.class_pattern, self.new_rule.regex);
                     ui.end_row();
 
                     ui.label("Window Title Pattern:");
-                    let mut title_text = self.new_rule.title_pattern.clone().unwrap_or_default();
-                    if ui.text_edit_singleline(&mut title_text).changed() {
-                        self.new_rule.title_pattern = if title_text.is_empty() {
-                            None
-                        } else {
-                            Some(title_text)
-                        };
-                    }
+                    pattern_edit(ui, &mut self.new_rule.title_pattern, self.new_rule.regex);
+                    ui.end_row();
+
+                    ui.label("Process Name Pattern:");
+                    pattern_edit(ui, &mut self.new_rule.process_pattern, self.new_rule.regex);
+                    ui.end_row();
+
+                    ui.label("Exclude Class:");
+                    pattern_edit(
+                        ui,
+                        &mut self.new_rule.class_exclude_pattern,
+                        self.new_rule.regex,
+                    );
+                    ui.end_row();
+
+                    ui.label("Exclude Title:");
+                    pattern_edit(
+                        ui,
+                        &mut self.new_rule.title_exclude_pattern,
+                        self.new_rule.regex,
+                    );
+                    ui.end_row();
+
+                    ui.label("");
+                    ui.checkbox(&mut self.new_rule.regex, "Regular expressions")
+                        .on_hover_text(REGEX_HINT);
                     ui.end_row();
 
                     ui.label("");
                     ui.weak(
-                        "A Display Name and at least one pattern (Class or Title) are required.",
+                        "A Display Name and at least one pattern (Class, Title or Process) are \
+                         required. Patterns are case-insensitive.",
                     );
                     ui.end_row();
 
                     ui.label("Limit:");
                     ui.checkbox(&mut self.new_rule.limit, "Limit to single instance")
-                        .on_hover_text(
-                            "If checked, only the first matching window will be previewed.",
-                        );
+                        .on_hover_text(MULTI_INSTANCE_HINT);
                     ui.end_row();
                 });
 
+            if (self.new_rule.class_pattern.is_some()
+                || self.new_rule.title_pattern.is_some()
+                || self.new_rule.process_pattern.is_some())
+                && match_preview(ui, &self.new_rule, self.running_apps.as_deref())
+            {
+                self.refresh_running_apps();
+            }
+
             ui.add_space(10.0);
 
             let is_valid = !self.new_rule.alias.is_empty()
-                && (self.new_rule.class_pattern.is_some() || self.new_rule.title_pattern.is_some());
+                && (self.new_rule.class_pattern.is_some()
+                    || self.new_rule.title_pattern.is_some()
+                    || self.new_rule.process_pattern.is_some());
 
             ui.horizontal(|ui| {
                 ui.add_enabled_ui(is_valid, |ui| {
@@ -760,6 +871,9 @@ impl SourcesTab {
                         self.new_rule.alias.clear();
                         self.new_rule.class_pattern = None;
                         self.new_rule.title_pattern = None;
+                        self.new_rule.process_pattern = None;
+                        self.new_rule.class_exclude_pattern = None;
+                        self.new_rule.title_exclude_pattern = None;
                         self.new_rule.limit = false;
                         self.new_rule.active_border_color = None;
                         self.new_rule.inactive_border_color = None;
@@ -788,3 +902,78 @@ impl SourcesTab {
         changed
     }
 }
+
+/// Single-line pattern editor; clearing the text removes the pattern
+fn pattern_edit(ui: &mut Ui, pattern: &mut Option<String>, regex: bool) -> bool {
+    let mut changed = false;
+    ui.vertical(|ui| {
+        let mut text = pattern.clone().unwrap_or_default();
+        if ui.text_edit_singleline(&mut text).changed() {
+            *pattern = if text.is_empty() { None } else { Some(text) };
+            changed = true;
+        }
+        if let Some(Err(e)) = pattern.as_deref().filter(|_| regex).map(pattern::compile) {
+            ui.label(
+                egui::RichText::new("Invalid regex, matched as plain text")
+                    .small()
+                    .color(COLOR_WARNING),
+            )
+            .on_hover_text(e.to_string());
+        }
+    });
+    changed
+}
+
+/// Live preview of the running windows `rule` matches. Returns true when the user asks
+/// for the window list to be (re)loaded.
+fn match_preview(
+    ui: &mut Ui,
+    rule: &CustomWindowRule,
+    running_apps: Option<&[WindowInfo]>,
+) -> bool {
+    let mut refresh = false;
+    let Some(apps) = running_apps else {
+        ui.horizontal(|ui| {
+            ui.weak("Match preview:");
+            refresh = ui.small_button("Load running windows").clicked();
+        });
+        return refresh;
+    };
+
+    let matches: Vec<&WindowInfo> = apps
+        .iter()
+        .filter(|app| rule.matches(&app.title, &app.class, &app.processes))
+        .collect();
+
+    ui.horizontal(|ui| {
+        let color = if matches.is_empty() {
+            COLOR_WARNING
+        } else {
+            COLOR_SUCCESS
+        };
+        ui.colored_label(
+            color,
+            format!("Matches {} running window(s)", matches.len()),
+        );
+        refresh = ui
+            .small_button("🔄")
+            .on_hover_text("Refresh running windows")
+            .clicked();
+    });
+    for app in matches.iter().take(MATCH_PREVIEW_LIMIT) {
+        let process = app
+            .processes
+            .first()
+            .map(|name| format!(" [{}]", name))
+            .unwrap_or_default();
+        ui.label(
+            egui::RichText::new(format!("{} ({}){}", app.class, app.title, process))
+                .small()
+                .weak(),
+        );
+    }
+    if matches.len() > MATCH_PREVIEW_LIMIT {
+        ui.weak(format!("…and {} more", matches.len() - MATCH_PREVIEW_LIMIT));
+    }
+    refresh
+}
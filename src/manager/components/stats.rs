@@ -0,0 +1,152 @@
+//! Stats tab: how long each character was focused this session, and when clients logged in
+//! or out
+
+use std::collections::VecDeque;
+
+use crate::common::constants::manager_ui::*;
+use crate::common::ipc::{CharacterEvent, CharacterEventKind, FocusTime};
+use crate::config::profile::GlobalSettings;
+use eframe::egui;
+
+/// Returns true if a setting changed
+pub fn ui(
+    ui: &mut egui::Ui,
+    stats: &[FocusTime],
+    events: &mut VecDeque<CharacterEvent>,
+    global: &mut GlobalSettings,
+) -> bool {
+    let mut changed = false;
+
+    ui.group(|ui| {
+        ui.label(egui::RichText::new("Focus Time").strong());
+        ui.label(
+            egui::RichText::new(
+                "Time each character's client had focus since the preview daemon started",
+            )
+            .small()
+            .weak(),
+        );
+        ui.add_space(ITEM_SPACING);
+
+        if stats.is_empty() {
+            ui.label("No character has had focus yet");
+        } else {
+            let total: u64 = stats.iter().map(|s| s.focused_secs).sum();
+            egui::Grid::new("focus_stats_grid")
+                .num_columns(4)
+                .spacing([ITEM_SPACING * 2.0, ITEM_SPACING])
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.label(egui::RichText::new("Character").strong());
+                    ui.label(egui::RichText::new("Focused").strong());
+                    ui.label(egui::RichText::new("Share").strong());
+                    ui.label(egui::RichText::new("Switches").strong());
+                    ui.end_row();
+
+                    for entry in stats {
+                        let share = if total == 0 {
+                            0.0
+                        } else {
+                            entry.focused_secs as f32 / total as f32
+                        };
+                        ui.label(&entry.name);
+                        ui.label(
+                            egui::RichText::new(format_duration(entry.focused_secs)).monospace(),
+                        );
+                        ui.add(
+                            egui::ProgressBar::new(share)
+                                .desired_width(120.0)
+                                .text(format!("{:.0}%", share * 100.0)),
+                        );
+                        ui.label(entry.switches.to_string());
+                        ui.end_row();
+                    }
+                });
+            ui.label(
+                egui::RichText::new(format!("Total: {}", format_duration(total)))
+                    .small()
+                    .weak(),
+            );
+        }
+
+        ui.add_space(ITEM_SPACING);
+        changed |= ui
+            .checkbox(&mut global.focus_stats_csv, "Write a CSV file per session")
+            .on_hover_text(
+                "Saved to ~/.config/eve-preview-manager/stats/ and updated every few seconds",
+            )
+            .changed();
+    });
+
+    ui.add_space(SECTION_SPACING);
+    login_history(ui, events);
+
+    changed
+}
+
+fn login_history(ui: &mut egui::Ui, events: &mut VecDeque<CharacterEvent>) {
+    ui.group(|ui| {
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new("Login History").strong());
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if !events.is_empty() && ui.small_button("Clear").clicked() {
+                    events.clear();
+                }
+            });
+        });
+        ui.label(
+            egui::RichText::new(
+                "Logins, logouts and closed clients since the Manager started, newest first",
+            )
+            .small()
+            .weak(),
+        );
+        ui.add_space(ITEM_SPACING);
+
+        if events.is_empty() {
+            ui.label("No logins or logouts yet");
+            return;
+        }
+        egui::Grid::new("login_history_grid")
+            .num_columns(2)
+            .spacing([ITEM_SPACING * 2.0, ITEM_SPACING / 2.0])
+            .striped(true)
+            .show(ui, |ui| {
+                for event in events.iter().rev() {
+                    ui.label(egui::RichText::new(format_time(event.timestamp)).monospace());
+                    let text = describe(&event.kind);
+                    if matches!(event.kind, CharacterEventKind::Closed(_)) {
+                        ui.label(egui::RichText::new(text).color(COLOR_WARNING));
+                    } else {
+                        ui.label(text);
+                    }
+                    ui.end_row();
+                }
+            });
+    });
+}
+
+fn describe(kind: &CharacterEventKind) -> String {
+    match kind {
+        CharacterEventKind::Login(name) => format!("{name} logged in"),
+        CharacterEventKind::Logout(name) => format!("{name} logged out"),
+        CharacterEventKind::Swap { from, to } => format!("{from} swapped to {to}"),
+        CharacterEventKind::Closed(name) => format!("{name}'s client closed"),
+    }
+}
+
+/// Local `HH:MM:SS` of a Unix timestamp
+fn format_time(timestamp: i64) -> String {
+    chrono::DateTime::from_timestamp(timestamp, 0)
+        .map(|time| {
+            time.with_timezone(&chrono::Local)
+                .format("%H:%M:%S")
+                .to_string()
+        })
+        .unwrap_or_default()
+}
+
+/// `H:MM:SS`
+fn format_duration(secs: u64) -> String {
+    format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
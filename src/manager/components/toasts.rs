@@ -0,0 +1,55 @@
+//! Toast area for daemon errors, stacked in the bottom-right corner
+
+use eframe::egui;
+use std::time::Instant;
+
+use crate::common::constants::manager_ui::*;
+use crate::manager::state::toasts::Toasts;
+
+pub fn render(ctx: &egui::Context, toasts: &mut Toasts) {
+    toasts.expire(Instant::now());
+    if toasts.is_empty() {
+        return;
+    }
+
+    let mut dismissed = None;
+    egui::Area::new(egui::Id::new("daemon_error_toasts"))
+        .anchor(
+            egui::Align2::RIGHT_BOTTOM,
+            egui::vec2(-ITEM_SPACING * 2.0, -ITEM_SPACING * 2.0),
+        )
+        .order(egui::Order::Foreground)
+        .show(ctx, |ui| {
+            ui.set_max_width(TOAST_WIDTH);
+            for (index, toast) in toasts.iter().enumerate() {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.set_width(TOAST_WIDTH);
+                    ui.horizontal(|ui| {
+                        ui.colored_label(
+                            COLOR_ERROR,
+                            egui::RichText::new(toast.error.kind.title()).strong(),
+                        );
+                        if toast.count > 1 {
+                            ui.label(
+                                egui::RichText::new(format!("×{}", toast.count))
+                                    .strong()
+                                    .color(COLOR_WARNING),
+                            )
+                            .on_hover_text("Times this error was reported");
+                        }
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            if ui.small_button("✖").on_hover_text("Dismiss").clicked() {
+                                dismissed = Some(index);
+                            }
+                        });
+                    });
+                    ui.label(egui::RichText::new(&toast.error.message).small());
+                });
+                ui.add_space(ITEM_SPACING / 2.0);
+            }
+        });
+
+    if let Some(index) = dismissed {
+        toasts.dismiss(index);
+    }
+}
@@ -5,7 +5,9 @@ use eframe::egui;
 use std::sync::{Arc, Mutex};
 
 #[cfg(target_os = "linux")]
-use crate::manager::{state::SharedState, utils::load_tray_icon_pixmap};
+use crate::common::i18n::{tr, tr_with};
+#[cfg(target_os = "linux")]
+use crate::manager::{app::show_manager_window, state::SharedState, utils::load_tray_icon_pixmap};
 
 /// System tray icon integration handling menu events and status updates
 #[cfg(target_os = "linux")]
@@ -34,7 +36,21 @@ impl ksni::Tray for AppTray {
     }
 
     fn title(&self) -> String {
-        "EVE Preview Manager".into()
+        let positions_dirty = self
+            .state
+            .lock()
+            .map(|state| state.positions_dirty)
+            .unwrap_or(false);
+        if positions_dirty {
+            tr("tray-title-unsaved")
+        } else {
+            tr("tray-title")
+        }
+    }
+
+    // Left click on the tray icon
+    fn activate(&mut self, _x: i32, _y: i32) {
+        show_manager_window(&self.ctx);
     }
 
     fn icon_pixmap(&self) -> Vec<ksni::Icon> {
@@ -47,7 +63,7 @@ impl ksni::Tray for AppTray {
         use ksni::menu::*;
 
         // Lock state to get current info
-        let (current_profile_idx, profile_names) = {
+        let (current_profile_idx, profile_names, positions_dirty, characters) = {
             if let Ok(state) = self.state.lock() {
                 let profile_names: Vec<String> = state
                     .config
@@ -56,16 +72,58 @@ impl ksni::Tray for AppTray {
                     .map(|p| p.profile_name.clone())
                     .collect();
                 let idx = state.selected_profile_idx;
-                (idx, profile_names)
+                // (name, visible)
+                let characters: Vec<(String, bool)> = state
+                    .detected_characters
+                    .iter()
+                    .map(|name| (name.clone(), !state.hidden_characters.contains(name)))
+                    .collect();
+                (idx, profile_names, state.positions_dirty, characters)
             } else {
-                (0, vec!["default".to_string()])
+                (0, vec!["default".to_string()], false, Vec::new())
             }
         };
 
+        // Per-character thumbnail visibility, not saved
+        let thumbnail_items: Vec<ksni::MenuItem<Self>> = if characters.is_empty() {
+            vec![
+                StandardItem {
+                    label: tr("tray-no-characters"),
+                    enabled: false,
+                    ..Default::default()
+                }
+                .into(),
+            ]
+        } else {
+            characters
+                .into_iter()
+                .map(|(name, visible)| {
+                    CheckmarkItem {
+                        label: menu_label(&name),
+                        checked: visible,
+                        activate: Box::new(move |this: &mut AppTray| {
+                            if let Ok(mut state) = this.state.lock() {
+                                state.set_character_hidden(&name, visible);
+                            }
+                        }),
+                        ..Default::default()
+                    }
+                    .into()
+                })
+                .collect()
+        };
+
         vec![
+            // Show the Manager window (e.g. after starting hidden in the tray)
+            StandardItem {
+                label: tr("tray-show-manager"),
+                activate: Box::new(|this: &mut AppTray| show_manager_window(&this.ctx)),
+                ..Default::default()
+            }
+            .into(),
             // Refresh item
             StandardItem {
-                label: "Refresh".into(),
+                label: tr("tray-refresh"),
                 activate: Box::new(|this: &mut AppTray| {
                     if let Ok(mut state) = this.state.lock() {
                         state.reload_daemon_config();
@@ -77,29 +135,55 @@ impl ksni::Tray for AppTray {
             .into(),
             // Separator
             MenuItem::Separator,
-            // Profile selector (radio group)
-            RadioGroup {
-                selected: current_profile_idx,
-                select: Box::new(|this: &mut AppTray, idx| {
-                    if let Ok(mut state) = this.state.lock() {
-                        state.switch_profile(idx);
+            // Profile switcher, the active profile checked
+            SubMenu {
+                label: tr_with(
+                    "tray-profiles",
+                    &[(
+                        "profile",
+                        profile_names
+                            .get(current_profile_idx)
+                            .map(|name| menu_label(name))
+                            .unwrap_or_default(),
+                    )],
+                ),
+                submenu: vec![
+                    RadioGroup {
+                        selected: current_profile_idx,
+                        select: Box::new(|this: &mut AppTray, idx| {
+                            if let Ok(mut state) = this.state.lock() {
+                                state.switch_profile(idx);
+                            }
+                            this.ctx.request_repaint();
+                        }),
+                        options: profile_names
+                            .iter()
+                            .map(|name| RadioItem {
+                                label: menu_label(name),
+                                ..Default::default()
+                            })
+                            .collect(),
                     }
-                    this.ctx.request_repaint();
-                }),
-                options: profile_names
-                    .iter()
-                    .map(|name| RadioItem {
-                        label: name.clone(),
-                        ..Default::default()
-                    })
-                    .collect(),
+                    .into(),
+                ],
+                ..Default::default()
+            }
+            .into(),
+            SubMenu {
+                label: tr("tray-thumbnails"),
+                submenu: thumbnail_items,
+                ..Default::default()
             }
             .into(),
             // Separator
             MenuItem::Separator,
             // Save Thumbnail Positions
             StandardItem {
-                label: "Save Thumbnail Positions".into(),
+                label: if positions_dirty {
+                    tr("tray-save-positions-unsaved")
+                } else {
+                    tr("tray-save-positions")
+                },
                 activate: Box::new(|this: &mut AppTray| {
                     if let Ok(mut state) = this.state.lock() {
                         if let Err(e) = state.save_thumbnail_positions() {
@@ -110,11 +194,33 @@ impl ksni::Tray for AppTray {
                 ..Default::default()
             }
             .into(),
+            // Undo last drag
+            StandardItem {
+                label: tr("tray-undo-move"),
+                activate: Box::new(|this: &mut AppTray| {
+                    if let Ok(state) = this.state.lock() {
+                        state.undo_thumbnail_move();
+                    }
+                }),
+                ..Default::default()
+            }
+            .into(),
+            // Pick up clients the daemon missed
+            StandardItem {
+                label: tr("tray-rescan"),
+                activate: Box::new(|this: &mut AppTray| {
+                    if let Ok(state) = this.state.lock() {
+                        state.rescan_windows();
+                    }
+                }),
+                ..Default::default()
+            }
+            .into(),
             // Separator
             MenuItem::Separator,
             // Quit item
             StandardItem {
-                label: "Quit".into(),
+                label: tr("tray-quit"),
                 activate: Box::new(|this: &mut AppTray| {
                     if let Ok(mut state) = this.state.lock() {
                         state.should_quit = true;
@@ -127,3 +233,9 @@ impl ksni::Tray for AppTray {
         ]
     }
 }
+
+/// Escape a name for a menu label, where a single underscore marks an access key
+#[cfg(target_os = "linux")]
+fn menu_label(name: &str) -> String {
+    name.replace('_', "__")
+}
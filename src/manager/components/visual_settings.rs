@@ -1,5 +1,5 @@
 use crate::common::constants::manager_ui::*;
-use crate::common::types::Dimensions;
+use crate::common::types::{Dimensions, SkippedStyle, TextAnchor};
 use crate::config::profile::Profile;
 use eframe::egui;
 
@@ -109,6 +109,20 @@ fn render_visual_controls(
                 }
             });
 
+            if ui
+                .checkbox(
+                    &mut profile.thumbnail_argb_visual,
+                    "Blend opacity into the thumbnail (ARGB windows)",
+                )
+                .on_hover_text(
+                    "Draws thumbnails on a 32-bit visual with the opacity applied while \
+                     rendering, instead of asking the compositor through _NET_WM_WINDOW_OPACITY",
+                )
+                .changed()
+            {
+                changed = true;
+            }
+
             ui.add_space(ITEM_SPACING);
 
             // Active Border toggle
@@ -220,18 +234,211 @@ fn render_visual_controls(
                 }
             });
 
+            ui.horizontal(|ui| {
+                ui.label("Text Anchor:");
+                egui::ComboBox::from_id_salt("thumbnail_text_anchor")
+                    .selected_text(profile.thumbnail_text_anchor.label())
+                    .show_ui(ui, |ui| {
+                        for anchor in TextAnchor::ALL {
+                            if ui
+                                .selectable_value(
+                                    &mut profile.thumbnail_text_anchor,
+                                    anchor,
+                                    anchor.label(),
+                                )
+                                .changed()
+                            {
+                                changed = true;
+                            }
+                        }
+                    });
+            });
+
+            // Offsets point inward from the anchor corner; centered text can shift either way
+            let offset_range = if profile.thumbnail_text_anchor == TextAnchor::Center {
+                -100..=100
+            } else {
+                0..=100
+            };
             ui.horizontal(|ui| {
                 ui.label("Text Position:");
                 ui.label("X:");
                 if ui
-                    .add(egui::DragValue::new(&mut profile.thumbnail_text_x).range(0..=100))
+                    .add(
+                        egui::DragValue::new(&mut profile.thumbnail_text_x)
+                            .range(offset_range.clone()),
+                    )
                     .changed()
                 {
                     changed = true;
                 }
                 ui.label("Y:");
                 if ui
-                    .add(egui::DragValue::new(&mut profile.thumbnail_text_y).range(0..=100))
+                    .add(egui::DragValue::new(&mut profile.thumbnail_text_y).range(offset_range))
+                    .changed()
+                {
+                    changed = true;
+                }
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Badge Corner:");
+                egui::ComboBox::from_id_salt("thumbnail_badge_anchor")
+                    .selected_text(profile.thumbnail_badge_anchor.label())
+                    .show_ui(ui, |ui| {
+                        for anchor in TextAnchor::ALL {
+                            if ui
+                                .selectable_value(
+                                    &mut profile.thumbnail_badge_anchor,
+                                    anchor,
+                                    anchor.label(),
+                                )
+                                .changed()
+                            {
+                                changed = true;
+                            }
+                        }
+                    });
+            })
+            .response
+            .on_hover_text("Where per-character badges (Characters tab) are drawn");
+
+            ui.horizontal(|ui| {
+                ui.label("Skipped Indicator:");
+                egui::ComboBox::from_id_salt("thumbnail_skipped_style")
+                    .selected_text(profile.thumbnail_skipped_style.label())
+                    .show_ui(ui, |ui| {
+                        for style in SkippedStyle::ALL {
+                            if ui
+                                .selectable_value(
+                                    &mut profile.thumbnail_skipped_style,
+                                    style,
+                                    style.label(),
+                                )
+                                .changed()
+                            {
+                                changed = true;
+                            }
+                        }
+                    });
+
+                ui.add_enabled_ui(profile.thumbnail_skipped_style.uses_color(), |ui| {
+                    let text_edit =
+                        egui::TextEdit::singleline(&mut profile.thumbnail_skipped_color)
+                            .desired_width(100.0);
+                    if ui.add(text_edit).changed() {
+                        changed = true;
+                    }
+
+                    // Color picker button
+                    if let Ok(mut color) = parse_hex_color(&profile.thumbnail_skipped_color)
+                        && ui.color_edit_button_srgba(&mut color).changed()
+                    {
+                        profile.thumbnail_skipped_color = format_hex_color(color);
+                        changed = true;
+                    }
+                });
+            })
+            .response
+            .on_hover_text("How thumbnails of characters skipped in the cycle are marked");
+
+            ui.horizontal(|ui| {
+                ui.label("Cycle Order Badge:");
+                let selected = if profile.thumbnail_cycle_order_group.is_empty() {
+                    "Off"
+                } else {
+                    profile.thumbnail_cycle_order_group.as_str()
+                };
+                egui::ComboBox::from_id_salt("thumbnail_cycle_order_group")
+                    .selected_text(selected.to_string())
+                    .show_ui(ui, |ui| {
+                        if ui
+                            .selectable_value(
+                                &mut profile.thumbnail_cycle_order_group,
+                                String::new(),
+                                "Off",
+                            )
+                            .changed()
+                        {
+                            changed = true;
+                        }
+                        for group in &profile.cycle_groups {
+                            if ui
+                                .selectable_value(
+                                    &mut profile.thumbnail_cycle_order_group,
+                                    group.name.clone(),
+                                    &group.name,
+                                )
+                                .changed()
+                            {
+                                changed = true;
+                            }
+                        }
+                    });
+            })
+            .response
+            .on_hover_text(
+                "Shows each character's position in this cycle group as a numbered badge",
+            );
+
+            ui.horizontal(|ui| {
+                if ui
+                    .checkbox(
+                        &mut profile.thumbnail_next_target_highlight,
+                        "Highlight next cycle target:",
+                    )
+                    .on_hover_text(
+                        "Borders the thumbnail the next forward cycle hotkey press switches to",
+                    )
+                    .changed()
+                {
+                    changed = true;
+                }
+
+                ui.add_enabled_ui(profile.thumbnail_next_target_highlight, |ui| {
+                    let text_edit =
+                        egui::TextEdit::singleline(&mut profile.thumbnail_next_target_color)
+                            .desired_width(100.0);
+                    if ui.add(text_edit).changed() {
+                        changed = true;
+                    }
+
+                    // Color picker button
+                    if let Ok(mut color) = parse_hex_color(&profile.thumbnail_next_target_color)
+                        && ui.color_edit_button_srgba(&mut color).changed()
+                    {
+                        profile.thumbnail_next_target_color = format_hex_color(color);
+                        changed = true;
+                    }
+                });
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("HiDPI Scale:");
+                if ui
+                    .add(
+                        egui::DragValue::new(&mut profile.thumbnail_scale_percent)
+                            .range(0..=400)
+                            .speed(5)
+                            .custom_formatter(|n, _| {
+                                if n == 0.0 {
+                                    "Auto".to_string()
+                                } else {
+                                    format!("{}%", n)
+                                }
+                            })
+                            .custom_parser(|s| {
+                                if s.trim().eq_ignore_ascii_case("auto") {
+                                    Some(0.0)
+                                } else {
+                                    s.trim().trim_end_matches('%').parse().ok()
+                                }
+                            }),
+                    )
+                    .on_hover_text(
+                        "Scales default thumbnail sizes, text size and text position. \
+                         Auto detects each monitor's scale from its DPI.",
+                    )
                     .changed()
                 {
                     changed = true;
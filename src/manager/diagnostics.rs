@@ -0,0 +1,575 @@
+//! Environment diagnostics for the Manager's Diagnostics tab
+//!
+//! Probes the X server, session type, compositor, input permissions and fonts, and
+//! turns the results into checks with actionable hints.
+
+use x11rb::connection::Connection;
+use x11rb::protocol::xproto::{AtomEnum, ConnectionExt};
+use x11rb::rust_connection::RustConnection;
+
+use crate::common::constants::{input, paths, permissions};
+use crate::common::ipc::HotkeyLatency;
+use crate::config::profile::{HotkeyBackendType, Profile};
+use crate::daemon::font::discovery;
+
+/// X11 extensions the daemon relies on: (protocol name, display name, required)
+const EXTENSIONS: [(&str, &str, bool); 4] = [
+    ("Composite", "Composite", true),
+    ("DAMAGE", "Damage", true),
+    ("RENDER", "Render", true),
+    ("XFIXES", "XFixes", false),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Ok,
+    Info,
+    Warning,
+    Error,
+}
+
+impl CheckStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            CheckStatus::Ok => "OK",
+            CheckStatus::Info => "INFO",
+            CheckStatus::Warning => "WARN",
+            CheckStatus::Error => "FAIL",
+        }
+    }
+}
+
+/// Result of a single diagnostic check
+#[derive(Debug, Clone)]
+pub struct Check {
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+    pub hint: Option<String>,
+}
+
+impl Check {
+    fn new(name: impl Into<String>, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+
+    fn with_hint(mut self, hint: impl Into<String>) -> Self {
+        self.hint = Some(hint.into());
+        self
+    }
+}
+
+/// Run all checks against the current environment and active profile
+pub fn run(profile: &Profile) -> Vec<Check> {
+    let mut checks = Vec::new();
+
+    let wayland = std::env::var_os("WAYLAND_DISPLAY").is_some();
+    checks.push(session_check(
+        std::env::var("XDG_SESSION_TYPE").ok().as_deref(),
+        wayland,
+        std::env::var("DISPLAY").ok().as_deref(),
+    ));
+
+    match x11rb::connect(None) {
+        Ok((conn, screen_num)) => {
+            checks.push(Check::new("X11 connection", CheckStatus::Ok, "Connected"));
+            checks.extend(extension_checks(&conn));
+            let (cm_running, wm_name) = compositor_state(&conn, screen_num);
+            checks.push(compositor_check(cm_running, wm_name.as_deref(), wayland));
+        }
+        Err(e) => {
+            checks.push(
+                Check::new("X11 connection", CheckStatus::Error, e.to_string()).with_hint(
+                    "Previews need an X11 display. Check that DISPLAY is set and, on Wayland, that XWayland is enabled",
+                ),
+            );
+        }
+    }
+
+    checks.push(input_group_check(
+        profile.hotkey_backend,
+        std::env::var("USER").ok().as_deref(),
+        std::fs::read_to_string("/etc/group").ok().as_deref(),
+        std::fs::read_to_string("/proc/self/status").ok().as_deref(),
+    ));
+    checks.push(font_check(&profile.thumbnail_text_font));
+
+    checks
+}
+
+/// Plain-text report for pasting into bug reports
+pub fn format_report(checks: &[Check]) -> String {
+    let mut report = format!(
+        "EVE Preview Manager {} diagnostics\n",
+        env!("CARGO_PKG_VERSION")
+    );
+    for check in checks {
+        report.push_str(&format!(
+            "[{}] {}: {}\n",
+            check.status.label(),
+            check.name,
+            check.detail
+        ));
+        if let Some(hint) = &check.hint {
+            report.push_str(&format!("       {}\n", hint));
+        }
+    }
+    report
+}
+
+/// Hotkey switch latency reported by the daemon (`None` until the first hotkey switch)
+pub fn latency_check(latency: Option<&HotkeyLatency>) -> Check {
+    let name = "Hotkey latency";
+    let Some(latency) = latency else {
+        return Check::new(name, CheckStatus::Info, "No hotkey switches measured yet").with_hint(
+            "Switch clients with a hotkey to measure the time from key press to window activation",
+        );
+    };
+
+    let ms = |d: std::time::Duration| format!("{:.1} ms", d.as_secs_f64() * 1000.0);
+    let detail = format!(
+        "{:?} backend, {} switches: last {}, median {}, 95% {}, max {}",
+        latency.backend,
+        latency.samples,
+        ms(latency.last),
+        ms(latency.median),
+        ms(latency.p95),
+        ms(latency.max)
+    );
+    if latency.p95.as_millis() > u128::from(input::LATENCY_WARN_MS) {
+        Check::new(name, CheckStatus::Warning, detail).with_hint(
+            "Switching is slow. Compare with the other hotkey backend and include these numbers when reporting it",
+        )
+    } else {
+        Check::new(name, CheckStatus::Ok, detail)
+    }
+}
+
+fn session_check(session_type: Option<&str>, wayland: bool, display: Option<&str>) -> Check {
+    let has_display = display.is_some_and(|d| !d.is_empty());
+    if wayland || session_type == Some("wayland") {
+        if has_display {
+            Check::new("Session", CheckStatus::Ok, "Wayland with XWayland")
+        } else {
+            Check::new("Session", CheckStatus::Error, "Wayland without XWayland").with_hint(
+                "EVE and the previews run through XWayland. Enable XWayland in your compositor",
+            )
+        }
+    } else if session_type == Some("x11") || has_display {
+        Check::new("Session", CheckStatus::Info, "Native X11").with_hint(
+            "Supported, but some window managers fight the previews for stacking order. Wayland with XWayland is recommended",
+        )
+    } else {
+        Check::new("Session", CheckStatus::Warning, "Unknown session type")
+            .with_hint("Neither WAYLAND_DISPLAY nor DISPLAY is set")
+    }
+}
+
+fn extension_checks(conn: &RustConnection) -> Vec<Check> {
+    EXTENSIONS
+        .iter()
+        .map(|&(protocol_name, display_name, required)| {
+            let present = conn
+                .query_extension(protocol_name.as_bytes())
+                .ok()
+                .and_then(|cookie| cookie.reply().ok())
+                .is_some_and(|reply| reply.present);
+            let name = format!("{} extension", display_name);
+            match (present, required) {
+                (true, _) => Check::new(name, CheckStatus::Ok, "Available"),
+                (false, true) => Check::new(name, CheckStatus::Error, "Missing")
+                    .with_hint("Required to render previews. Update your X server or XWayland"),
+                (false, false) => Check::new(name, CheckStatus::Warning, "Missing")
+                    .with_hint("Optional, but its absence usually indicates an unusual X server"),
+            }
+        })
+        .collect()
+}
+
+/// Whether a compositing manager owns `_NET_WM_CM_S<n>`, and the window manager's name
+fn compositor_state(conn: &RustConnection, screen_num: usize) -> (bool, Option<String>) {
+    let intern = |name: &[u8]| {
+        conn.intern_atom(false, name)
+            .ok()
+            .and_then(|cookie| cookie.reply().ok())
+            .map(|reply| reply.atom)
+    };
+
+    let root = conn.setup().roots[screen_num].root;
+    let cm_running = crate::x11::compositor_running(conn, root);
+    let wm_name = (|| {
+        let check_atom = intern(b"_NET_SUPPORTING_WM_CHECK")?;
+        let name_atom = intern(b"_NET_WM_NAME")?;
+        let utf8_atom = intern(b"UTF8_STRING")?;
+        let wm_window = conn
+            .get_property(false, root, check_atom, AtomEnum::WINDOW, 0, 1)
+            .ok()?
+            .reply()
+            .ok()?
+            .value32()?
+            .next()?;
+        let name = conn
+            .get_property(false, wm_window, name_atom, utf8_atom, 0, 256)
+            .ok()?
+            .reply()
+            .ok()?;
+        Some(String::from_utf8_lossy(&name.value).into_owned()).filter(|n| !n.is_empty())
+    })();
+
+    (cm_running, wm_name)
+}
+
+fn compositor_check(cm_running: bool, wm_name: Option<&str>, wayland: bool) -> Check {
+    let wm = wm_name.unwrap_or("unknown window manager");
+    if cm_running {
+        Check::new(
+            "Compositor",
+            CheckStatus::Ok,
+            format!("Compositing manager running ({})", wm),
+        )
+    } else if wayland {
+        // Wayland compositors composite XWayland windows themselves
+        Check::new(
+            "Compositor",
+            CheckStatus::Info,
+            format!("Composited by the Wayland compositor ({})", wm),
+        )
+    } else {
+        Check::new(
+            "Compositor",
+            CheckStatus::Warning,
+            format!(
+                "No compositing manager ({}); thumbnails are drawn fully opaque",
+                wm
+            ),
+        )
+        .with_hint(
+            "Opacity needs a compositing manager, so the daemon turns it off. Without one, covered parts of EVE clients can also show up black or stale in their thumbnails. Enable compositing in your window manager or run one such as picom",
+        )
+    }
+}
+
+/// GID and listed members of the input group in the contents of `/etc/group`
+fn input_group(etc_group: &str) -> Option<(u32, Vec<&str>)> {
+    etc_group.lines().find_map(|line| {
+        let mut fields = line.split(':');
+        (fields.next()? == permissions::INPUT_GROUP).then_some(())?;
+        let gid = fields.nth(1)?.parse::<u32>().ok()?;
+        let members: Vec<&str> = fields.next().unwrap_or("").split(',').collect();
+        Some((gid, members))
+    })
+}
+
+/// Checks shown next to the evdev backend in the hotkey settings: group membership and
+/// whether the event devices can actually be opened
+pub fn evdev_access() -> Vec<Check> {
+    use std::os::unix::fs::MetadataExt;
+
+    let etc_group = std::fs::read_to_string("/etc/group").ok();
+    let mut nodes = Vec::new();
+    if let Ok(entries) = std::fs::read_dir(paths::DEV_INPUT) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if !path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with("event"))
+            {
+                continue;
+            }
+            let Ok(metadata) = std::fs::metadata(&path) else {
+                continue;
+            };
+            let readable = std::fs::File::open(&path).is_ok();
+            nodes.push(DeviceNode {
+                readable,
+                gid: metadata.gid(),
+            });
+        }
+    }
+
+    vec![
+        input_group_check(
+            HotkeyBackendType::Evdev,
+            std::env::var("USER").ok().as_deref(),
+            etc_group.as_deref(),
+            std::fs::read_to_string("/proc/self/status").ok().as_deref(),
+        ),
+        device_access_check(
+            &nodes,
+            etc_group
+                .as_deref()
+                .and_then(input_group)
+                .map(|(gid, _)| gid),
+        ),
+    ]
+}
+
+/// Ownership and accessibility of one `/dev/input/event*` node
+#[derive(Debug, Clone, Copy)]
+struct DeviceNode {
+    readable: bool,
+    gid: u32,
+}
+
+fn device_access_check(nodes: &[DeviceNode], input_gid: Option<u32>) -> Check {
+    let name = "Input devices";
+    if nodes.is_empty() {
+        return Check::new(
+            name,
+            CheckStatus::Error,
+            format!("No event devices in {}", paths::DEV_INPUT),
+        )
+        .with_hint("Use the X11 hotkey backend instead");
+    }
+
+    let readable = nodes.iter().filter(|node| node.readable).count();
+    let detail = format!("{} of {} event devices readable", readable, nodes.len());
+    if readable > 0 {
+        return Check::new(name, CheckStatus::Ok, detail);
+    }
+
+    let owned_by_input = input_gid.is_some_and(|gid| nodes.iter().all(|node| node.gid == gid));
+    if owned_by_input {
+        Check::new(name, CheckStatus::Error, detail).with_hint(format!(
+            "The devices belong to the '{}' group, so joining it is enough. Run `{}`, then log out and back in",
+            permissions::INPUT_GROUP,
+            permissions::ADD_TO_INPUT_GROUP
+        ))
+    } else {
+        Check::new(name, CheckStatus::Error, detail).with_hint(format!(
+            "The devices do not belong to the '{}' group on this system. Add a udev rule such as `{}` to {}, run `{}`, then join the group",
+            permissions::INPUT_GROUP,
+            permissions::UDEV_RULE,
+            permissions::UDEV_RULE_PATH,
+            permissions::RELOAD_UDEV_RULES
+        ))
+    }
+}
+
+fn input_group_check(
+    backend: HotkeyBackendType,
+    user: Option<&str>,
+    etc_group: Option<&str>,
+    proc_status: Option<&str>,
+) -> Check {
+    let name = "Input group";
+    let group = etc_group.and_then(input_group);
+
+    // Membership of the running process (only changes after logging in again)
+    let active = group.as_ref().is_some_and(|(gid, _)| {
+        proc_status
+            .and_then(|status| status.lines().find_map(|l| l.strip_prefix("Groups:")))
+            .is_some_and(|groups| {
+                groups
+                    .split_whitespace()
+                    .any(|g| g.parse::<u32>().ok() == Some(*gid))
+            })
+    });
+    let listed = group
+        .as_ref()
+        .zip(user)
+        .is_some_and(|((_, members), user)| members.contains(&user));
+
+    if backend != HotkeyBackendType::Evdev {
+        let detail = if active { "Member" } else { "Not a member" };
+        return Check::new(name, CheckStatus::Info, detail)
+            .with_hint("Only needed for the evdev hotkey backend");
+    }
+
+    if active {
+        Check::new(
+            name,
+            CheckStatus::Ok,
+            format!(
+                "Member, {} is accessible to evdev hotkeys",
+                paths::DEV_INPUT
+            ),
+        )
+    } else if listed {
+        Check::new(
+            name,
+            CheckStatus::Warning,
+            "Added, but not active in this session",
+        )
+        .with_hint("Log out and back in for the group change to take effect")
+    } else if group.is_none() {
+        Check::new(name, CheckStatus::Error, "No 'input' group on this system")
+            .with_hint("Use the X11 hotkey backend instead")
+    } else {
+        Check::new(name, CheckStatus::Error, "Not a member").with_hint(format!(
+            "The evdev hotkey backend needs access to {}. Run `{}`, then log out and back in, or switch to the X11 backend",
+            paths::DEV_INPUT,
+            permissions::ADD_TO_INPUT_GROUP
+        ))
+    }
+}
+
+fn font_check(configured_font: &str) -> Check {
+    let name = "Fonts";
+    if !configured_font.is_empty() {
+        return match discovery::find_font_path(configured_font) {
+            Ok(path) => Check::new(
+                name,
+                CheckStatus::Ok,
+                format!("'{}' found at {}", configured_font, path.display()),
+            ),
+            Err(_) => Check::new(
+                name,
+                CheckStatus::Warning,
+                format!("Configured font '{}' not found", configured_font),
+            )
+            .with_hint("Pick an installed font in the Appearance tab"),
+        };
+    }
+
+    match discovery::select_best_default_font() {
+        Ok((family, path)) => Check::new(
+            name,
+            CheckStatus::Ok,
+            format!("Default font '{}' found at {}", family, path.display()),
+        ),
+        Err(_) => Check::new(name, CheckStatus::Warning, "No TrueType font found").with_hint(
+            "Previews fall back to X11 core fonts. Install a monospace font such as DejaVu Sans Mono",
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GROUPS: &str = "wheel:x:10:alice\ninput:x:97:alice,bob\n";
+
+    #[test]
+    fn test_session_check() {
+        assert_eq!(
+            session_check(Some("wayland"), true, Some(":0")).status,
+            CheckStatus::Ok
+        );
+        assert_eq!(
+            session_check(Some("wayland"), true, None).status,
+            CheckStatus::Error
+        );
+        assert_eq!(
+            session_check(Some("x11"), false, Some(":0")).status,
+            CheckStatus::Info
+        );
+        assert_eq!(
+            session_check(None, false, None).status,
+            CheckStatus::Warning
+        );
+    }
+
+    #[test]
+    fn test_compositor_check() {
+        assert_eq!(
+            compositor_check(true, Some("KWin"), false).status,
+            CheckStatus::Ok
+        );
+        assert_eq!(
+            compositor_check(false, None, true).status,
+            CheckStatus::Info
+        );
+        assert_eq!(
+            compositor_check(false, Some("i3"), false).status,
+            CheckStatus::Warning
+        );
+    }
+
+    #[test]
+    fn test_input_group_check() {
+        let evdev = HotkeyBackendType::Evdev;
+        let active = "Name:\tepm\nGroups:\t10 97 \n";
+        let inactive = "Name:\tepm\nGroups:\t10\n";
+
+        let check = input_group_check(evdev, Some("alice"), Some(GROUPS), Some(active));
+        assert_eq!(check.status, CheckStatus::Ok);
+
+        // Added to the group but not logged in again yet
+        let check = input_group_check(evdev, Some("alice"), Some(GROUPS), Some(inactive));
+        assert_eq!(check.status, CheckStatus::Warning);
+
+        let check = input_group_check(evdev, Some("carol"), Some(GROUPS), Some(inactive));
+        assert_eq!(check.status, CheckStatus::Error);
+        assert!(
+            check
+                .hint
+                .unwrap()
+                .contains(permissions::ADD_TO_INPUT_GROUP)
+        );
+
+        let check = input_group_check(
+            HotkeyBackendType::X11,
+            Some("carol"),
+            Some(GROUPS),
+            Some(inactive),
+        );
+        assert_eq!(check.status, CheckStatus::Info);
+    }
+
+    #[test]
+    fn test_device_access_check() {
+        let node = |readable, gid| DeviceNode { readable, gid };
+
+        let check = device_access_check(&[], Some(97));
+        assert_eq!(check.status, CheckStatus::Error);
+
+        let check = device_access_check(&[node(false, 97), node(true, 97)], Some(97));
+        assert_eq!(check.status, CheckStatus::Ok);
+        assert_eq!(check.detail, "1 of 2 event devices readable");
+
+        // Owned by the input group: joining it is the fix
+        let check = device_access_check(&[node(false, 97)], Some(97));
+        assert!(
+            check
+                .hint
+                .unwrap()
+                .contains(permissions::ADD_TO_INPUT_GROUP)
+        );
+
+        // Owned by root: a udev rule is needed first
+        let check = device_access_check(&[node(false, 0)], Some(97));
+        assert!(check.hint.unwrap().contains(permissions::UDEV_RULE));
+    }
+
+    #[test]
+    fn test_latency_check() {
+        assert_eq!(latency_check(None).status, CheckStatus::Info);
+
+        let ms = std::time::Duration::from_millis;
+        let mut latency = HotkeyLatency {
+            backend: HotkeyBackendType::Evdev,
+            samples: 12,
+            last: ms(4),
+            median: ms(5),
+            p95: ms(9),
+            max: ms(120),
+        };
+        let check = latency_check(Some(&latency));
+        assert_eq!(check.status, CheckStatus::Ok);
+        assert_eq!(
+            check.detail,
+            "Evdev backend, 12 switches: last 4.0 ms, median 5.0 ms, 95% 9.0 ms, max 120.0 ms"
+        );
+
+        latency.p95 = ms(input::LATENCY_WARN_MS + 1);
+        assert_eq!(latency_check(Some(&latency)).status, CheckStatus::Warning);
+    }
+
+    #[test]
+    fn test_format_report() {
+        let checks = vec![
+            Check::new("Session", CheckStatus::Ok, "Wayland with XWayland"),
+            Check::new("Fonts", CheckStatus::Warning, "Missing").with_hint("Install one"),
+        ];
+        let report = format_report(&checks);
+        assert!(report.contains("[OK] Session: Wayland with XWayland\n"));
+        assert!(report.contains("[WARN] Fonts: Missing\n       Install one\n"));
+    }
+}
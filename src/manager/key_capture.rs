@@ -11,6 +11,7 @@ use tracing::{debug, info, warn};
 use crate::common::constants::{input, paths, permissions};
 use crate::config::{HotkeyBackendType, HotkeyBinding};
 use crate::input::device_detection;
+use crate::input::keymap::Keymap;
 use x11rb::connection::Connection;
 use x11rb::protocol::xproto::{ConnectionExt, GrabMode, KeyButMask};
 
@@ -37,6 +38,8 @@ pub struct CaptureState {
     pub super_key: bool,
     /// The main key that was pressed (None until a non-modifier key is pressed)
     pub key_code: Option<u16>,
+    /// Symbol the active layout puts on the main key
+    pub keysym: Option<u32>,
     /// Human-readable description of what's being detected
     pub description: String,
 }
@@ -49,6 +52,7 @@ impl CaptureState {
             alt: false,
             super_key: false,
             key_code: None,
+            keysym: None,
             description: "Press any key or mouse button...".to_string(),
         }
     }
@@ -78,13 +82,14 @@ impl CaptureState {
             }
         } else {
             // Key captured, show full binding
-            let binding = HotkeyBinding::new(
+            let mut binding = HotkeyBinding::new(
                 self.key_code.unwrap(),
                 self.ctrl,
                 self.shift,
                 self.alt,
                 self.super_key,
             );
+            binding.keysym = self.keysym;
             self.description = binding.display_name();
         }
     }
@@ -135,6 +140,48 @@ pub fn start_capture(
     Ok((state_rx, result_rx, cancel_tx))
 }
 
+/// How long a test read waits for a key event
+pub const TEST_READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Read from the evdev devices in the background until the first key event or
+/// `TEST_READ_TIMEOUT`. The receiver gets the device the event came from, or why none arrived.
+pub fn start_test_read() -> Receiver<Result<String, String>> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let _ = tx.send(test_read().map_err(|e| format!("{e:#}")));
+    });
+    rx
+}
+
+fn test_read() -> Result<String> {
+    let mut devices: Vec<_> = device_detection::find_all_input_devices_with_paths()?
+        .into_iter()
+        .map(|(device, path)| {
+            device.set_nonblocking(true).ok();
+            (device, device_detection::extract_device_id(&path))
+        })
+        .collect();
+
+    let start = std::time::Instant::now();
+    while start.elapsed() < TEST_READ_TIMEOUT {
+        for (device, device_id) in &mut devices {
+            if let Ok(mut events) = device.fetch_events()
+                && events.any(|event| event.event_type() == EventType::KEY)
+            {
+                info!(device_id = %device_id, "Test read received a key event");
+                return Ok(device_id.clone());
+            }
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    anyhow::bail!(
+        "Opened {} device(s) but no key events arrived within {} seconds",
+        devices.len(),
+        TEST_READ_TIMEOUT.as_secs()
+    )
+}
+
 /// Blocking key capture using X11 GrabKeyboard
 fn capture_key_x11(
     state_tx: Sender<CaptureState>,
@@ -193,6 +240,10 @@ fn capture_key_x11(
 
     info!("Keyboard grabbed for X11 key capture");
 
+    let keymap = Keymap::query(&conn)
+        .inspect_err(|e| warn!(error = %e, "Key labels will use US names"))
+        .ok();
+
     let mut state = CaptureState::new();
     let _ = state_tx.send(state.clone());
 
@@ -266,15 +317,17 @@ fn capture_key_x11(
                     } else {
                         // Non-modifier key pressed - this is our hotkey trigger
                         state.key_code = Some(evdev_code);
+                        state.keysym = keymap.as_ref().and_then(|k| k.keysym(evdev_code));
                         state.update_description();
 
-                        let binding = HotkeyBinding::new(
+                        let mut binding = HotkeyBinding::new(
                             evdev_code,
                             state.ctrl,
                             state.shift,
                             state.alt,
                             state.super_key,
                         );
+                        binding.keysym = state.keysym;
 
                         // X11 generic capture doesn't distinguish source devices
                         let _ = state_tx.send(state.clone());
@@ -331,6 +384,13 @@ fn capture_key_blocking(
         "Starting key capture on all input devices (non-blocking mode)"
     );
 
+    // evdev only reports physical keys; the X server knows what the layout prints on them
+    let keymap = x11rb::connect(None)
+        .map_err(anyhow::Error::from)
+        .and_then(|(conn, _)| Keymap::query(&conn))
+        .inspect_err(|e| warn!(error = %e, "Key labels will use US names"))
+        .ok();
+
     let mut state = CaptureState::new();
     let _ = state_tx.send(state.clone());
 
@@ -433,6 +493,7 @@ fn capture_key_blocking(
 
                             // Otherwise, capture the key
                             state.key_code = Some(key_code);
+                            state.keysym = keymap.as_ref().and_then(|k| k.keysym(key_code));
                             state.update_description();
                             let _ = state_tx.send(state.clone());
 
@@ -441,7 +502,7 @@ fn capture_key_blocking(
                                 contributing_devices.iter().cloned().collect();
                             source_devices.sort();
 
-                            let binding = HotkeyBinding::with_devices(
+                            let mut binding = HotkeyBinding::with_devices(
                                 key_code,
                                 state.ctrl,
                                 state.shift,
@@ -449,6 +510,7 @@ fn capture_key_blocking(
                                 state.super_key,
                                 source_devices,
                             );
+                            binding.keysym = state.keysym;
 
                             info!(binding = ?binding, "Key captured successfully");
                             return Ok(CaptureResult::Captured(binding));
@@ -0,0 +1,170 @@
+//! Shareable thumbnail layout snippets
+//!
+//! A snippet is one line of compact JSON holding the position and size of every character
+//! and custom source thumbnail, e.g.
+//! `{"eve-preview-layout":1,"characters":{"Alice":[10,20,480,270]},"sources":{}}`.
+//! Importing applies entries to thumbnails of the same name and reports the rest.
+
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+
+use crate::common::types::CharacterSettings;
+use crate::config::profile::Profile;
+use crate::manager::components::layout_editor::LayoutMove;
+
+/// Snippet format version
+const VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Snippet {
+    #[serde(rename = "eve-preview-layout")]
+    version: u32,
+    /// Name -> [x, y, width, height]
+    #[serde(default)]
+    characters: BTreeMap<String, [i32; 4]>,
+    #[serde(default)]
+    sources: BTreeMap<String, [i32; 4]>,
+}
+
+/// Outcome of applying a snippet to a profile
+#[derive(Debug, Default)]
+pub struct Import {
+    /// Thumbnails whose position or size changed, to send to the daemon
+    pub moves: Vec<LayoutMove>,
+    /// Entries already at the snippet's position
+    pub unchanged: usize,
+    /// Snippet names without a thumbnail in this profile
+    pub unknown: Vec<String>,
+}
+
+pub fn export(profile: &Profile) -> String {
+    let entries = |thumbnails: &HashMap<String, CharacterSettings>| {
+        thumbnails
+            .iter()
+            .map(|(name, s)| {
+                (
+                    name.clone(),
+                    [
+                        i32::from(s.x),
+                        i32::from(s.y),
+                        i32::from(s.dimensions.width),
+                        i32::from(s.dimensions.height),
+                    ],
+                )
+            })
+            .collect()
+    };
+    let snippet = Snippet {
+        version: VERSION,
+        characters: entries(&profile.character_thumbnails),
+        sources: entries(&profile.custom_source_thumbnails),
+    };
+    serde_json::to_string(&snippet).unwrap_or_default()
+}
+
+/// Apply the positions in `text` to thumbnails of the same name in `profile`
+pub fn import(profile: &mut Profile, text: &str) -> Result<Import> {
+    let snippet: Snippet =
+        serde_json::from_str(text.trim()).context("Not a thumbnail layout snippet")?;
+    if snippet.version > VERSION {
+        bail!(
+            "Layout snippet version {} is newer than this version supports ({})",
+            snippet.version,
+            VERSION
+        );
+    }
+
+    let mut result = Import::default();
+    for (is_custom, entries) in [(false, snippet.characters), (true, snippet.sources)] {
+        for (name, [x, y, width, height]) in entries {
+            let known = if is_custom {
+                profile.custom_source_thumbnails.contains_key(&name)
+            } else {
+                profile.character_thumbnails.contains_key(&name)
+            };
+            if !known {
+                result.unknown.push(name);
+                continue;
+            }
+
+            let clamp_i16 = |v: i32| v.clamp(i16::MIN.into(), i16::MAX.into()) as i16;
+            let clamp_u16 = |v: i32| v.clamp(0, u16::MAX.into()) as u16;
+            let (x, y, width, height) = (
+                clamp_i16(x),
+                clamp_i16(y),
+                clamp_u16(width),
+                clamp_u16(height),
+            );
+            if profile.update_thumbnail_position(&name, x, y, width, height, is_custom) {
+                result.moves.push(LayoutMove {
+                    name,
+                    is_custom,
+                    x,
+                    y,
+                    width,
+                    height,
+                    finished: false,
+                });
+            } else {
+                result.unchanged += 1;
+            }
+        }
+    }
+    // One save for the whole import
+    if let Some(last) = result.moves.last_mut() {
+        last.finished = true;
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile_with(names: &[(&str, i16)]) -> Profile {
+        let mut profile = Profile::default_with_name("Test".to_string(), String::new());
+        for &(name, x) in names {
+            profile
+                .character_thumbnails
+                .insert(name.to_string(), CharacterSettings::new(x, 100, 480, 270));
+        }
+        profile
+    }
+
+    #[test]
+    fn test_export_import_round_trip() {
+        let source = profile_with(&[("Alice", 10), ("Bob", 500)]);
+        let snippet = export(&source);
+        assert_eq!(
+            snippet,
+            r#"{"eve-preview-layout":1,"characters":{"Alice":[10,100,480,270],"Bob":[500,100,480,270]},"sources":{}}"#
+        );
+
+        let mut target = profile_with(&[("Alice", 0), ("Bob", 500), ("Carol", 0)]);
+        let result = import(&mut target, &format!("  {snippet}\n")).unwrap();
+        assert_eq!(result.moves.len(), 1);
+        assert_eq!(result.moves[0].name, "Alice");
+        assert!(result.moves[0].finished);
+        assert_eq!(result.unchanged, 1);
+        assert!(result.unknown.is_empty());
+        assert_eq!(target.character_thumbnails["Alice"].x, 10);
+        assert_eq!(target.character_thumbnails["Carol"].x, 0);
+    }
+
+    #[test]
+    fn test_import_reports_unknown_and_invalid() {
+        let mut profile = profile_with(&[("Alice", 0)]);
+        let result = import(
+            &mut profile,
+            r#"{"eve-preview-layout":1,"characters":{"Zed":[1,2,3,4]},"sources":{"Discord":[0,0,100,100]}}"#,
+        )
+        .unwrap();
+        assert!(result.moves.is_empty());
+        assert_eq!(result.unknown, vec!["Zed", "Discord"]);
+
+        assert!(import(&mut profile, "not json").is_err());
+        assert!(import(&mut profile, r#"{"eve-preview-layout":2}"#).is_err());
+    }
+}
@@ -1,8 +1,12 @@
 //! Manager module - administrative interface for profile and daemon lifecycle
 
 mod app;
+mod autostart;
+mod cheat_sheet;
 pub mod components;
+mod diagnostics;
 mod key_capture;
+mod layout_snippet;
 pub mod state;
 pub mod utils;
 pub mod x11_utils;
@@ -1,3 +1,4 @@
+use std::collections::{BTreeSet, HashSet, VecDeque};
 use std::process::Child;
 use std::sync::mpsc::Receiver;
 use std::time::Instant;
@@ -6,12 +7,19 @@ use anyhow::{Context, Result};
 use tracing::{debug, error, info, warn};
 
 use crate::common::constants::manager_ui::*;
-use crate::common::ipc::{BootstrapMessage, ConfigMessage, DaemonMessage};
+use crate::common::ipc::{
+    BootstrapMessage, CharacterEvent, ConfigMessage, DaemonMessage, FocusTime, HotkeyLatency,
+};
+use crate::common::types::MonitorScale;
 use crate::config::DaemonConfig;
 use crate::config::profile::Config;
+use crate::manager::components::layout_editor::LayoutMove;
 use ipc_channel::ipc::{IpcReceiver, IpcSender};
 
-use super::{DaemonStatus, StatusMessage};
+use super::daemon_log::DaemonLog;
+use super::history::ProfileHistory;
+use super::toasts::Toasts;
+use super::{DaemonStatus, RestartBackoff, StatusMessage};
 
 /// Determines the behavior of `save_config`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -28,14 +36,43 @@ pub enum SaveMode {
 // Core application state shared between Manager and Tray
 pub struct SharedState {
     pub config: Config,
+    /// Monitors for thumbnails saved as monitor percentages, read at startup
+    pub monitors: Vec<MonitorScale>,
     pub debug_mode: bool,
     pub daemon: Option<Child>,
+    /// Connected to a standalone daemon (`--daemon-only`) that we did not spawn
+    pub external_daemon: bool,
     pub daemon_status: DaemonStatus,
+    /// When the current daemon process was started (for stability tracking)
+    pub daemon_started_at: Option<Instant>,
+    pub restart_backoff: RestartBackoff,
+    /// Crash count the user last acknowledged in the crash dialog
+    pub crash_dialog_acknowledged: u32,
+    /// Recent daemon output
+    pub daemon_log: DaemonLog,
     pub last_health_check: Instant,
     pub status_message: Option<StatusMessage>,
     pub config_status_message: Option<StatusMessage>,
     pub settings_changed: bool,
+    /// Daemon reports thumbnail positions that are not saved to disk yet
+    pub positions_dirty: bool,
+    /// Focus time per character in the daemon's session, most focused first
+    pub focus_stats: Vec<FocusTime>,
+    /// Hotkey switch latency the daemon last reported
+    pub hotkey_latency: Option<HotkeyLatency>,
+    /// Logins, logouts, swaps and closed clients since the Manager started, oldest first
+    pub character_events: VecDeque<CharacterEvent>,
+    /// Non-fatal daemon errors shown as toasts
+    pub toasts: Toasts,
+    /// Characters with a client the daemon currently tracks
+    pub detected_characters: BTreeSet<String>,
+    /// Characters whose thumbnails are hidden from the tray (session-only)
+    pub hidden_characters: HashSet<String>,
+    /// Characters the daemon skips while cycling, as it last reported
+    pub skipped_characters: BTreeSet<String>,
     pub selected_profile_idx: usize,
+    /// Undo/redo snapshots of the active profile
+    pub history: ProfileHistory,
     pub should_quit: bool,
     pub last_save_attempt: Instant,
 
@@ -52,7 +89,11 @@ pub struct SharedState {
 }
 
 impl SharedState {
-    pub fn new(config: Config, debug_mode: bool) -> Self {
+    pub fn new(mut config: Config, debug_mode: bool) -> Self {
+        let monitors = crate::manager::x11_utils::monitor_scales();
+        for profile in &mut config.profiles {
+            profile.apply_monitor_percent(&monitors);
+        }
         let selected_profile_idx = config
             .profiles
             .iter()
@@ -61,14 +102,29 @@ impl SharedState {
 
         Self {
             config,
+            monitors,
             debug_mode,
             daemon: None,
+            external_daemon: false,
             daemon_status: DaemonStatus::Stopped,
+            daemon_started_at: None,
+            restart_backoff: RestartBackoff::default(),
+            crash_dialog_acknowledged: 0,
+            daemon_log: DaemonLog::new(),
             last_health_check: Instant::now(),
             status_message: None,
             config_status_message: None,
             settings_changed: false,
+            positions_dirty: false,
+            focus_stats: Vec::new(),
+            hotkey_latency: None,
+            character_events: VecDeque::new(),
+            toasts: Toasts::default(),
+            detected_characters: BTreeSet::new(),
+            hidden_characters: HashSet::new(),
+            skipped_characters: BTreeSet::new(),
             selected_profile_idx,
+            history: ProfileHistory::default(),
             should_quit: false,
             last_save_attempt: Instant::now(),
 
@@ -109,36 +165,12 @@ impl SharedState {
                 custom_source_thumbnails = disk_profile.custom_source_thumbnails.clone();
             }
 
-            // Filter based on custom rules in profile.
-            let rules = &selected_profile.custom_windows;
-            let mut move_keys = Vec::new();
-            for key in character_thumbnails.keys() {
-                if rules.iter().any(|r| r.alias == *key) {
-                    move_keys.push(key.clone());
-                }
-            }
-
-            for key in move_keys {
-                if let Some(val) = character_thumbnails.remove(&key) {
-                    custom_source_thumbnails.insert(key, val);
-                }
-            }
-
-            // Build hotkeys for profile switching (requires looking at all profiles)
-            let mut profile_hotkeys = std::collections::HashMap::new();
-            for profile in &self.config.profiles {
-                if let Some(ref binding) = profile.hotkey_profile_switch {
-                    profile_hotkeys.insert(binding.clone(), profile.profile_name.clone());
-                }
-            }
-
-            let daemon_config = DaemonConfig {
-                profile: selected_profile,
+            let daemon_config = DaemonConfig::from_profile(
+                &self.config,
+                selected_profile,
                 character_thumbnails,
                 custom_source_thumbnails,
-                profile_hotkeys,
-                runtime_hidden: false,
-            };
+            );
 
             if let Err(e) = tx.send(ConfigMessage::Full(Box::new(daemon_config))) {
                 error!(error = %e, "Failed to send config update to daemon");
@@ -151,6 +183,8 @@ impl SharedState {
     }
 
     pub fn save_config(&mut self, mode: SaveMode) -> Result<()> {
+        self.sync_monitor_percent();
+
         // Prepare config for saving
         // If mode is IMPLICIT (e.g. on exit or settings change),
         // we must ensure we don't accidentally persist transient window movements for profiles
@@ -167,9 +201,7 @@ impl SharedState {
                             .iter()
                             .find(|p| p.profile_name == profile.profile_name)
                     {
-                        profile.character_thumbnails = disk_profile.character_thumbnails.clone();
-                        profile.custom_source_thumbnails =
-                            disk_profile.custom_source_thumbnails.clone();
+                        profile.restore_saved_thumbnails(disk_profile);
                     }
                 }
             } else {
@@ -202,9 +234,23 @@ impl SharedState {
         Ok(())
     }
 
+    /// Replace the in-memory config with one reviewed in the save preview, then save it
+    pub fn save_reviewed_config(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        self.history.invalidate();
+        self.selected_profile_idx = self
+            .config
+            .profiles
+            .iter()
+            .position(|p| p.profile_name == self.config.global.selected_profile)
+            .unwrap_or(0);
+        self.save_config(SaveMode::Explicit)
+    }
+
     /// Save config to disk WITHOUT syncing to daemon via IPC
     /// Used when the Daemon already knows about the change (e.g., it initiated the PositionChanged event)
     pub fn save_config_no_sync(&mut self, mode: SaveMode) -> Result<()> {
+        self.sync_monitor_percent();
         let mut config_to_save = self.config.clone();
 
         if mode == SaveMode::Implicit {
@@ -216,9 +262,7 @@ impl SharedState {
                             .iter()
                             .find(|p| p.profile_name == profile.profile_name)
                     {
-                        profile.character_thumbnails = disk_profile.character_thumbnails.clone();
-                        profile.custom_source_thumbnails =
-                            disk_profile.custom_source_thumbnails.clone();
+                        profile.restore_saved_thumbnails(disk_profile);
                     }
                 }
             } else {
@@ -267,8 +311,31 @@ impl SharedState {
         }
     }
 
+    /// Switch to the monitors the daemon previews. Thumbnails saved as monitor percentages
+    /// keep any edit made on the old monitors and take their geometry on the new ones.
+    pub fn set_monitors(&mut self, monitors: Vec<MonitorScale>) {
+        if monitors.is_empty() || monitors == self.monitors {
+            return;
+        }
+        self.sync_monitor_percent();
+        self.monitors = monitors;
+        for profile in &mut self.config.profiles {
+            profile.apply_monitor_percent(&self.monitors);
+        }
+    }
+
+    /// Re-measure the monitor percentages of thumbnails moved or resized since loading
+    fn sync_monitor_percent(&mut self) {
+        for profile in &mut self.config.profiles {
+            profile.sync_monitor_percent(&self.monitors);
+        }
+    }
+
     pub fn discard_changes(&mut self) {
         self.config = Config::load().unwrap_or_default();
+        for profile in &mut self.config.profiles {
+            profile.apply_monitor_percent(&self.monitors);
+        }
 
         // Re-find selected profile index after reload
         self.selected_profile_idx = self
@@ -279,6 +346,9 @@ impl SharedState {
             .unwrap_or(0);
 
         self.settings_changed = false;
+        self.history.clear();
+        // The daemon may be cycling in an unsaved order
+        self.push_cycle_order();
         self.config_status_message = Some(StatusMessage {
             text: "Changes discarded".to_string(),
             color: COLOR_ERROR,
@@ -286,6 +356,168 @@ impl SharedState {
         info!("Configuration changes discarded");
     }
 
+    /// Take the undo baseline of the active profile if it went stale
+    pub fn sync_profile_history(&mut self) {
+        if let Some(profile) = self.config.profiles.get(self.selected_profile_idx) {
+            self.history.sync(profile);
+        }
+    }
+
+    /// Record an edit made in the UI to the active profile
+    pub fn record_profile_edit(&mut self) {
+        if let Some(profile) = self.config.profiles.get(self.selected_profile_idx) {
+            self.history.commit(profile, Instant::now());
+        }
+    }
+
+    /// Undo the last profile edit; the result is unsaved like any other edit
+    pub fn undo_profile_edit(&mut self) {
+        if let Some(profile) = self.config.profiles.get_mut(self.selected_profile_idx)
+            && self.history.undo(profile)
+        {
+            self.settings_changed = true;
+            self.config_status_message = None;
+            self.push_cycle_order();
+        }
+    }
+
+    /// Re-apply the last undone profile edit
+    pub fn redo_profile_edit(&mut self) {
+        if let Some(profile) = self.config.profiles.get_mut(self.selected_profile_idx)
+            && self.history.redo(profile)
+        {
+            self.settings_changed = true;
+            self.config_status_message = None;
+            self.push_cycle_order();
+        }
+    }
+
+    /// Ask the daemon to revert the most recent thumbnail drag
+    pub fn undo_thumbnail_move(&self) {
+        if let Some(ref tx) = self.ipc_config_tx
+            && let Err(e) = tx.send(ConfigMessage::UndoMove)
+        {
+            error!(error = %e, "Failed to send undo request to daemon");
+        }
+    }
+
+    /// Ask the daemon to rescan all windows for clients it missed
+    pub fn rescan_windows(&self) {
+        if let Some(ref tx) = self.ipc_config_tx
+            && let Err(e) = tx.send(ConfigMessage::Rescan)
+        {
+            error!(error = %e, "Failed to send rescan request to daemon");
+        }
+    }
+
+    /// Send the active profile's cycle group order to the daemon, which cycles in it right
+    /// away (the edit itself still waits for a save)
+    pub fn push_cycle_order(&self) {
+        let Some(profile) = self.config.get_active_profile() else {
+            return;
+        };
+        let orders = crate::config::profile::CycleGroup::resolve_nested(&profile.cycle_groups)
+            .into_iter()
+            .map(|group| (group.name, group.cycle_list))
+            .collect();
+        if let Some(ref tx) = self.ipc_config_tx
+            && let Err(e) = tx.send(ConfigMessage::CycleOrder(orders))
+        {
+            error!(error = %e, "Failed to send cycle group order to daemon");
+        }
+    }
+
+    /// Hide or show a character's thumbnail for the rest of the session
+    pub fn set_character_hidden(&mut self, name: &str, hidden: bool) {
+        if hidden {
+            self.hidden_characters.insert(name.to_string());
+        } else {
+            self.hidden_characters.remove(name);
+        }
+        if let Some(ref tx) = self.ipc_config_tx
+            && let Err(e) = tx.send(ConfigMessage::SetCharacterHidden {
+                name: name.to_string(),
+                hidden,
+            })
+        {
+            error!(error = %e, character = name, "Failed to send thumbnail visibility to daemon");
+        }
+    }
+
+    /// Skip a character while cycling, or include it again
+    pub fn set_character_skipped(&self, name: &str, skipped: bool) {
+        if let Some(ref tx) = self.ipc_config_tx
+            && let Err(e) = tx.send(ConfigMessage::SetCharacterSkipped {
+                name: name.to_string(),
+                skipped,
+            })
+        {
+            error!(error = %e, character = name, "Failed to send skip status to daemon");
+        }
+    }
+
+    /// Keep the active profile's saved skip list in step with the daemon's skips while the
+    /// profile persists them (and empty otherwise)
+    pub fn persist_skipped_characters(&mut self) {
+        // Without a daemon the skips are unknown; the saved list is what it starts with
+        if self.ipc_config_tx.is_none() {
+            return;
+        }
+        let Some(profile) = self.config.profiles.get_mut(self.selected_profile_idx) else {
+            return;
+        };
+        let skipped: Vec<String> = if profile.hotkey_skip_persist {
+            self.skipped_characters.iter().cloned().collect()
+        } else {
+            Vec::new()
+        };
+        if profile.hotkey_skipped_characters == skipped {
+            return;
+        }
+        profile.hotkey_skipped_characters = skipped;
+        self.history.invalidate();
+        // Pending edits are saved together with the list later
+        if !self.settings_changed
+            && let Err(err) = self.save_config_no_sync(SaveMode::Implicit)
+        {
+            error!(error = ?err, "Failed to save skipped characters");
+        }
+    }
+
+    /// Send a thumbnail moved in the layout editor to the daemon.
+    /// Once the drag ends the move is saved (auto-save) or flagged as unsaved.
+    pub fn apply_layout_move(&mut self, moved: &LayoutMove) {
+        if let Some(ref tx) = self.ipc_config_tx
+            && let Err(e) = tx.send(ConfigMessage::ThumbnailMove {
+                name: moved.name.clone(),
+                is_custom: moved.is_custom,
+                x: moved.x,
+                y: moved.y,
+                width: moved.width,
+                height: moved.height,
+            })
+        {
+            error!(error = %e, "Failed to send thumbnail move to daemon");
+        }
+
+        if !moved.finished {
+            return;
+        }
+
+        let auto_save = self
+            .config
+            .get_active_profile()
+            .is_some_and(|p| p.thumbnail_auto_save_position);
+        if auto_save {
+            // Daemon already has the position
+            if let Err(e) = self.save_config_no_sync(SaveMode::Explicit) {
+                error!(error = ?e, "Failed to save layout change");
+            }
+        } else {
+            self.positions_dirty = true;
+        }
+    }
+
     pub fn save_thumbnail_positions(&mut self) -> Result<()> {
         self.save_config(SaveMode::Explicit)
             .context("Failed to save configuration")?;
@@ -373,4 +605,43 @@ mod tests {
             "Heartbeat should update timestamp"
         );
     }
+
+    #[test]
+    fn test_daemon_monitors_resolve_percentages() {
+        use crate::common::ipc::DaemonMessage;
+        use crate::common::types::{CharacterSettings, MonitorPercent, MonitorScale};
+        use std::sync::mpsc;
+
+        let monitor = |width, height| MonitorScale {
+            x: 0,
+            y: 0,
+            width,
+            height,
+            scale: 1.0,
+        };
+        let mut state = SharedState::new(Config::default(), false);
+        state.monitors = vec![monitor(2560, 1440)];
+        let mut settings = CharacterSettings::new(256, 144, 512, 288);
+        settings.monitor_percent =
+            MonitorPercent::measure(settings.position(), settings.dimensions, &state.monitors);
+        state.config.profiles[0]
+            .character_thumbnails
+            .insert("Alice".to_string(), settings);
+
+        let (tx, rx) = mpsc::channel();
+        state.daemon_status_rx = Some(rx);
+        tx.send(DaemonMessage::Monitors(vec![monitor(1920, 1080)]))
+            .unwrap();
+        state.poll_daemon();
+
+        let alice = &state.config.profiles[0].character_thumbnails["Alice"];
+        assert_eq!((alice.x, alice.y, alice.dimensions.width), (192, 108, 384));
+        // Saving doesn't turn the new pixels back into other percentages
+        let percent = alice.monitor_percent;
+        state.sync_monitor_percent();
+        assert_eq!(
+            state.config.profiles[0].character_thumbnails["Alice"].monitor_percent,
+            percent
+        );
+    }
 }
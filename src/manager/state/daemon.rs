@@ -1,11 +1,14 @@
 use anyhow::{Context, Result};
-use ipc_channel::ipc::IpcOneShotServer;
+use ipc_channel::ipc::{IpcOneShotServer, IpcSender};
 use std::sync::mpsc;
 use std::time::{Duration, Instant};
 use tracing::{debug, error, info, warn};
 
 use crate::common::constants::manager_ui::*;
-use crate::common::ipc::{BootstrapMessage, ConfigMessage, DaemonMessage};
+use crate::common::ipc::{
+    AttachMessage, BootstrapMessage, ConfigMessage, DaemonError, DaemonErrorKind, DaemonMessage,
+    attach_endpoint_path,
+};
 
 use super::core::SaveMode;
 use crate::manager::utils::spawn_daemon;
@@ -15,20 +18,74 @@ use super::SharedState;
 
 impl SharedState {
     pub fn start_daemon(&mut self) -> Result<()> {
-        if self.daemon.is_some() {
+        if self.daemon.is_some() || self.external_daemon {
             return Ok(());
         }
 
+        // Prefer a standalone daemon (e.g. systemd user service) if one is running
+        match self.attach_daemon() {
+            Ok(true) => return Ok(()),
+            Ok(false) => {}
+            Err(e) => warn!(error = ?e, "Failed to attach to running daemon, spawning a new one"),
+        }
+
         // 1. Create IPC OneShot Server
         let (server, server_name) =
             IpcOneShotServer::<BootstrapMessage>::new().context("Failed to create IPC server")?;
 
         // 2. Spawn Daemon with server name
-        let child = spawn_daemon(&server_name, self.debug_mode)?;
+        let mut child = spawn_daemon(&server_name, self.debug_mode, &self.config.global)?;
         let pid = child.id();
         debug!(pid, server_name = %server_name, "Started daemon process");
 
+        if let Some(stdout) = child.stdout.take() {
+            self.daemon_log.capture(stdout, false);
+        }
+        if let Some(stderr) = child.stderr.take() {
+            self.daemon_log.capture(stderr, true);
+        }
+
         // 3. Spawn thread to wait for connection (avoid blocking Manager)
+        self.wait_for_bootstrap(server);
+
+        self.daemon = Some(child);
+        self.daemon_status = DaemonStatus::Starting;
+        self.daemon_started_at = Some(Instant::now());
+        self.restart_backoff.cancel();
+        Ok(())
+    }
+
+    /// Attach to a standalone daemon advertised in the runtime directory.
+    ///
+    /// Returns `Ok(false)` if no daemon is advertised or the advertised endpoint is stale.
+    fn attach_daemon(&mut self) -> Result<bool> {
+        let endpoint_path = attach_endpoint_path();
+        let Ok(endpoint) = std::fs::read_to_string(&endpoint_path) else {
+            return Ok(false);
+        };
+
+        let attach_sender = match IpcSender::<AttachMessage>::connect(endpoint.trim().to_string()) {
+            Ok(sender) => sender,
+            Err(e) => {
+                debug!(path = ?endpoint_path, error = %e, "Ignoring stale daemon attach endpoint");
+                return Ok(false);
+            }
+        };
+
+        let (server, server_name) =
+            IpcOneShotServer::<BootstrapMessage>::new().context("Failed to create IPC server")?;
+        attach_sender
+            .send(server_name)
+            .context("Failed to send attach request to daemon")?;
+        info!("Attaching to running standalone daemon");
+
+        self.wait_for_bootstrap(server);
+        self.external_daemon = true;
+        self.daemon_status = DaemonStatus::Starting;
+        Ok(true)
+    }
+
+    fn wait_for_bootstrap(&mut self, server: IpcOneShotServer<BootstrapMessage>) {
         let (tx, rx) = mpsc::channel();
         self.bootstrap_rx = Some(rx);
 
@@ -44,20 +101,32 @@ impl SharedState {
                 }
             }
         });
-
-        self.daemon = Some(child);
-        self.daemon_status = DaemonStatus::Starting;
-        Ok(())
     }
 
     pub fn stop_daemon(&mut self) -> Result<()> {
+        if self.external_daemon {
+            // Never kill a daemon we did not spawn; dropping the channels detaches from it
+            info!("Detaching from standalone daemon");
+            self.external_daemon = false;
+            self.daemon_status = DaemonStatus::Stopped;
+            self.ipc_config_tx = None;
+            self.ipc_status_rx = None;
+            self.daemon_status_rx = None;
+            self.bootstrap_rx = None;
+            self.positions_dirty = false;
+            return Ok(());
+        }
+
         if let Some(mut child) = self.daemon.take() {
             info!(pid = child.id(), "Stopping daemon process");
 
-            if let Err(e) = child.kill() {
-                error!(pid = child.id(), error = %e, "Failed to send SIGKILL to daemon");
-            } else {
-                debug!(pid = child.id(), "SIGKILL sent successfully");
+            // Ask the daemon to exit so it can flush positions and release X11 resources
+            if !terminate_gracefully(&mut child) {
+                if let Err(e) = child.kill() {
+                    error!(pid = child.id(), error = %e, "Failed to send SIGKILL to daemon");
+                } else {
+                    debug!(pid = child.id(), "SIGKILL sent successfully");
+                }
             }
 
             match child.wait() {
@@ -78,6 +147,7 @@ impl SharedState {
             self.ipc_config_tx = None;
             self.ipc_status_rx = None;
             self.daemon_status_rx = None;
+            self.positions_dirty = false;
         }
         Ok(())
     }
@@ -94,6 +164,15 @@ impl SharedState {
     }
 
     pub fn reload_daemon_config(&mut self) {
+        if self.external_daemon && self.ipc_config_tx.is_some() {
+            // A standalone daemon outlives us, so push the new config instead of restarting it
+            info!("Config reload requested - syncing standalone daemon");
+            if let Err(err) = self.sync_to_daemon() {
+                error!(error = ?err, "Failed to sync config to standalone daemon");
+            }
+            return;
+        }
+
         info!("Config reload requested - restarting daemon");
         self.restart_daemon();
     }
@@ -122,6 +201,13 @@ impl SharedState {
             // Sync config to daemon
             let _ = self.sync_to_daemon();
 
+            // The daemon reports its clients again; carry hidden thumbnails over to it
+            self.detected_characters.clear();
+            self.skipped_characters.clear();
+            for name in self.hidden_characters.clone() {
+                self.set_character_hidden(&name, true);
+            }
+
             self.bootstrap_rx = None; // Done
             self.daemon_status = DaemonStatus::Running;
 
@@ -133,6 +219,8 @@ impl SharedState {
 
         // 2. Poll Status Messages
         let mut profile_switch_request = None;
+        let mut auto_included = false;
+        let mut skipped_changed = false;
 
         // Collect messages first to avoid holding an immutable borrow on self while calling mutable methods (save_config)
         let messages: Vec<DaemonMessage> = if let Some(ref rx) = self.daemon_status_rx {
@@ -149,9 +237,11 @@ impl SharedState {
             match msg {
                 DaemonMessage::Log { level, message } => {
                     info!(level = %level, "Daemon: {}", message);
+                    self.daemon_log.push(&format!("{} {}", level, message));
                 }
                 DaemonMessage::Error(e) => {
-                    error!("Daemon Error: {}", e);
+                    error!(kind = ?e.kind, "Daemon Error: {}", e.message);
+                    self.toasts.push(e, Instant::now());
                 }
                 DaemonMessage::Status(msg) => {
                     info!("Daemon Status: {}", msg);
@@ -167,16 +257,19 @@ impl SharedState {
                     width,
                     height,
                     is_custom,
+                    client_origin,
                 } => {
                     let mut changed = false;
                     if let Some(profile) = self.config.get_active_profile_mut() {
                         changed = profile
                             .update_thumbnail_position(&name, x, y, width, height, is_custom);
+                        changed |= profile.update_client_origin(&name, client_origin, is_custom);
                     }
 
                     if !changed {
                         continue;
                     }
+                    self.history.invalidate();
 
                     let auto_save = self
                         .config
@@ -187,9 +280,9 @@ impl SharedState {
                     debug!("Position changed: auto_save={}", auto_save);
 
                     if auto_save {
-                        // Debounce save: only write to disk if it's been at least 1 second since last attempt
+                        // Debounce save: only write to disk if the debounce delay passed since last attempt
                         if self.last_save_attempt.elapsed()
-                            > Duration::from_millis(AUTO_SAVE_DELAY_MS)
+                            > self.config.global.timing.save_debounce()
                         {
                             // Save to disk only (Daemon already has the correct position)
                             let _ = self.save_config_no_sync(SaveMode::Explicit);
@@ -219,12 +312,87 @@ impl SharedState {
                         info!("Daemon detected custom source: {}", name);
                     } else {
                         info!("Daemon detected character: {}", name);
+                        self.detected_characters.insert(name.clone());
+                        if let Some(profile) = self.config.get_active_profile_mut() {
+                            self.history.invalidate();
+                            let new = profile.is_new_character(&name);
+                            profile.mark_character_seen(&name, chrono::Utc::now().timestamp());
+                            if new && profile.auto_include_character(&name) {
+                                info!("Added new character {} to its cycle groups", name);
+                                auto_included = true;
+                            }
+                        }
                     }
                 }
+                DaemonMessage::CharacterLost { name } => {
+                    debug!("Daemon lost character: {}", name);
+                    self.detected_characters.remove(&name);
+                }
                 DaemonMessage::RequestProfileSwitch(name) => {
                     info!("Daemon requested profile switch: {}", name);
                     profile_switch_request = Some(name);
                 }
+                DaemonMessage::SkippedCharacters(names) => {
+                    debug!(count = names.len(), "Daemon reported skipped characters");
+                    self.skipped_characters = names.into_iter().collect();
+                    skipped_changed = true;
+                }
+                DaemonMessage::Monitors(monitors) => {
+                    debug!(count = monitors.len(), "Daemon reported its monitors");
+                    self.set_monitors(monitors);
+                }
+                DaemonMessage::SavePositions {
+                    profile,
+                    character_thumbnails,
+                    custom_source_thumbnails,
+                } => {
+                    // The daemon leaves config.json to us while we are attached. Merging into
+                    // the file keeps unsaved edits in the editor out of it.
+                    if let Some(current) = self
+                        .config
+                        .profiles
+                        .iter_mut()
+                        .find(|p| p.profile_name == profile)
+                    {
+                        current.merge_thumbnail_positions(
+                            &character_thumbnails,
+                            &custom_source_thumbnails,
+                        );
+                        self.history.invalidate();
+                    }
+                    if let Err(e) = crate::config::profile::Config::save_thumbnail_positions(
+                        &crate::config::profile::Config::path(),
+                        &profile,
+                        &character_thumbnails,
+                        &custom_source_thumbnails,
+                        &self.monitors,
+                    ) {
+                        error!(error = ?e, "Failed to save daemon thumbnail positions");
+                        self.toasts.push(
+                            DaemonError::new(
+                                DaemonErrorKind::Save,
+                                format!("Failed to save thumbnail positions: {e:#}"),
+                            ),
+                            Instant::now(),
+                        );
+                    }
+                }
+                DaemonMessage::PositionsDirty(dirty) => {
+                    debug!(dirty, "Daemon reported unsaved positions state");
+                    self.positions_dirty = dirty;
+                }
+                DaemonMessage::FocusStats(stats) => {
+                    self.focus_stats = stats;
+                }
+                DaemonMessage::HotkeyLatency(latency) => {
+                    self.hotkey_latency = Some(latency);
+                }
+                DaemonMessage::CharacterEvent(event) => {
+                    if self.character_events.len() >= CHARACTER_HISTORY_CAPACITY {
+                        self.character_events.pop_front();
+                    }
+                    self.character_events.push_back(event);
+                }
                 DaemonMessage::Heartbeat => {
                     self.ipc_healthy = true;
                     self.last_heartbeat = Instant::now();
@@ -233,6 +401,21 @@ impl SharedState {
             }
         }
 
+        if auto_included {
+            if self.settings_changed {
+                // Leave the user's pending edits unsaved; the daemon still cycles the new member
+                if let Err(err) = self.sync_to_daemon() {
+                    warn!(error = ?err, "Failed to sync auto-included character to daemon");
+                }
+            } else if let Err(err) = self.save_config(SaveMode::Implicit) {
+                error!(error = ?err, "Failed to save auto-included character");
+            }
+        }
+
+        if skipped_changed {
+            self.persist_skipped_characters();
+        }
+
         if let Some(name) = profile_switch_request {
             if let Some(idx) = self
                 .config
@@ -248,7 +431,7 @@ impl SharedState {
 
         // IPC Health Check
         // If connected but no heartbeat for 15s (5s grace * 3), assume hung process
-        if self.daemon.is_some()
+        if (self.daemon.is_some() || self.external_daemon)
             && self.ipc_healthy
             && self.last_heartbeat.elapsed() > Duration::from_secs(5)
         {
@@ -256,19 +439,26 @@ impl SharedState {
             if self.daemon_status == DaemonStatus::Running {
                 self.missed_heartbeats += 1;
 
-                // We poll roughly every DAEMON_CHECK_INTERVAL_MS (500ms).
+                // We poll roughly every `timing.event_poll_ms` (500ms by default).
                 // So wait 30 ticks (15s) or just use time elapsed.
                 // Actually, simpler to just check total elapsed time since last beat.
                 if self.last_heartbeat.elapsed() > Duration::from_secs(15) {
-                    warn!("IPC appears unhealthy (no heartbeat for 15s), restarting daemon");
                     self.ipc_healthy = false;
-                    self.restart_daemon();
+                    if self.external_daemon {
+                        warn!("IPC appears unhealthy (no heartbeat for 15s), re-attaching daemon");
+                        self.restart_daemon();
+                    } else {
+                        warn!("IPC appears unhealthy (no heartbeat for 15s), killing hung daemon");
+                        let _ = self.stop_daemon();
+                        self.daemon_status = DaemonStatus::Crashed(None);
+                        self.schedule_restart();
+                    }
                     return; // Restart will reset everything
                 }
             }
         }
 
-        if self.last_health_check.elapsed() < Duration::from_millis(DAEMON_CHECK_INTERVAL_MS) {
+        if self.last_health_check.elapsed() < self.config.global.timing.health_check() {
             return;
         }
         self.last_health_check = Instant::now();
@@ -286,12 +476,74 @@ impl SharedState {
                     self.ipc_config_tx = None;
                     self.ipc_status_rx = None;
                     self.daemon_status_rx = None;
+                    self.bootstrap_rx = None;
+                    self.positions_dirty = false;
+                    if !status.success() {
+                        self.schedule_restart();
+                    }
+                }
+                Ok(None) => {
+                    // Forgive earlier crashes once the daemon has been up for a while
+                    if self
+                        .daemon_started_at
+                        .is_some_and(|t| t.elapsed() > Duration::from_secs(DAEMON_STABLE_RUN_SECS))
+                    {
+                        self.restart_backoff.record_stable();
+                    }
                 }
-                Ok(None) => {}
                 Err(err) => {
                     error!(error = ?err, "Failed to query daemon status");
                 }
             }
         }
+
+        // Supervised restart once the backoff delay has elapsed
+        if self.daemon.is_none()
+            && !self.external_daemon
+            && self.restart_backoff.is_due(Instant::now())
+        {
+            info!(
+                crashes = self.restart_backoff.crash_count,
+                "Restarting daemon after crash"
+            );
+            if let Err(err) = self.start_daemon() {
+                error!(error = ?err, "Automatic daemon restart failed");
+                self.schedule_restart();
+            }
+        }
+    }
+
+    /// Record a daemon crash and schedule an automatic restart with backoff
+    fn schedule_restart(&mut self) {
+        self.daemon_started_at = None;
+        let delay = self.restart_backoff.record_crash(Instant::now());
+        warn!(
+            crashes = self.restart_backoff.crash_count,
+            delay_ms = delay.as_millis() as u64,
+            "Daemon crashed, scheduling restart"
+        );
+    }
+}
+
+/// Send SIGTERM and wait briefly for the daemon to exit.
+/// Returns false if it is still running afterwards.
+fn terminate_gracefully(child: &mut std::process::Child) -> bool {
+    use nix::sys::signal::{Signal, kill};
+    use nix::unistd::Pid;
+
+    if let Err(e) = kill(Pid::from_raw(child.id() as i32), Signal::SIGTERM) {
+        warn!(pid = child.id(), error = %e, "Failed to send SIGTERM to daemon");
+        return false;
+    }
+
+    let deadline = Instant::now() + Duration::from_millis(DAEMON_SHUTDOWN_TIMEOUT_MS);
+    while Instant::now() < deadline {
+        match child.try_wait() {
+            Ok(Some(_)) => return true,
+            Ok(None) => std::thread::sleep(Duration::from_millis(50)),
+            Err(_) => return false,
+        }
     }
+    warn!(pid = child.id(), "Daemon did not exit after SIGTERM");
+    false
 }
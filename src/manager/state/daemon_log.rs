@@ -0,0 +1,165 @@
+//! In-memory capture of the daemon's log output
+
+use std::collections::VecDeque;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::sync::{Arc, Mutex};
+
+use crate::common::constants::manager_ui::DAEMON_LOG_CAPACITY;
+
+/// Ring buffer of recent daemon log lines, shared with the capture thread
+#[derive(Clone, Default)]
+pub struct DaemonLog {
+    lines: Arc<Mutex<VecDeque<String>>>,
+}
+
+impl DaemonLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a line, dropping the oldest once the buffer is full
+    pub fn push(&self, line: &str) {
+        if let Ok(mut lines) = self.lines.lock() {
+            if lines.len() >= DAEMON_LOG_CAPACITY {
+                lines.pop_front();
+            }
+            lines.push_back(strip_ansi(line));
+        }
+    }
+
+    /// Copy of the buffered lines, oldest first
+    pub fn snapshot(&self) -> Vec<String> {
+        self.lines
+            .lock()
+            .map(|lines| lines.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Drop all buffered lines
+    pub fn clear(&self) {
+        if let Ok(mut lines) = self.lines.lock() {
+            lines.clear();
+        }
+    }
+
+    /// Forward one of the daemon's output streams to ours while keeping a copy in the buffer
+    pub fn capture<R: Read + Send + 'static>(&self, stream: R, to_stderr: bool) {
+        let log = self.clone();
+        std::thread::spawn(move || {
+            let reader = BufReader::new(stream);
+            for line in reader.lines() {
+                let Ok(line) = line else { break };
+                let _ = if to_stderr {
+                    writeln!(std::io::stderr(), "{}", line)
+                } else {
+                    writeln!(std::io::stdout(), "{}", line)
+                };
+                log.push(&line);
+            }
+        });
+    }
+}
+
+/// Severity of a captured log line, ordered from most to least verbose
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    pub const ALL: [LogLevel; 5] = [
+        LogLevel::Trace,
+        LogLevel::Debug,
+        LogLevel::Info,
+        LogLevel::Warn,
+        LogLevel::Error,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            LogLevel::Trace => "TRACE",
+            LogLevel::Debug => "DEBUG",
+            LogLevel::Info => "INFO",
+            LogLevel::Warn => "WARN",
+            LogLevel::Error => "ERROR",
+        }
+    }
+
+    /// Detect the level of a tracing fmt line (`<timestamp>  INFO target: message`).
+    ///
+    /// Only the first few tokens are inspected so messages mentioning a level are not misread.
+    pub fn parse_line(line: &str) -> Option<Self> {
+        line.split_whitespace().take(3).find_map(|token| {
+            Self::ALL
+                .into_iter()
+                .find(|level| token.eq_ignore_ascii_case(level.label()))
+        })
+    }
+}
+
+/// Remove ANSI color escape sequences emitted by the tracing formatter
+fn strip_ansi(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' && chars.peek() == Some(&'[') {
+            chars.next();
+            // Skip parameters until the final byte of the CSI sequence
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_ansi() {
+        assert_eq!(
+            strip_ansi("\u{1b}[2m2024\u{1b}[0m \u{1b}[32m INFO\u{1b}[0m started"),
+            "2024  INFO started"
+        );
+        assert_eq!(strip_ansi("plain text"), "plain text");
+    }
+
+    #[test]
+    fn test_capacity_drops_oldest() {
+        let log = DaemonLog::new();
+        for i in 0..DAEMON_LOG_CAPACITY + 5 {
+            log.push(&format!("line {}", i));
+        }
+        let lines = log.snapshot();
+        assert_eq!(lines.len(), DAEMON_LOG_CAPACITY);
+        assert_eq!(lines[0], "line 5");
+    }
+
+    #[test]
+    fn test_parse_log_level() {
+        assert_eq!(
+            LogLevel::parse_line("2025-01-01T00:00:00.000Z  WARN eve_preview_manager: hi"),
+            Some(LogLevel::Warn)
+        );
+        assert_eq!(
+            LogLevel::parse_line("2025-01-01T00:00:00.000Z ERROR x: failed"),
+            Some(LogLevel::Error)
+        );
+        // Level words later in the message are ignored
+        assert_eq!(
+            LogLevel::parse_line("Daemon error: something about INFO"),
+            None
+        );
+        assert!(LogLevel::Debug < LogLevel::Info);
+    }
+}
@@ -0,0 +1,221 @@
+//! Undo/redo of profile edits made in the manager
+//!
+//! Each entry is a full snapshot of the active profile taken before an edit. The snapshot is
+//! the profile as of the last recorded edit (the baseline), so nothing is cloned on frames
+//! without edits. Changes made outside the tabs (e.g. by the daemon) invalidate the baseline,
+//! and it is taken again on the next frame. Edits arriving in quick succession (slider drags,
+//! typing) collapse into one entry. Switching to another profile starts a fresh history.
+
+use std::time::{Duration, Instant};
+
+use crate::config::profile::Profile;
+
+/// Snapshots kept per direction
+const HISTORY_LIMIT: usize = 50;
+
+/// Edits closer together than this become a single undo step
+const COALESCE_WINDOW: Duration = Duration::from_millis(750);
+
+#[derive(Default)]
+pub struct ProfileHistory {
+    /// Profile the snapshots belong to
+    profile_name: String,
+    undo: Vec<Profile>,
+    redo: Vec<Profile>,
+    last_edit: Option<Instant>,
+    /// The active profile before the next edit
+    baseline: Option<Profile>,
+    /// A layout drag is in progress; the baseline is kept from before it started
+    pinned: bool,
+}
+
+impl ProfileHistory {
+    /// Take the baseline from `profile` unless it is still current. Called before the tabs
+    /// can edit the profile.
+    pub fn sync(&mut self, profile: &Profile) {
+        if self
+            .baseline
+            .as_ref()
+            .is_none_or(|baseline| baseline.profile_name != profile.profile_name)
+        {
+            self.baseline = Some(profile.clone());
+            self.pinned = false;
+        }
+    }
+
+    /// The profile changed outside an edit; the baseline is taken again on the next sync
+    pub fn invalidate(&mut self) {
+        if !self.pinned {
+            self.baseline = None;
+        }
+    }
+
+    /// Keep the baseline until the next commit (a layout drag in progress)
+    pub fn pin(&mut self) {
+        self.pinned = self.baseline.is_some();
+    }
+
+    /// The profile as it was before the edits of this frame
+    pub fn baseline(&self) -> Option<&Profile> {
+        self.baseline.as_ref()
+    }
+
+    /// Record the baseline as the state before an edit made at `now`, which left the profile
+    /// as `profile`
+    pub fn commit(&mut self, profile: &Profile, now: Instant) {
+        if let Some(before) = self.baseline.take() {
+            self.record(before, now);
+        }
+        self.baseline = Some(profile.clone());
+        self.pinned = false;
+    }
+
+    /// Record `before`, the profile as it was before an edit made at `now`
+    fn record(&mut self, before: Profile, now: Instant) {
+        if before.profile_name != self.profile_name {
+            self.clear();
+            self.profile_name = before.profile_name.clone();
+        }
+
+        let coalesce = self
+            .last_edit
+            .is_some_and(|last| now.saturating_duration_since(last) < COALESCE_WINDOW);
+        self.last_edit = Some(now);
+        self.redo.clear();
+        if coalesce && !self.undo.is_empty() {
+            return;
+        }
+
+        self.undo.push(before);
+        if self.undo.len() > HISTORY_LIMIT {
+            self.undo.remove(0);
+        }
+    }
+
+    pub fn can_undo(&self, profile: &Profile) -> bool {
+        profile.profile_name == self.profile_name && !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self, profile: &Profile) -> bool {
+        profile.profile_name == self.profile_name && !self.redo.is_empty()
+    }
+
+    /// Restore the snapshot before the last edit. Returns false if there is nothing to undo.
+    pub fn undo(&mut self, profile: &mut Profile) -> bool {
+        if !self.can_undo(profile) {
+            return false;
+        }
+        let previous = self.undo.pop().expect("checked by can_undo");
+        self.redo.push(std::mem::replace(profile, previous));
+        self.last_edit = None;
+        self.baseline = None;
+        true
+    }
+
+    /// Re-apply the last undone edit. Returns false if there is nothing to redo.
+    pub fn redo(&mut self, profile: &mut Profile) -> bool {
+        if !self.can_redo(profile) {
+            return false;
+        }
+        let next = self.redo.pop().expect("checked by can_redo");
+        self.undo.push(std::mem::replace(profile, next));
+        self.last_edit = None;
+        self.baseline = None;
+        true
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.last_edit = None;
+        self.baseline = None;
+        self.pinned = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(opacity: u8) -> Profile {
+        let mut profile = Profile::default_with_name("Test".to_string(), String::new());
+        profile.thumbnail_opacity = opacity;
+        profile
+    }
+
+    #[test]
+    fn test_undo_redo_round_trip() {
+        let mut history = ProfileHistory::default();
+        let start = Instant::now();
+        let mut current = profile(10);
+
+        history.record(current.clone(), start);
+        current.thumbnail_opacity = 20;
+        history.record(current.clone(), start + COALESCE_WINDOW * 2);
+        current.thumbnail_opacity = 30;
+
+        assert!(history.undo(&mut current));
+        assert_eq!(current.thumbnail_opacity, 20);
+        assert!(history.undo(&mut current));
+        assert_eq!(current.thumbnail_opacity, 10);
+        assert!(!history.undo(&mut current));
+
+        assert!(history.redo(&mut current));
+        assert!(history.redo(&mut current));
+        assert_eq!(current.thumbnail_opacity, 30);
+        assert!(!history.redo(&mut current));
+    }
+
+    #[test]
+    fn test_rapid_edits_coalesce() {
+        let mut history = ProfileHistory::default();
+        let start = Instant::now();
+        let mut current = profile(10);
+
+        for step in 0..5u8 {
+            history.record(
+                current.clone(),
+                start + Duration::from_millis(step as u64 * 50),
+            );
+            current.thumbnail_opacity += 10;
+        }
+
+        assert!(history.undo(&mut current));
+        assert_eq!(current.thumbnail_opacity, 10);
+        assert!(!history.undo(&mut current));
+    }
+
+    #[test]
+    fn test_commit_records_baseline_and_pin_keeps_it() {
+        let mut history = ProfileHistory::default();
+        let start = Instant::now();
+        let mut current = profile(10);
+        history.sync(&current);
+
+        current.thumbnail_opacity = 20;
+        history.commit(&current, start);
+        assert_eq!(history.baseline().map(|p| p.thumbnail_opacity), Some(20));
+
+        // A drag in progress keeps the state from before it across daemon updates
+        history.pin();
+        current.thumbnail_opacity = 30;
+        history.invalidate();
+        history.sync(&current);
+        history.commit(&current, start + COALESCE_WINDOW * 2);
+
+        assert!(history.undo(&mut current));
+        assert_eq!(current.thumbnail_opacity, 20);
+        assert!(history.undo(&mut current));
+        assert_eq!(current.thumbnail_opacity, 10);
+    }
+
+    #[test]
+    fn test_other_profile_is_not_affected() {
+        let mut history = ProfileHistory::default();
+        history.record(profile(10), Instant::now());
+
+        let mut other = Profile::default_with_name("Other".to_string(), String::new());
+        assert!(!history.can_undo(&other));
+        assert!(!history.undo(&mut other));
+    }
+}
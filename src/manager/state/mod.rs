@@ -1,5 +1,8 @@
 pub mod core;
 pub mod daemon;
+pub mod daemon_log;
+pub mod history;
+pub mod toasts;
 pub mod types;
 
 pub use self::core::SharedState;
@@ -0,0 +1,101 @@
+//! Non-fatal daemon errors shown as toasts in the manager
+//!
+//! A repeat of an error already on screen bumps that toast's counter instead of stacking
+//! another one. Toasts expire a while after their last repeat or when dismissed.
+
+use std::time::{Duration, Instant};
+
+use crate::common::ipc::DaemonError;
+
+/// Toasts shown at once; the oldest is dropped beyond this
+const MAX_TOASTS: usize = 5;
+
+/// Time a toast stays after its last repeat
+const TOAST_LIFETIME: Duration = Duration::from_secs(20);
+
+pub struct Toast {
+    pub error: DaemonError,
+    /// Times the error was reported while the toast was shown
+    pub count: u32,
+    last: Instant,
+}
+
+#[derive(Default)]
+pub struct Toasts {
+    /// Oldest first
+    toasts: Vec<Toast>,
+}
+
+impl Toasts {
+    /// Show `error` reported at `now`, counting it on an existing toast for the same error
+    pub fn push(&mut self, error: DaemonError, now: Instant) {
+        let toast = match self.toasts.iter().position(|t| t.error == error) {
+            Some(index) => {
+                let mut toast = self.toasts.remove(index);
+                toast.count += 1;
+                toast.last = now;
+                toast
+            }
+            None => Toast {
+                error,
+                count: 1,
+                last: now,
+            },
+        };
+        self.toasts.push(toast);
+        if self.toasts.len() > MAX_TOASTS {
+            self.toasts.remove(0);
+        }
+    }
+
+    /// Drop toasts whose error was last reported too long before `now`
+    pub fn expire(&mut self, now: Instant) {
+        self.toasts
+            .retain(|t| now.saturating_duration_since(t.last) < TOAST_LIFETIME);
+    }
+
+    pub fn dismiss(&mut self, index: usize) {
+        if index < self.toasts.len() {
+            self.toasts.remove(index);
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Toast> {
+        self.toasts.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.toasts.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::ipc::DaemonErrorKind;
+
+    fn error(message: &str) -> DaemonError {
+        DaemonError::new(DaemonErrorKind::Save, message)
+    }
+
+    #[test]
+    fn test_repeats_are_counted_and_expire() {
+        let start = Instant::now();
+        let mut toasts = Toasts::default();
+        toasts.push(error("disk full"), start);
+        toasts.push(error("read-only"), start);
+        toasts.push(error("disk full"), start + Duration::from_secs(15));
+
+        let shown: Vec<(&str, u32)> = toasts
+            .iter()
+            .map(|t| (t.error.message.as_str(), t.count))
+            .collect();
+        assert_eq!(shown, [("read-only", 1), ("disk full", 2)]);
+
+        // The repeat kept "disk full" around
+        toasts.expire(start + TOAST_LIFETIME + Duration::from_secs(1));
+        assert_eq!(toasts.iter().count(), 1);
+        toasts.dismiss(0);
+        assert!(toasts.is_empty());
+    }
+}
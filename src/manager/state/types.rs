@@ -1,13 +1,65 @@
 use crate::common::constants::manager_ui::*;
+use crate::common::i18n::{tr, tr_with};
 use eframe::egui;
+use std::time::{Duration, Instant};
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub enum ManagerTab {
     Behavior,
     Appearance,
     Hotkeys,
     Characters,
     Sources,
+    Layout,
+    Diagnostics,
+    Stats,
+    Logs,
+}
+
+impl ManagerTab {
+    /// Tabs in header order, left to right
+    pub const ALL: [ManagerTab; 9] = [
+        ManagerTab::Behavior,
+        ManagerTab::Hotkeys,
+        ManagerTab::Appearance,
+        ManagerTab::Characters,
+        ManagerTab::Sources,
+        ManagerTab::Layout,
+        ManagerTab::Diagnostics,
+        ManagerTab::Stats,
+        ManagerTab::Logs,
+    ];
+
+    /// Stable name used to persist the tab in `GlobalSettings`
+    pub fn key(&self) -> &'static str {
+        match self {
+            ManagerTab::Behavior => "behavior",
+            ManagerTab::Appearance => "appearance",
+            ManagerTab::Hotkeys => "hotkeys",
+            ManagerTab::Characters => "characters",
+            ManagerTab::Sources => "sources",
+            ManagerTab::Layout => "layout",
+            ManagerTab::Diagnostics => "diagnostics",
+            ManagerTab::Stats => "stats",
+            ManagerTab::Logs => "logs",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<ManagerTab> {
+        Self::ALL.into_iter().find(|tab| tab.key() == key)
+    }
+
+    /// Neighbouring tab in header order, wrapping around
+    pub fn cycle(&self, forward: bool) -> ManagerTab {
+        let idx = Self::ALL.iter().position(|t| t == self).unwrap_or(0);
+        let len = Self::ALL.len();
+        let next = if forward {
+            (idx + 1) % len
+        } else {
+            (idx + len - 1) % len
+        };
+        Self::ALL[next]
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -29,12 +81,12 @@ impl DaemonStatus {
 
     pub fn label(&self) -> String {
         match self {
-            DaemonStatus::Running => "Daemon running".to_string(),
-            DaemonStatus::Starting => "Daemon starting...".to_string(),
-            DaemonStatus::Stopped => "Daemon stopped".to_string(),
+            DaemonStatus::Running => tr("daemon-running"),
+            DaemonStatus::Starting => tr("daemon-starting"),
+            DaemonStatus::Stopped => tr("daemon-stopped"),
             DaemonStatus::Crashed(code) => match code {
-                Some(code) => format!("Daemon crashed (exit {code})"),
-                None => "Daemon crashed".to_string(),
+                Some(code) => tr_with("daemon-crashed-exit", &[("code", code.to_string())]),
+                None => tr("daemon-crashed"),
             },
         }
     }
@@ -44,3 +96,116 @@ pub struct StatusMessage {
     pub text: String,
     pub color: egui::Color32,
 }
+
+/// Exponential backoff for supervised daemon restarts
+#[derive(Debug, Default)]
+pub struct RestartBackoff {
+    /// Total crashes observed this session
+    pub crash_count: u32,
+    /// Crashes since the daemon last ran stably (drives the delay)
+    consecutive_crashes: u32,
+    /// When the next automatic restart is due
+    pub next_restart: Option<Instant>,
+}
+
+impl RestartBackoff {
+    /// Record a crash and schedule the next restart, returning the chosen delay
+    pub fn record_crash(&mut self, now: Instant) -> Duration {
+        self.crash_count += 1;
+        self.consecutive_crashes += 1;
+        let delay = Self::delay_for(self.consecutive_crashes);
+        self.next_restart = Some(now + delay);
+        delay
+    }
+
+    /// The daemon has been running long enough to consider it healthy again
+    pub fn record_stable(&mut self) {
+        self.consecutive_crashes = 0;
+    }
+
+    /// Whether a scheduled restart should happen now
+    pub fn is_due(&self, now: Instant) -> bool {
+        self.next_restart.is_some_and(|at| now >= at)
+    }
+
+    /// Clear any pending restart (e.g. after a manual start)
+    pub fn cancel(&mut self) {
+        self.next_restart = None;
+    }
+
+    fn delay_for(consecutive_crashes: u32) -> Duration {
+        let exponent = consecutive_crashes.saturating_sub(1).min(16);
+        let delay_ms = DAEMON_RESTART_BASE_DELAY_MS.saturating_mul(1 << exponent);
+        Duration::from_millis(delay_ms.min(DAEMON_RESTART_MAX_DELAY_MS))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_doubles_and_caps() {
+        let mut backoff = RestartBackoff::default();
+        let now = Instant::now();
+
+        assert_eq!(
+            backoff.record_crash(now),
+            Duration::from_millis(DAEMON_RESTART_BASE_DELAY_MS)
+        );
+        assert_eq!(
+            backoff.record_crash(now),
+            Duration::from_millis(DAEMON_RESTART_BASE_DELAY_MS * 2)
+        );
+        for _ in 0..20 {
+            backoff.record_crash(now);
+        }
+        assert_eq!(
+            backoff.record_crash(now),
+            Duration::from_millis(DAEMON_RESTART_MAX_DELAY_MS)
+        );
+        assert_eq!(backoff.crash_count, 23);
+    }
+
+    #[test]
+    fn test_backoff_resets_after_stable_run() {
+        let mut backoff = RestartBackoff::default();
+        let now = Instant::now();
+        backoff.record_crash(now);
+        backoff.record_crash(now);
+        backoff.record_stable();
+
+        assert_eq!(
+            backoff.record_crash(now),
+            Duration::from_millis(DAEMON_RESTART_BASE_DELAY_MS)
+        );
+        // Total count is kept for the crash dialog
+        assert_eq!(backoff.crash_count, 3);
+    }
+
+    #[test]
+    fn test_backoff_due() {
+        let mut backoff = RestartBackoff::default();
+        let now = Instant::now();
+        assert!(!backoff.is_due(now));
+
+        let delay = backoff.record_crash(now);
+        assert!(!backoff.is_due(now));
+        assert!(backoff.is_due(now + delay));
+
+        backoff.cancel();
+        assert!(!backoff.is_due(now + delay));
+    }
+
+    #[test]
+    fn test_manager_tab_keys_and_cycle() {
+        for tab in ManagerTab::ALL {
+            assert_eq!(ManagerTab::from_key(tab.key()), Some(tab));
+        }
+        assert_eq!(ManagerTab::from_key(""), None);
+
+        assert_eq!(ManagerTab::Behavior.cycle(true), ManagerTab::Hotkeys);
+        assert_eq!(ManagerTab::Behavior.cycle(false), ManagerTab::Logs);
+        assert_eq!(ManagerTab::Logs.cycle(true), ManagerTab::Behavior);
+    }
+}
@@ -1,6 +1,9 @@
 use anyhow::{Context, Result, anyhow};
 use std::io::Cursor;
-use std::process::{Child, Command};
+use std::process::{Child, Command, Stdio};
+
+use crate::common::logging;
+use crate::config::profile::{Config, GlobalSettings};
 
 #[cfg(target_os = "linux")]
 pub fn load_tray_icon_pixmap() -> Result<ksni::Icon> {
@@ -85,7 +88,7 @@ pub fn load_window_icon() -> Result<egui::IconData> {
     })
 }
 
-pub fn spawn_daemon(ipc_server_name: &str, debug: bool) -> Result<Child> {
+pub fn spawn_daemon(ipc_server_name: &str, debug: bool, global: &GlobalSettings) -> Result<Child> {
     let exe_path = std::env::current_exe().context("Failed to resolve executable path")?;
     let mut command = Command::new(exe_path);
     command
@@ -93,9 +96,27 @@ pub fn spawn_daemon(ipc_server_name: &str, debug: bool) -> Result<Child> {
         .arg("--ipc-server")
         .arg(ipc_server_name);
 
+    // The daemon writes positions back to the config file itself
+    if let Some(path) = Config::path_override() {
+        command.arg("--config").arg(path);
+    }
+
+    // The daemon does not read logging settings from the config file, forward them.
+    // `--debug` and an inherited RUST_LOG take precedence over the configured level.
     if debug {
         command.arg("--debug");
+    } else if std::env::var_os("RUST_LOG").is_none()
+        && logging::LEVELS.contains(&global.log_level.as_str())
+    {
+        command.arg("--log-level").arg(&global.log_level);
     }
+    if global.log_to_file {
+        command.arg("--log-file");
+    }
+
+    // Capture output so the Manager can show the daemon log
+    command.stdout(Stdio::piped());
+    command.stderr(Stdio::piped());
 
     command.spawn().context("Failed to spawn daemon process")
 }
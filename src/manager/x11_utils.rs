@@ -1,13 +1,19 @@
 use anyhow::{Context, Result};
 use x11rb::connection::Connection;
+use x11rb::protocol::randr::ConnectionExt as _;
 use x11rb::protocol::xproto::{AtomEnum, ConnectionExt, Window};
 
+use crate::common::process::process_names;
+use crate::common::types::MonitorScale;
+
 #[derive(Clone, Debug)]
 pub struct WindowInfo {
     #[allow(dead_code)]
     pub id: Window,
     pub title: String,
     pub class: String,
+    /// Names of the owning process (from `_NET_WM_PID`), for process-based rules
+    pub processes: Vec<String>,
 }
 
 pub fn get_running_applications() -> Result<Vec<WindowInfo>> {
@@ -33,6 +39,12 @@ pub fn get_running_applications() -> Result<Vec<WindowInfo>> {
         .context("Failed to intern _NET_WM_NAME")?
         .atom;
 
+    let net_wm_pid = conn
+        .intern_atom(false, b"_NET_WM_PID")?
+        .reply()
+        .context("Failed to intern _NET_WM_PID")?
+        .atom;
+
     // Get list of windows
     let reply = conn
         .get_property(
@@ -96,11 +108,22 @@ pub fn get_running_applications() -> Result<Vec<WindowInfo>> {
             if !class.is_empty() && !title.is_empty() {
                 // Determine if we should show it
                 // Skip EVE Preview Manager itself?
-                if class != "eve-preview-manager" && class != "com.evepreview.manager" {
+                if class != "eve-preview-manager"
+                    && class != "com.evepreview.manager"
+                    && class != crate::common::constants::x11::THUMBNAIL_CLASS
+                {
+                    let processes = conn
+                        .get_property(false, window, net_wm_pid, AtomEnum::CARDINAL, 0, 1)?
+                        .reply()
+                        .ok()
+                        .and_then(|reply| reply.value32().and_then(|mut v| v.next()))
+                        .map(process_names)
+                        .unwrap_or_default();
                     windows.push(WindowInfo {
                         id: window,
                         title,
                         class,
+                        processes,
                     });
                 }
             }
@@ -112,3 +135,79 @@ pub fn get_running_applications() -> Result<Vec<WindowInfo>> {
 
     Ok(windows)
 }
+
+/// A monitor of the X screen, in root window coordinates
+#[derive(Clone, Debug, PartialEq)]
+pub struct MonitorInfo {
+    pub name: String,
+    pub x: i16,
+    pub y: i16,
+    pub width: u16,
+    pub height: u16,
+    pub primary: bool,
+}
+
+/// List the active monitors via RandR, or the whole screen as one monitor if RandR 1.5
+/// is unavailable (e.g. nested servers)
+pub fn get_monitors() -> Result<Vec<MonitorInfo>> {
+    let (conn, screen_num) = x11rb::connect(None).context("Failed to connect to X11")?;
+    let screen = &conn.setup().roots[screen_num];
+
+    let randr_monitors = conn
+        .randr_query_version(1, 5)
+        .ok()
+        .and_then(|cookie| cookie.reply().ok())
+        .filter(|version| (version.major_version, version.minor_version) >= (1, 5))
+        .and_then(|_| conn.randr_get_monitors(screen.root, true).ok())
+        .and_then(|cookie| cookie.reply().ok())
+        .map(|reply| reply.monitors)
+        .unwrap_or_default();
+
+    let mut monitors = Vec::new();
+    for monitor in randr_monitors {
+        let name = conn
+            .get_atom_name(monitor.name)?
+            .reply()
+            .map(|reply| String::from_utf8_lossy(&reply.name).into_owned())
+            .unwrap_or_default();
+        monitors.push(MonitorInfo {
+            name,
+            x: monitor.x,
+            y: monitor.y,
+            width: monitor.width,
+            height: monitor.height,
+            primary: monitor.primary,
+        });
+    }
+
+    if monitors.is_empty() {
+        monitors.push(MonitorInfo {
+            name: "Screen".to_string(),
+            x: 0,
+            y: 0,
+            width: screen.width_in_pixels,
+            height: screen.height_in_pixels,
+            primary: true,
+        });
+    }
+    monitors.sort_by_key(|m| (m.x, m.y));
+    Ok(monitors)
+}
+
+/// Monitors for measuring thumbnails saved as monitor percentages (empty without an X server)
+pub fn monitor_scales() -> Vec<MonitorScale> {
+    get_monitors()
+        .map(|monitors| {
+            monitors
+                .into_iter()
+                .map(|m| MonitorScale {
+                    x: m.x,
+                    y: m.y,
+                    width: m.width,
+                    height: m.height,
+                    scale: 1.0,
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
@@ -0,0 +1,246 @@
+//! Headless X server harness for tests
+//!
+//! Starts a private Xvfb (or the server named by `EVE_PREVIEW_MANAGER_TEST_XSERVER`, e.g.
+//! `Xephyr` to watch a run) and creates fake EVE client windows carrying the same
+//! properties the Wine client sets. There is no window manager, so the harness
+//! publishes `_NET_CLIENT_LIST` itself.
+//!
+//! Tests using it skip themselves when no X server binary is installed, unless
+//! `EVE_PREVIEW_MANAGER_TEST_XSERVER_REQUIRED` is set (as in CI), which makes them fail:
+//!
+//! ```ignore
+//! let Some(server) = XServer::start() else { return };
+//! ```
+
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use x11rb::connection::Connection;
+use x11rb::protocol::xproto::{
+    AtomEnum, ConnectionExt, CreateWindowAux, PropMode, Screen, Window, WindowClass,
+};
+use x11rb::rust_connection::RustConnection;
+use x11rb::wrapper::ConnectionExt as _;
+
+use crate::common::constants::eve;
+
+/// Environment variable selecting the X server binary
+const SERVER_ENV: &str = "EVE_PREVIEW_MANAGER_TEST_XSERVER";
+
+/// Environment variable that turns a missing X server binary into a test failure
+const REQUIRED_ENV: &str = "EVE_PREVIEW_MANAGER_TEST_XSERVER_REQUIRED";
+
+/// First display number tried; high enough to stay clear of real sessions
+const FIRST_DISPLAY: u32 = 90;
+
+/// Display numbers tried before giving up (parallel tests each take one)
+const DISPLAY_ATTEMPTS: u32 = 50;
+
+/// Time the server gets to create its socket
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Screen size of the test server
+pub const SCREEN_WIDTH: u16 = 1920;
+pub const SCREEN_HEIGHT: u16 = 1080;
+
+/// A private X server, killed on drop
+pub struct XServer {
+    child: Child,
+    display: String,
+}
+
+impl XServer {
+    /// Start a server on a free display, or `None` if the binary is not installed
+    pub fn start() -> Option<Self> {
+        let binary = std::env::var(SERVER_ENV).unwrap_or_else(|_| "Xvfb".to_string());
+
+        for number in FIRST_DISPLAY..FIRST_DISPLAY + DISPLAY_ATTEMPTS {
+            if Path::new(&format!("/tmp/.X{}-lock", number)).exists() {
+                continue;
+            }
+
+            let display = format!(":{}", number);
+            let mut command = Command::new(&binary);
+            command.arg(&display);
+            if binary.contains("Xephyr") {
+                command.args(["-screen", &format!("{}x{}", SCREEN_WIDTH, SCREEN_HEIGHT)]);
+            } else {
+                command.args([
+                    "-screen",
+                    "0",
+                    &format!("{}x{}x24", SCREEN_WIDTH, SCREEN_HEIGHT),
+                ]);
+            }
+            command
+                .args(["-nolisten", "tcp", "+extension", "Composite"])
+                .stdout(Stdio::null())
+                .stderr(Stdio::null());
+
+            let child = match command.spawn() {
+                Ok(child) => child,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                    if std::env::var_os(REQUIRED_ENV).is_some() {
+                        panic!("{} not installed but {} is set", binary, REQUIRED_ENV);
+                    }
+                    eprintln!("{} not installed, skipping headless X11 test", binary);
+                    return None;
+                }
+                Err(e) => panic!("Failed to start {}: {}", binary, e),
+            };
+
+            let mut server = Self { child, display };
+            if server.wait_until_ready(number) {
+                return Some(server);
+            }
+            // Display taken by a concurrent test or stale socket; try the next one
+        }
+        panic!(
+            "No free display for {} after {} attempts",
+            binary, DISPLAY_ATTEMPTS
+        );
+    }
+
+    fn wait_until_ready(&mut self, number: u32) -> bool {
+        let socket = format!("/tmp/.X11-unix/X{}", number);
+        let deadline = Instant::now() + STARTUP_TIMEOUT;
+        while Instant::now() < deadline {
+            if let Ok(Some(_)) = self.child.try_wait() {
+                return false;
+            }
+            if Path::new(&socket).exists() && x11rb::connect(Some(&self.display)).is_ok() {
+                return true;
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        false
+    }
+
+    /// Display name of this server, e.g. `:90`
+    pub fn display(&self) -> &str {
+        &self.display
+    }
+
+    /// Open a client connection to this server
+    pub fn connect(&self) -> (RustConnection, usize) {
+        x11rb::connect(Some(&self.display)).expect("Failed to connect to test X server")
+    }
+}
+
+impl Drop for XServer {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Create a mapped window that looks like an EVE client (`EVE - <character>`, or `EVE`
+/// when `character` is `None` for the login screen)
+pub fn create_eve_client(
+    conn: &RustConnection,
+    screen: &Screen,
+    character: Option<&str>,
+    x: i16,
+    y: i16,
+) -> Result<Window> {
+    let window = conn.generate_id()?;
+    conn.create_window(
+        screen.root_depth,
+        window,
+        screen.root,
+        x,
+        y,
+        1024,
+        768,
+        0,
+        WindowClass::INPUT_OUTPUT,
+        screen.root_visual,
+        &CreateWindowAux::new().background_pixel(screen.black_pixel),
+    )
+    .context("Failed to create fake EVE window")?;
+
+    // The Wine client reports exefile.exe as both instance and class
+    conn.change_property8(
+        PropMode::REPLACE,
+        window,
+        AtomEnum::WM_CLASS,
+        AtomEnum::STRING,
+        b"exefile.exe\0exefile.exe\0",
+    )?;
+    set_title(conn, window, &eve_title(character))?;
+    conn.map_window(window)?;
+    conn.sync()
+        .context("Failed to sync after creating fake EVE window")?;
+    Ok(window)
+}
+
+/// Create a mapped non-EVE window with the given title and WM_CLASS
+pub fn create_window(
+    conn: &RustConnection,
+    screen: &Screen,
+    title: &str,
+    class: &str,
+) -> Result<Window> {
+    let window = conn.generate_id()?;
+    conn.create_window(
+        screen.root_depth,
+        window,
+        screen.root,
+        0,
+        0,
+        640,
+        480,
+        0,
+        WindowClass::INPUT_OUTPUT,
+        screen.root_visual,
+        &CreateWindowAux::new().background_pixel(screen.white_pixel),
+    )
+    .context("Failed to create fake window")?;
+    conn.change_property8(
+        PropMode::REPLACE,
+        window,
+        AtomEnum::WM_CLASS,
+        AtomEnum::STRING,
+        format!("{0}\0{0}\0", class).as_bytes(),
+    )?;
+    set_title(conn, window, title)?;
+    conn.map_window(window)?;
+    conn.sync()
+        .context("Failed to sync after creating fake window")?;
+    Ok(window)
+}
+
+/// Title of an EVE client window for `character` (login screen if `None`)
+pub fn eve_title(character: Option<&str>) -> String {
+    match character {
+        Some(name) => format!("{}{}", eve::WINDOW_TITLE_PREFIX, name),
+        None => eve::LOGGED_OUT_TITLE.to_string(),
+    }
+}
+
+/// Set WM_NAME (STRING), as the EVE client does when a character logs in or out
+pub fn set_title(conn: &RustConnection, window: Window, title: &str) -> Result<()> {
+    conn.change_property8(
+        PropMode::REPLACE,
+        window,
+        AtomEnum::WM_NAME,
+        AtomEnum::STRING,
+        title.as_bytes(),
+    )?;
+    conn.sync().context("Failed to sync after setting title")?;
+    Ok(())
+}
+
+/// Publish `_NET_CLIENT_LIST` on the root window, standing in for a window manager
+pub fn publish_client_list(conn: &RustConnection, root: Window, windows: &[Window]) -> Result<()> {
+    let atom = conn
+        .intern_atom(false, b"_NET_CLIENT_LIST")?
+        .reply()
+        .context("Failed to intern _NET_CLIENT_LIST")?
+        .atom;
+    conn.change_property32(PropMode::REPLACE, root, atom, AtomEnum::WINDOW, windows)?;
+    conn.sync()
+        .context("Failed to sync after publishing client list")?;
+    Ok(())
+}
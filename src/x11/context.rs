@@ -1,6 +1,9 @@
 //! Application context and cached X11 state
 
+use std::collections::HashMap;
+
 use anyhow::{Context, Result};
+use x11rb::connection::Connection;
 use x11rb::protocol::render::{ConnectionExt as RenderExt, Fixed, Pictformat};
 use x11rb::protocol::xproto::*;
 use x11rb::rust_connection::RustConnection;
@@ -13,6 +16,8 @@ pub struct AppContext<'a> {
     pub screen: &'a Screen,
     pub atoms: &'a CachedAtoms,
     pub formats: &'a CachedFormats,
+    /// The server speaks XInput 2.2 with us, so thumbnails can take touch events
+    pub touch: bool,
 }
 
 /// Pre-cached X11 atoms to avoid repeated roundtrips
@@ -26,6 +31,8 @@ pub struct CachedAtoms {
     pub net_wm_window_opacity: Atom,
     pub wm_class: Atom,
     pub net_active_window: Atom,
+    pub net_wm_desktop: Atom,
+    pub net_current_desktop: Atom,
     pub wm_change_state: Atom,
     pub wm_state: Atom,
     pub net_client_list: Atom,
@@ -44,6 +51,14 @@ pub struct CachedAtoms {
     pub net_wm_window_type_dnd: Atom,
     pub net_wm_name: Atom,
     pub net_wm_visible_name: Atom,
+    pub net_wm_state_sticky: Atom,
+    pub net_wm_state_skip_taskbar: Atom,
+    pub net_wm_state_skip_pager: Atom,
+    pub utf8_string: Atom,
+    pub motif_wm_hints: Atom,
+    pub clipboard: Atom,
+    pub targets: Atom,
+    pub image_png: Atom,
 }
 
 impl CachedAtoms {
@@ -97,6 +112,18 @@ impl CachedAtoms {
                 .reply()
                 .context("Failed to get reply for _NET_ACTIVE_WINDOW atom")?
                 .atom,
+            net_wm_desktop: conn
+                .intern_atom(false, b"_NET_WM_DESKTOP")
+                .context("Failed to intern _NET_WM_DESKTOP atom")?
+                .reply()
+                .context("Failed to get reply for _NET_WM_DESKTOP atom")?
+                .atom,
+            net_current_desktop: conn
+                .intern_atom(false, b"_NET_CURRENT_DESKTOP")
+                .context("Failed to intern _NET_CURRENT_DESKTOP atom")?
+                .reply()
+                .context("Failed to get reply for _NET_CURRENT_DESKTOP atom")?
+                .atom,
             wm_change_state: conn
                 .intern_atom(false, b"WM_CHANGE_STATE")
                 .context("Failed to intern WM_CHANGE_STATE atom")?
@@ -205,6 +232,54 @@ impl CachedAtoms {
                 .reply()
                 .context("Failed to get reply for _NET_WM_VISIBLE_NAME atom")?
                 .atom,
+            net_wm_state_sticky: conn
+                .intern_atom(false, b"_NET_WM_STATE_STICKY")
+                .context("Failed to intern _NET_WM_STATE_STICKY atom")?
+                .reply()
+                .context("Failed to get reply for _NET_WM_STATE_STICKY atom")?
+                .atom,
+            net_wm_state_skip_taskbar: conn
+                .intern_atom(false, b"_NET_WM_STATE_SKIP_TASKBAR")
+                .context("Failed to intern _NET_WM_STATE_SKIP_TASKBAR atom")?
+                .reply()
+                .context("Failed to get reply for _NET_WM_STATE_SKIP_TASKBAR atom")?
+                .atom,
+            net_wm_state_skip_pager: conn
+                .intern_atom(false, b"_NET_WM_STATE_SKIP_PAGER")
+                .context("Failed to intern _NET_WM_STATE_SKIP_PAGER atom")?
+                .reply()
+                .context("Failed to get reply for _NET_WM_STATE_SKIP_PAGER atom")?
+                .atom,
+            utf8_string: conn
+                .intern_atom(false, b"UTF8_STRING")
+                .context("Failed to intern UTF8_STRING atom")?
+                .reply()
+                .context("Failed to get reply for UTF8_STRING atom")?
+                .atom,
+            motif_wm_hints: conn
+                .intern_atom(false, b"_MOTIF_WM_HINTS")
+                .context("Failed to intern _MOTIF_WM_HINTS atom")?
+                .reply()
+                .context("Failed to get reply for _MOTIF_WM_HINTS atom")?
+                .atom,
+            clipboard: conn
+                .intern_atom(false, b"CLIPBOARD")
+                .context("Failed to intern CLIPBOARD atom")?
+                .reply()
+                .context("Failed to get reply for CLIPBOARD atom")?
+                .atom,
+            targets: conn
+                .intern_atom(false, b"TARGETS")
+                .context("Failed to intern TARGETS atom")?
+                .reply()
+                .context("Failed to get reply for TARGETS atom")?
+                .atom,
+            image_png: conn
+                .intern_atom(false, b"image/png")
+                .context("Failed to intern image/png atom")?
+                .reply()
+                .context("Failed to get reply for image/png atom")?
+                .atom,
         })
     }
 }
@@ -212,8 +287,15 @@ impl CachedAtoms {
 /// Pre-cached picture formats to avoid repeated expensive queries
 #[derive(Debug)]
 pub struct CachedFormats {
+    /// Format of the root visual, used for thumbnail windows
     pub rgb: Pictformat,
-    pub argb: Pictformat,
+    /// 32-bit format with alpha, used for overlays; None on servers without one
+    pub argb: Option<Pictformat>,
+    /// Visual with the `argb` format and a colormap for it, for translucent thumbnail windows
+    pub argb_visual: Option<(Visualid, Colormap)>,
+    /// Format of every visual the server can render, for matching source windows
+    visuals: HashMap<Visualid, Pictformat>,
+    root_depth: u8,
 }
 
 impl CachedFormats {
@@ -224,21 +306,100 @@ impl CachedFormats {
             .reply()
             .context("Failed to get RENDER formats reply")?;
 
-        let rgb = formats_reply
-            .formats
+        let visuals: HashMap<Visualid, Pictformat> = formats_reply
+            .screens
             .iter()
-            .find(|f| f.depth == screen.root_depth && f.direct.alpha_mask == 0)
-            .ok_or_else(|| anyhow::anyhow!("No RGB format found for depth {}", screen.root_depth))?
-            .id;
+            .flat_map(|s| s.depths.iter())
+            .flat_map(|d| d.visuals.iter())
+            .map(|v| (v.visual, v.format))
+            .collect();
 
+        // Prefer the root visual's own format; fall back to any alpha-less one of its depth
+        let available = || {
+            formats_reply
+                .formats
+                .iter()
+                .map(|f| format!("depth {} alpha {:#x}", f.depth, f.direct.alpha_mask))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let rgb = visuals
+            .get(&screen.root_visual)
+            .copied()
+            .or_else(|| {
+                formats_reply
+                    .formats
+                    .iter()
+                    .find(|f| f.depth == screen.root_depth && f.direct.alpha_mask == 0)
+                    .map(|f| f.id)
+            })
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "No RENDER format for the root visual (depth {}); the server offers: {}",
+                    screen.root_depth,
+                    available()
+                )
+            })?;
+
+        // Without one, overlays fall back to the root visual and thumbnail windows to the
+        // default visual
         let argb = formats_reply
             .formats
             .iter()
             .find(|f| f.depth == x11::ARGB_DEPTH && f.direct.alpha_mask != 0)
-            .ok_or_else(|| anyhow::anyhow!("No ARGB format found for depth {}", x11::ARGB_DEPTH))?
-            .id;
+            .map(|f| f.id);
+        if argb.is_none() {
+            tracing::warn!(
+                formats = %available(),
+                "No ARGB RENDER format for depth {}, thumbnail overlays and label text are left out",
+                x11::ARGB_DEPTH
+            );
+        }
+
+        let argb_visual = match formats_reply
+            .screens
+            .iter()
+            .flat_map(|s| s.depths.iter())
+            .filter(|d| d.depth == x11::ARGB_DEPTH)
+            .flat_map(|d| d.visuals.iter())
+            .find(|v| Some(v.format) == argb)
+        {
+            Some(visual) => {
+                let colormap = conn
+                    .generate_id()
+                    .context("Failed to generate ID for ARGB colormap")?;
+                conn.create_colormap(ColormapAlloc::NONE, colormap, screen.root, visual.visual)
+                    .context("Failed to create colormap for the ARGB visual")?;
+                Some((visual.visual, colormap))
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            rgb,
+            argb,
+            argb_visual,
+            visuals,
+            root_depth: screen.root_depth,
+        })
+    }
+
+    /// Depth and format for overlay pixmaps and images: 32-bit ARGB, or the opaque root visual
+    /// on servers without an ARGB format
+    pub fn overlay(&self) -> (u8, Pictformat) {
+        match self.argb {
+            Some(argb) => (x11::ARGB_DEPTH, argb),
+            None => (self.root_depth, self.rgb),
+        }
+    }
 
-        Ok(Self { rgb, argb })
+    /// Format for reading a window with the given visual and depth, if Render supports it
+    pub fn for_window(&self, visual: Visualid, depth: u8, root_depth: u8) -> Option<Pictformat> {
+        self.visuals.get(&visual).copied().or(match depth {
+            d if d == x11::ARGB_DEPTH => self.argb,
+            d if d == root_depth => Some(self.rgb),
+            _ => None,
+        })
     }
 }
 
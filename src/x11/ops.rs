@@ -116,6 +116,90 @@ pub fn minimize_window(
     Ok(())
 }
 
+/// Requests the window manager to move a window to another virtual desktop (`_NET_WM_DESKTOP`)
+pub fn move_window_to_desktop(
+    conn: &RustConnection,
+    screen: &Screen,
+    atoms: &CachedAtoms,
+    window: Window,
+    desktop: u32,
+) -> Result<()> {
+    let event = ClientMessageEvent {
+        response_type: CLIENT_MESSAGE_EVENT,
+        format: 32,
+        sequence: 0,
+        window,
+        type_: atoms.net_wm_desktop,
+        data: ClientMessageData::from([desktop, x11::ACTIVE_WINDOW_SOURCE_PAGER, 0, 0, 0]),
+    };
+
+    conn.send_event(
+        false,
+        screen.root,
+        EventMask::SUBSTRUCTURE_NOTIFY | EventMask::SUBSTRUCTURE_REDIRECT,
+        event,
+    )
+    .context(format!(
+        "Failed to send _NET_WM_DESKTOP event for window {}",
+        window
+    ))?;
+
+    conn.flush()
+        .context("Failed to flush X11 connection after moving window to desktop")?;
+    Ok(())
+}
+
+/// Requests the window manager to switch to another virtual desktop (`_NET_CURRENT_DESKTOP`)
+pub fn switch_desktop(
+    conn: &RustConnection,
+    screen: &Screen,
+    atoms: &CachedAtoms,
+    desktop: u32,
+    timestamp: u32,
+) -> Result<()> {
+    let event = ClientMessageEvent {
+        response_type: CLIENT_MESSAGE_EVENT,
+        format: 32,
+        sequence: 0,
+        window: screen.root,
+        type_: atoms.net_current_desktop,
+        data: ClientMessageData::from([desktop, timestamp, 0, 0, 0]),
+    };
+
+    conn.send_event(
+        false,
+        screen.root,
+        EventMask::SUBSTRUCTURE_NOTIFY | EventMask::SUBSTRUCTURE_REDIRECT,
+        event,
+    )
+    .context(format!(
+        "Failed to send _NET_CURRENT_DESKTOP event for desktop {}",
+        desktop
+    ))?;
+
+    conn.flush()
+        .context("Failed to flush X11 connection after switching desktop")?;
+    Ok(())
+}
+
+/// Raise a window to the top of the stack without focusing it, first moving its top-left
+/// corner to `position` if given
+pub fn raise_window(
+    conn: &RustConnection,
+    window: Window,
+    position: Option<crate::common::types::Position>,
+) -> Result<()> {
+    let mut aux = ConfigureWindowAux::new().stack_mode(StackMode::ABOVE);
+    if let Some(position) = position {
+        aux = aux.x(i32::from(position.x)).y(i32::from(position.y));
+    }
+    conn.configure_window(window, &aux)
+        .context(format!("Failed to raise window {}", window))?;
+    conn.flush()
+        .context("Failed to flush X11 connection after raising window")?;
+    Ok(())
+}
+
 /// Requests the window manager to restore/unminimize a window using EWMH protocols
 pub fn unminimize_window(
     conn: &RustConnection,
@@ -216,3 +300,33 @@ fn refresh_pointer_state(conn: &RustConnection, window: Window, timestamp: u32)
 
     Ok(())
 }
+
+/// Create an override-redirect window filled with `background`, which the window manager
+/// leaves alone and never focuses. `what` names the window in error messages.
+pub fn create_overlay_window(
+    conn: &RustConnection,
+    screen: &Screen,
+    background: u32,
+    what: &str,
+) -> Result<Window> {
+    let window = conn
+        .generate_id()
+        .context(format!("Failed to generate {what} window ID"))?;
+    conn.create_window(
+        screen.root_depth,
+        window,
+        screen.root,
+        0,
+        0,
+        1,
+        1,
+        0,
+        WindowClass::INPUT_OUTPUT,
+        screen.root_visual,
+        &CreateWindowAux::new()
+            .override_redirect(x11::OVERRIDE_REDIRECT)
+            .background_pixel(background),
+    )
+    .context(format!("Failed to create {what} window"))?;
+    Ok(window)
+}
@@ -3,26 +3,37 @@
 use anyhow::{Context, Result};
 use tracing::debug;
 use x11rb::connection::Connection;
-use x11rb::errors::ReplyError;
+use x11rb::errors::{ConnectionError, ReplyError, ReplyOrIdError};
 use x11rb::protocol::xproto::*;
 use x11rb::rust_connection::RustConnection;
 
 use super::CachedAtoms;
-use crate::common::constants::{eve, x11};
-use crate::common::types::EveWindowType;
+use crate::common::constants::x11;
+use crate::common::types::{DisplayScales, EveWindowType, MonitorScale, Position, scale_for_dpi};
+use crate::config::detection::EveDetection;
 
-/// Identifies if a window belongs to EVE Online by inspecting its properties and title
+/// Identifies if a window belongs to EVE Online by matching its title (WM_NAME, then the
+/// UTF-8 `_NET_WM_NAME` used by localized clients) and WM_CLASS against `detection`.
+/// Windows of a marked client class whose title matches nothing count as login screens.
 pub fn is_window_eve(
     conn: &RustConnection,
     window: Window,
     atoms: &CachedAtoms,
+    detection: &EveDetection,
 ) -> Result<Option<EveWindowType>> {
+    // Send both title queries up front to pay a single round trip
     let cookie = conn
         .get_property(false, window, atoms.wm_name, AtomEnum::STRING, 0, 1024)
         .context(format!(
             "Failed to query WM_NAME property for window {}",
             window
         ))?;
+    let net_name_cookie = conn
+        .get_property(false, window, atoms.net_wm_name, AtomEnum::ANY, 0, 1024)
+        .context(format!(
+            "Failed to query _NET_WM_NAME property for window {}",
+            window
+        ))?;
     let name_prop = match cookie.reply() {
         Ok(reply) => reply,
         Err(ReplyError::X11Error(err)) if err.error_kind == x11rb::protocol::ErrorKind::Window => {
@@ -36,21 +47,26 @@ pub fn is_window_eve(
             return Err(err).context(format!("Failed to get WM_NAME reply for window {}", window));
         }
     };
+    let net_name = net_name_cookie
+        .reply()
+        .map(|reply| String::from_utf8_lossy(&reply.value).into_owned())
+        .unwrap_or_default();
+
+    let wm_class = if detection.checks_class() {
+        get_window_class(conn, window, atoms)?
+    } else {
+        None
+    };
+
     let title = String::from_utf8_lossy(&name_prop.value).into_owned();
-    Ok(
-        if let Some(name) = title.strip_prefix(eve::WINDOW_TITLE_PREFIX) {
-            if name.to_lowercase().contains("steam_app_") {
-                debug!(window=window, name=%name, "Ignored steam_app container title");
-                None
-            } else {
-                Some(EveWindowType::LoggedIn(name.to_string()))
-            }
-        } else if title == eve::LOGGED_OUT_TITLE {
-            Some(EveWindowType::LoggedOut)
-        } else {
-            None
-        },
-    )
+    Ok(detection
+        .classify(&title, wm_class.as_deref())
+        .or_else(|| detection.classify(&net_name, wm_class.as_deref()))
+        .or_else(|| {
+            detection
+                .is_client_class(wm_class.as_deref())
+                .then_some(EveWindowType::LoggedOut)
+        }))
 }
 
 /// Get the WM_CLASS property of a window (returns the second string, which is the class name)
@@ -165,11 +181,12 @@ pub fn get_active_eve_window(
     conn: &RustConnection,
     screen: &Screen,
     atoms: &CachedAtoms,
+    detection: &EveDetection,
 ) -> Result<Option<Window>> {
     let active_window = get_active_window(conn, screen, atoms)?;
 
     if let Some(active_window) = active_window {
-        if is_window_eve(conn, active_window, atoms)
+        if is_window_eve(conn, active_window, atoms, detection)
             .context(format!(
                 "Failed to check if active window {} is EVE client",
                 active_window
@@ -215,6 +232,64 @@ pub fn get_active_window(
     }
 }
 
+/// Read a single CARDINAL property, if set
+fn get_cardinal(
+    conn: &RustConnection,
+    window: Window,
+    property: Atom,
+    name: &str,
+) -> Result<Option<u32>> {
+    let reply = conn
+        .get_property(false, window, property, AtomEnum::CARDINAL, 0, 1)
+        .context(format!("Failed to query {} property", name))?
+        .reply()
+        .context(format!("Failed to get reply for {} query", name))?;
+    Ok(reply.value32().and_then(|mut values| values.next()))
+}
+
+/// Get the virtual desktop a window is on (`_NET_WM_DESKTOP`), if the WM reports it
+pub fn get_window_desktop(
+    conn: &RustConnection,
+    window: Window,
+    atoms: &CachedAtoms,
+) -> Result<Option<u32>> {
+    get_cardinal(conn, window, atoms.net_wm_desktop, "_NET_WM_DESKTOP")
+}
+
+/// Get the current virtual desktop (`_NET_CURRENT_DESKTOP`), if the WM reports it
+pub fn get_current_desktop(
+    conn: &RustConnection,
+    screen: &Screen,
+    atoms: &CachedAtoms,
+) -> Result<Option<u32>> {
+    get_cardinal(
+        conn,
+        screen.root,
+        atoms.net_current_desktop,
+        "_NET_CURRENT_DESKTOP",
+    )
+}
+
+/// Get the `_NET_WM_PID` of a window, if set
+pub fn get_window_pid(
+    conn: &RustConnection,
+    window: Window,
+    atoms: &CachedAtoms,
+) -> Result<Option<u32>> {
+    let Ok(prop) = conn
+        .get_property(false, window, atoms.net_wm_pid, AtomEnum::CARDINAL, 0, 1)
+        .context(format!("Failed to query _NET_WM_PID for {}", window))?
+        .reply()
+    else {
+        return Ok(None);
+    };
+    Ok(prop
+        .value
+        .get(..x11::PID_PROPERTY_SIZE)
+        .and_then(|bytes| bytes.try_into().ok())
+        .map(u32::from_ne_bytes))
+}
+
 /// Check if a window is a "normal" top-level application window
 /// (Filters out docks, popups, desktop, etc. based on _NET_WM_WINDOW_TYPE)
 pub fn is_normal_window(
@@ -290,12 +365,39 @@ pub fn is_normal_window(
     }
 }
 
+/// Walk the window tree below `root` up to `depth` levels, parents before children
+pub fn get_window_tree(conn: &RustConnection, root: Window, depth: usize) -> Result<Vec<Window>> {
+    let mut windows = Vec::new();
+    let mut level = vec![root];
+    for _ in 0..depth {
+        let mut next = Vec::new();
+        for parent in level {
+            // Windows can disappear mid-walk; skip them rather than failing the scan
+            let Ok(reply) = conn
+                .query_tree(parent)
+                .context("Failed to send QueryTree request")?
+                .reply()
+            else {
+                continue;
+            };
+            next.extend(reply.children);
+        }
+        windows.extend_from_slice(&next);
+        level = next;
+    }
+    Ok(windows)
+}
+
 /// Get the list of client windows from _NET_CLIENT_LIST property on root window
-pub fn get_client_list(conn: &RustConnection, atoms: &CachedAtoms) -> Result<Vec<Window>> {
+pub fn get_client_list(
+    conn: &RustConnection,
+    root: Window,
+    atoms: &CachedAtoms,
+) -> Result<Vec<Window>> {
     let prop = conn
         .get_property(
             false,
-            conn.setup().roots[0].root,
+            root,
             atoms.net_client_list,
             AtomEnum::WINDOW,
             0,
@@ -312,3 +414,267 @@ pub fn get_client_list(conn: &RustConnection, atoms: &CachedAtoms) -> Result<Vec
 
     Ok(windows)
 }
+
+/// Whether an X display name (`host:display.screen`) selects a screen explicitly
+pub fn display_names_screen(display: &str) -> bool {
+    display
+        .rsplit_once(':')
+        .is_some_and(|(_, number)| number.contains('.'))
+}
+
+/// First screen of a multi-screen display that has an EVE client, if any
+pub fn find_eve_screen(
+    conn: &RustConnection,
+    atoms: &CachedAtoms,
+    detection: &EveDetection,
+) -> Option<usize> {
+    conn.setup().roots.iter().position(|screen| {
+        get_window_tree(
+            conn,
+            screen.root,
+            crate::common::constants::scan::TREE_DEPTH,
+        )
+        .unwrap_or_default()
+        .into_iter()
+        .any(|window| matches!(is_window_eve(conn, window, atoms, detection), Ok(Some(_))))
+    })
+}
+
+/// Whether a compositing manager owns `_NET_WM_CM_S<n>` for the screen of `root`
+pub fn compositor_running(conn: &RustConnection, root: Window) -> bool {
+    let Some(screen_num) = conn.setup().roots.iter().position(|s| s.root == root) else {
+        return false;
+    };
+    conn.intern_atom(false, format!("_NET_WM_CM_S{}", screen_num).as_bytes())
+        .ok()
+        .and_then(|cookie| cookie.reply().ok())
+        .and_then(|reply| conn.get_selection_owner(reply.atom).ok())
+        .and_then(|cookie| cookie.reply().ok())
+        .is_some_and(|reply| reply.owner != x11rb::NONE)
+}
+
+/// Highest refresh rate of the active CRTCs in Hz, if RandR reports one
+pub fn get_refresh_rate(conn: &RustConnection, root: Window) -> Option<f64> {
+    use x11rb::protocol::randr::ConnectionExt as _;
+
+    let resources = conn
+        .randr_get_screen_resources_current(root)
+        .ok()?
+        .reply()
+        .ok()?;
+    resources
+        .crtcs
+        .iter()
+        .filter_map(|&crtc| {
+            conn.randr_get_crtc_info(crtc, resources.config_timestamp)
+                .ok()?
+                .reply()
+                .ok()
+        })
+        .filter(|info| info.mode != 0)
+        .filter_map(|info| resources.modes.iter().find(|mode| mode.id == info.mode))
+        .filter_map(mode_refresh_rate)
+        .reduce(f64::max)
+}
+
+/// Whether `error`, or anything it wraps, means the connection to the X server is gone
+/// rather than that the server refused a request
+pub fn is_connection_lost(error: &anyhow::Error) -> bool {
+    fn lost(error: &ConnectionError) -> bool {
+        matches!(
+            error,
+            ConnectionError::IoError(_) | ConnectionError::UnknownError
+        )
+    }
+    error.chain().any(|cause| {
+        cause.downcast_ref::<ConnectionError>().is_some_and(lost)
+            || matches!(
+                cause.downcast_ref::<ReplyError>(),
+                Some(ReplyError::ConnectionError(e)) if lost(e)
+            )
+            || matches!(
+                cause.downcast_ref::<ReplyOrIdError>(),
+                Some(ReplyOrIdError::ConnectionError(e)) if lost(e)
+            )
+    })
+}
+
+/// Position of `window`'s top-left corner in root window coordinates
+pub fn get_root_position(conn: &RustConnection, window: Window, root: Window) -> Option<Position> {
+    let reply = conn
+        .translate_coordinates(window, root, 0, 0)
+        .ok()?
+        .reply()
+        .ok()?;
+    Some(Position::new(reply.dst_x, reply.dst_y))
+}
+
+/// Position of `window`'s center in root window coordinates
+pub fn get_window_center(conn: &RustConnection, window: Window, root: Window) -> Option<Position> {
+    let geometry = conn.get_geometry(window).ok()?.reply().ok()?;
+    let reply = conn
+        .translate_coordinates(
+            window,
+            root,
+            (geometry.width / 2) as i16,
+            (geometry.height / 2) as i16,
+        )
+        .ok()?
+        .reply()
+        .ok()?;
+    Some(Position::new(reply.dst_x, reply.dst_y))
+}
+
+/// `Xft.dpi` from the root window's X resources (RESOURCE_MANAGER), if set
+pub fn get_xft_dpi(conn: &RustConnection, root: Window) -> Option<f32> {
+    let reply = conn
+        .get_property(
+            false,
+            root,
+            AtomEnum::RESOURCE_MANAGER,
+            AtomEnum::STRING,
+            0,
+            u32::MAX / 4,
+        )
+        .ok()?
+        .reply()
+        .ok()?;
+    parse_xft_dpi(&String::from_utf8_lossy(&reply.value))
+}
+
+/// Value of the `Xft.dpi` line in X resources
+fn parse_xft_dpi(resources: &str) -> Option<f32> {
+    resources.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        (key.trim() == "Xft.dpi")
+            .then(|| value.trim().parse::<f32>().ok())
+            .flatten()
+            .filter(|dpi| *dpi > 0.0)
+    })
+}
+
+/// DPI of a monitor from its physical width, or None when the size is implausible
+/// (projectors and many virtual outputs report 0 or an aspect ratio in centimetres)
+fn monitor_dpi(width: u16, width_mm: u32) -> Option<f32> {
+    (width_mm >= 100).then(|| f32::from(width) * 25.4 / width_mm as f32)
+}
+
+/// Scale factor of every RandR monitor, from its physical DPI
+///
+/// Monitors without a plausible physical size use `Xft.dpi`, and 1x if that isn't set either.
+pub fn get_display_scales(conn: &RustConnection, root: Window) -> DisplayScales {
+    use x11rb::protocol::randr::ConnectionExt as _;
+
+    let fallback = get_xft_dpi(conn, root).map_or(1.0, scale_for_dpi);
+    let monitors = conn
+        .randr_get_monitors(root, true)
+        .ok()
+        .and_then(|cookie| cookie.reply().ok())
+        .map(|reply| reply.monitors)
+        .unwrap_or_default();
+
+    let scale_of = |m: &x11rb::protocol::randr::MonitorInfo| {
+        monitor_dpi(m.width, m.width_in_millimeters).map_or(fallback, scale_for_dpi)
+    };
+    let primary = monitors
+        .iter()
+        .find(|m| m.primary)
+        .or(monitors.first())
+        .map_or(fallback, scale_of);
+
+    DisplayScales {
+        monitors: monitors
+            .iter()
+            .map(|m| MonitorScale {
+                x: m.x,
+                y: m.y,
+                width: m.width,
+                height: m.height,
+                scale: scale_of(m),
+            })
+            .collect(),
+        primary,
+    }
+}
+
+/// Refresh rate of a RandR mode in Hz
+fn mode_refresh_rate(mode: &x11rb::protocol::randr::ModeInfo) -> Option<f64> {
+    use x11rb::protocol::randr::ModeFlag;
+
+    let mut vtotal = f64::from(mode.vtotal);
+    if mode.mode_flags.contains(ModeFlag::DOUBLE_SCAN) {
+        vtotal *= 2.0;
+    }
+    if mode.mode_flags.contains(ModeFlag::INTERLACE) {
+        vtotal /= 2.0;
+    }
+    let total = f64::from(mode.htotal) * vtotal;
+    (total > 0.0 && mode.dot_clock > 0).then(|| f64::from(mode.dot_clock) / total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use x11rb::protocol::randr::{ModeFlag, ModeInfo};
+
+    #[test]
+    fn test_display_names_screen() {
+        assert!(display_names_screen(":0.1"));
+        assert!(display_names_screen("localhost:10.0"));
+        assert!(!display_names_screen(":1"));
+        assert!(!display_names_screen("[::1]:0"));
+        assert!(!display_names_screen(""));
+    }
+
+    #[test]
+    fn test_parse_xft_dpi() {
+        let resources = "Xcursor.size:\t24\nXft.dpi:\t192\nXft.antialias:\t1\n";
+        assert_eq!(parse_xft_dpi(resources), Some(192.0));
+        assert_eq!(parse_xft_dpi("Xft.dpi: 0\n"), None);
+        assert_eq!(parse_xft_dpi("Xft.hinting:\t1\n"), None);
+    }
+
+    #[test]
+    fn test_monitor_dpi() {
+        // 27" 4K panel, 597mm wide
+        assert!((monitor_dpi(3840, 597).unwrap() - 163.4).abs() < 0.1);
+        assert_eq!(monitor_dpi(1920, 16), None);
+        assert_eq!(monitor_dpi(1920, 0), None);
+    }
+
+    #[test]
+    fn test_mode_refresh_rate() {
+        // 1920x1080@60 (CEA-861)
+        let mode = ModeInfo {
+            dot_clock: 148_500_000,
+            htotal: 2200,
+            vtotal: 1125,
+            ..Default::default()
+        };
+        let rate = mode_refresh_rate(&mode).unwrap();
+        assert!((rate - 60.0).abs() < 0.01);
+
+        let interlaced = ModeInfo {
+            mode_flags: ModeFlag::INTERLACE,
+            ..mode
+        };
+        assert!((mode_refresh_rate(&interlaced).unwrap() - 120.0).abs() < 0.01);
+
+        assert_eq!(mode_refresh_rate(&ModeInfo::default()), None);
+    }
+
+    #[test]
+    fn test_is_connection_lost() {
+        let io = || std::io::Error::from(std::io::ErrorKind::BrokenPipe);
+        let lost = anyhow::Error::from(ReplyError::ConnectionError(ConnectionError::IoError(io())))
+            .context("Failed to query geometry");
+        assert!(is_connection_lost(&lost));
+        assert!(is_connection_lost(&anyhow::Error::from(
+            ConnectionError::IoError(io())
+        )));
+
+        let refused = anyhow::Error::from(ConnectionError::UnsupportedExtension);
+        assert!(!is_connection_lost(&refused));
+        assert!(!is_connection_lost(&anyhow::anyhow!("No such window")));
+    }
+}